@@ -1,10 +1,20 @@
-use a2a_agents::reimbursement_agent::{AuthConfig, ReimbursementServer, ServerConfig};
+use a2a_agents::reimbursement_agent::{
+    AuthConfig, DebugConfig, Money, PromptFields, PromptTemplates, ReimbursementServer,
+    ServerConfig, StatusPage, StatusTracker, StorageConfig, annotated_example,
+    prompts::EXPENSE_SUBMISSION, server_config_schema, status_routes,
+};
 use a2a_client::{
-    WebA2AClient,
-    components::{MessageView, TaskView, create_sse_stream},
+    WarmUpError, WebA2AClient,
+    components::{
+        MessageView, TaskView, UploadConfig, UploadStore, task_update_stream, upload_routes,
+    },
 };
 use a2a_rs::{
-    domain::{ListTasksParams, TaskState, TaskStatusUpdateEvent},
+    domain::{
+        A2AError, ErrorDetail, ListTasksParams, TaskField, TaskState, WebhookEvent,
+        error_catalog::codes,
+    },
+    observability::TaskLogHub,
     services::AsyncA2AClient,
 };
 use askama::Template;
@@ -15,12 +25,13 @@ use axum::{
     response::Response as AxumResponse,
     routing::{get, post},
 };
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serde::Deserialize;
 use std::{net::SocketAddr, sync::Arc};
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 use tracing::{error, info, warn};
+use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
 /// Command-line arguments for the unified A2A Reimbursement Demo
@@ -64,12 +75,46 @@ struct Args {
     /// Frontend WebSocket usage (true to use WebSocket for subscriptions)
     #[clap(long, default_value = "false")]
     frontend_use_websocket: bool,
+
+    /// Run a utility command instead of the servers
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Inspect the agent configuration format
+    Config {
+        #[clap(subcommand)]
+        action: ConfigCommand,
+    },
+    /// Apply pending database migrations to the configured SQLx storage and exit
+    #[cfg(feature = "sqlx")]
+    Migrate,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print the JSON Schema of the agent configuration file
+    Schema,
+    /// Print a fully populated, commented configuration file
+    Example,
 }
 
 // Frontend AppState
 struct AppState {
     client: Arc<WebA2AClient>,
     webhook_token: String,
+    uploads: UploadStore,
+    status: StatusTracker,
+    prompts: PromptTemplates,
+}
+
+/// Settings for the optional `/status` dashboard
+struct StatusSettings {
+    debug: DebugConfig,
+    /// Shown on the dashboard; never includes credentials
+    storage_backend: String,
 }
 
 // Template structs
@@ -108,6 +153,11 @@ struct NewChatForm {
     agent_url: String,
 }
 
+#[derive(Deserialize)]
+struct CancelForm {
+    reason: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct TasksQuery {
     state: Option<String>,
@@ -134,14 +184,33 @@ struct ExpenseSubmitForm {
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
 
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
-
     // Parse command-line arguments
     let args = Args::parse();
 
+    // Utility commands print to stdout and exit before any logging starts
+    match &args.command {
+        Some(Command::Config { action }) => {
+            match action {
+                ConfigCommand::Schema => {
+                    println!("{}", serde_json::to_string_pretty(&server_config_schema())?)
+                }
+                ConfigCommand::Example => print!("{}", annotated_example()),
+            }
+            return Ok(());
+        }
+        #[cfg(feature = "sqlx")]
+        Some(Command::Migrate) => return run_migrations(&args).await,
+        None => {}
+    }
+
+    // Initialize logging, capturing per-task lines for the debug log stream
+    let task_logs = TaskLogHub::new();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .with(tracing_subscriber::fmt::layer())
+        .with(task_logs.layer())
+        .init();
+
     println!("🚀 A2A Reimbursement Agent Demo");
     println!("===============================================");
     println!();
@@ -157,23 +226,23 @@ async fn main() -> anyhow::Result<()> {
 
     // Start based on mode
     match args.mode.as_str() {
-        "agent" => start_agent_only(args).await?,
+        "agent" => start_agent_only(args, task_logs).await?,
         "frontend" => start_frontend_only(args).await?,
-        "all" => start_all(args).await?,
+        "all" => start_all(args, task_logs).await?,
         _ => unreachable!(),
     }
 
     Ok(())
 }
 
-async fn start_agent_only(args: Args) -> anyhow::Result<()> {
+async fn start_agent_only(args: Args, task_logs: TaskLogHub) -> anyhow::Result<()> {
     println!("🤖 Starting Agent Backend Only");
     println!("───────────────────────────────");
 
     let config = load_agent_config(&args)?;
     print_agent_info(&config, &args);
 
-    let server = ReimbursementServer::from_config(config);
+    let server = ReimbursementServer::from_config(config).with_task_log_hub(task_logs);
 
     match args.transport.as_str() {
         "http" => {
@@ -223,6 +292,7 @@ async fn start_frontend_only(args: Args) -> anyhow::Result<()> {
         token
     });
 
+    // The agent's storage is not visible from a separate frontend
     start_frontend_server(
         &args.host,
         args.frontend_port,
@@ -230,13 +300,17 @@ async fn start_frontend_only(args: Args) -> anyhow::Result<()> {
         ws_url,
         args.frontend_use_websocket,
         webhook_token,
+        StatusSettings {
+            debug: DebugConfig::from_env(),
+            storage_backend: "Unknown (remote agent)".to_string(),
+        },
     )
     .await?;
 
     Ok(())
 }
 
-async fn start_all(args: Args) -> anyhow::Result<()> {
+async fn start_all(args: Args, task_logs: TaskLogHub) -> anyhow::Result<()> {
     println!("🔄 Starting Full Stack (Agent + Frontend)");
     println!("──────────────────────────────────────────");
 
@@ -269,8 +343,11 @@ async fn start_all(args: Args) -> anyhow::Result<()> {
     let http_url = format!("http://{}:{}", args.host, args.agent_http_port);
     let ws_url = format!("ws://{}:{}", args.host, args.agent_ws_port);
 
+    let debug = config.debug.clone();
+    let storage_backend = storage_description(&config.storage);
+
     // Start both servers concurrently
-    let agent_server = ReimbursementServer::from_config(config);
+    let agent_server = ReimbursementServer::from_config(config).with_task_log_hub(task_logs);
 
     let transport = args.transport.clone();
     let host = args.host.clone();
@@ -299,6 +376,10 @@ async fn start_all(args: Args) -> anyhow::Result<()> {
             ws_url,
             frontend_use_websocket,
             webhook_token,
+            StatusSettings {
+                debug,
+                storage_backend,
+            },
         )
         .await
     };
@@ -313,6 +394,26 @@ async fn start_all(args: Args) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Apply pending migrations, as the agent would on startup
+#[cfg(feature = "sqlx")]
+async fn run_migrations(args: &Args) -> anyhow::Result<()> {
+    let config = load_agent_config(args)?;
+    let applied = ReimbursementServer::from_config(config)
+        .migrate()
+        .await
+        .map_err(|e| anyhow::anyhow!("Migration failed: {}", e))?;
+    if applied.is_empty() {
+        println!("✅ Database schema is up to date");
+    }
+    for migration in applied {
+        println!(
+            "✅ Applied {} migration {:03} ({})",
+            migration.scope, migration.version, migration.name
+        );
+    }
+    Ok(())
+}
+
 fn load_agent_config(args: &Args) -> anyhow::Result<ServerConfig> {
     let mut config = if let Some(config_path) = &args.config {
         println!("📄 Loading agent config from: {}", config_path);
@@ -334,10 +435,21 @@ fn load_agent_config(args: &Args) -> anyhow::Result<ServerConfig> {
     Ok(config)
 }
 
+/// Name the storage backend without exposing connection credentials
+fn storage_description(storage: &StorageConfig) -> String {
+    match storage {
+        StorageConfig::InMemory => "In-memory".to_string(),
+        StorageConfig::Sqlx { url, .. } => {
+            let scheme = url.split(':').next().unwrap_or("unknown");
+            format!("SQLx ({})", scheme)
+        }
+    }
+}
+
 fn print_agent_info(config: &ServerConfig, args: &Args) {
     println!("   📍 Host: {}", config.host);
-    println!("   🔌 HTTP Port: {}", config.http_port);
-    println!("   📡 WebSocket Port: {}", config.ws_port);
+    println!("   🔌 HTTP: {}", config.http_bind_address());
+    println!("   📡 WebSocket: {}", config.ws_bind_address());
     println!("   ⚙️  Transport: {}", args.transport);
 
     match &config.storage {
@@ -353,7 +465,7 @@ fn print_agent_info(config: &ServerConfig, args: &Args) {
         AuthConfig::None => {
             println!("   🔓 Authentication: None (public access)");
         }
-        AuthConfig::BearerToken { tokens, format } => {
+        AuthConfig::BearerToken { tokens, format, .. } => {
             println!(
                 "   🔐 Authentication: Bearer token ({} token(s){})",
                 tokens.len(),
@@ -367,6 +479,7 @@ fn print_agent_info(config: &ServerConfig, args: &Args) {
             keys,
             location,
             name,
+            ..
         } => {
             println!(
                 "   🔐 Authentication: API key ({} {} '{}', {} key(s))",
@@ -386,19 +499,73 @@ async fn start_frontend_server(
     ws_url: String,
     use_websocket: bool,
     webhook_token: String,
+    status: StatusSettings,
 ) -> anyhow::Result<()> {
-    let client = if use_websocket {
+    let mut client = if let Ok(card_url) = std::env::var("AGENT_CARD_URL") {
+        // The agent card decides transports and auth instead of the URL settings
+        info!("Configuring client from agent card at {}", card_url);
+        let mut builder = WebA2AClient::agent_card_builder(card_url);
+        if let Ok(token) = std::env::var("AGENT_AUTH_TOKEN") {
+            builder = builder.credential(token);
+        }
+        builder.connect().await?
+    } else if use_websocket {
         info!("Using WebSocket client for subscriptions at {}", ws_url);
         info!("Using HTTP client for API calls at {}", http_url);
-        WebA2AClient::new_with_websocket(http_url, ws_url)
+        match std::env::var("AGENT_AUTH_TOKEN") {
+            Ok(token) => WebA2AClient::new_with_websocket_auth(http_url, ws_url, token),
+            Err(_) => WebA2AClient::new_with_websocket(http_url, ws_url),
+        }
     } else {
         info!("Using HTTP client at {}", http_url);
         WebA2AClient::new_http(http_url)
     };
 
+    // Connect and learn the agent's capabilities before the first page load.
+    // A rejected credential will not fix itself, but the agent may still be
+    // starting up.
+    match client.warm_up().await {
+        Ok(card) => info!("Connected to agent {}", card.name),
+        Err(e @ WarmUpError::AuthRejected { .. }) => return Err(e.into()),
+        Err(e) => warn!("Agent warm-up failed, assuming full support: {}", e),
+    }
+
+    let client = Arc::new(client);
+    let tracker = StatusTracker::new();
+    let StatusSettings {
+        debug,
+        storage_backend,
+    } = status;
+    let status_page = if debug.status_page && debug.admin_tokens.is_empty() {
+        warn!("Status page is enabled but no admin tokens are configured; not serving it");
+        None
+    } else if debug.status_page {
+        info!("Serving the status dashboard at /status");
+        Some(
+            StatusPage::new(client.clone(), tracker.clone(), debug.admin_tokens)
+                .with_storage_backend(storage_backend),
+        )
+    } else {
+        None
+    };
+
+    // Checked now so a broken template fails startup, not a submission
+    let prompts = match std::env::var("PROMPT_TEMPLATES") {
+        Ok(path) => {
+            info!("Loading prompt templates from {}", path);
+            PromptTemplates::load(&path)
+                .map_err(|e| anyhow::anyhow!("Invalid prompt templates in {}: {}", path, e))?
+        }
+        Err(_) => PromptTemplates::default(),
+    };
+
+    let uploads = UploadStore::new(UploadConfig::default());
     let state = AppState {
-        client: Arc::new(client),
+        client,
         webhook_token,
+        uploads: uploads.clone(),
+        status: tracker,
+        prompts,
     };
 
     let app = Router::new()
@@ -412,6 +579,8 @@ async fn start_frontend_server(
         .route("/chat/:task_id/cancel", post(cancel_task))
         .route("/chat/:task_id/stream", get(stream_task))
         .route("/webhook/push-notification", post(handle_push_notification))
+        .merge(upload_routes(uploads))
+        .merge(status_page.map(status_routes).unwrap_or_default())
         .nest_service("/static", ServeDir::new("static"))
         .layer(CorsLayer::permissive())
         .with_state(Arc::new(state));
@@ -450,35 +619,30 @@ async fn submit_expense(
     State(state): State<Arc<AppState>>,
     Form(form): Form<ExpenseSubmitForm>,
 ) -> Result<AxumResponse, AppError> {
-    use a2a_rs::domain::{Message, Part, Role};
+    use a2a_rs::domain::{Message, Role};
 
+    let amount = validate_expense_form(&form).map_err(AppError::User)?;
     let task_id = Uuid::new_v4().to_string();
 
-    let expense_details = format!(
-        "I need to submit an expense reimbursement:\n\n\
-        Category: {}\n\
-        Amount: ${}\n\
-        Date: {}\n\
-        {}\
-        Description: {}\n\
-        {}",
-        form.category,
-        form.amount,
-        form.date,
-        form.vendor
-            .as_ref()
-            .map(|v| format!("Vendor: {}\n", v))
-            .unwrap_or_default(),
-        form.description,
-        form.project_code
-            .as_ref()
-            .map(|p| format!("Project/Cost Center: {}\n", p))
-            .unwrap_or_default()
-    );
+    let fields = PromptFields::from([
+        ("category".to_string(), form.category.clone()),
+        ("amount".to_string(), amount.to_formatted_string()),
+        ("date".to_string(), form.date.clone()),
+        ("vendor".to_string(), form.vendor.clone().unwrap_or_default()),
+        ("description".to_string(), form.description.clone()),
+        (
+            "project_code".to_string(),
+            form.project_code.clone().unwrap_or_default(),
+        ),
+    ]);
+    let parts = state
+        .prompts
+        .render(EXPENSE_SUBMISSION, &fields)
+        .expect("the expense template is built in");
 
     let message = Message {
         role: Role::User,
-        parts: vec![Part::text(expense_details)],
+        parts,
         metadata: None,
         reference_task_ids: None,
         message_id: Uuid::new_v4().to_string(),
@@ -490,13 +654,12 @@ async fn submit_expense(
 
     let response = state
         .client
-        .http
         .send_task_message(&task_id, &message, None, Some(50))
         .await
-        .map_err(|e| AppError(anyhow::anyhow!("Failed to submit expense: {}", e)))?;
+        .map_err(|e| AppError::from_a2a("Failed to submit expense", e))?;
 
     info!(
-        "Expense submitted for task {}, response state: {:?}",
+        "Expense submitted for task {}, response state: {}",
         task_id, response.status.state
     );
 
@@ -512,17 +675,19 @@ async fn submit_expense(
         },
     };
 
-    match state
-        .client
-        .http
-        .set_task_push_notification(&push_config)
-        .await
-    {
+    match state.client.set_task_push_notification(&push_config).await {
         Ok(_) => info!(
             "Push notification registered for expense task {} with authentication",
             task_id
         ),
-        Err(e) => warn!("Failed to register push notification: {}", e),
+        Err(A2AError::PushNotificationNotSupported) => info!(
+            "Agent does not support push notifications, skipping webhook for task {}",
+            task_id
+        ),
+        Err(e) => {
+            warn!("Failed to register push notification: {}", e);
+            state.status.record_registration_failure(&e);
+        }
     }
 
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
@@ -545,7 +710,7 @@ async fn tasks_page(
     let mut params = ListTasksParams::default();
 
     if let Some(state_str) = &query.state {
-        if let Ok(state) = serde_json::from_str::<TaskState>(&format!("\"{}\"", state_str)) {
+        if let Ok(state) = state_str.parse::<TaskState>() {
             params.status = Some(state);
         }
     }
@@ -554,10 +719,9 @@ async fn tasks_page(
 
     let result = state
         .client
-        .http
         .list_tasks(&params)
         .await
-        .map_err(|e| AppError(anyhow::anyhow!("Failed to list tasks: {}", e)))?;
+        .map_err(|e| AppError::from_a2a("Failed to list tasks", e))?;
 
     let tasks: Vec<TaskView> = result.tasks.into_iter().map(TaskView::from_task).collect();
 
@@ -574,44 +738,35 @@ async fn chat_page(
     State(state): State<Arc<AppState>>,
     Path(task_id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let mut retry_count = 0;
-    let max_retries = 3;
-
-    let (messages, task_state) = loop {
-        match state.client.http.get_task(&task_id, Some(50)).await {
-            Ok(task) => {
-                info!(
-                    "Retrieved task {} with {} history items",
-                    task_id,
-                    task.history.as_ref().map(|h| h.len()).unwrap_or(0)
-                );
-
-                let state = Some(format!("{:?}", task.status.state));
-                let messages = task
-                    .history
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(MessageView::from_message_with_json_parsing)
-                    .collect();
-                break (messages, state);
-            }
-            Err(e) => {
-                retry_count += 1;
-                if retry_count >= max_retries {
-                    warn!(
-                        "Failed to get task {} after {} retries: {}",
-                        task_id, max_retries, e
-                    );
-                    break (vec![], None);
-                }
-                info!(
-                    "Task {} not found, retrying ({}/{})",
-                    task_id, retry_count, max_retries
-                );
-                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-            }
-        }
-    };
+    // Opening the task creates it on first visit, so a chat started with
+    // `new_chat` never races the agent into a "task not found". The page only
+    // shows message text, so leave out what would only weigh it down.
+    let opened = state
+        .client
+        .http
+        .get_or_create_task_excluding(
+            &task_id,
+            None,
+            None,
+            &[TaskField::FileBytes, TaskField::Artifacts, TaskField::Metadata],
+        )
+        .await
+        .map_err(|e| AppError::from_a2a("Failed to open chat", e))?;
+    let task = opened.task;
+    info!(
+        "Opened task {} ({}) with {} history items",
+        task_id,
+        if opened.created { "new" } else { "existing" },
+        task.history.as_ref().map(|h| h.len()).unwrap_or(0)
+    );
+
+    let task_state = Some(task.status.state.to_string());
+    let messages = task
+        .history
+        .unwrap_or_default()
+        .into_iter()
+        .map(MessageView::from_message_with_json_parsing)
+        .collect();
 
     let template = ChatTemplate {
         task_id,
@@ -634,30 +789,27 @@ async fn send_message(
     while let Some(field) = multipart
         .next_field()
         .await
-        .map_err(|e| AppError(anyhow::anyhow!("Failed to read multipart field: {}", e)))?
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read multipart field: {}", e)))?
     {
         let name = field.name().unwrap_or("").to_string();
 
         match name.as_str() {
             "task_id" => {
-                task_id = field
-                    .text()
-                    .await
-                    .map_err(|e| AppError(anyhow::anyhow!("Failed to read task_id: {}", e)))?;
+                task_id = field.text().await.map_err(|e| {
+                    AppError::Internal(anyhow::anyhow!("Failed to read task_id: {}", e))
+                })?;
             }
             "message" => {
-                message_text = field
-                    .text()
-                    .await
-                    .map_err(|e| AppError(anyhow::anyhow!("Failed to read message: {}", e)))?;
+                message_text = field.text().await.map_err(|e| {
+                    AppError::Internal(anyhow::anyhow!("Failed to read message: {}", e))
+                })?;
             }
             "receipt" => {
                 let file_name = field.file_name().map(|s| s.to_string());
                 let content_type = field.content_type().map(|s| s.to_string());
-                let data = field
-                    .bytes()
-                    .await
-                    .map_err(|e| AppError(anyhow::anyhow!("Failed to read file: {}", e)))?;
+                let data = field.bytes().await.map_err(|e| {
+                    AppError::Internal(anyhow::anyhow!("Failed to read file: {}", e))
+                })?;
 
                 if !data.is_empty() {
                     info!(
@@ -682,6 +834,24 @@ async fn send_message(
                     parts.push(file_part);
                 }
             }
+            "upload_id" => {
+                // A receipt sent earlier through the resumable upload routes
+                let upload_id = field.text().await.map_err(|e| {
+                    AppError::Internal(anyhow::anyhow!("Failed to read upload_id: {}", e))
+                })?;
+                if !upload_id.is_empty() {
+                    let file = state.uploads.finalize(&upload_id).map_err(|e| {
+                        AppError::User(
+                            ErrorDetail::field(codes::VALIDATION_INVALID, "upload_id")
+                                .with_param("reason", e.to_string()),
+                        )
+                    })?;
+                    parts.push(Part::File {
+                        file,
+                        metadata: None,
+                    });
+                }
+            }
             _ => {
                 warn!("Unknown form field: {}", name);
             }
@@ -693,11 +863,17 @@ async fn send_message(
     }
 
     if task_id.is_empty() {
-        return Err(AppError(anyhow::anyhow!("Missing task_id")));
+        return Err(AppError::User(ErrorDetail::field(
+            codes::VALIDATION_REQUIRED,
+            "task_id",
+        )));
     }
 
     if parts.is_empty() {
-        return Err(AppError(anyhow::anyhow!("Message cannot be empty")));
+        return Err(AppError::User(ErrorDetail::field(
+            codes::VALIDATION_REQUIRED,
+            "message",
+        )));
     }
 
     let message = Message {
@@ -714,10 +890,9 @@ async fn send_message(
 
     let response = state
         .client
-        .http
         .send_task_message(&task_id, &message, None, Some(50))
         .await
-        .map_err(|e| AppError(anyhow::anyhow!("Failed to send message: {}", e)))?;
+        .map_err(|e| AppError::from_a2a("Failed to send message", e))?;
 
     info!(
         "Message sent successfully for task {}, response has {} history items",
@@ -737,20 +912,22 @@ async fn send_message(
         },
     };
 
-    match state
-        .client
-        .http
-        .set_task_push_notification(&push_config)
-        .await
-    {
+    match state.client.set_task_push_notification(&push_config).await {
         Ok(_) => info!(
             "Push notification registered for task {} with authentication",
             task_id
         ),
-        Err(e) => warn!(
-            "Failed to register push notification for task {}: {}",
-            task_id, e
+        Err(A2AError::PushNotificationNotSupported) => info!(
+            "Agent does not support push notifications, skipping webhook for task {}",
+            task_id
         ),
+        Err(e) => {
+            warn!(
+                "Failed to register push notification for task {}: {}",
+                task_id, e
+            );
+            state.status.record_registration_failure(&e);
+        }
     }
 
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
@@ -761,13 +938,14 @@ async fn send_message(
 async fn cancel_task(
     State(state): State<Arc<AppState>>,
     Path(task_id): Path<String>,
+    Form(form): Form<CancelForm>,
 ) -> Result<AxumResponse, AppError> {
-    state
-        .client
-        .http
-        .cancel_task(&task_id)
-        .await
-        .map_err(|e| AppError(anyhow::anyhow!("Failed to cancel task: {}", e)))?;
+    let client = &state.client.http;
+    let result = match form.reason.as_deref().map(str::trim) {
+        Some(reason) if !reason.is_empty() => client.cancel_task_with_reason(&task_id, reason).await,
+        _ => client.cancel_task(&task_id).await,
+    };
+    result.map_err(|e| AppError::from_a2a("Failed to cancel task", e))?;
 
     Ok(axum::response::Redirect::to(&format!("/chat/{}", task_id)).into_response())
 }
@@ -778,13 +956,16 @@ async fn stream_task(
 ) -> axum::response::sse::Sse<
     impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>,
 > {
-    create_sse_stream(state.client.clone(), task_id)
+    let events = state
+        .status
+        .track_subscription(task_update_stream(state.client.clone(), task_id));
+    axum::response::sse::Sse::new(events).keep_alive(axum::response::sse::KeepAlive::default())
 }
 
 async fn handle_push_notification(
     State(state): State<Arc<AppState>>,
     headers: axum::http::HeaderMap,
-    axum::Json(event): axum::Json<TaskStatusUpdateEvent>,
+    axum::Json(event): axum::Json<WebhookEvent>,
 ) -> Result<AxumResponse, AppError> {
     let auth_header = headers
         .get(axum::http::header::AUTHORIZATION)
@@ -801,34 +982,103 @@ async fn handle_push_notification(
     if !authenticated {
         warn!(
             "Unauthorized push notification attempt for task {}",
-            event.task_id
+            event.task_id()
         );
-        return Err(AppError(anyhow::anyhow!("Unauthorized")));
+        state.status.record_push_rejected(event.task_id());
+        return Err(AppError::Internal(anyhow::anyhow!("Unauthorized")));
     }
+    state.status.record_push_received();
 
-    info!(
-        "✅ Authenticated push notification for task {}: state={:?}",
-        event.task_id, event.status.state
-    );
+    match &event {
+        WebhookEvent::StatusUpdate(update) | WebhookEvent::Finished(update) => info!(
+            "✅ Authenticated push notification for task {}: state={}",
+            update.task_id, update.status.state
+        ),
+        WebhookEvent::InputRequired(update) => info!(
+            "✅ Task {} is waiting for input from the user",
+            update.task_id
+        ),
+        WebhookEvent::ArtifactUpdate(update) => info!(
+            "✅ Task {} produced artifact {}",
+            update.task_id, update.artifact.artifact_id
+        ),
+    }
 
     Ok(axum::response::Json(serde_json::json!({
         "status": "received",
-        "task_id": event.task_id,
+        "task_id": event.task_id(),
+        "event_kind": event.event_kind(),
         "authenticated": true
     }))
     .into_response())
 }
 
+/// Errors returned by frontend handlers
+///
+/// User errors are answered with their stable code and parameters so the page can
+/// render them in the user's language; anything else is logged and reported as a
+/// generic internal error.
 #[derive(Debug)]
-struct AppError(anyhow::Error);
+enum AppError {
+    User(ErrorDetail),
+    Internal(anyhow::Error),
+}
+
+impl AppError {
+    /// Classify an agent error, keeping its code when the user can act on it
+    fn from_a2a(context: &str, error: A2AError) -> Self {
+        let detail = error.error_detail();
+        if detail.error_code == codes::INTERNAL {
+            AppError::Internal(anyhow::anyhow!("{}: {}", context, error))
+        } else {
+            AppError::User(detail)
+        }
+    }
+}
 
 impl IntoResponse for AppError {
     fn into_response(self) -> AxumResponse {
-        error!("Application error: {}", self.0);
+        use axum::http::StatusCode;
+
+        let (status, detail) = match self {
+            AppError::User(detail) => {
+                warn!(error_code = %detail.error_code, "Rejected request: {}", detail);
+                let status = match detail.error_code.as_str() {
+                    codes::TASK_NOT_FOUND => StatusCode::NOT_FOUND,
+                    _ => StatusCode::BAD_REQUEST,
+                };
+                (status, detail)
+            }
+            AppError::Internal(e) => {
+                error!("Application error: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ErrorDetail::new(codes::INTERNAL),
+                )
+            }
+        };
+
         (
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Internal server error: {}", self.0),
+            status,
+            axum::response::Json(serde_json::json!({
+                "errorCode": detail.error_code,
+                "params": detail.params,
+                "message": detail.message(),
+            })),
         )
             .into_response()
     }
 }
+
+/// Check the expense form, returning the parsed amount
+fn validate_expense_form(form: &ExpenseSubmitForm) -> Result<Money, ErrorDetail> {
+    let required = [
+        ("category", &form.category),
+        ("date", &form.date),
+        ("description", &form.description),
+    ];
+    if let Some((field, _)) = required.iter().find(|(_, value)| value.trim().is_empty()) {
+        return Err(ErrorDetail::field(codes::VALIDATION_REQUIRED, *field));
+    }
+    Money::parse(&form.amount)
+}
@@ -13,14 +13,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create configuration with SQLx storage
     let config = ServerConfig {
         host: "127.0.0.1".to_string(),
+        http_host: None,
+        ws_host: None,
         http_port: 8080,
         ws_port: 8081,
         storage: StorageConfig::Sqlx {
             url: "sqlite://reimbursement_test.db".to_string(),
             max_connections: 5,
+            min_connections: 0,
+            acquire_timeout_secs: 30,
+            idle_timeout_secs: None,
+            max_lifetime_secs: None,
+            statement_timeout_ms: None,
             enable_logging: true,
+            cache: None,
         },
         auth: Default::default(),
+        method_access: Default::default(),
+        retention: Default::default(),
+        processing_timeout: Default::default(),
+        default_page_size: 50,
+        max_page_size: 100,
+        debug: Default::default(),
+        sse_bridge: false,
     };
 
     println!("🚀 Starting Reimbursement Agent with SQLx Storage");
@@ -0,0 +1,144 @@
+//! Approval documents for reimbursed expenses
+//!
+//! An approved request gets a one-page PDF stating what was approved, by whom
+//! and when. It is attached to the task as an artifact, so the client can
+//! list it once the task completes and download it.
+
+use a2a_rs::domain::{Artifact, Part};
+use base64::Engine;
+
+use super::types::ProcessingDetails;
+
+/// ID of the approval document among a task's artifacts
+pub const APPROVAL_ARTIFACT_ID: &str = "approval";
+
+/// The approval document for request `request_id`, as a PDF artifact
+pub fn approval_artifact(request_id: &str, details: Option<&ProcessingDetails>) -> Artifact {
+    let mut lines = vec![
+        "Expense Reimbursement Approval".to_string(),
+        String::new(),
+        format!("Request: {}", request_id),
+    ];
+    if let Some(details) = details {
+        if let Some(amount) = &details.approved_amount {
+            lines.push(format!("Approved amount: {}", amount.to_formatted_string()));
+        }
+        if let Some(date) = &details.approval_date {
+            lines.push(format!("Approved on: {}", date));
+        }
+        if let Some(approver) = &details.approver {
+            lines.push(format!("Approved by: {}", approver));
+        }
+    }
+
+    let file_name = format!("approval-{}.pdf", request_id);
+    let pdf = base64::engine::general_purpose::STANDARD.encode(render_pdf(&lines));
+    Artifact {
+        artifact_id: APPROVAL_ARTIFACT_ID.to_string(),
+        name: Some(file_name.clone()),
+        description: Some(format!("Approval of reimbursement request {}", request_id)),
+        parts: vec![Part::file_from_bytes(
+            pdf,
+            Some(file_name),
+            Some("application/pdf".to_string()),
+        )],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    }
+}
+
+/// A single-page PDF showing `lines` in Helvetica
+fn render_pdf(lines: &[String]) -> Vec<u8> {
+    let mut content = String::from("BT /F1 14 Tf 20 TL 72 720 Td\n");
+    for line in lines {
+        let escaped = line
+            .replace('\\', "\\\\")
+            .replace('(', "\\(")
+            .replace(')', "\\)");
+        content.push_str(&format!("({}) Tj T*\n", escaped));
+    }
+    content.push_str("ET");
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+         /Resources << /Font << /F1 5 0 R >> >> >>"
+            .to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+    pdf.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reimbursement_agent::types::Money;
+
+    #[test]
+    fn test_approval_artifact_is_a_pdf_naming_the_request() {
+        let details = ProcessingDetails {
+            approved_amount: Some(Money::Number {
+                amount: 42.5,
+                currency: "USD".to_string(),
+            }),
+            approval_date: Some("2026-10-14".to_string()),
+            approver: Some("Finance (Team)".to_string()),
+            rejection_reason: None,
+            required_documents: None,
+        };
+
+        let artifact = approval_artifact("req-7", Some(&details));
+        artifact.validate().unwrap();
+        assert_eq!(artifact.artifact_id, APPROVAL_ARTIFACT_ID);
+        assert_eq!(artifact.name.as_deref(), Some("approval-req-7.pdf"));
+
+        let Part::File { file, .. } = &artifact.parts[0] else {
+            panic!("Expected a file part");
+        };
+        assert_eq!(file.mime_type.as_deref(), Some("application/pdf"));
+        let pdf = base64::engine::general_purpose::STANDARD
+            .decode(file.bytes.as_ref().unwrap())
+            .unwrap();
+        let pdf = String::from_utf8(pdf).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(Request: req-7) Tj"));
+        assert!(pdf.contains("(Approved amount: $42.50) Tj"));
+        assert!(pdf.contains("(Approved by: Finance \\(Team\\)) Tj"));
+
+        // The cross-reference table points at each object
+        let xref = pdf.rfind("startxref\n").unwrap();
+        let xref: usize = pdf[xref + 10..].lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with("xref"));
+        let first = pdf[xref..].lines().nth(3).unwrap();
+        let offset: usize = first[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with("1 0 obj"));
+    }
+}
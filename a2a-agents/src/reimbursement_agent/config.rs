@@ -1,31 +1,63 @@
+#[cfg(feature = "sqlx")]
+use a2a_rs::adapter::storage::DatabaseConfig;
+use a2a_rs::{
+    adapter::{MethodAccessPolicy, ProcessingTimeout, TaskCacheConfig, TaskRetentionPolicy},
+    domain::{PageSizeLimits, TaskState},
+};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::env;
+use std::{
+    collections::HashMap,
+    env,
+    net::{IpAddr, Ipv6Addr},
+    path::Path,
+    time::Duration,
+};
+
+use super::config_schema::strip_comments;
 
 /// Storage backend configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 #[serde(tag = "type")]
 pub enum StorageConfig {
     /// In-memory storage (default)
+    #[default]
     InMemory,
     /// SQLx-based persistent storage
     Sqlx {
         /// Database URL (e.g., sqlite:tasks.db, postgres://localhost/a2a)
         url: String,
         /// Maximum number of connections in the pool
+        #[schemars(range(min = 1))]
         #[serde(default = "default_max_connections")]
         max_connections: u32,
+        /// Connections the pool keeps open even when idle
+        #[serde(default)]
+        min_connections: u32,
+        /// Seconds a request waits for a free connection before failing as
+        /// retryable
+        #[schemars(range(min = 1))]
+        #[serde(default = "default_acquire_timeout_secs")]
+        acquire_timeout_secs: u64,
+        /// Seconds an idle connection is kept open
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        idle_timeout_secs: Option<u64>,
+        /// Seconds after which a connection is replaced
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_lifetime_secs: Option<u64>,
+        /// Milliseconds a statement waits on a locked database before failing
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        statement_timeout_ms: Option<u64>,
         /// Enable SQL query logging
         #[serde(default)]
         enable_logging: bool,
+        /// Cache task reads and listings in front of the database
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache: Option<StorageCacheConfig>,
     },
 }
 
-impl Default for StorageConfig {
-    fn default() -> Self {
-        Self::InMemory
-    }
-}
-
 impl StorageConfig {
     /// Create storage config from environment variables
     pub fn from_env() -> Self {
@@ -36,27 +68,152 @@ impl StorageConfig {
                     .ok()
                     .and_then(|s| s.parse().ok())
                     .unwrap_or_else(default_max_connections),
+                min_connections: env::var("DATABASE_MIN_CONNECTIONS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(0),
+                acquire_timeout_secs: env::var("DATABASE_ACQUIRE_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_else(default_acquire_timeout_secs),
+                idle_timeout_secs: env::var("DATABASE_IDLE_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok()),
+                max_lifetime_secs: env::var("DATABASE_MAX_LIFETIME_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok()),
+                statement_timeout_ms: env::var("DATABASE_STATEMENT_TIMEOUT_MS")
+                    .ok()
+                    .and_then(|s| s.parse().ok()),
                 enable_logging: env::var("DATABASE_ENABLE_LOGGING")
                     .ok()
                     .map(|s| s.to_lowercase() == "true" || s == "1")
                     .unwrap_or(false),
+                cache: env::var("DATABASE_CACHE")
+                    .ok()
+                    .filter(|s| s.to_lowercase() == "true" || s == "1")
+                    .map(|_| StorageCacheConfig::default()),
             }
         } else {
             Self::InMemory
         }
     }
+
+    /// Pool and connection settings for SQLx storage, if it is configured
+    #[cfg(feature = "sqlx")]
+    pub fn database_config(&self) -> Option<DatabaseConfig> {
+        match self {
+            Self::InMemory => None,
+            Self::Sqlx {
+                url,
+                max_connections,
+                min_connections,
+                acquire_timeout_secs,
+                idle_timeout_secs,
+                max_lifetime_secs,
+                statement_timeout_ms,
+                enable_logging,
+                ..
+            } => Some(
+                DatabaseConfig::builder()
+                    .url(url.clone())
+                    .max_connections(*max_connections)
+                    .min_connections(*min_connections)
+                    .timeout_seconds(*acquire_timeout_secs)
+                    .maybe_idle_timeout_seconds(*idle_timeout_secs)
+                    .maybe_max_lifetime_seconds(*max_lifetime_secs)
+                    .maybe_statement_timeout_ms(*statement_timeout_ms)
+                    .enable_logging(*enable_logging)
+                    .build(),
+            ),
+        }
+    }
+
+    /// Read cache to put in front of the storage, if one is configured
+    pub fn cache_config(&self) -> Option<TaskCacheConfig> {
+        match self {
+            Self::Sqlx {
+                cache: Some(cache), ..
+            } => Some(cache.to_cache_config()),
+            _ => None,
+        }
+    }
+}
+
+/// Read cache in front of SQLx storage.
+///
+/// Task reads and listings are served from memory for a short time, so
+/// dashboards polling the same tasks do not each reach the database. Writes
+/// made by this server are seen at once; writes by other servers sharing
+/// the database only once the entries expire.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct StorageCacheConfig {
+    /// Milliseconds a task read is served from the cache
+    #[serde(default = "default_cache_task_ttl_ms")]
+    pub task_ttl_ms: u64,
+    /// Milliseconds a listing is served from the cache
+    #[serde(default = "default_cache_list_ttl_ms")]
+    pub list_ttl_ms: u64,
+    /// Most task reads and listings kept, each
+    #[schemars(range(min = 1))]
+    #[serde(default = "default_cache_capacity")]
+    pub capacity: usize,
+}
+
+impl Default for StorageCacheConfig {
+    fn default() -> Self {
+        Self {
+            task_ttl_ms: default_cache_task_ttl_ms(),
+            list_ttl_ms: default_cache_list_ttl_ms(),
+            capacity: default_cache_capacity(),
+        }
+    }
+}
+
+impl StorageCacheConfig {
+    /// Convert to the cache configuration used by the storage wrapper
+    pub fn to_cache_config(&self) -> TaskCacheConfig {
+        TaskCacheConfig::new()
+            .with_task_ttl(Duration::from_millis(self.task_ttl_ms))
+            .with_list_ttl(Duration::from_millis(self.list_ttl_ms))
+            .with_capacity(self.capacity)
+    }
+}
+
+fn default_cache_task_ttl_ms() -> u64 {
+    2_000
+}
+
+fn default_cache_list_ttl_ms() -> u64 {
+    500
+}
+
+fn default_cache_capacity() -> usize {
+    1024
 }
 
 fn default_max_connections() -> u32 {
     10
 }
 
+fn default_acquire_timeout_secs() -> u64 {
+    30
+}
+
 /// Server configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct ServerConfig {
     /// Host to bind to
     #[serde(default = "default_host")]
     pub host: String,
+    /// Host for the HTTP listener, overriding `host`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_host: Option<String>,
+    /// Host for the WebSocket listener, overriding `host`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_host: Option<String>,
     /// Port for HTTP server
     #[serde(default = "default_http_port")]
     pub http_port: u16,
@@ -69,16 +226,49 @@ pub struct ServerConfig {
     /// Authentication configuration
     #[serde(default)]
     pub auth: AuthConfig,
+    /// JSON-RPC methods each auth role may call
+    #[serde(default)]
+    pub method_access: MethodAccessPolicy,
+    /// Task retention and cleanup configuration
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// How long a message may be processed before its task is failed
+    #[serde(default)]
+    pub processing_timeout: ProcessingTimeoutConfig,
+    /// Page size of `tasks/list` when the client sends none
+    #[schemars(range(min = 1))]
+    #[serde(default = "default_page_size")]
+    pub default_page_size: u32,
+    /// Largest page size a client may request; bigger requests are clamped
+    #[schemars(range(min = 1))]
+    #[serde(default = "default_max_page_size")]
+    pub max_page_size: u32,
+    /// Debugging endpoints, all off by default
+    #[serde(default)]
+    pub debug: DebugConfig,
+    /// When running WebSocket-only, serve an SSE bridge to the WebSocket agent
+    /// on the HTTP listener for clients that cannot open a WebSocket
+    #[serde(default)]
+    pub sse_bridge: bool,
 }
 
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
             host: default_host(),
+            http_host: None,
+            ws_host: None,
             http_port: default_http_port(),
             ws_port: default_ws_port(),
             storage: StorageConfig::default(),
             auth: AuthConfig::default(),
+            method_access: MethodAccessPolicy::default(),
+            retention: RetentionConfig::default(),
+            processing_timeout: ProcessingTimeoutConfig::default(),
+            default_page_size: default_page_size(),
+            max_page_size: default_max_page_size(),
+            debug: DebugConfig::default(),
+            sse_bridge: false,
         }
     }
 }
@@ -88,6 +278,8 @@ impl ServerConfig {
     pub fn from_env() -> Self {
         Self {
             host: env::var("HOST").unwrap_or_else(|_| default_host()),
+            http_host: env::var("HTTP_HOST").ok(),
+            ws_host: env::var("WS_HOST").ok(),
             http_port: env::var("HTTP_PORT")
                 .ok()
                 .and_then(|s| s.parse().ok())
@@ -98,23 +290,410 @@ impl ServerConfig {
                 .unwrap_or_else(default_ws_port),
             storage: StorageConfig::from_env(),
             auth: AuthConfig::from_env(),
+            method_access: MethodAccessPolicy::default(),
+            retention: RetentionConfig::from_env(),
+            processing_timeout: ProcessingTimeoutConfig::from_env(),
+            default_page_size: env::var("DEFAULT_PAGE_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(default_page_size),
+            max_page_size: env::var("MAX_PAGE_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(default_max_page_size),
+            debug: DebugConfig::from_env(),
+            sse_bridge: env::var("SSE_BRIDGE")
+                .ok()
+                .map(|s| s.to_lowercase() == "true" || s == "1")
+                .unwrap_or(false),
         }
     }
 
-    /// Load config from file or environment
+    /// Load config from the file named by `CONFIG_FILE`, or else from the
+    /// environment
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
         // First try to load from config file
         if let Ok(config_path) = env::var("CONFIG_FILE") {
-            let config_str = std::fs::read_to_string(config_path)?;
-            let config: Self = serde_json::from_str(&config_str)?;
-            Ok(config)
+            return Self::load_from(config_path);
+        }
+        // Fall back to environment variables
+        let config = Self::from_env();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load config from a JSON file.
+    ///
+    /// Lines of the config file starting with `//` are comments.
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_str = std::fs::read_to_string(path)?;
+        let config = serde_json::from_str::<Self>(&strip_comments(&config_str))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Page size limits applied to task listings
+    pub fn page_limits(&self) -> PageSizeLimits {
+        PageSizeLimits::new(self.default_page_size, self.max_page_size)
+    }
+
+    /// The method access policy to enforce, or `None` when it defines no
+    /// roles and every credential may call every method
+    pub fn enforced_method_access(&self) -> Option<&MethodAccessPolicy> {
+        let policy = &self.method_access;
+        (!policy.roles.is_empty() || policy.default_role.is_some()).then_some(policy)
+    }
+
+    /// Host the HTTP listener binds to
+    pub fn http_host(&self) -> &str {
+        self.http_host.as_deref().unwrap_or(&self.host)
+    }
+
+    /// Host the WebSocket listener binds to
+    pub fn ws_host(&self) -> &str {
+        self.ws_host.as_deref().unwrap_or(&self.host)
+    }
+
+    /// Socket address of the HTTP listener
+    pub fn http_bind_address(&self) -> String {
+        bind_address(self.http_host(), self.http_port)
+    }
+
+    /// Socket address of the WebSocket listener
+    pub fn ws_bind_address(&self) -> String {
+        bind_address(self.ws_host(), self.ws_port)
+    }
+
+    /// URL the SSE bridge connects to the WebSocket listener on
+    pub fn ws_upstream_url(&self) -> String {
+        // A wildcard bind address is reachable over loopback
+        let host = if is_unspecified(self.ws_host()) {
+            match self.ws_host().parse::<IpAddr>() {
+                Ok(IpAddr::V6(_)) => "::1",
+                _ => "127.0.0.1",
+            }
         } else {
-            // Fall back to environment variables
-            Ok(Self::from_env())
+            self.ws_host()
+        };
+        format!("ws://{}", bind_address(host, self.ws_port))
+    }
+
+    /// Check that the listener addresses are usable
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, host) in [
+            ("host", Some(self.host.as_str())),
+            ("http_host", self.http_host.as_deref()),
+            ("ws_host", self.ws_host.as_deref()),
+        ] {
+            if let Some(host) = host {
+                validate_host(name, host)?;
+            }
+        }
+
+        let same_interface = self.http_host() == self.ws_host()
+            || [self.http_host(), self.ws_host()]
+                .iter()
+                .any(|host| is_unspecified(host));
+        if same_interface && self.http_port == self.ws_port {
+            return Err(format!(
+                "HTTP and WebSocket listeners both bind port {} on overlapping addresses ({} and {})",
+                self.http_port,
+                self.http_host(),
+                self.ws_host()
+            ));
         }
+
+        if self.default_page_size == 0 || self.default_page_size > self.max_page_size {
+            return Err(format!(
+                "default_page_size must be between 1 and max_page_size ({}), got {}",
+                self.max_page_size, self.default_page_size
+            ));
+        }
+
+        // An undefined role would silently refuse every method
+        if let Some(default_role) = &self.method_access.default_role
+            && !self.method_access.roles.contains_key(default_role)
+        {
+            return Err(format!(
+                "method_access.default_role {} is not one of its roles",
+                default_role
+            ));
+        }
+        if let Some(roles) = self.auth.roles() {
+            let mut undefined: Vec<&str> = roles
+                .values()
+                .filter(|role| !self.method_access.roles.contains_key(*role))
+                .map(String::as_str)
+                .collect();
+            if !undefined.is_empty() {
+                undefined.sort_unstable();
+                undefined.dedup();
+                return Err(format!(
+                    "auth assigns roles missing from method_access: {}",
+                    undefined.join(", ")
+                ));
+            }
+        }
+
+        if self.processing_timeout.default_secs == Some(0) {
+            return Err("processing_timeout.default_secs must be greater than zero".to_string());
+        }
+        if let Some((skill, _)) = self
+            .processing_timeout
+            .skill_secs
+            .iter()
+            .find(|(_, secs)| **secs == 0)
+        {
+            return Err(format!(
+                "processing_timeout.skill_secs for '{}' must be greater than zero",
+                skill
+            ));
+        }
+
+        if self.debug.task_logs && self.debug.admin_tokens.is_empty() {
+            return Err(
+                "debug.task_logs is enabled but no debug.admin_tokens are configured".to_string(),
+            );
+        }
+
+        if self.debug.status_page && self.debug.admin_tokens.is_empty() {
+            return Err(
+                "debug.status_page is enabled but no debug.admin_tokens are configured".to_string(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a host and port as a socket address, bracketing IPv6 literals
+fn bind_address(host: &str, port: u16) -> String {
+    if host.parse::<Ipv6Addr>().is_ok() {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
     }
 }
 
+fn is_unspecified(host: &str) -> bool {
+    host.parse::<IpAddr>().is_ok_and(|ip| ip.is_unspecified())
+}
+
+/// Accept IP literals and RFC 1123 host names
+fn validate_host(name: &str, host: &str) -> Result<(), String> {
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+
+    let valid_hostname = !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if valid_hostname {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid {} '{}': expected an IP address or host name",
+            name, host
+        ))
+    }
+}
+
+/// Task retention configuration.
+///
+/// Tasks in a terminal state are deleted once their last status update is older
+/// than the TTL for that state. States without a TTL are kept forever.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// TTL for completed tasks, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_ttl_secs: Option<u64>,
+    /// TTL for canceled tasks, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canceled_ttl_secs: Option<u64>,
+    /// TTL for failed tasks, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_ttl_secs: Option<u64>,
+    /// TTL for rejected tasks, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected_ttl_secs: Option<u64>,
+    /// Seconds between cleanup sweeps
+    #[schemars(range(min = 1))]
+    #[serde(default = "default_cleanup_interval_secs")]
+    pub cleanup_interval_secs: u64,
+    /// Maximum number of tasks deleted per batch
+    #[schemars(range(min = 1))]
+    #[serde(default = "default_cleanup_batch_size")]
+    pub cleanup_batch_size: usize,
+    /// NDJSON file expired tasks are appended to before deletion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            completed_ttl_secs: None,
+            canceled_ttl_secs: None,
+            failed_ttl_secs: None,
+            rejected_ttl_secs: None,
+            cleanup_interval_secs: default_cleanup_interval_secs(),
+            cleanup_batch_size: default_cleanup_batch_size(),
+            archive_path: None,
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Create retention config from environment variables
+    pub fn from_env() -> Self {
+        let secs = |name: &str| env::var(name).ok().and_then(|s| s.parse().ok());
+        Self {
+            completed_ttl_secs: secs("TASK_TTL_COMPLETED_SECS"),
+            canceled_ttl_secs: secs("TASK_TTL_CANCELED_SECS"),
+            failed_ttl_secs: secs("TASK_TTL_FAILED_SECS"),
+            rejected_ttl_secs: secs("TASK_TTL_REJECTED_SECS"),
+            cleanup_interval_secs: secs("TASK_CLEANUP_INTERVAL_SECS")
+                .unwrap_or_else(default_cleanup_interval_secs),
+            cleanup_batch_size: env::var("TASK_CLEANUP_BATCH_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(default_cleanup_batch_size),
+            archive_path: env::var("TASK_ARCHIVE_PATH").ok(),
+        }
+    }
+
+    /// Build the retention policy used by the cleanup worker
+    pub fn to_policy(&self) -> TaskRetentionPolicy {
+        let mut policy = TaskRetentionPolicy::new()
+            .with_interval(Duration::from_secs(self.cleanup_interval_secs))
+            .with_batch_size(self.cleanup_batch_size);
+
+        let ttls = [
+            (TaskState::Completed, self.completed_ttl_secs),
+            (TaskState::Canceled, self.canceled_ttl_secs),
+            (TaskState::Failed, self.failed_ttl_secs),
+            (TaskState::Rejected, self.rejected_ttl_secs),
+        ];
+        for (state, ttl) in ttls {
+            if let Some(secs) = ttl {
+                policy = policy.with_ttl(state, Duration::from_secs(secs));
+            }
+        }
+
+        if let Some(path) = &self.archive_path {
+            policy = policy.with_archive_path(path);
+        }
+        policy
+    }
+}
+
+/// Processing timeout configuration.
+///
+/// A message still being handled after its limit is abandoned and its task
+/// failed with a `task.timeout` error. Without any limit, handlers may run
+/// forever.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProcessingTimeoutConfig {
+    /// Seconds a message may take, for skills without their own limit
+    #[schemars(range(min = 1))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_secs: Option<u64>,
+    /// Seconds a message may take, keyed by skill id
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub skill_secs: HashMap<String, u64>,
+}
+
+impl ProcessingTimeoutConfig {
+    /// Create processing timeout config from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            default_secs: env::var("PROCESSING_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok()),
+            skill_secs: HashMap::new(),
+        }
+    }
+
+    /// Build the timeout applied by the request processor, if any limit is set
+    pub fn to_timeout(&self) -> Option<ProcessingTimeout> {
+        if self.default_secs.is_none() && self.skill_secs.is_empty() {
+            return None;
+        }
+        let mut timeout = ProcessingTimeout::new();
+        if let Some(secs) = self.default_secs {
+            timeout = timeout.with_default_timeout(Duration::from_secs(secs));
+        }
+        for (skill, secs) in &self.skill_secs {
+            timeout = timeout.with_skill_timeout(skill, Duration::from_secs(*secs));
+        }
+        Some(timeout)
+    }
+}
+
+/// Debugging endpoints; keep these off in production.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct DebugConfig {
+    /// Serve `GET /tasks/{id}/logs`, streaming each task's server logs
+    #[serde(default)]
+    pub task_logs: bool,
+    /// Serve the `GET /status` dashboard on the web frontend
+    #[serde(default)]
+    pub status_page: bool,
+    /// Bearer tokens allowed to use the debugging endpoints
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub admin_tokens: Vec<String>,
+}
+
+impl DebugConfig {
+    /// Create debug config from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            task_logs: env::var("DEBUG_TASK_LOGS")
+                .ok()
+                .map(|s| s.to_lowercase() == "true" || s == "1")
+                .unwrap_or(false),
+            status_page: env::var("DEBUG_STATUS_PAGE")
+                .ok()
+                .map(|s| s.to_lowercase() == "true" || s == "1")
+                .unwrap_or(false),
+            admin_tokens: env::var("DEBUG_ADMIN_TOKENS")
+                .ok()
+                .map(|s| {
+                    s.split(',')
+                        .map(|t| t.trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn default_cleanup_interval_secs() -> u64 {
+    300
+}
+
+fn default_cleanup_batch_size() -> usize {
+    100
+}
+
+fn default_page_size() -> u32 {
+    PageSizeLimits::default().default_page_size
+}
+
+fn default_max_page_size() -> u32 {
+    PageSizeLimits::default().max_page_size
+}
+
 fn default_host() -> String {
     "127.0.0.1".to_string()
 }
@@ -128,10 +707,12 @@ fn default_ws_port() -> u16 {
 }
 
 /// Authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 #[serde(tag = "type")]
 pub enum AuthConfig {
     /// No authentication (default for development)
+    #[default]
     None,
     /// Bearer token authentication
     BearerToken {
@@ -140,26 +721,27 @@ pub enum AuthConfig {
         /// Optional bearer format description (e.g., "JWT")
         #[serde(skip_serializing_if = "Option::is_none")]
         format: Option<String>,
+        /// Role of each token that has one, keyed by token
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        roles: HashMap<String, String>,
     },
     /// API Key authentication
     ApiKey {
         /// Valid API keys
         keys: Vec<String>,
         /// Location of the API key: "header", "query", or "cookie"
+        #[schemars(extend("enum" = ["header", "query", "cookie"]))]
         #[serde(default = "default_api_key_location")]
         location: String,
         /// Name of the header/query param/cookie
         #[serde(default = "default_api_key_name")]
         name: String,
+        /// Role of each key that has one, keyed by API key
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        roles: HashMap<String, String>,
     },
 }
 
-impl Default for AuthConfig {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl AuthConfig {
     /// Create auth config from environment variables
     pub fn from_env() -> Self {
@@ -175,6 +757,7 @@ impl AuthConfig {
                 return Self::BearerToken {
                     tokens,
                     format: env::var("AUTH_BEARER_FORMAT").ok(),
+                    roles: HashMap::new(),
                 };
             }
         }
@@ -193,6 +776,7 @@ impl AuthConfig {
                     location: env::var("AUTH_API_KEY_LOCATION")
                         .unwrap_or_else(|_| default_api_key_location()),
                     name: env::var("AUTH_API_KEY_NAME").unwrap_or_else(|_| default_api_key_name()),
+                    roles: HashMap::new(),
                 };
             }
         }
@@ -200,6 +784,14 @@ impl AuthConfig {
         // Default to no authentication
         Self::None
     }
+
+    /// The roles assigned to credentials, keyed by credential
+    pub fn roles(&self) -> Option<&HashMap<String, String>> {
+        match self {
+            Self::None => None,
+            Self::BearerToken { roles, .. } | Self::ApiKey { roles, .. } => Some(roles),
+        }
+    }
 }
 
 fn default_api_key_location() -> String {
@@ -209,3 +801,200 @@ fn default_api_key_location() -> String {
 fn default_api_key_name() -> String {
     "X-API-Key".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listener_hosts_fall_back_to_host() {
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.http_bind_address(), "127.0.0.1:8080");
+        assert_eq!(config.ws_bind_address(), "127.0.0.1:8081");
+
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            http_host: Some("0.0.0.0".to_string()),
+            ws_host: Some("::1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config.http_bind_address(), "0.0.0.0:8080");
+        assert_eq!(config.ws_bind_address(), "[::1]:8081");
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_listener_hosts_are_deserialized() {
+        let config: ServerConfig = serde_json::from_str(
+            r#"{"host": "127.0.0.1", "http_host": "0.0.0.0", "ws_host": "localhost"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.http_host(), "0.0.0.0");
+        assert_eq!(config.ws_host(), "localhost");
+
+        let config: ServerConfig = serde_json::from_str(r#"{"host": "10.0.0.5"}"#).unwrap();
+        assert_eq!(config.http_host(), "10.0.0.5");
+        assert_eq!(config.ws_host(), "10.0.0.5");
+    }
+
+    #[test]
+    fn test_invalid_listener_hosts_are_rejected() {
+        let invalid = ServerConfig {
+            ws_host: Some("internal host".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().unwrap_err().contains("ws_host"));
+
+        let invalid = ServerConfig {
+            http_host: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().unwrap_err().contains("http_host"));
+
+        // The same port is fine on distinct interfaces but not on overlapping ones
+        let distinct = ServerConfig {
+            http_host: Some("127.0.0.1".to_string()),
+            ws_host: Some("127.0.0.2".to_string()),
+            http_port: 9000,
+            ws_port: 9000,
+            ..Default::default()
+        };
+        distinct.validate().unwrap();
+
+        let overlapping = ServerConfig {
+            http_host: Some("0.0.0.0".to_string()),
+            ..distinct
+        };
+        assert!(overlapping.validate().is_err());
+    }
+
+    #[test]
+    fn test_task_logs_are_off_unless_configured_with_tokens() {
+        let config: ServerConfig = serde_json::from_str(r#"{"host": "127.0.0.1"}"#).unwrap();
+        assert!(!config.debug.task_logs);
+
+        let without_tokens = ServerConfig {
+            debug: DebugConfig {
+                task_logs: true,
+                status_page: false,
+                admin_tokens: Vec::new(),
+            },
+            ..Default::default()
+        };
+        assert!(
+            without_tokens
+                .validate()
+                .unwrap_err()
+                .contains("admin_tokens")
+        );
+
+        let config: ServerConfig = serde_json::from_str(
+            r#"{"debug": {"task_logs": true, "admin_tokens": ["debug-token"]}}"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_page_sizes_are_validated() {
+        let config: ServerConfig =
+            serde_json::from_str(r#"{"default_page_size": 20, "max_page_size": 200}"#).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.page_limits().resolve(None), 20);
+        assert_eq!(config.page_limits().resolve(Some(500)), 200);
+
+        let invalid = ServerConfig {
+            default_page_size: 300,
+            max_page_size: 200,
+            ..Default::default()
+        };
+        assert!(
+            invalid
+                .validate()
+                .unwrap_err()
+                .contains("default_page_size")
+        );
+    }
+
+    #[test]
+    fn test_auth_roles_must_be_defined() {
+        let config: ServerConfig = serde_json::from_str(
+            r#"{
+                "auth": {
+                    "type": "BearerToken",
+                    "tokens": ["admin-token"],
+                    "roles": {"viewer-token": "viewer"}
+                },
+                "method_access": {
+                    "roles": {"viewer": {"allow": ["tasks/get", "tasks/list"]}}
+                }
+            }"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.auth.roles().unwrap()["viewer-token"], "viewer");
+        assert!(
+            config
+                .method_access
+                .check(Some("viewer"), "message/send")
+                .is_err()
+        );
+
+        let config: ServerConfig = serde_json::from_str(
+            r#"{"auth": {"type": "ApiKey", "keys": [], "roles": {"key-1": "auditor"}}}"#,
+        )
+        .unwrap();
+        assert!(config.validate().unwrap_err().contains("auditor"));
+
+        // The default role must be defined too
+        let mut config = config;
+        config.auth = AuthConfig::None;
+        config.method_access.default_role = Some("operator".to_string());
+        assert!(config.validate().unwrap_err().contains("operator"));
+    }
+
+    #[test]
+    fn test_processing_timeouts_are_validated() {
+        let config: ServerConfig = serde_json::from_str(r#"{"host": "127.0.0.1"}"#).unwrap();
+        assert!(config.processing_timeout.to_timeout().is_none());
+
+        let config: ServerConfig = serde_json::from_str(
+            r#"{"processing_timeout": {"default_secs": 60, "skill_secs": {"process_reimbursement": 300}}}"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        assert!(config.processing_timeout.to_timeout().is_some());
+
+        let config: ServerConfig = serde_json::from_str(
+            r#"{"processing_timeout": {"skill_secs": {"process_reimbursement": 0}}}"#,
+        )
+        .unwrap();
+        assert!(
+            config
+                .validate()
+                .unwrap_err()
+                .contains("process_reimbursement")
+        );
+    }
+
+    #[test]
+    fn test_storage_cache_is_off_unless_configured() {
+        let config: ServerConfig =
+            serde_json::from_str(r#"{"storage": {"type": "Sqlx", "url": "sqlite:tasks.db"}}"#)
+                .unwrap();
+        assert!(config.storage.cache_config().is_none());
+        assert!(ServerConfig::default().storage.cache_config().is_none());
+
+        let config: ServerConfig = serde_json::from_str(
+            r#"{"storage": {"type": "Sqlx", "url": "sqlite:tasks.db", "cache": {"task_ttl_ms": 5000}}}"#,
+        )
+        .unwrap();
+        let cache = config.storage.cache_config().unwrap();
+        assert_eq!(cache.task_ttl, Duration::from_secs(5));
+        assert_eq!(cache.list_ttl, Duration::from_millis(500));
+        assert_eq!(cache.capacity, 1024);
+    }
+}
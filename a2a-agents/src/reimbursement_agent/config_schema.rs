@@ -0,0 +1,270 @@
+//! JSON Schema and annotated example for [`ServerConfig`]
+//!
+//! The schema is derived from the config types, taking each field's
+//! description from its doc comment, so it cannot fall out of step with
+//! them. The tests below check that the example config sets every field the
+//! schema describes, so the annotated example documents them all.
+
+use schemars::generate::SchemaSettings;
+use serde_json::{Map, Value};
+
+use a2a_rs::adapter::{MethodAccessPolicy, RoleAccess};
+
+use super::config::{
+    AuthConfig, DebugConfig, ProcessingTimeoutConfig, RetentionConfig, ServerConfig,
+    StorageCacheConfig, StorageConfig,
+};
+
+/// A JSON Schema (draft 2020-12) describing every field `ServerConfig` accepts
+pub fn server_config_schema() -> Value {
+    let generator = SchemaSettings::draft2020_12()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator();
+    generator.into_root_schema_for::<ServerConfig>().to_value()
+}
+
+/// A config with every field set, used for the annotated example
+pub fn example_config() -> ServerConfig {
+    ServerConfig {
+        host: "127.0.0.1".to_string(),
+        http_host: Some("0.0.0.0".to_string()),
+        ws_host: Some("127.0.0.1".to_string()),
+        http_port: 8080,
+        ws_port: 8081,
+        storage: StorageConfig::Sqlx {
+            url: "sqlite:reimbursements.db".to_string(),
+            max_connections: 10,
+            min_connections: 1,
+            acquire_timeout_secs: 5,
+            idle_timeout_secs: Some(600),
+            max_lifetime_secs: Some(1_800),
+            statement_timeout_ms: Some(5_000),
+            enable_logging: false,
+            cache: Some(StorageCacheConfig::default()),
+        },
+        auth: AuthConfig::BearerToken {
+            tokens: vec!["admin-token".to_string()],
+            format: Some("JWT".to_string()),
+            roles: [("viewer-token".to_string(), "viewer".to_string())].into(),
+        },
+        method_access: MethodAccessPolicy::new()
+            .with_role(
+                "viewer",
+                RoleAccess::new()
+                    .allowing(vec!["tasks/get".to_string(), "tasks/list".to_string()])
+                    .denying(vec!["tasks/pushNotificationConfig/*".to_string()]),
+            )
+            .with_role("operator", RoleAccess::new())
+            .with_default_role("operator"),
+        retention: RetentionConfig {
+            completed_ttl_secs: Some(86_400),
+            canceled_ttl_secs: Some(3_600),
+            failed_ttl_secs: Some(604_800),
+            rejected_ttl_secs: Some(3_600),
+            archive_path: Some("archive/tasks.ndjson".to_string()),
+            ..Default::default()
+        },
+        processing_timeout: ProcessingTimeoutConfig {
+            default_secs: Some(300),
+            skill_secs: [("process_reimbursement".to_string(), 120)].into(),
+        },
+        default_page_size: 50,
+        max_page_size: 100,
+        debug: DebugConfig {
+            task_logs: false,
+            status_page: true,
+            admin_tokens: vec!["debug-token".to_string()],
+        },
+        sse_bridge: false,
+    }
+}
+
+/// [`example_config`] as JSON, with each field preceded by a `//` comment
+/// taken from the schema.
+///
+/// `ServerConfig::load_from` skips comment lines, so the output can be saved and
+/// used as a config file directly.
+pub fn annotated_example() -> String {
+    let example = serde_json::to_value(example_config()).expect("config serializes");
+    let mut out = String::new();
+    write_annotated(&mut out, &example, Some(&server_config_schema()), 0);
+    out.push('\n');
+    out
+}
+
+/// Remove the whole-line `//` comments written by [`annotated_example`]
+pub(crate) fn strip_comments(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn write_annotated(out: &mut String, value: &Value, schema: Option<&Value>, depth: usize) {
+    let fields = match value {
+        Value::Object(fields) => fields,
+        // Config lists only hold strings, so they fit on one line
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(Value::to_string).collect();
+            out.push_str(&format!("[{}]", items.join(", ")));
+            return;
+        }
+        other => {
+            out.push_str(&other.to_string());
+            return;
+        }
+    };
+    if fields.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    let schema = schema.map(|schema| variant_schema(schema, fields));
+    let indent = "  ".repeat(depth + 1);
+    out.push_str("{\n");
+    for (i, (name, field)) in fields.iter().enumerate() {
+        let field_schema = schema.and_then(|schema| property_schema(schema, name));
+        // The tag of an enum is described by its variant
+        let described = if name == "type" { schema } else { field_schema };
+        if let Some(description) = described
+            .and_then(|schema| schema.get("description"))
+            .and_then(Value::as_str)
+        {
+            for line in description.lines() {
+                out.push_str(format!("{}// {}", indent, line).trim_end());
+                out.push('\n');
+            }
+        }
+        out.push_str(&format!("{}{}: ", indent, Value::from(name.as_str())));
+        write_annotated(out, field, field_schema, depth + 1);
+        if i + 1 < fields.len() {
+            out.push(',');
+        }
+        out.push('\n');
+    }
+    out.push_str(&"  ".repeat(depth));
+    out.push('}');
+}
+
+/// The schema of an object, choosing among the variants of a tagged enum the
+/// one matching the object's `type` tag
+fn variant_schema<'a>(schema: &'a Value, object: &Map<String, Value>) -> &'a Value {
+    schema
+        .get("oneOf")
+        .and_then(Value::as_array)
+        .and_then(|variants| {
+            variants
+                .iter()
+                .find(|variant| variant.pointer("/properties/type/const") == object.get("type"))
+        })
+        .unwrap_or(schema)
+}
+
+/// The schema of field `name` of an object
+fn property_schema<'a>(schema: &'a Value, name: &str) -> Option<&'a Value> {
+    if let Some(field) = schema.pointer(&format!("/properties/{}", name)) {
+        return Some(field);
+    }
+    schema
+        .get("additionalProperties")
+        .filter(|values| values.is_object())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn assert_valid(schema: &Value, instance: &Value) {
+        let validator = jsonschema::validator_for(schema).expect("schema compiles");
+        if let Err(errors) = validator.validate(instance) {
+            let errors: Vec<String> = errors
+                .map(|error| format!("{} at {}", error, error.instance_path))
+                .collect();
+            panic!("{:#?}", errors);
+        }
+    }
+
+    /// Every property the schema describes is present in `value`
+    fn assert_covers(schema: &Value, value: &Value, path: &str) {
+        let Value::Object(fields) = value else {
+            return;
+        };
+        let properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .or_else(|| {
+                schema.get("oneOf")?.as_array()?.iter().find_map(|variant| {
+                    (variant.pointer("/properties/type/const") == fields.get("type"))
+                        .then(|| variant["properties"].as_object().cloned())
+                        .flatten()
+                })
+            })
+            .unwrap_or_default();
+        for (name, property) in &properties {
+            let field = fields
+                .get(name)
+                .unwrap_or_else(|| panic!("{}.{} is described but not set", path, name));
+            assert_covers(property, field, &format!("{}.{}", path, name));
+        }
+    }
+
+    #[test]
+    fn test_schema_matches_the_config_types() {
+        let schema = server_config_schema();
+        let example = serde_json::to_value(example_config()).unwrap();
+        assert_valid(&schema, &example);
+        assert_covers(&schema, &example, "config");
+
+        // Variants the example does not use are described too
+        for auth in [
+            AuthConfig::None,
+            AuthConfig::ApiKey {
+                keys: vec!["key-1".to_string()],
+                location: "query".to_string(),
+                name: "api_key".to_string(),
+                roles: HashMap::from([("key-1".to_string(), "viewer".to_string())]),
+            },
+        ] {
+            let auth = serde_json::to_value(auth).unwrap();
+            assert_valid(&schema["properties"]["auth"], &auth);
+            assert_covers(&schema["properties"]["auth"], &auth, "auth");
+        }
+        let storage = serde_json::to_value(StorageConfig::InMemory).unwrap();
+        assert_valid(&schema["properties"]["storage"], &storage);
+
+        // Defaults validate as well
+        assert_valid(
+            &schema,
+            &serde_json::to_value(ServerConfig::default()).unwrap(),
+        );
+
+        // Unknown fields are reported
+        let mut typo = example.clone();
+        typo["http_prot"] = json!(8080);
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        assert!(!validator.is_valid(&typo));
+    }
+
+    #[test]
+    fn test_annotated_example_round_trips_through_load() {
+        let annotated = annotated_example();
+        assert!(annotated.contains("  // Port for HTTP server\n  \"http_port\": 8080"));
+        assert!(annotated.contains("// Database URL"), "{}", annotated);
+
+        let path = std::env::temp_dir().join(format!(
+            "reimbursement-config-example-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, &annotated).unwrap();
+        let loaded = ServerConfig::load_from(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(example_config()).unwrap()
+        );
+    }
+}
@@ -7,10 +7,13 @@ use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
-use a2a_rs::domain::{A2AError, Message, Part, Role, Task, TaskState};
+use a2a_rs::domain::{
+    A2AError, ErrorDetail, Message, Part, Role, Task, TaskResult, TaskState, error_catalog::codes,
+};
 use a2a_rs::port::message_handler::AsyncMessageHandler;
 
 use super::ai_client::{AiClient, ChatMessage};
+use super::approval::approval_artifact;
 use super::types::*;
 
 // NOTE: Task storage is handled by DefaultRequestProcessor + SQLx/InMemory storage
@@ -546,6 +549,9 @@ Example response when asking for info:
                         self.store_file_metadata(&file_id, file_meta);
                     }
                 }
+                Part::Unknown(_) => {
+                    debug!(part_index = idx, kind = part.kind(), "Skipping unknown part kind");
+                }
             }
         }
 
@@ -819,12 +825,9 @@ Example response when asking for info:
             ReimbursementRequest::Initial { amount, .. } => {
                 // Validate amount if provided
                 if let Some(money) = amount {
-                    if let Err(e) = money.validate() {
-                        warn!(error = %e, "Amount validation failed");
-                        return Err(A2AError::ValidationError {
-                            field: "amount".to_string(),
-                            message: e,
-                        });
+                    if let Err(detail) = money.validate() {
+                        warn!(error_code = %detail.error_code, "Amount validation failed");
+                        return Err(A2AError::UserError(detail));
                     }
                 }
                 Ok(())
@@ -838,33 +841,28 @@ Example response when asking for info:
             } => {
                 // Validate required fields
                 if date.trim().is_empty() {
-                    return Err(A2AError::ValidationError {
-                        field: "date".to_string(),
-                        message: "Date is required".to_string(),
-                    });
+                    return Err(A2AError::UserError(ErrorDetail::field(
+                        codes::VALIDATION_REQUIRED,
+                        "date",
+                    )));
                 }
 
                 if purpose.trim().is_empty() {
-                    return Err(A2AError::ValidationError {
-                        field: "purpose".to_string(),
-                        message: "Purpose is required".to_string(),
-                    });
+                    return Err(A2AError::UserError(ErrorDetail::field(
+                        codes::VALIDATION_REQUIRED,
+                        "purpose",
+                    )));
                 }
 
                 // Validate amount
-                if let Err(e) = amount.validate() {
-                    return Err(A2AError::ValidationError {
-                        field: "amount".to_string(),
-                        message: e,
-                    });
-                }
+                amount.validate().map_err(A2AError::UserError)?;
 
                 // Validate against rules
                 if !self.validation_rules.allowed_categories.contains(category) {
-                    return Err(A2AError::ValidationError {
-                        field: "category".to_string(),
-                        message: format!("Category {:?} is not allowed", category),
-                    });
+                    return Err(A2AError::UserError(
+                        ErrorDetail::field(codes::VALIDATION_NOT_ALLOWED, "category")
+                            .with_param("value", format!("{:?}", category)),
+                    ));
                 }
 
                 // TODO: Add more validation (date range, amount limits, etc.)
@@ -873,10 +871,10 @@ Example response when asking for info:
             }
             ReimbursementRequest::StatusQuery { request_id } => {
                 if !request_id.starts_with("req_") {
-                    return Err(A2AError::ValidationError {
-                        field: "request_id".to_string(),
-                        message: "Invalid request ID format".to_string(),
-                    });
+                    return Err(A2AError::UserError(ErrorDetail::field(
+                        codes::VALIDATION_INVALID_FORMAT,
+                        "request_id",
+                    )));
                 }
                 Ok(())
             }
@@ -1226,22 +1224,44 @@ Example response when asking for info:
     }
 }
 
-#[async_trait]
-impl<T> AsyncMessageHandler for ReimbursementHandler<T>
+impl<T> ReimbursementHandler<T>
 where
     T: a2a_rs::port::AsyncTaskManager + Clone + Send + Sync + 'static,
 {
-    #[instrument(skip(self, message), fields(
-        task_id = %task_id,
-        message_id = %message.message_id,
-        session_id = ?_session_id,
-        parts_count = message.parts.len()
-    ))]
-    async fn process_message<'a>(
+    /// Add the user's message to the task's history, checking the task is
+    /// still at `expected_version` if one is given
+    async fn add_user_message(
         &self,
-        task_id: &'a str,
-        message: &'a Message,
-        _session_id: Option<&'a str>,
+        task_id: &str,
+        message: &Message,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        match expected_version {
+            Some(expected_version) => {
+                self.task_manager
+                    .update_task_status_at_version(
+                        task_id,
+                        expected_version,
+                        TaskState::Working,
+                        Some(message.clone()),
+                    )
+                    .await
+            }
+            None => {
+                self.task_manager
+                    .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+                    .await
+            }
+        }
+    }
+
+    /// Process a reimbursement message, with the first write to the task
+    /// checking `expected_version` if one is given
+    async fn handle(
+        &self,
+        task_id: &str,
+        message: &Message,
+        expected_version: Option<u64>,
     ) -> Result<Task, A2AError> {
         error!(
             "🚨 HANDLER CALLED: Processing reimbursement request for task_id={}",
@@ -1256,6 +1276,11 @@ where
             None
         };
 
+        // A task expected at a version must already exist
+        if existing_task.is_none() && expected_version.is_some() {
+            return Err(A2AError::TaskNotFound(task_id.to_string()));
+        }
+
         // If task doesn't exist, create it
         if existing_task.is_none() {
             let context_id = message
@@ -1272,8 +1297,7 @@ where
             if task.status.state == TaskState::Completed {
                 // This is a follow-up to a completed task
                 // Add user message to history
-                self.task_manager
-                    .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+                self.add_user_message(task_id, message, expected_version)
                     .await?;
 
                 // Send a simple acknowledgment
@@ -1295,8 +1319,7 @@ where
         }
 
         // Add the user's message to history first
-        self.task_manager
-            .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+        self.add_user_message(task_id, message, expected_version)
             .await?;
 
         // Send immediate acknowledgment
@@ -1390,6 +1413,44 @@ where
                 }
             };
 
+            // Completed results are also attached to the task as structured data
+            if let (TaskState::Completed, ReimbursementResponse::Result { .. }) =
+                (&task_state, &response)
+            {
+                let result = TaskResult::from_value(&response).map(|result| {
+                    result.with_skill_id(PROCESS_REIMBURSEMENT_SKILL.to_string())
+                });
+                let stored = match result {
+                    Ok(result) => handler
+                        .task_manager
+                        .set_task_result(&task_id_owned, result)
+                        .await
+                        .map(|_| ()),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = stored {
+                    warn!(task_id = %task_id_owned, error = %e, "Failed to attach structured result to task");
+                }
+            }
+
+            // Approved requests come with an approval document the client can download
+            if let ReimbursementResponse::Result {
+                request_id,
+                status: ProcessingStatus::Approved,
+                details,
+                ..
+            } = &response
+            {
+                let artifact = approval_artifact(request_id, details.as_ref());
+                if let Err(e) = handler
+                    .task_manager
+                    .add_task_artifact(&task_id_owned, artifact)
+                    .await
+                {
+                    warn!(task_id = %task_id_owned, error = %e, "Failed to attach approval document to task");
+                }
+            }
+
             // Create response message
             let response_parts = handler.response_to_parts(response);
             let response_message = Message::builder()
@@ -1432,6 +1493,37 @@ where
         let final_task = self.task_manager.get_task(task_id, Some(50)).await?;
         Ok(final_task)
     }
+}
+
+#[async_trait]
+impl<T> AsyncMessageHandler for ReimbursementHandler<T>
+where
+    T: a2a_rs::port::AsyncTaskManager + Clone + Send + Sync + 'static,
+{
+    #[instrument(skip(self, message), fields(
+        task_id = %task_id,
+        message_id = %message.message_id,
+        session_id = ?_session_id,
+        parts_count = message.parts.len()
+    ))]
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        _session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        self.handle(task_id, message, None).await
+    }
+
+    async fn process_message_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        _session_id: Option<&'a str>,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        self.handle(task_id, message, Some(expected_version)).await
+    }
 
     async fn validate_message<'a>(&self, message: &'a Message) -> Result<(), A2AError> {
         if message.parts.is_empty() {
@@ -1488,6 +1580,8 @@ where
                         }
                     }
                 }
+                // Parts from newer protocol versions carry nothing to validate
+                Part::Unknown(_) => {}
             }
         }
 
@@ -1,14 +1,24 @@
 //! Reimbursement agent implementation
 
 pub mod ai_client;
+pub mod approval;
 pub mod config;
+pub mod config_schema;
 pub mod handler;
+pub mod prompts;
 pub mod server;
+pub mod status;
 pub mod types;
 
 // Re-export key types for convenience
 pub use ai_client::{AiClient, AiConfig, ChatMessage};
-pub use config::{AuthConfig, ServerConfig, StorageConfig};
+pub use approval::{APPROVAL_ARTIFACT_ID, approval_artifact};
+pub use config::{
+    AuthConfig, DebugConfig, RetentionConfig, ServerConfig, StorageCacheConfig, StorageConfig,
+};
+pub use config_schema::{annotated_example, example_config, server_config_schema};
 pub use handler::ReimbursementHandler;
+pub use prompts::{PromptFields, PromptTemplate, PromptTemplates};
 pub use server::ReimbursementServer;
+pub use status::{StatusPage, StatusTracker, status_routes};
 pub use types::*;
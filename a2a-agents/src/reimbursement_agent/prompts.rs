@@ -0,0 +1,384 @@
+//! Templates turning structured form data into agent prompts
+//!
+//! A [`PromptTemplate`] renders a message's text from `{field}` placeholders
+//! and, optionally, a `Data` part carrying the same fields in structured form,
+//! so the agent does not have to parse them back out of the prose. A line
+//! naming a field without a value is left out, so optional fields don't leave
+//! empty labels behind.
+//!
+//! [`PromptTemplates`] starts with the built-in templates. A JSON file can
+//! replace any of them to change the phrasing for another agent or locale:
+//!
+//! ```json
+//! {
+//!   "expense_submission": {
+//!     "text": "Bitte erstatten Sie {amount} vom {date}.\nZweck: {description}",
+//!     "data": { "amount": "{amount}", "date": "{date}", "purpose": "{description}" }
+//!   }
+//! }
+//! ```
+//!
+//! Templates are checked when they are loaded, so a misspelled placeholder
+//! fails at startup rather than when a user submits a form.
+
+use std::collections::BTreeMap;
+
+use a2a_rs::domain::Part;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Values for a template's placeholders; empty values count as unset
+pub type PromptFields = BTreeMap<String, String>;
+
+/// Name of the template for expense form submissions
+pub const EXPENSE_SUBMISSION: &str = "expense_submission";
+
+/// Fields the expense submission template may use
+pub const EXPENSE_FIELDS: &[&str] = &[
+    "category",
+    "amount",
+    "date",
+    "vendor",
+    "description",
+    "project_code",
+];
+
+const EXPENSE_TEXT: &str = "I need to submit an expense reimbursement:\n\n\
+    Category: {category}\n\
+    Amount: {amount}\n\
+    Date: {date}\n\
+    Vendor: {vendor}\n\
+    Description: {description}\n\
+    Project/Cost Center: {project_code}\n";
+
+/// Keys match what the reimbursement handler reads from `Data` parts
+const EXPENSE_DATA: &[(&str, &str)] = &[
+    ("date", "{date}"),
+    ("amount", "{amount}"),
+    ("purpose", "{description}"),
+    ("expense_type", "{category}"),
+    ("vendor", "{vendor}"),
+    ("project_code", "{project_code}"),
+];
+
+/// A built-in template and the fields it is rendered with
+struct BuiltIn {
+    name: &'static str,
+    fields: &'static [&'static str],
+    text: &'static str,
+    data: &'static [(&'static str, &'static str)],
+}
+
+const BUILT_IN: &[BuiltIn] = &[BuiltIn {
+    name: EXPENSE_SUBMISSION,
+    fields: EXPENSE_FIELDS,
+    text: EXPENSE_TEXT,
+    data: EXPENSE_DATA,
+}];
+
+/// A checked template for one kind of message
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "TemplateSource")]
+pub struct PromptTemplate {
+    text: String,
+    data: BTreeMap<String, String>,
+}
+
+/// A template as written in a file, before it is checked
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateSource {
+    text: String,
+    #[serde(default)]
+    data: BTreeMap<String, String>,
+}
+
+impl TryFrom<TemplateSource> for PromptTemplate {
+    type Error = String;
+
+    fn try_from(source: TemplateSource) -> Result<Self, Self::Error> {
+        Self::new(source.text, source.data, None)
+    }
+}
+
+impl PromptTemplate {
+    /// Check a template, limiting its placeholders to `fields` if given.
+    ///
+    /// Each `data` entry becomes a key of the `Data` part, its value rendered
+    /// from a template of its own; a template without `data` sends no part.
+    pub fn new(
+        text: String,
+        data: BTreeMap<String, String>,
+        fields: Option<&[&str]>,
+    ) -> Result<Self, String> {
+        if text.trim().is_empty() {
+            return Err("text must not be empty".to_string());
+        }
+        check_placeholders("text", &text, fields)?;
+        for (key, value) in &data {
+            if key.trim().is_empty() {
+                return Err("data keys must not be empty".to_string());
+            }
+            check_placeholders(&format!("data.{}", key), value, fields)?;
+        }
+        Ok(Self { text, data })
+    }
+
+    /// Render the message parts: the text, then the `Data` part if the
+    /// template has one and any of its values are set
+    pub fn render(&self, fields: &PromptFields) -> Vec<Part> {
+        let text = self
+            .text
+            .split('\n')
+            .filter_map(|line| fill(line, fields))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut parts = vec![Part::text(text)];
+
+        let data: Map<String, Value> = self
+            .data
+            .iter()
+            .filter_map(|(key, value)| {
+                let value = fill(value, fields)?;
+                Some((key.clone(), Value::String(value)))
+            })
+            .collect();
+        if !data.is_empty() {
+            parts.push(Part::data(data));
+        }
+        parts
+    }
+}
+
+/// The templates a frontend renders its messages with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplates {
+    templates: BTreeMap<String, PromptTemplate>,
+}
+
+impl Default for PromptTemplates {
+    fn default() -> Self {
+        let templates = BUILT_IN
+            .iter()
+            .map(|built_in| {
+                let data = built_in
+                    .data
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect();
+                let template =
+                    PromptTemplate::new(built_in.text.to_string(), data, Some(built_in.fields))
+                        .expect("built-in prompt templates are valid");
+                (built_in.name.to_string(), template)
+            })
+            .collect();
+        Self { templates }
+    }
+}
+
+impl PromptTemplates {
+    /// The built-in templates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the built-in template `name`, checking that it only uses the
+    /// fields that template is rendered with
+    pub fn set(&mut self, name: &str, template: PromptTemplate) -> Result<(), String> {
+        let Some(built_in) = BUILT_IN.iter().find(|built_in| built_in.name == name) else {
+            return Err(format!("unknown prompt template '{}'", name));
+        };
+        let template = PromptTemplate::new(template.text, template.data, Some(built_in.fields))
+            .map_err(|e| format!("{}: {}", name, e))?;
+        self.templates.insert(name.to_string(), template);
+        Ok(())
+    }
+
+    /// The built-in templates with those in `json` replacing them, where
+    /// `json` maps template names to `{ "text": ..., "data": {...} }`
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let overrides: BTreeMap<String, PromptTemplate> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let mut templates = Self::new();
+        for (name, template) in overrides {
+            templates.set(&name, template)?;
+        }
+        Ok(templates)
+    }
+
+    /// Load template overrides from a JSON file
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(Self::from_json(&json)?)
+    }
+
+    /// The template `name`, if there is one
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Render the template `name` with `fields`
+    pub fn render(&self, name: &str, fields: &PromptFields) -> Option<Vec<Part>> {
+        self.get(name).map(|template| template.render(fields))
+    }
+}
+
+/// The `{name}` placeholders of a template, or why it is malformed
+fn placeholders(template: &str) -> Result<Vec<&str>, String> {
+    let mut found = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err("has a '}' without a matching '{'".to_string());
+        }
+        let after = &rest[start + 1..];
+        let end = after
+            .find(['{', '}'])
+            .filter(|&end| after[end..].starts_with('}'))
+            .ok_or_else(|| "has a '{' without a matching '}'".to_string())?;
+        let name = &after[..end];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("has an invalid placeholder '{{{}}}'", name));
+        }
+        found.push(name);
+        rest = &after[end + 1..];
+    }
+    Ok(found)
+}
+
+fn check_placeholders(what: &str, template: &str, fields: Option<&[&str]>) -> Result<(), String> {
+    let names = placeholders(template).map_err(|e| format!("{} {}", what, e))?;
+    if let Some(fields) = fields {
+        if let Some(unknown) = names.iter().find(|name| !fields.contains(name)) {
+            return Err(format!(
+                "{} uses unknown field '{}', expected one of: {}",
+                what,
+                unknown,
+                fields.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Substitute `fields` into a checked template, or `None` if it names a field
+/// without a value
+fn fill(template: &str, fields: &PromptFields) -> Option<String> {
+    let mut filled = template.to_string();
+    for name in placeholders(template).ok()? {
+        let value = fields.get(name).filter(|value| !value.trim().is_empty())?;
+        filled = filled.replacen(&format!("{{{}}}", name), value, 1);
+    }
+    Some(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expense_fields(vendor: Option<&str>) -> PromptFields {
+        let mut fields = PromptFields::from([
+            ("category".to_string(), "travel".to_string()),
+            ("amount".to_string(), "$42.50".to_string()),
+            ("date".to_string(), "2024-03-01".to_string()),
+            ("description".to_string(), "Taxi to the airport".to_string()),
+            ("project_code".to_string(), String::new()),
+        ]);
+        if let Some(vendor) = vendor {
+            fields.insert("vendor".to_string(), vendor.to_string());
+        }
+        fields
+    }
+
+    #[test]
+    fn test_expense_template_renders_text_and_data_parts() {
+        let parts = PromptTemplates::new()
+            .render(EXPENSE_SUBMISSION, &expense_fields(Some("City Cabs")))
+            .unwrap();
+        assert_eq!(parts.len(), 2);
+
+        match &parts[0] {
+            Part::Text { text, .. } => assert_eq!(
+                text,
+                "I need to submit an expense reimbursement:\n\n\
+                 Category: travel\n\
+                 Amount: $42.50\n\
+                 Date: 2024-03-01\n\
+                 Vendor: City Cabs\n\
+                 Description: Taxi to the airport\n"
+            ),
+            other => panic!("Expected a text part, got {:?}", other),
+        }
+        match &parts[1] {
+            Part::Data { data, .. } => assert_eq!(
+                Value::Object(data.clone()),
+                serde_json::json!({
+                    "date": "2024-03-01",
+                    "amount": "$42.50",
+                    "purpose": "Taxi to the airport",
+                    "expense_type": "travel",
+                    "vendor": "City Cabs",
+                })
+            ),
+            other => panic!("Expected a data part, got {:?}", other),
+        }
+
+        // Unset optional fields drop their lines and keys
+        let parts = PromptTemplates::new()
+            .render(EXPENSE_SUBMISSION, &expense_fields(None))
+            .unwrap();
+        let Part::Text { text, .. } = &parts[0] else {
+            panic!("Expected a text part");
+        };
+        assert!(!text.contains("Vendor"), "{}", text);
+        let Part::Data { data, .. } = &parts[1] else {
+            panic!("Expected a data part");
+        };
+        assert!(!data.contains_key("vendor"));
+    }
+
+    #[test]
+    fn test_overrides_replace_the_phrasing() {
+        let templates = PromptTemplates::from_json(
+            r#"{"expense_submission": {"text": "Bitte erstatten: {amount} ({description})"}}"#,
+        )
+        .unwrap();
+        let parts = templates
+            .render(EXPENSE_SUBMISSION, &expense_fields(None))
+            .unwrap();
+        assert_eq!(parts.len(), 1, "a template without data sends no data part");
+        let Part::Text { text, .. } = &parts[0] else {
+            panic!("Expected a text part");
+        };
+        assert_eq!(text, "Bitte erstatten: $42.50 (Taxi to the airport)");
+    }
+
+    #[test]
+    fn test_invalid_templates_are_rejected_at_load() {
+        for (json, expected) in [
+            (
+                r#"{"expense_submission": {"text": "Amount: {amount"}}"#,
+                "without a matching '}'",
+            ),
+            (
+                r#"{"expense_submission": {"text": "Total: {total}"}}"#,
+                "unknown field 'total'",
+            ),
+            (
+                r#"{"expense_submission": {"text": "ok", "data": {"sum": "{amount} }"}}}"#,
+                "data.sum has a '}'",
+            ),
+            (
+                r#"{"lunch_order": {"text": "{amount}"}}"#,
+                "unknown prompt template",
+            ),
+            (
+                r#"{"expense_submission": {"text": " "}}"#,
+                "must not be empty",
+            ),
+        ] {
+            let error = PromptTemplates::from_json(json).unwrap_err();
+            assert!(error.contains(expected), "{}: {}", json, error);
+        }
+    }
+}
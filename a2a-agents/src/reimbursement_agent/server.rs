@@ -1,19 +1,41 @@
 use a2a_rs::adapter::{
-    BearerTokenAuthenticator, DefaultRequestProcessor, HttpPushNotificationSender, HttpServer,
-    InMemoryTaskStorage, SimpleAgentInfo, WebSocketServer,
+    BearerTokenAuthenticator, CachedTaskStorage, DefaultRequestProcessor,
+    HttpPushNotificationSender, HttpServer, InMemoryTaskStorage, SimpleAgentInfo, SseBridge,
+    TaskCleanupWorker, TaskLogConfig, WebSocketServer,
 };
-use a2a_rs::port::{AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager};
+use a2a_rs::domain::{ContentPolicy, SecurityScheme};
+use a2a_rs::observability::TaskLogHub;
+use a2a_rs::port::{
+    AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager, Authenticator,
+};
+use a2a_rs::services::{
+    AgentInfoProvider, AsyncA2ARequestProcessor, MiddlewareChain, RequestMiddleware,
+};
+use std::collections::HashMap;
 
 // SQLx storage support (feature-gated)
 #[cfg(feature = "sqlx")]
-use a2a_rs::adapter::storage::SqlxTaskStorage;
+use a2a_rs::adapter::storage::{
+    SqlxTaskStorage,
+    migrations::{self, AppliedMigration},
+};
 
 use super::config::{AuthConfig, ServerConfig, StorageConfig};
 use super::handler::ReimbursementHandler;
+use super::types::{PROCESS_REIMBURSEMENT_SKILL, reimbursement_result_schema};
+
+/// Reimbursement tables, applied after the base task tables. New migrations
+/// go at the end: they are numbered by position.
+#[cfg(feature = "sqlx")]
+pub const REIMBURSEMENT_MIGRATIONS: &[&str] = &[include_str!(
+    "../../migrations/001_create_reimbursements.sql"
+)];
 
 /// Modern A2A server setup using ReimbursementHandler
 pub struct ReimbursementServer {
     config: ServerConfig,
+    task_logs: Option<TaskLogHub>,
+    middleware: MiddlewareChain,
 }
 
 impl ReimbursementServer {
@@ -21,17 +43,80 @@ impl ReimbursementServer {
     pub fn new(host: String, port: u16) -> Self {
         let config = ServerConfig {
             host,
+            http_host: None,
+            ws_host: None,
             http_port: port,
             ws_port: port + 1,
             storage: StorageConfig::default(),
             auth: AuthConfig::default(),
+            method_access: Default::default(),
+            retention: Default::default(),
+            processing_timeout: Default::default(),
+            default_page_size: 50,
+            max_page_size: 100,
+            debug: Default::default(),
+            sse_bridge: false,
         };
-        Self { config }
+        Self::from_config(config)
     }
 
     /// Create server from config
     pub fn from_config(config: ServerConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            task_logs: None,
+            middleware: MiddlewareChain::new(),
+        }
+    }
+
+    /// Capture task logs into `hub`, served over HTTP when `debug.task_logs` is
+    /// enabled. The hub's layer must be part of the installed tracing subscriber.
+    pub fn with_task_log_hub(mut self, hub: TaskLogHub) -> Self {
+        self.task_logs = Some(hub);
+        self
+    }
+
+    /// Run every RPC, over HTTP and WebSocket, through `middleware`.
+    ///
+    /// Middleware runs in the order it is added before a request is handled,
+    /// and in reverse order once the response is ready.
+    pub fn with_middleware(mut self, middleware: impl RequestMiddleware + 'static) -> Self {
+        self.middleware = self.middleware.with(middleware);
+        self
+    }
+
+    /// Mount the task log stream on `server` if a hub is set
+    fn attach_task_logs<P, A, Auth>(&self, server: HttpServer<P, A, Auth>) -> HttpServer<P, A, Auth>
+    where
+        P: AsyncA2ARequestProcessor + Clone + Send + Sync + 'static,
+        A: AgentInfoProvider + Clone + Send + Sync + 'static,
+        Auth: Authenticator + Clone + Send + Sync + 'static,
+    {
+        let Some(hub) = &self.task_logs else {
+            return server;
+        };
+        let debug = &self.config.debug;
+        if debug.task_logs {
+            println!(
+                "🪵 Debug task logs: http://{}/tasks/{{id}}/logs",
+                self.config.http_bind_address()
+            );
+        }
+        let config = TaskLogConfig::new(BearerTokenAuthenticator::new(debug.admin_tokens.clone()))
+            .with_enabled(debug.task_logs);
+        server.with_task_logs(hub.clone(), config)
+    }
+
+    /// Serve the SSE bridge to the WebSocket listener on the HTTP address
+    fn spawn_sse_bridge(&self) {
+        let address = self.config.http_bind_address();
+        let bridge = SseBridge::new(self.config.ws_upstream_url()).with_history_length(50);
+        println!("🌉 SSE bridge: http://{}/tasks/{{id}}/stream", address);
+        tokio::spawn(async move {
+            if let Err(e) = bridge.start(&address).await {
+                eprintln!("❌ SSE bridge error: {}", e);
+            }
+        });
     }
 
     /// Create in-memory storage
@@ -41,53 +126,99 @@ impl ReimbursementServer {
             .with_timeout(30)
             .with_max_retries(3);
         InMemoryTaskStorage::with_push_sender(push_sender)
+            .with_page_limits(self.config.page_limits())
+    }
+
+    /// Content policy for uploaded receipts: images and PDFs whose contents match
+    /// the declared type
+    fn receipt_content_policy() -> ContentPolicy {
+        ContentPolicy::new()
+            .with_allowed_types(vec!["image/*".to_string(), "application/pdf".to_string()])
+            .reject_mismatches(true)
+    }
+
+    /// Bearer authenticator accepting `tokens`, giving each its configured role
+    fn bearer_authenticator(
+        tokens: &[String],
+        roles: &HashMap<String, String>,
+    ) -> BearerTokenAuthenticator {
+        roles.iter().fold(
+            BearerTokenAuthenticator::new(tokens.to_vec()),
+            |authenticator, (token, role)| authenticator.with_role(token.clone(), role.clone()),
+        )
+    }
+
+    /// Bring the configured SQLx database's schema up to date without
+    /// starting the servers, returning the migrations that were applied
+    #[cfg(feature = "sqlx")]
+    pub async fn migrate(&self) -> Result<Vec<AppliedMigration>, Box<dyn std::error::Error>> {
+        let database = self
+            .config
+            .storage
+            .database_config()
+            .ok_or("Migrations need SQLx storage, but in-memory storage is configured")?;
+        database.validate()?;
+        let pool = database
+            .pool_options()
+            .connect_with(database.connect_options()?)
+            .await?;
+        let applied = migrations::migrate(&pool, REIMBURSEMENT_MIGRATIONS).await;
+        pool.close().await;
+        Ok(applied?)
     }
 
     #[cfg(feature = "sqlx")]
     /// Create SQLx storage (only available with sqlx feature)
-    async fn create_sqlx_storage(
-        &self,
-        url: &str,
-        _max_connections: u32,
-        enable_logging: bool,
-    ) -> Result<SqlxTaskStorage, Box<dyn std::error::Error>> {
+    async fn create_sqlx_storage(&self) -> Result<SqlxTaskStorage, Box<dyn std::error::Error>> {
+        let database = self
+            .config
+            .storage
+            .database_config()
+            .ok_or("SQLx storage is not configured")?;
         tracing::info!(
             "Using SQLx storage with URL: {} and push notification support",
-            url
+            database.url
+        );
+        tracing::info!(
+            "SQLx pool: {}-{} connections, {}s acquire timeout",
+            database.min_connections,
+            database.max_connections,
+            database.timeout_seconds
         );
-        if enable_logging {
+        if database.enable_logging {
             tracing::info!("SQL query logging enabled");
         }
 
-        // Include reimbursement-specific migrations
-        let reimbursement_migrations = &[include_str!(
-            "../../migrations/001_create_reimbursements.sql"
-        )];
-
         // SqlxTaskStorage uses HttpPushNotificationSender by default
-        let storage = SqlxTaskStorage::with_migrations(url, reimbursement_migrations)
+        let storage = SqlxTaskStorage::with_config(&database, REIMBURSEMENT_MIGRATIONS)
             .await
             .map_err(|e| format!("Failed to create SQLx storage: {}", e))?;
-        Ok(storage)
+        Ok(storage.with_page_limits(self.config.page_limits()))
     }
 
     /// Start the HTTP server
     pub async fn start_http(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.config.validate()?;
         match &self.config.storage {
             StorageConfig::InMemory => {
                 let storage = self.create_in_memory_storage();
-                self.start_http_server(storage).await
+                self.with_cleanup(&storage, self.start_http_server(storage.clone(), false))
+                    .await
             }
             #[cfg(feature = "sqlx")]
-            StorageConfig::Sqlx {
-                url,
-                max_connections,
-                enable_logging,
-            } => {
-                let storage = self
-                    .create_sqlx_storage(url, *max_connections, *enable_logging)
-                    .await?;
-                self.start_http_server(storage).await
+            StorageConfig::Sqlx { .. } => {
+                let storage = self.create_sqlx_storage().await?;
+                match self.config.storage.cache_config() {
+                    Some(cache) => {
+                        let storage = CachedTaskStorage::new(storage).with_config(cache);
+                        self.with_cleanup(&storage, self.start_http_server(storage.clone(), false))
+                            .await
+                    }
+                    None => {
+                        self.with_cleanup(&storage, self.start_http_server(storage.clone(), false))
+                            .await
+                    }
+                }
             }
             #[cfg(not(feature = "sqlx"))]
             StorageConfig::Sqlx { .. } => {
@@ -96,14 +227,44 @@ impl ReimbursementServer {
         }
     }
 
+    /// Run a server future alongside the task cleanup worker, if retention is
+    /// configured, and stop the worker once the server exits
+    async fn with_cleanup<S, F>(
+        &self,
+        storage: &S,
+        server: F,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        S: AsyncTaskManager + Clone + Send + Sync + 'static,
+        F: std::future::Future<Output = Result<(), Box<dyn std::error::Error>>>,
+    {
+        let policy = self.config.retention.to_policy();
+        let cleanup = policy.is_enabled().then(|| {
+            tracing::info!("Starting task cleanup worker");
+            TaskCleanupWorker::new(storage.clone(), policy).spawn()
+        });
+
+        let result = server.await;
+
+        if let Some(cleanup) = cleanup {
+            cleanup.shutdown().await;
+        }
+        result
+    }
+
     /// Start HTTP server
-    async fn start_http_server<S>(&self, storage: S) -> Result<(), Box<dyn std::error::Error>>
+    async fn start_http_server<S>(
+        &self,
+        storage: S,
+        advertise_websocket: bool,
+    ) -> Result<(), Box<dyn std::error::Error>>
     where
         S: AsyncTaskManager + AsyncNotificationManager + Clone + Send + Sync + 'static,
     {
         // Create message handler with storage for history management
         let message_handler = ReimbursementHandler::new(storage.clone());
-        self.start_with_handler(message_handler, storage).await
+        self.start_with_handler(message_handler, storage, advertise_websocket)
+            .await
     }
 
     /// Start HTTP server with specific handler
@@ -111,15 +272,16 @@ impl ReimbursementServer {
         &self,
         message_handler: H,
         storage: S,
+        advertise_websocket: bool,
     ) -> Result<(), Box<dyn std::error::Error>>
     where
         S: AsyncTaskManager + AsyncNotificationManager + Clone + Send + Sync + 'static,
         H: a2a_rs::port::message_handler::AsyncMessageHandler + Clone + Send + Sync + 'static,
     {
         // Create agent info with reimbursement capabilities
-        let agent_info = SimpleAgentInfo::new(
+        let mut agent_info = SimpleAgentInfo::new(
             "Reimbursement Agent".to_string(),
-            format!("http://{}:{}", self.config.host, self.config.http_port),
+            format!("http://{}", self.config.http_bind_address()),
         )
         .with_description("An intelligent agent that handles employee reimbursement requests, from form generation to approval processing.".to_string())
         .with_provider(
@@ -148,31 +310,46 @@ impl ReimbursementServer {
             ]),
             Some(vec!["text".to_string(), "data".to_string()]),
             Some(vec!["text".to_string(), "data".to_string()]),
-        );
+        )
+        .with_skill_output_schema(PROCESS_REIMBURSEMENT_SKILL, reimbursement_result_schema());
+
+        // Let clients discover the WebSocket endpoint and auth scheme from the card
+        if advertise_websocket {
+            agent_info = agent_info.add_interface(
+                "WEBSOCKET".to_string(),
+                format!("ws://{}", self.config.ws_bind_address()),
+            );
+        }
+        if let AuthConfig::BearerToken { format, .. } = &self.config.auth {
+            agent_info = agent_info.with_security_scheme(
+                "bearer".to_string(),
+                SecurityScheme::Http {
+                    scheme: "bearer".to_string(),
+                    bearer_format: format.clone(),
+                    description: None,
+                },
+            );
+        }
 
         // Create processor with separate handlers and agent info
-        let processor = DefaultRequestProcessor::new(
+        let mut processor = DefaultRequestProcessor::new(
             message_handler,
             storage.clone(), // storage implements AsyncTaskManager
             storage,         // storage also implements AsyncNotificationManager
             agent_info.clone(),
-        );
+        )
+        .with_content_policy(Self::receipt_content_policy())
+        .with_middleware(self.middleware.clone());
+        if let Some(timeout) = self.config.processing_timeout.to_timeout() {
+            processor = processor.with_processing_timeout(timeout);
+        }
 
         // Create HTTP server
-        let bind_address = format!("{}:{}", self.config.host, self.config.http_port);
+        let bind_address = self.config.http_bind_address();
 
-        println!(
-            "🌐 Starting HTTP reimbursement server on {}:{}",
-            self.config.host, self.config.http_port
-        );
-        println!(
-            "📋 Agent card: http://{}:{}/agent-card",
-            self.config.host, self.config.http_port
-        );
-        println!(
-            "🛠️  Skills: http://{}:{}/skills",
-            self.config.host, self.config.http_port
-        );
+        println!("🌐 Starting HTTP reimbursement server on {}", bind_address);
+        println!("📋 Agent card: http://{}/agent-card", bind_address);
+        println!("🛠️  Skills: http://{}/skills", bind_address);
 
         match &self.config.storage {
             StorageConfig::InMemory => println!("💾 Storage: In-memory (non-persistent)"),
@@ -184,13 +361,18 @@ impl ReimbursementServer {
                 println!("🔓 Authentication: None (public access)");
 
                 // Create server without authentication
-                let server = HttpServer::new(processor, agent_info, bind_address);
+                let server =
+                    self.attach_task_logs(HttpServer::new(processor, agent_info, bind_address));
                 server
                     .start()
                     .await
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
             }
-            AuthConfig::BearerToken { tokens, format } => {
+            AuthConfig::BearerToken {
+                tokens,
+                format,
+                roles,
+            } => {
                 println!(
                     "🔐 Authentication: Bearer token ({} token(s){})",
                     tokens.len(),
@@ -200,9 +382,13 @@ impl ReimbursementServer {
                         .unwrap_or_default()
                 );
 
-                let authenticator = BearerTokenAuthenticator::new(tokens.clone());
-                let server =
+                let authenticator = Self::bearer_authenticator(tokens, roles);
+                let mut server =
                     HttpServer::with_auth(processor, agent_info, bind_address, authenticator);
+                if let Some(policy) = self.config.enforced_method_access() {
+                    server = server.with_method_access(policy.clone());
+                }
+                let server = self.attach_task_logs(server);
                 server
                     .start()
                     .await
@@ -212,6 +398,7 @@ impl ReimbursementServer {
                 keys,
                 location,
                 name,
+                ..
             } => {
                 println!(
                     "🔐 Authentication: API key ({} {}, {} key(s))",
@@ -222,7 +409,8 @@ impl ReimbursementServer {
                 println!("⚠️  API key authentication not yet supported, using no authentication");
 
                 // Create server without authentication
-                let server = HttpServer::new(processor, agent_info, bind_address);
+                let server =
+                    self.attach_task_logs(HttpServer::new(processor, agent_info, bind_address));
                 server
                     .start()
                     .await
@@ -233,21 +421,30 @@ impl ReimbursementServer {
 
     /// Start the WebSocket server
     pub async fn start_websocket(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.config.validate()?;
+        if self.config.sse_bridge {
+            self.spawn_sse_bridge();
+        }
         match &self.config.storage {
             StorageConfig::InMemory => {
                 let storage = self.create_in_memory_storage();
-                self.start_websocket_server(storage).await
+                self.with_cleanup(&storage, self.start_websocket_server(storage.clone()))
+                    .await
             }
             #[cfg(feature = "sqlx")]
-            StorageConfig::Sqlx {
-                url,
-                max_connections,
-                enable_logging,
-            } => {
-                let storage = self
-                    .create_sqlx_storage(url, *max_connections, *enable_logging)
-                    .await?;
-                self.start_websocket_server(storage).await
+            StorageConfig::Sqlx { .. } => {
+                let storage = self.create_sqlx_storage().await?;
+                match self.config.storage.cache_config() {
+                    Some(cache) => {
+                        let storage = CachedTaskStorage::new(storage).with_config(cache);
+                        self.with_cleanup(&storage, self.start_websocket_server(storage.clone()))
+                            .await
+                    }
+                    None => {
+                        self.with_cleanup(&storage, self.start_websocket_server(storage.clone()))
+                            .await
+                    }
+                }
             }
             #[cfg(not(feature = "sqlx"))]
             StorageConfig::Sqlx { .. } => {
@@ -273,7 +470,7 @@ impl ReimbursementServer {
         // Create agent info with reimbursement capabilities
         let agent_info = SimpleAgentInfo::new(
             "Reimbursement Agent".to_string(),
-            format!("ws://{}:{}", self.config.host, self.config.ws_port),
+            format!("ws://{}", self.config.ws_bind_address()),
         )
         .with_description("An intelligent agent that handles employee reimbursement requests, from form generation to approval processing.".to_string())
         .with_provider(
@@ -302,27 +499,30 @@ impl ReimbursementServer {
             ]),
             Some(vec!["text".to_string(), "data".to_string()]),
             Some(vec!["text".to_string(), "data".to_string()]),
-        );
+        )
+        .with_skill_output_schema(PROCESS_REIMBURSEMENT_SKILL, reimbursement_result_schema());
 
         // Create processor with separate handlers and agent info
-        let processor = DefaultRequestProcessor::new(
+        let mut processor = DefaultRequestProcessor::new(
             message_handler,
             storage.clone(), // storage implements AsyncTaskManager
             storage.clone(), // storage also implements AsyncNotificationManager
             agent_info.clone(),
-        );
+        )
+        .with_content_policy(Self::receipt_content_policy())
+        .with_middleware(self.middleware.clone());
+        if let Some(timeout) = self.config.processing_timeout.to_timeout() {
+            processor = processor.with_processing_timeout(timeout);
+        }
 
         // Create WebSocket server
-        let bind_address = format!("{}:{}", self.config.host, self.config.ws_port);
+        let bind_address = self.config.ws_bind_address();
 
         println!(
-            "🔌 Starting WebSocket reimbursement server on {}:{}",
-            self.config.host, self.config.ws_port
-        );
-        println!(
-            "📋 WebSocket URL: ws://{}:{}",
-            self.config.host, self.config.ws_port
+            "🔌 Starting WebSocket reimbursement server on {}",
+            bind_address
         );
+        println!("📋 WebSocket URL: ws://{}", bind_address);
 
         match &self.config.storage {
             StorageConfig::InMemory => println!("💾 Storage: In-memory (non-persistent)"),
@@ -341,7 +541,11 @@ impl ReimbursementServer {
                     .await
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
             }
-            AuthConfig::BearerToken { tokens, format } => {
+            AuthConfig::BearerToken {
+                tokens,
+                format,
+                roles,
+            } => {
                 println!(
                     "🔐 Authentication: Bearer token ({} token(s){})",
                     tokens.len(),
@@ -351,15 +555,18 @@ impl ReimbursementServer {
                         .unwrap_or_default()
                 );
 
-                let authenticator = BearerTokenAuthenticator::new(tokens.clone());
+                let authenticator = Self::bearer_authenticator(tokens, roles);
                 // Pass storage as the streaming handler (it implements AsyncStreamingHandler)
-                let server = WebSocketServer::with_auth(
+                let mut server = WebSocketServer::with_auth(
                     processor,
                     agent_info,
                     storage,
                     bind_address,
                     authenticator,
                 );
+                if let Some(policy) = self.config.enforced_method_access() {
+                    server = server.with_method_access(policy.clone());
+                }
                 server
                     .start()
                     .await
@@ -369,6 +576,7 @@ impl ReimbursementServer {
                 keys,
                 location,
                 name,
+                ..
             } => {
                 println!(
                     "🔐 Authentication: API key ({} {}, {} key(s))",
@@ -391,6 +599,7 @@ impl ReimbursementServer {
 
     /// Start both HTTP and WebSocket servers
     pub async fn start_all(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.config.validate()?;
         println!("🚀 Starting modern reimbursement agent...");
         println!("🔄 Starting both HTTP and WebSocket servers with SHARED storage");
 
@@ -400,22 +609,27 @@ impl ReimbursementServer {
                     "💾 Storage: In-memory (non-persistent) - SHARED between HTTP and WebSocket"
                 );
                 let storage = self.create_in_memory_storage();
-                self.start_both_with_storage(storage).await
+                self.with_cleanup(&storage, self.start_both_with_storage(storage.clone()))
+                    .await
             }
             #[cfg(feature = "sqlx")]
-            StorageConfig::Sqlx {
-                url,
-                max_connections,
-                enable_logging,
-            } => {
+            StorageConfig::Sqlx { url, .. } => {
                 println!(
                     "💾 Storage: SQLx ({}) - SHARED between HTTP and WebSocket",
                     url
                 );
-                let storage = self
-                    .create_sqlx_storage(url, *max_connections, *enable_logging)
-                    .await?;
-                self.start_both_with_storage(storage).await
+                let storage = self.create_sqlx_storage().await?;
+                match self.config.storage.cache_config() {
+                    Some(cache) => {
+                        let storage = CachedTaskStorage::new(storage).with_config(cache);
+                        self.with_cleanup(&storage, self.start_both_with_storage(storage.clone()))
+                            .await
+                    }
+                    None => {
+                        self.with_cleanup(&storage, self.start_both_with_storage(storage.clone()))
+                            .await
+                    }
+                }
             }
             #[cfg(not(feature = "sqlx"))]
             StorageConfig::Sqlx { .. } => {
@@ -442,11 +656,13 @@ impl ReimbursementServer {
         // Clone config for the server tasks
         let http_config = self.config.clone();
         let ws_config = self.config.clone();
+        let task_logs = self.task_logs.clone();
 
         // Start HTTP server in a separate task with shared storage
         let http_handle = tokio::spawn(async move {
-            let server = ReimbursementServer::from_config(http_config);
-            if let Err(e) = server.start_http_server(http_storage).await {
+            let mut server = ReimbursementServer::from_config(http_config);
+            server.task_logs = task_logs;
+            if let Err(e) = server.start_http_server(http_storage, true).await {
                 eprintln!("❌ HTTP server error: {}", e);
             }
         });
@@ -472,3 +688,220 @@ impl ReimbursementServer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use a2a_rs::{adapter::WebSocketClient, domain::Message, services::AsyncA2AClient};
+    use std::{sync::Arc, time::Duration};
+    use tokio::net::TcpStream;
+
+    async fn wait_until_reachable(address: &str) -> bool {
+        for _ in 0..50 {
+            if TcpStream::connect(address).await.is_ok() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_start_all_binds_listeners_to_their_own_hosts() {
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            http_host: None,
+            ws_host: Some("127.0.0.2".to_string()),
+            http_port: 8304,
+            ws_port: 8305,
+            storage: StorageConfig::InMemory,
+            auth: AuthConfig::None,
+            method_access: Default::default(),
+            retention: Default::default(),
+            processing_timeout: Default::default(),
+            default_page_size: 50,
+            max_page_size: 100,
+            debug: Default::default(),
+            sse_bridge: false,
+        };
+        let server = ReimbursementServer::from_config(config);
+
+        let checks = async {
+            assert!(wait_until_reachable("127.0.0.1:8304").await);
+            assert!(wait_until_reachable("127.0.0.2:8305").await);
+
+            // Each listener is bound only to its own interface
+            assert!(TcpStream::connect("127.0.0.2:8304").await.is_err());
+            assert!(TcpStream::connect("127.0.0.1:8305").await.is_err());
+
+            // The agent card advertises the HTTP listener's address
+            let card: serde_json::Value = reqwest::get("http://127.0.0.1:8304/agent-card")
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(card["url"], "http://127.0.0.1:8304");
+            assert_eq!(
+                card["additionalInterfaces"][0]["url"],
+                "ws://127.0.0.2:8305"
+            );
+        };
+
+        tokio::select! {
+            result = server.start_all() => panic!("servers stopped early: {:?}", result.err()),
+            _ = checks => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_websocket_only_server_bridges_sse() {
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            http_port: 8333,
+            ws_port: 8334,
+            sse_bridge: true,
+            ..Default::default()
+        };
+        assert_eq!(config.ws_upstream_url(), "ws://127.0.0.1:8334");
+        let server = ReimbursementServer::from_config(config);
+
+        let checks = async {
+            assert!(wait_until_reachable("127.0.0.1:8334").await);
+            assert!(wait_until_reachable("127.0.0.1:8333").await);
+
+            // Subscribe over HTTP before the task exists, then create it over
+            // the WebSocket
+            let mut response = reqwest::get("http://127.0.0.1:8333/tasks/bridged-trip/stream")
+                .await
+                .unwrap();
+            assert_eq!(response.headers()["content-type"], "text/event-stream");
+            tokio::spawn(async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                let client = WebSocketClient::new("ws://127.0.0.1:8334".to_string());
+                let message = Message::user_text(
+                    "Taxi to the airport, $30".to_string(),
+                    "msg-bridged".to_string(),
+                );
+                let _ = client
+                    .send_task_message("bridged-trip", &message, None, None)
+                    .await;
+            });
+
+            let mut body = String::new();
+            let bridged = tokio::time::timeout(Duration::from_secs(5), async {
+                while !body.contains("event: task-status") {
+                    let chunk = response.chunk().await.unwrap().expect("stream ended");
+                    body.push_str(&String::from_utf8_lossy(&chunk));
+                }
+            })
+            .await;
+            assert!(bridged.is_ok(), "no status update bridged: {:?}", body);
+            assert!(body.contains(r#""taskId":"bridged-trip""#), "{}", body);
+        };
+
+        tokio::select! {
+            result = server.start_websocket() => panic!("server stopped early: {:?}", result.err()),
+            _ = checks => {}
+        }
+    }
+
+    type AuditEntries = Arc<std::sync::Mutex<Vec<(String, Option<String>)>>>;
+
+    /// Records the method and caller of every request
+    #[derive(Clone, Default)]
+    struct AuditLog(AuditEntries);
+
+    #[async_trait::async_trait]
+    impl RequestMiddleware for AuditLog {
+        async fn before_dispatch(
+            &self,
+            request: &mut a2a_rs::services::ServerRequest,
+        ) -> Result<(), a2a_rs::domain::A2AError> {
+            let caller = request.principal.as_ref().map(|p| p.id.clone());
+            self.0
+                .lock()
+                .unwrap()
+                .push((request.method().to_string(), caller));
+            Ok(())
+        }
+    }
+
+    /// Refuses to cancel tasks
+    struct NoCancel;
+
+    #[async_trait::async_trait]
+    impl RequestMiddleware for NoCancel {
+        async fn before_dispatch(
+            &self,
+            request: &mut a2a_rs::services::ServerRequest,
+        ) -> Result<(), a2a_rs::domain::A2AError> {
+            if request.method() == "tasks/cancel" {
+                return Err(a2a_rs::domain::A2AError::UnsupportedOperation(
+                    "Cancellation is disabled".to_string(),
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_audits_and_rejects_requests() {
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            http_port: 8345,
+            ws_port: 8346,
+            auth: AuthConfig::BearerToken {
+                tokens: vec!["auditor-token".to_string()],
+                format: None,
+                roles: HashMap::new(),
+            },
+            ..Default::default()
+        };
+        let audit = AuditLog::default();
+        let server = ReimbursementServer::from_config(config)
+            .with_middleware(audit.clone())
+            .with_middleware(NoCancel);
+
+        let checks = async {
+            assert!(wait_until_reachable("127.0.0.1:8345").await);
+            let client = a2a_rs::HttpClient::with_auth(
+                "http://127.0.0.1:8345".to_string(),
+                "auditor-token".to_string(),
+            );
+            let message = Message::user_text(
+                "Lunch with a client, $45".to_string(),
+                "msg-audited".to_string(),
+            );
+            client
+                .send_task_message("audited-lunch", &message, None, None)
+                .await
+                .unwrap();
+
+            match client.cancel_task("audited-lunch").await.unwrap_err() {
+                a2a_rs::domain::A2AError::JsonRpc { code, .. } => {
+                    assert_eq!(code, a2a_rs::domain::error::UNSUPPORTED_OPERATION)
+                }
+                other => panic!("Expected the cancel to be refused, got {:?}", other),
+            }
+            let task = client.get_task("audited-lunch", None).await.unwrap();
+            assert_ne!(task.status.state, a2a_rs::domain::TaskState::Canceled);
+
+            let calls = audit.0.lock().unwrap().clone();
+            let caller = Some("auditor-token".to_string());
+            assert_eq!(
+                calls,
+                vec![
+                    ("tasks/send".to_string(), caller.clone()),
+                    ("tasks/cancel".to_string(), caller.clone()),
+                    ("tasks/get".to_string(), caller),
+                ]
+            );
+        };
+
+        tokio::select! {
+            result = server.start_http() => panic!("server stopped early: {:?}", result.err()),
+            _ = checks => {}
+        }
+    }
+}
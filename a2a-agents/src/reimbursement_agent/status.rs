@@ -0,0 +1,475 @@
+//! Admin status dashboard for the web frontend
+//!
+//! `GET /status` renders task counts by state, the most recently updated tasks,
+//! live subscriptions, the storage backend and push-delivery health.
+//! `GET /status/stream` sends the same figures as `status` SSE events so the
+//! page refreshes itself. Both routes require one of the admin tokens, either as
+//! a bearer token or as a `token` query parameter, since `EventSource` cannot
+//! set headers.
+
+use a2a_client::{WebA2AClient, components::TaskView};
+use a2a_rs::domain::{A2AError, ListTasksParams, TaskState};
+use askama::Template;
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{
+        Html, IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::Infallible,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tracing::warn;
+
+/// States shown on the dashboard, in display order
+const STATES: [TaskState; 9] = [
+    TaskState::Submitted,
+    TaskState::Working,
+    TaskState::InputRequired,
+    TaskState::AuthRequired,
+    TaskState::Completed,
+    TaskState::Canceled,
+    TaskState::Failed,
+    TaskState::Rejected,
+    TaskState::Unknown,
+];
+
+/// Number of recently updated tasks listed on the dashboard
+const RECENT_TASKS: i32 = 10;
+
+/// Counters the frontend keeps for the dashboard
+#[derive(Clone, Default)]
+pub struct StatusTracker {
+    inner: Arc<TrackerInner>,
+}
+
+#[derive(Default)]
+struct TrackerInner {
+    subscriptions: AtomicUsize,
+    push: Mutex<PushHealth>,
+}
+
+/// Push notifications received by the frontend's webhook
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PushHealth {
+    /// Authenticated deliveries
+    pub received: u64,
+    /// Deliveries refused for a missing or wrong token
+    pub rejected: u64,
+    /// Push configs the agent refused to register
+    pub registration_failures: u64,
+    pub last_received: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl PushHealth {
+    /// `idle` before any activity, `degraded` once anything failed
+    pub fn label(&self) -> &'static str {
+        if self.rejected > 0 || self.registration_failures > 0 {
+            "degraded"
+        } else if self.received > 0 {
+            "healthy"
+        } else {
+            "idle"
+        }
+    }
+}
+
+impl StatusTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count `stream` as an active subscription until it is dropped
+    pub fn track_subscription<S: Stream>(&self, stream: S) -> impl Stream<Item = S::Item> + use<S> {
+        self.inner.subscriptions.fetch_add(1, Ordering::Relaxed);
+        let guard = SubscriptionGuard(self.inner.clone());
+        stream.map(move |item| {
+            let _ = &guard;
+            item
+        })
+    }
+
+    /// Number of task update streams currently open
+    pub fn active_subscriptions(&self) -> usize {
+        self.inner.subscriptions.load(Ordering::Relaxed)
+    }
+
+    pub fn record_push_received(&self) {
+        let mut push = self.push();
+        push.received += 1;
+        push.last_received = Some(Utc::now());
+    }
+
+    pub fn record_push_rejected(&self, task_id: &str) {
+        let mut push = self.push();
+        push.rejected += 1;
+        push.last_error = Some(format!("Unauthorized delivery for task {}", task_id));
+    }
+
+    pub fn record_registration_failure(&self, error: &A2AError) {
+        let mut push = self.push();
+        push.registration_failures += 1;
+        push.last_error = Some(format!("Registration failed: {}", error));
+    }
+
+    pub fn push_health(&self) -> PushHealth {
+        self.push().clone()
+    }
+
+    fn push(&self) -> std::sync::MutexGuard<'_, PushHealth> {
+        self.inner
+            .push
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+struct SubscriptionGuard(Arc<TrackerInner>);
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        self.0.subscriptions.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Number of tasks in one state
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateCount {
+    pub state: String,
+    pub count: usize,
+}
+
+/// Figures shown on the dashboard
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusSnapshot {
+    pub generated_at: DateTime<Utc>,
+    pub total_tasks: usize,
+    pub counts: Vec<StateCount>,
+    pub recent: Vec<TaskView>,
+    pub active_subscriptions: usize,
+    pub storage_backend: String,
+    pub push: PushHealth,
+    pub push_label: String,
+}
+
+impl StatusSnapshot {
+    /// Count of tasks in `state`
+    pub fn count(&self, state: &TaskState) -> usize {
+        self.counts
+            .iter()
+            .find(|count| count.state == state.as_str())
+            .map(|count| count.count)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Template)]
+#[template(path = "status.html")]
+struct StatusTemplate {
+    snapshot: StatusSnapshot,
+    token: Option<String>,
+}
+
+/// Gather the dashboard figures from the agent and the tracker
+pub async fn collect_status(
+    client: &WebA2AClient,
+    tracker: &StatusTracker,
+    storage_backend: &str,
+) -> Result<StatusSnapshot, A2AError> {
+    let mut counts = Vec::with_capacity(STATES.len());
+    for state in STATES {
+        let params = ListTasksParams {
+            status: Some(state.clone()),
+            page_size: Some(1),
+            ..Default::default()
+        };
+        let result = client.list_tasks(&params).await?;
+        counts.push(StateCount {
+            state: state.to_string(),
+            count: result.total_size.max(0) as usize,
+        });
+    }
+
+    let params = ListTasksParams {
+        page_size: Some(RECENT_TASKS),
+        history_length: Some(1),
+        ..Default::default()
+    };
+    let recent = client.list_tasks(&params).await?;
+    let push = tracker.push_health();
+
+    Ok(StatusSnapshot {
+        generated_at: Utc::now(),
+        total_tasks: recent.total_size.max(0) as usize,
+        counts,
+        recent: recent.tasks.into_iter().map(TaskView::from_task).collect(),
+        active_subscriptions: tracker.active_subscriptions(),
+        storage_backend: storage_backend.to_string(),
+        push_label: push.label().to_string(),
+        push,
+    })
+}
+
+/// Render the dashboard page for `snapshot`
+pub fn render_status(snapshot: StatusSnapshot, token: Option<String>) -> askama::Result<String> {
+    StatusTemplate { snapshot, token }.render()
+}
+
+/// What the status routes need to build the dashboard
+#[derive(Clone)]
+pub struct StatusPage {
+    client: Arc<WebA2AClient>,
+    tracker: StatusTracker,
+    admin_tokens: Arc<Vec<String>>,
+    storage_backend: String,
+    refresh_interval: Duration,
+}
+
+impl StatusPage {
+    pub fn new(
+        client: Arc<WebA2AClient>,
+        tracker: StatusTracker,
+        admin_tokens: Vec<String>,
+    ) -> Self {
+        Self {
+            client,
+            tracker,
+            admin_tokens: Arc::new(admin_tokens),
+            storage_backend: "Unknown".to_string(),
+            refresh_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Describe the agent's storage, without credentials
+    pub fn with_storage_backend(mut self, storage_backend: String) -> Self {
+        self.storage_backend = storage_backend;
+        self
+    }
+
+    /// How often the stream sends fresh figures (default 5 seconds)
+    pub fn with_refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    fn authorized(&self, headers: &HeaderMap, query: &TokenQuery) -> bool {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        bearer
+            .or(query.token.as_deref())
+            .is_some_and(|token| self.admin_tokens.iter().any(|admin| admin == token))
+    }
+
+    async fn snapshot(&self) -> Result<StatusSnapshot, A2AError> {
+        collect_status(&self.client, &self.tracker, &self.storage_backend).await
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// Routes serving the dashboard, empty when no admin tokens are configured
+pub fn status_routes<S>(page: StatusPage) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if page.admin_tokens.is_empty() {
+        return Router::new();
+    }
+    Router::new()
+        .route("/status", get(status_page))
+        .route("/status/stream", get(status_stream))
+        .with_state(page)
+}
+
+async fn status_page(
+    State(page): State<StatusPage>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    if !page.authorized(&headers, &query) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let rendered = match page.snapshot().await {
+        Ok(snapshot) => render_status(snapshot, query.token),
+        Err(e) => {
+            warn!("Failed to collect status: {}", e);
+            return (StatusCode::BAD_GATEWAY, "Failed to reach the agent").into_response();
+        }
+    };
+    match rendered {
+        Ok(html) => Html(html).into_response(),
+        Err(e) => {
+            warn!("Failed to render status page: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn status_stream(
+    State(page): State<StatusPage>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    if !page.authorized(&headers, &query) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let events = futures::stream::unfold(page, |page| async move {
+        tokio::time::sleep(page.refresh_interval).await;
+        let event = match page.snapshot().await {
+            Ok(snapshot) => Event::default()
+                .event("status")
+                .json_data(&snapshot)
+                .unwrap_or_else(|_| Event::default().comment("unserializable status")),
+            Err(e) => Event::default().event("status-error").data(e.to_string()),
+        };
+        Some((Ok::<_, Infallible>(event), page))
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use a2a_rs::{
+        adapter::business::DefaultMessageHandler,
+        adapter::{DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, SimpleAgentInfo},
+        port::AsyncTaskManager,
+    };
+
+    async fn seed(storage: &InMemoryTaskStorage, task_id: &str, state: TaskState) {
+        storage.create_task(task_id, "ctx-status").await.unwrap();
+        if state != TaskState::Submitted {
+            storage
+                .update_task_status(task_id, state, None)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_page_renders_seeded_tasks() {
+        let storage = InMemoryTaskStorage::new();
+        seed(&storage, "expense-1", TaskState::Completed).await;
+        seed(&storage, "expense-2", TaskState::Completed).await;
+        seed(&storage, "expense-3", TaskState::Working).await;
+        seed(&storage, "expense-4", TaskState::Submitted).await;
+        seed(&storage, "expense-5", TaskState::Failed).await;
+
+        let agent_info = SimpleAgentInfo::new(
+            "Status Agent".to_string(),
+            "http://127.0.0.1:8336".to_string(),
+        );
+        let processor = DefaultRequestProcessor::new(
+            DefaultMessageHandler::new(storage.clone()),
+            storage.clone(),
+            storage.clone(),
+            agent_info.clone(),
+        );
+        let server = HttpServer::new(processor, agent_info, "127.0.0.1:8336".to_string());
+        tokio::spawn(async move { server.start().await });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let client = Arc::new(WebA2AClient::new_http("http://127.0.0.1:8336".to_string()));
+        let tracker = StatusTracker::new();
+        tracker.record_push_received();
+        let subscription = tracker.track_subscription(futures::stream::pending::<()>());
+
+        let snapshot = collect_status(&client, &tracker, "In-memory")
+            .await
+            .unwrap();
+        assert_eq!(snapshot.total_tasks, 5);
+        assert_eq!(snapshot.count(&TaskState::Completed), 2);
+        assert_eq!(snapshot.count(&TaskState::Working), 1);
+        assert_eq!(snapshot.count(&TaskState::Submitted), 1);
+        assert_eq!(snapshot.count(&TaskState::Failed), 1);
+        assert_eq!(snapshot.count(&TaskState::Canceled), 0);
+        assert_eq!(snapshot.recent.len(), 5);
+        assert_eq!(snapshot.active_subscriptions, 1);
+        assert_eq!(snapshot.push_label, "healthy");
+
+        let html = render_status(snapshot, None).unwrap();
+        assert!(html.contains(r#"<td id="count-completed">2</td>"#));
+        assert!(html.contains(r#"<td id="count-working">1</td>"#));
+        assert!(html.contains(r#"<span id="total-tasks">5</span>"#));
+        assert!(html.contains(r#"<span id="active-subscriptions">1</span>"#));
+        assert!(html.contains("In-memory"));
+        for task_id in ["expense-1", "expense-3", "expense-5"] {
+            assert!(html.contains(task_id), "missing {}", task_id);
+        }
+
+        drop(subscription);
+        assert_eq!(tracker.active_subscriptions(), 0);
+
+        // Served behind the admin token
+        let page = StatusPage::new(client, tracker, vec!["admin-token".to_string()])
+            .with_storage_backend("In-memory".to_string());
+        let app: Router = status_routes(page);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:8337")
+            .await
+            .unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let http = reqwest::Client::new();
+        let url = "http://127.0.0.1:8337/status";
+        let response = http.get(url).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        let response = http.get(url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let response = http
+            .get(url)
+            .bearer_auth("admin-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert!(
+            response
+                .text()
+                .await
+                .unwrap()
+                .contains(r#"<td id="count-completed">2</td>"#)
+        );
+        let response = http
+            .get(format!("{}?token=admin-token", url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+    }
+
+    #[test]
+    fn test_push_health_labels() {
+        let tracker = StatusTracker::new();
+        assert_eq!(tracker.push_health().label(), "idle");
+        tracker.record_push_received();
+        assert_eq!(tracker.push_health().label(), "healthy");
+        tracker.record_push_rejected("expense-1");
+        let push = tracker.push_health();
+        assert_eq!(push.label(), "degraded");
+        assert_eq!(push.rejected, 1);
+        assert!(push.last_error.unwrap().contains("expense-1"));
+    }
+}
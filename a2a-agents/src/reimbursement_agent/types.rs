@@ -1,5 +1,6 @@
+use a2a_rs::domain::{ErrorDetail, error_catalog::codes};
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde_json::{Map, Value, json};
 
 /// Standard reimbursement request structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,21 +67,45 @@ impl Money {
         }
     }
 
-    pub fn validate(&self) -> Result<(), String> {
-        match self {
-            Money::String(s) => {
-                // Basic validation for string format
-                if s.is_empty() {
-                    return Err("Amount cannot be empty".to_string());
-                }
-                Ok(())
+    /// Parse a user-entered amount such as `42.50`, `$1,200` or `USD 100.00`
+    pub fn parse(input: &str) -> Result<Self, ErrorDetail> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(ErrorDetail::field(codes::VALIDATION_REQUIRED, "amount"));
+        }
+
+        let (currency, number) = match input.split_once(char::is_whitespace) {
+            Some((code, rest))
+                if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) =>
+            {
+                (code.to_ascii_uppercase(), rest.trim())
             }
+            _ => (default_currency(), input),
+        };
+        let amount = number
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse::<f64>()
+            .map_err(|_| ErrorDetail::field(codes::VALIDATION_INVALID_NUMBER, "amount"))?;
+
+        let money = Money::Number { amount, currency };
+        money.validate()?;
+        Ok(money)
+    }
+
+    /// Check the amount is a positive number, reporting the failure as a coded error
+    pub fn validate(&self) -> Result<(), ErrorDetail> {
+        match self {
+            Money::String(s) => Money::parse(s).map(|_| ()),
             Money::Number { amount, .. } => {
-                if *amount <= 0.0 {
-                    return Err("Amount must be positive".to_string());
-                }
                 if amount.is_nan() || amount.is_infinite() {
-                    return Err("Amount must be a valid number".to_string());
+                    return Err(ErrorDetail::field(
+                        codes::VALIDATION_INVALID_NUMBER,
+                        "amount",
+                    ));
+                }
+                if *amount <= 0.0 {
+                    return Err(ErrorDetail::field(codes::VALIDATION_NOT_POSITIVE, "amount"));
                 }
                 Ok(())
             }
@@ -147,6 +172,38 @@ pub enum ReimbursementResponse {
     },
 }
 
+/// ID of the skill that processes reimbursement requests
+pub const PROCESS_REIMBURSEMENT_SKILL: &str = "process_reimbursement";
+
+/// JSON schema of the structured result attached to completed reimbursement tasks.
+///
+/// The result is the serialized [`ReimbursementResponse::Result`].
+pub fn reimbursement_result_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["type", "request_id", "status"],
+        "properties": {
+            "type": {"type": "string", "enum": ["result"]},
+            "request_id": {"type": "string"},
+            "status": {
+                "type": "string",
+                "enum": ["pending", "under_review", "approved", "rejected", "requires_additional_info"]
+            },
+            "message": {"type": "string"},
+            "details": {
+                "type": "object",
+                "properties": {
+                    "approved_amount": {"type": ["string", "object"]},
+                    "approval_date": {"type": "string"},
+                    "approver": {"type": "string"},
+                    "rejection_reason": {"type": "string"},
+                    "required_documents": {"type": "array", "items": {"type": "string"}}
+                }
+            }
+        }
+    })
+}
+
 /// Processing status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -294,3 +351,34 @@ impl Default for ValidationRules {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_parsing_uses_stable_codes() {
+        assert_eq!(
+            Money::parse("$1,250.50").unwrap().to_formatted_string(),
+            "$1250.50"
+        );
+        assert_eq!(
+            Money::parse("EUR 40").unwrap().to_formatted_string(),
+            "EUR 40.00"
+        );
+
+        let err = Money::parse("forty dollars").unwrap_err();
+        assert_eq!(err.error_code, codes::VALIDATION_INVALID_NUMBER);
+        assert_eq!(err.params["field"], "amount");
+        assert_eq!(err.message(), "Amount must be a number");
+
+        let err = Money::parse("  ").unwrap_err();
+        assert_eq!(err.message(), "Amount is required");
+
+        let err = Money::parse("-5").unwrap_err();
+        assert_eq!(err.error_code, codes::VALIDATION_NOT_POSITIVE);
+        assert_eq!(err.message(), "Amount must be greater than zero");
+
+        assert!(Money::String("USD 100.00".to_string()).validate().is_ok());
+    }
+}
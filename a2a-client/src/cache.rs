@@ -0,0 +1,296 @@
+//! A shared cache for values that are costly to fetch, such as agent cards
+//! and access tokens
+//!
+//! Entries live for their own TTL and the least recently used are evicted
+//! once the cache is full. Concurrent misses for one key wait on a single
+//! fetch instead of each fetching, and an entry close to expiry is refreshed
+//! in the background while its current value is still served.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Default number of entries a cache holds
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+/// Default share of an entry's TTL, at its end, during which it is refreshed
+/// in the background
+pub const DEFAULT_REFRESH_AHEAD: f64 = 0.2;
+
+/// Counters for tuning a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache, including those that waited on
+    /// another caller's fetch
+    pub hits: u64,
+    /// Lookups that had to fetch
+    pub misses: u64,
+    /// Background refreshes started before an entry expired
+    pub refreshes: u64,
+    /// Entries dropped to make room
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    fetched_at: Instant,
+    ttl: Duration,
+    last_used: u64,
+    refreshing: bool,
+}
+
+impl<V> Entry<V> {
+    fn is_fresh(&self) -> bool {
+        self.fetched_at.elapsed() < self.ttl
+    }
+
+    fn is_due_for_refresh(&self, refresh_ahead: f64) -> bool {
+        self.fetched_at.elapsed() >= self.ttl.mul_f64(1.0 - refresh_ahead)
+    }
+}
+
+struct State<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// One lock per key being fetched, so concurrent misses fetch once
+    flights: HashMap<K, Arc<tokio::sync::Mutex<()>>>,
+    /// Advanced on every use, ordering entries by recency
+    clock: u64,
+    /// Share of an entry's TTL, at its end, during which it is refreshed
+    refresh_ahead: f64,
+}
+
+struct Inner<K, V> {
+    capacity: usize,
+    state: Mutex<State<K, V>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    refreshes: AtomicU64,
+    evictions: AtomicU64,
+}
+
+/// A TTL and LRU cache shared by every clone of it.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use a2a_client::cache::SharedCache;
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let cache: SharedCache<String, String> = SharedCache::new(16);
+/// let card = cache
+///     .get_or_fetch("http://agent".to_string(), || async {
+///         Ok::<_, anyhow::Error>(("card".to_string(), Duration::from_secs(60)))
+///     })
+///     .await?;
+/// assert_eq!(card, "card");
+/// assert_eq!(cache.stats().misses, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SharedCache<K, V> {
+    inner: Arc<Inner<K, V>>,
+}
+
+impl<K, V> Clone for SharedCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> SharedCache<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Create a cache holding at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                capacity: capacity.max(1),
+                state: Mutex::new(State {
+                    entries: HashMap::new(),
+                    flights: HashMap::new(),
+                    clock: 0,
+                    refresh_ahead: DEFAULT_REFRESH_AHEAD,
+                }),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                refreshes: AtomicU64::new(0),
+                evictions: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Refresh entries in the background once `share` of their TTL is left,
+    /// between 0 (never) and 1
+    pub fn with_refresh_ahead(self, share: f64) -> Self {
+        self.inner.state.lock().unwrap().refresh_ahead = share.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The cached value for `key`, fetched with `fetch` if it is missing or
+    /// expired.
+    ///
+    /// `fetch` yields the value and how long it may be reused. While one
+    /// caller fetches a key, others asking for it wait and share the result;
+    /// if the fetch fails, the next of them fetches in turn. An entry in its
+    /// refresh window is returned as is while `fetch` runs in the background.
+    pub async fn get_or_fetch<F, Fut, E>(&self, key: K, fetch: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(V, Duration), E>> + Send + 'static,
+        E: Send + 'static,
+    {
+        let flight = {
+            let mut state = self.inner.state.lock().unwrap();
+            if let Some(value) = self.lookup(&mut state, &key) {
+                let refresh_ahead = state.refresh_ahead;
+                let refresh = state.entries.get_mut(&key).is_some_and(|entry| {
+                    let due = !entry.refreshing && entry.is_due_for_refresh(refresh_ahead);
+                    entry.refreshing |= due;
+                    due
+                });
+                drop(state);
+                if refresh {
+                    self.refresh_in_background(key, fetch);
+                }
+                return Ok(value);
+            }
+            state.flights.entry(key.clone()).or_default().clone()
+        };
+
+        let _flight = flight.lock().await;
+        // Another caller may have fetched it while this one waited
+        if let Some(value) = self.lookup(&mut self.inner.state.lock().unwrap(), &key) {
+            return Ok(value);
+        }
+
+        self.inner.misses.fetch_add(1, Ordering::Relaxed);
+        let fetched = fetch().await;
+        let mut state = self.inner.state.lock().unwrap();
+        state.flights.remove(&key);
+        let (value, ttl) = fetched?;
+        self.store(&mut state, key, value.clone(), ttl);
+        Ok(value)
+    }
+
+    /// Store `value` for `key`, replacing any cached one
+    pub fn insert(&self, key: K, value: V, ttl: Duration) {
+        let mut state = self.inner.state.lock().unwrap();
+        self.store(&mut state, key, value, ttl);
+    }
+
+    /// Drop the entry for `key`, so the next lookup fetches it
+    pub fn remove(&self, key: &K) {
+        self.inner.state.lock().unwrap().entries.remove(key);
+    }
+
+    /// Number of entries held, including expired ones not yet dropped
+    pub fn len(&self) -> usize {
+        self.inner.state.lock().unwrap().entries.len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hits, misses, refreshes and evictions so far
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            refreshes: self.inner.refreshes.load(Ordering::Relaxed),
+            evictions: self.inner.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The fresh value for `key`, counted as a hit and marked as used
+    fn lookup(&self, state: &mut State<K, V>, key: &K) -> Option<V> {
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state
+            .entries
+            .get_mut(key)
+            .filter(|entry| entry.is_fresh())?;
+        entry.last_used = clock;
+        self.inner.hits.fetch_add(1, Ordering::Relaxed);
+        Some(entry.value.clone())
+    }
+
+    fn store(&self, state: &mut State<K, V>, key: K, value: V, ttl: Duration) {
+        state.clock += 1;
+        let entry = Entry {
+            value,
+            fetched_at: Instant::now(),
+            ttl,
+            last_used: state.clock,
+            refreshing: false,
+        };
+        if state.entries.insert(key, entry).is_some() {
+            return;
+        }
+
+        // Expired entries go first, then the least recently used
+        state.entries.retain(|_, entry| entry.is_fresh());
+        while state.entries.len() > self.inner.capacity {
+            let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            state.entries.remove(&oldest);
+            self.inner.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn refresh_in_background<F, Fut, E>(&self, key: K, fetch: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(V, Duration), E>> + Send + 'static,
+        E: Send + 'static,
+    {
+        self.inner.refreshes.fetch_add(1, Ordering::Relaxed);
+        let cache = self.clone();
+        tokio::spawn(async move {
+            let fetched = fetch().await;
+            let mut state = cache.inner.state.lock().unwrap();
+            match fetched {
+                Ok((value, ttl)) => cache.store(&mut state, key, value, ttl),
+                // Keep serving the current value until it expires, and try
+                // again on the next lookup
+                Err(_) => {
+                    if let Some(entry) = state.entries.get_mut(&key) {
+                        entry.refreshing = false;
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,242 @@
+//! Streaming file artifacts to the browser in chunks
+//!
+//! A file artifact can be large, and an agent may produce it over several
+//! artifact updates (`append` for every update after the first, `lastChunk`
+//! on the final one). Rather than sending each update whole, the SSE stream
+//! splits the file's bytes into [`FileChunk`]s sent as [`FILE_CHUNK_EVENT`]
+//! events, so the browser can start receiving a file before the agent has
+//! finished producing it.
+//!
+//! Chunks of an artifact are numbered from 0, and the last one is marked
+//! `final`. Each carries whole bytes in its own base64 string, so chunks can
+//! be decoded one at a time. [`FileAssembler`] puts them back together.
+
+use std::{collections::HashMap, fmt};
+
+use a2a_rs::domain::{Part, TaskArtifactUpdateEvent};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// SSE event name for file chunks
+pub const FILE_CHUNK_EVENT: &str = "artifact-chunk";
+
+/// Bytes of the file in each chunk; a multiple of 3, so no chunk ends in
+/// base64 padding
+pub const DEFAULT_CHUNK_SIZE: usize = 48 * 1024;
+
+/// Largest file [`FileAssembler::default`] reassembles
+pub const DEFAULT_MAX_FILE_SIZE: usize = 25 * 1024 * 1024;
+
+/// One piece of a file artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChunk {
+    pub task_id: String,
+    pub artifact_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Position of the chunk within the file, starting at 0
+    pub index: u64,
+    /// Base64 encoded bytes of this chunk
+    pub data: String,
+    /// Whether this is the file's last chunk
+    #[serde(rename = "final")]
+    pub final_: bool,
+}
+
+/// Splits file artifact updates into chunks, numbering them across the
+/// updates of each artifact
+#[derive(Debug)]
+pub struct FileChunker {
+    chunk_size: usize,
+    /// Index of the next chunk of each artifact still being streamed
+    next_index: HashMap<String, u64>,
+}
+
+impl Default for FileChunker {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHUNK_SIZE)
+    }
+}
+
+impl FileChunker {
+    /// Create a chunker putting `chunk_size` bytes of the file in each chunk
+    pub fn new(chunk_size: usize) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            next_index: HashMap::new(),
+        }
+    }
+
+    /// The chunks for an artifact update, or `None` when it should be sent
+    /// as a whole: anything but a single file part with inline bytes.
+    ///
+    /// An update without `append` starts the artifact over at index 0.
+    pub fn chunks(&mut self, update: &TaskArtifactUpdateEvent) -> Option<Vec<FileChunk>> {
+        let [Part::File { file, .. }] = update.artifact.parts.as_slice() else {
+            return None;
+        };
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(file.bytes.as_deref()?)
+            .ok()?;
+
+        let artifact_id = &update.artifact.artifact_id;
+        let mut index = match update.append {
+            Some(true) => self.next_index.get(artifact_id).copied().unwrap_or(0),
+            _ => 0,
+        };
+        let last_update = update.last_chunk != Some(false);
+
+        let pieces: Vec<&[u8]> = if bytes.is_empty() {
+            vec![&[]]
+        } else {
+            bytes.chunks(self.chunk_size).collect()
+        };
+        let count = pieces.len();
+        let chunks = pieces
+            .into_iter()
+            .enumerate()
+            .map(|(position, piece)| {
+                let chunk = FileChunk {
+                    task_id: update.task_id.clone(),
+                    artifact_id: artifact_id.clone(),
+                    name: file.name.clone(),
+                    mime_type: file.mime_type.clone(),
+                    index,
+                    data: base64::engine::general_purpose::STANDARD.encode(piece),
+                    final_: last_update && position + 1 == count,
+                };
+                index += 1;
+                chunk
+            })
+            .collect();
+
+        if last_update {
+            self.next_index.remove(artifact_id);
+        } else {
+            self.next_index.insert(artifact_id.clone(), index);
+        }
+        Some(chunks)
+    }
+}
+
+/// A file put back together from its chunks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembledFile {
+    pub artifact_id: String,
+    pub name: Option<String>,
+    pub mime_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// Why a chunk could not be added to its file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkError {
+    /// A chunk was missed or repeated
+    OutOfOrder { expected: u64, received: u64 },
+    /// The file grew past the assembler's limit
+    TooLarge { limit: usize },
+    /// The chunk's data is not valid base64
+    InvalidData,
+}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkError::OutOfOrder { expected, received } => {
+                write!(f, "Expected chunk {} but got {}", expected, received)
+            }
+            ChunkError::TooLarge { limit } => write!(f, "File exceeds {} bytes", limit),
+            ChunkError::InvalidData => write!(f, "Chunk data is not valid base64"),
+        }
+    }
+}
+
+impl std::error::Error for ChunkError {}
+
+/// Reassembles files from their chunks, refusing files over a size limit.
+///
+/// A failed chunk discards what was received of its file; the next chunk
+/// with index 0 starts it again.
+#[derive(Debug)]
+pub struct FileAssembler {
+    max_size: usize,
+    /// Files still receiving chunks, with the index each expects next
+    partial: HashMap<String, (u64, AssembledFile)>,
+}
+
+impl Default for FileAssembler {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FILE_SIZE)
+    }
+}
+
+impl FileAssembler {
+    /// Create an assembler refusing files larger than `max_size` bytes
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            partial: HashMap::new(),
+        }
+    }
+
+    /// Add a chunk, returning the whole file once its final chunk arrives
+    pub fn push(&mut self, chunk: &FileChunk) -> Result<Option<AssembledFile>, ChunkError> {
+        let result = self.append(chunk);
+        if result.is_err() {
+            self.partial.remove(&chunk.artifact_id);
+        }
+        result
+    }
+
+    fn append(&mut self, chunk: &FileChunk) -> Result<Option<AssembledFile>, ChunkError> {
+        if chunk.index == 0 {
+            self.partial.insert(
+                chunk.artifact_id.clone(),
+                (
+                    0,
+                    AssembledFile {
+                        artifact_id: chunk.artifact_id.clone(),
+                        name: chunk.name.clone(),
+                        mime_type: chunk.mime_type.clone(),
+                        bytes: Vec::new(),
+                    },
+                ),
+            );
+        }
+        let Some((expected, file)) = self.partial.get_mut(&chunk.artifact_id) else {
+            return Err(ChunkError::OutOfOrder {
+                expected: 0,
+                received: chunk.index,
+            });
+        };
+        if chunk.index != *expected {
+            return Err(ChunkError::OutOfOrder {
+                expected: *expected,
+                received: chunk.index,
+            });
+        }
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&chunk.data)
+            .map_err(|_| ChunkError::InvalidData)?;
+        if file.bytes.len() + bytes.len() > self.max_size {
+            return Err(ChunkError::TooLarge {
+                limit: self.max_size,
+            });
+        }
+        file.bytes.extend_from_slice(&bytes);
+        *expected += 1;
+
+        if chunk.final_ {
+            Ok(self
+                .partial
+                .remove(&chunk.artifact_id)
+                .map(|(_, file)| file))
+        } else {
+            Ok(None)
+        }
+    }
+}
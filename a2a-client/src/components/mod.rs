@@ -1,7 +1,15 @@
 //! Reusable web components for A2A interfaces
 
+pub mod file_chunks;
 pub mod streaming;
 pub mod task_viewer;
+pub mod uploads;
 
-pub use streaming::create_sse_stream;
+pub use file_chunks::{
+    AssembledFile, ChunkError, FILE_CHUNK_EVENT, FileAssembler, FileChunk, FileChunker,
+};
+pub use streaming::{
+    create_sse_stream, create_task_list_sse_stream, task_list_stream, task_update_stream,
+};
 pub use task_viewer::{MessageView, TaskView};
+pub use uploads::{UploadConfig, UploadError, UploadStatus, UploadStore, upload_routes};
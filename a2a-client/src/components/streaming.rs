@@ -1,17 +1,23 @@
 //! Server-Sent Events (SSE) streaming components
 
-use a2a_rs::services::{AsyncA2AClient, StreamItem};
+use a2a_rs::{
+    domain::{ListTasksParams, ListTasksStreamItem},
+    services::{AsyncA2AClient, StreamItem},
+};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use futures::StreamExt;
 use std::{convert::Infallible, sync::Arc, time::Duration};
 use tracing::{error, info, warn};
 
+use super::file_chunks::{FILE_CHUNK_EVENT, FileChunker};
 use crate::WebA2AClient;
 
 /// Create an SSE stream for task updates
 ///
 /// This function handles:
-/// - WebSocket streaming if available
+/// - WebSocket streaming if available, resyncing after missed events
+/// - One WebSocket subscription per task, shared by every tab following it
+/// - File artifacts split into [`FILE_CHUNK_EVENT`] events
 /// - Fallback to HTTP polling
 /// - Automatic retry logic
 /// - Serialization to JSON events
@@ -19,61 +25,127 @@ pub fn create_sse_stream(
     client: Arc<WebA2AClient>,
     task_id: String,
 ) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
-    let stream = async_stream::stream! {
+    Sse::new(task_update_stream(client, task_id)).keep_alive(KeepAlive::default())
+}
+
+/// Create an SSE stream of a task listing.
+///
+/// Each listed task is sent as a `task` event as soon as it arrives and the
+/// listing's counts as a final `summary` event, so the tasks view can render
+/// its first rows before the page is complete. A failure is sent as a final
+/// `error` event.
+pub fn create_task_list_sse_stream(
+    client: Arc<WebA2AClient>,
+    params: ListTasksParams,
+) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
+    Sse::new(task_list_stream(client, params)).keep_alive(KeepAlive::default())
+}
+
+/// The events behind [`create_task_list_sse_stream`]
+pub fn task_list_stream(
+    client: Arc<WebA2AClient>,
+    params: ListTasksParams,
+) -> impl futures::Stream<Item = Result<Event, Infallible>> {
+    async_stream::stream! {
+        let mut items = match client.list_tasks_stream(&params).await {
+            Ok(items) => items,
+            Err(e) => {
+                warn!("Failed to list tasks: {}", e);
+                yield Ok(Event::default().event("error").data(e.to_string()));
+                return;
+            }
+        };
+        while let Some(item) = items.next().await {
+            let event = match item {
+                Ok(ListTasksStreamItem::Task(task)) => Event::default().event("task").json_data(task),
+                Ok(ListTasksStreamItem::Summary(summary)) => {
+                    Event::default().event("summary").json_data(summary)
+                }
+                Err(e) => {
+                    warn!("Task listing failed: {}", e);
+                    yield Ok(Event::default().event("error").data(e.to_string()));
+                    return;
+                }
+            };
+            match event {
+                Ok(event) => yield Ok(event),
+                Err(e) => error!("Failed to serialize listed item: {}", e),
+            }
+        }
+    }
+}
+
+/// The events behind [`create_sse_stream`], for callers that wrap the stream
+/// before turning it into a response
+pub fn task_update_stream(
+    client: Arc<WebA2AClient>,
+    task_id: String,
+) -> impl futures::Stream<Item = Result<Event, Infallible>> {
+    async_stream::stream! {
         // Check if we have a WebSocket client
-        if let Some(ws_client) = client.websocket() {
+        // Only subscribe when the agent can stream; otherwise poll
+        if client.has_websocket() && client.supports_streaming() {
             info!("Attempting to subscribe to task {} via WebSocket", task_id);
 
             let mut retry_count = 0;
             let max_retries = 60; // 60 retries with 1 second delay = 1 minute
+            // File artifacts are sent in chunks so large files start arriving early
+            let mut chunker = FileChunker::default();
 
             loop {
-                match ws_client.subscribe_to_task(&task_id, Some(50)).await {
-                    Ok(mut event_stream) => {
+                // Tabs following the task share one subscription to it
+                match client.subscribe_shared(&task_id, Some(50)) {
+                    Ok(event_stream) => {
+                        let mut event_stream = std::pin::pin!(event_stream);
                         info!("Successfully subscribed to task {} via WebSocket", task_id);
 
-                        while let Some(result) = event_stream.next().await {
-                            match result {
-                                Ok(stream_item) => {
-                                    let (event_type, event_data) = match &stream_item {
-                                        StreamItem::Task(task) => {
-                                            match serde_json::to_string(task) {
-                                                Ok(json) => ("task-update", json),
-                                                Err(e) => {
-                                                    error!("Failed to serialize task: {}", e);
-                                                    continue;
-                                                }
-                                            }
+                        while let Some(stream_item) = event_stream.next().await {
+                            let (event_type, event_data) = match &stream_item {
+                                StreamItem::Task(task) => {
+                                    match serde_json::to_string(task) {
+                                        Ok(json) => ("task-update", json),
+                                        Err(e) => {
+                                            error!("Failed to serialize task: {}", e);
+                                            continue;
                                         }
-                                        StreamItem::StatusUpdate(status) => {
-                                            match serde_json::to_string(status) {
-                                                Ok(json) => ("task-status", json),
-                                                Err(e) => {
-                                                    error!("Failed to serialize status: {}", e);
-                                                    continue;
-                                                }
-                                            }
+                                    }
+                                }
+                                StreamItem::StatusUpdate(status) => {
+                                    match serde_json::to_string(status) {
+                                        Ok(json) => ("task-status", json),
+                                        Err(e) => {
+                                            error!("Failed to serialize status: {}", e);
+                                            continue;
                                         }
-                                        StreamItem::ArtifactUpdate(artifact) => {
-                                            match serde_json::to_string(artifact) {
-                                                Ok(json) => ("artifact", json),
+                                    }
+                                }
+                                StreamItem::ArtifactUpdate(artifact) => {
+                                    if let Some(chunks) = chunker.chunks(artifact) {
+                                        for chunk in chunks {
+                                            match serde_json::to_string(&chunk) {
+                                                Ok(json) => yield Ok(Event::default()
+                                                    .event(FILE_CHUNK_EVENT)
+                                                    .data(json)),
                                                 Err(e) => {
-                                                    error!("Failed to serialize artifact: {}", e);
-                                                    continue;
+                                                    error!("Failed to serialize file chunk: {}", e);
                                                 }
                                             }
                                         }
-                                    };
-
-                                    yield Ok(Event::default()
-                                        .event(event_type)
-                                        .data(event_data));
-                                }
-                                Err(e) => {
-                                    warn!("Stream error (continuing): {}", e);
-                                    continue;
+                                        continue;
+                                    }
+                                    match serde_json::to_string(artifact) {
+                                        Ok(json) => ("artifact", json),
+                                        Err(e) => {
+                                            error!("Failed to serialize artifact: {}", e);
+                                            continue;
+                                        }
+                                    }
                                 }
-                            }
+                            };
+
+                            yield Ok(Event::default()
+                                .event(event_type)
+                                .data(event_data));
                         }
                         break;
                     }
@@ -141,7 +213,5 @@ pub fn create_sse_stream(
                 tokio::time::sleep(Duration::from_secs(2)).await;
             }
         }
-    };
-
-    Sse::new(stream).keep_alive(KeepAlive::default())
+    }
 }
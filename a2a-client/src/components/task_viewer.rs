@@ -7,9 +7,14 @@ use serde::Serialize;
 #[derive(Debug, Serialize, Clone)]
 pub struct TaskView {
     pub task_id: String,
+    /// Wire name of the task's state, e.g. `input-required`
     pub state: String,
     pub message_count: usize,
     pub last_message_preview: Option<String>,
+    /// Structured result of a completed task
+    pub result: Option<serde_json::Value>,
+    /// Tags for rendering as chips, in sorted order
+    pub tags: Vec<String>,
 }
 
 impl TaskView {
@@ -29,9 +34,11 @@ impl TaskView {
 
         Self {
             task_id: task.id,
-            state: format!("{:?}", task.status.state),
+            state: task.status.state.to_string(),
             message_count,
             last_message_preview,
+            result: task.result.map(|result| result.data),
+            tags: task.tags,
         }
     }
 }
@@ -64,6 +71,7 @@ impl MessageView {
                         .unwrap_or("unnamed");
                     format!("[Data: {}]", name)
                 }
+                MessagePart::Unknown(_) => format!("[{} part]", part.kind()),
             })
             .collect::<Vec<_>>()
             .join("\n");
@@ -75,7 +83,10 @@ impl MessageView {
         }
     }
 
-    /// Create a MessageView with JSON parsing for structured responses
+    /// Create a MessageView with JSON parsing for structured responses.
+    ///
+    /// Prefer [`TaskView::result`] for reading task outcomes; this only extracts
+    /// JSON embedded in message text for display.
     pub fn from_message_with_json_parsing(msg: a2a_rs::domain::Message) -> Self {
         let content = msg
             .parts
@@ -0,0 +1,381 @@
+//! Resumable file uploads for agent frontends
+//!
+//! Large files are sent in chunks so a dropped connection only costs the chunk
+//! in flight:
+//!
+//! 1. `POST /uploads` with `{"fileName", "mimeType", "totalSize"}` returns an
+//!    upload id and the current offset (0).
+//! 2. `PUT /uploads/{id}?offset=N` appends the request body at byte `N`. Bytes
+//!    are stored as they arrive, so an interrupted chunk keeps what was received.
+//! 3. `GET /uploads/{id}` reports the current offset; after a drop the client
+//!    resumes from there.
+//! 4. Once every byte is in, [`UploadStore::finalize`] assembles the file into a
+//!    [`FileContent`] to attach to a message.
+//!
+//! Uploads untouched for longer than [`UploadConfig::ttl`] expire.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use a2a_rs::domain::FileContent;
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use base64::Engine;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Limits applied to resumable uploads
+#[derive(Debug, Clone)]
+pub struct UploadConfig {
+    /// Largest file accepted, in bytes
+    pub max_total_size: u64,
+    /// How long an upload may sit idle before it expires
+    pub ttl: Duration,
+}
+
+impl Default for UploadConfig {
+    fn default() -> Self {
+        Self {
+            max_total_size: 25 << 20,
+            ttl: Duration::from_secs(3600),
+        }
+    }
+}
+
+impl UploadConfig {
+    /// Create a config with a 25 MiB limit and a one hour idle timeout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the largest accepted file in bytes
+    pub fn with_max_total_size(mut self, bytes: u64) -> Self {
+        self.max_total_size = bytes;
+        self
+    }
+
+    /// Set how long an idle upload is kept
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+/// Progress of an upload, as reported to the client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadStatus {
+    pub upload_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Bytes received so far; the next chunk starts here
+    pub offset: u64,
+    pub total_size: u64,
+}
+
+impl UploadStatus {
+    /// Whether every byte has been received
+    pub fn is_complete(&self) -> bool {
+        self.offset == self.total_size
+    }
+}
+
+/// Why an upload operation was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadError {
+    /// No upload with this id exists, or it expired
+    NotFound(String),
+    /// The file is larger than allowed, or a chunk runs past the declared size
+    TooLarge { limit: u64 },
+    /// A chunk didn't start where the previous one ended
+    OffsetMismatch { expected: u64 },
+    /// The upload was finalized before every byte arrived
+    Incomplete { received: u64, total: u64 },
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::NotFound(id) => write!(f, "Upload {} not found or expired", id),
+            UploadError::TooLarge { limit } => write!(f, "Upload exceeds {} bytes", limit),
+            UploadError::OffsetMismatch { expected } => {
+                write!(f, "Chunk must start at offset {}", expected)
+            }
+            UploadError::Incomplete { received, total } => {
+                write!(
+                    f,
+                    "Upload incomplete: {} of {} bytes received",
+                    received, total
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+impl UploadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            UploadError::NotFound(_) => StatusCode::NOT_FOUND,
+            UploadError::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            UploadError::OffsetMismatch { .. } | UploadError::Incomplete { .. } => {
+                StatusCode::CONFLICT
+            }
+        }
+    }
+}
+
+impl IntoResponse for UploadError {
+    fn into_response(self) -> Response {
+        let mut body = serde_json::json!({ "error": self.to_string() });
+        if let UploadError::OffsetMismatch { expected } = &self {
+            body["offset"] = (*expected).into();
+        }
+        (self.status_code(), Json(body)).into_response()
+    }
+}
+
+struct Upload {
+    file_name: Option<String>,
+    mime_type: Option<String>,
+    total_size: u64,
+    data: Vec<u8>,
+    last_activity: Instant,
+}
+
+impl Upload {
+    fn status(&self, upload_id: &str) -> UploadStatus {
+        UploadStatus {
+            upload_id: upload_id.to_string(),
+            file_name: self.file_name.clone(),
+            mime_type: self.mime_type.clone(),
+            offset: self.data.len() as u64,
+            total_size: self.total_size,
+        }
+    }
+}
+
+/// In-memory store of uploads in progress, shared by the upload routes and the
+/// handlers that attach finished files to messages
+#[derive(Clone, Default)]
+pub struct UploadStore {
+    config: UploadConfig,
+    uploads: Arc<Mutex<HashMap<String, Upload>>>,
+}
+
+impl UploadStore {
+    /// Create a store enforcing `config`
+    pub fn new(config: UploadConfig) -> Self {
+        Self {
+            config,
+            uploads: Arc::default(),
+        }
+    }
+
+    /// Start an upload of `total_size` bytes
+    pub fn initiate(
+        &self,
+        file_name: Option<String>,
+        mime_type: Option<String>,
+        total_size: u64,
+    ) -> Result<UploadStatus, UploadError> {
+        if total_size > self.config.max_total_size {
+            return Err(UploadError::TooLarge {
+                limit: self.config.max_total_size,
+            });
+        }
+        self.purge_expired();
+
+        let upload_id = uuid::Uuid::new_v4().to_string();
+        let upload = Upload {
+            file_name,
+            mime_type,
+            total_size,
+            data: Vec::new(),
+            last_activity: Instant::now(),
+        };
+        let status = upload.status(&upload_id);
+        self.uploads
+            .lock()
+            .unwrap()
+            .insert(upload_id.clone(), upload);
+        info!("Started upload {} of {} bytes", upload_id, total_size);
+        Ok(status)
+    }
+
+    /// Append `chunk` at `offset`, which must equal the bytes received so far
+    pub fn append(
+        &self,
+        upload_id: &str,
+        offset: u64,
+        chunk: &[u8],
+    ) -> Result<UploadStatus, UploadError> {
+        self.with_upload(upload_id, |upload| {
+            let received = upload.data.len() as u64;
+            if offset != received {
+                return Err(UploadError::OffsetMismatch { expected: received });
+            }
+            if received + chunk.len() as u64 > upload.total_size {
+                return Err(UploadError::TooLarge {
+                    limit: upload.total_size,
+                });
+            }
+            upload.data.extend_from_slice(chunk);
+            Ok(upload.status(upload_id))
+        })
+    }
+
+    /// Current progress of an upload
+    pub fn status(&self, upload_id: &str) -> Result<UploadStatus, UploadError> {
+        self.with_upload(upload_id, |upload| Ok(upload.status(upload_id)))
+    }
+
+    /// Assemble a complete upload into file content and remove it from the store
+    pub fn finalize(&self, upload_id: &str) -> Result<FileContent, UploadError> {
+        let mut uploads = self.uploads.lock().unwrap();
+        let upload = match uploads.get(upload_id) {
+            Some(upload) if !self.is_expired(upload) => upload,
+            _ => return Err(UploadError::NotFound(upload_id.to_string())),
+        };
+        let received = upload.data.len() as u64;
+        if received != upload.total_size {
+            return Err(UploadError::Incomplete {
+                received,
+                total: upload.total_size,
+            });
+        }
+
+        let upload = uploads.remove(upload_id).unwrap();
+        info!("Finalized upload {} ({} bytes)", upload_id, received);
+        Ok(FileContent {
+            name: upload.file_name,
+            mime_type: upload.mime_type,
+            bytes: Some(base64::engine::general_purpose::STANDARD.encode(&upload.data)),
+            uri: None,
+        })
+    }
+
+    /// Drop uploads idle for longer than the TTL, returning how many were removed
+    pub fn purge_expired(&self) -> usize {
+        let mut uploads = self.uploads.lock().unwrap();
+        let before = uploads.len();
+        uploads.retain(|_, upload| !self.is_expired(upload));
+        let purged = before - uploads.len();
+        if purged > 0 {
+            warn!("Expired {} incomplete uploads", purged);
+        }
+        purged
+    }
+
+    fn is_expired(&self, upload: &Upload) -> bool {
+        upload.last_activity.elapsed() > self.config.ttl
+    }
+
+    fn with_upload<T>(
+        &self,
+        upload_id: &str,
+        f: impl FnOnce(&mut Upload) -> Result<T, UploadError>,
+    ) -> Result<T, UploadError> {
+        let mut uploads = self.uploads.lock().unwrap();
+        match uploads.get_mut(upload_id) {
+            Some(upload) if !self.is_expired(upload) => {
+                upload.last_activity = Instant::now();
+                f(upload)
+            }
+            Some(_) => {
+                uploads.remove(upload_id);
+                Err(UploadError::NotFound(upload_id.to_string()))
+            }
+            None => Err(UploadError::NotFound(upload_id.to_string())),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitiateRequest {
+    file_name: Option<String>,
+    mime_type: Option<String>,
+    total_size: u64,
+}
+
+#[derive(Deserialize)]
+struct ChunkQuery {
+    offset: u64,
+}
+
+/// Routes implementing the upload protocol, to be merged into a frontend router
+pub fn upload_routes<S>(store: UploadStore) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/uploads", post(initiate_upload))
+        .route("/uploads/:upload_id", get(upload_status).put(upload_chunk))
+        .with_state(store)
+}
+
+async fn initiate_upload(
+    State(store): State<UploadStore>,
+    Json(request): Json<InitiateRequest>,
+) -> Result<(StatusCode, Json<UploadStatus>), UploadError> {
+    let status = store.initiate(request.file_name, request.mime_type, request.total_size)?;
+    Ok((StatusCode::CREATED, Json(status)))
+}
+
+async fn upload_status(
+    State(store): State<UploadStore>,
+    Path(upload_id): Path<String>,
+) -> Result<Json<UploadStatus>, UploadError> {
+    store.status(&upload_id).map(Json)
+}
+
+async fn upload_chunk(
+    State(store): State<UploadStore>,
+    Path(upload_id): Path<String>,
+    Query(query): Query<ChunkQuery>,
+    body: Body,
+) -> Result<Json<UploadStatus>, Response> {
+    let mut status = store
+        .status(&upload_id)
+        .map_err(IntoResponse::into_response)?;
+    if status.offset != query.offset {
+        return Err(UploadError::OffsetMismatch {
+            expected: status.offset,
+        }
+        .into_response());
+    }
+
+    // Store bytes as they arrive so a dropped connection keeps its progress
+    let mut stream = body.into_data_stream();
+    while let Some(frame) = stream.next().await {
+        let bytes = match frame {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(
+                    "Upload {} interrupted at offset {}: {}",
+                    upload_id, status.offset, e
+                );
+                return Err((StatusCode::BAD_REQUEST, Json(status)).into_response());
+            }
+        };
+        status = store
+            .append(&upload_id, status.offset, &bytes)
+            .map_err(IntoResponse::into_response)?;
+    }
+    Ok(Json(status))
+}
@@ -0,0 +1,401 @@
+//! Client configuration driven by an agent's published card
+//!
+//! The agent card lists the transports an agent serves (its main `url` plus any
+//! `additionalInterfaces`) and the security schemes it requires. The helpers here
+//! fetch and cache the card, pick the transports a [`WebA2AClient`] should use and
+//! decide how its credential is presented.
+
+use std::{sync::LazyLock, time::Duration};
+
+use a2a_rs::domain::{A2AError, AgentCard, SecurityScheme};
+use anyhow::{Context, anyhow, bail};
+use tokio::net::TcpStream;
+
+#[cfg(feature = "signing")]
+use crate::RequestSigner;
+use crate::{
+    WebA2AClient, WebA2AClientBuilder,
+    cache::{CacheStats, DEFAULT_CACHE_CAPACITY, SharedCache},
+};
+
+/// Path agents serve their card from, used when a bare base URL is given
+pub const WELL_KNOWN_AGENT_CARD_PATH: &str = "/.well-known/agent-card.json";
+
+/// Default time a fetched card is reused before it is fetched again
+pub const DEFAULT_CARD_TTL: Duration = Duration::from_secs(300);
+
+/// How long a WebSocket endpoint gets to accept a connection before HTTP is used
+const WS_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Cards keyed by URL and the bearer token they were fetched with
+pub type CardCache = SharedCache<(String, Option<String>), AgentCard>;
+
+static SHARED_CARDS: LazyLock<CardCache> =
+    LazyLock::new(|| SharedCache::new(DEFAULT_CACHE_CAPACITY));
+
+/// The process-wide card cache used by every [`AgentCardCache`] unless given
+/// its own, so clients of one agent fetch its card once
+pub fn shared_card_cache() -> &'static CardCache {
+    &SHARED_CARDS
+}
+
+/// An agent card fetched from a URL and reused until its TTL expires.
+///
+/// Cards are kept in a [`CardCache`] shared by every `AgentCardCache`, so
+/// concurrent lookups of one card fetch it once and a card near expiry is
+/// refreshed in the background.
+pub struct AgentCardCache {
+    card_url: String,
+    ttl: Duration,
+    client: reqwest::Client,
+    bearer_token: Option<String>,
+    cards: CardCache,
+}
+
+impl AgentCardCache {
+    /// Create a cache for the card at `card_url`.
+    ///
+    /// A URL without a path (e.g. `http://localhost:8080`) is resolved to the
+    /// agent's well-known card location.
+    pub fn new(card_url: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            card_url: resolve_card_url(&card_url.into()),
+            ttl,
+            client: reqwest::Client::new(),
+            bearer_token: None,
+            cards: shared_card_cache().clone(),
+        }
+    }
+
+    /// Keep the card in `cards` rather than the process-wide cache
+    pub fn with_cache(mut self, cards: CardCache) -> Self {
+        self.cards = cards;
+        self
+    }
+
+    /// Send a bearer token when fetching the card, for agents that protect it
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// The URL the card is fetched from
+    pub fn card_url(&self) -> &str {
+        &self.card_url
+    }
+
+    /// The cached card, fetching it first if it is missing or expired
+    pub async fn get(&self) -> anyhow::Result<AgentCard> {
+        let (client, card_url, bearer_token, ttl) = (
+            self.client.clone(),
+            self.card_url.clone(),
+            self.bearer_token.clone(),
+            self.ttl,
+        );
+        self.cards
+            .get_or_fetch(self.key(), move || async move {
+                let card = fetch_card(&client, &card_url, bearer_token.as_deref()).await?;
+                Ok((card, ttl))
+            })
+            .await
+    }
+
+    /// Fetch the card again regardless of its age
+    pub async fn refresh(&self) -> anyhow::Result<AgentCard> {
+        let card = fetch_card(&self.client, &self.card_url, self.bearer_token.as_deref()).await?;
+        self.cards.insert(self.key(), card.clone(), self.ttl);
+        Ok(card)
+    }
+
+    /// Drop the cached card so the next [`get`](Self::get) fetches it again
+    pub fn invalidate(&self) {
+        self.cards.remove(&self.key());
+    }
+
+    /// Hits and misses of the cache the card is kept in
+    pub fn stats(&self) -> CacheStats {
+        self.cards.stats()
+    }
+
+    fn key(&self) -> (String, Option<String>) {
+        (self.card_url.clone(), self.bearer_token.clone())
+    }
+}
+
+async fn fetch_card(
+    client: &reqwest::Client,
+    card_url: &str,
+    bearer_token: Option<&str>,
+) -> anyhow::Result<AgentCard> {
+    let mut request = client.get(card_url);
+    if let Some(token) = bearer_token {
+        request = request.bearer_auth(token);
+    }
+
+    request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to fetch agent card from {}", card_url))?
+        .json()
+        .await
+        .with_context(|| format!("Invalid agent card at {}", card_url))
+}
+
+/// Transport endpoints selected from an agent card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardTransports {
+    /// JSON-RPC over HTTP endpoint
+    pub http_url: String,
+    /// WebSocket endpoint, if one is advertised
+    pub ws_url: Option<String>,
+}
+
+impl CardTransports {
+    /// Pick the HTTP and WebSocket endpoints advertised by a card.
+    ///
+    /// The card's main URL is considered first, then its additional interfaces
+    /// in order. An interface counts as WebSocket when its transport says so or
+    /// its URL uses a `ws`/`wss` scheme.
+    pub fn from_card(card: &AgentCard) -> anyhow::Result<Self> {
+        let interfaces = std::iter::once((card.preferred_transport.as_str(), card.url.as_str()))
+            .chain(
+                card.additional_interfaces
+                    .iter()
+                    .flatten()
+                    .map(|interface| (interface.transport.as_str(), interface.url.as_str())),
+            );
+
+        let mut http_url = None;
+        let mut ws_url = None;
+        for (transport, url) in interfaces {
+            if is_websocket(transport, url) {
+                ws_url.get_or_insert_with(|| url.to_string());
+            } else if url.starts_with("http://") || url.starts_with("https://") {
+                http_url.get_or_insert_with(|| url.to_string());
+            }
+        }
+
+        let http_url = http_url.ok_or_else(|| {
+            anyhow!(
+                "Agent card for '{}' advertises no HTTP interface",
+                card.name
+            )
+        })?;
+        Ok(Self { http_url, ws_url })
+    }
+}
+
+/// How the client presents its credential, as declared by the card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardAuth {
+    /// The agent declares no security requirements
+    None,
+    /// Send the credential as a bearer token
+    Bearer,
+}
+
+impl CardAuth {
+    /// Work out the authentication a card requires.
+    ///
+    /// Fails when the card requires a scheme the client cannot satisfy, or
+    /// requires authentication and no credential was supplied.
+    pub fn from_card(card: &AgentCard, has_credential: bool) -> anyhow::Result<Self> {
+        let Some(schemes) = card.security_schemes.as_ref().filter(|s| !s.is_empty()) else {
+            return Ok(Self::None);
+        };
+
+        // Explicit requirements name the schemes in use; otherwise any declared one applies
+        let required: Vec<&String> = match &card.security {
+            Some(requirements) if !requirements.is_empty() => {
+                requirements.iter().flat_map(|r| r.keys()).collect()
+            }
+            _ => schemes.keys().collect(),
+        };
+
+        let bearer = required.iter().find(|name| {
+            matches!(
+                schemes.get(name.as_str()),
+                Some(SecurityScheme::Http { scheme, .. }) if scheme.eq_ignore_ascii_case("bearer")
+            ) || matches!(
+                schemes.get(name.as_str()),
+                Some(SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIdConnect { .. })
+            )
+        });
+
+        match bearer {
+            Some(_) if has_credential => Ok(Self::Bearer),
+            Some(name) => bail!(
+                "Agent '{}' requires '{}' authentication but no credential was provided",
+                card.name,
+                name
+            ),
+            None => bail!(
+                "Agent '{}' requires unsupported authentication schemes: {:?}",
+                card.name,
+                required
+            ),
+        }
+    }
+}
+
+/// Builder for a [`WebA2AClient`] configured from an agent card
+pub struct AgentCardClientBuilder {
+    card_url: String,
+    ttl: Duration,
+    credential: Option<String>,
+    #[cfg(feature = "signing")]
+    signer: Option<RequestSigner>,
+}
+
+impl AgentCardClientBuilder {
+    /// Create a builder for the card at `card_url`
+    pub fn new(card_url: impl Into<String>) -> Self {
+        Self {
+            card_url: card_url.into(),
+            ttl: DEFAULT_CARD_TTL,
+            credential: None,
+            #[cfg(feature = "signing")]
+            signer: None,
+        }
+    }
+
+    /// Credential to present if the card declares a security scheme
+    pub fn credential(mut self, credential: impl Into<String>) -> Self {
+        self.credential = Some(credential.into());
+        self
+    }
+
+    /// How long the fetched card is reused
+    pub fn card_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sign HTTP requests with an Ed25519 key
+    #[cfg(feature = "signing")]
+    pub fn request_signer(mut self, signer: RequestSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Fetch the card and build a client for the transports it advertises
+    pub async fn connect(self) -> anyhow::Result<WebA2AClient> {
+        let mut cache = AgentCardCache::new(self.card_url, self.ttl);
+        if let Some(credential) = &self.credential {
+            cache = cache.with_bearer_token(credential.clone());
+        }
+        let card = cache.get().await?;
+        let mut client = build_from_card(
+            &card,
+            self.credential.clone(),
+            #[cfg(feature = "signing")]
+            self.signer.clone(),
+        )
+        .await?;
+
+        client.card = Some(std::sync::Arc::new(CardSource {
+            cache,
+            credential: self.credential,
+            #[cfg(feature = "signing")]
+            signer: self.signer,
+        }));
+        Ok(client)
+    }
+}
+
+/// Everything needed to rebuild a client when its card changes
+pub(crate) struct CardSource {
+    pub(crate) cache: AgentCardCache,
+    credential: Option<String>,
+    #[cfg(feature = "signing")]
+    signer: Option<RequestSigner>,
+}
+
+impl CardSource {
+    /// Build a fresh client from the current card
+    pub(crate) async fn rebuild(&self, card: &AgentCard) -> anyhow::Result<WebA2AClient> {
+        build_from_card(
+            card,
+            self.credential.clone(),
+            #[cfg(feature = "signing")]
+            self.signer.clone(),
+        )
+        .await
+    }
+}
+
+async fn build_from_card(
+    card: &AgentCard,
+    credential: Option<String>,
+    #[cfg(feature = "signing")] signer: Option<RequestSigner>,
+) -> anyhow::Result<WebA2AClient> {
+    let transports = CardTransports::from_card(card)?;
+    let auth = CardAuth::from_card(card, credential.is_some())?;
+
+    let mut builder =
+        WebA2AClientBuilder::new(transports.http_url).capabilities(card.capabilities.clone());
+    if let Some(ws_url) = transports.ws_url {
+        if is_reachable(&ws_url).await {
+            builder = builder.websocket(ws_url);
+        } else {
+            tracing::warn!("WebSocket endpoint {} is unreachable, using HTTP", ws_url);
+        }
+    }
+    if let (CardAuth::Bearer, Some(token)) = (auth, credential) {
+        builder = builder.auth_token(token);
+    }
+    #[cfg(feature = "signing")]
+    if let Some(signer) = signer {
+        builder = builder.request_signer(signer);
+    }
+
+    Ok(builder.build())
+}
+
+/// Whether an error means the transport itself failed rather than the agent
+/// rejecting the request
+pub(crate) fn is_transport_error(error: &A2AError) -> bool {
+    match error {
+        A2AError::Io(_) => true,
+        A2AError::Internal(message) => {
+            message.starts_with("HTTP client error")
+                || message.starts_with("HTTP request")
+                || message.starts_with("WebSocket")
+        }
+        _ => false,
+    }
+}
+
+fn is_websocket(transport: &str, url: &str) -> bool {
+    transport.eq_ignore_ascii_case("WEBSOCKET")
+        || transport.eq_ignore_ascii_case("WS")
+        || url.starts_with("ws://")
+        || url.starts_with("wss://")
+}
+
+fn resolve_card_url(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/');
+    match trimmed.split_once("://") {
+        Some((_, rest)) if !rest.contains('/') => {
+            format!("{}{}", trimmed, WELL_KNOWN_AGENT_CARD_PATH)
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Check that something accepts connections at a WebSocket URL's host and port
+async fn is_reachable(ws_url: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(ws_url) else {
+        return false;
+    };
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return false;
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    matches!(
+        tokio::time::timeout(WS_PROBE_TIMEOUT, TcpStream::connect((host, port))).await,
+        Ok(Ok(_))
+    )
+}
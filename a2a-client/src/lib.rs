@@ -7,6 +7,10 @@
 //! - SSE/WebSocket streaming helpers
 //! - Display formatters for A2A types
 //! - Axum route builders
+//! - Transport and auth configuration from an agent card
+//! - A durable outbox queueing messages while the agent is unreachable
+//! - Shared caches for agent cards and OAuth access tokens
+//! - Waiting for a task to finish, streamed or polled
 //!
 //! # Examples
 //!
@@ -15,30 +19,68 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
-//!     let client = WebA2AClient::auto_connect("http://localhost:8080").await?;
+//!     let client = WebA2AClient::from_agent_card("http://localhost:8080").await?;
 //!     // Use client to interact with A2A agent...
 //!     Ok(())
 //! }
 //! ```
 
+pub mod cache;
 pub mod components;
+pub mod discovery;
+pub mod oauth;
+pub mod outbox;
+mod shared_subscription;
+mod subscription;
 pub mod utils;
+mod wait;
+mod warm_up;
 
-use a2a_rs::{HttpClient, WebSocketClient};
+use a2a_rs::{
+    HttpClient, RetryPolicy, WebSocketClient, WebSocketOptions,
+    domain::{
+        A2AError, AgentCapabilities, IdGenerator, ListTasksParams, ListTasksResult, Message, Task,
+        TaskPushNotificationConfig,
+    },
+    port::ListTasksStream,
+    services::{AsyncA2AClient, InterceptorChain},
+};
+use discovery::{AgentCardCache, AgentCardClientBuilder, CardSource, is_transport_error};
+use shared_subscription::SharedSources;
 use std::sync::Arc;
 
+#[cfg(feature = "signing")]
+pub use a2a_rs::RequestSigner;
+pub use a2a_rs::services::{ClientRequest, RequestInterceptor};
+pub use wait::WaitError;
+pub use warm_up::WarmUpError;
+
 /// Web-friendly A2A client that wraps both HTTP and WebSocket clients
 pub struct WebA2AClient {
     pub http: HttpClient,
     pub ws: Option<Arc<WebSocketClient>>,
+    /// Agent card the transports were configured from, if any
+    card: Option<Arc<CardSource>>,
+    /// Capabilities advertised by the agent, once known
+    capabilities: Option<AgentCapabilities>,
+    /// Task subscriptions shared by the streams following each task
+    shared_sources: SharedSources,
 }
 
 impl WebA2AClient {
+    /// Start building a client for the given HTTP endpoint
+    pub fn builder(http_url: impl Into<String>) -> WebA2AClientBuilder {
+        WebA2AClientBuilder::new(http_url)
+    }
+
     /// Create a new client with HTTP only
     pub fn new_http(base_url: String) -> Self {
         Self {
             http: HttpClient::new(base_url),
             ws: None,
+            card: None,
+            capabilities: None,
+            shared_sources: SharedSources::default(),
         }
     }
 
@@ -47,14 +89,125 @@ impl WebA2AClient {
         Self {
             http: HttpClient::new(http_url),
             ws: Some(Arc::new(WebSocketClient::new(ws_url))),
+            card: None,
+            capabilities: None,
+            shared_sources: SharedSources::default(),
+        }
+    }
+
+    /// Create a new client with both HTTP and WebSocket that presents `token`
+    /// as a bearer token: in the `Authorization` header over HTTP, and in the
+    /// auth handshake sent as the first message of every WebSocket connection
+    pub fn new_with_websocket_auth(http_url: String, ws_url: String, token: String) -> Self {
+        Self {
+            http: HttpClient::with_auth(http_url, token.clone()),
+            ws: Some(Arc::new(WebSocketClient::with_auth(ws_url, token))),
+            card: None,
+            capabilities: None,
+            shared_sources: SharedSources::default(),
         }
     }
 
-    /// Auto-connect to an agent, detecting available transports
+    /// Configure a client from the agent card at `card_url`.
+    ///
+    /// The card's advertised interfaces decide the transports: WebSocket is used
+    /// when advertised and reachable, HTTP otherwise. Use
+    /// [`agent_card_builder`](Self::agent_card_builder) to supply a credential
+    /// for agents that declare a security scheme.
+    pub async fn from_agent_card(card_url: &str) -> anyhow::Result<Self> {
+        AgentCardClientBuilder::new(card_url).connect().await
+    }
+
+    /// Start building a client configured from an agent card
+    pub fn agent_card_builder(card_url: impl Into<String>) -> AgentCardClientBuilder {
+        AgentCardClientBuilder::new(card_url)
+    }
+
+    /// Auto-connect to an agent, detecting available transports from its card
+    /// and falling back to plain HTTP when no card is served
     pub async fn auto_connect(base_url: &str) -> anyhow::Result<Self> {
-        // For now, just use HTTP
-        // TODO: Try to detect WebSocket support
-        Ok(Self::new_http(base_url.to_string()))
+        match Self::from_agent_card(base_url).await {
+            Ok(client) => Ok(client),
+            Err(e) => {
+                tracing::debug!("No usable agent card at {}: {:#}", base_url, e);
+                Ok(Self::new_http(base_url.to_string()))
+            }
+        }
+    }
+
+    /// The cached agent card, when the client was configured from one
+    pub fn agent_card(&self) -> Option<&AgentCardCache> {
+        self.card.as_ref().map(|source| &source.cache)
+    }
+
+    /// Fetch the agent card again and reconfigure the transports from it
+    pub async fn refresh_from_card(&mut self) -> anyhow::Result<()> {
+        let Some(source) = self.card.clone() else {
+            anyhow::bail!("Client was not configured from an agent card");
+        };
+        let card = source.cache.refresh().await?;
+        let rebuilt = source.rebuild(&card).await?;
+        self.http = rebuilt.http;
+        self.ws = rebuilt.ws;
+        self.capabilities = rebuilt.capabilities;
+        Ok(())
+    }
+
+    /// Capabilities advertised by the agent, if they are known.
+    ///
+    /// Clients configured from an agent card know them from the start; others
+    /// learn them through [`discover_capabilities`](Self::discover_capabilities).
+    /// While unknown, every operation is attempted.
+    pub fn capabilities(&self) -> Option<&AgentCapabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// Fetch the agent card over HTTP and remember the capabilities it advertises
+    pub async fn discover_capabilities(&mut self) -> Result<&AgentCapabilities, A2AError> {
+        let card = self.http.get_agent_card().await?;
+        Ok(self.capabilities.insert(card.capabilities))
+    }
+
+    /// Whether task updates can be streamed, or the capability is unknown
+    pub fn supports_streaming(&self) -> bool {
+        self.capabilities.as_ref().is_none_or(|c| c.streaming)
+    }
+
+    /// Whether the agent can push notifications, or the capability is unknown
+    pub fn supports_push_notifications(&self) -> bool {
+        self.capabilities
+            .as_ref()
+            .is_none_or(|c| c.push_notifications)
+    }
+
+    /// Send a message over HTTP, refusing part kinds the agent does not accept
+    pub async fn send_task_message(
+        &self,
+        task_id: &str,
+        message: &Message,
+        session_id: Option<&str>,
+        history_length: Option<u32>,
+    ) -> Result<Task, A2AError> {
+        if let Some(capabilities) = &self.capabilities {
+            capabilities.ensure_parts(&message.parts)?;
+        }
+        self.http
+            .send_task_message(task_id, message, session_id, history_length)
+            .await
+    }
+
+    /// Register a push notification webhook for a task.
+    ///
+    /// Fails with [`A2AError::PushNotificationNotSupported`] without contacting
+    /// the agent when it does not advertise push notifications.
+    pub async fn set_task_push_notification(
+        &self,
+        config: &TaskPushNotificationConfig,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        if let Some(capabilities) = &self.capabilities {
+            capabilities.ensure_push_notifications()?;
+        }
+        self.http.set_task_push_notification(config).await
     }
 
     /// Check if WebSocket is available
@@ -66,6 +219,206 @@ impl WebA2AClient {
     pub fn websocket(&self) -> Option<&Arc<WebSocketClient>> {
         self.ws.as_ref()
     }
+
+    /// List tasks with filtering and pagination (v0.3.0)
+    ///
+    /// Uses the WebSocket connection when one is configured, so clients that keep a
+    /// single socket open don't need to fall back to HTTP. Both transports return
+    /// the same `ListTasksResult` shape.
+    ///
+    /// For clients configured from an agent card, a transport failure refreshes
+    /// the cached card and a failed WebSocket request is retried over HTTP.
+    pub async fn list_tasks(&self, params: &ListTasksParams) -> Result<ListTasksResult, A2AError> {
+        let result = match &self.ws {
+            Some(ws) => ws.list_tasks(params).await,
+            None => self.http.list_tasks(params).await,
+        };
+
+        match (&self.card, result) {
+            (Some(source), Err(e)) if is_transport_error(&e) => {
+                if let Err(refresh_error) = source.cache.refresh().await {
+                    tracing::warn!("Failed to refresh agent card: {:#}", refresh_error);
+                }
+                if self.ws.is_some() {
+                    self.http.list_tasks(params).await
+                } else {
+                    Err(e)
+                }
+            }
+            (_, result) => result,
+        }
+    }
+
+    /// Stream a page of tasks one task at a time, ending with the listing's
+    /// summary, so a view can render the first rows before the rest arrive.
+    ///
+    /// Tasks are streamed over the WebSocket connection when one is
+    /// configured. Over HTTP the page is fetched whole and then yielded task by
+    /// task, so both transports produce the same items.
+    pub async fn list_tasks_stream(
+        &self,
+        params: &ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        let result = match &self.ws {
+            Some(ws) => ws.list_tasks_stream(params).await,
+            None => return self.list_tasks_page_stream(params).await,
+        };
+
+        match (&self.card, result) {
+            (Some(source), Err(e)) if is_transport_error(&e) => {
+                if let Err(refresh_error) = source.cache.refresh().await {
+                    tracing::warn!("Failed to refresh agent card: {:#}", refresh_error);
+                }
+                self.list_tasks_page_stream(params).await
+            }
+            (_, result) => result,
+        }
+    }
+
+    /// Fetch a page over HTTP and yield it as a streamed listing
+    async fn list_tasks_page_stream(
+        &self,
+        params: &ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        let page = self.http.list_tasks(params).await?;
+        let items = page.into_stream_items().into_iter().map(Ok);
+        Ok(Box::pin(futures::stream::iter(items)))
+    }
+}
+
+/// Builder for [`WebA2AClient`] with optional WebSocket, auth and request signing
+pub struct WebA2AClientBuilder {
+    http_url: String,
+    ws_url: Option<String>,
+    auth_token: Option<String>,
+    ws_options: WebSocketOptions,
+    capabilities: Option<AgentCapabilities>,
+    retry_policy: Option<RetryPolicy>,
+    interceptors: InterceptorChain,
+    request_ids: Option<Arc<dyn IdGenerator>>,
+    #[cfg(feature = "signing")]
+    signer: Option<RequestSigner>,
+}
+
+impl WebA2AClientBuilder {
+    /// Create a builder for the given HTTP endpoint
+    pub fn new(http_url: impl Into<String>) -> Self {
+        Self {
+            http_url: http_url.into(),
+            ws_url: None,
+            auth_token: None,
+            ws_options: WebSocketOptions::default(),
+            capabilities: None,
+            retry_policy: None,
+            interceptors: InterceptorChain::new(),
+            request_ids: None,
+            #[cfg(feature = "signing")]
+            signer: None,
+        }
+    }
+
+    /// Also connect over WebSocket
+    pub fn websocket(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    /// Message size limit and compression settings for the WebSocket connection.
+    ///
+    /// Compression is offered by default and used whenever the server accepts it.
+    pub fn websocket_options(mut self, options: WebSocketOptions) -> Self {
+        self.ws_options = options;
+        self
+    }
+
+    /// Capabilities the agent advertises, consulted before optional operations
+    pub fn capabilities(mut self, capabilities: AgentCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Send a bearer token with every HTTP request and in the WebSocket auth
+    /// handshake
+    pub fn auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Retry HTTP requests the agent refuses as busy, waiting as long as its
+    /// `Retry-After` hint asks when it sends one
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Run every RPC, over HTTP and WebSocket, through `interceptor`.
+    ///
+    /// Interceptors run in the order they are added before a request is sent,
+    /// and in reverse order once its response arrives.
+    pub fn interceptor(mut self, interceptor: impl RequestInterceptor + 'static) -> Self {
+        self.interceptors = self.interceptors.with(interceptor);
+        self
+    }
+
+    /// Mint the `X-Request-ID` of HTTP requests with `ids` instead of random
+    /// UUIDs. A request keeps its id across retries.
+    pub fn request_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.request_ids = Some(Arc::new(ids));
+        self
+    }
+
+    /// Sign HTTP requests with an Ed25519 key.
+    ///
+    /// Each request carries a detached signature over the canonical body in the
+    /// `X-A2A-Request-Signature` header. WebSocket frames have no headers, so
+    /// deployments that require signatures should route RPCs over HTTP.
+    #[cfg(feature = "signing")]
+    pub fn request_signer(mut self, signer: RequestSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Build the client
+    pub fn build(self) -> WebA2AClient {
+        let http = match &self.auth_token {
+            Some(token) => HttpClient::with_auth(self.http_url, token.clone()),
+            None => HttpClient::new(self.http_url),
+        };
+        let http = match self.retry_policy {
+            Some(policy) => http.with_retry_policy(policy),
+            None => http,
+        }
+        .with_interceptors(self.interceptors.clone());
+        let http = match self.request_ids {
+            Some(ids) => http.with_request_id_generator(ids),
+            None => http,
+        };
+        #[cfg(feature = "signing")]
+        let http = match self.signer {
+            Some(signer) => http.with_request_signer(signer),
+            None => http,
+        };
+
+        let ws = self.ws_url.map(|url| {
+            let client = match &self.auth_token {
+                Some(token) => WebSocketClient::with_auth(url, token.clone()),
+                None => WebSocketClient::new(url),
+            };
+            Arc::new(
+                client
+                    .with_options(self.ws_options)
+                    .with_interceptors(self.interceptors),
+            )
+        });
+
+        WebA2AClient {
+            http,
+            ws,
+            card: None,
+            capabilities: self.capabilities,
+            shared_sources: SharedSources::default(),
+        }
+    }
 }
 
 /// Application state for Axum web applications
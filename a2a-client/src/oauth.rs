@@ -0,0 +1,163 @@
+//! Access tokens from an OAuth 2.0 client credentials grant
+//!
+//! [`ClientCredentials`] exchanges a client id and secret for an access token
+//! at the authorization server's token endpoint and sends it as a bearer token
+//! with every request. Tokens are kept in a [`TokenCache`] until they expire,
+//! refreshed shortly before, and fetched once however many requests need one.
+
+use std::{sync::LazyLock, time::Duration};
+
+use a2a_rs::{
+    application::JSONRPCResponse,
+    domain::A2AError,
+    services::{ClientRequest, RequestInterceptor},
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::cache::{CacheStats, DEFAULT_CACHE_CAPACITY, SharedCache};
+
+/// Lifetime assumed for a token whose response gives no `expires_in`
+pub const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(300);
+
+/// Tokens keyed by token URL, client id and requested scopes
+pub type TokenCache = SharedCache<(String, String, String), String>;
+
+static SHARED_TOKENS: LazyLock<TokenCache> =
+    LazyLock::new(|| SharedCache::new(DEFAULT_CACHE_CAPACITY));
+
+/// The process-wide token cache used by every [`ClientCredentials`] unless
+/// given its own
+pub fn shared_token_cache() -> &'static TokenCache {
+    &SHARED_TOKENS
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+/// Bearer tokens obtained with the client credentials grant.
+///
+/// # Example
+/// ```rust,no_run
+/// use a2a_client::{WebA2AClient, oauth::ClientCredentials};
+///
+/// let credentials = ClientCredentials::new(
+///     "https://auth.example.com/oauth/token",
+///     "expense-frontend",
+///     "client-secret",
+/// )
+/// .with_scopes(["tasks:write"]);
+/// let client = WebA2AClient::builder("https://agent.example.com")
+///     .interceptor(credentials)
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct ClientCredentials {
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scopes: Vec<String>,
+    client: reqwest::Client,
+    tokens: TokenCache,
+}
+
+impl ClientCredentials {
+    /// Create a token source for the client `client_id` at `token_url`
+    pub fn new(
+        token_url: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            scopes: Vec::new(),
+            client: reqwest::Client::new(),
+            tokens: shared_token_cache().clone(),
+        }
+    }
+
+    /// Scopes to request with the token
+    pub fn with_scopes<S: Into<String>>(mut self, scopes: impl IntoIterator<Item = S>) -> Self {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Keep tokens in `tokens` rather than the process-wide cache
+    pub fn with_cache(mut self, tokens: TokenCache) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    /// A valid access token, fetched first if none is cached or it expired
+    pub async fn token(&self) -> Result<String, A2AError> {
+        let scope = self.scopes.join(" ");
+        let key = (
+            self.token_url.clone(),
+            self.client_id.clone(),
+            scope.clone(),
+        );
+        let (client, token_url, client_id, client_secret) = (
+            self.client.clone(),
+            self.token_url.clone(),
+            self.client_id.clone(),
+            self.client_secret.clone(),
+        );
+        self.tokens
+            .get_or_fetch(key, move || async move {
+                let mut form = vec![
+                    ("grant_type", "client_credentials"),
+                    ("client_id", client_id.as_str()),
+                    ("client_secret", client_secret.as_str()),
+                ];
+                if !scope.is_empty() {
+                    form.push(("scope", scope.as_str()));
+                }
+                let token_error = |error: reqwest::Error| {
+                    A2AError::Internal(format!(
+                        "Failed to fetch access token from {}: {}",
+                        token_url, error
+                    ))
+                };
+
+                let response: TokenResponse = client
+                    .post(&token_url)
+                    .form(&form)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(token_error)?
+                    .json()
+                    .await
+                    .map_err(token_error)?;
+                let ttl = response
+                    .expires_in
+                    .map_or(DEFAULT_TOKEN_TTL, Duration::from_secs);
+                Ok((response.access_token, ttl))
+            })
+            .await
+    }
+
+    /// Hits and misses of the cache tokens are kept in
+    pub fn stats(&self) -> CacheStats {
+        self.tokens.stats()
+    }
+}
+
+#[async_trait]
+impl RequestInterceptor for ClientCredentials {
+    async fn before_request(
+        &self,
+        request: &mut ClientRequest,
+    ) -> Result<Option<JSONRPCResponse>, A2AError> {
+        let token = self.token().await?;
+        request
+            .headers
+            .insert("Authorization".to_string(), format!("Bearer {}", token));
+        Ok(None)
+    }
+}
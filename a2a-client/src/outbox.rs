@@ -0,0 +1,396 @@
+//! Durable outbox for sending messages over unreliable networks
+//!
+//! [`Outbox::send`] tries to deliver a message right away. When the agent
+//! can't be reached, or is temporarily unavailable, the message is queued
+//! instead of lost. The queue is persisted through an [`OutboxStore`], so it
+//! survives a restart, and [`Outbox::flush`] delivers it once connectivity
+//! returns; [`Outbox::spawn_retry`] flushes in the background.
+//!
+//! A message's id is its idempotency key. A send whose response was lost may
+//! still have reached the agent, so before resending a queued message the
+//! outbox looks for it in the task's history, and marks it sent if found.
+//!
+//! Messages for a task are delivered in the order they were sent: while one
+//! is queued, later messages for the same task queue behind it.
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use a2a_rs::{
+    domain::{A2AError, Message, Task},
+    services::AsyncA2AClient,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::broadcast, task::JoinHandle};
+
+use crate::discovery::is_transport_error;
+
+/// Delivery attempts before a queued message is marked failed
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 20;
+
+/// Where a message stands in the outbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeliveryState {
+    /// Waiting for the agent to become reachable
+    Queued,
+    /// Delivered to the agent
+    Sent,
+    /// Given up on: the agent rejected it or every attempt failed
+    Failed,
+}
+
+/// A message that went through the outbox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxEntry {
+    /// The message id, identifying the message across retries
+    pub idempotency_key: String,
+    pub task_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub message: Message,
+    pub state: DeliveryState,
+    /// Delivery attempts made after the message was queued
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Result of [`Outbox::send`]
+#[derive(Debug, Clone)]
+pub enum SendOutcome {
+    /// Delivered right away
+    Sent(Box<Task>),
+    /// Queued for delivery, under this idempotency key
+    Queued(String),
+}
+
+/// A change in a message's delivery state, as broadcast by the outbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryUpdate {
+    pub idempotency_key: String,
+    pub task_id: String,
+    pub state: DeliveryState,
+}
+
+/// Persistence for the outbox, so queued messages survive a restart
+pub trait OutboxStore: Send + Sync {
+    /// Load the entries saved last
+    fn load(&self) -> Result<Vec<OutboxEntry>, A2AError>;
+
+    /// Replace the saved entries
+    fn save(&self, entries: &[OutboxEntry]) -> Result<(), A2AError>;
+}
+
+/// Keeps entries in memory; clones share them
+#[derive(Debug, Clone, Default)]
+pub struct MemoryOutboxStore {
+    entries: Arc<Mutex<Vec<OutboxEntry>>>,
+}
+
+impl MemoryOutboxStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OutboxStore for MemoryOutboxStore {
+    fn load(&self) -> Result<Vec<OutboxEntry>, A2AError> {
+        Ok(self.entries.lock().unwrap().clone())
+    }
+
+    fn save(&self, entries: &[OutboxEntry]) -> Result<(), A2AError> {
+        *self.entries.lock().unwrap() = entries.to_vec();
+        Ok(())
+    }
+}
+
+/// Keeps entries in a JSON file, replaced atomically on every save
+#[derive(Debug, Clone)]
+pub struct FileOutboxStore {
+    path: PathBuf,
+}
+
+impl FileOutboxStore {
+    /// Store entries at `path`; a missing file holds no entries
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl OutboxStore for FileOutboxStore {
+    fn load(&self) -> Result<Vec<OutboxEntry>, A2AError> {
+        match fs::read(&self.path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, entries: &[OutboxEntry]) -> Result<(), A2AError> {
+        let temporary = self.path.with_extension("tmp");
+        fs::write(&temporary, serde_json::to_vec(entries)?)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Whether a send may succeed later without any change to the message
+fn is_retryable(error: &A2AError) -> bool {
+    is_transport_error(error) || error.is_retryable()
+}
+
+/// Sends messages, queueing those the agent could not be reached for
+pub struct Outbox<C> {
+    client: C,
+    store: Box<dyn OutboxStore>,
+    entries: Mutex<Vec<OutboxEntry>>,
+    updates: broadcast::Sender<DeliveryUpdate>,
+    max_attempts: u32,
+    /// Held by a flush, so only one delivers at a time
+    flushing: tokio::sync::Mutex<()>,
+}
+
+impl<C> Outbox<C>
+where
+    C: AsyncA2AClient + 'static,
+{
+    /// Create an outbox sending through `client`, restoring the entries
+    /// saved in `store`
+    pub fn open(client: C, store: impl OutboxStore + 'static) -> Result<Self, A2AError> {
+        let entries = store.load()?;
+        Ok(Self {
+            client,
+            store: Box::new(store),
+            entries: Mutex::new(entries),
+            updates: broadcast::channel(64).0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            flushing: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Mark a queued message failed after this many delivery attempts
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// The client messages are sent through
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Every entry, in the order the messages were sent
+    pub fn entries(&self) -> Vec<OutboxEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Delivery state of the message with this idempotency key
+    pub fn state(&self, idempotency_key: &str) -> Option<DeliveryState> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|entry| entry.idempotency_key == idempotency_key)
+            .map(|entry| entry.state)
+    }
+
+    /// Receive every change in a message's delivery state
+    pub fn subscribe(&self) -> broadcast::Receiver<DeliveryUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Drop sent and failed entries, returning how many were removed
+    pub fn remove_finished(&self) -> Result<usize, A2AError> {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|entry| entry.state == DeliveryState::Queued);
+        self.store.save(&entries)?;
+        Ok(before - entries.len())
+    }
+
+    /// Send a message, queueing it if the agent can't be reached.
+    ///
+    /// Errors other than an unreachable or temporarily unavailable agent
+    /// are returned as they are, without queueing.
+    pub async fn send(
+        &self,
+        task_id: &str,
+        message: &Message,
+        session_id: Option<&str>,
+    ) -> Result<SendOutcome, A2AError> {
+        let entry = OutboxEntry {
+            idempotency_key: message.message_id.clone(),
+            task_id: task_id.to_string(),
+            session_id: session_id.map(str::to_string),
+            message: message.clone(),
+            state: DeliveryState::Queued,
+            attempts: 0,
+            last_error: None,
+        };
+
+        let waiting = self.entries.lock().unwrap().iter().any(|queued| {
+            queued.state == DeliveryState::Queued
+                && (queued.task_id == task_id || queued.idempotency_key == entry.idempotency_key)
+        });
+        if !waiting {
+            match self
+                .client
+                .send_task_message(task_id, message, session_id, None)
+                .await
+            {
+                Ok(task) => return Ok(SendOutcome::Sent(Box::new(task))),
+                Err(e) if is_retryable(&e) => {
+                    tracing::warn!(task_id, "Agent unreachable, queueing message: {}", e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let key = entry.idempotency_key.clone();
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if !entries.iter().any(|queued| {
+                queued.idempotency_key == key && queued.state == DeliveryState::Queued
+            }) {
+                entries.push(entry);
+                self.store.save(&entries)?;
+            }
+        }
+        self.notify(&key, task_id, DeliveryState::Queued);
+        Ok(SendOutcome::Queued(key))
+    }
+
+    /// Try to deliver every queued message, returning how many were sent.
+    ///
+    /// Stops at the first message the agent still can't be reached for,
+    /// leaving it and the rest queued.
+    pub async fn flush(&self) -> Result<usize, A2AError> {
+        let _flushing = self.flushing.lock().await;
+        let queued: Vec<OutboxEntry> = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.state == DeliveryState::Queued)
+            .collect();
+
+        let mut sent = 0;
+        for entry in queued {
+            match self.deliver(&entry).await {
+                Ok(()) => {
+                    self.update(&entry, DeliveryState::Sent, None)?;
+                    sent += 1;
+                }
+                Err(e) if is_retryable(&e) => {
+                    let attempts = self.record_attempt(&entry, &e)?;
+                    if attempts >= self.max_attempts {
+                        self.update(&entry, DeliveryState::Failed, Some(e.to_string()))?;
+                        continue;
+                    }
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        task_id = %entry.task_id,
+                        "Queued message rejected by the agent: {}",
+                        e
+                    );
+                    self.update(&entry, DeliveryState::Failed, Some(e.to_string()))?;
+                }
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Flush every `interval` until the returned handle is aborted
+    pub fn spawn_retry(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let outbox = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(e) = outbox.flush().await {
+                    tracing::warn!("Failed to flush outbox: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Send a queued message unless an earlier attempt already reached the agent
+    async fn deliver(&self, entry: &OutboxEntry) -> Result<(), A2AError> {
+        match self.client.get_task(&entry.task_id, None).await {
+            Ok(task) => {
+                let delivered = task
+                    .history
+                    .iter()
+                    .flatten()
+                    .any(|message| message.message_id == entry.idempotency_key);
+                if delivered {
+                    return Ok(());
+                }
+            }
+            Err(e) if is_transport_error(&e) => return Err(e),
+            // Most likely the task doesn't exist yet
+            Err(_) => {}
+        }
+        self.client
+            .send_task_message(
+                &entry.task_id,
+                &entry.message,
+                entry.session_id.as_deref(),
+                None,
+            )
+            .await
+            .map(drop)
+    }
+
+    fn record_attempt(&self, entry: &OutboxEntry, error: &A2AError) -> Result<u32, A2AError> {
+        let mut entries = self.entries.lock().unwrap();
+        let mut attempts = 0;
+        if let Some(stored) = entries
+            .iter_mut()
+            .find(|stored| stored.idempotency_key == entry.idempotency_key)
+        {
+            stored.attempts += 1;
+            stored.last_error = Some(error.to_string());
+            attempts = stored.attempts;
+        }
+        self.store.save(&entries)?;
+        Ok(attempts)
+    }
+
+    fn update(
+        &self,
+        entry: &OutboxEntry,
+        state: DeliveryState,
+        error: Option<String>,
+    ) -> Result<(), A2AError> {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(stored) = entries
+                .iter_mut()
+                .find(|stored| stored.idempotency_key == entry.idempotency_key)
+            {
+                stored.state = state;
+                if error.is_some() {
+                    stored.last_error = error;
+                }
+            }
+            self.store.save(&entries)?;
+        }
+        self.notify(&entry.idempotency_key, &entry.task_id, state);
+        Ok(())
+    }
+
+    fn notify(&self, idempotency_key: &str, task_id: &str, state: DeliveryState) {
+        // Nobody listening is fine
+        let _ = self.updates.send(DeliveryUpdate {
+            idempotency_key: idempotency_key.to_string(),
+            task_id: task_id.to_string(),
+            state,
+        });
+    }
+}
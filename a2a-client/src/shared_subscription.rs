@@ -0,0 +1,250 @@
+//! Task subscriptions shared by every stream following the same task
+//!
+//! Each open tab streams a task's updates; rather than each opening its own
+//! WebSocket subscription, the streams of one task join a subscription the
+//! client opens for the first of them and closes when the last one goes
+//! away. A stream joining late starts from the task as the subscription last
+//! saw it, then follows its updates. A stream falling too far behind skips
+//! what it missed and starts again from the latest state.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use a2a_rs::{
+    domain::{A2AError, Task, TaskStatusUpdateEvent},
+    services::StreamItem,
+};
+use futures::{Stream, StreamExt};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::AbortHandle,
+};
+use tracing::{debug, warn};
+
+use crate::WebA2AClient;
+
+/// Items buffered for each stream of a shared subscription
+const SUBSCRIBER_BUFFER: usize = 64;
+
+/// A task ID and the history length its subscription asked for
+type SourceKey = (String, Option<u32>);
+
+/// A client's open shared subscriptions
+pub(crate) type SharedSources = Arc<Mutex<HashMap<SourceKey, Arc<SharedSource>>>>;
+
+impl WebA2AClient {
+    /// Follow a task's updates through the subscription shared by every
+    /// caller following it with the same `history_length`.
+    ///
+    /// The subscription behaves like
+    /// [`subscribe_to_task`](Self::subscribe_to_task), ending once the task
+    /// reaches a terminal state; its errors are logged rather than yielded.
+    /// A caller joining an open subscription first gets the task as last
+    /// seen. Fails with [`A2AError::UnsupportedOperation`] when no WebSocket
+    /// endpoint is configured.
+    pub fn subscribe_shared(
+        self: &Arc<Self>,
+        task_id: &str,
+        history_length: Option<u32>,
+    ) -> Result<impl Stream<Item = StreamItem> + Send + 'static, A2AError> {
+        if self.ws.is_none() {
+            return Err(A2AError::UnsupportedOperation(
+                "No WebSocket endpoint configured".to_string(),
+            ));
+        }
+
+        let key = (task_id.to_string(), history_length);
+        let mut sources = self.shared_sources.lock().unwrap();
+        let source = match sources.get(&key) {
+            Some(source) => source.clone(),
+            None => {
+                debug!("Opening shared subscription to task {}", key.0);
+                let source = Arc::new(SharedSource::new(history_length));
+                let pump = tokio::spawn(pump(
+                    self.clone(),
+                    key.clone(),
+                    source.clone(),
+                    self.shared_sources.clone(),
+                ));
+                source.state.lock().unwrap().pump = Some(pump.abort_handle());
+                sources.insert(key.clone(), source.clone());
+                source
+            }
+        };
+
+        let mut state = source.state.lock().unwrap();
+        state.subscribers += 1;
+        let pending = state.replay(history_length);
+        let receiver = state.sender.subscribe();
+        drop(state);
+
+        let joined = Joined {
+            key,
+            source,
+            sources: self.shared_sources.clone(),
+            pending,
+            receiver,
+        };
+        Ok(futures::stream::unfold(joined, |mut joined| async move {
+            let item = joined.next().await?;
+            Some((item, joined))
+        }))
+    }
+
+    /// The number of task subscriptions currently shared by
+    /// [`subscribe_shared`](Self::subscribe_shared) callers
+    pub fn shared_subscriptions(&self) -> usize {
+        self.shared_sources.lock().unwrap().len()
+    }
+}
+
+/// A task subscription and the streams sharing it
+pub(crate) struct SharedSource {
+    /// History sent with the task to streams joining late
+    history_length: Option<u32>,
+    state: Mutex<SourceState>,
+}
+
+struct SourceState {
+    /// Fans each update out to every stream
+    sender: broadcast::Sender<StreamItem>,
+    /// The task as of the latest update, once it was received
+    snapshot: Option<Task>,
+    /// The latest status update, in case the task was never received
+    last_status: Option<TaskStatusUpdateEvent>,
+    /// How many streams share the subscription
+    subscribers: usize,
+    /// The task reading the subscription
+    pump: Option<AbortHandle>,
+}
+
+impl SharedSource {
+    fn new(history_length: Option<u32>) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        Self {
+            history_length,
+            state: Mutex::new(SourceState {
+                sender,
+                snapshot: None,
+                last_status: None,
+                subscribers: 0,
+                pump: None,
+            }),
+        }
+    }
+}
+
+impl SourceState {
+    /// Fold an update into the snapshot
+    fn record(&mut self, item: &StreamItem) {
+        item.apply_to(&mut self.snapshot);
+        if let StreamItem::StatusUpdate(update) = item {
+            self.last_status = Some(update.clone());
+        }
+    }
+
+    /// The items bringing a joining stream up to date
+    fn replay(&self, history_length: Option<u32>) -> VecDeque<StreamItem> {
+        match (&self.snapshot, &self.last_status) {
+            (Some(task), _) => {
+                VecDeque::from([StreamItem::Task(task.with_limited_history(history_length))])
+            }
+            (None, Some(update)) => VecDeque::from([StreamItem::StatusUpdate(update.clone())]),
+            (None, None) => VecDeque::new(),
+        }
+    }
+}
+
+/// Read the subscription to `key`, fanning its updates out to the streams of
+/// `source` until it ends or the last stream leaves
+async fn pump(
+    client: Arc<WebA2AClient>,
+    key: SourceKey,
+    source: Arc<SharedSource>,
+    sources: SharedSources,
+) {
+    match client.subscribe_to_task(&key.0, source.history_length) {
+        Ok(updates) => {
+            let mut updates = std::pin::pin!(updates);
+            while let Some(item) = updates.next().await {
+                match item {
+                    Ok(item) => {
+                        let mut state = source.state.lock().unwrap();
+                        state.record(&item);
+                        // Nobody listening only means every stream just left
+                        let _ = state.sender.send(item);
+                    }
+                    Err(e) => warn!("Stream error (continuing): {}", e),
+                }
+            }
+        }
+        Err(e) => warn!("Failed to subscribe to task {}: {}", key.0, e),
+    }
+
+    // Later streams open a fresh subscription; the current ones end once the
+    // sender they were reading from is dropped
+    debug!("Shared subscription to task {} ended", key.0);
+    let mut sources = sources.lock().unwrap();
+    if sources
+        .get(&key)
+        .is_some_and(|current| Arc::ptr_eq(current, &source))
+    {
+        sources.remove(&key);
+    }
+    let (closed, _) = broadcast::channel(1);
+    source.state.lock().unwrap().sender = closed;
+}
+
+/// One stream's place in a shared subscription
+struct Joined {
+    key: SourceKey,
+    source: Arc<SharedSource>,
+    sources: SharedSources,
+    /// Items to deliver before reading the shared ones
+    pending: VecDeque<StreamItem>,
+    receiver: broadcast::Receiver<StreamItem>,
+}
+
+impl Joined {
+    /// The next item for this stream, or `None` once the subscription ended
+    async fn next(&mut self) -> Option<StreamItem> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            match self.receiver.recv().await {
+                Ok(item) => return Some(item),
+                // Skip what was missed and start again from the latest state
+                Err(RecvError::Lagged(_)) => {
+                    let state = self.source.state.lock().unwrap();
+                    self.pending = state.replay(self.source.history_length);
+                    self.receiver = state.sender.subscribe();
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl Drop for Joined {
+    fn drop(&mut self) {
+        let mut sources = self.sources.lock().unwrap();
+        let mut state = self.source.state.lock().unwrap();
+        state.subscribers -= 1;
+        if state.subscribers > 0 {
+            return;
+        }
+        if let Some(pump) = state.pump.take() {
+            pump.abort();
+        }
+        if sources
+            .get(&self.key)
+            .is_some_and(|current| Arc::ptr_eq(current, &self.source))
+        {
+            debug!("Closing shared subscription to task {}", self.key.0);
+            sources.remove(&self.key);
+        }
+    }
+}
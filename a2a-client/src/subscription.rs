@@ -0,0 +1,290 @@
+//! Task subscriptions that recover from missed events and dropped connections
+//!
+//! Events arrive numbered by the WebSocket server. A gap in the numbers means
+//! events were lost, so the subscription resyncs by fetching the task over
+//! HTTP and delivering it before carrying on. Numbers already seen are dropped.
+//!
+//! When the connection drops, the subscription reconnects and subscribes
+//! again. The server answers a new subscription with the current task and
+//! replays its latest status and artifacts; replays the client has already
+//! delivered are dropped, so consumers see each change once.
+//!
+//! A watched task is kept as a local copy instead: the subscription asks for
+//! the task's first snapshot whole and for what changed after that, and
+//! applies each change to the copy.
+
+use std::{collections::VecDeque, time::Duration};
+
+use a2a_rs::{
+    WebSocketClient,
+    adapter::{SequenceCheck, SequenceTracker, SequencedItem, SequencedStream},
+    domain::{A2AError, Artifact, Task, TaskSnapshotOptions, TaskStatus},
+    services::{AsyncA2AClient, StreamItem},
+};
+use futures::{Stream, StreamExt};
+use tracing::warn;
+
+use crate::WebA2AClient;
+
+/// Reconnects attempted in a row before the subscription gives up
+const MAX_RECONNECTS: u32 = 5;
+
+/// Pause before each reconnect
+const RECONNECT_DELAY: Duration = Duration::from_millis(200);
+
+/// State of one subscription, advanced one delivered item at a time
+struct TaskSubscription<'a> {
+    client: &'a WebA2AClient,
+    ws: WebSocketClient,
+    task_id: String,
+    history_length: Option<u32>,
+    events: Option<SequencedStream>,
+    tracker: SequenceTracker,
+    /// Items ready to deliver, such as a resync followed by the event that
+    /// revealed the gap
+    pending: VecDeque<StreamItem>,
+    /// The latest status delivered, so replayed statuses can be dropped
+    last_status: Option<TaskStatus>,
+    /// Artifacts of the last delivered task, which the server may replay
+    delivered_artifacts: Vec<Artifact>,
+    /// Connection failures since the last received event
+    failures: u32,
+    done: bool,
+}
+
+impl<'a> TaskSubscription<'a> {
+    fn new(
+        client: &'a WebA2AClient,
+        ws: WebSocketClient,
+        task_id: &str,
+        history_length: Option<u32>,
+    ) -> Self {
+        Self {
+            client,
+            ws,
+            task_id: task_id.to_string(),
+            history_length,
+            events: None,
+            tracker: SequenceTracker::new(),
+            pending: VecDeque::new(),
+            last_status: None,
+            delivered_artifacts: Vec::new(),
+            failures: 0,
+            done: false,
+        }
+    }
+
+    /// The next item to deliver, or `None` once the task has finished or the
+    /// subscription has given up
+    async fn next(&mut self) -> Option<Result<StreamItem, A2AError>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+
+            let events = match self.events.as_mut() {
+                Some(events) => events,
+                None => match self
+                    .ws
+                    .subscribe_sequenced(&self.task_id, self.history_length)
+                    .await
+                {
+                    Ok(events) => self.events.insert(events),
+                    Err(e) => {
+                        if let Err(e) = self.reconnect(e).await {
+                            return Some(Err(e));
+                        }
+                        continue;
+                    }
+                },
+            };
+
+            match events.next().await {
+                Some(Ok(SequencedItem { sequence, item })) => {
+                    self.failures = 0;
+                    match sequence.map(|sequence| self.tracker.observe(sequence)) {
+                        Some(SequenceCheck::Duplicate) => continue,
+                        Some(SequenceCheck::Gap { expected, received }) => {
+                            warn!(
+                                "Task {} subscription expected event {} but got {}, resyncing",
+                                self.task_id, expected, received
+                            );
+                            self.resync().await;
+                        }
+                        Some(SequenceCheck::InOrder) | None => {}
+                    }
+                    self.deliver(item);
+                }
+                // The agent refused the subscription; reconnecting will not help
+                Some(Err(e @ A2AError::JsonRpc { .. })) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                Some(Err(e)) => {
+                    if let Err(e) = self.reconnect(e).await {
+                        return Some(Err(e));
+                    }
+                }
+                None => {
+                    if let Err(e) = self
+                        .reconnect(A2AError::Internal("Subscription ended".to_string()))
+                        .await
+                    {
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fetch the task over HTTP and queue it for delivery
+    async fn resync(&mut self) {
+        match self
+            .client
+            .http
+            .get_task(&self.task_id, self.history_length)
+            .await
+        {
+            Ok(task) => self.deliver(StreamItem::Task(task)),
+            Err(e) => warn!("Failed to resync task {}: {}", self.task_id, e),
+        }
+    }
+
+    /// Queue an item unless it repeats what was already delivered
+    fn deliver(&mut self, item: StreamItem) {
+        match &item {
+            StreamItem::Task(task) => {
+                self.last_status = Some(task.status.clone());
+                self.delivered_artifacts = task.artifacts.clone().unwrap_or_default();
+                self.done = task.status.state.is_terminal();
+            }
+            StreamItem::StatusUpdate(update) => {
+                // Task changes may come without a new status, and applying
+                // one again is harmless
+                let replayed = update.task.is_none()
+                    && update.diff.is_none()
+                    && self.last_status.as_ref().is_some_and(|last| {
+                        update.status.timestamp.is_some()
+                            && update.status.timestamp <= last.timestamp
+                    });
+                if replayed {
+                    return;
+                }
+                self.last_status = Some(update.status.clone());
+                self.done = update.final_ || update.status.state.is_terminal();
+            }
+            StreamItem::ArtifactUpdate(update) => {
+                let replayed = self.delivered_artifacts.iter().position(|artifact| {
+                    serde_json::to_value(artifact).ok()
+                        == serde_json::to_value(&update.artifact).ok()
+                });
+                if let Some(index) = replayed {
+                    self.delivered_artifacts.remove(index);
+                    return;
+                }
+            }
+        }
+        self.pending.push_back(item);
+    }
+
+    /// Drop the connection and prepare a fresh one, failing once too many
+    /// attempts in a row have failed
+    async fn reconnect(&mut self, error: A2AError) -> Result<(), A2AError> {
+        self.events = None;
+        self.failures += 1;
+        if self.failures > MAX_RECONNECTS {
+            self.done = true;
+            return Err(error);
+        }
+        warn!(
+            "Task {} subscription failed ({}), reconnecting",
+            self.task_id, error
+        );
+        tokio::time::sleep(RECONNECT_DELAY).await;
+        self.ws = self.ws.detached();
+        self.tracker.reset();
+        Ok(())
+    }
+}
+
+impl WebA2AClient {
+    /// Subscribe to a task's updates over WebSocket.
+    ///
+    /// The stream resyncs when it detects missed events, drops repeated ones
+    /// and reconnects when the connection drops. It ends once the task reaches
+    /// a terminal state, or with an error after repeated failed reconnects.
+    /// Fails with [`A2AError::UnsupportedOperation`] when no WebSocket endpoint
+    /// is configured.
+    pub fn subscribe_to_task(
+        &self,
+        task_id: &str,
+        history_length: Option<u32>,
+    ) -> Result<impl Stream<Item = Result<StreamItem, A2AError>> + '_, A2AError> {
+        let ws = self.ws.as_ref().ok_or_else(|| {
+            A2AError::UnsupportedOperation("No WebSocket endpoint configured".to_string())
+        })?;
+        let subscription =
+            TaskSubscription::new(self, ws.as_ref().clone(), task_id, history_length);
+        Ok(futures::stream::unfold(
+            subscription,
+            |mut subscription| async move {
+                let item = subscription.next().await?;
+                Some((item, subscription))
+            },
+        ))
+    }
+
+    /// Follow a task's state over WebSocket, yielding the task as it is after
+    /// each change.
+    ///
+    /// Only the first snapshot of the task is sent whole; after that the
+    /// server sends what changed, which is applied to a local copy, so a task
+    /// with a long history costs little to follow. History is limited to the
+    /// `history_length` most recent entries, if set. Like
+    /// [`subscribe_to_task`](Self::subscribe_to_task), the stream resyncs,
+    /// reconnects and ends once the task reaches a terminal state.
+    pub fn watch_task(
+        &self,
+        task_id: &str,
+        history_length: Option<u32>,
+    ) -> Result<impl Stream<Item = Result<Task, A2AError>> + '_, A2AError> {
+        let ws = self.ws.as_ref().ok_or_else(|| {
+            A2AError::UnsupportedOperation("No WebSocket endpoint configured".to_string())
+        })?;
+        let mut snapshots = TaskSnapshotOptions::new().with_diffs();
+        if let Some(history_length) = history_length {
+            snapshots = snapshots.with_history_length(history_length);
+        }
+        let ws = ws.as_ref().clone().with_task_snapshots(snapshots);
+        let subscription = TaskSubscription::new(self, ws, task_id, history_length);
+        let items = futures::stream::unfold(subscription, |mut subscription| async move {
+            let item = subscription.next().await?;
+            Some((item, subscription))
+        });
+
+        let mut replica: Option<Task> = None;
+        Ok(items.filter_map(move |item| {
+            let task = match item {
+                Ok(StreamItem::Task(task)) => {
+                    replica = Some(task);
+                    replica.clone().map(Ok)
+                }
+                Ok(StreamItem::StatusUpdate(update)) => {
+                    update.apply_to(&mut replica);
+                    if let Some(task) = replica.as_mut() {
+                        *task = task.with_limited_history(history_length);
+                    }
+                    replica.clone().map(Ok)
+                }
+                // Artifact events are not kept by the task; its own changes
+                // come with status updates
+                Ok(StreamItem::ArtifactUpdate(_)) => None,
+                Err(e) => Some(Err(e)),
+            };
+            futures::future::ready(task)
+        }))
+    }
+}
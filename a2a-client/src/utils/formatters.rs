@@ -35,6 +35,7 @@ pub fn format_message_content(parts: &[Part]) -> String {
                     .unwrap_or("unnamed");
                 format!("[Data: {}]", name)
             }
+            Part::Unknown(_) => format!("[{} part]", part.kind()),
         })
         .collect::<Vec<_>>()
         .join("\n")
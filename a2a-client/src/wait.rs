@@ -0,0 +1,180 @@
+//! Waiting for a task to finish
+//!
+//! Callers that submit a task and only care about its outcome would otherwise
+//! poll `tasks/get` or follow a subscription by hand. The client follows the
+//! task over WebSocket when the agent streams, and polls over HTTP with a
+//! growing interval otherwise, until the task reaches a terminal state.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use a2a_rs::{
+    domain::{A2AError, Task},
+    services::{AsyncA2AClient, StreamItem},
+};
+use futures::StreamExt;
+use tracing::warn;
+
+use crate::{WebA2AClient, discovery::is_transport_error};
+
+/// Longest pause between polls, however long the wait
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Why waiting for a task to finish failed
+#[derive(Debug)]
+pub enum WaitError {
+    /// The task was still running when the timeout elapsed
+    TimedOut {
+        task_id: String,
+        timeout: Duration,
+        /// The task as last seen, if it was fetched at all
+        last: Option<Box<Task>>,
+    },
+    /// The agent could not report on the task, for example because it does
+    /// not exist
+    Agent(A2AError),
+}
+
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitError::TimedOut {
+                task_id, timeout, ..
+            } => write!(f, "Task {} did not finish within {:?}", task_id, timeout),
+            WaitError::Agent(error) => write!(f, "Failed to follow task: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for WaitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WaitError::TimedOut { .. } => None,
+            WaitError::Agent(error) => Some(error),
+        }
+    }
+}
+
+impl From<A2AError> for WaitError {
+    fn from(error: A2AError) -> Self {
+        WaitError::Agent(error)
+    }
+}
+
+/// How the wait left off
+enum Followed {
+    Finished(Task),
+    /// The subscription ended early; polling takes over
+    Failed,
+    TimedOut(Option<Task>),
+}
+
+impl WebA2AClient {
+    /// Wait until a task reaches a terminal state and return it.
+    ///
+    /// The task is followed over WebSocket when one is configured and the
+    /// agent streams; otherwise, or if the subscription fails, it is fetched
+    /// every `poll_interval`, doubling the pause after each poll up to a few
+    /// seconds. Fails with [`WaitError::TimedOut`] once `timeout` elapses.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use a2a_client::WebA2AClient;
+    ///
+    /// # async fn example(client: WebA2AClient) -> anyhow::Result<()> {
+    /// let task = client
+    ///     .wait_for_completion("expense-1", Duration::from_secs(60), Duration::from_millis(500))
+    ///     .await?;
+    /// println!("{:?}", task.status.state);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_completion(
+        &self,
+        task_id: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<Task, WaitError> {
+        let deadline = Instant::now() + timeout;
+        let timed_out = |last: Option<Task>| WaitError::TimedOut {
+            task_id: task_id.to_string(),
+            timeout,
+            last: last.map(Box::new),
+        };
+
+        let mut last = None;
+        if self.ws.is_some() && self.supports_streaming() {
+            match self.follow(task_id, deadline).await {
+                Followed::Finished(task) => return Ok(task),
+                Followed::TimedOut(task) => return Err(timed_out(task)),
+                Followed::Failed => {}
+            }
+        }
+
+        let mut interval = poll_interval;
+        loop {
+            match self.http.get_task(task_id, None).await {
+                Ok(task) if task.status.state.is_terminal() => return Ok(task),
+                Ok(task) => last = Some(task),
+                // The next poll may get through
+                Err(e) if is_transport_error(&e) => {
+                    warn!("Failed to poll task {}: {}", task_id, e)
+                }
+                Err(e) => return Err(WaitError::Agent(e)),
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(timed_out(last));
+            }
+            tokio::time::sleep(interval.min(remaining)).await;
+            interval = (interval * 2).min(MAX_POLL_INTERVAL.max(poll_interval));
+        }
+    }
+
+    /// Follow the task's subscription until it finishes or `deadline` passes
+    async fn follow(&self, task_id: &str, deadline: Instant) -> Followed {
+        let Ok(items) = self.subscribe_to_task(task_id, None) else {
+            return Followed::Failed;
+        };
+        let mut items = Box::pin(items);
+        let mut last = None;
+        let followed = tokio::time::timeout_at(deadline.into(), async {
+            while let Some(item) = items.next().await {
+                match item {
+                    Ok(StreamItem::Task(task)) if task.status.state.is_terminal() => {
+                        return Followed::Finished(task);
+                    }
+                    Ok(StreamItem::Task(task)) => last = Some(task),
+                    // Status events do not carry the whole task
+                    Ok(StreamItem::StatusUpdate(update)) if update.status.state.is_terminal() => {
+                        return match self.http.get_task(task_id, None).await {
+                            Ok(task) => Followed::Finished(task),
+                            Err(e) => {
+                                warn!("Failed to fetch finished task {}: {}", task_id, e);
+                                Followed::Failed
+                            }
+                        };
+                    }
+                    Ok(StreamItem::StatusUpdate(update)) => {
+                        if let Some(task) = last.as_mut() {
+                            task.status = update.status;
+                        }
+                    }
+                    Ok(StreamItem::ArtifactUpdate(_)) => {}
+                    Err(e) => {
+                        warn!("Subscription to task {} failed, polling: {}", task_id, e);
+                        return Followed::Failed;
+                    }
+                }
+            }
+            Followed::Failed
+        })
+        .await;
+        followed.unwrap_or(Followed::TimedOut(last))
+    }
+}
@@ -0,0 +1,173 @@
+//! Startup preflight for [`WebA2AClient`]
+//!
+//! The first request to an agent pays for DNS, TLS and the WebSocket
+//! handshake, and is also where a wrong credential first shows up. Warming up
+//! at startup moves that cost and those failures ahead of user traffic.
+
+use std::fmt;
+
+use a2a_rs::{
+    domain::{A2AError, AgentCard, ListTasksParams},
+    services::AsyncA2AClient,
+};
+
+use crate::{WebA2AClient, discovery::is_transport_error};
+
+const HTTP: &str = "HTTP";
+const WEBSOCKET: &str = "WebSocket";
+
+/// Why warming up a client failed
+#[derive(Debug)]
+pub enum WarmUpError {
+    /// The agent could not be reached over `transport`
+    Unreachable {
+        transport: &'static str,
+        error: A2AError,
+    },
+    /// The agent refused the client's credentials over `transport`
+    AuthRejected {
+        transport: &'static str,
+        error: A2AError,
+    },
+    /// The agent was reached but did not serve a usable agent card
+    Agent(A2AError),
+}
+
+impl fmt::Display for WarmUpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarmUpError::Unreachable { transport, error } => {
+                write!(f, "Agent unreachable over {}: {}", transport, error)
+            }
+            WarmUpError::AuthRejected { transport, error } => {
+                write!(
+                    f,
+                    "Agent rejected credentials over {}: {}",
+                    transport, error
+                )
+            }
+            WarmUpError::Agent(error) => write!(f, "Agent card unavailable: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for WarmUpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WarmUpError::Unreachable { error, .. }
+            | WarmUpError::AuthRejected { error, .. }
+            | WarmUpError::Agent(error) => Some(error),
+        }
+    }
+}
+
+impl WarmUpError {
+    fn http(error: A2AError) -> Self {
+        if is_auth_rejection(&error) {
+            WarmUpError::AuthRejected {
+                transport: HTTP,
+                error,
+            }
+        } else if is_transport_error(&error) {
+            WarmUpError::Unreachable {
+                transport: HTTP,
+                error,
+            }
+        } else {
+            WarmUpError::Agent(error)
+        }
+    }
+
+    fn websocket(error: A2AError) -> Self {
+        if is_auth_rejection(&error) {
+            WarmUpError::AuthRejected {
+                transport: WEBSOCKET,
+                error,
+            }
+        } else {
+            WarmUpError::Unreachable {
+                transport: WEBSOCKET,
+                error,
+            }
+        }
+    }
+}
+
+/// Whether the agent turned the request away for its credentials
+fn is_auth_rejection(error: &A2AError) -> bool {
+    let A2AError::Internal(message) = error else {
+        return false;
+    };
+    ["401", "403"].iter().any(|status| {
+        message.starts_with(&format!("HTTP response error: {} ", status))
+            || message.contains(&format!("HTTP error: {} ", status))
+    }) || message.contains("Authentication rejected")
+}
+
+/// Classify a failure to fetch the card of a card-configured client
+fn card_error(error: anyhow::Error) -> WarmUpError {
+    let cause = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<reqwest::Error>());
+    let message = format!("{:#}", error);
+    match cause {
+        Some(cause) if cause.is_connect() || cause.is_timeout() => WarmUpError::Unreachable {
+            transport: HTTP,
+            error: A2AError::Internal(message),
+        },
+        Some(cause)
+            if cause
+                .status()
+                .is_some_and(|status| matches!(status.as_u16(), 401 | 403)) =>
+        {
+            WarmUpError::AuthRejected {
+                transport: HTTP,
+                error: A2AError::Internal(message),
+            }
+        }
+        _ => WarmUpError::Agent(A2AError::Internal(message)),
+    }
+}
+
+impl WebA2AClient {
+    /// Connect to the agent ahead of the first real request.
+    ///
+    /// Fetches the agent card, remembering the capabilities it advertises, and
+    /// sends an authenticated request over HTTP, which leaves a pooled
+    /// connection for later requests. When a WebSocket endpoint is configured,
+    /// a connection is opened and authenticated, then closed again, as
+    /// WebSocket requests open their own connections.
+    ///
+    /// Safe to call at startup: it reads one page of at most one task and
+    /// changes nothing on the agent.
+    pub async fn warm_up(&mut self) -> Result<AgentCard, WarmUpError> {
+        let card = match &self.card {
+            Some(source) => source.cache.get().await.map_err(card_error)?,
+            None => self
+                .http
+                .get_agent_card()
+                .await
+                .map_err(WarmUpError::http)?,
+        };
+        self.capabilities = Some(card.capabilities.clone());
+
+        // The card may be public, so a JSON-RPC call is what proves the
+        // credentials. Any answer from the agent itself will do.
+        let probe = ListTasksParams {
+            page_size: Some(1),
+            ..Default::default()
+        };
+        if let Err(e) = self.http.list_tasks(&probe).await {
+            if is_auth_rejection(&e) || is_transport_error(&e) {
+                return Err(WarmUpError::http(e));
+            }
+        }
+
+        if let Some(ws) = &self.ws {
+            ws.check_connection()
+                .await
+                .map_err(WarmUpError::websocket)?;
+        }
+        Ok(card)
+    }
+}
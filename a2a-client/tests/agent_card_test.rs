@@ -0,0 +1,147 @@
+//! Tests for configuring a client purely from a served agent card
+
+mod common;
+
+use std::time::Duration;
+
+use a2a_client::{
+    WebA2AClient,
+    discovery::{CardAuth, CardTransports},
+};
+use a2a_rs::{
+    adapter::{BearerTokenAuthenticator, HttpServer, InMemoryTaskStorage, SimpleAgentInfo},
+    domain::{ListTasksParams, SecurityScheme},
+    port::AsyncTaskManager,
+    services::AgentInfoProvider,
+};
+use common::{processor, serve_as, wait_until_reachable};
+
+fn agent_info(http_port: u16, ws_port: u16) -> SimpleAgentInfo {
+    SimpleAgentInfo::new(
+        "Card Agent".to_string(),
+        format!("http://127.0.0.1:{}", http_port),
+    )
+    .add_interface(
+        "WEBSOCKET".to_string(),
+        format!("ws://127.0.0.1:{}", ws_port),
+    )
+}
+
+#[tokio::test]
+async fn test_client_prefers_advertised_websocket() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("card-task", "ctx-card").await.unwrap();
+    serve_as(
+        &storage,
+        agent_info(8306, 8307),
+        "127.0.0.1:8306",
+        Some("127.0.0.1:8307"),
+    )
+    .await;
+
+    let client = WebA2AClient::from_agent_card("http://127.0.0.1:8306")
+        .await
+        .unwrap();
+    assert!(client.has_websocket());
+
+    let result = client
+        .list_tasks(&ListTasksParams::default())
+        .await
+        .unwrap();
+    assert!(result.tasks.iter().any(|task| task.id == "card-task"));
+
+    // The card is cached and exposed for inspection
+    let cache = client.agent_card().unwrap();
+    assert_eq!(
+        cache.card_url(),
+        "http://127.0.0.1:8306/.well-known/agent-card.json"
+    );
+    let card = cache.get().await.unwrap();
+    assert_eq!(card.name, "Card Agent");
+    assert_eq!(
+        CardTransports::from_card(&card).unwrap(),
+        CardTransports {
+            http_url: "http://127.0.0.1:8306".to_string(),
+            ws_url: Some("ws://127.0.0.1:8307".to_string()),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_unreachable_websocket_falls_back_to_http() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("http-task", "ctx-card").await.unwrap();
+    // Port 8309 is advertised but nothing listens on it
+    serve_as(&storage, agent_info(8308, 8309), "127.0.0.1:8308", None).await;
+
+    let client = WebA2AClient::from_agent_card("http://127.0.0.1:8308/agent-card")
+        .await
+        .unwrap();
+    assert!(!client.has_websocket());
+
+    let result = client
+        .list_tasks(&ListTasksParams::default())
+        .await
+        .unwrap();
+    assert!(result.tasks.iter().any(|task| task.id == "http-task"));
+}
+
+#[tokio::test]
+async fn test_declared_bearer_scheme_configures_auth() {
+    let storage = InMemoryTaskStorage::new();
+    storage
+        .create_task("secure-task", "ctx-card")
+        .await
+        .unwrap();
+
+    let agent_info = SimpleAgentInfo::new(
+        "Secure Agent".to_string(),
+        "http://127.0.0.1:8310".to_string(),
+    )
+    .with_security_scheme(
+        "bearer".to_string(),
+        SecurityScheme::Http {
+            scheme: "bearer".to_string(),
+            bearer_format: None,
+            description: None,
+        },
+    );
+    let card = agent_info.get_agent_card().await.unwrap();
+    assert_eq!(CardAuth::from_card(&card, true).unwrap(), CardAuth::Bearer);
+    let err = CardAuth::from_card(&card, false).unwrap_err();
+    assert!(err.to_string().contains("no credential was provided"));
+
+    let server = HttpServer::with_auth(
+        processor(&storage, &agent_info),
+        agent_info.clone(),
+        "127.0.0.1:8310".to_string(),
+        BearerTokenAuthenticator::new(vec!["secret-token".to_string()]),
+    );
+    tokio::spawn(async move { server.start().await });
+    wait_until_reachable("127.0.0.1:8310").await;
+
+    // This agent also protects its card, so discovery needs the credential too
+    let err = WebA2AClient::agent_card_builder("http://127.0.0.1:8310")
+        .connect()
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Failed to fetch agent card"));
+
+    let client = WebA2AClient::agent_card_builder("http://127.0.0.1:8310")
+        .credential("secret-token")
+        .card_ttl(Duration::from_millis(50))
+        .connect()
+        .await
+        .unwrap();
+    let result = client
+        .list_tasks(&ListTasksParams::default())
+        .await
+        .unwrap();
+    assert!(result.tasks.iter().any(|task| task.id == "secure-task"));
+
+    // An expired card is fetched again with the same credential
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let card = client.agent_card().unwrap().get().await.unwrap();
+    assert_eq!(card.name, "Secure Agent");
+}
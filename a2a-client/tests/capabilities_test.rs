@@ -0,0 +1,120 @@
+//! Tests for capability negotiation before optional operations
+
+mod common;
+
+use a2a_client::WebA2AClient;
+use a2a_rs::{
+    adapter::{InMemoryTaskStorage, SimpleAgentInfo},
+    domain::{
+        A2AError, AgentCapabilities, FileContent, Message, Part, PushNotificationConfig,
+        TaskPushNotificationConfig,
+    },
+    port::AsyncTaskManager,
+    services::AgentInfoProvider,
+};
+use common::serve_as;
+
+fn push_config(task_id: &str) -> TaskPushNotificationConfig {
+    TaskPushNotificationConfig {
+        task_id: task_id.to_string(),
+        push_notification_config: PushNotificationConfig {
+            id: None,
+            url: "http://127.0.0.1:9/webhook".to_string(),
+            token: None,
+            authentication: None,
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_push_registration_short_circuits_without_capability() {
+    // Nothing listens here, so any request that is actually sent fails in transport
+    let capabilities = AgentCapabilities {
+        streaming: true,
+        ..Default::default()
+    };
+    let client = WebA2AClient::builder("http://127.0.0.1:8316")
+        .capabilities(capabilities)
+        .build();
+    assert!(!client.supports_push_notifications());
+
+    let err = client
+        .set_task_push_notification(&push_config("task-1"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, A2AError::PushNotificationNotSupported));
+
+    // Without known capabilities the request is attempted and the transport fails
+    let unknown = WebA2AClient::new_http("http://127.0.0.1:8316".to_string());
+    assert!(unknown.supports_push_notifications());
+    let err = unknown
+        .set_task_push_notification(&push_config("task-1"))
+        .await
+        .unwrap_err();
+    assert!(!matches!(err, A2AError::PushNotificationNotSupported));
+}
+
+#[tokio::test]
+async fn test_capabilities_come_from_the_agent_card() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("cap-task", "ctx-cap").await.unwrap();
+    let agent_info = SimpleAgentInfo::new(
+        "Text Agent".to_string(),
+        "http://127.0.0.1:8315".to_string(),
+    )
+    .with_streaming()
+    .with_supported_part_kinds(vec!["text".to_string()]);
+
+    let card = serde_json::to_value(agent_info.get_agent_card().await.unwrap()).unwrap();
+    assert_eq!(card["capabilities"]["supportedPartKinds"][0], "text");
+    assert_eq!(card["capabilities"]["pushNotifications"], false);
+
+    serve_as(&storage, agent_info, "127.0.0.1:8315", None).await;
+
+    // Card-configured clients know the capabilities up front
+    let client = WebA2AClient::from_agent_card("http://127.0.0.1:8315")
+        .await
+        .unwrap();
+    let capabilities = client.capabilities().unwrap();
+    assert!(capabilities.streaming);
+    assert!(!capabilities.push_notifications);
+    let err = client
+        .set_task_push_notification(&push_config("cap-task"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, A2AError::PushNotificationNotSupported));
+
+    // Other clients discover them from the card served next to the endpoint
+    let mut client = WebA2AClient::new_http("http://127.0.0.1:8315".to_string());
+    assert!(client.capabilities().is_none());
+    assert!(client.discover_capabilities().await.unwrap().streaming);
+    assert!(!client.supports_push_notifications());
+
+    // Part kinds the agent does not accept are refused before sending
+    let mut message = Message::user_text("See attached".to_string(), "msg-cap".to_string());
+    message.parts.push(Part::File {
+        file: FileContent {
+            name: Some("receipt.pdf".to_string()),
+            mime_type: Some("application/pdf".to_string()),
+            bytes: Some("JVBERi0=".to_string()),
+            uri: None,
+        },
+        metadata: None,
+    });
+    let err = client
+        .send_task_message("cap-task", &message, None, None)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("does not accept file parts"),
+        "{}",
+        err
+    );
+
+    let text = Message::user_text("Plain text".to_string(), "msg-text".to_string());
+    let task = client
+        .send_task_message("cap-task", &text, None, None)
+        .await
+        .unwrap();
+    assert_eq!(task.id, "cap-task");
+}
@@ -0,0 +1,79 @@
+//! Common test utilities
+
+use std::time::Duration;
+
+use a2a_rs::adapter::{
+    DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, SimpleAgentInfo, WebSocketServer,
+    business::DefaultMessageHandler,
+};
+use tokio::net::TcpStream;
+
+/// A request processor keeping tasks and push notification configs in an
+/// in-memory storage
+#[allow(dead_code)]
+pub type Processor = DefaultRequestProcessor<
+    DefaultMessageHandler<InMemoryTaskStorage>,
+    InMemoryTaskStorage,
+    InMemoryTaskStorage,
+    SimpleAgentInfo,
+>;
+
+/// A request processor over `storage`, answering as `agent_info`
+#[allow(dead_code)]
+pub fn processor(storage: &InMemoryTaskStorage, agent_info: &SimpleAgentInfo) -> Processor {
+    DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    )
+}
+
+/// Wait for a server to accept connections at `address`
+#[allow(dead_code)]
+pub async fn wait_until_reachable(address: &str) {
+    for _ in 0..50 {
+        if TcpStream::connect(address).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("{} never became reachable", address);
+}
+
+/// Serve `storage` over HTTP at `http_address` and, if given, over
+/// WebSocket at `ws_address`, returning once they are reachable
+#[allow(dead_code)]
+pub async fn serve(storage: &InMemoryTaskStorage, http_address: &str, ws_address: Option<&str>) {
+    let agent_info =
+        SimpleAgentInfo::new("Test Agent".to_string(), format!("http://{}", http_address));
+    serve_as(storage, agent_info, http_address, ws_address).await;
+}
+
+/// Like [`serve`], answering as `agent_info`
+#[allow(dead_code)]
+pub async fn serve_as(
+    storage: &InMemoryTaskStorage,
+    agent_info: SimpleAgentInfo,
+    http_address: &str,
+    ws_address: Option<&str>,
+) {
+    let server = HttpServer::new(
+        processor(storage, &agent_info),
+        agent_info.clone(),
+        http_address.to_string(),
+    );
+    tokio::spawn(async move { server.start().await });
+    wait_until_reachable(http_address).await;
+
+    if let Some(ws_address) = ws_address {
+        let server = WebSocketServer::new(
+            processor(storage, &agent_info),
+            agent_info,
+            storage.clone(),
+            ws_address.to_string(),
+        );
+        tokio::spawn(async move { server.start().await });
+        wait_until_reachable(ws_address).await;
+    }
+}
@@ -0,0 +1,123 @@
+//! Tests for streaming file artifacts in chunks and reassembling them
+
+use a2a_client::components::{ChunkError, FileAssembler, FileChunk, FileChunker};
+use a2a_rs::domain::{Artifact, FileContent, Part, TaskArtifactUpdateEvent};
+use base64::Engine;
+
+fn report_bytes() -> Vec<u8> {
+    (0..1000u32).map(|i| (i * 7 % 256) as u8).collect()
+}
+
+/// One update of a PDF report being generated
+fn update(bytes: &[u8], append: Option<bool>, last_chunk: Option<bool>) -> TaskArtifactUpdateEvent {
+    TaskArtifactUpdateEvent {
+        task_id: "expense".to_string(),
+        context_id: "ctx-1".to_string(),
+        kind: "artifact-update".to_string(),
+        artifact: Artifact {
+            artifact_id: "report".to_string(),
+            name: None,
+            description: None,
+            parts: vec![Part::File {
+                file: FileContent {
+                    name: Some("report.pdf".to_string()),
+                    mime_type: Some("application/pdf".to_string()),
+                    bytes: Some(base64::engine::general_purpose::STANDARD.encode(bytes)),
+                    uri: None,
+                },
+                metadata: None,
+            }],
+            metadata: None,
+            extensions: None,
+            created_at: None,
+        },
+        append,
+        last_chunk,
+        metadata: None,
+    }
+}
+
+/// Send chunks through JSON, as the SSE stream does
+fn over_the_wire(chunks: Vec<FileChunk>) -> Vec<FileChunk> {
+    chunks
+        .iter()
+        .map(|chunk| serde_json::from_str(&serde_json::to_string(chunk).unwrap()).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_multi_chunk_artifact_reassembles_into_the_original_bytes() {
+    let bytes = report_bytes();
+    let mut chunker = FileChunker::new(96);
+
+    // The agent produces the report in three updates
+    let mut chunks = Vec::new();
+    for (piece, append, last_chunk) in [
+        (&bytes[..400], None, Some(false)),
+        (&bytes[400..700], Some(true), Some(false)),
+        (&bytes[700..], Some(true), Some(true)),
+    ] {
+        chunks.extend(chunker.chunks(&update(piece, append, last_chunk)).unwrap());
+    }
+    let chunks = over_the_wire(chunks);
+
+    let indexes: Vec<u64> = chunks.iter().map(|chunk| chunk.index).collect();
+    assert_eq!(indexes, (0..chunks.len() as u64).collect::<Vec<_>>());
+    assert!(chunks.len() > 3);
+    let finals: Vec<bool> = chunks.iter().map(|chunk| chunk.final_).collect();
+    assert_eq!(finals.iter().filter(|final_| **final_).count(), 1);
+    assert!(finals.last().unwrap());
+
+    let mut assembler = FileAssembler::default();
+    let (last, rest) = chunks.split_last().unwrap();
+    for chunk in rest {
+        assert_eq!(assembler.push(chunk).unwrap(), None);
+    }
+    let file = assembler.push(last).unwrap().unwrap();
+    assert_eq!(file.artifact_id, "report");
+    assert_eq!(file.name.as_deref(), Some("report.pdf"));
+    assert_eq!(file.mime_type.as_deref(), Some("application/pdf"));
+    assert_eq!(file.bytes, bytes);
+}
+
+#[test]
+fn test_artifacts_other_than_inline_files_are_not_chunked() {
+    let mut chunker = FileChunker::default();
+    let mut text = update(b"", None, None);
+    text.artifact.parts = vec![Part::text("Summary".to_string())];
+    assert!(chunker.chunks(&text).is_none());
+
+    let mut linked = update(b"", None, None);
+    if let Part::File { file, .. } = &mut linked.artifact.parts[0] {
+        file.bytes = None;
+        file.uri = Some("https://example.com/report.pdf".to_string());
+    }
+    assert!(chunker.chunks(&linked).is_none());
+}
+
+#[test]
+fn test_assembler_refuses_oversized_and_out_of_order_files() {
+    let bytes = report_bytes();
+    let chunks = FileChunker::new(96)
+        .chunks(&update(&bytes, None, None))
+        .unwrap();
+
+    let mut assembler = FileAssembler::new(500);
+    let refused = chunks
+        .iter()
+        .map(|chunk| assembler.push(chunk))
+        .find_map(Result::err);
+    assert_eq!(refused, Some(ChunkError::TooLarge { limit: 500 }));
+
+    let mut assembler = FileAssembler::default();
+    assembler.push(&chunks[0]).unwrap();
+    assert_eq!(
+        assembler.push(&chunks[2]),
+        Err(ChunkError::OutOfOrder {
+            expected: 1,
+            received: 2
+        })
+    );
+    // The partial file was dropped, so continuing without a restart fails too
+    assert!(assembler.push(&chunks[1]).is_err());
+}
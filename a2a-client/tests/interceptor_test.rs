@@ -0,0 +1,179 @@
+//! Tests for request interceptors registered on the client builder
+
+mod common;
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use a2a_client::{ClientRequest, RequestInterceptor, WebA2AClient};
+use a2a_rs::{
+    adapter::{
+        BearerTokenAuthenticator, HttpServer, InMemoryTaskStorage, SimpleAgentInfo, WebSocketServer,
+    },
+    application::JSONRPCResponse,
+    domain::{A2AError, Task},
+    port::AsyncTaskManager,
+    services::AsyncA2AClient,
+};
+use async_trait::async_trait;
+use common::{processor, wait_until_reachable};
+
+/// Adds a fixed header to every request
+struct HeaderInterceptor {
+    name: &'static str,
+    value: &'static str,
+}
+
+#[async_trait]
+impl RequestInterceptor for HeaderInterceptor {
+    async fn before_request(
+        &self,
+        request: &mut ClientRequest,
+    ) -> Result<Option<JSONRPCResponse>, A2AError> {
+        request
+            .headers
+            .insert(self.name.to_string(), self.value.to_string());
+        Ok(None)
+    }
+}
+
+/// Counts requests and responses
+#[derive(Clone, Default)]
+struct CountingInterceptor {
+    requests: Arc<AtomicUsize>,
+    failures: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl RequestInterceptor for CountingInterceptor {
+    async fn before_request(
+        &self,
+        _request: &mut ClientRequest,
+    ) -> Result<Option<JSONRPCResponse>, A2AError> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        Ok(None)
+    }
+
+    async fn after_response(
+        &self,
+        _request: &ClientRequest,
+        response: &mut Result<JSONRPCResponse, A2AError>,
+    ) {
+        if response.is_err() {
+            self.failures.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Answers `tasks/get` from a fixed task without contacting the agent
+struct CachedTask(Task);
+
+#[async_trait]
+impl RequestInterceptor for CachedTask {
+    async fn before_request(
+        &self,
+        request: &mut ClientRequest,
+    ) -> Result<Option<JSONRPCResponse>, A2AError> {
+        if request.method() != "tasks/get" {
+            return Ok(None);
+        }
+        Ok(Some(JSONRPCResponse::success(
+            None,
+            serde_json::to_value(&self.0)?,
+        )))
+    }
+}
+
+#[tokio::test]
+async fn test_header_interceptor_authenticates_requests() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    let agent_info = SimpleAgentInfo::new(
+        "Guarded Agent".to_string(),
+        "http://127.0.0.1:8342".to_string(),
+    );
+    let server = HttpServer::with_auth(
+        processor(&storage, &agent_info),
+        agent_info,
+        "127.0.0.1:8342".to_string(),
+        BearerTokenAuthenticator::new(vec!["secret".to_string()]),
+    );
+    tokio::spawn(async move { server.start().await });
+    wait_until_reachable("127.0.0.1:8342").await;
+
+    let anonymous = WebA2AClient::builder("http://127.0.0.1:8342").build();
+    assert!(anonymous.http.get_task("expense", None).await.is_err());
+
+    let client = WebA2AClient::builder("http://127.0.0.1:8342")
+        .interceptor(HeaderInterceptor {
+            name: "authorization",
+            value: "Bearer secret",
+        })
+        .build();
+    let task = client.http.get_task("expense", None).await.unwrap();
+    assert_eq!(task.id, "expense");
+}
+
+#[tokio::test]
+async fn test_counting_interceptor_sees_http_and_websocket_rpcs() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    let agent_info = SimpleAgentInfo::new(
+        "Counted Agent".to_string(),
+        "http://127.0.0.1:8343".to_string(),
+    );
+    let http = HttpServer::new(
+        processor(&storage, &agent_info),
+        agent_info.clone(),
+        "127.0.0.1:8343".to_string(),
+    );
+    tokio::spawn(async move { http.start().await });
+    let ws = WebSocketServer::new(
+        processor(&storage, &agent_info),
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8344".to_string(),
+    );
+    tokio::spawn(async move { ws.start().await });
+    wait_until_reachable("127.0.0.1:8343").await;
+    wait_until_reachable("127.0.0.1:8344").await;
+
+    let counter = CountingInterceptor::default();
+    let client = WebA2AClient::builder("http://127.0.0.1:8343")
+        .websocket("ws://127.0.0.1:8344")
+        .interceptor(counter.clone())
+        .build();
+
+    client.http.get_task("expense", None).await.unwrap();
+    client
+        .websocket()
+        .unwrap()
+        .get_task("expense", None)
+        .await
+        .unwrap();
+    assert_eq!(counter.requests.load(Ordering::SeqCst), 2);
+    assert_eq!(counter.failures.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_interceptor_can_short_circuit() {
+    let counter = CountingInterceptor::default();
+    // Nothing listens here, so only answered requests can succeed
+    let client = WebA2AClient::builder("http://127.0.0.1:9")
+        .interceptor(counter.clone())
+        .interceptor(CachedTask(Task::new(
+            "cached".to_string(),
+            "ctx-1".to_string(),
+        )))
+        .build();
+
+    let task = client.http.get_task("cached", None).await.unwrap();
+    assert_eq!(task.id, "cached");
+    assert!(client.http.cancel_task("cached").await.is_err());
+
+    // The outer interceptor saw both requests, and only the cancel failed
+    assert_eq!(counter.requests.load(Ordering::SeqCst), 2);
+    assert_eq!(counter.failures.load(Ordering::SeqCst), 1);
+}
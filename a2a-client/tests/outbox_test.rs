@@ -0,0 +1,159 @@
+//! Tests for queueing messages while the agent is unreachable
+
+use std::{sync::Arc, time::Duration};
+
+use a2a_client::outbox::{DeliveryState, FileOutboxStore, MemoryOutboxStore, Outbox, SendOutcome};
+use a2a_rs::{
+    HttpClient,
+    adapter::{
+        DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, SimpleAgentInfo,
+        business::DefaultMessageHandler,
+    },
+    domain::{Message, Role},
+    port::AsyncTaskManager,
+    services::AsyncA2AClient,
+};
+use tokio::net::TcpStream;
+
+/// Start an agent on `port`, returning its storage
+async fn start_agent(port: u16) -> InMemoryTaskStorage {
+    let storage = InMemoryTaskStorage::new();
+    let address = format!("127.0.0.1:{}", port);
+    let agent_info =
+        SimpleAgentInfo::new("Expense Agent".to_string(), format!("http://{}", address));
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, address.clone());
+    tokio::spawn(async move { server.start().await });
+    for _ in 0..50 {
+        if TcpStream::connect(&address).await.is_ok() {
+            return storage;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("{} never became reachable", address);
+}
+
+fn expense(id: &str) -> Message {
+    Message::user_text("Lunch with a client, $42".to_string(), id.to_string())
+}
+
+/// How many times the message with `message_id` is in the task's history
+async fn deliveries(storage: &InMemoryTaskStorage, task_id: &str, message_id: &str) -> usize {
+    storage
+        .get_task(task_id, None)
+        .await
+        .unwrap()
+        .history
+        .unwrap_or_default()
+        .iter()
+        .filter(|message| message.message_id == message_id)
+        .count()
+}
+
+#[tokio::test]
+async fn test_send_during_an_outage_is_delivered_once_connectivity_returns() {
+    let client = HttpClient::new("http://127.0.0.1:8354".to_string());
+    let outbox = Arc::new(Outbox::open(client, MemoryOutboxStore::new()).unwrap());
+    let mut updates = outbox.subscribe();
+
+    // Nothing is listening yet
+    let outcome = outbox.send("expense", &expense("m-1"), None).await.unwrap();
+    assert!(matches!(&outcome, SendOutcome::Queued(key) if key == "m-1"));
+    assert_eq!(outbox.state("m-1"), Some(DeliveryState::Queued));
+    assert_eq!(updates.recv().await.unwrap().state, DeliveryState::Queued);
+
+    // A later message for the same task waits behind the first
+    let outcome = outbox.send("expense", &expense("m-2"), None).await.unwrap();
+    assert!(matches!(outcome, SendOutcome::Queued(_)));
+    assert_eq!(updates.recv().await.unwrap().state, DeliveryState::Queued);
+
+    let retry = outbox.spawn_retry(Duration::from_millis(50));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(outbox.state("m-1"), Some(DeliveryState::Queued));
+    assert!(outbox.entries()[0].attempts > 0);
+
+    let storage = start_agent(8354).await;
+    for key in ["m-1", "m-2"] {
+        let update = tokio::time::timeout(Duration::from_secs(5), updates.recv())
+            .await
+            .expect("queued message never delivered")
+            .unwrap();
+        assert_eq!(update.idempotency_key, key);
+        assert_eq!(update.state, DeliveryState::Sent);
+    }
+    retry.abort();
+
+    assert_eq!(deliveries(&storage, "expense", "m-1").await, 1);
+    assert_eq!(deliveries(&storage, "expense", "m-2").await, 1);
+    let history = storage
+        .get_task("expense", None)
+        .await
+        .unwrap()
+        .history
+        .unwrap();
+    let order: Vec<_> = history
+        .iter()
+        .filter(|message| message.role == Role::User)
+        .map(|message| message.message_id.as_str())
+        .collect();
+    assert_eq!(order, vec!["m-1", "m-2"]);
+
+    // Once the agent is reachable, sends go straight through
+    let outcome = outbox.send("expense", &expense("m-3"), None).await.unwrap();
+    assert!(matches!(outcome, SendOutcome::Sent(_)));
+    assert_eq!(outbox.remove_finished().unwrap(), 2);
+    assert!(outbox.entries().is_empty());
+}
+
+#[tokio::test]
+async fn test_queued_send_survives_a_restart() {
+    let path = std::env::temp_dir().join(format!("outbox-{}.json", uuid::Uuid::new_v4()));
+
+    let client = HttpClient::new("http://127.0.0.1:8355".to_string());
+    let outbox = Outbox::open(client, FileOutboxStore::new(&path)).unwrap();
+    outbox
+        .send("expense", &expense("m-1"), Some("session-1"))
+        .await
+        .unwrap();
+    drop(outbox);
+
+    let storage = start_agent(8355).await;
+    let client = HttpClient::new("http://127.0.0.1:8355".to_string());
+    let outbox = Outbox::open(client, FileOutboxStore::new(&path)).unwrap();
+    assert_eq!(outbox.state("m-1"), Some(DeliveryState::Queued));
+    assert_eq!(outbox.entries()[0].session_id.as_deref(), Some("session-1"));
+
+    assert_eq!(outbox.flush().await.unwrap(), 1);
+    assert_eq!(deliveries(&storage, "expense", "m-1").await, 1);
+
+    // The delivered state was saved too
+    let client = HttpClient::new("http://127.0.0.1:8355".to_string());
+    let reopened = Outbox::open(client, FileOutboxStore::new(&path)).unwrap();
+    assert_eq!(reopened.state("m-1"), Some(DeliveryState::Sent));
+    assert_eq!(reopened.flush().await.unwrap(), 0);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_message_that_already_reached_the_agent_is_not_resent() {
+    let client = HttpClient::new("http://127.0.0.1:8356".to_string());
+    let outbox = Outbox::open(client, MemoryOutboxStore::new()).unwrap();
+    outbox.send("expense", &expense("m-1"), None).await.unwrap();
+
+    // The first attempt reached the agent after all; only its response was lost
+    let storage = start_agent(8356).await;
+    outbox
+        .client()
+        .send_task_message("expense", &expense("m-1"), None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(outbox.flush().await.unwrap(), 1);
+    assert_eq!(outbox.state("m-1"), Some(DeliveryState::Sent));
+    assert_eq!(deliveries(&storage, "expense", "m-1").await, 1);
+}
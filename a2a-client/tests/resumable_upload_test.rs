@@ -0,0 +1,159 @@
+//! Tests for resumable, chunked file uploads
+
+use std::time::Duration;
+
+use a2a_client::components::{UploadConfig, UploadError, UploadStatus, UploadStore, upload_routes};
+use base64::Engine;
+use serde_json::json;
+use tokio::{io::AsyncWriteExt, net::TcpStream};
+
+const RECEIPT: &[u8] = b"%PDF-1.7 receipt: taxi 42.50 USD";
+
+async fn serve(store: UploadStore, port: u16) -> String {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .unwrap();
+    tokio::spawn(async move { axum::serve(listener, upload_routes::<()>(store)).await });
+    format!("127.0.0.1:{}", port)
+}
+
+async fn status(client: &reqwest::Client, address: &str, upload_id: &str) -> UploadStatus {
+    client
+        .get(format!("http://{}/uploads/{}", address, upload_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_upload_resumes_after_interrupted_chunk() {
+    let store = UploadStore::new(UploadConfig::default());
+    let address = serve(store.clone(), 8314).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/uploads", address))
+        .json(&json!({
+            "fileName": "receipt.pdf",
+            "mimeType": "application/pdf",
+            "totalSize": RECEIPT.len()
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let upload: UploadStatus = response.json().await.unwrap();
+    assert_eq!(upload.offset, 0);
+    let upload_id = upload.upload_id;
+
+    // The first chunk arrives intact
+    let response = client
+        .put(format!("http://{}/uploads/{}?offset=0", address, upload_id))
+        .body(RECEIPT[..10].to_vec())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.json::<UploadStatus>().await.unwrap().offset, 10);
+
+    // The second chunk is cut off after 5 of its 12 bytes
+    let mut socket = TcpStream::connect(&address).await.unwrap();
+    let head = format!(
+        "PUT /uploads/{}?offset=10 HTTP/1.1\r\nHost: {}\r\nContent-Length: 12\r\n\r\n",
+        upload_id, address
+    );
+    socket.write_all(head.as_bytes()).await.unwrap();
+    socket.write_all(&RECEIPT[10..15]).await.unwrap();
+    socket.flush().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    drop(socket);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The bytes that made it are kept, and replaying from a stale offset is refused
+    let resumed = status(&client, &address, &upload_id).await;
+    assert_eq!(resumed.offset, 15);
+    assert!(!resumed.is_complete());
+    let response = client
+        .put(format!(
+            "http://{}/uploads/{}?offset=10",
+            address, upload_id
+        ))
+        .body(RECEIPT[10..].to_vec())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["offset"], 15);
+
+    // Finalizing early fails; resuming from the reported offset completes the file
+    assert_eq!(
+        store.finalize(&upload_id).unwrap_err(),
+        UploadError::Incomplete {
+            received: 15,
+            total: RECEIPT.len() as u64,
+        }
+    );
+    let response = client
+        .put(format!(
+            "http://{}/uploads/{}?offset={}",
+            address, upload_id, resumed.offset
+        ))
+        .body(RECEIPT[15..].to_vec())
+        .send()
+        .await
+        .unwrap();
+    assert!(response.json::<UploadStatus>().await.unwrap().is_complete());
+
+    let file = store.finalize(&upload_id).unwrap();
+    assert_eq!(file.name.as_deref(), Some("receipt.pdf"));
+    assert_eq!(file.mime_type.as_deref(), Some("application/pdf"));
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(file.bytes.unwrap())
+        .unwrap();
+    assert_eq!(bytes, RECEIPT);
+
+    // A finalized upload is gone
+    assert!(matches!(
+        store.status(&upload_id),
+        Err(UploadError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_upload_limits_and_expiry() {
+    let store = UploadStore::new(
+        UploadConfig::new()
+            .with_max_total_size(16)
+            .with_ttl(Duration::from_millis(50)),
+    );
+
+    assert_eq!(
+        store.initiate(None, None, 17).unwrap_err(),
+        UploadError::TooLarge { limit: 16 }
+    );
+
+    // Chunks may not run past the declared size
+    let upload = store.initiate(None, None, 8).unwrap();
+    assert_eq!(
+        store
+            .append(&upload.upload_id, 0, b"123456789")
+            .unwrap_err(),
+        UploadError::TooLarge { limit: 8 }
+    );
+    store.append(&upload.upload_id, 0, b"1234").unwrap();
+
+    // Idle uploads expire
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+        store.append(&upload.upload_id, 4, b"5678").unwrap_err(),
+        UploadError::NotFound(upload.upload_id.clone())
+    );
+
+    let stale = store.initiate(None, None, 8).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(store.purge_expired(), 1);
+    assert!(store.finalize(&stale.upload_id).is_err());
+}
@@ -0,0 +1,198 @@
+//! Tests for the shared card and token cache
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use a2a_client::{
+    cache::SharedCache,
+    oauth::{ClientCredentials, TokenCache},
+};
+use futures::future::join_all;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// A fetch that counts its calls and takes a while, returning the call number
+fn counting_fetch(
+    calls: &Arc<AtomicUsize>,
+    ttl: Duration,
+) -> impl FnOnce() -> futures::future::BoxFuture<'static, Result<(usize, Duration), String>> + use<>
+{
+    let calls = calls.clone();
+    move || {
+        Box::pin(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok((calls.fetch_add(1, Ordering::SeqCst) + 1, ttl))
+        })
+    }
+}
+
+/// Serve a token endpoint that answers slowly, issuing `token-<n>` for the
+/// n-th request with the given lifetime
+async fn serve_tokens(address: &str, expires_in: u64) -> Arc<AtomicUsize> {
+    let listener = TcpListener::bind(address).await.unwrap();
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let counter = counter.clone();
+            tokio::spawn(async move {
+                let mut buffer = [0u8; 4096];
+                let read = stream.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                assert!(request.starts_with("POST /token"), "{}", request);
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                let body = format!(
+                    r#"{{"access_token":"token-{}","token_type":"Bearer","expires_in":{}}}"#,
+                    n, expires_in
+                );
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            });
+        }
+    });
+    requests
+}
+
+#[tokio::test]
+async fn test_concurrent_misses_fetch_once() {
+    let cache: SharedCache<String, usize> = SharedCache::new(16);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let lookups = (0..20).map(|_| {
+        let cache = cache.clone();
+        let fetch = counting_fetch(&calls, Duration::from_secs(60));
+        tokio::spawn(async move { cache.get_or_fetch("card".to_string(), fetch).await })
+    });
+    let values: Vec<usize> = join_all(lookups)
+        .await
+        .into_iter()
+        .map(|value| value.unwrap().unwrap())
+        .collect();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(values.iter().all(|value| *value == 1));
+    let stats = cache.stats();
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.hits, 19);
+    assert!(stats.hit_ratio() > 0.9);
+}
+
+#[tokio::test]
+async fn test_concurrent_token_requests_share_one_grant() {
+    let requests = serve_tokens("127.0.0.1:8367", 3600).await;
+    let credentials = ClientCredentials::new("http://127.0.0.1:8367/token", "frontend", "secret")
+        .with_scopes(["tasks:write"])
+        .with_cache(TokenCache::new(16));
+
+    let tokens = join_all((0..10).map(|_| credentials.token())).await;
+    assert!(
+        tokens
+            .iter()
+            .all(|token| token.as_ref().unwrap() == "token-1")
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(credentials.stats().misses, 1);
+}
+
+#[tokio::test]
+async fn test_expired_entries_are_fetched_again() {
+    let cache: SharedCache<String, usize> = SharedCache::new(16).with_refresh_ahead(0.0);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let ttl = Duration::from_millis(100);
+
+    let first = cache
+        .get_or_fetch("card".to_string(), counting_fetch(&calls, ttl))
+        .await
+        .unwrap();
+    let cached = cache
+        .get_or_fetch("card".to_string(), counting_fetch(&calls, ttl))
+        .await
+        .unwrap();
+    assert_eq!((first, cached), (1, 1));
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let refetched = cache
+        .get_or_fetch("card".to_string(), counting_fetch(&calls, ttl))
+        .await
+        .unwrap();
+    assert_eq!(refetched, 2);
+    assert_eq!(cache.stats().misses, 2);
+
+    // A token past its lifetime is requested again
+    let requests = serve_tokens("127.0.0.1:8368", 1).await;
+    let credentials = ClientCredentials::new("http://127.0.0.1:8368/token", "frontend", "secret")
+        .with_cache(TokenCache::new(16).with_refresh_ahead(0.0));
+    assert_eq!(credentials.token().await.unwrap(), "token-1");
+    assert_eq!(credentials.token().await.unwrap(), "token-1");
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(credentials.token().await.unwrap(), "token-2");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_entries_near_expiry_refresh_in_the_background() {
+    let cache: SharedCache<String, usize> = SharedCache::new(16).with_refresh_ahead(0.5);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let ttl = Duration::from_millis(400);
+
+    cache
+        .get_or_fetch("card".to_string(), counting_fetch(&calls, ttl))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    // The current value is served at once while a fresh one is fetched
+    let value = tokio::time::timeout(
+        Duration::from_millis(20),
+        cache.get_or_fetch("card".to_string(), counting_fetch(&calls, ttl)),
+    )
+    .await
+    .expect("a value in its refresh window is served without waiting")
+    .unwrap();
+    assert_eq!(value, 1);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let value = cache
+        .get_or_fetch("card".to_string(), counting_fetch(&calls, ttl))
+        .await
+        .unwrap();
+    assert_eq!(value, 2);
+    let stats = cache.stats();
+    assert_eq!((stats.misses, stats.refreshes), (1, 1));
+}
+
+#[tokio::test]
+async fn test_least_recently_used_entry_is_evicted() {
+    let cache: SharedCache<&str, usize> = SharedCache::new(2);
+    let ttl = Duration::from_secs(60);
+    cache.insert("a", 1, ttl);
+    cache.insert("b", 2, ttl);
+    // Using "a" leaves "b" as the least recently used
+    cache
+        .get_or_fetch("a", || async { Err::<(usize, Duration), ()>(()) })
+        .await
+        .unwrap();
+    cache.insert("c", 3, ttl);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.stats().evictions, 1);
+    let refetched = cache
+        .get_or_fetch("b", move || async move { Ok::<_, ()>((20, ttl)) })
+        .await
+        .unwrap();
+    assert_eq!(refetched, 20);
+}
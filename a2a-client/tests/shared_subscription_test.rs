@@ -0,0 +1,79 @@
+//! Tests for tabs following one task sharing a single subscription to it
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use a2a_client::{WebA2AClient, components::task_update_stream};
+use a2a_rs::{adapter::InMemoryTaskStorage, domain::TaskState, port::AsyncTaskManager};
+use axum::{
+    body::BodyDataStream,
+    response::{IntoResponse, sse::Sse},
+};
+use common::serve;
+use futures::StreamExt;
+
+/// The body of a tab's SSE response for `task_id`
+fn open_tab(client: &Arc<WebA2AClient>, task_id: &str) -> BodyDataStream {
+    Sse::new(task_update_stream(client.clone(), task_id.to_string()))
+        .into_response()
+        .into_body()
+        .into_data_stream()
+}
+
+/// The type and data of the tab's next event
+async fn next_event(tab: &mut BodyDataStream) -> (String, String) {
+    let chunk = tokio::time::timeout(Duration::from_secs(3), tab.next())
+        .await
+        .expect("no event received")
+        .expect("stream ended")
+        .unwrap();
+    let chunk = String::from_utf8(chunk.to_vec()).unwrap();
+    let field = |name: &str| {
+        chunk
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .unwrap_or_else(|| panic!("no {} in {:?}", name, chunk))
+            .to_string()
+    };
+    (field("event: "), field("data: "))
+}
+
+#[tokio::test]
+async fn test_tabs_following_a_task_share_one_subscription() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    serve(&storage, "127.0.0.1:8388", Some("127.0.0.1:8389")).await;
+
+    let client = Arc::new(WebA2AClient::new_with_websocket(
+        "http://127.0.0.1:8388".to_string(),
+        "ws://127.0.0.1:8389".to_string(),
+    ));
+    let mut first = open_tab(&client, "expense");
+    assert_eq!(next_event(&mut first).await.0, "task-update");
+
+    // A tab opened later starts from the task as last seen
+    let mut second = open_tab(&client, "expense");
+    let (event, data) = next_event(&mut second).await;
+    assert_eq!(event, "task-update");
+    assert!(data.contains("\"submitted\""));
+    assert_eq!(client.shared_subscriptions(), 1);
+
+    // Both tabs get the same updates from the one subscription
+    storage
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    let first_update = next_event(&mut first).await;
+    let second_update = next_event(&mut second).await;
+    assert_eq!(first_update.0, "task-status");
+    assert!(first_update.1.contains("\"working\""));
+    assert_eq!(first_update, second_update);
+    assert_eq!(client.shared_subscriptions(), 1);
+
+    // The subscription closes once the last tab goes away
+    drop(first);
+    assert_eq!(client.shared_subscriptions(), 1);
+    drop(second);
+    assert_eq!(client.shared_subscriptions(), 0);
+}
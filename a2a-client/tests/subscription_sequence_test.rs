@@ -0,0 +1,199 @@
+//! Tests for resyncing and deduplicating numbered WebSocket events
+
+mod common;
+
+use std::time::Duration;
+
+use a2a_client::WebA2AClient;
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, SequenceCheck, SequenceTracker,
+        SimpleAgentInfo, business::DefaultMessageHandler,
+    },
+    domain::{Artifact, Part, Task, TaskState},
+    port::AsyncTaskManager,
+    services::StreamItem,
+};
+use common::wait_until_reachable;
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio_tungstenite::{accept_async, tungstenite::Message as WsMessage};
+
+fn task(state: TaskState, timestamp: &str, artifacts: Option<Vec<Artifact>>) -> Value {
+    let mut task = Task::new("expense".to_string(), "ctx-1".to_string());
+    task.status.state = state;
+    task.status.timestamp = Some(timestamp.parse().unwrap());
+    task.artifacts = artifacts;
+    serde_json::to_value(task).unwrap()
+}
+
+fn status(state: &str, timestamp: &str, final_: bool) -> Value {
+    json!({
+        "taskId": "expense",
+        "contextId": "ctx-1",
+        "kind": "status-update",
+        "status": {"state": state, "timestamp": timestamp},
+        "final": final_
+    })
+}
+
+fn receipt() -> Artifact {
+    Artifact {
+        artifact_id: "receipt".to_string(),
+        name: None,
+        description: None,
+        parts: vec![Part::text("Receipt".to_string())],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    }
+}
+
+fn artifact() -> Value {
+    json!({
+        "taskId": "expense",
+        "contextId": "ctx-1",
+        "kind": "artifact-update",
+        "artifact": receipt()
+    })
+}
+
+/// Serve one scripted connection: answer the subscription request with
+/// `reply`, then send each numbered event and close
+async fn serve(listener: &TcpListener, reply: Value, events: Vec<(u64, Value)>) {
+    let (stream, _) = listener.accept().await.unwrap();
+    let mut ws = accept_async(stream).await.unwrap();
+    let request = match ws.next().await.unwrap().unwrap() {
+        WsMessage::Text(text) => serde_json::from_str::<Value>(&text).unwrap(),
+        other => panic!("Expected a subscription request, got {:?}", other),
+    };
+    assert_eq!(request["method"], "tasks/resubscribe");
+    let id = request["id"].clone();
+
+    let reply = json!({"jsonrpc": "2.0", "id": id, "result": reply});
+    ws.send(WsMessage::Text(reply.to_string())).await.unwrap();
+    for (sequence, result) in events {
+        let frame = json!({"jsonrpc": "2.0", "id": id, "result": result, "sequence": sequence});
+        ws.send(WsMessage::Text(frame.to_string())).await.unwrap();
+    }
+    let _ = ws.close(None).await;
+}
+
+#[test]
+fn test_tracker_reports_gaps_and_duplicates() {
+    let mut tracker = SequenceTracker::new();
+    assert_eq!(tracker.observe(1), SequenceCheck::InOrder);
+    assert_eq!(tracker.observe(2), SequenceCheck::InOrder);
+    assert_eq!(
+        tracker.observe(5),
+        SequenceCheck::Gap {
+            expected: 3,
+            received: 5
+        }
+    );
+    assert_eq!(tracker.observe(4), SequenceCheck::Duplicate);
+    assert_eq!(tracker.observe(5), SequenceCheck::Duplicate);
+    assert_eq!(tracker.observe(6), SequenceCheck::InOrder);
+
+    tracker.reset();
+    assert_eq!(tracker.observe(1), SequenceCheck::InOrder);
+}
+
+#[tokio::test]
+async fn test_gap_triggers_resync_and_replays_are_dropped() {
+    // The HTTP agent holds the task state a resync fetches
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    storage
+        .update_task_status("expense", TaskState::InputRequired, None)
+        .await
+        .unwrap();
+    let agent_info = SimpleAgentInfo::new(
+        "Resync Agent".to_string(),
+        "http://127.0.0.1:8347".to_string(),
+    );
+    let http = HttpServer::new(
+        DefaultRequestProcessor::new(
+            DefaultMessageHandler::new(storage.clone()),
+            storage.clone(),
+            storage.clone(),
+            agent_info.clone(),
+        ),
+        agent_info,
+        "127.0.0.1:8347".to_string(),
+    );
+    tokio::spawn(async move { http.start().await });
+    wait_until_reachable("127.0.0.1:8347").await;
+
+    let listener = TcpListener::bind("127.0.0.1:8348").await.unwrap();
+    tokio::spawn(async move {
+        // Event 3 is lost and event 4 is delivered twice
+        serve(
+            &listener,
+            task(TaskState::Submitted, "2030-01-01T00:00:00Z", None),
+            vec![
+                (1, status("working", "2030-01-01T00:00:01Z", false)),
+                (2, artifact()),
+                (4, status("working", "2030-01-01T00:00:04Z", false)),
+                (4, status("working", "2030-01-01T00:00:04Z", false)),
+            ],
+        )
+        .await;
+        // After the reconnect the server replays the latest status and the
+        // artifact before the final status
+        serve(
+            &listener,
+            task(
+                TaskState::Working,
+                "2030-01-01T00:00:04Z",
+                Some(vec![receipt()]),
+            ),
+            vec![
+                (1, status("working", "2030-01-01T00:00:04Z", false)),
+                (2, artifact()),
+                (3, status("completed", "2030-01-01T00:00:05Z", true)),
+            ],
+        )
+        .await;
+    });
+
+    let client = WebA2AClient::builder("http://127.0.0.1:8347")
+        .websocket("ws://127.0.0.1:8348")
+        .build();
+    let items: Vec<StreamItem> = tokio::time::timeout(
+        Duration::from_secs(10),
+        client
+            .subscribe_to_task("expense", None)
+            .unwrap()
+            .map(|item| item.unwrap())
+            .collect(),
+    )
+    .await
+    .unwrap();
+
+    let summary: Vec<String> = items
+        .iter()
+        .map(|item| match item {
+            StreamItem::Task(task) => format!("task {:?}", task.status.state),
+            StreamItem::StatusUpdate(update) => format!("status {:?}", update.status.state),
+            StreamItem::ArtifactUpdate(update) => {
+                format!("artifact {}", update.artifact.artifact_id)
+            }
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "task Submitted",
+            "status Working",
+            "artifact receipt",
+            // The gap before event 4 fetched the task over HTTP
+            "task InputRequired",
+            "status Working",
+            // The reconnect's reply, after which only the final status is new
+            "task Working",
+            "status Completed",
+        ]
+    );
+}
@@ -0,0 +1,98 @@
+//! Tests for streaming a task listing through the web client
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use a2a_client::{WebA2AClient, components::task_list_stream};
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{ListTasksParams, ListTasksStreamItem},
+    port::{AsyncTaskManager, ListTasksStream},
+};
+use axum::response::IntoResponse;
+use common::serve;
+use futures::StreamExt;
+
+async fn seed_tasks(storage: &InMemoryTaskStorage, count: usize) {
+    for i in 0..count {
+        storage
+            .create_task(&format!("expense-{}", i), "ctx-list")
+            .await
+            .unwrap();
+    }
+}
+
+/// The IDs of the streamed tasks and the summary's count and total
+async fn collect(mut stream: ListTasksStream) -> (Vec<String>, (i32, i32)) {
+    let mut ids = Vec::new();
+    while let Some(item) = stream.next().await {
+        match item.unwrap() {
+            ListTasksStreamItem::Task(task) => ids.push(task.id),
+            ListTasksStreamItem::Summary(summary) => {
+                assert!(stream.next().await.is_none(), "the summary comes last");
+                return (ids, (summary.count, summary.total_size));
+            }
+        }
+    }
+    panic!("stream ended without a summary");
+}
+
+#[tokio::test]
+async fn test_listing_streams_the_same_page_over_either_transport() {
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 4).await;
+    serve(&storage, "127.0.0.1:8383", Some("127.0.0.1:8384")).await;
+
+    let params = ListTasksParams {
+        page_size: Some(3),
+        ..Default::default()
+    };
+    let listed = storage.list_tasks_v3(&params).await.unwrap();
+    let listed_ids: Vec<_> = listed.tasks.into_iter().map(|task| task.id).collect();
+
+    let ws = WebA2AClient::new_with_websocket(
+        "http://127.0.0.1:8383".to_string(),
+        "ws://127.0.0.1:8384".to_string(),
+    );
+    let streamed = ws.list_tasks_stream(&params).await.unwrap();
+    let (ids, counts) = tokio::time::timeout(Duration::from_secs(2), collect(streamed))
+        .await
+        .unwrap();
+    assert_eq!(ids, listed_ids);
+    assert_eq!(counts, (3, 4));
+
+    let http = WebA2AClient::new_http("http://127.0.0.1:8383".to_string());
+    let (ids, counts) = collect(http.list_tasks_stream(&params).await.unwrap()).await;
+    assert_eq!(ids, listed_ids);
+    assert_eq!(counts, (3, 4));
+}
+
+#[tokio::test]
+async fn test_listing_sse_sends_each_task_then_the_summary() {
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 2).await;
+    serve(&storage, "127.0.0.1:8385", Some("127.0.0.1:8386")).await;
+
+    let client = Arc::new(WebA2AClient::new_with_websocket(
+        "http://127.0.0.1:8385".to_string(),
+        "ws://127.0.0.1:8386".to_string(),
+    ));
+    let events = task_list_stream(client, ListTasksParams::default());
+    let response = axum::response::sse::Sse::new(events).into_response();
+    let body = tokio::time::timeout(
+        Duration::from_secs(2),
+        axum::body::to_bytes(response.into_body(), usize::MAX),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    let events: Vec<_> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: "))
+        .collect();
+    assert_eq!(events, ["task", "task", "summary"]);
+    assert!(body.contains("\"count\":2"));
+}
@@ -0,0 +1,83 @@
+//! Tests for following a task's state from the diffs of its updates
+
+mod common;
+
+use std::time::Duration;
+
+use a2a_client::WebA2AClient;
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{Message, TaskResult, TaskState},
+    port::AsyncTaskManager,
+    services::AsyncA2AClient,
+};
+use common::serve;
+use futures::StreamExt;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_watched_task_matches_a_freshly_fetched_one() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-watch").await.unwrap();
+    let message = Message::user_text("Reimburse my taxi".to_string(), "m-0".to_string());
+    storage
+        .update_task_status("expense", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+    serve(&storage, "127.0.0.1:8365", Some("127.0.0.1:8366")).await;
+
+    let client = WebA2AClient::new_with_websocket(
+        "http://127.0.0.1:8365".to_string(),
+        "ws://127.0.0.1:8366".to_string(),
+    );
+    let mut states = Box::pin(client.watch_task("expense", None).unwrap());
+
+    let updates = storage.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        for i in 1..4 {
+            let message =
+                Message::agent_text(format!("Checking receipt {}", i), format!("m-{}", i));
+            updates
+                .update_task_status("expense", TaskState::Working, Some(message))
+                .await
+                .unwrap();
+        }
+        updates
+            .complete_task_with_result(
+                "expense",
+                TaskResult::new(json!({"status": "approved"})),
+                Some(Message::agent_text(
+                    "Approved".to_string(),
+                    "m-4".to_string(),
+                )),
+            )
+            .await
+            .unwrap();
+    });
+
+    let watched = tokio::time::timeout(Duration::from_secs(5), async {
+        let mut last = None;
+        while let Some(task) = states.next().await {
+            let task = task.unwrap();
+            let done = task.status.state.is_terminal();
+            last = Some(task);
+            if done {
+                break;
+            }
+        }
+        last.expect("no task state received")
+    })
+    .await
+    .expect("task never finished");
+
+    let mut fetched = client.http.get_task("expense", None).await.unwrap();
+    // The cursor is only added to `tasks/get` responses
+    fetched.history_cursor = None;
+    assert_eq!(
+        serde_json::to_value(&watched).unwrap(),
+        serde_json::to_value(&fetched).unwrap()
+    );
+    assert_eq!(watched.history.as_ref().unwrap().len(), 5);
+    assert_eq!(watched.result.unwrap().data["status"], "approved");
+}
@@ -0,0 +1,137 @@
+//! Tests for waiting until a task reaches a terminal state
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use a2a_client::{WaitError, WebA2AClient};
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{A2AError, AgentCapabilities, Message, TaskState},
+    port::AsyncTaskManager,
+};
+use common::serve;
+
+/// A working task, completed by the agent after `delay`
+async fn working_task(storage: &InMemoryTaskStorage, task_id: &str, delay: Option<Duration>) {
+    storage.create_task(task_id, "ctx-wait").await.unwrap();
+    let message = Message::user_text("Reimburse my taxi".to_string(), "m-1".to_string());
+    storage
+        .update_task_status(task_id, TaskState::Working, Some(message))
+        .await
+        .unwrap();
+
+    let Some(delay) = delay else {
+        return;
+    };
+    let storage = storage.clone();
+    let task_id = task_id.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let reply = Message::agent_text("Approved".to_string(), "m-2".to_string());
+        storage
+            .update_task_status(&task_id, TaskState::Completed, Some(reply))
+            .await
+            .unwrap();
+    });
+}
+
+#[tokio::test]
+async fn test_polling_returns_the_task_once_it_completes() {
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "expense", Some(Duration::from_millis(300))).await;
+    serve(&storage, "127.0.0.1:8370", None).await;
+
+    let client = WebA2AClient::new_http("http://127.0.0.1:8370".to_string());
+    let task = client
+        .wait_for_completion("expense", Duration::from_secs(5), Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(task.status.state, TaskState::Completed);
+    assert_eq!(task.history.as_ref().unwrap().len(), 2);
+
+    // A task that does not exist fails at once
+    let started = Instant::now();
+    let error = client
+        .wait_for_completion("missing", Duration::from_secs(5), Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WaitError::Agent(_)), "{:?}", error);
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_streaming_agents_are_followed_rather_than_polled() {
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "expense", Some(Duration::from_millis(300))).await;
+    serve(&storage, "127.0.0.1:8371", Some("127.0.0.1:8372")).await;
+
+    let client = WebA2AClient::new_with_websocket(
+        "http://127.0.0.1:8371".to_string(),
+        "ws://127.0.0.1:8372".to_string(),
+    );
+    // Polling this rarely would not see the task finish in time
+    let started = Instant::now();
+    let task = client
+        .wait_for_completion("expense", Duration::from_secs(3), Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(task.status.state, TaskState::Completed);
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_agents_without_streaming_are_polled() {
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "expense", Some(Duration::from_millis(200))).await;
+    serve(&storage, "127.0.0.1:8373", None).await;
+
+    // Nothing listens on the advertised WebSocket endpoint
+    let client = WebA2AClient::builder("http://127.0.0.1:8373")
+        .websocket("ws://127.0.0.1:8374")
+        .capabilities(AgentCapabilities::default())
+        .build();
+    let task = client
+        .wait_for_completion("expense", Duration::from_secs(5), Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(task.status.state, TaskState::Completed);
+}
+
+#[tokio::test]
+async fn test_never_finishing_task_times_out() {
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "stuck", None).await;
+    serve(&storage, "127.0.0.1:8375", None).await;
+
+    let client = WebA2AClient::new_http("http://127.0.0.1:8375".to_string());
+    let started = Instant::now();
+    let error = client
+        .wait_for_completion(
+            "stuck",
+            Duration::from_millis(400),
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap_err();
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(400), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(2), "{:?}", elapsed);
+
+    match error {
+        WaitError::TimedOut {
+            task_id,
+            timeout,
+            last,
+        } => {
+            assert_eq!(task_id, "stuck");
+            assert_eq!(timeout, Duration::from_millis(400));
+            assert_eq!(last.unwrap().status.state, TaskState::Working);
+        }
+        other => panic!("Expected a timeout, got {:?}", other),
+    }
+    assert_eq!(
+        WaitError::Agent(A2AError::TaskNotFound("stuck".to_string())).to_string(),
+        "Failed to follow task: Task not found: stuck"
+    );
+}
@@ -0,0 +1,128 @@
+//! Tests for warming up a client before its first request
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use a2a_client::{WarmUpError, WebA2AClient};
+use a2a_rs::{
+    adapter::{
+        BearerTokenAuthenticator, HttpServer, InMemoryTaskStorage, SimpleAgentInfo, WebSocketServer,
+    },
+    port::AsyncTaskManager,
+    services::AsyncA2AClient,
+};
+use common::{processor, wait_until_reachable};
+
+#[tokio::test]
+async fn test_warm_up_surfaces_a_rejected_token_before_any_request() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    let agent_info = SimpleAgentInfo::new(
+        "Guarded Agent".to_string(),
+        "http://127.0.0.1:8350".to_string(),
+    );
+    let server = HttpServer::with_auth(
+        processor(&storage, &agent_info),
+        agent_info,
+        "127.0.0.1:8350".to_string(),
+        BearerTokenAuthenticator::new(vec!["secret".to_string()]),
+    );
+    tokio::spawn(async move { server.start().await });
+    wait_until_reachable("127.0.0.1:8350").await;
+
+    let mut client = WebA2AClient::builder("http://127.0.0.1:8350")
+        .auth_token("wrong")
+        .build();
+    let started = Instant::now();
+    let error = client.warm_up().await.unwrap_err();
+    assert!(
+        matches!(
+            error,
+            WarmUpError::AuthRejected {
+                transport: "HTTP",
+                ..
+            }
+        ),
+        "unexpected error: {}",
+        error
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert!(client.capabilities().is_none());
+
+    let mut client = WebA2AClient::builder("http://127.0.0.1:8350")
+        .auth_token("secret")
+        .build();
+    let card = client.warm_up().await.unwrap();
+    assert_eq!(card.name, "Guarded Agent");
+    assert!(client.capabilities().is_some());
+    let task = client.http.get_task("expense", None).await.unwrap();
+    assert_eq!(task.id, "expense");
+}
+
+#[tokio::test]
+async fn test_warm_up_checks_the_websocket_handshake() {
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Streaming Agent".to_string(),
+        "http://127.0.0.1:8351".to_string(),
+    );
+    let http = HttpServer::new(
+        processor(&storage, &agent_info),
+        agent_info.clone(),
+        "127.0.0.1:8351".to_string(),
+    );
+    tokio::spawn(async move { http.start().await });
+    let ws = WebSocketServer::with_auth(
+        processor(&storage, &agent_info),
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8352".to_string(),
+        BearerTokenAuthenticator::new(vec!["ws-token".to_string()]),
+    );
+    tokio::spawn(async move { ws.start().await });
+    wait_until_reachable("127.0.0.1:8351").await;
+    wait_until_reachable("127.0.0.1:8352").await;
+
+    // The HTTP endpoint takes anyone, so only the handshake catches the token
+    let mut client = WebA2AClient::builder("http://127.0.0.1:8351")
+        .websocket("ws://127.0.0.1:8352")
+        .auth_token("wrong")
+        .build();
+    let error = client.warm_up().await.unwrap_err();
+    assert!(
+        matches!(
+            error,
+            WarmUpError::AuthRejected {
+                transport: "WebSocket",
+                ..
+            }
+        ),
+        "unexpected error: {}",
+        error
+    );
+
+    let mut client = WebA2AClient::builder("http://127.0.0.1:8351")
+        .websocket("ws://127.0.0.1:8352")
+        .auth_token("ws-token")
+        .build();
+    client.warm_up().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_warm_up_reports_an_unreachable_agent() {
+    // Nothing listens on this port
+    let mut client = WebA2AClient::builder("http://127.0.0.1:8353").build();
+    let error = client.warm_up().await.unwrap_err();
+    assert!(
+        matches!(
+            error,
+            WarmUpError::Unreachable {
+                transport: "HTTP",
+                ..
+            }
+        ),
+        "unexpected error: {}",
+        error
+    );
+}
@@ -274,6 +274,7 @@ fn bench_part_operations(c: &mut Criterion) {
                 Part::Text { text, .. } => !text.is_empty(),
                 Part::Data { data, .. } => !data.is_empty(),
                 Part::File { file, .. } => file.bytes.is_some() || file.uri.is_some(),
+                Part::Unknown(_) => false,
             }
         })
     });
@@ -327,6 +328,8 @@ fn bench_agent_operations(c: &mut Criterion) {
         examples: Some(vec!["example1".to_string(), "example2".to_string()]),
         input_modes: Some(vec!["text/plain".to_string()]),
         output_modes: Some(vec!["text/plain".to_string()]),
+        input_schema: None,
+        output_schema: None,
         security: None,
     };
 
@@ -166,6 +166,7 @@ fn main() {
             push_notifications: false,
             state_transition_history: true,
             extensions: None,
+            supported_part_kinds: None,
         })
         .default_input_modes(vec!["text".to_string(), "json".to_string()])
         .default_output_modes(vec!["text".to_string(), "json".to_string()])
@@ -0,0 +1,32 @@
+//! Fuzz the wire-format parsers for inbound requests, messages, parts and tasks
+//!
+//! Run with `cargo +nightly fuzz run parse_message` from `a2a-rs/`.
+
+#![no_main]
+
+use a2a_rs::{
+    application::parse_request,
+    domain::{Message, Part, Task},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    // Every input must either parse or fail with an error, never panic
+    let _ = parse_request(text);
+    let _ = serde_json::from_str::<Message>(text);
+    let _ = serde_json::from_str::<Task>(text);
+
+    // A part that parses must survive a round trip unchanged
+    if let Ok(part) = serde_json::from_str::<Part>(text) {
+        let json = serde_json::to_string(&part).expect("parsed parts serialize");
+        let reparsed: Part = serde_json::from_str(&json).expect("serialized parts parse");
+        assert_eq!(
+            serde_json::to_value(&part).unwrap(),
+            serde_json::to_value(&reparsed).unwrap()
+        );
+    }
+});
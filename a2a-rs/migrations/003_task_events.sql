@@ -0,0 +1,14 @@
+-- Append-only event log per task
+-- Records status transitions, appended messages and push notification config changes
+
+CREATE TABLE IF NOT EXISTS task_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id TEXT NOT NULL,
+    sequence INTEGER NOT NULL,  -- Position in the task's log, starting at 1
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    recorded_at TEXT NOT NULL,  -- RFC 3339 timestamp
+    UNIQUE (task_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_events_task_id ON task_events(task_id, sequence);
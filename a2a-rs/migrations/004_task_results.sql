@@ -0,0 +1,7 @@
+-- Structured skill results attached to tasks
+
+CREATE TABLE IF NOT EXISTS task_results (
+    task_id TEXT PRIMARY KEY,
+    result JSONB NOT NULL,      -- Serialized TaskResult
+    recorded_at TEXT NOT NULL   -- RFC 3339 timestamp
+);
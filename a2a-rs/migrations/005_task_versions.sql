@@ -0,0 +1,7 @@
+-- Per-task version numbers for optimistic concurrency control
+-- Tasks without a row predate versioning and count as version 0
+
+CREATE TABLE IF NOT EXISTS task_versions (
+    task_id TEXT PRIMARY KEY,
+    version INTEGER NOT NULL    -- Incremented on every status or history change
+);
@@ -0,0 +1,9 @@
+-- Tags for organizing tasks
+
+CREATE TABLE IF NOT EXISTS task_tags (
+    task_id TEXT NOT NULL,
+    tag TEXT NOT NULL,          -- Validated by the domain: 1-32 of [A-Za-z0-9._-]
+    PRIMARY KEY (task_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_tags_tag ON task_tags(tag);
@@ -26,6 +26,8 @@ use crate::{
 pub struct BearerTokenAuthenticator {
     /// The valid tokens
     tokens: Vec<String>,
+    /// Roles assigned to individual tokens
+    roles: HashMap<String, String>,
     /// The security scheme configuration
     scheme: SecurityScheme,
 }
@@ -35,6 +37,7 @@ impl BearerTokenAuthenticator {
     pub fn new(tokens: Vec<String>) -> Self {
         Self {
             tokens,
+            roles: HashMap::new(),
             scheme: SecurityScheme::Http {
                 scheme: "bearer".to_string(),
                 bearer_format: None,
@@ -47,6 +50,7 @@ impl BearerTokenAuthenticator {
     pub fn with_format(tokens: Vec<String>, format: String) -> Self {
         Self {
             tokens,
+            roles: HashMap::new(),
             scheme: SecurityScheme::Http {
                 scheme: "bearer".to_string(),
                 bearer_format: Some(format),
@@ -54,6 +58,15 @@ impl BearerTokenAuthenticator {
             },
         }
     }
+
+    /// Accept `token` and give the principals it authenticates `role`
+    pub fn with_role(mut self, token: String, role: String) -> Self {
+        if !self.tokens.contains(&token) {
+            self.tokens.push(token.clone());
+        }
+        self.roles.insert(token, role);
+        self
+    }
 }
 
 #[async_trait]
@@ -62,10 +75,11 @@ impl Authenticator for BearerTokenAuthenticator {
         self.validate_context(context)?;
 
         if self.tokens.contains(&context.credential) {
-            Ok(AuthPrincipal::new(
-                context.credential.clone(),
-                "bearer".to_string(),
-            ))
+            let principal = AuthPrincipal::new(context.credential.clone(), "bearer".to_string());
+            Ok(match self.roles.get(&context.credential) {
+                Some(role) => principal.with_role(role.clone()),
+                None => principal,
+            })
         } else {
             Err(A2AError::Internal(
                 "Invalid authentication token".to_string(),
@@ -140,6 +154,8 @@ impl AuthContextExtractor for BearerTokenExtractor {
 pub struct ApiKeyAuthenticator {
     /// Valid API keys
     api_keys: Vec<String>,
+    /// Roles assigned to individual keys
+    roles: HashMap<String, String>,
     /// The security scheme configuration
     scheme: SecurityScheme,
 }
@@ -149,6 +165,7 @@ impl ApiKeyAuthenticator {
     pub fn new(api_keys: Vec<String>, location: String, name: String) -> Self {
         Self {
             api_keys,
+            roles: HashMap::new(),
             scheme: SecurityScheme::ApiKey {
                 location,
                 name,
@@ -171,6 +188,15 @@ impl ApiKeyAuthenticator {
     pub fn cookie(api_keys: Vec<String>, cookie_name: String) -> Self {
         Self::new(api_keys, "cookie".to_string(), cookie_name)
     }
+
+    /// Accept `api_key` and give the principals it authenticates `role`
+    pub fn with_role(mut self, api_key: String, role: String) -> Self {
+        if !self.api_keys.contains(&api_key) {
+            self.api_keys.push(api_key.clone());
+        }
+        self.roles.insert(api_key, role);
+        self
+    }
 }
 
 #[async_trait]
@@ -179,17 +205,19 @@ impl Authenticator for ApiKeyAuthenticator {
         self.validate_context(context)?;
 
         if self.api_keys.contains(&context.credential) {
-            Ok(
-                AuthPrincipal::new(context.credential.clone(), "apikey".to_string())
-                    .with_attribute(
-                        "location".to_string(),
-                        context
-                            .metadata
-                            .get("location")
-                            .unwrap_or(&String::new())
-                            .clone(),
-                    ),
-            )
+            let principal = AuthPrincipal::new(context.credential.clone(), "apikey".to_string())
+                .with_attribute(
+                    "location".to_string(),
+                    context
+                        .metadata
+                        .get("location")
+                        .unwrap_or(&String::new())
+                        .clone(),
+                );
+            Ok(match self.roles.get(&context.credential) {
+                Some(role) => principal.with_role(role.clone()),
+                None => principal,
+            })
         } else {
             Err(A2AError::Internal("Invalid API key".to_string()))
         }
@@ -382,9 +410,10 @@ mod http_auth {
             if let Some(context) = extractor.extract_from_headers(headers).await {
                 // Try to authenticate with the extracted context
                 match state.authenticator.authenticate(&context).await {
-                    Ok(_principal) => {
-                        // Authentication successful, we could add the principal to request extensions
-                        // For now, just proceed with the request
+                    Ok(principal) => {
+                        // Handlers can read the principal, e.g. to check its role
+                        let mut req = req;
+                        req.extensions_mut().insert(principal);
                         return Ok(next.run(req).await);
                     }
                     Err(_) => {
@@ -0,0 +1,140 @@
+//! Per-role access control for JSON-RPC methods
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    application::{JSONRPCError, JSONRPCResponse, json_rpc},
+    domain::A2AError,
+};
+
+/// Methods that read tasks or the agent card without changing anything
+pub const READ_METHODS: &[&str] = &[
+    "tasks/get",
+    "tasks/list",
+    "tasks/listStream",
+    "tasks/events",
+    "tasks/artifacts/list",
+    "tasks/artifacts/get",
+    "tasks/resubscribe",
+    "tasks/pushNotificationConfig/get",
+    "tasks/pushNotificationConfig/list",
+    "agent/getExtendedCard",
+    "agent/getAuthenticatedExtendedCard",
+];
+
+/// The methods a single role may call.
+///
+/// Patterns are exact method names, or a prefix followed by `*` such as
+/// `tasks/pushNotificationConfig/*`. A method matching `deny` is refused even
+/// when it also matches `allow`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RoleAccess {
+    /// Methods the role may call, or every method when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+    /// Methods the role may never call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl RoleAccess {
+    /// A role that may call every method
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A role limited to [`READ_METHODS`]
+    pub fn read_only() -> Self {
+        Self::new().allowing(READ_METHODS.iter().map(|method| method.to_string()))
+    }
+
+    /// Limit the role to the given methods, adding to any already allowed
+    pub fn allowing(mut self, methods: impl IntoIterator<Item = String>) -> Self {
+        self.allow.get_or_insert_with(Vec::new).extend(methods);
+        self
+    }
+
+    /// Refuse the given methods
+    pub fn denying(mut self, methods: impl IntoIterator<Item = String>) -> Self {
+        self.deny.extend(methods);
+        self
+    }
+
+    /// Whether the role may call `method`
+    pub fn permits(&self, method: &str) -> bool {
+        let matches = |pattern: &String| match pattern.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => pattern == method,
+        };
+        if self.deny.iter().any(matches) {
+            return false;
+        }
+        self.allow
+            .as_ref()
+            .is_none_or(|allow| allow.iter().any(matches))
+    }
+}
+
+/// Which JSON-RPC methods each auth role may call.
+///
+/// Principals without a role are checked as the default role, and refused
+/// every method when there is none. A role missing from the policy is refused
+/// every method too, so a typo in a role name fails closed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MethodAccessPolicy {
+    /// Access rules keyed by role name
+    #[serde(default)]
+    pub roles: HashMap<String, RoleAccess>,
+    /// Role whose rules apply to principals without a role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_role: Option<String>,
+}
+
+impl MethodAccessPolicy {
+    /// Create a policy with no roles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the methods `role` may call
+    pub fn with_role(mut self, role: impl Into<String>, access: RoleAccess) -> Self {
+        self.roles.insert(role.into(), access);
+        self
+    }
+
+    /// Check principals without a role as `role`
+    pub fn with_default_role(mut self, role: impl Into<String>) -> Self {
+        self.default_role = Some(role.into());
+        self
+    }
+
+    /// Check that a principal with `role` may call `method`
+    pub fn check(&self, role: Option<&str>, method: &str) -> Result<(), A2AError> {
+        let Some(role) = role.or(self.default_role.as_deref()) else {
+            return Err(A2AError::MethodNotAuthorized(method.to_string()));
+        };
+        match self.roles.get(role) {
+            Some(access) if access.permits(method) => Ok(()),
+            _ => Err(A2AError::MethodNotAuthorized(method.to_string())),
+        }
+    }
+
+    /// The serialized JSON-RPC error answering `request` when its method is
+    /// refused.
+    ///
+    /// Requests whose method cannot be read are let through, so the request
+    /// processor answers them with the usual parse errors.
+    pub(crate) fn denial(&self, role: Option<&str>, request: &str) -> Option<String> {
+        let value: Value = serde_json::from_str(request).ok()?;
+        let method = value.get("method")?.as_str()?;
+        let error = self.check(role, method).err()?;
+        let response =
+            JSONRPCResponse::error(json_rpc::request_id(request), JSONRPCError::from(error));
+        serde_json::to_string(&response).ok()
+    }
+}
@@ -6,15 +6,23 @@ pub mod authenticator;
 #[cfg(feature = "auth")]
 pub mod jwt;
 
+#[cfg(any(feature = "http-server", feature = "ws-server"))]
+pub mod method_access;
+
 #[cfg(feature = "auth")]
 pub mod oauth2;
 
+#[cfg(feature = "signing")]
+pub mod signing;
+
 // Re-export authentication types
 #[cfg(any(feature = "http-server", feature = "ws-server"))]
 pub use authenticator::{
     ApiKeyAuthenticator, ApiKeyExtractor, BearerTokenAuthenticator, BearerTokenExtractor,
     NoopAuthenticator,
 };
+#[cfg(any(feature = "http-server", feature = "ws-server"))]
+pub use method_access::{MethodAccessPolicy, READ_METHODS, RoleAccess};
 
 #[cfg(feature = "auth")]
 pub use jwt::{JwtAuthenticator, JwtExtractor};
@@ -22,5 +30,11 @@ pub use jwt::{JwtAuthenticator, JwtExtractor};
 #[cfg(feature = "auth")]
 pub use oauth2::{OAuth2Authenticator, OAuth2Extractor, OpenIdConnectAuthenticator};
 
+#[cfg(feature = "signing")]
+pub use signing::{RequestSigner, RequestVerifier, SIGNATURE_HEADER, canonicalize_json};
+
 #[cfg(feature = "http-server")]
 pub use authenticator::with_auth;
+
+#[cfg(all(feature = "signing", feature = "http-server"))]
+pub use signing::with_signature_verification;
@@ -0,0 +1,378 @@
+//! Detached Ed25519 request signatures
+//!
+//! Clients sign every JSON-RPC request body with an Ed25519 key and send the
+//! result in the [`SIGNATURE_HEADER`] header. Servers verify the signature against
+//! a set of registered public keys and reject stale or replayed requests.
+//!
+//! The header has the form
+//! `keyId=<key id>,ts=<unix seconds>,nonce=<nonce>,sig=<base64url signature>`,
+//! where the signature covers `<key id>.<ts>.<nonce>.<canonical body>`. The body is
+//! canonicalized with [`canonicalize_json`] so that both sides agree on the signed
+//! bytes regardless of key order or whitespace.
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    sync::{Arc, Mutex},
+};
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use ed25519_dalek::{Signature, Signer, Verifier};
+use serde_json::Value;
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::domain::A2AError;
+
+/// HTTP header carrying the detached request signature
+pub const SIGNATURE_HEADER: &str = "X-A2A-Request-Signature";
+
+/// Default allowed clock skew between client and server, in seconds
+pub const DEFAULT_MAX_SKEW_SECS: i64 = 300;
+
+/// Serialize a JSON value deterministically.
+///
+/// Object keys are sorted lexicographically at every level and no whitespace is
+/// emitted. Strings and numbers use serde_json's standard encoding.
+pub fn canonicalize_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => {
+            let _ = write!(out, "{}", scalar);
+        }
+    }
+}
+
+fn signing_payload(key_id: &str, timestamp: i64, nonce: &str, body: &Value) -> String {
+    format!(
+        "{}.{}.{}.{}",
+        key_id,
+        timestamp,
+        nonce,
+        canonicalize_json(body)
+    )
+}
+
+/// Parsed contents of a [`SIGNATURE_HEADER`] value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureHeader {
+    pub key_id: String,
+    pub timestamp: i64,
+    pub nonce: String,
+    pub signature: String,
+}
+
+impl SignatureHeader {
+    /// Parse a header value
+    pub fn parse(value: &str) -> Result<Self, A2AError> {
+        let mut key_id = None;
+        let mut timestamp = None;
+        let mut nonce = None;
+        let mut signature = None;
+
+        for part in value.split(',') {
+            let (name, val) = part
+                .trim()
+                .split_once('=')
+                .ok_or_else(|| invalid("malformed signature header"))?;
+            match name {
+                "keyId" => key_id = Some(val.to_string()),
+                "ts" => {
+                    timestamp = Some(
+                        val.parse::<i64>()
+                            .map_err(|_| invalid("invalid signature timestamp"))?,
+                    )
+                }
+                "nonce" => nonce = Some(val.to_string()),
+                "sig" => signature = Some(val.to_string()),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            key_id: key_id.ok_or_else(|| invalid("missing keyId"))?,
+            timestamp: timestamp.ok_or_else(|| invalid("missing ts"))?,
+            nonce: nonce.ok_or_else(|| invalid("missing nonce"))?,
+            signature: signature.ok_or_else(|| invalid("missing sig"))?,
+        })
+    }
+
+    /// Format as a header value
+    pub fn to_header_value(&self) -> String {
+        format!(
+            "keyId={},ts={},nonce={},sig={}",
+            self.key_id, self.timestamp, self.nonce, self.signature
+        )
+    }
+}
+
+fn invalid(reason: &str) -> A2AError {
+    A2AError::Internal(format!("Invalid request signature: {}", reason))
+}
+
+/// Client-side signer producing [`SIGNATURE_HEADER`] values
+#[derive(Clone)]
+pub struct RequestSigner {
+    key_id: String,
+    signing_key: SigningKey,
+}
+
+impl RequestSigner {
+    /// Create a signer for the given key id
+    pub fn new(key_id: impl Into<String>, signing_key: SigningKey) -> Self {
+        Self {
+            key_id: key_id.into(),
+            signing_key,
+        }
+    }
+
+    /// Create a signer from a raw 32-byte Ed25519 secret key
+    pub fn from_bytes(key_id: impl Into<String>, secret: &[u8; 32]) -> Self {
+        Self::new(key_id, SigningKey::from_bytes(secret))
+    }
+
+    /// The key id sent with each signature
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// The public key servers need to verify this signer's requests
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Sign a request body with the current time and a fresh nonce
+    pub fn sign(&self, body: &Value) -> String {
+        self.sign_with(
+            body,
+            chrono::Utc::now().timestamp(),
+            &uuid::Uuid::new_v4().to_string(),
+        )
+    }
+
+    /// Sign a request body with an explicit timestamp and nonce
+    pub fn sign_with(&self, body: &Value, timestamp: i64, nonce: &str) -> String {
+        let payload = signing_payload(&self.key_id, timestamp, nonce, body);
+        let signature = self.signing_key.sign(payload.as_bytes());
+        SignatureHeader {
+            key_id: self.key_id.clone(),
+            timestamp,
+            nonce: nonce.to_string(),
+            signature: URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+        }
+        .to_header_value()
+    }
+}
+
+/// Server-side verifier for [`SIGNATURE_HEADER`] values.
+///
+/// Requests are rejected when the key id is unknown, the signature doesn't match
+/// the canonical body, the timestamp is outside the allowed skew, or the nonce has
+/// already been seen within that window. Clones share the replay cache.
+#[derive(Clone)]
+pub struct RequestVerifier {
+    keys: HashMap<String, VerifyingKey>,
+    max_skew_secs: i64,
+    /// Nonces seen within the skew window, keyed by `<key id>:<nonce>`
+    seen_nonces: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl RequestVerifier {
+    /// Create a verifier with no registered keys
+    pub fn new() -> Self {
+        Self {
+            keys: HashMap::new(),
+            max_skew_secs: DEFAULT_MAX_SKEW_SECS,
+            seen_nonces: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Register a public key
+    pub fn with_key(mut self, key_id: impl Into<String>, key: VerifyingKey) -> Self {
+        self.keys.insert(key_id.into(), key);
+        self
+    }
+
+    /// Register a public key from its raw 32-byte encoding
+    pub fn with_key_bytes(
+        self,
+        key_id: impl Into<String>,
+        bytes: &[u8; 32],
+    ) -> Result<Self, A2AError> {
+        let key = VerifyingKey::from_bytes(bytes)
+            .map_err(|e| A2AError::Internal(format!("Invalid Ed25519 public key: {}", e)))?;
+        Ok(self.with_key(key_id, key))
+    }
+
+    /// Set the allowed clock skew in seconds
+    pub fn with_max_skew(mut self, seconds: i64) -> Self {
+        self.max_skew_secs = seconds;
+        self
+    }
+
+    /// Verify a signature header against a request body, returning the key id
+    pub fn verify(&self, header: &str, body: &Value) -> Result<String, A2AError> {
+        self.verify_at(header, body, chrono::Utc::now().timestamp())
+    }
+
+    /// Verify a signature header as of the given unix time
+    pub fn verify_at(&self, header: &str, body: &Value, now: i64) -> Result<String, A2AError> {
+        let header = SignatureHeader::parse(header)?;
+
+        let key = self
+            .keys
+            .get(&header.key_id)
+            .ok_or_else(|| invalid("unknown key id"))?;
+
+        if (now - header.timestamp).abs() > self.max_skew_secs {
+            return Err(invalid("timestamp outside allowed window"));
+        }
+
+        let signature_bytes = URL_SAFE_NO_PAD
+            .decode(&header.signature)
+            .map_err(|_| invalid("signature is not valid base64url"))?;
+        let signature = Signature::from_slice(&signature_bytes)
+            .map_err(|_| invalid("signature has wrong length"))?;
+
+        let payload = signing_payload(&header.key_id, header.timestamp, &header.nonce, body);
+        key.verify(payload.as_bytes(), &signature)
+            .map_err(|_| invalid("signature does not match request body"))?;
+
+        // Only record the nonce once the signature is known to be genuine, so
+        // forged requests can't poison the cache
+        let mut seen = self
+            .seen_nonces
+            .lock()
+            .map_err(|_| A2AError::Internal("Replay cache lock poisoned".to_string()))?;
+        let window = self.max_skew_secs;
+        seen.retain(|_, ts| (now - *ts).abs() <= window);
+
+        let nonce_key = format!("{}:{}", header.key_id, header.nonce);
+        if seen.contains_key(&nonce_key) {
+            return Err(invalid("replayed request"));
+        }
+        seen.insert(nonce_key, header.timestamp);
+
+        Ok(header.key_id)
+    }
+}
+
+impl Default for RequestVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "http-server")]
+mod http_signing {
+    use super::*;
+
+    use axum::{
+        body::Body,
+        extract::State,
+        http::{Method, Request, StatusCode},
+        middleware::Next,
+        response::{IntoResponse, Response},
+    };
+    use serde_json::json;
+
+    /// Maximum request body size buffered for signature verification
+    const MAX_SIGNED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+    fn unauthorized(error: A2AError) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            axum::Json(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": {
+                    "code": -32600,
+                    "message": "Request signature verification failed",
+                    "data": error.to_string()
+                }
+            })),
+        )
+            .into_response()
+    }
+
+    /// Axum middleware rejecting POST requests without a valid signature
+    pub async fn signature_middleware(
+        State(verifier): State<Arc<RequestVerifier>>,
+        req: Request<Body>,
+        next: Next,
+    ) -> Response {
+        // Only JSON-RPC calls are signed; agent card discovery stays public
+        if req.method() != Method::POST {
+            return next.run(req).await;
+        }
+
+        let Some(header) = req
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string)
+        else {
+            return unauthorized(invalid("missing signature header"));
+        };
+
+        let (parts, body) = req.into_parts();
+        let bytes = match axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(e) => return unauthorized(invalid(&format!("unreadable body: {}", e))),
+        };
+        let value: Value = match serde_json::from_slice(&bytes) {
+            Ok(value) => value,
+            Err(_) => return unauthorized(invalid("body is not valid JSON")),
+        };
+
+        if let Err(e) = verifier.verify(&header, &value) {
+            return unauthorized(e);
+        }
+
+        next.run(Request::from_parts(parts, Body::from(bytes)))
+            .await
+    }
+
+    /// Apply signature verification to a router
+    pub fn with_signature_verification<R>(router: R, verifier: RequestVerifier) -> axum::Router
+    where
+        R: Into<axum::Router>,
+    {
+        router.into().layer(axum::middleware::from_fn_with_state(
+            Arc::new(verifier),
+            signature_middleware,
+        ))
+    }
+}
+
+#[cfg(feature = "http-server")]
+pub use http_signing::with_signature_verification;
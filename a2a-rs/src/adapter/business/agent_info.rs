@@ -5,7 +5,10 @@
 use async_trait::async_trait;
 
 use crate::{
-    domain::{A2AError, AgentCapabilities, AgentCard, AgentProvider, AgentSkill},
+    domain::{
+        A2AError, AgentCapabilities, AgentCard, AgentInterface, AgentProvider, AgentSkill,
+        SecurityScheme,
+    },
     services::server::AgentInfoProvider,
 };
 
@@ -85,6 +88,12 @@ impl SimpleAgentInfo {
         self
     }
 
+    /// Restrict the message part kinds the agent accepts (`text`, `file`, `data`)
+    pub fn with_supported_part_kinds(mut self, kinds: Vec<String>) -> Self {
+        self.card.capabilities.supported_part_kinds = Some(kinds);
+        self
+    }
+
     /// Enable authenticated extended card support (v0.3.0)
     pub fn with_authenticated_extended_card(mut self) -> Self {
         self.card.supports_authenticated_extended_card = Some(true);
@@ -98,6 +107,28 @@ impl SimpleAgentInfo {
         self
     }
 
+    /// Advertise an additional interface, e.g. a WebSocket endpoint next to the HTTP one
+    pub fn add_interface(mut self, transport: String, url: String) -> Self {
+        self.card
+            .additional_interfaces
+            .get_or_insert_with(Vec::new)
+            .push(AgentInterface { url, transport });
+        self
+    }
+
+    /// Declare a security scheme and require it for every request
+    pub fn with_security_scheme(mut self, name: String, scheme: SecurityScheme) -> Self {
+        self.card
+            .security
+            .get_or_insert_with(Vec::new)
+            .push([(name.clone(), Vec::new())].into_iter().collect());
+        self.card
+            .security_schemes
+            .get_or_insert_with(Default::default)
+            .insert(name, scheme);
+        self
+    }
+
     /// Add an input mode
     pub fn add_input_mode(mut self, mode: String) -> Self {
         self.card.default_input_modes.push(mode);
@@ -150,6 +181,22 @@ impl SimpleAgentInfo {
         self
     }
 
+    /// Declare the input schema of an already added skill
+    pub fn with_skill_input_schema(mut self, id: &str, schema: serde_json::Value) -> Self {
+        if let Some(skill) = self.card.skills.iter_mut().find(|skill| skill.id == id) {
+            skill.input_schema = Some(schema);
+        }
+        self
+    }
+
+    /// Declare the output schema of an already added skill
+    pub fn with_skill_output_schema(mut self, id: &str, schema: serde_json::Value) -> Self {
+        if let Some(skill) = self.card.skills.iter_mut().find(|skill| skill.id == id) {
+            skill.output_schema = Some(schema);
+        }
+        self
+    }
+
     /// Add a skill using the AgentSkill builder
     pub fn add_skill_object(mut self, skill: AgentSkill) -> Self {
         self.card.skills.push(skill);
@@ -0,0 +1,136 @@
+//! Scanning file attachments of incoming messages
+//!
+//! An [`AttachmentScanner`] sees every file part of a message before the
+//! message handler runs, and so before anything is stored. It either rejects
+//! the file, which blocks the whole message, or passes it with details that
+//! are recorded in the part's metadata under [`SCAN_METADATA_KEY`].
+//!
+//! Integrating a malware scanner such as ClamAV means implementing
+//! [`AttachmentScanner::scan`]: decode the file's bytes, submit them, and map
+//! the result to a [`ScanVerdict`]. A scanner that cannot reach its backend
+//! should return an error, which also blocks the message.
+
+// This module is already conditionally compiled with #[cfg(feature = "server")] in mod.rs
+
+use async_trait::async_trait;
+use base64::Engine;
+use serde_json::{Value, json};
+
+use crate::domain::{
+    A2AError, FileContent,
+    validation::content::{matches_pattern, normalize, sniff_mime_type},
+};
+
+/// Part metadata key holding the details of a passed scan
+pub const SCAN_METADATA_KEY: &str = "attachmentScan";
+
+/// Outcome of scanning one file
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanVerdict {
+    /// The file may be stored; `details`, if any, are recorded on the part
+    Clean { details: Option<Value> },
+    /// The file must not be stored, for the given reason
+    Rejected { reason: String },
+}
+
+/// Interface for scanning file parts before a message is processed
+#[async_trait]
+pub trait AttachmentScanner: Send + Sync {
+    /// Scan a file part of an incoming message
+    async fn scan(&self, file: &FileContent) -> Result<ScanVerdict, A2AError>;
+}
+
+/// A scanner that passes every file without recording anything
+#[derive(Debug, Clone, Default)]
+pub struct NoopAttachmentScanner;
+
+#[async_trait]
+impl AttachmentScanner for NoopAttachmentScanner {
+    async fn scan(&self, _file: &FileContent) -> Result<ScanVerdict, A2AError> {
+        Ok(ScanVerdict::Clean { details: None })
+    }
+}
+
+/// A reference scanner rejecting files by size and type.
+///
+/// Embedded files are measured and sniffed; files given only by URI can't be
+/// inspected, so only their declared type is checked.
+#[derive(Debug, Clone, Default)]
+pub struct SizeTypeScanner {
+    /// Largest accepted file in bytes, `None` accepts any size
+    max_size: Option<usize>,
+    /// Rejected types (`image/*` or an exact type)
+    blocked_types: Vec<String>,
+}
+
+impl SizeTypeScanner {
+    /// Create a scanner that passes everything, recording each file's size
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject embedded files larger than `bytes`
+    pub fn with_max_size(mut self, bytes: usize) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Reject files of these types, whether declared or detected
+    pub fn with_blocked_types(mut self, types: Vec<String>) -> Self {
+        self.blocked_types = types;
+        self
+    }
+
+    fn blocked(&self, mime: &str) -> bool {
+        let mime = normalize(mime);
+        self.blocked_types
+            .iter()
+            .any(|pattern| matches_pattern(pattern, &mime))
+    }
+}
+
+#[async_trait]
+impl AttachmentScanner for SizeTypeScanner {
+    async fn scan(&self, file: &FileContent) -> Result<ScanVerdict, A2AError> {
+        let bytes = match &file.bytes {
+            Some(encoded) => Some(
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| A2AError::ValidationError {
+                        field: "file.bytes".to_string(),
+                        message: format!("File content is not valid base64: {}", e),
+                    })?,
+            ),
+            None => None,
+        };
+
+        if let (Some(limit), Some(bytes)) = (self.max_size, &bytes) {
+            if bytes.len() > limit {
+                return Ok(ScanVerdict::Rejected {
+                    reason: format!("{} bytes exceeds the limit of {} bytes", bytes.len(), limit),
+                });
+            }
+        }
+
+        let detected = bytes.as_deref().and_then(sniff_mime_type);
+        if let Some(mime) = file
+            .mime_type
+            .iter()
+            .map(String::as_str)
+            .chain(detected)
+            .find(|mime| self.blocked(mime))
+        {
+            return Ok(ScanVerdict::Rejected {
+                reason: format!("files of type {} are not accepted", mime),
+            });
+        }
+
+        Ok(ScanVerdict::Clean {
+            details: Some(json!({
+                "scanner": "size-type",
+                "inspected": bytes.is_some(),
+                "sizeBytes": bytes.as_ref().map(Vec::len),
+            })),
+        })
+    }
+}
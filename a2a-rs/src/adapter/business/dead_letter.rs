@@ -0,0 +1,134 @@
+//! Dead-letter queue for push notifications that could not be delivered
+
+// This module is already conditionally compiled with #[cfg(feature = "server")] in mod.rs
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+
+use crate::domain::{
+    DeadLetter, DeliveryAttempt, ListDeadLettersParams, PurgeDeadLettersParams,
+    PushNotificationConfig, WebhookEvent,
+};
+
+/// Dead letters kept before the oldest are dropped
+pub const DEFAULT_DEAD_LETTER_CAPACITY: usize = 1000;
+
+/// A dead letter with the config needed to deliver it again, which is not
+/// listed since it may hold credentials
+struct Entry {
+    letter: DeadLetter,
+    config: PushNotificationConfig,
+}
+
+/// In-memory queue of push notifications whose delivery attempts all failed.
+///
+/// Clones share the same queue, so a handle given to a sender with
+/// `HttpPushNotificationSender::with_dead_letters` can be kept to inspect it.
+/// Once the queue is full, the oldest dead letter is dropped for each new one.
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    entries: Arc<Mutex<VecDeque<Entry>>>,
+    capacity: usize,
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeadLetterQueue {
+    /// Create an empty queue holding up to [`DEFAULT_DEAD_LETTER_CAPACITY`]
+    /// dead letters
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::new())),
+            capacity: DEFAULT_DEAD_LETTER_CAPACITY,
+        }
+    }
+
+    /// Keep at most `capacity` dead letters
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Set aside `event`, which could not be delivered to the webhook in
+    /// `config`
+    pub fn push(
+        &self,
+        config: &PushNotificationConfig,
+        event: &WebhookEvent,
+        attempts: Vec<DeliveryAttempt>,
+        last_error: String,
+    ) -> DeadLetter {
+        let letter = DeadLetter {
+            id: uuid::Uuid::new_v4().to_string(),
+            task_id: event.task_id().to_string(),
+            url: config.url.clone(),
+            event: event.clone(),
+            attempts,
+            last_error,
+            dead_lettered_at: Utc::now(),
+        };
+        self.insert(letter.clone(), config.clone());
+        letter
+    }
+
+    /// Dead letters matching `params`, oldest first
+    pub fn list(&self, params: &ListDeadLettersParams) -> Vec<DeadLetter> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| {
+                params
+                    .task_id
+                    .as_ref()
+                    .is_none_or(|task_id| *task_id == entry.letter.task_id)
+            })
+            .map(|entry| entry.letter.clone())
+            .collect()
+    }
+
+    /// Remove the dead letters matching `params`, returning how many there were
+    pub fn purge(&self, params: &PurgeDeadLettersParams) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|entry| !params.matches(&entry.letter));
+        before - entries.len()
+    }
+
+    /// Number of dead letters in the queue
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether the queue holds no dead letters
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove the dead letter `id` for another delivery, so concurrent
+    /// requeues of it cannot both deliver it
+    pub(crate) fn take(&self, id: &str) -> Option<(DeadLetter, PushNotificationConfig)> {
+        let mut entries = self.entries.lock().unwrap();
+        let index = entries.iter().position(|entry| entry.letter.id == id)?;
+        entries
+            .remove(index)
+            .map(|entry| (entry.letter, entry.config))
+    }
+
+    /// Put back a dead letter whose redelivery failed
+    pub(crate) fn insert(&self, letter: DeadLetter, config: PushNotificationConfig) {
+        let mut entries = self.entries.lock().unwrap();
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(Entry { letter, config });
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 
 use crate::{
-    domain::{A2AError, Message, Task, TaskState},
+    domain::{A2AError, IdGenerator, Message, Task, TaskState, UuidGenerator},
     port::{AsyncMessageHandler, AsyncTaskManager},
 };
 
@@ -17,6 +17,8 @@ where
 {
     /// Task manager for handling task operations
     task_manager: Arc<T>,
+    /// Ids for response messages
+    ids: Arc<dyn IdGenerator>,
 }
 
 impl<T> DefaultMessageHandler<T>
@@ -27,34 +29,53 @@ where
     pub fn new(task_manager: T) -> Self {
         Self {
             task_manager: Arc::new(task_manager),
+            ids: Arc::new(UuidGenerator),
         }
     }
-}
 
-#[async_trait]
-impl<T> AsyncMessageHandler for DefaultMessageHandler<T>
-where
-    T: AsyncTaskManager + Send + Sync + 'static,
-{
-    async fn process_message<'a>(
+    /// Mint response message ids with `ids` instead of random UUIDs
+    pub fn with_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
+
+    /// Add `message` to the task's history and echo it back, first checking
+    /// the task is at `expected_version` if one is given
+    async fn process(
         &self,
-        task_id: &'a str,
-        message: &'a Message,
-        session_id: Option<&'a str>,
+        task_id: &str,
+        message: &Message,
+        session_id: Option<&str>,
+        expected_version: Option<u64>,
     ) -> Result<Task, A2AError> {
-        // Check if task exists
-        let task_exists = self.task_manager.task_exists(task_id).await?;
+        // First, update the task with the incoming message to add it to history
+        match expected_version {
+            // A task expected at a version must already exist
+            Some(expected_version) => {
+                self.task_manager
+                    .update_task_status_at_version(
+                        task_id,
+                        expected_version,
+                        TaskState::Working,
+                        Some(message.clone()),
+                    )
+                    .await?;
+            }
+            None => {
+                // Check if task exists
+                let task_exists = self.task_manager.task_exists(task_id).await?;
 
-        if !task_exists {
-            // Create a new task
-            let context_id = session_id.unwrap_or("default");
-            self.task_manager.create_task(task_id, context_id).await?;
-        }
+                if !task_exists {
+                    // Create a new task
+                    let context_id = session_id.unwrap_or("default");
+                    self.task_manager.create_task(task_id, context_id).await?;
+                }
 
-        // First, update the task with the incoming message to add it to history
-        self.task_manager
-            .update_task_status(task_id, TaskState::Working, Some(message.clone()))
-            .await?;
+                self.task_manager
+                    .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+                    .await?;
+            }
+        }
 
         // Create a simple echo response
         let response_message = Message::builder()
@@ -71,7 +92,7 @@ where
                     .collect::<Vec<_>>()
                     .join(" ")
             ))])
-            .message_id(uuid::Uuid::new_v4().to_string())
+            .message_id(self.ids.next_id())
             .task_id(task_id.to_string())
             .context_id(message.context_id.clone().unwrap_or_default())
             .build();
@@ -86,3 +107,29 @@ where
         Ok(final_task)
     }
 }
+
+#[async_trait]
+impl<T> AsyncMessageHandler for DefaultMessageHandler<T>
+where
+    T: AsyncTaskManager + Send + Sync + 'static,
+{
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        self.process(task_id, message, session_id, None).await
+    }
+
+    async fn process_message_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        session_id: Option<&'a str>,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        self.process(task_id, message, session_id, Some(expected_version))
+            .await
+    }
+}
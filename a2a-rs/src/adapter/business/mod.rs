@@ -3,17 +3,35 @@
 #[cfg(feature = "server")]
 pub mod agent_info;
 #[cfg(feature = "server")]
+pub mod attachment_scanner;
+#[cfg(feature = "server")]
+pub mod dead_letter;
+#[cfg(feature = "server")]
 pub mod message_handler;
 #[cfg(feature = "server")]
+pub mod processing_timeout;
+#[cfg(feature = "server")]
 pub mod push_notification;
 #[cfg(feature = "server")]
 pub mod request_processor;
+#[cfg(feature = "server")]
+pub mod skill_router;
+#[cfg(feature = "server")]
+pub mod task_cleanup;
 
 // Re-export business implementations
 #[cfg(feature = "server")]
 pub use agent_info::SimpleAgentInfo;
 #[cfg(feature = "server")]
+pub use attachment_scanner::{
+    AttachmentScanner, NoopAttachmentScanner, ScanVerdict, SizeTypeScanner,
+};
+#[cfg(feature = "server")]
+pub use dead_letter::DeadLetterQueue;
+#[cfg(feature = "server")]
 pub use message_handler::DefaultMessageHandler;
+#[cfg(feature = "server")]
+pub use processing_timeout::ProcessingTimeout;
 #[cfg(all(feature = "server", feature = "http-client"))]
 pub use push_notification::HttpPushNotificationSender;
 #[cfg(feature = "server")]
@@ -22,3 +40,7 @@ pub use push_notification::{
 };
 #[cfg(feature = "server")]
 pub use request_processor::DefaultRequestProcessor;
+#[cfg(feature = "server")]
+pub use skill_router::{SkillMatcher, SkillRouter};
+#[cfg(feature = "server")]
+pub use task_cleanup::{CleanupReport, TaskCleanupHandle, TaskCleanupWorker, TaskRetentionPolicy};
@@ -0,0 +1,80 @@
+//! Time limits on message handling
+//!
+//! A handler still running when its limit runs out is dropped, and the task is
+//! failed with a `task.timeout` error so it does not stay `working` forever.
+
+// This module is already conditionally compiled with #[cfg(feature = "server")] in mod.rs
+
+use std::{collections::HashMap, time::Duration};
+
+use serde_json::{Map, Value};
+
+use crate::domain::{
+    ErrorDetail, Message, error_catalog::codes, validation::content::SKILL_ID_KEY,
+};
+
+/// Status message metadata key holding the [`ErrorDetail`] of a timed-out task
+pub const ERROR_METADATA_KEY: &str = "error";
+
+/// How long the message handler may take, overall and per skill
+///
+/// The skill is read from the `skillId` metadata of the incoming message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessingTimeout {
+    default_timeout: Option<Duration>,
+    skill_timeouts: HashMap<String, Duration>,
+}
+
+impl ProcessingTimeout {
+    /// Create a timeout that limits nothing until limits are added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit messages for skills without a limit of their own
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Limit messages for `skill_id`, overriding the default
+    pub fn with_skill_timeout(mut self, skill_id: impl Into<String>, timeout: Duration) -> Self {
+        self.skill_timeouts.insert(skill_id.into(), timeout);
+        self
+    }
+
+    /// The limit that applies to `message`, if any
+    pub fn for_message(&self, message: &Message) -> Option<Duration> {
+        message
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(SKILL_ID_KEY))
+            .and_then(Value::as_str)
+            .and_then(|skill_id| self.skill_timeouts.get(skill_id))
+            .copied()
+            .or(self.default_timeout)
+    }
+
+    /// Status message `message_id` for a task failed after `timeout`
+    pub fn status_message(
+        task_id: &str,
+        context_id: &str,
+        timeout: Duration,
+        message_id: String,
+    ) -> Message {
+        let detail = ErrorDetail::new(codes::TASK_TIMEOUT)
+            .with_param("taskId", task_id)
+            .with_param("timeoutSeconds", timeout.as_secs_f64().to_string());
+
+        let mut message = Message::agent_text(detail.message(), message_id);
+        message.task_id = Some(task_id.to_string());
+        message.context_id = Some(context_id.to_string());
+        let mut metadata = Map::new();
+        // An error code and string parameters always serialize
+        if let Ok(detail) = serde_json::to_value(&detail) {
+            metadata.insert(ERROR_METADATA_KEY.to_string(), detail);
+        }
+        message.metadata = Some(metadata);
+        message
+    }
+}
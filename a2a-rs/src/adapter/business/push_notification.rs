@@ -12,8 +12,17 @@ use reqwest::{
 };
 use tokio::sync::Mutex;
 
+#[cfg(feature = "http-client")]
+use crate::adapter::business::DeadLetterQueue;
+use crate::domain::{
+    A2AError, DeadLetter, ListDeadLettersParams, PurgeDeadLettersParams, PurgeDeadLettersResult,
+    PushNotificationConfig, RequeueDeadLetterResult, TaskArtifactUpdateEvent,
+    TaskStatusUpdateEvent,
+};
+#[cfg(feature = "http-client")]
 use crate::domain::{
-    A2AError, PushNotificationConfig, TaskArtifactUpdateEvent, TaskStatusUpdateEvent,
+    DeliveryAttempt, WebhookEvent,
+    error_catalog::{ErrorDetail, codes},
 };
 
 /// Interface for a push notification sender
@@ -32,6 +41,31 @@ pub trait PushNotificationSender: Send + Sync {
         config: &PushNotificationConfig,
         event: &TaskArtifactUpdateEvent,
     ) -> Result<(), A2AError>;
+
+    /// Notifications this sender gave up delivering, if it keeps them
+    async fn list_dead_letters(
+        &self,
+        _params: &ListDeadLettersParams,
+    ) -> Result<Vec<DeadLetter>, A2AError> {
+        Err(no_dead_letters())
+    }
+
+    /// Deliver the dead letter `id` again
+    async fn requeue_dead_letter(&self, _id: &str) -> Result<RequeueDeadLetterResult, A2AError> {
+        Err(no_dead_letters())
+    }
+
+    /// Drop the dead letters matching `params`
+    async fn purge_dead_letters(
+        &self,
+        _params: &PurgeDeadLettersParams,
+    ) -> Result<PurgeDeadLettersResult, A2AError> {
+        Err(no_dead_letters())
+    }
+}
+
+fn no_dead_letters() -> A2AError {
+    A2AError::UnsupportedOperation("Undelivered push notifications are not kept".to_string())
 }
 
 /// HTTP-based push notification sender
@@ -45,6 +79,8 @@ pub struct HttpPushNotificationSender {
     max_retries: u32,
     /// Backoff factor in milliseconds
     backoff_ms: u64,
+    /// Where notifications go once every retry failed
+    dead_letters: Option<DeadLetterQueue>,
 }
 
 #[cfg(feature = "http-client")]
@@ -63,6 +99,7 @@ impl HttpPushNotificationSender {
             timeout: 30,      // Default timeout in seconds
             max_retries: 3,   // Default max retries
             backoff_ms: 1000, // Default backoff in milliseconds (1 second)
+            dead_letters: None,
         }
     }
 
@@ -84,6 +121,13 @@ impl HttpPushNotificationSender {
         self
     }
 
+    /// Keep notifications that fail every retry in `queue`, where they can be
+    /// listed and requeued
+    pub fn with_dead_letters(mut self, queue: DeadLetterQueue) -> Self {
+        self.dead_letters = Some(queue);
+        self
+    }
+
     /// Get the headers for a request
     fn get_headers(&self, config: &PushNotificationConfig) -> HeaderMap {
         let mut headers = HeaderMap::new();
@@ -133,19 +177,43 @@ impl HttpPushNotificationSender {
 }
 
 #[cfg(feature = "http-client")]
-#[async_trait]
-impl PushNotificationSender for HttpPushNotificationSender {
-    async fn send_status_update(
+impl HttpPushNotificationSender {
+    /// Deliver `event` to the webhook in `config`, retrying with backoff and
+    /// dead-lettering it if every attempt fails
+    async fn deliver(
         &self,
         config: &PushNotificationConfig,
-        event: &TaskStatusUpdateEvent,
+        event: &WebhookEvent,
+    ) -> Result<(), A2AError> {
+        let mut attempts = Vec::new();
+        let result = self.try_deliver(config, event, &mut attempts).await;
+        if let (Err(e), Some(queue)) = (&result, &self.dead_letters) {
+            queue.push(config, event, attempts, e.to_string());
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                task_id = %event.task_id(),
+                url = %config.url,
+                "Push notification dead-lettered"
+            );
+        }
+        result
+    }
+
+    /// Deliver `event` with retries and backoff, recording each attempt in
+    /// `attempts`
+    async fn try_deliver(
+        &self,
+        config: &PushNotificationConfig,
+        event: &WebhookEvent,
+        attempts: &mut Vec<DeliveryAttempt>,
     ) -> Result<(), A2AError> {
         let mut last_error = None;
 
         #[cfg(feature = "tracing")]
         tracing::debug!(
-            task_id = %event.task_id,
+            task_id = %event.task_id(),
             url = %config.url,
+            event_kind = event.event_kind(),
             "Preparing to send HTTP push notification"
         );
 
@@ -156,7 +224,7 @@ impl PushNotificationSender for HttpPushNotificationSender {
                 let backoff = self.backoff_ms * (1 << (attempt - 1));
                 #[cfg(feature = "tracing")]
                 tracing::debug!(
-                    task_id = %event.task_id,
+                    task_id = %event.task_id(),
                     attempt = attempt,
                     backoff_ms = backoff,
                     "Retrying push notification after backoff"
@@ -167,12 +235,13 @@ impl PushNotificationSender for HttpPushNotificationSender {
             // Send the notification
             #[cfg(feature = "tracing")]
             tracing::debug!(
-                task_id = %event.task_id,
+                task_id = %event.task_id(),
                 attempt = attempt,
                 url = %config.url,
                 "Sending HTTP POST request for push notification"
             );
 
+            let attempted_at = chrono::Utc::now();
             match self
                 .client
                 .post(&config.url)
@@ -186,7 +255,7 @@ impl PushNotificationSender for HttpPushNotificationSender {
                     let status = response.status();
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
-                        task_id = %event.task_id,
+                        task_id = %event.task_id(),
                         status = %status,
                         "Received response from push notification endpoint"
                     );
@@ -195,24 +264,33 @@ impl PushNotificationSender for HttpPushNotificationSender {
                     if status.is_success() {
                         #[cfg(feature = "tracing")]
                         tracing::info!(
-                            task_id = %event.task_id,
+                            task_id = %event.task_id(),
                             status = %status,
                             "Push notification HTTP request succeeded"
                         );
+                        attempts.push(DeliveryAttempt {
+                            attempted_at,
+                            status_code: Some(status.as_u16()),
+                            error: None,
+                        });
                         return Ok(());
                     } else {
                         let body = response.text().await.unwrap_or_default();
                         #[cfg(feature = "tracing")]
                         tracing::warn!(
-                            task_id = %event.task_id,
+                            task_id = %event.task_id(),
                             status = %status,
                             body = %body,
                             "Push notification HTTP request failed"
                         );
-                        last_error = Some(A2AError::Internal(format!(
-                            "Push notification failed with status {}: {}",
-                            status, body
-                        )));
+                        let error =
+                            format!("Push notification failed with status {}: {}", status, body);
+                        attempts.push(DeliveryAttempt {
+                            attempted_at,
+                            status_code: Some(status.as_u16()),
+                            error: Some(error.clone()),
+                        });
+                        last_error = Some(A2AError::Internal(error));
 
                         // Don't retry on client errors (4xx)
                         if status.is_client_error() {
@@ -223,15 +301,18 @@ impl PushNotificationSender for HttpPushNotificationSender {
                 Err(e) => {
                     #[cfg(feature = "tracing")]
                     tracing::warn!(
-                        task_id = %event.task_id,
+                        task_id = %event.task_id(),
                         error = %e,
                         "Failed to send HTTP request for push notification"
                     );
                     // Store the error but continue retrying
-                    last_error = Some(A2AError::Internal(format!(
-                        "Failed to send push notification: {}",
-                        e
-                    )));
+                    let error = format!("Failed to send push notification: {}", e);
+                    attempts.push(DeliveryAttempt {
+                        attempted_at,
+                        status_code: None,
+                        error: Some(error.clone()),
+                    });
+                    last_error = Some(A2AError::Internal(error));
                 }
             }
         }
@@ -241,64 +322,76 @@ impl PushNotificationSender for HttpPushNotificationSender {
             A2AError::Internal("Unknown error sending push notification".to_string())
         }))
     }
+}
+
+#[cfg(feature = "http-client")]
+#[async_trait]
+impl PushNotificationSender for HttpPushNotificationSender {
+    async fn send_status_update(
+        &self,
+        config: &PushNotificationConfig,
+        event: &TaskStatusUpdateEvent,
+    ) -> Result<(), A2AError> {
+        let event = WebhookEvent::from_status_update(event.clone());
+        self.deliver(config, &event).await
+    }
 
     async fn send_artifact_update(
         &self,
         config: &PushNotificationConfig,
         event: &TaskArtifactUpdateEvent,
     ) -> Result<(), A2AError> {
-        let mut last_error = None;
-
-        // Try with retries
-        for attempt in 0..=self.max_retries {
-            // If this is a retry, wait with exponential backoff
-            if attempt > 0 {
-                let backoff = self.backoff_ms * (1 << (attempt - 1));
-                tokio::time::sleep(tokio::time::Duration::from_millis(backoff)).await;
-            }
+        let event = WebhookEvent::ArtifactUpdate(event.clone());
+        self.deliver(config, &event).await
+    }
 
-            // Send the notification
-            match self
-                .client
-                .post(&config.url)
-                .headers(self.get_headers(config))
-                .json(event)
-                .timeout(std::time::Duration::from_secs(self.timeout))
-                .send()
-                .await
-            {
-                Ok(response) => {
-                    // Check if the request was successful
-                    if response.status().is_success() {
-                        return Ok(());
-                    } else {
-                        let status = response.status();
-                        let body = response.text().await.unwrap_or_default();
-                        last_error = Some(A2AError::Internal(format!(
-                            "Push notification failed with status {}: {}",
-                            status, body
-                        )));
+    async fn list_dead_letters(
+        &self,
+        params: &ListDeadLettersParams,
+    ) -> Result<Vec<DeadLetter>, A2AError> {
+        let queue = self.dead_letters.as_ref().ok_or_else(no_dead_letters)?;
+        Ok(queue.list(params))
+    }
 
-                        // Don't retry on client errors (4xx)
-                        if status.is_client_error() {
-                            break;
-                        }
-                    }
-                }
-                Err(e) => {
-                    // Store the error but continue retrying
-                    last_error = Some(A2AError::Internal(format!(
-                        "Failed to send push notification: {}",
-                        e
-                    )));
-                }
-            }
+    /// Deliver the dead letter `id` again with the usual retries and backoff,
+    /// putting it back with the new attempts if they all fail
+    async fn requeue_dead_letter(&self, id: &str) -> Result<RequeueDeadLetterResult, A2AError> {
+        let queue = self.dead_letters.as_ref().ok_or_else(no_dead_letters)?;
+        let (mut letter, config) = queue.take(id).ok_or_else(|| {
+            A2AError::UserError(
+                ErrorDetail::new(codes::PUSH_DEAD_LETTER_NOT_FOUND).with_param("id", id),
+            )
+        })?;
+        let result = self
+            .try_deliver(&config, &letter.event, &mut letter.attempts)
+            .await;
+        if let Err(e) = &result {
+            letter.last_error = e.to_string();
+            letter.dead_lettered_at = chrono::Utc::now();
+            queue.insert(letter.clone(), config);
         }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            task_id = %letter.task_id,
+            dead_letter_id = %letter.id,
+            delivered = result.is_ok(),
+            "Requeued dead-lettered push notification"
+        );
 
-        // Return the last error if we had one
-        Err(last_error.unwrap_or_else(|| {
-            A2AError::Internal("Unknown error sending push notification".to_string())
-        }))
+        Ok(RequeueDeadLetterResult {
+            delivered: result.is_ok(),
+            dead_letter: letter,
+        })
+    }
+
+    async fn purge_dead_letters(
+        &self,
+        params: &PurgeDeadLettersParams,
+    ) -> Result<PurgeDeadLettersResult, A2AError> {
+        let queue = self.dead_letters.as_ref().ok_or_else(no_dead_letters)?;
+        Ok(PurgeDeadLettersResult {
+            purged: queue.purge(params),
+        })
     }
 }
 
@@ -437,4 +530,25 @@ impl PushNotificationRegistry {
             Ok(())
         }
     }
+
+    /// Notifications the sender gave up delivering
+    pub async fn list_dead_letters(
+        &self,
+        params: &ListDeadLettersParams,
+    ) -> Result<Vec<DeadLetter>, A2AError> {
+        self.sender.list_dead_letters(params).await
+    }
+
+    /// Ask the sender to deliver the dead letter `id` again
+    pub async fn requeue_dead_letter(&self, id: &str) -> Result<RequeueDeadLetterResult, A2AError> {
+        self.sender.requeue_dead_letter(id).await
+    }
+
+    /// Drop the sender's dead letters matching `params`
+    pub async fn purge_dead_letters(
+        &self,
+        params: &PurgeDeadLettersParams,
+    ) -> Result<PurgeDeadLettersResult, A2AError> {
+        self.sender.purge_dead_letters(params).await
+    }
 }
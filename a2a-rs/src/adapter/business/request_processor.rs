@@ -2,22 +2,41 @@
 
 // This module is already conditionally compiled with #[cfg(feature = "server")] in mod.rs
 
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
 
 use crate::{
+    adapter::business::{
+        ProcessingTimeout,
+        attachment_scanner::{AttachmentScanner, SCAN_METADATA_KEY, ScanVerdict},
+    },
     application::{
         JSONRPCError, JSONRPCResponse,
         json_rpc::{
             self, A2ARequest, CancelTaskRequest, GetExtendedCardRequest,
-            GetTaskPushNotificationRequest, GetTaskRequest, SendTaskRequest,
-            SendTaskStreamingRequest, SetTaskPushNotificationRequest, TaskResubscriptionRequest,
+            GetTaskPushNotificationRequest, GetTaskRequest, ListDeadLettersRequest,
+            ListTasksStreamRequest, PurgeDeadLettersRequest, RequeueDeadLetterRequest,
+            SendTaskRequest, SendTaskStreamingRequest, SetTaskPushNotificationRequest,
+            TaskResubscriptionRequest,
         },
     },
-    domain::A2AError,
-    port::{AsyncMessageHandler, AsyncNotificationManager, AsyncTaskManager},
-    services::server::{AgentInfoProvider, AsyncA2ARequestProcessor},
+    domain::{
+        A2AError, ContentPolicy, GetTaskArtifactResult, IdGenerator, ImportTasksResult,
+        ListTaskArtifactsResult, Message, MessageSchemas, Part, Task, TaskCancellation, TaskField,
+        TaskState, UuidGenerator,
+        validation::message_schema::{message_skill_id, task_skill_id},
+    },
+    port::{
+        AsyncMessageHandler, AsyncNotificationManager, AsyncTaskManager, AuthPrincipal,
+        ListTasksStream, authenticator::ADMIN_ROLE,
+    },
+    services::{
+        middleware::MiddlewareChain,
+        server::{AgentInfoProvider, AsyncA2ARequestProcessor},
+    },
 };
 
 /// Default implementation of a request processor that routes requests to business handlers
@@ -37,6 +56,18 @@ where
     notification_manager: Arc<N>,
     /// Agent info provider
     agent_info: Arc<A>,
+    /// Policy applied to file parts of incoming messages
+    content_policy: Option<Arc<ContentPolicy>>,
+    /// Input schemas messages must match, by the skill of their task
+    message_schemas: Option<Arc<MessageSchemas>>,
+    /// Scanner run on file parts of incoming messages before they are handled
+    attachment_scanner: Option<Arc<dyn AttachmentScanner>>,
+    /// Limits on how long the message handler may run
+    processing_timeout: Option<Arc<ProcessingTimeout>>,
+    /// Middleware run around the dispatch of every request
+    middleware: MiddlewareChain,
+    /// Ids for the status messages of timed out tasks
+    ids: Arc<dyn IdGenerator>,
 }
 
 impl<M, T, N, A> DefaultRequestProcessor<M, T, N, A>
@@ -58,8 +89,54 @@ where
             task_manager: Arc::new(task_manager),
             notification_manager: Arc::new(notification_manager),
             agent_info: Arc::new(agent_info),
+            content_policy: None,
+            message_schemas: None,
+            attachment_scanner: None,
+            processing_timeout: None,
+            middleware: MiddlewareChain::new(),
+            ids: Arc::new(UuidGenerator),
         }
     }
+
+    /// Sniff, validate and relabel file parts of incoming messages
+    pub fn with_content_policy(mut self, policy: ContentPolicy) -> Self {
+        self.content_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Reject incoming messages whose structured input does not match the
+    /// schema of their skill: the one the message names, or else the one its
+    /// task was started for
+    pub fn with_message_schemas(mut self, schemas: MessageSchemas) -> Self {
+        self.message_schemas = Some(Arc::new(schemas));
+        self
+    }
+
+    /// Scan file parts of incoming messages, rejecting the message for any
+    /// file the scanner rejects
+    pub fn with_attachment_scanner(mut self, scanner: impl AttachmentScanner + 'static) -> Self {
+        self.attachment_scanner = Some(Arc::new(scanner));
+        self
+    }
+
+    /// Run every parsed request through `middleware` before dispatching it
+    pub fn with_middleware(mut self, middleware: MiddlewareChain) -> Self {
+        self.middleware = middleware;
+        self
+    }
+
+    /// Fail tasks whose message handling runs past `timeout`
+    pub fn with_processing_timeout(mut self, timeout: ProcessingTimeout) -> Self {
+        self.processing_timeout = Some(Arc::new(timeout));
+        self
+    }
+
+    /// Mint the status message ids of timed out tasks with `ids` instead of
+    /// random UUIDs
+    pub fn with_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
 }
 
 impl<H, A> DefaultRequestProcessor<H, H, H, A>
@@ -75,6 +152,12 @@ where
             task_manager: handler_arc.clone(),
             notification_manager: handler_arc,
             agent_info: Arc::new(agent_info),
+            content_policy: None,
+            message_schemas: None,
+            attachment_scanner: None,
+            processing_timeout: None,
+            middleware: MiddlewareChain::new(),
+            ids: Arc::new(UuidGenerator),
         }
     }
 }
@@ -86,6 +169,157 @@ where
     N: AsyncNotificationManager + Send + Sync + 'static,
     A: AgentInfoProvider + Send + Sync + 'static,
 {
+    /// Apply the content policy, if any, to an incoming message
+    fn check_message<'m>(&self, message: &'m Message) -> Result<Cow<'m, Message>, A2AError> {
+        match &self.content_policy {
+            Some(policy) => {
+                let mut message = message.clone();
+                policy.apply(&mut message)?;
+                Ok(Cow::Owned(message))
+            }
+            None => Ok(Cow::Borrowed(message)),
+        }
+    }
+
+    /// Run the attachment scanner, if any, on the file parts of a message,
+    /// recording the details of each passed scan on its part
+    async fn scan_attachments<'m>(
+        &self,
+        message: Cow<'m, Message>,
+    ) -> Result<Cow<'m, Message>, A2AError> {
+        let Some(scanner) = &self.attachment_scanner else {
+            return Ok(message);
+        };
+        if !message
+            .parts
+            .iter()
+            .any(|part| matches!(part, Part::File { .. }))
+        {
+            return Ok(message);
+        }
+
+        let mut message = message.into_owned();
+        for (index, part) in message.parts.iter_mut().enumerate() {
+            let Part::File { file, metadata } = part else {
+                continue;
+            };
+            match scanner.scan(file).await? {
+                ScanVerdict::Clean { details } => {
+                    if let Some(details) = details {
+                        metadata
+                            .get_or_insert_with(Default::default)
+                            .insert(SCAN_METADATA_KEY.to_string(), details);
+                    }
+                }
+                ScanVerdict::Rejected { reason } => {
+                    tracing::warn!(
+                        message_id = %message.message_id,
+                        part = index,
+                        reason = %reason,
+                        "Attachment rejected by scanner"
+                    );
+                    return Err(A2AError::ValidationError {
+                        field: format!("parts[{}].file", index),
+                        message: format!(
+                            "File {} was rejected: {}",
+                            file.name.as_deref().unwrap_or("<unnamed>"),
+                            reason
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(Cow::Owned(message))
+    }
+
+    /// Reject a message that references tasks this agent does not know
+    async fn check_references(&self, message: &Message) -> Result<(), A2AError> {
+        for task_id in message.reference_task_ids.iter().flatten() {
+            if !self.task_manager.task_exists(task_id).await? {
+                return Err(A2AError::ValidationError {
+                    field: "referenceTaskIds".to_string(),
+                    message: format!("Referenced task '{}' does not exist", task_id),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a message for `task_id` against the input schema of its skill
+    async fn check_message_schema(&self, task_id: &str, message: &Message) -> Result<(), A2AError> {
+        let Some(schemas) = self.message_schemas.as_ref().filter(|s| !s.is_empty()) else {
+            return Ok(());
+        };
+        if let Some(skill_id) = message_skill_id(message) {
+            return schemas.validate(message, Some(skill_id));
+        }
+        // A follow-up is for the skill its task was started for
+        match self.task_manager.get_task(task_id, None).await {
+            Ok(task) => schemas.validate(message, task_skill_id(&task)),
+            Err(A2AError::TaskNotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Run the message handler, at `expected_version` if one is given, failing
+    /// the task if it exceeds its time limit
+    ///
+    /// A handler that runs out of time is dropped, which cancels whatever it
+    /// was awaiting.
+    async fn handle_message(
+        &self,
+        task_id: &str,
+        message: &Message,
+        session_id: Option<&str>,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        let processing = async {
+            match expected_version {
+                Some(expected_version) => {
+                    self.message_handler
+                        .process_message_at_version(task_id, message, session_id, expected_version)
+                        .await
+                }
+                None => {
+                    self.message_handler
+                        .process_message(task_id, message, session_id)
+                        .await
+                }
+            }
+        };
+        let Some(limit) = self
+            .processing_timeout
+            .as_ref()
+            .and_then(|timeout| timeout.for_message(message))
+        else {
+            return processing.await;
+        };
+
+        match tokio::time::timeout(limit, processing).await {
+            Ok(result) => result,
+            Err(_) => {
+                tracing::warn!(
+                    task_id = %task_id,
+                    timeout_secs = limit.as_secs_f64(),
+                    "Message handler timed out, failing task"
+                );
+                let context_id = message.context_id.as_deref().unwrap_or(task_id);
+                if !self.task_manager.task_exists(task_id).await? {
+                    self.task_manager.create_task(task_id, context_id).await?;
+                }
+                let status = ProcessingTimeout::status_message(
+                    task_id,
+                    context_id,
+                    limit,
+                    self.ids.next_id(),
+                );
+                self.task_manager
+                    .update_task_status(task_id, TaskState::Failed, Some(status))
+                    .await
+            }
+        }
+    }
+
     /// Process a send task request
     async fn process_send_task(
         &self,
@@ -100,11 +334,17 @@ where
             "🔄 DefaultRequestProcessor: About to call message_handler.process_message"
         );
 
+        let message = self
+            .scan_attachments(self.check_message(&params.message)?)
+            .await?;
+        self.check_references(&message).await?;
+        self.check_message_schema(&params.id, &message).await?;
+
         // Process the message through the handler
-        // The handler is responsible for managing history
+        // The handler is responsible for managing history, and for checking
+        // the expected version as part of its first write
         let task = self
-            .message_handler
-            .process_message(&params.id, &params.message, session_id)
+            .handle_message(&params.id, &message, session_id, params.expected_version)
             .await?;
 
         tracing::info!(
@@ -124,10 +364,27 @@ where
         request: &GetTaskRequest,
     ) -> Result<JSONRPCResponse, A2AError> {
         let params = &request.params;
-        let task = self
-            .task_manager
-            .get_task(&params.id, params.history_length)
-            .await?;
+        let excluded = &params.exclude;
+        let task = if excluded.contains(&TaskField::History) {
+            // Without history there is no cursor to return
+            self.task_manager
+                .get_task_excluding(&params.id, Some(0), excluded)
+                .await?
+        } else if params.since.is_some() {
+            // The cursor counts the full history, so page it only afterwards
+            self.task_manager
+                .get_task_excluding(&params.id, None, excluded)
+                .await?
+                .with_history_page(params.since.as_deref(), params.history_length)?
+        } else {
+            // The end of the full history is the cursor to sync from later,
+            // even when only its latest entries are returned
+            self.task_manager
+                .get_task_excluding(&params.id, None, excluded)
+                .await?
+                .with_history_since(None)?
+                .with_limited_history(params.history_length)
+        };
 
         Ok(JSONRPCResponse::success(
             request.id.clone(),
@@ -135,13 +392,26 @@ where
         ))
     }
 
-    /// Process a cancel task request
+    /// Process a cancel task request, recording `principal` as the canceler
     async fn process_cancel_task(
         &self,
         request: &CancelTaskRequest,
+        principal: Option<&AuthPrincipal>,
     ) -> Result<JSONRPCResponse, A2AError> {
         let params = &request.params;
-        let task = self.task_manager.cancel_task(&params.id).await?;
+        let mut cancellation = TaskCancellation::new();
+        if let Some(reason) = &params.reason {
+            cancellation = cancellation.with_reason(reason.clone());
+        }
+        if let Some(principal) = principal {
+            cancellation = cancellation.with_canceled_by(principal.id.clone());
+        }
+        cancellation.validate()?;
+
+        let task = self
+            .task_manager
+            .cancel_task_with(&params.id, &cancellation, params.expected_version)
+            .await?;
 
         Ok(JSONRPCResponse::success(
             request.id.clone(),
@@ -231,11 +501,17 @@ where
         let params = &request.params;
         let session_id = params.session_id.as_deref();
 
+        let message = self
+            .scan_attachments(self.check_message(&params.message)?)
+            .await?;
+        self.check_references(&message).await?;
+        self.check_message_schema(&params.id, &message).await?;
+
         // Process the message through the handler
-        // The handler is responsible for managing history
+        // The handler is responsible for managing history, and for checking
+        // the expected version as part of its first write
         let task = self
-            .message_handler
-            .process_message(&params.id, &params.message, session_id)
+            .handle_message(&params.id, &message, session_id, params.expected_version)
             .await?;
 
         Ok(JSONRPCResponse::success(
@@ -277,6 +553,22 @@ where
         ))
     }
 
+    /// Open the stream of tasks a tasks/listStream request lists
+    async fn open_list_tasks_stream(
+        &self,
+        request: &A2ARequest,
+    ) -> Result<ListTasksStream, A2AError> {
+        let A2ARequest::ListTasksStream(request) = request else {
+            return Err(A2AError::InvalidRequest(format!(
+                "Expected a tasks/listStream request, got '{}'",
+                request.method()
+            )));
+        };
+        let default_params = crate::domain::ListTasksParams::default();
+        let params = request.params.as_ref().unwrap_or(&default_params);
+        self.task_manager.list_tasks_stream(params).await
+    }
+
     async fn process_get_push_notification_config(
         &self,
         request: &crate::application::handlers::task::GetTaskPushNotificationConfigRequest,
@@ -328,59 +620,262 @@ where
         ))
     }
 
-    async fn process_get_authenticated_extended_card(
+    async fn process_get_task_events(
         &self,
-        request: &crate::application::handlers::agent::GetAuthenticatedExtendedCardRequest,
+        request: &crate::application::handlers::task::GetTaskEventsRequest,
     ) -> Result<JSONRPCResponse, A2AError> {
-        // Get the authenticated extended card from the agent info provider
-        // Authentication checking should be handled by middleware before this point
-        let card = self.agent_info.get_authenticated_extended_card().await?;
+        let result = self.task_manager.get_task_events(&request.params).await?;
 
         Ok(JSONRPCResponse::success(
             request.id.clone(),
-            serde_json::to_value(card)?,
+            serde_json::to_value(result)?,
         ))
     }
-}
 
-#[async_trait]
-impl<M, T, N, A> AsyncA2ARequestProcessor for DefaultRequestProcessor<M, T, N, A>
-where
-    M: AsyncMessageHandler + Send + Sync + 'static,
-    T: AsyncTaskManager + Send + Sync + 'static,
-    N: AsyncNotificationManager + Send + Sync + 'static,
-    A: AgentInfoProvider + Send + Sync + 'static,
-{
-    async fn process_raw_request<'a>(&self, request: &'a str) -> Result<String, A2AError> {
-        // Parse the request
-        let request = match json_rpc::parse_request(request) {
-            Ok(req) => req,
-            Err(e) => {
-                // Return a JSON-RPC error response
-                let error = JSONRPCError::from(e);
-                let response = JSONRPCResponse::error(None, error);
-                return Ok(serde_json::to_string(&response)?);
+    async fn process_list_task_artifacts(
+        &self,
+        request: &crate::application::handlers::task::ListTaskArtifactsRequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        let artifacts = self
+            .task_manager
+            .list_task_artifacts(&request.params.id)
+            .await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(ListTaskArtifactsResult { artifacts })?,
+        ))
+    }
+
+    async fn process_get_task_artifact(
+        &self,
+        request: &crate::application::handlers::task::GetTaskArtifactRequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        let params = &request.params;
+        let artifact = self
+            .task_manager
+            .get_task_artifact(&params.id, &params.artifact_id)
+            .await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(GetTaskArtifactResult::page(&artifact, params))?,
+        ))
+    }
+
+    async fn process_add_task_tags(
+        &self,
+        request: &crate::application::handlers::task::AddTaskTagsRequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        let task = self.task_manager.add_task_tags(&request.params).await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(task)?,
+        ))
+    }
+
+    async fn process_remove_task_tags(
+        &self,
+        request: &crate::application::handlers::task::RemoveTaskTagsRequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        let task = self.task_manager.remove_task_tags(&request.params).await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(task)?,
+        ))
+    }
+
+    /// Process a get-or-create request. The initial message goes through the
+    /// same checks as a sent message, but is stored without being handled.
+    async fn process_get_or_create_task(
+        &self,
+        request: &crate::application::handlers::task::GetOrCreateTaskRequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        let params = &request.params;
+        if params.id.trim().is_empty() {
+            return Err(A2AError::ValidationError {
+                field: "id".to_string(),
+                message: "Task ID must not be empty".to_string(),
+            });
+        }
+        let message = match &params.message {
+            Some(message) => {
+                let message = self.scan_attachments(self.check_message(message)?).await?;
+                self.check_references(&message).await?;
+                self.check_message_schema(&params.id, &message).await?;
+                Some(message)
             }
+            None => None,
         };
 
-        // Process the request
-        let response = match self.process_request(&request).await {
-            Ok(resp) => resp,
-            Err(e) => {
-                // Return a JSON-RPC error response
-                let error = JSONRPCError::from(e);
-                let response = JSONRPCResponse::error(request.id().cloned(), error);
-                return Ok(serde_json::to_string(&response)?);
+        let mut result = self
+            .task_manager
+            .get_or_create_task(
+                &params.id,
+                params.context_id.as_deref().unwrap_or("default"),
+                message.as_deref(),
+            )
+            .await?;
+        tracing::debug!(task_id = %params.id, created = result.created, "Opened task");
+        result.task = result.task.without_fields(&params.exclude);
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(result)?,
+        ))
+    }
+
+    /// Process an import tasks request, which only principals with the
+    /// [`ADMIN_ROLE`] may send
+    async fn process_import_tasks(
+        &self,
+        request: &crate::application::handlers::task::ImportTasksRequest,
+        principal: Option<&AuthPrincipal>,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        require_admin(&request.method, principal)?;
+        let records = &request.params.tasks;
+
+        // Records that fail to decode are reported like any other rejected
+        // task; the rest go to the task manager in one batch
+        let mut tasks = Vec::with_capacity(records.len());
+        let mut malformed = Vec::new();
+        for (index, record) in records.iter().enumerate() {
+            match Task::deserialize(record) {
+                Ok(task) => tasks.push(task),
+                Err(e) => {
+                    let id = record.get("id").and_then(Value::as_str).unwrap_or_default();
+                    malformed.push((
+                        index,
+                        id,
+                        A2AError::InvalidParams(format!("Malformed task: {}", e)),
+                    ));
+                }
             }
+        }
+        let imported = self.task_manager.import_tasks(&tasks).await?;
+
+        // Report every record's outcome in request order
+        let mut result = ImportTasksResult {
+            imported: imported.imported,
+            failed: imported.failed,
+            ..Default::default()
         };
+        let mut outcomes = imported.results.into_iter();
+        let mut malformed = malformed.into_iter().peekable();
+        for index in 0..records.len() {
+            match malformed.next_if(|(at, _, _)| *at == index) {
+                Some((_, id, e)) => result.record(id, Err(e)),
+                None => result.results.extend(outcomes.next()),
+            }
+        }
+        tracing::info!(
+            imported = result.imported,
+            failed = result.failed,
+            "Imported tasks"
+        );
 
-        // Serialize the response
-        Ok(serde_json::to_string(&response)?)
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(result)?,
+        ))
     }
 
-    async fn process_request<'a>(
+    /// Process a request listing dead-lettered push notifications, which
+    /// only admins may send
+    async fn process_list_dead_letters(
         &self,
-        request: &'a A2ARequest,
+        request: &ListDeadLettersRequest,
+        principal: Option<&AuthPrincipal>,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        require_admin(&request.method, principal)?;
+        let letters = self
+            .notification_manager
+            .list_dead_letters(&request.params)
+            .await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(letters)?,
+        ))
+    }
+
+    /// Process a request delivering a dead-lettered push notification again,
+    /// which only admins may send
+    async fn process_requeue_dead_letter(
+        &self,
+        request: &RequeueDeadLetterRequest,
+        principal: Option<&AuthPrincipal>,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        require_admin(&request.method, principal)?;
+        let result = self
+            .notification_manager
+            .requeue_dead_letter(&request.params.id)
+            .await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(result)?,
+        ))
+    }
+
+    /// Process a request dropping dead-lettered push notifications, which
+    /// only admins may send
+    async fn process_purge_dead_letters(
+        &self,
+        request: &PurgeDeadLettersRequest,
+        principal: Option<&AuthPrincipal>,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        require_admin(&request.method, principal)?;
+        let result = self
+            .notification_manager
+            .purge_dead_letters(&request.params)
+            .await?;
+        tracing::info!(purged = result.purged, "Purged dead letters");
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(result)?,
+        ))
+    }
+
+    async fn process_get_authenticated_extended_card(
+        &self,
+        request: &crate::application::handlers::agent::GetAuthenticatedExtendedCardRequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        // Get the authenticated extended card from the agent info provider
+        // Authentication checking should be handled by middleware before this point
+        let card = self.agent_info.get_authenticated_extended_card().await?;
+
+        Ok(JSONRPCResponse::success(
+            request.id.clone(),
+            serde_json::to_value(card)?,
+        ))
+    }
+
+    /// Process a parsed request sent by `principal`
+    async fn process_request_as(
+        &self,
+        request: &A2ARequest,
+        principal: Option<&AuthPrincipal>,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        if self.middleware.is_empty() {
+            return self.dispatch(request, principal).await;
+        }
+        self.middleware
+            .run(request.clone(), principal.cloned(), |request| async move {
+                self.dispatch(&request.request, request.principal.as_ref())
+                    .await
+            })
+            .await
+    }
+
+    /// Route a parsed request to its handler
+    async fn dispatch(
+        &self,
+        request: &A2ARequest,
+        principal: Option<&AuthPrincipal>,
     ) -> Result<JSONRPCResponse, A2AError> {
         match request {
             A2ARequest::SendTask(req) => self.process_send_task(req).await,
@@ -392,7 +887,7 @@ where
                 ))
             }
             A2ARequest::GetTask(req) => self.process_get_task(req).await,
-            A2ARequest::CancelTask(req) => self.process_cancel_task(req).await,
+            A2ARequest::CancelTask(req) => self.process_cancel_task(req, principal).await,
             A2ARequest::SetTaskPushNotification(req) => {
                 self.process_set_push_notification(req).await
             }
@@ -411,6 +906,17 @@ where
             A2ARequest::GetExtendedCard(req) => self.process_get_extended_card(req).await,
             // v0.3.0 new methods
             A2ARequest::ListTasks(req) => self.process_list_tasks(req).await,
+            // Transports that cannot stream get the page whole, as tasks/list
+            // would return it
+            A2ARequest::ListTasksStream(req) => {
+                let request = crate::application::handlers::task::ListTasksRequest {
+                    jsonrpc: req.jsonrpc.clone(),
+                    id: req.id.clone(),
+                    method: req.method.clone(),
+                    params: req.params.clone(),
+                };
+                self.process_list_tasks(&request).await
+            }
             A2ARequest::GetTaskPushNotificationConfig(req) => {
                 self.process_get_push_notification_config(req).await
             }
@@ -423,6 +929,22 @@ where
             A2ARequest::GetAuthenticatedExtendedCard(req) => {
                 self.process_get_authenticated_extended_card(req).await
             }
+            A2ARequest::GetTaskEvents(req) => self.process_get_task_events(req).await,
+            A2ARequest::AddTaskTags(req) => self.process_add_task_tags(req).await,
+            A2ARequest::RemoveTaskTags(req) => self.process_remove_task_tags(req).await,
+            A2ARequest::GetOrCreateTask(req) => self.process_get_or_create_task(req).await,
+            A2ARequest::ListTaskArtifacts(req) => self.process_list_task_artifacts(req).await,
+            A2ARequest::GetTaskArtifact(req) => self.process_get_task_artifact(req).await,
+            A2ARequest::ImportTasks(req) => self.process_import_tasks(req, principal).await,
+            A2ARequest::ListDeadLetters(req) => {
+                self.process_list_dead_letters(req, principal).await
+            }
+            A2ARequest::RequeueDeadLetter(req) => {
+                self.process_requeue_dead_letter(req, principal).await
+            }
+            A2ARequest::PurgeDeadLetters(req) => {
+                self.process_purge_dead_letters(req, principal).await
+            }
             A2ARequest::Generic(req) => {
                 // Handle unknown method
                 Err(A2AError::MethodNotFound(format!(
@@ -433,3 +955,95 @@ where
         }
     }
 }
+
+#[async_trait]
+impl<M, T, N, A> AsyncA2ARequestProcessor for DefaultRequestProcessor<M, T, N, A>
+where
+    M: AsyncMessageHandler + Send + Sync + 'static,
+    T: AsyncTaskManager + Send + Sync + 'static,
+    N: AsyncNotificationManager + Send + Sync + 'static,
+    A: AgentInfoProvider + Send + Sync + 'static,
+{
+    async fn process_raw_request<'a>(&self, request: &'a str) -> Result<String, A2AError> {
+        self.process_raw_request_as(request, None).await
+    }
+
+    async fn process_raw_request_as<'a>(
+        &self,
+        request: &'a str,
+        principal: Option<&'a AuthPrincipal>,
+    ) -> Result<String, A2AError> {
+        // Parse the request
+        let request = match json_rpc::parse_request(request) {
+            Ok(req) => req,
+            Err(e) => {
+                // Return a JSON-RPC error response, echoing the id when one can be read
+                let error = JSONRPCError::from(e);
+                let response = JSONRPCResponse::error(json_rpc::request_id(request), error);
+                return Ok(serde_json::to_string(&response)?);
+            }
+        };
+
+        // Process the request
+        let response = match self.process_request_as(&request, principal).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                // Return a JSON-RPC error response
+                let error = JSONRPCError::from(e);
+                let response = JSONRPCResponse::error(request.id().cloned(), error);
+                return Ok(serde_json::to_string(&response)?);
+            }
+        };
+
+        // Serialize the response
+        Ok(serde_json::to_string(&response)?)
+    }
+
+    async fn process_request<'a>(
+        &self,
+        request: &'a A2ARequest,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        self.process_request_as(request, None).await
+    }
+
+    async fn process_list_tasks_stream<'a>(
+        &self,
+        request: &'a ListTasksStreamRequest,
+        principal: Option<&'a AuthPrincipal>,
+    ) -> Result<ListTasksStream, A2AError> {
+        let request = A2ARequest::ListTasksStream(request.clone());
+        if self.middleware.is_empty() {
+            return self.open_list_tasks_stream(&request).await;
+        }
+
+        // Middleware sees the request and an empty result; the stream itself
+        // is handed back beside the response
+        let stream = std::sync::Mutex::new(None);
+        let slot = &stream;
+        self.middleware
+            .run(request, principal.cloned(), |request| async move {
+                let opened = self.open_list_tasks_stream(&request.request).await?;
+                *slot.lock().unwrap() = Some(opened);
+                Ok(JSONRPCResponse::success(
+                    request.request.id().cloned(),
+                    Value::Null,
+                ))
+            })
+            .await?;
+        stream.into_inner().unwrap().ok_or_else(|| {
+            A2AError::Internal("The listing was answered without being streamed".to_string())
+        })
+    }
+
+    async fn check_ready(&self) -> Result<(), A2AError> {
+        self.task_manager.check_health().await
+    }
+}
+
+/// Refuse `method` unless `principal` has the [`ADMIN_ROLE`]
+fn require_admin(method: &str, principal: Option<&AuthPrincipal>) -> Result<(), A2AError> {
+    if principal.and_then(AuthPrincipal::role) != Some(ADMIN_ROLE) {
+        return Err(A2AError::MethodNotAuthorized(method.to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,189 @@
+//! Routing incoming messages to the agent's skills
+//!
+//! A [`SkillRouter`] lets one agent expose several skills, each served by its
+//! own [`AsyncMessageHandler`]. A message names the skill it wants under
+//! [`SKILL_ID_KEY`] in its metadata; a message that names none goes to
+//! the first skill whose matcher accepts it.
+//!
+//! The router's [`skills`](SkillRouter::skills) are what the agent card should
+//! advertise, so clients can offer them in a skill picker:
+//!
+//! ```rust,ignore
+//! let router = SkillRouter::new()
+//!     .with_skill(expenses, expense_handler)
+//!     .with_matched_skill(travel, |message| mentions(message, "flight"), travel_handler);
+//! let agent_info = SimpleAgentInfo::new(name, url).with_skills(router.skills());
+//! ```
+
+// This module is already conditionally compiled with #[cfg(feature = "server")] in mod.rs
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        A2AError, AgentSkill, ErrorDetail, Message, Task, error_catalog::codes,
+        validation::content::SKILL_ID_KEY,
+    },
+    port::AsyncMessageHandler,
+};
+
+/// Decides whether a skill accepts a message that names no skill
+pub type SkillMatcher = Arc<dyn Fn(&Message) -> bool + Send + Sync>;
+
+/// A registered skill and the handler serving it
+#[derive(Clone)]
+struct Route {
+    skill: AgentSkill,
+    matcher: Option<SkillMatcher>,
+    handler: Arc<dyn AsyncMessageHandler>,
+}
+
+/// A message handler dispatching each message to one of several skills
+#[derive(Clone, Default)]
+pub struct SkillRouter {
+    routes: Vec<Route>,
+}
+
+impl SkillRouter {
+    /// Create a router without skills
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a skill reached only by naming its id.
+    ///
+    /// A skill registered again under the same id replaces the earlier one.
+    pub fn with_skill(
+        self,
+        skill: AgentSkill,
+        handler: impl AsyncMessageHandler + 'static,
+    ) -> Self {
+        self.with_route(Route {
+            skill,
+            matcher: None,
+            handler: Arc::new(handler),
+        })
+    }
+
+    /// Register a skill that also takes messages naming no skill when
+    /// `matcher` accepts them; matchers are tried in registration order
+    pub fn with_matched_skill(
+        self,
+        skill: AgentSkill,
+        matcher: impl Fn(&Message) -> bool + Send + Sync + 'static,
+        handler: impl AsyncMessageHandler + 'static,
+    ) -> Self {
+        self.with_route(Route {
+            skill,
+            matcher: Some(Arc::new(matcher)),
+            handler: Arc::new(handler),
+        })
+    }
+
+    fn with_route(mut self, route: Route) -> Self {
+        match self
+            .routes
+            .iter_mut()
+            .find(|existing| existing.skill.id == route.skill.id)
+        {
+            Some(existing) => *existing = route,
+            None => self.routes.push(route),
+        }
+        self
+    }
+
+    /// The registered skills, in registration order
+    pub fn skills(&self) -> Vec<AgentSkill> {
+        self.routes
+            .iter()
+            .map(|route| route.skill.clone())
+            .collect()
+    }
+
+    /// The skill that would handle `message`.
+    ///
+    /// Fails with [`codes::SKILL_NOT_FOUND`] if the message names an unknown
+    /// skill, and with [`codes::SKILL_NO_MATCH`] if it names none and no
+    /// matcher accepts it.
+    pub fn route(&self, message: &Message) -> Result<&AgentSkill, A2AError> {
+        self.find(message).map(|route| &route.skill)
+    }
+
+    fn find(&self, message: &Message) -> Result<&Route, A2AError> {
+        let requested = message
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(SKILL_ID_KEY));
+
+        match requested {
+            Some(value) => {
+                let skill_id = value.as_str().ok_or_else(|| A2AError::ValidationError {
+                    field: format!("message.metadata.{}", SKILL_ID_KEY),
+                    message: "Skill id must be a string".to_string(),
+                })?;
+                self.routes
+                    .iter()
+                    .find(|route| route.skill.id == skill_id)
+                    .ok_or_else(|| {
+                        A2AError::UserError(
+                            ErrorDetail::new(codes::SKILL_NOT_FOUND)
+                                .with_param("skillId", skill_id),
+                        )
+                    })
+            }
+            None => self
+                .routes
+                .iter()
+                .find(|route| {
+                    route
+                        .matcher
+                        .as_ref()
+                        .is_some_and(|matches| matches(message))
+                })
+                .ok_or_else(|| A2AError::UserError(ErrorDetail::new(codes::SKILL_NO_MATCH))),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncMessageHandler for SkillRouter {
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        let route = self.find(message)?;
+        tracing::debug!(task_id, skill_id = %route.skill.id, "Routing message to skill");
+        route
+            .handler
+            .process_message(task_id, message, session_id)
+            .await
+    }
+
+    async fn process_message_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        session_id: Option<&'a str>,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        let route = self.find(message)?;
+        tracing::debug!(task_id, skill_id = %route.skill.id, "Routing message to skill");
+        route
+            .handler
+            .process_message_at_version(task_id, message, session_id, expected_version)
+            .await
+    }
+
+    async fn validate_message<'a>(&self, message: &'a Message) -> Result<(), A2AError> {
+        self.find(message)?.handler.validate_message(message).await
+    }
+
+    async fn transform_message(&self, message: Message) -> Result<Message, A2AError> {
+        let handler = self.find(&message)?.handler.clone();
+        handler.transform_message(message).await
+    }
+}
@@ -0,0 +1,251 @@
+//! Background cleanup of expired tasks
+
+// This module is already conditionally compiled with #[cfg(feature = "server")] in mod.rs
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::{
+    domain::{A2AError, Task, TaskState},
+    port::AsyncTaskManager,
+};
+
+/// How long tasks in each terminal state are kept, and how cleanup runs
+#[derive(Debug, Clone)]
+pub struct TaskRetentionPolicy {
+    /// Time-to-live per state, measured from the task's last status update
+    ttls: Vec<(TaskState, Duration)>,
+    /// Time between cleanup sweeps
+    interval: Duration,
+    /// Maximum number of tasks deleted per storage call
+    batch_size: usize,
+    /// NDJSON file expired tasks are appended to before deletion
+    archive_path: Option<PathBuf>,
+}
+
+impl Default for TaskRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            ttls: Vec::new(),
+            interval: Duration::from_secs(300),
+            batch_size: 100,
+            archive_path: None,
+        }
+    }
+}
+
+impl TaskRetentionPolicy {
+    /// Create a policy that keeps every task until a TTL is configured
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expire tasks in `state` once their status is older than `ttl`
+    pub fn with_ttl(mut self, state: TaskState, ttl: Duration) -> Self {
+        self.ttls.retain(|(s, _)| s != &state);
+        self.ttls.push((state, ttl));
+        self
+    }
+
+    /// Set the time between cleanup sweeps
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how many tasks are deleted per batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Append expired tasks to an NDJSON file before deleting them
+    pub fn with_archive_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.archive_path = Some(path.into());
+        self
+    }
+
+    /// Whether any state has a TTL
+    pub fn is_enabled(&self) -> bool {
+        !self.ttls.is_empty()
+    }
+
+    /// The TTL configured for a state, if any
+    pub fn ttl_for(&self, state: &TaskState) -> Option<Duration> {
+        self.ttls
+            .iter()
+            .find(|(s, _)| s == state)
+            .map(|(_, ttl)| *ttl)
+    }
+
+    /// Time between cleanup sweeps
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// Outcome of a cleanup sweep
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanupReport {
+    /// Tasks written to the archive
+    pub archived: usize,
+    /// Tasks removed from storage
+    pub deleted: usize,
+}
+
+/// Worker that periodically deletes (and optionally archives) expired tasks.
+///
+/// Deletes happen in batches of the policy's batch size so a large backlog never
+/// holds the storage lock for long. Shutdown is checked between batches, so a
+/// sweep in progress finishes its current batch before the worker stops.
+pub struct TaskCleanupWorker<T>
+where
+    T: AsyncTaskManager + Send + Sync + 'static,
+{
+    storage: Arc<T>,
+    policy: TaskRetentionPolicy,
+}
+
+impl<T> TaskCleanupWorker<T>
+where
+    T: AsyncTaskManager + Send + Sync + 'static,
+{
+    /// Create a worker for the given storage
+    pub fn new(storage: T, policy: TaskRetentionPolicy) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            policy,
+        }
+    }
+
+    /// Run a single sweep using the current time
+    pub async fn run_once(&self) -> Result<CleanupReport, A2AError> {
+        self.sweep(Utc::now(), None).await
+    }
+
+    /// Run a single sweep as of `now`
+    pub async fn run_once_at(&self, now: DateTime<Utc>) -> Result<CleanupReport, A2AError> {
+        self.sweep(now, None).await
+    }
+
+    /// Start sweeping in the background every policy interval
+    pub fn spawn(self) -> TaskCleanupHandle {
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+        let join = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.policy.interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown_rx.changed() => break,
+                }
+
+                match self.sweep(Utc::now(), Some(&shutdown_rx)).await {
+                    #[cfg(feature = "tracing")]
+                    Ok(report) if report.deleted > 0 => tracing::info!(
+                        deleted = report.deleted,
+                        archived = report.archived,
+                        "Removed expired tasks"
+                    ),
+                    Ok(_) => {}
+                    #[cfg(feature = "tracing")]
+                    Err(e) => tracing::warn!("Task cleanup failed: {}", e),
+                    #[cfg(not(feature = "tracing"))]
+                    Err(_) => {}
+                }
+
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+        });
+
+        TaskCleanupHandle { shutdown_tx, join }
+    }
+
+    async fn sweep(
+        &self,
+        now: DateTime<Utc>,
+        shutdown: Option<&watch::Receiver<bool>>,
+    ) -> Result<CleanupReport, A2AError> {
+        let mut report = CleanupReport::default();
+
+        for (state, ttl) in &self.policy.ttls {
+            let ttl = chrono::Duration::from_std(*ttl)
+                .map_err(|e| A2AError::Internal(format!("Invalid task TTL: {}", e)))?;
+            let cutoff = now - ttl;
+
+            loop {
+                if shutdown.is_some_and(|rx| *rx.borrow()) {
+                    return Ok(report);
+                }
+
+                let batch = self
+                    .storage
+                    .list_tasks_updated_before(state, cutoff, self.policy.batch_size)
+                    .await?;
+                if batch.is_empty() {
+                    break;
+                }
+
+                if let Some(path) = &self.policy.archive_path {
+                    archive_tasks(path, &batch).await?;
+                    report.archived += batch.len();
+                }
+
+                let ids: Vec<String> = batch.iter().map(|task| task.id.clone()).collect();
+                report.deleted += self.storage.delete_tasks(&ids).await?;
+
+                if batch.len() < self.policy.batch_size {
+                    break;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Handle to a running [`TaskCleanupWorker`]
+pub struct TaskCleanupHandle {
+    shutdown_tx: watch::Sender<bool>,
+    join: JoinHandle<()>,
+}
+
+impl TaskCleanupHandle {
+    /// Stop the worker, waiting for any in-flight batch to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        let _ = self.join.await;
+    }
+}
+
+/// Append tasks to an NDJSON file, one task per line
+async fn archive_tasks(path: &Path, tasks: &[Task]) -> Result<(), A2AError> {
+    let mut lines = String::new();
+    for task in tasks {
+        lines.push_str(&serde_json::to_string(task)?);
+        lines.push('\n');
+    }
+
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        file.write_all(lines.as_bytes())?;
+        file.sync_data()
+    })
+    .await
+    .map_err(|e| A2AError::Internal(format!("Archive task panicked: {}", e)))??;
+
+    Ok(())
+}
@@ -20,27 +20,47 @@ pub mod transport;
 
 // Client re-exports (from transport)
 #[cfg(feature = "http-client")]
-pub use transport::http::HttpClient;
+pub use transport::http::{HttpClient, RetryPolicy};
+#[cfg(any(feature = "http-client", feature = "http-server"))]
+pub use transport::http::{PRIORITY_HEADER, REQUEST_ID_HEADER, RequestPriority};
 #[cfg(feature = "ws-client")]
-pub use transport::websocket::WebSocketClient;
+pub use transport::websocket::{SequencedItem, SequencedStream, WebSocketClient};
+#[cfg(any(feature = "ws-client", feature = "ws-server"))]
+pub use transport::websocket::{
+    SequenceCheck, SequenceTracker, WebSocketCredentials, WebSocketOptions,
+};
 
 // Server re-exports (from various modules)
 #[cfg(feature = "http-server")]
 pub use auth::with_auth;
 #[cfg(any(feature = "http-server", feature = "ws-server"))]
-pub use auth::{ApiKeyAuthenticator, BearerTokenAuthenticator, NoopAuthenticator};
+pub use auth::{
+    ApiKeyAuthenticator, BearerTokenAuthenticator, MethodAccessPolicy, NoopAuthenticator, RoleAccess,
+};
 #[cfg(feature = "auth")]
 pub use auth::{JwtAuthenticator, OAuth2Authenticator, OpenIdConnectAuthenticator};
+#[cfg(feature = "signing")]
+pub use auth::{RequestSigner, RequestVerifier};
 #[cfg(all(feature = "server", feature = "http-client"))]
 pub use business::HttpPushNotificationSender;
 #[cfg(feature = "server")]
-pub use business::{DefaultRequestProcessor, SimpleAgentInfo};
+pub use business::{AttachmentScanner, NoopAttachmentScanner, ScanVerdict, SizeTypeScanner};
+#[cfg(feature = "server")]
+pub use business::{DeadLetterQueue, DefaultRequestProcessor, ProcessingTimeout, SimpleAgentInfo};
 #[cfg(feature = "server")]
 pub use business::{NoopPushNotificationSender, PushNotificationRegistry, PushNotificationSender};
 #[cfg(feature = "server")]
-pub use storage::InMemoryTaskStorage;
+pub use business::{SkillMatcher, SkillRouter};
+#[cfg(feature = "server")]
+pub use business::{TaskCleanupHandle, TaskCleanupWorker, TaskRetentionPolicy};
+#[cfg(feature = "server")]
+pub use storage::{CachedTaskStorage, InMemoryTaskStorage, TaskCacheConfig, TaskCacheStats};
 #[cfg(feature = "http-server")]
-pub use transport::http::HttpServer;
+pub use transport::http::{HttpServer, RequestId, RequestLimits};
+#[cfg(all(feature = "http-server", feature = "ws-client"))]
+pub use transport::http::SseBridge;
+#[cfg(all(feature = "http-server", feature = "tracing"))]
+pub use transport::http::TaskLogConfig;
 #[cfg(feature = "ws-server")]
 pub use transport::websocket::WebSocketServer;
 
@@ -0,0 +1,842 @@
+//! A read-through cache in front of any task storage
+//!
+//! Dashboards read the same tasks and listings far more often than agents
+//! change them. [`CachedTaskStorage`] wraps another [`AsyncTaskManager`] and
+//! keeps recent task reads for a short time and listings for a shorter one.
+//! Every write made through the wrapper drops the cached entries for that
+//! task and all cached listings, so readers sharing the wrapper see their own
+//! writes at once. Writes that bypass the wrapper go unseen until the entries
+//! expire, which is what the TTLs bound.
+//!
+//! The wrapper also passes notification and streaming calls on to the
+//! storage, so it can fill every storage slot of a request processor and be
+//! the streaming handler of a server.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::Stream;
+
+use crate::domain::{
+    A2AError, Artifact, ArtifactSummary, DeadLetter, DeleteTaskPushNotificationConfigParams,
+    GetOrCreateTaskResult, GetTaskEventsParams, GetTaskEventsResult,
+    GetTaskPushNotificationConfigParams, ImportTasksResult, InputRequest, ListDeadLettersParams,
+    ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult, Message,
+    PurgeDeadLettersParams, PurgeDeadLettersResult, RequeueDeadLetterResult, Task,
+    TaskArtifactUpdateEvent, TaskCancellation, TaskField, TaskPushNotificationConfig, TaskResult,
+    TaskState, TaskStatusUpdateEvent, TaskTagsParams,
+};
+use crate::port::{
+    AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager, ListTasksStream,
+    StreamingSubscriber, UpdateEvent,
+};
+
+/// How long cached reads are kept and how many are kept at most
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskCacheConfig {
+    /// How long a task read is served from the cache
+    pub task_ttl: Duration,
+    /// How long a listing is served from the cache
+    pub list_ttl: Duration,
+    /// Most task reads and listings kept, each
+    pub capacity: usize,
+}
+
+impl Default for TaskCacheConfig {
+    fn default() -> Self {
+        Self {
+            task_ttl: Duration::from_secs(2),
+            list_ttl: Duration::from_millis(500),
+            capacity: 1024,
+        }
+    }
+}
+
+impl TaskCacheConfig {
+    /// Create the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve task reads from the cache for `ttl`
+    pub fn with_task_ttl(mut self, ttl: Duration) -> Self {
+        self.task_ttl = ttl;
+        self
+    }
+
+    /// Serve listings from the cache for `ttl`
+    pub fn with_list_ttl(mut self, ttl: Duration) -> Self {
+        self.list_ttl = ttl;
+        self
+    }
+
+    /// Keep at most `capacity` task reads and as many listings
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+}
+
+/// Counters describing how well the cache is doing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskCacheStats {
+    /// Reads served from the cache
+    pub hits: u64,
+    /// Reads passed on to the storage
+    pub misses: u64,
+    /// Writes that dropped cached entries
+    pub invalidations: u64,
+    /// Entries dropped to stay within capacity
+    pub evictions: u64,
+    /// Task reads and listings currently cached
+    pub entries: usize,
+}
+
+impl TaskCacheStats {
+    /// Share of reads served from the cache, if there were any reads
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let reads = self.hits + self.misses;
+        (reads > 0).then(|| self.hits as f64 / reads as f64)
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    stored_at: Instant,
+}
+
+/// Keyed entries that expire after a TTL and are evicted oldest first
+struct Entries<K, V> {
+    entries: HashMap<K, Entry<V>>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Entries<K, V> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &K, ttl: Duration) -> Option<V> {
+        match self.entries.get(key) {
+            Some(entry) if entry.stored_at.elapsed() < ttl => Some(entry.value.clone()),
+            Some(_) => {
+                self.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store `value`, returning how many entries were evicted to make room
+    fn insert(&mut self, key: K, value: V, ttl: Duration, capacity: usize) -> u64 {
+        let mut evicted = 0;
+        if !self.entries.contains_key(&key) && self.entries.len() >= capacity {
+            self.entries
+                .retain(|_, entry| entry.stored_at.elapsed() < ttl);
+            while self.entries.len() >= capacity.max(1) {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.stored_at)
+                    .map(|(key, _)| key.clone());
+                let Some(oldest) = oldest else {
+                    break;
+                };
+                self.entries.remove(&oldest);
+                evicted += 1;
+            }
+        }
+        if capacity > 0 {
+            self.entries.insert(
+                key,
+                Entry {
+                    value,
+                    stored_at: Instant::now(),
+                },
+            );
+        }
+        evicted
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+type TaskKey = (String, Option<u32>);
+type ListKey = (Option<String>, Option<u32>);
+
+struct CacheState {
+    tasks: Entries<TaskKey, Task>,
+    lists: Entries<ListKey, Vec<Task>>,
+    /// `list_tasks_v3` pages, keyed by their serialized params
+    pages: Entries<String, ListTasksResult>,
+    /// Bumped by every write, so that a read which started before a write
+    /// does not cache what it fetched
+    generation: u64,
+    stats: TaskCacheStats,
+}
+
+impl CacheState {
+    fn new() -> Self {
+        Self {
+            tasks: Entries::new(),
+            lists: Entries::new(),
+            pages: Entries::new(),
+            generation: 0,
+            stats: TaskCacheStats::default(),
+        }
+    }
+}
+
+/// A read-through cache in front of the task storage `S`.
+///
+/// Clones share one cache, so give every component that writes tasks a clone
+/// of the wrapper rather than the storage itself. Notification configs,
+/// event logs and artifacts are always read from the storage, and
+/// subscriptions and broadcasts go straight to it.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use a2a_rs::adapter::storage::{CachedTaskStorage, InMemoryTaskStorage, TaskCacheConfig};
+/// use a2a_rs::port::AsyncTaskManager;
+///
+/// # async fn example() -> Result<(), a2a_rs::domain::A2AError> {
+/// let storage = CachedTaskStorage::new(InMemoryTaskStorage::new())
+///     .with_config(TaskCacheConfig::new().with_task_ttl(Duration::from_secs(5)));
+///
+/// storage.create_task("expense-1", "ctx-1").await?;
+/// storage.get_task("expense-1", None).await?;
+/// storage.get_task("expense-1", None).await?;
+/// assert_eq!(storage.stats().hits, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CachedTaskStorage<S> {
+    inner: S,
+    config: TaskCacheConfig,
+    enabled: Arc<AtomicBool>,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl<S: Clone> Clone for CachedTaskStorage<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            config: self.config.clone(),
+            enabled: self.enabled.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<S> CachedTaskStorage<S> {
+    /// Cache reads from `inner` with the default configuration
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            config: TaskCacheConfig::default(),
+            enabled: Arc::new(AtomicBool::new(true)),
+            state: Arc::new(Mutex::new(CacheState::new())),
+        }
+    }
+
+    /// Use `config` for TTLs and capacity
+    pub fn with_config(mut self, config: TaskCacheConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The wrapped storage
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Whether reads are served from the cache
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Turn caching on or off for every clone. Turning it off drops what is
+    /// cached, so turning it back on starts from fresh reads.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    /// Drop every cached entry
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.tasks = Entries::new();
+        state.lists = Entries::new();
+        state.pages = Entries::new();
+        state.generation += 1;
+    }
+
+    /// Hit, miss and eviction counts since the wrapper was created
+    pub fn stats(&self) -> TaskCacheStats {
+        let state = self.lock();
+        TaskCacheStats {
+            entries: state.tasks.len() + state.lists.len() + state.pages.len(),
+            ..state.stats
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // The state holds no invariants a panicking reader could break
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Drop the entries for `task_ids` and every listing
+    fn invalidate<'t>(&self, task_ids: impl IntoIterator<Item = &'t str>) {
+        let mut state = self.lock();
+        state.generation += 1;
+        state.stats.invalidations += 1;
+        for task_id in task_ids {
+            state
+                .tasks
+                .entries
+                .retain(|(cached_id, _), _| cached_id != task_id);
+        }
+        state.lists = Entries::new();
+        state.pages = Entries::new();
+    }
+
+    /// The cached value under `key`, or the generation to store a fresh read
+    /// under
+    fn cached<K, V>(
+        &self,
+        entries: impl FnOnce(&mut CacheState) -> &mut Entries<K, V>,
+        key: &K,
+        ttl: Duration,
+    ) -> Result<V, u64>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+    {
+        let mut state = self.lock();
+        match entries(&mut state).get(key, ttl) {
+            Some(value) => {
+                state.stats.hits += 1;
+                Ok(value)
+            }
+            None => {
+                state.stats.misses += 1;
+                Err(state.generation)
+            }
+        }
+    }
+
+    /// Cache a fresh read unless a write happened since it started
+    fn store<K, V>(
+        &self,
+        entries: impl FnOnce(&mut CacheState) -> &mut Entries<K, V>,
+        key: K,
+        value: V,
+        ttl: Duration,
+        generation: u64,
+    ) where
+        K: Hash + Eq + Clone,
+        V: Clone,
+    {
+        let mut state = self.lock();
+        if state.generation != generation {
+            return;
+        }
+        let evicted = entries(&mut state).insert(key, value, ttl, self.config.capacity);
+        state.stats.evictions += evicted;
+    }
+
+    /// Drop `task_id`'s entries once a write to it has been attempted
+    fn written<T>(&self, task_id: &str, result: Result<T, A2AError>) -> Result<T, A2AError> {
+        self.invalidate([task_id]);
+        result
+    }
+}
+
+#[async_trait]
+impl<S: AsyncTaskManager> AsyncTaskManager for CachedTaskStorage<S> {
+    async fn create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+    ) -> Result<Task, A2AError> {
+        let result = self.inner.create_task(task_id, context_id).await;
+        self.written(task_id, result)
+    }
+
+    async fn get_task<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+    ) -> Result<Task, A2AError> {
+        if !self.is_enabled() {
+            return self.inner.get_task(task_id, history_length).await;
+        }
+        let ttl = self.config.task_ttl;
+        let key = (task_id.to_string(), history_length);
+        let generation = match self.cached(|state| &mut state.tasks, &key, ttl) {
+            Ok(task) => return Ok(task),
+            Err(generation) => generation,
+        };
+        let task = self.inner.get_task(task_id, history_length).await?;
+        self.store(|state| &mut state.tasks, key, task.clone(), ttl, generation);
+        Ok(task)
+    }
+
+    async fn get_task_excluding<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+        excluded: &'a [TaskField],
+    ) -> Result<Task, A2AError> {
+        if excluded.is_empty() {
+            return self.get_task(task_id, history_length).await;
+        }
+        if !self.is_enabled() {
+            return self
+                .inner
+                .get_task_excluding(task_id, history_length, excluded)
+                .await;
+        }
+        // A cached copy is trimmed, but a partial read is not cached
+        let key = (task_id.to_string(), history_length);
+        match self.cached(|state| &mut state.tasks, &key, self.config.task_ttl) {
+            Ok(task) => Ok(task.without_fields(excluded)),
+            Err(_) => {
+                self.inner
+                    .get_task_excluding(task_id, history_length, excluded)
+                    .await
+            }
+        }
+    }
+
+    async fn get_or_create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+        initial_message: Option<&'a Message>,
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        let result = self
+            .inner
+            .get_or_create_task(task_id, context_id, initial_message)
+            .await;
+        self.written(task_id, result)
+    }
+
+    async fn update_task_status<'a>(
+        &self,
+        task_id: &'a str,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        let result = self.inner.update_task_status(task_id, state, message).await;
+        self.written(task_id, result)
+    }
+
+    async fn cancel_task<'a>(&self, task_id: &'a str) -> Result<Task, A2AError> {
+        let result = self.inner.cancel_task(task_id).await;
+        self.written(task_id, result)
+    }
+
+    async fn task_exists<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
+        self.inner.task_exists(task_id).await
+    }
+
+    async fn list_tasks<'a>(
+        &self,
+        context_id: Option<&'a str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Task>, A2AError> {
+        if !self.is_enabled() {
+            return self.inner.list_tasks(context_id, limit).await;
+        }
+        let ttl = self.config.list_ttl;
+        let key = (context_id.map(str::to_string), limit);
+        let generation = match self.cached(|state| &mut state.lists, &key, ttl) {
+            Ok(tasks) => return Ok(tasks),
+            Err(generation) => generation,
+        };
+        let tasks = self.inner.list_tasks(context_id, limit).await?;
+        self.store(
+            |state| &mut state.lists,
+            key,
+            tasks.clone(),
+            ttl,
+            generation,
+        );
+        Ok(tasks)
+    }
+
+    async fn get_task_metadata<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, A2AError> {
+        let task = self.get_task(task_id, None).await?;
+        Ok(task.metadata.unwrap_or_default())
+    }
+
+    async fn list_tasks_v3<'a>(
+        &self,
+        params: &'a ListTasksParams,
+    ) -> Result<ListTasksResult, A2AError> {
+        if !self.is_enabled() {
+            return self.inner.list_tasks_v3(params).await;
+        }
+        let ttl = self.config.list_ttl;
+        // Fields serialize in a fixed order, so equal queries share an entry
+        let key = serde_json::to_string(params)?;
+        let generation = match self.cached(|state| &mut state.pages, &key, ttl) {
+            Ok(page) => return Ok(page),
+            Err(generation) => generation,
+        };
+        let page = self.inner.list_tasks_v3(params).await?;
+        self.store(|state| &mut state.pages, key, page.clone(), ttl, generation);
+        Ok(page)
+    }
+
+    async fn list_tasks_stream<'a>(
+        &self,
+        params: &'a ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        // Streams exist to avoid holding a page in memory
+        self.inner.list_tasks_stream(params).await
+    }
+
+    async fn get_task_events<'a>(
+        &self,
+        params: &'a GetTaskEventsParams,
+    ) -> Result<GetTaskEventsResult, A2AError> {
+        self.inner.get_task_events(params).await
+    }
+
+    async fn set_task_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: TaskResult,
+    ) -> Result<Task, A2AError> {
+        let result = self.inner.set_task_result(task_id, result).await;
+        self.written(task_id, result)
+    }
+
+    async fn complete_task_with_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: TaskResult,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        let result = self
+            .inner
+            .complete_task_with_result(task_id, result, message)
+            .await;
+        self.written(task_id, result)
+    }
+
+    async fn add_task_artifact<'a>(
+        &self,
+        task_id: &'a str,
+        artifact: Artifact,
+    ) -> Result<Task, A2AError> {
+        let result = self.inner.add_task_artifact(task_id, artifact).await;
+        self.written(task_id, result)
+    }
+
+    async fn list_task_artifacts<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<Vec<ArtifactSummary>, A2AError> {
+        self.inner.list_task_artifacts(task_id).await
+    }
+
+    async fn get_task_artifact<'a>(
+        &self,
+        task_id: &'a str,
+        artifact_id: &'a str,
+    ) -> Result<Artifact, A2AError> {
+        self.inner.get_task_artifact(task_id, artifact_id).await
+    }
+
+    async fn request_input<'a>(
+        &self,
+        task_id: &'a str,
+        request: &'a InputRequest,
+    ) -> Result<Task, A2AError> {
+        let result = self.inner.request_input(task_id, request).await;
+        self.written(task_id, result)
+    }
+
+    async fn check_task_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        // A cached version could pass a check the stored task would fail
+        self.inner
+            .check_task_version(task_id, expected_version)
+            .await
+    }
+
+    async fn update_task_status_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        let result = self
+            .inner
+            .update_task_status_at_version(task_id, expected_version, state, message)
+            .await;
+        self.written(task_id, result)
+    }
+
+    async fn cancel_task_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        let result = self
+            .inner
+            .cancel_task_at_version(task_id, expected_version)
+            .await;
+        self.written(task_id, result)
+    }
+
+    async fn cancel_task_with<'a>(
+        &self,
+        task_id: &'a str,
+        cancellation: &'a TaskCancellation,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        let result = self
+            .inner
+            .cancel_task_with(task_id, cancellation, expected_version)
+            .await;
+        self.written(task_id, result)
+    }
+
+    async fn add_task_tags<'a>(&self, params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        let result = self.inner.add_task_tags(params).await;
+        self.written(&params.id, result)
+    }
+
+    async fn remove_task_tags<'a>(&self, params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        let result = self.inner.remove_task_tags(params).await;
+        self.written(&params.id, result)
+    }
+
+    async fn list_referencing_tasks<'a>(&self, task_id: &'a str) -> Result<Vec<Task>, A2AError> {
+        self.inner.list_referencing_tasks(task_id).await
+    }
+
+    async fn import_task<'a>(&self, task: &'a Task) -> Result<(), A2AError> {
+        let result = self.inner.import_task(task).await;
+        self.written(&task.id, result)
+    }
+
+    async fn import_tasks<'a>(&self, tasks: &'a [Task]) -> Result<ImportTasksResult, A2AError> {
+        let result = self.inner.import_tasks(tasks).await;
+        self.invalidate(tasks.iter().map(|task| task.id.as_str()));
+        result
+    }
+
+    async fn list_tasks_updated_before<'a>(
+        &self,
+        state: &'a TaskState,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Task>, A2AError> {
+        self.inner
+            .list_tasks_updated_before(state, before, limit)
+            .await
+    }
+
+    async fn delete_tasks<'a>(&self, task_ids: &'a [String]) -> Result<usize, A2AError> {
+        let result = self.inner.delete_tasks(task_ids).await;
+        self.invalidate(task_ids.iter().map(String::as_str));
+        result
+    }
+
+    async fn check_health(&self) -> Result<(), A2AError> {
+        self.inner.check_health().await
+    }
+
+    async fn get_push_notification_config<'a>(
+        &self,
+        params: &'a GetTaskPushNotificationConfigParams,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        self.inner.get_push_notification_config(params).await
+    }
+
+    async fn list_push_notification_configs<'a>(
+        &self,
+        params: &'a ListTaskPushNotificationConfigParams,
+    ) -> Result<Vec<TaskPushNotificationConfig>, A2AError> {
+        self.inner.list_push_notification_configs(params).await
+    }
+
+    async fn delete_push_notification_config<'a>(
+        &self,
+        params: &'a DeleteTaskPushNotificationConfigParams,
+    ) -> Result<(), A2AError> {
+        self.inner.delete_push_notification_config(params).await
+    }
+}
+
+#[async_trait]
+impl<S: AsyncNotificationManager> AsyncNotificationManager for CachedTaskStorage<S> {
+    async fn set_task_notification<'a>(
+        &self,
+        config: &'a TaskPushNotificationConfig,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        self.inner.set_task_notification(config).await
+    }
+
+    async fn get_task_notification<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<TaskPushNotificationConfig, A2AError> {
+        self.inner.get_task_notification(task_id).await
+    }
+
+    async fn remove_task_notification<'a>(&self, task_id: &'a str) -> Result<(), A2AError> {
+        self.inner.remove_task_notification(task_id).await
+    }
+
+    async fn has_task_notification<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
+        self.inner.has_task_notification(task_id).await
+    }
+
+    async fn notify_task_status_update<'a>(
+        &self,
+        task_id: &'a str,
+        status_update: &'a TaskStatusUpdateEvent,
+    ) -> Result<(), A2AError> {
+        self.inner
+            .notify_task_status_update(task_id, status_update)
+            .await
+    }
+
+    async fn notify_task_artifact_update<'a>(
+        &self,
+        task_id: &'a str,
+        artifact_update: &'a TaskArtifactUpdateEvent,
+    ) -> Result<(), A2AError> {
+        self.inner
+            .notify_task_artifact_update(task_id, artifact_update)
+            .await
+    }
+
+    async fn list_dead_letters<'a>(
+        &self,
+        params: &'a ListDeadLettersParams,
+    ) -> Result<Vec<DeadLetter>, A2AError> {
+        self.inner.list_dead_letters(params).await
+    }
+
+    async fn requeue_dead_letter<'a>(
+        &self,
+        id: &'a str,
+    ) -> Result<RequeueDeadLetterResult, A2AError> {
+        self.inner.requeue_dead_letter(id).await
+    }
+
+    async fn purge_dead_letters<'a>(
+        &self,
+        params: &'a PurgeDeadLettersParams,
+    ) -> Result<PurgeDeadLettersResult, A2AError> {
+        self.inner.purge_dead_letters(params).await
+    }
+}
+
+#[async_trait]
+impl<S: AsyncStreamingHandler> AsyncStreamingHandler for CachedTaskStorage<S> {
+    async fn add_status_subscriber<'a>(
+        &self,
+        task_id: &'a str,
+        subscriber: Box<dyn StreamingSubscriber<TaskStatusUpdateEvent> + Send + Sync>,
+    ) -> Result<String, A2AError> {
+        self.inner.add_status_subscriber(task_id, subscriber).await
+    }
+
+    async fn add_artifact_subscriber<'a>(
+        &self,
+        task_id: &'a str,
+        subscriber: Box<dyn StreamingSubscriber<TaskArtifactUpdateEvent> + Send + Sync>,
+    ) -> Result<String, A2AError> {
+        self.inner
+            .add_artifact_subscriber(task_id, subscriber)
+            .await
+    }
+
+    async fn remove_subscription<'a>(&self, subscription_id: &'a str) -> Result<(), A2AError> {
+        self.inner.remove_subscription(subscription_id).await
+    }
+
+    async fn remove_task_subscribers<'a>(&self, task_id: &'a str) -> Result<(), A2AError> {
+        self.inner.remove_task_subscribers(task_id).await
+    }
+
+    async fn get_subscriber_count<'a>(&self, task_id: &'a str) -> Result<usize, A2AError> {
+        self.inner.get_subscriber_count(task_id).await
+    }
+
+    async fn has_subscribers<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
+        self.inner.has_subscribers(task_id).await
+    }
+
+    async fn broadcast_status_update<'a>(
+        &self,
+        task_id: &'a str,
+        update: TaskStatusUpdateEvent,
+    ) -> Result<(), A2AError> {
+        self.inner.broadcast_status_update(task_id, update).await
+    }
+
+    async fn broadcast_artifact_update<'a>(
+        &self,
+        task_id: &'a str,
+        update: TaskArtifactUpdateEvent,
+    ) -> Result<(), A2AError> {
+        self.inner.broadcast_artifact_update(task_id, update).await
+    }
+
+    async fn status_update_stream<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<TaskStatusUpdateEvent, A2AError>> + Send>>, A2AError>
+    {
+        self.inner.status_update_stream(task_id).await
+    }
+
+    async fn artifact_update_stream<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<TaskArtifactUpdateEvent, A2AError>> + Send>>,
+        A2AError,
+    > {
+        self.inner.artifact_update_stream(task_id).await
+    }
+
+    async fn combined_update_stream<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<UpdateEvent, A2AError>> + Send>>, A2AError> {
+        self.inner.combined_update_stream(task_id).await
+    }
+
+    async fn validate_streaming_params<'a>(&self, task_id: &'a str) -> Result<(), A2AError> {
+        self.inner.validate_streaming_params(task_id).await
+    }
+}
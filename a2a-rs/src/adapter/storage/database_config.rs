@@ -5,7 +5,12 @@ use bon::Builder;
 #[cfg(feature = "sqlx-storage")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "sqlx-storage")]
-use std::collections::HashMap;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+#[cfg(feature = "sqlx-storage")]
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+#[cfg(feature = "sqlx-storage")]
+use crate::domain::A2AError;
 
 #[cfg(feature = "sqlx-storage")]
 /// Database configuration with connection examples
@@ -16,9 +21,27 @@ pub struct DatabaseConfig {
     /// Maximum number of connections in the pool
     #[builder(default = 10)]
     pub max_connections: u32,
-    /// Connection timeout in seconds
+    /// Connections the pool keeps open even when idle
+    #[builder(default = 0)]
+    #[serde(default)]
+    pub min_connections: u32,
+    /// Seconds to wait for a free connection before failing with
+    /// [`A2AError::Unavailable`]
     #[builder(default = 30)]
     pub timeout_seconds: u64,
+    /// Seconds an idle connection is kept before it is closed; SQLx's default
+    /// of 10 minutes when unset
+    #[serde(default)]
+    pub idle_timeout_seconds: Option<u64>,
+    /// Seconds after which a connection is replaced; SQLx's default of
+    /// 30 minutes when unset
+    #[serde(default)]
+    pub max_lifetime_seconds: Option<u64>,
+    /// Milliseconds a statement may wait on a locked database before failing.
+    /// SQLite has no timeout on running statements, so this is its busy
+    /// timeout; SQLx's default of 5 seconds when unset
+    #[serde(default)]
+    pub statement_timeout_ms: Option<u64>,
     /// Whether to enable SQL query logging
     #[builder(default = false)]
     pub enable_logging: bool,
@@ -79,10 +102,16 @@ impl DatabaseConfig {
     /// Expected environment variables:
     /// - `DATABASE_URL`: Required - the database connection URL
     /// - `DATABASE_MAX_CONNECTIONS`: Optional - defaults to 10
+    /// - `DATABASE_MIN_CONNECTIONS`: Optional - defaults to 0
     /// - `DATABASE_TIMEOUT_SECONDS`: Optional - defaults to 30  
+    /// - `DATABASE_IDLE_TIMEOUT_SECONDS`: Optional
+    /// - `DATABASE_MAX_LIFETIME_SECONDS`: Optional
+    /// - `DATABASE_STATEMENT_TIMEOUT_MS`: Optional
     /// - `DATABASE_ENABLE_LOGGING`: Optional - defaults to false
     pub fn from_env() -> Result<Self, std::env::VarError> {
         let url = std::env::var("DATABASE_URL")?;
+        let optional =
+            |name: &str| -> Option<u64> { std::env::var(name).ok().and_then(|s| s.parse().ok()) };
 
         let max_connections = std::env::var("DATABASE_MAX_CONNECTIONS")
             .ok()
@@ -102,7 +131,11 @@ impl DatabaseConfig {
         Ok(Self::builder()
             .url(url)
             .max_connections(max_connections)
+            .min_connections(optional("DATABASE_MIN_CONNECTIONS").unwrap_or(0) as u32)
             .timeout_seconds(timeout_seconds)
+            .maybe_idle_timeout_seconds(optional("DATABASE_IDLE_TIMEOUT_SECONDS"))
+            .maybe_max_lifetime_seconds(optional("DATABASE_MAX_LIFETIME_SECONDS"))
+            .maybe_statement_timeout_ms(optional("DATABASE_STATEMENT_TIMEOUT_MS"))
             .enable_logging(enable_logging)
             .build())
     }
@@ -117,6 +150,10 @@ impl DatabaseConfig {
             return Err("Max connections must be greater than 0".to_string());
         }
 
+        if self.min_connections > self.max_connections {
+            return Err("Min connections cannot exceed max connections".to_string());
+        }
+
         if self.timeout_seconds == 0 {
             return Err("Timeout must be greater than 0".to_string());
         }
@@ -132,6 +169,34 @@ impl DatabaseConfig {
         Ok(())
     }
 
+    /// Pool settings from this configuration
+    pub fn pool_options(&self) -> SqlitePoolOptions {
+        let mut options = SqlitePoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(Duration::from_secs(self.timeout_seconds));
+        if let Some(seconds) = self.idle_timeout_seconds {
+            options = options.idle_timeout(Duration::from_secs(seconds));
+        }
+        if let Some(seconds) = self.max_lifetime_seconds {
+            options = options.max_lifetime(Duration::from_secs(seconds));
+        }
+        options
+    }
+
+    /// Connection settings from this configuration
+    pub fn connect_options(&self) -> Result<SqliteConnectOptions, A2AError> {
+        let mut options = SqliteConnectOptions::from_str(&self.url)
+            .map_err(|e| A2AError::DatabaseError(format!("Invalid database URL: {}", e)))?;
+        if let Some(millis) = self.statement_timeout_ms {
+            options = options.busy_timeout(Duration::from_millis(millis));
+        }
+        if !self.enable_logging {
+            options = sqlx::ConnectOptions::disable_statement_logging(options);
+        }
+        Ok(options)
+    }
+
     /// Get the database type from the URL
     pub fn database_type(&self) -> &str {
         if self.url.starts_with("sqlite:") {
@@ -0,0 +1,49 @@
+//! Incremental task listings shared by the storage implementations
+
+use std::future::Future;
+
+use crate::{
+    domain::{A2AError, ListTasksStreamItem, ListTasksSummary, Task},
+    port::ListTasksStream,
+};
+
+/// Stream a page of tasks, loading each one only when the consumer polls for
+/// it, and finish with `summary` counting the tasks actually yielded.
+///
+/// `load` returns `None` for a task deleted since the page was selected; it is
+/// skipped. The stream ends after the first error.
+pub(crate) fn stream_listed_tasks<F, Fut>(
+    task_ids: Vec<String>,
+    summary: ListTasksSummary,
+    load: F,
+) -> ListTasksStream
+where
+    F: FnMut(String) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Option<Task>, A2AError>> + Send,
+{
+    let state = (task_ids.into_iter(), load, Some(summary));
+    Box::pin(futures::stream::unfold(
+        state,
+        |(mut task_ids, mut load, mut summary)| async move {
+            let current = summary.as_mut()?;
+            for task_id in task_ids.by_ref() {
+                match load(task_id).await {
+                    Ok(Some(task)) => {
+                        current.count += 1;
+                        return Some((
+                            Ok(ListTasksStreamItem::Task(Box::new(task))),
+                            (task_ids, load, summary),
+                        ));
+                    }
+                    Ok(None) => continue,
+                    Err(e) => return Some((Err(e), (task_ids, load, None))),
+                }
+            }
+            let summary = summary.take()?;
+            Some((
+                Ok(ListTasksStreamItem::Summary(summary)),
+                (task_ids, load, None),
+            ))
+        },
+    ))
+}
@@ -0,0 +1,240 @@
+//! Versioned schema migrations for SQLx storage
+//!
+//! The schema of [`SqlxTaskStorage`](super::SqlxTaskStorage) is built by the
+//! embedded [`MIGRATIONS`], applied in version order. Each migration runs in
+//! its own transaction and is recorded in the `schema_migrations` table in
+//! the same transaction, so it is applied exactly once: migrating a database
+//! that is already up to date changes nothing.
+//!
+//! A database recording a version newer than [`SCHEMA_VERSION`] was migrated
+//! by a later release. It is refused rather than used with a schema this
+//! build does not know.
+//!
+//! Applications extend the schema with additional migrations. These are
+//! recorded under their own scope, numbered from 1 in the order given, so
+//! new ones must be appended to the end of the list.
+
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
+
+use super::sqlx_storage::database_error;
+use crate::domain::A2AError;
+
+/// Scope recording the migrations embedded in this crate
+pub const BASE_SCOPE: &str = "a2a";
+
+/// Scope recording the additional migrations of the application
+pub const APPLICATION_SCOPE: &str = "application";
+
+/// An embedded schema migration
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// The migrations building the task tables, in version order
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        sql: include_str!("../../../migrations/001_initial_schema.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "v030_push_configs",
+        sql: include_str!("../../../migrations/002_v030_push_configs.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "task_events",
+        sql: include_str!("../../../migrations/003_task_events.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "task_results",
+        sql: include_str!("../../../migrations/004_task_results.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "task_versions",
+        sql: include_str!("../../../migrations/005_task_versions.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "task_tags",
+        sql: include_str!("../../../migrations/006_task_tags.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "task_references",
+        sql: include_str!("../../../migrations/007_task_references.sql"),
+    },
+];
+
+/// The schema version this build creates and understands
+pub const SCHEMA_VERSION: i64 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+const CREATE_MIGRATIONS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        scope TEXT NOT NULL,
+        version INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        applied_at TEXT NOT NULL DEFAULT (datetime('now')),
+        PRIMARY KEY (scope, version)
+    )";
+
+/// A migration applied by [`migrate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub scope: &'static str,
+    pub version: i64,
+    pub name: String,
+}
+
+/// Bring the schema up to date, returning the migrations that were applied.
+///
+/// Fails without changing anything when the database records a newer schema
+/// than this build knows, including more additional migrations than
+/// `additional_migrations` holds.
+pub async fn migrate(
+    pool: &SqlitePool,
+    additional_migrations: &[&str],
+) -> Result<Vec<AppliedMigration>, A2AError> {
+    sqlx::query(CREATE_MIGRATIONS_TABLE)
+        .execute(pool)
+        .await
+        .map_err(|e| database_error("Failed to create schema_migrations table", e))?;
+
+    let base_version = recorded_version(pool, BASE_SCOPE).await?;
+    if base_version > SCHEMA_VERSION {
+        return Err(A2AError::DatabaseError(format!(
+            "Database schema version {} is newer than version {} supported by this build",
+            base_version, SCHEMA_VERSION
+        )));
+    }
+    // Storage opened without the application's migrations leaves its tables alone
+    let application_version = recorded_version(pool, APPLICATION_SCOPE).await?;
+    let known_application_version = additional_migrations.len() as i64;
+    if !additional_migrations.is_empty() && application_version > known_application_version {
+        return Err(A2AError::DatabaseError(format!(
+            "Database has {} application migrations applied but only {} are known to this build",
+            application_version, known_application_version
+        )));
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > base_version) {
+        let name = migration.name.to_string();
+        if apply(pool, BASE_SCOPE, migration.version, &name, migration.sql).await? {
+            applied.push(AppliedMigration {
+                scope: BASE_SCOPE,
+                version: migration.version,
+                name,
+            });
+        }
+    }
+    for (version, sql) in (1..).zip(additional_migrations) {
+        if version <= application_version {
+            continue;
+        }
+        let name = format!("additional_{:03}", version);
+        if apply(pool, APPLICATION_SCOPE, version, &name, sql).await? {
+            applied.push(AppliedMigration {
+                scope: APPLICATION_SCOPE,
+                version,
+                name,
+            });
+        }
+    }
+    Ok(applied)
+}
+
+/// The schema version of the database, 0 when it was never migrated
+pub async fn schema_version(pool: &SqlitePool) -> Result<i64, A2AError> {
+    let migrated = sqlx::query(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| database_error("Failed to read schema version", e))?;
+    match migrated {
+        Some(_) => recorded_version(pool, BASE_SCOPE).await,
+        None => Ok(0),
+    }
+}
+
+/// Highest version recorded for `scope`
+async fn recorded_version(pool: &SqlitePool, scope: &str) -> Result<i64, A2AError> {
+    let row = sqlx::query(
+        "SELECT COALESCE(MAX(version), 0) AS version FROM schema_migrations WHERE scope = ?",
+    )
+    .bind(scope)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| database_error("Failed to read schema version", e))?;
+    row.try_get("version")
+        .map_err(|e| database_error("Failed to read schema version", e))
+}
+
+/// Apply one migration and record it, returning whether it was still pending
+async fn apply(
+    pool: &SqlitePool,
+    scope: &str,
+    version: i64,
+    name: &str,
+    sql: &str,
+) -> Result<bool, A2AError> {
+    let context = format!("Migration {} {:03} ({}) failed", scope, version, name);
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| database_error(&context, e))?;
+
+    // Roll back before returning the connection to the pool, rather than
+    // when it is next used, so the failed migration holds no locks
+    let pending = match record(&mut tx, scope, version, name, sql).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            let _ = tx.rollback().await;
+            return Err(database_error(&context, e));
+        }
+    };
+    if !pending {
+        let _ = tx.rollback().await;
+        return Ok(false);
+    }
+    tx.commit().await.map_err(|e| database_error(&context, e))?;
+
+    #[cfg(feature = "tracing")]
+    tracing::info!("Applied migration {} {:03} ({})", scope, version, name);
+    Ok(true)
+}
+
+/// Run a migration and record it within `tx`, unless it is already recorded
+async fn record(
+    tx: &mut Transaction<'_, Sqlite>,
+    scope: &str,
+    version: i64,
+    name: &str,
+    sql: &str,
+) -> Result<bool, sqlx::Error> {
+    // Another process sharing the database may have applied it meanwhile
+    let recorded = sqlx::query("SELECT 1 FROM schema_migrations WHERE scope = ? AND version = ?")
+        .bind(scope)
+        .bind(version)
+        .fetch_optional(&mut **tx)
+        .await?;
+    if recorded.is_some() {
+        return Ok(false);
+    }
+
+    sqlx::query(sql).execute(&mut **tx).await?;
+    sqlx::query("INSERT INTO schema_migrations (scope, version, name) VALUES (?, ?, ?)")
+        .bind(scope)
+        .bind(version)
+        .bind(name)
+        .execute(&mut **tx)
+        .await?;
+    Ok(true)
+}
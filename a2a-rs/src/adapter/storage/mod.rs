@@ -1,5 +1,9 @@
 //! Storage adapter implementations
 
+#[cfg(feature = "server")]
+pub mod cached_storage;
+#[cfg(feature = "server")]
+mod listing;
 #[cfg(feature = "server")]
 pub mod task_storage;
 
@@ -9,6 +13,11 @@ pub mod sqlx_storage;
 #[cfg(feature = "sqlx-storage")]
 pub mod database_config;
 
+#[cfg(feature = "sqlx-storage")]
+pub mod migrations;
+
+#[cfg(feature = "server")]
+pub use cached_storage::{CachedTaskStorage, TaskCacheConfig, TaskCacheStats};
 #[cfg(feature = "server")]
 pub use task_storage::InMemoryTaskStorage;
 
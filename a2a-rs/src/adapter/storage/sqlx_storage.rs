@@ -27,15 +27,22 @@ use crate::adapter::business::push_notification::NoopPushNotificationSender;
 
 #[cfg(feature = "sqlx-storage")]
 use crate::domain::{
-    A2AError, Artifact, Message, Task, TaskArtifactUpdateEvent, TaskPushNotificationConfig,
-    TaskState, TaskStatus, TaskStatusUpdateEvent,
+    A2AError, Artifact, ArtifactSummary, Clock, GetOrCreateTaskResult, GetTaskEventsParams,
+    GetTaskEventsResult, IdGenerator, InputRequest, ListTasksParams, ListTasksSummary, Message,
+    MessageSanitizer, PageSizeLimits, SystemClock, TagMatch, Task, TaskArtifactUpdateEvent,
+    TaskCancellation, TaskEventRecord, TaskField, TaskLogEvent, TaskPushNotificationConfig,
+    TaskResult, TaskState, TaskStatus, TaskStatusUpdateEvent, TaskTagsParams, UuidGenerator,
+    core::task::{MAX_TAGS_PER_TASK, TaskExclusions, validate_tag},
 };
 #[cfg(feature = "sqlx-storage")]
 use crate::port::{
-    AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager,
+    AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager, ListTasksStream,
     streaming_handler::Subscriber,
 };
 
+#[cfg(feature = "sqlx-storage")]
+use super::{database_config::DatabaseConfig, listing::stream_listed_tasks, migrations::migrate};
+
 #[cfg(feature = "sqlx-storage")]
 use std::sync::Arc;
 #[cfg(feature = "sqlx-storage")]
@@ -46,6 +53,68 @@ type StatusSubscribers = Vec<Box<dyn Subscriber<TaskStatusUpdateEvent> + Send +
 #[cfg(feature = "sqlx-storage")]
 type ArtifactSubscribers = Vec<Box<dyn Subscriber<TaskArtifactUpdateEvent> + Send + Sync>>;
 
+#[cfg(feature = "sqlx-storage")]
+/// Map a SQLx error to a domain error, described by `context`.
+///
+/// Failures are classified so callers know whether retrying can help:
+/// - a database out of reach, including every pooled connection being busy,
+///   is reported as [`A2AError::Unavailable`]
+/// - rows that cannot be decoded as [`A2AError::StorageCorrupt`]
+/// - unique and foreign key violations as [`A2AError::StorageConflict`]
+///
+/// Anything else stays an opaque [`A2AError::DatabaseError`].
+pub(super) fn database_error(context: &str, error: sqlx::Error) -> A2AError {
+    match error {
+        sqlx::Error::PoolTimedOut => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Database pool saturated: no connection free ({})", context);
+            A2AError::Unavailable(format!("{}: no database connection available", context))
+        }
+        sqlx::Error::PoolClosed
+        | sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::WorkerCrashed => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Database unreachable ({}): {}", context, error);
+            A2AError::Unavailable(format!("{}: {}", context, error))
+        }
+        sqlx::Error::Database(ref database) if is_busy(database.code().as_deref()) => {
+            A2AError::Unavailable(format!("{}: {}", context, error))
+        }
+        sqlx::Error::Database(ref database)
+            if matches!(
+                database.kind(),
+                sqlx::error::ErrorKind::UniqueViolation
+                    | sqlx::error::ErrorKind::ForeignKeyViolation
+            ) =>
+        {
+            A2AError::StorageConflict(format!("{}: {}", context, error))
+        }
+        sqlx::Error::ColumnDecode { .. } | sqlx::Error::Decode(_) => {
+            #[cfg(feature = "tracing")]
+            tracing::error!("Stored data is corrupt ({}): {}", context, error);
+            A2AError::StorageCorrupt(format!("{}: {}", context, error))
+        }
+        error => A2AError::DatabaseError(format!("{}: {}", context, error)),
+    }
+}
+
+#[cfg(feature = "sqlx-storage")]
+/// Whether a database error code means the database is too busy to answer
+/// now: SQLite's busy and locked results, including their extended codes
+fn is_busy(code: Option<&str>) -> bool {
+    code.and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| matches!(code & 0xff, 5 | 6))
+}
+
+#[cfg(feature = "sqlx-storage")]
+/// A JSON column that could not be parsed back
+fn corrupt_column(column: &str, error: serde_json::Error) -> A2AError {
+    #[cfg(feature = "tracing")]
+    tracing::error!("Stored {} is corrupt: {}", column, error);
+    A2AError::StorageCorrupt(format!("Failed to parse {}: {}", column, error))
+}
+
 #[cfg(feature = "sqlx-storage")]
 /// Structure to hold subscribers for a task
 pub(crate) struct TaskSubscribers {
@@ -72,18 +141,25 @@ pub struct SqlxTaskStorage {
     subscribers: Arc<Mutex<HashMap<String, TaskSubscribers>>>,
     /// Push notification registry
     push_notification_registry: Arc<PushNotificationRegistry>,
+    /// Page sizes applied to `list_tasks_v3`
+    page_limits: PageSizeLimits,
+    /// Timestamps for logged events, results and artifacts
+    clock: Arc<dyn Clock>,
+    /// Ids for cancellation messages, push configs and subscriptions
+    ids: Arc<dyn IdGenerator>,
+    /// Cleanup applied to messages before they are stored
+    sanitizer: MessageSanitizer,
 }
 
 #[cfg(feature = "sqlx-storage")]
 impl SqlxTaskStorage {
     /// Create a new SQLx task storage with the given database URL
     pub async fn new(database_url: &str) -> Result<Self, A2AError> {
-        let pool = SqlitePool::connect(database_url).await.map_err(|e| {
-            A2AError::DatabaseError(format!("Failed to connect to database: {}", e))
-        })?;
+        let pool = SqlitePool::connect(database_url)
+            .await
+            .map_err(|e| database_error("Failed to connect to database", e))?;
 
-        // Run base migrations
-        Self::run_base_migrations(&pool).await?;
+        migrate(&pool, &[]).await?;
 
         // Use the appropriate push notification sender based on available features
         #[cfg(feature = "http-client")]
@@ -97,6 +173,10 @@ impl SqlxTaskStorage {
             pool,
             subscribers: Arc::new(Mutex::new(HashMap::new())),
             push_notification_registry: Arc::new(push_registry),
+            page_limits: PageSizeLimits::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            sanitizer: MessageSanitizer::new(),
         })
     }
 
@@ -105,12 +185,11 @@ impl SqlxTaskStorage {
         database_url: &str,
         push_sender: impl PushNotificationSender + 'static,
     ) -> Result<Self, A2AError> {
-        let pool = SqlitePool::connect(database_url).await.map_err(|e| {
-            A2AError::DatabaseError(format!("Failed to connect to database: {}", e))
-        })?;
+        let pool = SqlitePool::connect(database_url)
+            .await
+            .map_err(|e| database_error("Failed to connect to database", e))?;
 
-        // Run migrations
-        Self::run_base_migrations(&pool).await?;
+        migrate(&pool, &[]).await?;
 
         let push_registry = PushNotificationRegistry::new(push_sender);
 
@@ -118,6 +197,10 @@ impl SqlxTaskStorage {
             pool,
             subscribers: Arc::new(Mutex::new(HashMap::new())),
             push_notification_registry: Arc::new(push_registry),
+            page_limits: PageSizeLimits::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            sanitizer: MessageSanitizer::new(),
         })
     }
 
@@ -126,15 +209,11 @@ impl SqlxTaskStorage {
         database_url: &str,
         additional_migrations: &[&str],
     ) -> Result<Self, A2AError> {
-        let pool = SqlitePool::connect(database_url).await.map_err(|e| {
-            A2AError::DatabaseError(format!("Failed to connect to database: {}", e))
-        })?;
-
-        // Run base migrations
-        Self::run_base_migrations(&pool).await?;
+        let pool = SqlitePool::connect(database_url)
+            .await
+            .map_err(|e| database_error("Failed to connect to database", e))?;
 
-        // Run additional migrations
-        Self::run_additional_migrations(&pool, additional_migrations).await?;
+        migrate(&pool, additional_migrations).await?;
 
         // Use the appropriate push notification sender based on available features
         #[cfg(feature = "http-client")]
@@ -148,112 +227,135 @@ impl SqlxTaskStorage {
             pool,
             subscribers: Arc::new(Mutex::new(HashMap::new())),
             push_notification_registry: Arc::new(push_registry),
+            page_limits: PageSizeLimits::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            sanitizer: MessageSanitizer::new(),
         })
     }
 
-    /// Run base A2A framework migrations
-    async fn run_base_migrations(pool: &SqlitePool) -> Result<(), A2AError> {
-        // For now, assume SQLite and run the SQLite migrations
-        // In a real implementation, you'd detect the database type from the URL
-        sqlx::query(include_str!("../../../migrations/001_initial_schema.sql"))
-            .execute(pool)
+    /// Create a new SQLx task storage with the pool settings of `config` and
+    /// additional migrations
+    pub async fn with_config(
+        config: &DatabaseConfig,
+        additional_migrations: &[&str],
+    ) -> Result<Self, A2AError> {
+        config.validate().map_err(A2AError::DatabaseError)?;
+        let pool = config
+            .pool_options()
+            .connect_with(config.connect_options()?)
             .await
-            .map_err(|e| A2AError::DatabaseError(format!("Migration 001 failed: {}", e)))?;
+            .map_err(|e| database_error("Failed to connect to database", e))?;
 
-        // Run v0.3.0 migration for enhanced push notification configs
-        sqlx::query(include_str!(
-            "../../../migrations/002_v030_push_configs.sql"
-        ))
-        .execute(pool)
-        .await
-        .map_err(|e| A2AError::DatabaseError(format!("Migration 002 failed: {}", e)))?;
+        migrate(&pool, additional_migrations).await?;
 
-        Ok(())
+        #[cfg(feature = "http-client")]
+        let push_sender = HttpPushNotificationSender::new();
+        #[cfg(not(feature = "http-client"))]
+        let push_sender = NoopPushNotificationSender::default();
+
+        Ok(Self {
+            pool,
+            subscribers: Arc::new(Mutex::new(HashMap::new())),
+            push_notification_registry: Arc::new(PushNotificationRegistry::new(push_sender)),
+            page_limits: PageSizeLimits::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            sanitizer: MessageSanitizer::new(),
+        })
     }
 
-    /// Run additional migrations provided by the application
-    async fn run_additional_migrations(
-        pool: &SqlitePool,
-        migrations: &[&str],
-    ) -> Result<(), A2AError> {
-        for (i, migration_sql) in migrations.iter().enumerate() {
-            sqlx::query(migration_sql)
-                .execute(pool)
-                .await
-                .map_err(|e| {
-                    A2AError::DatabaseError(format!("Additional migration {} failed: {}", i + 1, e))
-                })?;
-        }
-        Ok(())
+    /// The connection pool, for querying tables added by additional migrations
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Set the default and maximum page size of task listings
+    pub fn with_page_limits(mut self, limits: PageSizeLimits) -> Self {
+        self.page_limits = limits;
+        self
+    }
+
+    /// Stamp logged events, results and artifacts with `clock` instead of the
+    /// system clock.
+    ///
+    /// Task status timestamps are still kept by the database.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Mint cancellation message, push config and subscription ids with `ids`
+    /// instead of random UUIDs
+    pub fn with_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
+
+    /// Sanitize messages, artifacts, results and cancellation reasons with
+    /// `sanitizer` before storing them, instead of storing them unchanged
+    pub fn with_sanitizer(mut self, sanitizer: MessageSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
     }
 
     /// Convert database row to Task
     fn row_to_task(row: &sqlx::sqlite::SqliteRow) -> Result<Task, A2AError> {
         let task_id: String = row
             .try_get("id")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get task_id: {}", e)))?;
+            .map_err(|e| database_error("Failed to get task_id", e))?;
         let context_id: String = row
             .try_get("context_id")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get context_id: {}", e)))?;
+            .map_err(|e| database_error("Failed to get context_id", e))?;
         let status_state: String = row
             .try_get("status_state")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get status_state: {}", e)))?;
+            .map_err(|e| database_error("Failed to get status_state", e))?;
         let status_message_json: Option<String> = row
             .try_get("status_message")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get status_message: {}", e)))?;
+            .map_err(|e| database_error("Failed to get status_message", e))?;
         let metadata_json: Option<String> = row
             .try_get("metadata")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get metadata: {}", e)))?;
+            .map_err(|e| database_error("Failed to get metadata", e))?;
         let artifacts_json: Option<String> = row
             .try_get("artifacts")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get artifacts: {}", e)))?;
-
-        // Parse task state
-        let state = match status_state.as_str() {
-            "submitted" => TaskState::Submitted,
-            "working" => TaskState::Working,
-            "input-required" => TaskState::InputRequired,
-            "completed" => TaskState::Completed,
-            "canceled" => TaskState::Canceled,
-            "failed" => TaskState::Failed,
-            "rejected" => TaskState::Rejected,
-            "auth-required" => TaskState::AuthRequired,
-            "unknown" => TaskState::Unknown,
-            _ => TaskState::Unknown,
-        };
+            .map_err(|e| database_error("Failed to get artifacts", e))?;
+
+        // Unrecognized states are read as unknown
+        let state = status_state.parse().unwrap_or(TaskState::Unknown);
 
         // Parse status message
         let status_message = if let Some(msg_str) = status_message_json {
-            Some(serde_json::from_str(&msg_str).map_err(|e| {
-                A2AError::DatabaseError(format!("Failed to parse status message: {}", e))
-            })?)
+            Some(serde_json::from_str(&msg_str).map_err(|e| corrupt_column("status message", e))?)
         } else {
             None
         };
 
         // Parse metadata
-        let metadata =
-            if let Some(meta_str) = metadata_json {
-                Some(serde_json::from_str(&meta_str).map_err(|e| {
-                    A2AError::DatabaseError(format!("Failed to parse metadata: {}", e))
-                })?)
-            } else {
-                None
-            };
+        let metadata = if let Some(meta_str) = metadata_json {
+            Some(serde_json::from_str(&meta_str).map_err(|e| corrupt_column("metadata", e))?)
+        } else {
+            None
+        };
 
         // Parse artifacts
         let artifacts = if let Some(artifacts_str) = artifacts_json {
-            Some(serde_json::from_str(&artifacts_str).map_err(|e| {
-                A2AError::DatabaseError(format!("Failed to parse artifacts: {}", e))
-            })?)
+            Some(serde_json::from_str(&artifacts_str).map_err(|e| corrupt_column("artifacts", e))?)
         } else {
             None
         };
 
+        // updated_at is maintained by the database as "YYYY-MM-DD HH:MM:SS" in UTC
+        let updated_at = row
+            .try_get::<String, _>("updated_at")
+            .ok()
+            .and_then(|s| chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").ok())
+            .map(|naive| naive.and_utc())
+            .unwrap_or_else(chrono::Utc::now);
+
         let task_status = TaskStatus {
             state,
             message: status_message,
-            timestamp: Some(chrono::Utc::now()), // For now, use current time
+            timestamp: Some(updated_at),
         };
 
         let task = Task {
@@ -263,9 +365,203 @@ impl SqlxTaskStorage {
             history: None, // Will be set separately if needed
             metadata,
             artifacts,
+            result: None, // Will be set separately if needed
             kind: "task".to_string(),
+            version: 0,                     // Will be set separately if needed
+            tags: Vec::new(),               // Will be set separately if needed
+            reference_task_ids: Vec::new(), // Will be set separately if needed
+            history_cursor: None,
+        };
+
+        Ok(task)
+    }
+
+    /// Load the structured result attached to a task, if any
+    async fn load_task_result<'e, E>(
+        executor: E,
+        task_id: &str,
+    ) -> Result<Option<TaskResult>, A2AError>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let row = sqlx::query("SELECT result FROM task_results WHERE task_id = ?")
+            .bind(task_id)
+            .fetch_optional(executor)
+            .await
+            .map_err(|e| database_error("Failed to load task result", e))?;
+
+        row.map(|row| {
+            let json: String = row
+                .try_get("result")
+                .map_err(|e| database_error("Failed to get result", e))?;
+            serde_json::from_str(&json).map_err(|e| corrupt_column("result", e))
+        })
+        .transpose()
+    }
+
+    /// Load a task's version; tasks created before versioning are at 0
+    async fn load_task_version<'e, E>(executor: E, task_id: &str) -> Result<u64, A2AError>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let row = sqlx::query("SELECT version FROM task_versions WHERE task_id = ?")
+            .bind(task_id)
+            .fetch_optional(executor)
+            .await
+            .map_err(|e| database_error("Failed to load task version", e))?;
+
+        row.map_or(Ok(0), |row| {
+            row.try_get::<i64, _>("version")
+                .map(|version| version as u64)
+                .map_err(|e| database_error("Failed to get version", e))
+        })
+    }
+
+    /// Load a task's tags in sorted order
+    async fn load_task_tags<'e, E>(executor: E, task_id: &str) -> Result<Vec<String>, A2AError>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query_scalar("SELECT tag FROM task_tags WHERE task_id = ? ORDER BY tag")
+            .bind(task_id)
+            .fetch_all(executor)
+            .await
+            .map_err(|e| database_error("Failed to load task tags", e))
+    }
+
+    /// Load the tasks a task references in sorted order
+    async fn load_task_references<'e, E>(
+        executor: E,
+        task_id: &str,
+    ) -> Result<Vec<String>, A2AError>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query_scalar(
+            "SELECT referenced_task_id FROM task_references WHERE task_id = ? \
+             ORDER BY referenced_task_id",
+        )
+        .bind(task_id)
+        .fetch_all(executor)
+        .await
+        .map_err(|e| database_error("Failed to load task references", e))
+    }
+
+    /// Fail with `TaskNotFound` unless the task exists
+    async fn ensure_task_exists(
+        conn: &mut sqlx::SqliteConnection,
+        task_id: &str,
+    ) -> Result<(), A2AError> {
+        let exists = sqlx::query("SELECT id FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(conn)
+            .await
+            .map_err(|e| database_error("Failed to get task", e))?;
+        match exists {
+            Some(_) => Ok(()),
+            None => Err(A2AError::TaskNotFound(task_id.to_string())),
+        }
+    }
+
+    /// Increment a task's version, failing with a conflict unless it is at
+    /// `expected_version`
+    ///
+    /// The compare and increment is a single statement, so of two transactions
+    /// expecting the same version only the first to write succeeds.
+    async fn bump_version(
+        conn: &mut sqlx::SqliteConnection,
+        task_id: &str,
+        expected_version: Option<u64>,
+    ) -> Result<(), A2AError> {
+        let Some(expected) = expected_version else {
+            sqlx::query(
+                "INSERT INTO task_versions (task_id, version) VALUES (?, 1) \
+                 ON CONFLICT(task_id) DO UPDATE SET version = version + 1",
+            )
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| database_error("Failed to bump task version", e))?;
+            return Ok(());
+        };
+
+        sqlx::query("INSERT OR IGNORE INTO task_versions (task_id, version) VALUES (?, 0)")
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| database_error("Failed to bump task version", e))?;
+        let result = sqlx::query(
+            "UPDATE task_versions SET version = version + 1 WHERE task_id = ? AND version = ?",
+        )
+        .bind(task_id)
+        .bind(expected as i64)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| database_error("Failed to bump task version", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(A2AError::VersionConflict {
+                task_id: task_id.to_string(),
+                expected,
+                actual: Self::load_task_version(&mut *conn, task_id).await?,
+            });
+        }
+        Ok(())
+    }
+
+    /// Load a task with optional history, leaving the `excluded` fields out
+    /// as they are read
+    async fn load_task(
+        &self,
+        task_id: &str,
+        history_length: Option<u32>,
+        excluded: &[TaskField],
+    ) -> Result<Task, A2AError> {
+        let exclusions = TaskExclusions::new(task_id, excluded);
+
+        // Get task from database, without the columns left out
+        let row = sqlx::query(
+            "SELECT id, context_id, status_state, status_message, updated_at, \
+             CASE WHEN ? THEN NULL ELSE metadata END AS metadata, \
+             CASE WHEN ? THEN NULL ELSE artifacts END AS artifacts \
+             FROM tasks WHERE id = ?",
+        )
+        .bind(exclusions.metadata)
+        .bind(exclusions.artifacts)
+        .bind(task_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| database_error("Failed to get task", e))?;
+
+        let Some(row) = row else {
+            return Err(A2AError::TaskNotFound(task_id.to_string()));
         };
 
+        let mut task = Self::row_to_task(&row)?;
+        if let Some(message) = &mut task.status.message {
+            exclusions.strip_message(message);
+        }
+        for artifact in task.artifacts.iter_mut().flatten() {
+            exclusions.strip_artifact(artifact);
+        }
+        task.result = Self::load_task_result(&self.pool, task_id).await?;
+        task.version = Self::load_task_version(&self.pool, task_id).await?;
+        task.tags = Self::load_task_tags(&self.pool, task_id).await?;
+        task.reference_task_ids = Self::load_task_references(&self.pool, task_id).await?;
+
+        // Load history, unless none of it is wanted
+        let history_length = exclusions.history_length(history_length);
+        if history_length != Some(0) {
+            let history = self
+                .load_task_history(task_id, history_length, &exclusions)
+                .await?;
+            task.history = if history.is_empty() {
+                None
+            } else {
+                Some(history)
+            };
+        }
+
         Ok(task)
     }
 
@@ -274,14 +570,15 @@ impl SqlxTaskStorage {
         &self,
         task_id: &str,
         limit: Option<u32>,
+        exclusions: &TaskExclusions<'_>,
     ) -> Result<Vec<Message>, A2AError> {
         let query_str = if let Some(limit) = limit {
             format!(
-                "SELECT timestamp, status_state, message FROM task_history WHERE task_id = ? ORDER BY timestamp DESC LIMIT {}",
+                "SELECT timestamp, status_state, message FROM task_history WHERE task_id = ? ORDER BY timestamp DESC, id DESC LIMIT {}",
                 limit
             )
         } else {
-            "SELECT timestamp, status_state, message FROM task_history WHERE task_id = ? ORDER BY timestamp DESC".to_string()
+            "SELECT timestamp, status_state, message FROM task_history WHERE task_id = ? ORDER BY timestamp DESC, id DESC".to_string()
         };
 
         let query = sqlx::query(&query_str);
@@ -290,18 +587,19 @@ impl SqlxTaskStorage {
             .bind(task_id)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to load task history: {}", e)))?;
+            .map_err(|e| database_error("Failed to load task history", e))?;
 
         let mut history = Vec::new();
         for row in rows {
-            let message_json: Option<String> = row.try_get("message").map_err(|e| {
-                A2AError::DatabaseError(format!("Failed to get message from history: {}", e))
-            })?;
+            let message_json: Option<String> = row
+                .try_get("message")
+                .map_err(|e| database_error("Failed to get message from history", e))?;
 
             if let Some(msg_str) = message_json {
-                let message: Message = serde_json::from_str(&msg_str).map_err(|e| {
-                    A2AError::DatabaseError(format!("Failed to parse message from history: {}", e))
-                })?;
+                let mut message: Message = serde_json::from_str(&msg_str)
+                    .map_err(|e| corrupt_column("message from history", e))?;
+                // Drop what is left out before decoding the next message
+                exclusions.strip_message(&mut message);
                 history.push(message);
             }
         }
@@ -313,22 +611,12 @@ impl SqlxTaskStorage {
 
     /// Add entry to task history
     async fn add_to_history(
-        &self,
+        conn: &mut sqlx::SqliteConnection,
         task_id: &str,
         state: TaskState,
-        message: Option<Message>,
+        message: Option<&Message>,
     ) -> Result<(), A2AError> {
-        let state_str = match state {
-            TaskState::Submitted => "submitted",
-            TaskState::Working => "working",
-            TaskState::InputRequired => "input-required",
-            TaskState::Completed => "completed",
-            TaskState::Canceled => "canceled",
-            TaskState::Failed => "failed",
-            TaskState::Rejected => "rejected",
-            TaskState::AuthRequired => "auth-required",
-            TaskState::Unknown => "unknown",
-        };
+        let state_str = state.as_str();
 
         let message_json = if let Some(msg) = message {
             Some(serde_json::to_string(&msg).map_err(|e| {
@@ -342,129 +630,491 @@ impl SqlxTaskStorage {
             .bind(task_id)
             .bind(state_str)
             .bind(message_json)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to add task history: {}", e)))?;
+            .map_err(|e| database_error("Failed to add task history", e))?;
+
+        let references = message.and_then(|msg| msg.reference_task_ids.as_deref());
+        for referenced in references.unwrap_or_default() {
+            if referenced == task_id {
+                continue;
+            }
+            sqlx::query(
+                "INSERT OR IGNORE INTO task_references (task_id, referenced_task_id) VALUES (?, ?)",
+            )
+            .bind(task_id)
+            .bind(referenced)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| database_error("Failed to add task reference", e))?;
+        }
 
         Ok(())
     }
 
-    /// Send a status update to all subscribers for a task
-    pub(crate) async fn broadcast_status_update(
+    /// Append an event to the task's event log
+    ///
+    /// Runs on the caller's connection so the event is committed in the same
+    /// transaction as the change it records.
+    async fn append_event(
         &self,
+        conn: &mut sqlx::SqliteConnection,
         task_id: &str,
-        status: TaskStatus,
-        final_: bool,
+        event: &TaskLogEvent,
     ) -> Result<(), A2AError> {
-        // Create the update event
-        let event = TaskStatusUpdateEvent {
-            task_id: task_id.to_string(),
-            context_id: "default".to_string(), // TODO: get actual context_id
-            kind: "status-update".to_string(),
-            status,
-            final_,
-            metadata: None,
-        };
-
-        // Get all subscribers for this task and notify them
-        {
-            let subscribers_guard = self.subscribers.lock().await;
-
-            if let Some(task_subscribers) = subscribers_guard.get(task_id) {
-                // Clone the subscribers so we don't hold the lock during notification
-                for subscriber in task_subscribers.status.iter() {
-                    if let Err(e) = subscriber.on_update(event.clone()).await {
-                        eprintln!("Failed to notify subscriber: {}", e);
-                    }
-                }
-            }
-        }; // Lock is dropped here
+        let payload = serde_json::to_string(event)
+            .map_err(|e| A2AError::DatabaseError(format!("Failed to serialize event: {}", e)))?;
 
-        // Send push notification if configured
-        if let Err(e) = self
-            .push_notification_registry
-            .send_status_update(task_id, &event)
-            .await
-        {
-            eprintln!("Failed to send push notification: {}", e);
-        }
+        sqlx::query(
+            "INSERT INTO task_events (task_id, sequence, event_type, payload, recorded_at) \
+             VALUES (?, (SELECT COALESCE(MAX(sequence), 0) + 1 FROM task_events WHERE task_id = ?), ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(task_id)
+        .bind(event.event_type())
+        .bind(payload)
+        .bind(self.clock.now().to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| database_error("Failed to append task event", e))?;
 
         Ok(())
     }
 
-    /// Send an artifact update to all subscribers for a task
-    pub(crate) async fn broadcast_artifact_update(
+    /// Record a status change (and the message that came with it) in the event log
+    async fn append_status_events(
         &self,
+        conn: &mut sqlx::SqliteConnection,
         task_id: &str,
-        artifact: Artifact,
-        _index: Option<u32>,
-        _final: bool,
+        state: TaskState,
+        message: Option<Message>,
     ) -> Result<(), A2AError> {
-        // Create the update event
-        let event = TaskArtifactUpdateEvent {
-            task_id: task_id.to_string(),
-            context_id: "default".to_string(), // TODO: get actual context_id
-            kind: "artifact-update".to_string(),
-            artifact,
-            append: None,
-            last_chunk: None,
-            metadata: None,
-        };
-
-        // Get all subscribers for this task
-        {
-            let subscribers_guard = self.subscribers.lock().await;
+        let row = sqlx::query("SELECT * FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| database_error("Failed to get task", e))?;
+        let mut task = Self::row_to_task(&row)?;
+        task.result = Self::load_task_result(&mut *conn, task_id).await?;
 
-            if let Some(task_subscribers) = subscribers_guard.get(task_id) {
-                // Clone the subscribers so we don't hold the lock during notification
-                for subscriber in task_subscribers.artifacts.iter() {
-                    if let Err(e) = subscriber.on_update(event.clone()).await {
-                        eprintln!("Failed to notify subscriber: {}", e);
-                    }
-                }
-            }
-        }; // Lock is dropped here
+        if let Some(message) = &message {
+            let event = TaskLogEvent::MessageAppended {
+                message: message.clone(),
+            };
+            self.append_event(conn, task_id, &event).await?;
+        }
 
-        // Send push notification if configured
-        if let Err(e) = self
-            .push_notification_registry
-            .send_artifact_update(task_id, &event)
+        task.status = TaskStatus {
+            state,
+            message,
+            timestamp: Some(self.clock.now()),
+        };
+        self.append_event(conn, task_id, &TaskLogEvent::status_update(&task))
             .await
-        {
-            eprintln!("Failed to send push notification: {}", e);
-        }
+    }
 
-        Ok(())
+    /// Store a task's result, replacing any earlier one, and record it in the
+    /// event log
+    async fn store_result(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+        task_id: &str,
+        mut result: TaskResult,
+    ) -> Result<(), A2AError> {
+        self.sanitizer.sanitize_result(&mut result);
+        let json = serde_json::to_string(&result)
+            .map_err(|e| A2AError::DatabaseError(format!("Failed to serialize result: {}", e)))?;
+        sqlx::query(
+            "INSERT INTO task_results (task_id, result, recorded_at) VALUES (?, ?, ?) \
+             ON CONFLICT(task_id) DO UPDATE SET result = excluded.result, recorded_at = excluded.recorded_at",
+        )
+        .bind(task_id)
+        .bind(json)
+        .bind(self.clock.now().to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| database_error("Failed to store task result", e))?;
+        self.append_event(conn, task_id, &TaskLogEvent::ResultSet { result })
+            .await
     }
-}
 
-#[cfg(feature = "sqlx-storage")]
-#[async_trait]
-impl AsyncTaskManager for SqlxTaskStorage {
-    async fn create_task<'a>(
+    /// Update a task's status, checking its version in the same transaction if
+    /// one is expected
+    async fn update_status(
         &self,
-        task_id: &'a str,
-        context_id: &'a str,
+        task_id: &str,
+        expected_version: Option<u64>,
+        state: TaskState,
+        message: Option<Message>,
     ) -> Result<Task, A2AError> {
-        // Check if task already exists
-        let existing = sqlx::query("SELECT id FROM tasks WHERE id = ?")
-            .bind(task_id)
-            .fetch_optional(&self.pool)
+        self.update_status_with(task_id, expected_version, state, None, |_| Ok(message))
             .await
-            .map_err(|e| {
-                A2AError::DatabaseError(format!("Failed to check existing task: {}", e))
-            })?;
+    }
 
-        if existing.is_some() {
-            return Err(A2AError::TaskNotFound(format!(
-                "Task {} already exists",
-                task_id
+    /// Update a task's status with the message `message` makes from the task
+    /// as stored, checking its version in the same transaction if one is
+    /// expected. A `result` is stored in the same transaction.
+    ///
+    /// The task is read in the transaction making the update, so `message`
+    /// sees the task the update applies to and may refuse it.
+    async fn update_status_with(
+        &self,
+        task_id: &str,
+        expected_version: Option<u64>,
+        state: TaskState,
+        result: Option<TaskResult>,
+        message: impl FnOnce(&Task) -> Result<Option<Message>, A2AError>,
+    ) -> Result<Task, A2AError> {
+        let mut tx = self.begin().await?;
+        // Writing before reading takes the write lock up front, so the task
+        // cannot change in between and concurrent updates wait their turn
+        // rather than fail to upgrade a read
+        let locked = sqlx::query("UPDATE tasks SET status_state = status_state WHERE id = ?")
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to update task status", e))?;
+        if locked.rows_affected() == 0 {
+            return Err(A2AError::TaskNotFound(task_id.to_string()));
+        }
+        let row = sqlx::query("SELECT * FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to get task", e))?;
+        let mut message = message(&Self::row_to_task(&row)?)?;
+        if let Some(message) = message.as_mut() {
+            self.sanitizer.sanitize(message);
+        }
+        let message_json = message
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| {
+                A2AError::DatabaseError(format!("Failed to serialize status message: {}", e))
+            })?;
+
+        if let Some(result) = result {
+            self.store_result(&mut tx, task_id, result).await?;
+        }
+
+        // Update task in database, keeping the message as the status message
+        sqlx::query("UPDATE tasks SET status_state = ?, status_message = ? WHERE id = ?")
+            .bind(state.as_str())
+            .bind(message_json)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to update task status", e))?;
+        Self::bump_version(&mut tx, task_id, expected_version).await?;
+
+        // Add to history and record the change in the event log
+        let history_changed = message.is_some();
+        Self::add_to_history(&mut tx, task_id, state.clone(), message.as_ref()).await?;
+        self.append_status_events(&mut tx, task_id, state, message)
+            .await?;
+        Self::commit(tx).await?;
+
+        // Get updated task
+        let task = self.get_task(task_id, None).await?;
+
+        // Broadcast status update, with a snapshot when a message was appended
+        self.broadcast_status_update(
+            task_id,
+            task.status.clone(),
+            false,
+            task.final_result(),
+            task.final_artifacts(),
+            history_changed.then_some(&task),
+        )
+        .await?;
+
+        Ok(task)
+    }
+
+    /// Cancel a task, checking its version in the same transaction if one is
+    /// expected
+    async fn cancel(
+        &self,
+        task_id: &str,
+        expected_version: Option<u64>,
+        cancellation: &TaskCancellation,
+    ) -> Result<Task, A2AError> {
+        // Get current task
+        let task = self.get_task(task_id, None).await?;
+        if let Some(expected_version) = expected_version {
+            task.check_version(expected_version)?;
+        }
+
+        // Only working tasks can be canceled
+        if task.status.state != TaskState::Working {
+            return Err(A2AError::TaskNotCancelable(format!(
+                "Task {} is in state {:?} and cannot be canceled",
+                task_id, task.status.state
             )));
         }
 
-        // Create new task
-        let task = Task::new(task_id.to_string(), context_id.to_string());
+        // Create a cancellation message
+        let mut cancellation = cancellation.clone();
+        self.sanitizer.sanitize_cancellation(&mut cancellation);
+        let cancel_message =
+            cancellation.status_message(task_id, &task.context_id, self.ids.next_id());
+        let cancel_message_json = serde_json::to_string(&cancel_message).map_err(|e| {
+            A2AError::DatabaseError(format!("Failed to serialize status message: {}", e))
+        })?;
+
+        // Update task status, keeping the message so the cancellation details
+        // are returned with the task
+        let mut tx = self.begin().await?;
+        sqlx::query("UPDATE tasks SET status_state = ?, status_message = ? WHERE id = ?")
+            .bind("canceled")
+            .bind(cancel_message_json)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to cancel task", e))?;
+        Self::bump_version(&mut tx, task_id, expected_version).await?;
+
+        // Add to history with cancellation message
+        Self::add_to_history(&mut tx, task_id, TaskState::Canceled, Some(&cancel_message)).await?;
+        self.append_status_events(&mut tx, task_id, TaskState::Canceled, Some(cancel_message))
+            .await?;
+        Self::commit(tx).await?;
+
+        // Get updated task
+        let updated_task = self.get_task(task_id, None).await?;
+
+        // Broadcast status update (with final flag set to true)
+        self.broadcast_status_update(
+            task_id,
+            updated_task.status.clone(),
+            true,
+            updated_task.final_result(),
+            updated_task.final_artifacts(),
+            Some(&updated_task),
+        )
+        .await?;
+
+        Ok(updated_task)
+    }
+
+    /// Select `columns` of the page of tasks matching a listing, most
+    /// recently updated first, returning the rows and the listing's counts
+    async fn select_listed_page(
+        &self,
+        params: &ListTasksParams,
+        columns: &str,
+    ) -> Result<(Vec<sqlx::sqlite::SqliteRow>, ListTasksSummary), A2AError> {
+        // Build WHERE clause conditions
+        let mut where_conditions = Vec::new();
+
+        // Filter by context_id
+        if params.context_id.is_some() {
+            where_conditions.push("context_id = ?".to_string());
+        }
+
+        // Filter by status
+        if params.status.is_some() {
+            where_conditions.push("status_state = ?".to_string());
+        }
+
+        // Filter by lastUpdatedAfter
+        let timestamp_str = if let Some(last_updated_after) = params.last_updated_after {
+            // Convert milliseconds to SQLite timestamp
+            let timestamp = chrono::DateTime::from_timestamp_millis(last_updated_after)
+                .unwrap_or(chrono::Utc::now());
+            where_conditions.push("updated_at > ?".to_string());
+            Some(timestamp.format("%Y-%m-%d %H:%M:%S").to_string())
+        } else {
+            None
+        };
+
+        // Filter by tags; all-match requires every distinct tag to be present
+        let mut tags: Vec<&String> = params.tags.iter().flatten().collect();
+        tags.sort();
+        tags.dedup();
+        if !tags.is_empty() {
+            let placeholders = vec!["?"; tags.len()].join(", ");
+            let having = match params.tag_match.unwrap_or_default() {
+                TagMatch::Any => String::new(),
+                TagMatch::All => format!(
+                    " GROUP BY task_id HAVING COUNT(DISTINCT tag) = {}",
+                    tags.len()
+                ),
+            };
+            where_conditions.push(format!(
+                "id IN (SELECT task_id FROM task_tags WHERE tag IN ({}){})",
+                placeholders, having
+            ));
+        }
+
+        // Build WHERE clause
+        let where_clause = if where_conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", where_conditions.join(" AND "))
+        };
+
+        // First, get total count with same filters
+        let count_query = format!("SELECT COUNT(*) as count FROM tasks{}", where_clause);
+        let mut count_q = sqlx::query(&count_query);
+
+        // Bind parameters for count query
+        if let Some(ref context_id) = params.context_id {
+            count_q = count_q.bind(context_id);
+        }
+        if let Some(ref status) = params.status {
+            let state_str = status.as_str();
+            count_q = count_q.bind(state_str);
+        }
+        if let Some(ref ts) = timestamp_str {
+            count_q = count_q.bind(ts);
+        }
+        for tag in &tags {
+            count_q = count_q.bind(*tag);
+        }
+
+        let count_row = count_q
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| database_error("Failed to count tasks", e))?;
+
+        let total_size: i32 = count_row
+            .try_get("count")
+            .map_err(|e| database_error("Failed to get count", e))?;
+
+        // Handle pagination
+        let page_size = self.page_limits.resolve(params.page_size) as i32;
+        let offset = if let Some(ref token) = params.page_token {
+            token.parse::<i32>().unwrap_or(0)
+        } else {
+            0
+        };
+
+        // Build main query with LIMIT and OFFSET
+        let main_query = format!(
+            "SELECT {} FROM tasks{} ORDER BY updated_at DESC LIMIT ? OFFSET ?",
+            columns, where_clause
+        );
+
+        let mut main_q = sqlx::query(&main_query);
+
+        // Bind parameters for main query
+        if let Some(ref context_id) = params.context_id {
+            main_q = main_q.bind(context_id);
+        }
+        if let Some(ref status) = params.status {
+            let state_str = status.as_str();
+            main_q = main_q.bind(state_str);
+        }
+        if let Some(ref ts) = timestamp_str {
+            main_q = main_q.bind(ts);
+        }
+        for tag in &tags {
+            main_q = main_q.bind(*tag);
+        }
+
+        // Bind LIMIT and OFFSET
+        main_q = main_q.bind(page_size).bind(offset);
 
+        let rows = main_q
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| database_error("Failed to list tasks", e))?;
+
+        // Generate next page token
+        let has_more = offset + page_size < total_size;
+        let next_page_token = if has_more {
+            (offset + page_size).to_string()
+        } else {
+            String::new()
+        };
+
+        let summary = ListTasksSummary {
+            count: 0,
+            total_size,
+            page_size,
+            next_page_token,
+        };
+        Ok((rows, summary))
+    }
+
+    /// Load a listed task with the history and artifacts the listing asked
+    /// for, or `None` if it has been deleted
+    async fn load_listed_task(
+        &self,
+        task_id: &str,
+        params: &ListTasksParams,
+    ) -> Result<Option<Task>, A2AError> {
+        let row = sqlx::query("SELECT * FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| database_error("Failed to get task", e))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let task = Self::row_to_task(&row)?;
+        self.complete_listed_task(task, params).await.map(Some)
+    }
+
+    /// Fill in a listed task's result, version, tags and references, with
+    /// the history and artifacts the listing asked for
+    async fn complete_listed_task(
+        &self,
+        mut task: Task,
+        params: &ListTasksParams,
+    ) -> Result<Task, A2AError> {
+        let task_id = task.id.clone();
+        let task_id = task_id.as_str();
+        task.result = Self::load_task_result(&self.pool, task_id).await?;
+        task.version = Self::load_task_version(&self.pool, task_id).await?;
+        task.tags = Self::load_task_tags(&self.pool, task_id).await?;
+        task.reference_task_ids = Self::load_task_references(&self.pool, task_id).await?;
+
+        // Load history if requested
+        let history_length = params.history_length.unwrap_or(0);
+        if history_length > 0 {
+            let history = self
+                .load_task_history(
+                    task_id,
+                    Some(history_length as u32),
+                    &TaskExclusions::new(task_id, &[]),
+                )
+                .await?;
+            task.history = if history.is_empty() {
+                None
+            } else {
+                Some(history)
+            };
+        } else {
+            task.history = None;
+        }
+
+        // Remove artifacts if not requested
+        if !params.include_artifacts.unwrap_or(false) {
+            task.artifacts = None;
+        }
+
+        Ok(task)
+    }
+
+    /// Begin a database transaction
+    async fn begin(&self) -> Result<sqlx::Transaction<'_, sqlx::Sqlite>, A2AError> {
+        self.pool
+            .begin()
+            .await
+            .map_err(|e| database_error("Failed to begin transaction", e))
+    }
+
+    /// Insert a new task with its first history entry, returning false
+    /// without changing anything if a task with its ID exists
+    async fn insert_task(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+        task: &Task,
+    ) -> Result<bool, A2AError> {
         // Convert metadata and artifacts to JSON strings
         let metadata_json = task
             .metadata
@@ -480,160 +1130,542 @@ impl AsyncTaskManager for SqlxTaskStorage {
             .as_ref()
             .map(|m| serde_json::to_string(m).unwrap_or_default());
 
-        // Insert into database
-        sqlx::query("INSERT INTO tasks (id, context_id, status_state, status_message, metadata, artifacts) VALUES (?, ?, ?, ?, ?, ?)")
-            .bind(&task.id)
-            .bind(&task.context_id)
-            .bind("submitted")
-            .bind(status_message_str)
-            .bind(metadata_json)
-            .bind(artifacts_json)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to create task: {}", e)))?;
+        let inserted = sqlx::query(
+            "INSERT INTO tasks (id, context_id, status_state, status_message, metadata, artifacts) \
+             VALUES (?, ?, ?, ?, ?, ?) ON CONFLICT(id) DO NOTHING",
+        )
+        .bind(&task.id)
+        .bind(&task.context_id)
+        .bind(task.status.state.as_str())
+        .bind(status_message_str)
+        .bind(metadata_json)
+        .bind(artifacts_json)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| database_error("Failed to create task", e))?
+        .rows_affected()
+            == 1;
+        if !inserted {
+            return Ok(false);
+        }
 
-        // Add initial history entry
-        self.add_to_history(task_id, TaskState::Submitted, None)
+        // Add initial history entry, with the message the task starts with
+        let message = task.status.message.as_ref();
+        Self::add_to_history(conn, &task.id, task.status.state.clone(), message).await?;
+        Self::bump_version(conn, &task.id, None).await?;
+        if let Some(message) = message {
+            let event = TaskLogEvent::MessageAppended {
+                message: message.clone(),
+            };
+            self.append_event(conn, &task.id, &event).await?;
+        }
+        self.append_event(conn, &task.id, &TaskLogEvent::status_update(task))
             .await?;
+        Ok(true)
+    }
+
+    /// Commit a database transaction
+    async fn commit(tx: sqlx::Transaction<'_, sqlx::Sqlite>) -> Result<(), A2AError> {
+        tx.commit()
+            .await
+            .map_err(|e| database_error("Failed to commit transaction", e))
+    }
+
+    /// Send a status update to all subscribers for a task, with `snapshot`
+    /// for those that asked for task snapshots
+    pub(crate) async fn broadcast_status_update(
+        &self,
+        task_id: &str,
+        status: TaskStatus,
+        final_: bool,
+        result: Option<TaskResult>,
+        artifacts: Option<Vec<ArtifactSummary>>,
+        snapshot: Option<&Task>,
+    ) -> Result<(), A2AError> {
+        // Create the update event
+        let event = TaskStatusUpdateEvent {
+            task_id: task_id.to_string(),
+            context_id: "default".to_string(), // TODO: get actual context_id
+            kind: "status-update".to_string(),
+            status,
+            final_,
+            result,
+            artifacts,
+            metadata: None,
+            task: None,
+            diff: None,
+        };
+
+        // Get all subscribers for this task and notify them
+        {
+            let subscribers_guard = self.subscribers.lock().await;
+
+            if let Some(task_subscribers) = subscribers_guard.get(task_id) {
+                // Clone the subscribers so we don't hold the lock during notification
+                for subscriber in task_subscribers.status.iter() {
+                    let update =
+                        event.for_subscriber(snapshot, subscriber.task_snapshots().as_ref());
+                    if let Err(e) = subscriber.on_update(update).await {
+                        eprintln!("Failed to notify subscriber: {}", e);
+                    }
+                }
+            }
+        }; // Lock is dropped here
+
+        // Send push notification if configured
+        if let Err(e) = self
+            .push_notification_registry
+            .send_status_update(task_id, &event)
+            .await
+        {
+            eprintln!("Failed to send push notification: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Send an artifact update to all subscribers for a task
+    pub(crate) async fn broadcast_artifact_update(
+        &self,
+        task_id: &str,
+        artifact: Artifact,
+        _index: Option<u32>,
+        _final: bool,
+    ) -> Result<(), A2AError> {
+        // Create the update event
+        let event = TaskArtifactUpdateEvent {
+            task_id: task_id.to_string(),
+            context_id: "default".to_string(), // TODO: get actual context_id
+            kind: "artifact-update".to_string(),
+            artifact,
+            append: None,
+            last_chunk: None,
+            metadata: None,
+        };
+
+        // Get all subscribers for this task
+        {
+            let subscribers_guard = self.subscribers.lock().await;
+
+            if let Some(task_subscribers) = subscribers_guard.get(task_id) {
+                // Clone the subscribers so we don't hold the lock during notification
+                for subscriber in task_subscribers.artifacts.iter() {
+                    if let Err(e) = subscriber.on_update(event.clone()).await {
+                        eprintln!("Failed to notify subscriber: {}", e);
+                    }
+                }
+            }
+        }; // Lock is dropped here
+
+        // Send push notification if configured
+        if let Err(e) = self
+            .push_notification_registry
+            .send_artifact_update(task_id, &event)
+            .await
+        {
+            eprintln!("Failed to send push notification: {}", e);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[async_trait]
+impl AsyncTaskManager for SqlxTaskStorage {
+    async fn create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+    ) -> Result<Task, A2AError> {
+        let task = Task::new_at(
+            task_id.to_string(),
+            context_id.to_string(),
+            self.clock.now(),
+        );
+
+        let mut tx = self.begin().await?;
+        if !self.insert_task(&mut tx, &task).await? {
+            return Err(A2AError::TaskNotFound(format!(
+                "Task {} already exists",
+                task_id
+            )));
+        }
+        Self::commit(tx).await?;
 
         Ok(task)
     }
 
+    async fn get_or_create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+        initial_message: Option<&'a Message>,
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        let mut task = Task::new_at(
+            task_id.to_string(),
+            context_id.to_string(),
+            self.clock.now(),
+        );
+        if let Some(mut message) = initial_message.cloned() {
+            self.sanitizer.sanitize(&mut message);
+            task = task.with_initial_message(message);
+        }
+
+        // The insert either creates the task with its message or finds it
+        // taken, so concurrent callers can't both create it
+        let mut tx = self.begin().await?;
+        let created = self.insert_task(&mut tx, &task).await?;
+        if created {
+            Self::commit(tx).await?;
+        } else {
+            tx.rollback()
+                .await
+                .map_err(|e| database_error("Failed to roll back transaction", e))?;
+        }
+
+        Ok(GetOrCreateTaskResult {
+            task: self.get_task(task_id, None).await?,
+            created,
+        })
+    }
+
     async fn update_task_status<'a>(
         &self,
         task_id: &'a str,
         state: TaskState,
         message: Option<Message>,
     ) -> Result<Task, A2AError> {
-        // Convert state to string
-        let state_str = match state {
-            TaskState::Submitted => "submitted",
-            TaskState::Working => "working",
-            TaskState::InputRequired => "input-required",
-            TaskState::Completed => "completed",
-            TaskState::Canceled => "canceled",
-            TaskState::Failed => "failed",
-            TaskState::Rejected => "rejected",
-            TaskState::AuthRequired => "auth-required",
-            TaskState::Unknown => "unknown",
-        };
+        self.update_status(task_id, None, state, message).await
+    }
 
-        // Update task in database
-        let result = sqlx::query("UPDATE tasks SET status_state = ? WHERE id = ?")
-            .bind(state_str)
+    async fn task_exists<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
+        let row = sqlx::query("SELECT id FROM tasks WHERE id = ?")
             .bind(task_id)
-            .execute(&self.pool)
+            .fetch_optional(&self.pool)
             .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to update task status: {}", e)))?;
+            .map_err(|e| database_error("Failed to check task existence", e))?;
 
-        if result.rows_affected() == 0 {
+        Ok(row.is_some())
+    }
+
+    async fn get_task<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+    ) -> Result<Task, A2AError> {
+        self.load_task(task_id, history_length, &[]).await
+    }
+
+    async fn get_task_excluding<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+        excluded: &'a [TaskField],
+    ) -> Result<Task, A2AError> {
+        self.load_task(task_id, history_length, excluded).await
+    }
+
+    async fn cancel_task<'a>(&self, task_id: &'a str) -> Result<Task, A2AError> {
+        self.cancel(task_id, None, &TaskCancellation::new()).await
+    }
+
+    async fn set_task_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: TaskResult,
+    ) -> Result<Task, A2AError> {
+        let mut tx = self.begin().await?;
+        let exists = sqlx::query("SELECT id FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to get task", e))?;
+        if exists.is_none() {
             return Err(A2AError::TaskNotFound(task_id.to_string()));
         }
 
-        // Add to history
-        self.add_to_history(task_id, state, message).await?;
+        self.store_result(&mut tx, task_id, result).await?;
+        Self::bump_version(&mut tx, task_id, None).await?;
+        Self::commit(tx).await?;
 
-        // Get updated task
-        let task = self.get_task(task_id, None).await?;
-
-        // Broadcast status update
-        self.broadcast_status_update(task_id, task.status.clone(), false)
-            .await?;
+        self.get_task(task_id, None).await
+    }
 
-        Ok(task)
+    async fn complete_task_with_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: TaskResult,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.update_status_with(task_id, None, TaskState::Completed, Some(result), |_| {
+            Ok(message)
+        })
+        .await
     }
 
-    async fn task_exists<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
-        let row = sqlx::query("SELECT id FROM tasks WHERE id = ?")
+    async fn add_task_artifact<'a>(
+        &self,
+        task_id: &'a str,
+        artifact: Artifact,
+    ) -> Result<Task, A2AError> {
+        artifact.validate()?;
+        let mut artifact = Artifact {
+            created_at: Some(self.clock.now()),
+            ..artifact
+        };
+        self.sanitizer.sanitize_artifact(&mut artifact);
+
+        let mut tx = self.begin().await?;
+        let row = sqlx::query("SELECT artifacts FROM tasks WHERE id = ?")
             .bind(task_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *tx)
             .await
-            .map_err(|e| {
-                A2AError::DatabaseError(format!("Failed to check task existence: {}", e))
-            })?;
+            .map_err(|e| database_error("Failed to get task", e))?
+            .ok_or_else(|| A2AError::TaskNotFound(task_id.to_string()))?;
+        let artifacts_json: Option<String> = row
+            .try_get("artifacts")
+            .map_err(|e| database_error("Failed to get artifacts", e))?;
+        let mut artifacts: Vec<Artifact> = match artifacts_json {
+            Some(json) => {
+                serde_json::from_str(&json).map_err(|e| corrupt_column("artifacts", e))?
+            }
+            None => Vec::new(),
+        };
+        match artifacts
+            .iter_mut()
+            .find(|existing| existing.artifact_id == artifact.artifact_id)
+        {
+            Some(existing) => *existing = artifact.clone(),
+            None => artifacts.push(artifact.clone()),
+        }
+        let json = serde_json::to_string(&artifacts).map_err(|e| {
+            A2AError::DatabaseError(format!("Failed to serialize artifacts: {}", e))
+        })?;
 
-        Ok(row.is_some())
+        sqlx::query("UPDATE tasks SET artifacts = ? WHERE id = ?")
+            .bind(json)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to store task artifact", e))?;
+        Self::bump_version(&mut tx, task_id, None).await?;
+        let event = TaskLogEvent::ArtifactAdded {
+            artifact: ArtifactSummary::from(&artifact),
+        };
+        self.append_event(&mut tx, task_id, &event).await?;
+        Self::commit(tx).await?;
+
+        self.broadcast_artifact_update(task_id, artifact, None, true)
+            .await?;
+        self.get_task(task_id, None).await
+    }
+
+    async fn update_task_status_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.update_status(task_id, Some(expected_version), state, message)
+            .await
+    }
+
+    async fn request_input<'a>(
+        &self,
+        task_id: &'a str,
+        request: &'a InputRequest,
+    ) -> Result<Task, A2AError> {
+        self.update_status_with(task_id, None, TaskState::InputRequired, None, |task| {
+            task.check_can_request_input()?;
+            let message_id = self.ids.next_id();
+            Ok(Some(request.status_message(
+                task_id,
+                &task.context_id,
+                message_id,
+            )))
+        })
+        .await
     }
 
-    async fn get_task<'a>(
+    async fn cancel_task_at_version<'a>(
         &self,
         task_id: &'a str,
-        history_length: Option<u32>,
+        expected_version: u64,
     ) -> Result<Task, A2AError> {
-        // Get task from database
-        let row = sqlx::query("SELECT * FROM tasks WHERE id = ?")
-            .bind(task_id)
-            .fetch_optional(&self.pool)
+        self.cancel(task_id, Some(expected_version), &TaskCancellation::new())
             .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get task: {}", e)))?;
+    }
 
-        let Some(row) = row else {
-            return Err(A2AError::TaskNotFound(task_id.to_string()));
-        };
+    async fn cancel_task_with<'a>(
+        &self,
+        task_id: &'a str,
+        cancellation: &'a TaskCancellation,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        self.cancel(task_id, expected_version, cancellation).await
+    }
 
-        let mut task = Self::row_to_task(&row)?;
+    async fn add_task_tags<'a>(&self, params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        for tag in &params.tags {
+            validate_tag(tag)?;
+        }
 
-        // Load history
-        if history_length.is_some() || history_length.is_none() {
-            let history = self.load_task_history(task_id, history_length).await?;
-            task.history = if history.is_empty() {
-                None
-            } else {
-                Some(history)
-            };
+        let mut tx = self.begin().await?;
+        Self::ensure_task_exists(&mut tx, &params.id).await?;
+        for tag in &params.tags {
+            sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag) VALUES (?, ?)")
+                .bind(&params.id)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| database_error("Failed to add task tag", e))?;
         }
+        // Dropping the transaction on error rolls the new tags back
+        if Self::load_task_tags(&mut *tx, &params.id).await?.len() > MAX_TAGS_PER_TASK {
+            return Err(A2AError::ValidationError {
+                field: "tags".to_string(),
+                message: format!("A task may carry at most {} tags", MAX_TAGS_PER_TASK),
+            });
+        }
+        Self::commit(tx).await?;
 
-        Ok(task)
+        self.get_task(&params.id, None).await
     }
 
-    async fn cancel_task<'a>(&self, task_id: &'a str) -> Result<Task, A2AError> {
-        // Get current task
-        let task = self.get_task(task_id, None).await?;
-
-        // Only working tasks can be canceled
-        if task.status.state != TaskState::Working {
-            return Err(A2AError::TaskNotCancelable(format!(
-                "Task {} is in state {:?} and cannot be canceled",
-                task_id, task.status.state
-            )));
+    async fn remove_task_tags<'a>(&self, params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        let mut tx = self.begin().await?;
+        Self::ensure_task_exists(&mut tx, &params.id).await?;
+        for tag in &params.tags {
+            sqlx::query("DELETE FROM task_tags WHERE task_id = ? AND tag = ?")
+                .bind(&params.id)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| database_error("Failed to remove task tag", e))?;
         }
+        Self::commit(tx).await?;
 
-        // Create a cancellation message
-        let cancel_message = Message {
-            role: crate::domain::Role::Agent,
-            parts: vec![crate::domain::Part::Text {
-                text: format!("Task {} canceled.", task_id),
-                metadata: None,
-            }],
-            metadata: None,
-            reference_task_ids: None,
-            message_id: uuid::Uuid::new_v4().to_string(),
-            task_id: Some(task_id.to_string()),
-            context_id: Some(task.context_id.clone()),
-            extensions: None,
-            kind: "message".to_string(),
-        };
+        self.get_task(&params.id, None).await
+    }
 
-        // Update task status
-        sqlx::query("UPDATE tasks SET status_state = ? WHERE id = ?")
-            .bind("canceled")
-            .bind(task_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to cancel task: {}", e)))?;
+    async fn list_referencing_tasks<'a>(&self, task_id: &'a str) -> Result<Vec<Task>, A2AError> {
+        if !self.task_exists(task_id).await? {
+            return Err(A2AError::TaskNotFound(task_id.to_string()));
+        }
+        let referencing: Vec<String> = sqlx::query_scalar(
+            "SELECT task_id FROM task_references WHERE referenced_task_id = ? ORDER BY task_id",
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| database_error("Failed to list referencing tasks", e))?;
 
-        // Add to history with cancellation message
-        self.add_to_history(task_id, TaskState::Canceled, Some(cancel_message))
-            .await?;
+        let mut tasks = Vec::with_capacity(referencing.len());
+        for id in &referencing {
+            let mut task = self.get_task(id, Some(0)).await?;
+            task.history = None;
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
 
-        // Get updated task
-        let updated_task = self.get_task(task_id, None).await?;
+    async fn import_task<'a>(&self, task: &'a Task) -> Result<(), A2AError> {
+        let mut task = task.clone();
+        self.sanitizer.sanitize_task(&mut task);
+        let task = &task;
+        let state_str = task.status.state.as_str();
+        let status_message = task
+            .status
+            .message
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let metadata = task
+            .metadata
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let artifacts = task
+            .artifacts
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        // Keep the imported status time in the database's own format
+        let updated_at = task
+            .status
+            .timestamp
+            .unwrap_or_else(|| self.clock.now())
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        // The insert itself tells whether the ID is taken, so a concurrent
+        // import of the same task cannot slip in between a check and the write
+        let mut tx = self.begin().await?;
+        let inserted = sqlx::query(
+            "INSERT INTO tasks (id, context_id, status_state, status_message, metadata, artifacts, \
+             created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(id) DO NOTHING",
+        )
+        .bind(&task.id)
+        .bind(&task.context_id)
+        .bind(state_str)
+        .bind(status_message)
+        .bind(metadata)
+        .bind(artifacts)
+        .bind(&updated_at)
+        .bind(&updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| database_error("Failed to import task", e))?
+        .rows_affected();
+        if inserted == 0 {
+            return Err(A2AError::ValidationError {
+                field: "id".to_string(),
+                message: format!("Task '{}' already exists", task.id),
+            });
+        }
 
-        // Broadcast status update (with final flag set to true)
-        self.broadcast_status_update(task_id, updated_task.status.clone(), true)
+        for message in task.history.as_deref().unwrap_or_default() {
+            Self::add_to_history(&mut tx, &task.id, task.status.state.clone(), Some(message))
+                .await?;
+        }
+        for referenced in &task.reference_task_ids {
+            sqlx::query(
+                "INSERT OR IGNORE INTO task_references (task_id, referenced_task_id) VALUES (?, ?)",
+            )
+            .bind(&task.id)
+            .bind(referenced)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to add task reference", e))?;
+        }
+        for tag in &task.tags {
+            sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag) VALUES (?, ?)")
+                .bind(&task.id)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| database_error("Failed to add task tag", e))?;
+        }
+        if let Some(result) = &task.result {
+            sqlx::query("INSERT INTO task_results (task_id, result, recorded_at) VALUES (?, ?, ?)")
+                .bind(&task.id)
+                .bind(serde_json::to_string(result)?)
+                .bind(self.clock.now().to_rfc3339())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| database_error("Failed to store task result", e))?;
+        }
+        sqlx::query("INSERT INTO task_versions (task_id, version) VALUES (?, ?)")
+            .bind(&task.id)
+            .bind(task.version as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| database_error("Failed to set task version", e))?;
+        self.append_event(&mut tx, &task.id, &TaskLogEvent::status_update(task))
             .await?;
-
-        Ok(updated_task)
+        Self::commit(tx).await
     }
 
     // ===== v0.3.0 Methods =====
@@ -644,147 +1676,104 @@ impl AsyncTaskManager for SqlxTaskStorage {
     ) -> Result<crate::domain::ListTasksResult, A2AError> {
         use crate::domain::ListTasksResult;
 
-        // Build WHERE clause conditions
-        let mut where_conditions = Vec::new();
-
-        // Filter by context_id
-        if params.context_id.is_some() {
-            where_conditions.push("context_id = ?".to_string());
-        }
-
-        // Filter by status
-        if params.status.is_some() {
-            where_conditions.push("status_state = ?".to_string());
+        let (rows, summary) = self.select_listed_page(params, "*").await?;
+        let mut tasks = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let task = Self::row_to_task(row)?;
+            tasks.push(self.complete_listed_task(task, params).await?);
         }
 
-        // Filter by lastUpdatedAfter
-        let timestamp_str = if let Some(last_updated_after) = params.last_updated_after {
-            // Convert milliseconds to SQLite timestamp
-            let timestamp = chrono::DateTime::from_timestamp_millis(last_updated_after)
-                .unwrap_or(chrono::Utc::now());
-            where_conditions.push("updated_at > ?".to_string());
-            Some(timestamp.format("%Y-%m-%d %H:%M:%S").to_string())
-        } else {
-            None
-        };
-
-        // Build WHERE clause
-        let where_clause = if where_conditions.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", where_conditions.join(" AND "))
-        };
-
-        // First, get total count with same filters
-        let count_query = format!("SELECT COUNT(*) as count FROM tasks{}", where_clause);
-        let mut count_q = sqlx::query(&count_query);
+        Ok(ListTasksResult {
+            tasks,
+            total_size: summary.total_size,
+            page_size: summary.page_size,
+            next_page_token: summary.next_page_token,
+        })
+    }
 
-        // Bind parameters for count query
-        if let Some(ref context_id) = params.context_id {
-            count_q = count_q.bind(context_id);
-        }
-        if let Some(ref status) = params.status {
-            let state_str = match status {
-                crate::domain::TaskState::Submitted => "submitted",
-                crate::domain::TaskState::Working => "working",
-                crate::domain::TaskState::InputRequired => "input-required",
-                crate::domain::TaskState::Completed => "completed",
-                crate::domain::TaskState::Canceled => "canceled",
-                crate::domain::TaskState::Failed => "failed",
-                crate::domain::TaskState::Rejected => "rejected",
-                crate::domain::TaskState::AuthRequired => "auth-required",
-                crate::domain::TaskState::Unknown => "unknown",
-            };
-            count_q = count_q.bind(state_str);
-        }
-        if let Some(ref ts) = timestamp_str {
-            count_q = count_q.bind(ts);
-        }
+    async fn list_tasks_stream<'a>(
+        &self,
+        params: &'a crate::domain::ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        // Only the IDs are selected up front; each task is loaded as the
+        // stream reaches it
+        let (rows, summary) = self.select_listed_page(params, "id").await?;
+        let task_ids = rows
+            .iter()
+            .map(|row| row.try_get::<String, _>("id"))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| database_error("Failed to get task_id", e))?;
+
+        let storage = self.clone();
+        let params = params.clone();
+        Ok(stream_listed_tasks(task_ids, summary, move |task_id| {
+            let storage = storage.clone();
+            let params = params.clone();
+            async move { storage.load_listed_task(&task_id, &params).await }
+        }))
+    }
 
-        let count_row = count_q
+    async fn get_task_events<'a>(
+        &self,
+        params: &'a GetTaskEventsParams,
+    ) -> Result<GetTaskEventsResult, A2AError> {
+        let count_row = sqlx::query("SELECT COUNT(*) as count FROM task_events WHERE task_id = ?")
+            .bind(&params.id)
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to count tasks: {}", e)))?;
+            .map_err(|e| database_error("Failed to count task events", e))?;
 
         let total_size: i32 = count_row
             .try_get("count")
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to get count: {}", e)))?;
-
-        // Handle pagination
-        let page_size = params.page_size.unwrap_or(50).clamp(1, 100);
-        let offset = if let Some(ref token) = params.page_token {
-            token.parse::<i32>().unwrap_or(0)
-        } else {
-            0
-        };
-
-        // Build main query with LIMIT and OFFSET
-        let main_query = format!(
-            "SELECT * FROM tasks{} ORDER BY updated_at DESC LIMIT ? OFFSET ?",
-            where_clause
-        );
-
-        let mut main_q = sqlx::query(&main_query);
+            .map_err(|e| database_error("Failed to get count", e))?;
 
-        // Bind parameters for main query
-        if let Some(ref context_id) = params.context_id {
-            main_q = main_q.bind(context_id);
-        }
-        if let Some(ref status) = params.status {
-            let state_str = match status {
-                crate::domain::TaskState::Submitted => "submitted",
-                crate::domain::TaskState::Working => "working",
-                crate::domain::TaskState::InputRequired => "input-required",
-                crate::domain::TaskState::Completed => "completed",
-                crate::domain::TaskState::Canceled => "canceled",
-                crate::domain::TaskState::Failed => "failed",
-                crate::domain::TaskState::Rejected => "rejected",
-                crate::domain::TaskState::AuthRequired => "auth-required",
-                crate::domain::TaskState::Unknown => "unknown",
-            };
-            main_q = main_q.bind(state_str);
-        }
-        if let Some(ref ts) = timestamp_str {
-            main_q = main_q.bind(ts);
+        if total_size == 0 && !self.task_exists(&params.id).await? {
+            return Err(A2AError::TaskNotFound(params.id.clone()));
         }
 
-        // Bind LIMIT and OFFSET
-        main_q = main_q.bind(page_size).bind(offset);
-
-        let rows = main_q
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| A2AError::DatabaseError(format!("Failed to list tasks: {}", e)))?;
-
-        // Convert rows to tasks
-        let mut tasks: Vec<Task> = rows
-            .iter()
-            .filter_map(|row| Self::row_to_task(row).ok())
-            .collect();
+        let page_size = params.page_size.unwrap_or(50).clamp(1, 100);
+        let offset = params
+            .page_token
+            .as_ref()
+            .and_then(|token| token.parse::<i32>().ok())
+            .unwrap_or(0);
 
-        // Load history for each task if requested
-        let history_length = params.history_length.unwrap_or(0);
-        for task in &mut tasks {
-            if history_length > 0 {
-                let history = self
-                    .load_task_history(&task.id, Some(history_length as u32))
-                    .await?;
-                task.history = if history.is_empty() {
-                    None
-                } else {
-                    Some(history)
-                };
-            } else {
-                task.history = None;
-            }
+        let rows = sqlx::query(
+            "SELECT sequence, payload, recorded_at FROM task_events WHERE task_id = ? ORDER BY sequence ASC LIMIT ? OFFSET ?",
+        )
+        .bind(&params.id)
+        .bind(page_size)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| database_error("Failed to load task events", e))?;
 
-            // Remove artifacts if not requested
-            if !params.include_artifacts.unwrap_or(false) {
-                task.artifacts = None;
-            }
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let sequence: i64 = row
+                .try_get("sequence")
+                .map_err(|e| database_error("Failed to get sequence", e))?;
+            let payload: String = row
+                .try_get("payload")
+                .map_err(|e| database_error("Failed to get payload", e))?;
+            let recorded_at: String = row
+                .try_get("recorded_at")
+                .map_err(|e| database_error("Failed to get recorded_at", e))?;
+
+            let event: TaskLogEvent =
+                serde_json::from_str(&payload).map_err(|e| corrupt_column("event", e))?;
+            let timestamp = chrono::DateTime::parse_from_rfc3339(&recorded_at)
+                .map_err(|e| A2AError::StorageCorrupt(format!("Failed to parse timestamp: {}", e)))?
+                .with_timezone(&chrono::Utc);
+
+            events.push(TaskEventRecord {
+                sequence: sequence as u64,
+                task_id: params.id.clone(),
+                timestamp,
+                event,
+            });
         }
 
-        // Generate next page token
         let has_more = offset + page_size < total_size;
         let next_page_token = if has_more {
             (offset + page_size).to_string()
@@ -792,8 +1781,8 @@ impl AsyncTaskManager for SqlxTaskStorage {
             String::new()
         };
 
-        Ok(ListTasksResult {
-            tasks,
+        Ok(GetTaskEventsResult {
+            events,
             total_size,
             page_size,
             next_page_token,
@@ -817,15 +1806,15 @@ impl AsyncTaskManager for SqlxTaskStorage {
         .bind(config_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| A2AError::DatabaseError(format!("Failed to get push config: {}", e)))?;
+        .map_err(|e| database_error("Failed to get push config", e))?;
 
         if let Some(row) = row {
             let id: String = row
                 .try_get("id")
-                .map_err(|e| A2AError::DatabaseError(format!("Failed to get config id: {}", e)))?;
+                .map_err(|e| database_error("Failed to get config id", e))?;
             let url: String = row
                 .try_get("url")
-                .map_err(|e| A2AError::DatabaseError(format!("Failed to get url: {}", e)))?;
+                .map_err(|e| database_error("Failed to get url", e))?;
             let token: Option<String> = row.try_get("token").ok();
             let auth_json: Option<String> = row.try_get("authentication").ok();
 
@@ -863,7 +1852,7 @@ impl AsyncTaskManager for SqlxTaskStorage {
         .bind(&params.id)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| A2AError::DatabaseError(format!("Failed to list push configs: {}", e)))?;
+        .map_err(|e| database_error("Failed to list push configs", e))?;
 
         let configs: Vec<crate::domain::TaskPushNotificationConfig> = rows
             .iter()
@@ -899,19 +1888,109 @@ impl AsyncTaskManager for SqlxTaskStorage {
         params: &'a crate::domain::DeleteTaskPushNotificationConfigParams,
     ) -> Result<(), A2AError> {
         // Delete the specific config
-        let _result =
+        let mut tx = self.begin().await?;
+        let result =
             sqlx::query("DELETE FROM push_notification_configs WHERE task_id = ? AND id = ?")
                 .bind(&params.id)
                 .bind(&params.push_notification_config_id)
-                .execute(&self.pool)
+                .execute(&mut *tx)
                 .await
-                .map_err(|e| {
-                    A2AError::DatabaseError(format!("Failed to delete push config: {}", e))
-                })?;
+                .map_err(|e| database_error("Failed to delete push config", e))?;
+
+        if result.rows_affected() > 0 {
+            let event = TaskLogEvent::PushConfigRemoved {
+                config_id: Some(params.push_notification_config_id.clone()),
+            };
+            self.append_event(&mut tx, &params.id, &event).await?;
+        }
+        Self::commit(tx).await?;
 
         // Idempotent - don't error if already deleted (v0.3.0 spec behavior)
         Ok(())
     }
+
+    async fn check_health(&self) -> Result<(), A2AError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| database_error("Health check failed", e))?;
+        Ok(())
+    }
+
+    async fn list_tasks_updated_before<'a>(
+        &self,
+        state: &'a TaskState,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Task>, A2AError> {
+        let state_str = state.as_str();
+
+        let rows = sqlx::query(
+            "SELECT * FROM tasks WHERE status_state = ? AND updated_at < ? ORDER BY updated_at ASC LIMIT ?",
+        )
+        .bind(state_str)
+        .bind(before.format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| database_error("Failed to list expired tasks", e))?;
+
+        let mut tasks = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut task = Self::row_to_task(&row)?;
+            task.result = Self::load_task_result(&self.pool, &task.id).await?;
+            task.version = Self::load_task_version(&self.pool, &task.id).await?;
+            let history = self
+                .load_task_history(&task.id, None, &TaskExclusions::new(&task.id, &[]))
+                .await?;
+            task.history = if history.is_empty() {
+                None
+            } else {
+                Some(history)
+            };
+            tasks.push(task);
+        }
+
+        Ok(tasks)
+    }
+
+    async fn delete_tasks<'a>(&self, task_ids: &'a [String]) -> Result<usize, A2AError> {
+        // One transaction per batch keeps the write lock short and the delete atomic
+        let mut tx = self.begin().await?;
+        let mut deleted = 0;
+        for task_id in task_ids {
+            for table in [
+                "task_history",
+                "push_notification_configs",
+                "task_events",
+                "task_results",
+                "task_versions",
+                "task_tags",
+                "task_references",
+            ] {
+                sqlx::query(&format!("DELETE FROM {} WHERE task_id = ?", table))
+                    .bind(task_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| database_error(&format!("Failed to delete from {}", table), e))?;
+            }
+            let result = sqlx::query("DELETE FROM tasks WHERE id = ?")
+                .bind(task_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| database_error("Failed to delete task", e))?;
+            deleted += result.rows_affected() as usize;
+        }
+        Self::commit(tx).await?;
+
+        let mut subscribers = self.subscribers.lock().await;
+        for task_id in task_ids {
+            subscribers.remove(task_id);
+            self.push_notification_registry.unregister(task_id).await?;
+        }
+
+        Ok(deleted)
+    }
 }
 
 #[cfg(feature = "sqlx-storage")]
@@ -926,7 +2005,7 @@ impl AsyncNotificationManager for SqlxTaskStorage {
             .push_notification_config
             .id
             .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            .unwrap_or_else(|| self.ids.next_id());
 
         // Serialize authentication if present
         let auth_json = config
@@ -936,6 +2015,7 @@ impl AsyncNotificationManager for SqlxTaskStorage {
             .map(|auth| serde_json::to_string(auth).unwrap_or_default());
 
         // Store in database (using new schema with id, token, authentication)
+        let mut tx = self.begin().await?;
         sqlx::query(
             "INSERT OR REPLACE INTO push_notification_configs (id, task_id, url, token, authentication) VALUES (?, ?, ?, ?, ?)",
         )
@@ -944,20 +2024,26 @@ impl AsyncNotificationManager for SqlxTaskStorage {
         .bind(&config.push_notification_config.url)
         .bind(&config.push_notification_config.token)
         .bind(auth_json)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| {
-            A2AError::DatabaseError(format!("Failed to set push notification config: {}", e))
+            database_error("Failed to set push notification config", e)
         })?;
 
+        let mut result_config = config.clone();
+        result_config.push_notification_config.id = Some(config_id);
+        let event = TaskLogEvent::PushConfigSet {
+            config: result_config.clone(),
+        };
+        self.append_event(&mut tx, &config.task_id, &event).await?;
+        Self::commit(tx).await?;
+
         // Register with the push notification registry
         self.push_notification_registry
             .register(&config.task_id, config.push_notification_config.clone())
             .await?;
 
         // Return config with ID set
-        let mut result_config = config.clone();
-        result_config.push_notification_config.id = Some(config_id);
         Ok(result_config)
     }
 
@@ -972,19 +2058,16 @@ impl AsyncNotificationManager for SqlxTaskStorage {
                 .fetch_optional(&self.pool)
                 .await
                 .map_err(|e| {
-                    A2AError::DatabaseError(format!(
-                        "Failed to get push notification config: {}",
-                        e
-                    ))
+                    database_error("Failed to get push notification config", e)
                 })?;
 
         if let Some(row) = row {
             let id: String = row
                 .try_get("id")
-                .map_err(|e| A2AError::DatabaseError(format!("Failed to get id: {}", e)))?;
+                .map_err(|e| database_error("Failed to get id", e))?;
             let url: String = row
                 .try_get("url")
-                .map_err(|e| A2AError::DatabaseError(format!("Failed to get url: {}", e)))?;
+                .map_err(|e| database_error("Failed to get url", e))?;
             let token: Option<String> = row.try_get("token").ok();
             let auth_json: Option<String> = row.try_get("authentication").ok();
 
@@ -1013,18 +2096,51 @@ impl AsyncNotificationManager for SqlxTaskStorage {
 
     async fn remove_task_notification<'a>(&self, task_id: &'a str) -> Result<(), A2AError> {
         // Remove from database
+        let mut tx = self.begin().await?;
         sqlx::query("DELETE FROM push_notification_configs WHERE task_id = ?")
             .bind(task_id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
-            .map_err(|e| {
-                A2AError::DatabaseError(format!("Failed to remove push notification config: {}", e))
-            })?;
+            .map_err(|e| database_error("Failed to remove push notification config", e))?;
+        self.append_event(
+            &mut tx,
+            task_id,
+            &TaskLogEvent::PushConfigRemoved { config_id: None },
+        )
+        .await?;
+        Self::commit(tx).await?;
 
         // Unregister from registry
         self.push_notification_registry.unregister(task_id).await?;
         Ok(())
     }
+
+    async fn list_dead_letters<'a>(
+        &self,
+        params: &'a crate::domain::ListDeadLettersParams,
+    ) -> Result<Vec<crate::domain::DeadLetter>, A2AError> {
+        self.push_notification_registry
+            .list_dead_letters(params)
+            .await
+    }
+
+    async fn requeue_dead_letter<'a>(
+        &self,
+        id: &'a str,
+    ) -> Result<crate::domain::RequeueDeadLetterResult, A2AError> {
+        self.push_notification_registry
+            .requeue_dead_letter(id)
+            .await
+    }
+
+    async fn purge_dead_letters<'a>(
+        &self,
+        params: &'a crate::domain::PurgeDeadLettersParams,
+    ) -> Result<crate::domain::PurgeDeadLettersResult, A2AError> {
+        self.push_notification_registry
+            .purge_dead_letters(params)
+            .await
+    }
 }
 
 #[cfg(feature = "sqlx-storage")]
@@ -1035,6 +2151,12 @@ impl AsyncStreamingHandler for SqlxTaskStorage {
         task_id: &'a str,
         subscriber: Box<dyn Subscriber<TaskStatusUpdateEvent> + Send + Sync>,
     ) -> Result<String, A2AError> {
+        // A subscriber keeping its copy of the task up to date with diffs
+        // starts from a snapshot of it
+        let wants_snapshot = subscriber
+            .task_snapshots()
+            .is_some_and(|options| options.diffs);
+
         // Add the subscriber
         {
             let mut subscribers_guard = self.subscribers.lock().await;
@@ -1050,11 +2172,18 @@ impl AsyncStreamingHandler for SqlxTaskStorage {
         // But don't fail if the task doesn't exist yet - the subscriber will get updates when it's created
         if let Ok(task) = self.get_task(task_id, None).await {
             let _ = self
-                .broadcast_status_update(task_id, task.status, false)
+                .broadcast_status_update(
+                    task_id,
+                    task.status.clone(),
+                    false,
+                    task.final_result(),
+                    task.final_artifacts(),
+                    wants_snapshot.then_some(&task),
+                )
                 .await;
         }
 
-        Ok(format!("status-{}-{}", task_id, uuid::Uuid::new_v4()))
+        Ok(format!("status-{}-{}", task_id, self.ids.next_id()))
     }
 
     async fn add_artifact_subscriber<'a>(
@@ -1085,7 +2214,7 @@ impl AsyncStreamingHandler for SqlxTaskStorage {
             }
         }
 
-        Ok(format!("artifact-{}-{}", task_id, uuid::Uuid::new_v4()))
+        Ok(format!("artifact-{}-{}", task_id, self.ids.next_id()))
     }
 
     async fn remove_subscription<'a>(&self, _subscription_id: &'a str) -> Result<(), A2AError> {
@@ -1119,8 +2248,15 @@ impl AsyncStreamingHandler for SqlxTaskStorage {
         task_id: &'a str,
         update: TaskStatusUpdateEvent,
     ) -> Result<(), A2AError> {
-        self.broadcast_status_update(task_id, update.status, update.final_)
-            .await
+        self.broadcast_status_update(
+            task_id,
+            update.status,
+            update.final_,
+            update.result,
+            update.artifacts,
+            update.task.as_deref(),
+        )
+        .await
     }
 
     async fn broadcast_artifact_update<'a>(
@@ -1191,6 +2327,10 @@ impl Clone for SqlxTaskStorage {
             pool: self.pool.clone(),
             subscribers: self.subscribers.clone(),
             push_notification_registry: self.push_notification_registry.clone(),
+            page_limits: self.page_limits,
+            clock: self.clock.clone(),
+            ids: self.ids.clone(),
+            sanitizer: self.sanitizer.clone(),
         }
     }
 }
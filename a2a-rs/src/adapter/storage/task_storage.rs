@@ -17,14 +17,19 @@ use crate::adapter::business::push_notification::HttpPushNotificationSender;
 #[cfg(not(feature = "http-client"))]
 use crate::adapter::business::push_notification::NoopPushNotificationSender;
 use crate::domain::{
-    A2AError, Artifact, Message, Task, TaskArtifactUpdateEvent, TaskPushNotificationConfig,
-    TaskState, TaskStatus, TaskStatusUpdateEvent,
+    A2AError, Artifact, ArtifactSummary, Clock, GetOrCreateTaskResult, GetTaskEventsParams,
+    GetTaskEventsResult, IdGenerator, InputRequest, ListTasksParams, ListTasksSummary, Message,
+    MessageSanitizer, PageSizeLimits, SystemClock, Task, TaskArtifactUpdateEvent, TaskCancellation,
+    TaskEventRecord, TaskField, TaskLogEvent, TaskPushNotificationConfig, TaskResult, TaskState,
+    TaskStatus, TaskStatusUpdateEvent, TaskTagsParams, UuidGenerator,
 };
 use crate::port::{
-    AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager,
+    AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager, ListTasksStream,
     streaming_handler::Subscriber,
 };
 
+use super::listing::stream_listed_tasks;
+
 type StatusSubscribers = Vec<Box<dyn Subscriber<TaskStatusUpdateEvent> + Send + Sync>>;
 type ArtifactSubscribers = Vec<Box<dyn Subscriber<TaskArtifactUpdateEvent> + Send + Sync>>;
 
@@ -51,6 +56,16 @@ pub struct InMemoryTaskStorage {
     pub(crate) subscribers: Arc<Mutex<HashMap<String, TaskSubscribers>>>,
     /// Push notification registry
     pub(crate) push_notification_registry: Arc<PushNotificationRegistry>,
+    /// Append-only event log per task
+    pub(crate) event_log: Arc<Mutex<HashMap<String, Vec<TaskEventRecord>>>>,
+    /// Page sizes applied to `list_tasks_v3`
+    pub(crate) page_limits: PageSizeLimits,
+    /// Timestamps for task statuses and logged events
+    pub(crate) clock: Arc<dyn Clock>,
+    /// Ids for cancellation messages and subscriptions
+    pub(crate) ids: Arc<dyn IdGenerator>,
+    /// Cleanup applied to messages before they are stored
+    pub(crate) sanitizer: MessageSanitizer,
 }
 
 impl InMemoryTaskStorage {
@@ -68,6 +83,11 @@ impl InMemoryTaskStorage {
             tasks: Arc::new(Mutex::new(HashMap::new())),
             subscribers: Arc::new(Mutex::new(HashMap::new())),
             push_notification_registry: Arc::new(push_registry),
+            event_log: Arc::new(Mutex::new(HashMap::new())),
+            page_limits: PageSizeLimits::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            sanitizer: MessageSanitizer::new(),
         }
     }
 
@@ -79,9 +99,40 @@ impl InMemoryTaskStorage {
             tasks: Arc::new(Mutex::new(HashMap::new())),
             subscribers: Arc::new(Mutex::new(HashMap::new())),
             push_notification_registry: Arc::new(push_registry),
+            event_log: Arc::new(Mutex::new(HashMap::new())),
+            page_limits: PageSizeLimits::default(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            sanitizer: MessageSanitizer::new(),
         }
     }
 
+    /// Set the default and maximum page size of task listings
+    pub fn with_page_limits(mut self, limits: PageSizeLimits) -> Self {
+        self.page_limits = limits;
+        self
+    }
+
+    /// Stamp tasks and events with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Mint cancellation message and subscription ids with `ids` instead of
+    /// random UUIDs
+    pub fn with_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
+
+    /// Sanitize messages, artifacts, results and cancellation reasons with
+    /// `sanitizer` before storing them, instead of storing them unchanged
+    pub fn with_sanitizer(mut self, sanitizer: MessageSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
     /// Add a status update subscriber for streaming (convenience method)
     pub async fn add_status_subscriber_legacy(
         &self,
@@ -112,12 +163,132 @@ impl Default for InMemoryTaskStorage {
 }
 
 impl InMemoryTaskStorage {
-    /// Send a status update to all subscribers for a task
+    /// Append events to a task's log.
+    ///
+    /// Task mutations call this while still holding the tasks lock, so the log
+    /// always reflects the stored task state.
+    async fn append_events(&self, task_id: &str, events: Vec<TaskLogEvent>) {
+        let mut log_guard = self.event_log.lock().await;
+        let log = log_guard.entry(task_id.to_string()).or_default();
+        for event in events {
+            log.push(TaskEventRecord {
+                sequence: log.len() as u64 + 1,
+                task_id: task_id.to_string(),
+                timestamp: self.clock.now(),
+                event,
+            });
+        }
+    }
+
+    /// Select the page of tasks matching a listing, most recently updated
+    /// first, returning their IDs and the listing's counts
+    fn select_listed_page(
+        &self,
+        tasks: &HashMap<String, Task>,
+        params: &ListTasksParams,
+    ) -> (Vec<String>, ListTasksSummary) {
+        let mut matching: Vec<&Task> = tasks
+            .values()
+            .filter(|task| {
+                // Filter by context_id if provided
+                if let Some(ref context_id) = params.context_id {
+                    if &task.context_id != context_id {
+                        return false;
+                    }
+                }
+
+                // Filter by status if provided
+                if let Some(ref status) = params.status {
+                    if &task.status.state != status {
+                        return false;
+                    }
+                }
+
+                // Filter by lastUpdatedAfter if provided
+                if let Some(last_updated_after) = params.last_updated_after {
+                    if let Some(timestamp) = task.status.timestamp {
+                        let task_time_ms = timestamp.timestamp_millis();
+                        if task_time_ms <= last_updated_after {
+                            return false;
+                        }
+                    }
+                }
+
+                // Filter by tags if provided
+                if let Some(tags) = params.tags.as_ref().filter(|tags| !tags.is_empty()) {
+                    let tag_match = params.tag_match.unwrap_or_default();
+                    if !tag_match.matches(tags, &task.tags) {
+                        return false;
+                    }
+                }
+
+                true
+            })
+            .collect();
+
+        // Sort by timestamp (most recent first)
+        matching.sort_by_key(|task| {
+            std::cmp::Reverse(
+                task.status
+                    .timestamp
+                    .map(|t| t.timestamp_millis())
+                    .unwrap_or(0),
+            )
+        });
+
+        // Handle pagination
+        let page_size = self.page_limits.resolve(params.page_size) as usize;
+        let page_start = if let Some(ref token) = params.page_token {
+            // Parse page token as a number (simple implementation)
+            token.parse::<usize>().unwrap_or(0)
+        } else {
+            0
+        };
+        let page_start = page_start.min(matching.len());
+        let page_end = (page_start + page_size).min(matching.len());
+
+        // Generate next page token
+        let next_page_token = if page_end < matching.len() {
+            page_end.to_string()
+        } else {
+            String::new()
+        };
+
+        let task_ids = matching[page_start..page_end]
+            .iter()
+            .map(|task| task.id.clone())
+            .collect();
+        let summary = ListTasksSummary {
+            count: 0,
+            total_size: matching.len() as i32,
+            page_size: page_size as i32,
+            next_page_token,
+        };
+        (task_ids, summary)
+    }
+
+    /// Copy of a listed task with the history and artifacts the listing asked for
+    fn listed_task(task: &Task, params: &ListTasksParams) -> Task {
+        let history_length = params.history_length.unwrap_or(0);
+        let mut task = task.with_limited_history(Some(history_length as u32));
+
+        // Remove artifacts if not requested
+        if !params.include_artifacts.unwrap_or(false) {
+            task.artifacts = None;
+        }
+        task
+    }
+
+    /// Send a status update to all subscribers for a task, with `snapshot`
+    /// for those that asked for task snapshots
     pub(crate) async fn broadcast_status_update(
         &self,
         task_id: &str,
         status: TaskStatus,
         final_: bool,
+        result: Option<TaskResult>,
+        artifacts: Option<Vec<ArtifactSummary>>,
+        snapshot: Option<&Task>,
     ) -> Result<(), A2AError> {
         // Create the update event
         let event = TaskStatusUpdateEvent {
@@ -126,7 +297,11 @@ impl InMemoryTaskStorage {
             kind: "status-update".to_string(),
             status: status.clone(),
             final_,
+            result,
+            artifacts,
             metadata: None,
+            task: None,
+            diff: None,
         };
 
         #[cfg(feature = "tracing")]
@@ -152,7 +327,9 @@ impl InMemoryTaskStorage {
 
                 // Clone the subscribers so we don't hold the lock during notification
                 for (i, subscriber) in task_subscribers.status.iter().enumerate() {
-                    if let Err(e) = subscriber.on_update(event.clone()).await {
+                    let update =
+                        event.for_subscriber(snapshot, subscriber.task_snapshots().as_ref());
+                    if let Err(e) = subscriber.on_update(update).await {
                         #[cfg(feature = "tracing")]
                         tracing::error!(
                             task_id = %task_id,
@@ -244,6 +421,150 @@ impl InMemoryTaskStorage {
 
         Ok(())
     }
+
+    /// Update a task's status, first checking its version if one is expected
+    async fn update_status(
+        &self,
+        task_id: &str,
+        expected_version: Option<u64>,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.update_status_with(task_id, expected_version, state, None, |_| Ok(message))
+            .await
+    }
+
+    /// Update a task's status with the message `message` makes from the task
+    /// as stored, first checking its version if one is expected. A `result`
+    /// is attached to the task in the same update.
+    ///
+    /// `message` runs under the tasks lock, so it sees the task the update
+    /// applies to and may refuse it.
+    async fn update_status_with(
+        &self,
+        task_id: &str,
+        expected_version: Option<u64>,
+        state: TaskState,
+        mut result: Option<TaskResult>,
+        message: impl FnOnce(&Task) -> Result<Option<Message>, A2AError>,
+    ) -> Result<Task, A2AError> {
+        if let Some(result) = result.as_mut() {
+            self.sanitizer.sanitize_result(result);
+        }
+        let mut tasks_guard = self.tasks.lock().await;
+
+        let task = tasks_guard
+            .get_mut(task_id)
+            .ok_or_else(|| A2AError::TaskNotFound(task_id.to_string()))?;
+        if let Some(expected_version) = expected_version {
+            task.check_version(expected_version)?;
+        }
+        let mut message = message(task)?;
+        if let Some(message) = message.as_mut() {
+            self.sanitizer.sanitize(message);
+        }
+
+        // Update the task status with the optional message
+        let history_changed = message.is_some();
+        let mut events = Vec::new();
+        if let Some(result) = result {
+            events.push(TaskLogEvent::ResultSet {
+                result: result.clone(),
+            });
+            task.result = Some(result);
+        }
+        if let Some(message) = &message {
+            events.push(TaskLogEvent::MessageAppended {
+                message: message.clone(),
+            });
+        }
+        task.update_status_at(state, message, self.clock.now());
+        events.push(TaskLogEvent::status_update(task));
+
+        // Return a clone of the updated task
+        let updated_task = task.clone();
+        self.append_events(task_id, events).await;
+
+        // Release the lock before broadcasting
+        drop(tasks_guard);
+
+        // Broadcast status update, with a snapshot when a message was appended
+        self.broadcast_status_update(
+            task_id,
+            updated_task.status.clone(),
+            false,
+            updated_task.final_result(),
+            updated_task.final_artifacts(),
+            history_changed.then_some(&updated_task),
+        )
+        .await?;
+
+        Ok(updated_task)
+    }
+
+    /// Cancel a task, first checking its version if one is expected
+    async fn cancel(
+        &self,
+        task_id: &str,
+        expected_version: Option<u64>,
+        cancellation: &TaskCancellation,
+    ) -> Result<Task, A2AError> {
+        let mut cancellation = cancellation.clone();
+        self.sanitizer.sanitize_cancellation(&mut cancellation);
+
+        // Get and update the task
+        let task = {
+            let mut tasks_guard = self.tasks.lock().await;
+
+            let Some(task) = tasks_guard.get(task_id) else {
+                return Err(A2AError::TaskNotFound(task_id.to_string()));
+            };
+            if let Some(expected_version) = expected_version {
+                task.check_version(expected_version)?;
+            }
+
+            let mut updated_task = task.clone();
+
+            // Only working tasks can be canceled
+            if updated_task.status.state != TaskState::Working {
+                return Err(A2AError::TaskNotCancelable(format!(
+                    "Task {} is in state {:?} and cannot be canceled",
+                    task_id, updated_task.status.state
+                )));
+            }
+
+            // Create a cancellation message to add to history
+            let cancel_message =
+                cancellation.status_message(task_id, &updated_task.context_id, self.ids.next_id());
+
+            // Update the status with the cancellation message to track in history
+            let mut events = vec![TaskLogEvent::MessageAppended {
+                message: cancel_message.clone(),
+            }];
+            updated_task.update_status_at(
+                TaskState::Canceled,
+                Some(cancel_message),
+                self.clock.now(),
+            );
+            events.push(TaskLogEvent::status_update(&updated_task));
+            tasks_guard.insert(task_id.to_string(), updated_task.clone());
+            self.append_events(task_id, events).await;
+            updated_task
+        }; // Lock is dropped here
+
+        // Broadcast status update (with final flag set to true)
+        self.broadcast_status_update(
+            task_id,
+            task.status.clone(),
+            true,
+            task.final_result(),
+            task.final_artifacts(),
+            Some(&task),
+        )
+        .await?;
+
+        Ok(task)
+    }
 }
 
 #[async_trait]
@@ -262,38 +583,64 @@ impl AsyncTaskManager for InMemoryTaskStorage {
             )));
         }
 
-        let task = Task::new(task_id.to_string(), context_id.to_string());
+        let task = Task::new_at(
+            task_id.to_string(),
+            context_id.to_string(),
+            self.clock.now(),
+        );
         tasks_guard.insert(task_id.to_string(), task.clone());
+        self.append_events(task_id, vec![TaskLogEvent::status_update(&task)])
+            .await;
 
         Ok(task)
     }
 
-    async fn update_task_status<'a>(
+    async fn get_or_create_task<'a>(
         &self,
         task_id: &'a str,
-        state: TaskState,
-        message: Option<Message>,
-    ) -> Result<Task, A2AError> {
+        context_id: &'a str,
+        initial_message: Option<&'a Message>,
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        // Holding the lock from lookup to insert makes this atomic
         let mut tasks_guard = self.tasks.lock().await;
 
-        let task = tasks_guard
-            .get_mut(task_id)
-            .ok_or_else(|| A2AError::TaskNotFound(task_id.to_string()))?;
-
-        // Update the task status with the optional message
-        task.update_status(state, message);
-
-        // Return a clone of the updated task
-        let updated_task = task.clone();
+        if let Some(task) = tasks_guard.get(task_id) {
+            return Ok(GetOrCreateTaskResult {
+                task: task.clone(),
+                created: false,
+            });
+        }
 
-        // Release the lock before broadcasting
-        drop(tasks_guard);
+        let mut task = Task::new_at(
+            task_id.to_string(),
+            context_id.to_string(),
+            self.clock.now(),
+        );
+        let mut events = Vec::new();
+        if let Some(mut message) = initial_message.cloned() {
+            self.sanitizer.sanitize(&mut message);
+            events.push(TaskLogEvent::MessageAppended {
+                message: message.clone(),
+            });
+            task = task.with_initial_message(message);
+        }
+        events.push(TaskLogEvent::status_update(&task));
+        tasks_guard.insert(task_id.to_string(), task.clone());
+        self.append_events(task_id, events).await;
 
-        // Broadcast status update
-        self.broadcast_status_update(task_id, updated_task.status.clone(), false)
-            .await?;
+        Ok(GetOrCreateTaskResult {
+            task,
+            created: true,
+        })
+    }
 
-        Ok(updated_task)
+    async fn update_task_status<'a>(
+        &self,
+        task_id: &'a str,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.update_status(task_id, None, state, message).await
     }
 
     async fn task_exists<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
@@ -321,155 +668,319 @@ impl AsyncTaskManager for InMemoryTaskStorage {
         Ok(task)
     }
 
+    async fn get_task_excluding<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+        excluded: &'a [TaskField],
+    ) -> Result<Task, A2AError> {
+        let tasks_guard = self.tasks.lock().await;
+        let task = tasks_guard
+            .get(task_id)
+            .ok_or_else(|| A2AError::TaskNotFound(task_id.to_string()))?;
+
+        // Copy only what is kept
+        Ok(task.copy_without_fields(history_length, excluded))
+    }
+
     async fn cancel_task<'a>(&self, task_id: &'a str) -> Result<Task, A2AError> {
-        // Get and update the task
-        let task = {
-            let mut tasks_guard = self.tasks.lock().await;
+        self.cancel(task_id, None, &TaskCancellation::new()).await
+    }
 
-            let Some(task) = tasks_guard.get(task_id) else {
-                return Err(A2AError::TaskNotFound(task_id.to_string()));
-            };
+    async fn set_task_result<'a>(
+        &self,
+        task_id: &'a str,
+        mut result: TaskResult,
+    ) -> Result<Task, A2AError> {
+        self.sanitizer.sanitize_result(&mut result);
+        let mut tasks_guard = self.tasks.lock().await;
 
-            let mut updated_task = task.clone();
+        let task = tasks_guard
+            .get_mut(task_id)
+            .ok_or_else(|| A2AError::TaskNotFound(task_id.to_string()))?;
+        task.result = Some(result.clone());
+        task.version += 1;
+        let task = task.clone();
+        self.append_events(task_id, vec![TaskLogEvent::ResultSet { result }])
+            .await;
 
-            // Only working tasks can be canceled
-            if updated_task.status.state != TaskState::Working {
-                return Err(A2AError::TaskNotCancelable(format!(
-                    "Task {} is in state {:?} and cannot be canceled",
-                    task_id, updated_task.status.state
-                )));
-            }
+        Ok(task)
+    }
 
-            // Create a cancellation message to add to history
-            let cancel_message = Message {
-                role: crate::domain::Role::Agent,
-                parts: vec![crate::domain::Part::Text {
-                    text: format!("Task {} canceled.", task_id),
-                    metadata: None,
-                }],
-                metadata: None,
-                reference_task_ids: None,
-                message_id: uuid::Uuid::new_v4().to_string(),
-                task_id: Some(task_id.to_string()),
-                context_id: Some(updated_task.context_id.clone()),
-                extensions: None,
-                kind: "message".to_string(),
-            };
+    async fn complete_task_with_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: TaskResult,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.update_status_with(task_id, None, TaskState::Completed, Some(result), |_| {
+            Ok(message)
+        })
+        .await
+    }
 
-            // Update the status with the cancellation message to track in history
-            updated_task.update_status(TaskState::Canceled, Some(cancel_message));
-            tasks_guard.insert(task_id.to_string(), updated_task.clone());
-            updated_task
-        }; // Lock is dropped here
+    async fn add_task_artifact<'a>(
+        &self,
+        task_id: &'a str,
+        artifact: Artifact,
+    ) -> Result<Task, A2AError> {
+        artifact.validate()?;
+        let mut artifact = Artifact {
+            created_at: Some(self.clock.now()),
+            ..artifact
+        };
+        self.sanitizer.sanitize_artifact(&mut artifact);
 
-        // Broadcast status update (with final flag set to true)
-        self.broadcast_status_update(task_id, task.status.clone(), true)
+        let mut tasks_guard = self.tasks.lock().await;
+        let task = tasks_guard
+            .get_mut(task_id)
+            .ok_or_else(|| A2AError::TaskNotFound(task_id.to_string()))?;
+        task.put_artifact(artifact.clone());
+        let task = task.clone();
+        let summary = ArtifactSummary::from(&artifact);
+        self.append_events(
+            task_id,
+            vec![TaskLogEvent::ArtifactAdded { artifact: summary }],
+        )
+        .await;
+
+        // Release the lock before broadcasting
+        drop(tasks_guard);
+        self.broadcast_artifact_update(task_id, artifact, None, true)
             .await?;
 
         Ok(task)
     }
 
-    // ===== v0.3.0 New Methods =====
+    async fn update_task_status_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.update_status(task_id, Some(expected_version), state, message)
+            .await
+    }
 
-    async fn list_tasks_v3<'a>(
+    async fn request_input<'a>(
         &self,
-        params: &'a crate::domain::ListTasksParams,
-    ) -> Result<crate::domain::ListTasksResult, A2AError> {
-        use crate::domain::ListTasksResult;
+        task_id: &'a str,
+        request: &'a InputRequest,
+    ) -> Result<Task, A2AError> {
+        self.update_status_with(task_id, None, TaskState::InputRequired, None, |task| {
+            task.check_can_request_input()?;
+            let message_id = self.ids.next_id();
+            Ok(Some(request.status_message(
+                task_id,
+                &task.context_id,
+                message_id,
+            )))
+        })
+        .await
+    }
 
-        let tasks_guard = self.tasks.lock().await;
+    async fn cancel_task_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        self.cancel(task_id, Some(expected_version), &TaskCancellation::new())
+            .await
+    }
 
-        // Filter tasks based on parameters
-        let mut filtered_tasks: Vec<_> = tasks_guard
-            .values()
-            .filter(|task| {
-                // Filter by context_id if provided
-                if let Some(ref context_id) = params.context_id {
-                    if &task.context_id != context_id {
-                        return false;
-                    }
-                }
+    async fn cancel_task_with<'a>(
+        &self,
+        task_id: &'a str,
+        cancellation: &'a TaskCancellation,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        self.cancel(task_id, expected_version, cancellation).await
+    }
 
-                // Filter by status if provided
-                if let Some(ref status) = params.status {
-                    if &task.status.state != status {
-                        return false;
-                    }
-                }
+    async fn add_task_tags<'a>(&self, params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        let mut tasks_guard = self.tasks.lock().await;
 
-                // Filter by lastUpdatedAfter if provided
-                if let Some(last_updated_after) = params.last_updated_after {
-                    if let Some(timestamp) = task.status.timestamp {
-                        let task_time_ms = timestamp.timestamp_millis();
-                        if task_time_ms <= last_updated_after {
-                            return false;
-                        }
-                    }
-                }
+        let task = tasks_guard
+            .get_mut(&params.id)
+            .ok_or_else(|| A2AError::TaskNotFound(params.id.clone()))?;
+        task.add_tags(&params.tags)?;
 
-                true
+        Ok(task.clone())
+    }
+
+    async fn remove_task_tags<'a>(&self, params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        let mut tasks_guard = self.tasks.lock().await;
+
+        let task = tasks_guard
+            .get_mut(&params.id)
+            .ok_or_else(|| A2AError::TaskNotFound(params.id.clone()))?;
+        task.remove_tags(&params.tags);
+
+        Ok(task.clone())
+    }
+
+    async fn list_referencing_tasks<'a>(&self, task_id: &'a str) -> Result<Vec<Task>, A2AError> {
+        let tasks_guard = self.tasks.lock().await;
+        if !tasks_guard.contains_key(task_id) {
+            return Err(A2AError::TaskNotFound(task_id.to_string()));
+        }
+
+        let mut referencing: Vec<Task> = tasks_guard
+            .values()
+            .filter(|task| task.reference_task_ids.iter().any(|id| id == task_id))
+            .map(|task| Task {
+                history: None,
+                ..task.clone()
             })
-            .cloned()
             .collect();
+        referencing.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(referencing)
+    }
 
-        // Sort by timestamp (most recent first)
-        filtered_tasks.sort_by(|a, b| {
-            let a_time = a
-                .status
-                .timestamp
-                .map(|t| t.timestamp_millis())
-                .unwrap_or(0);
-            let b_time = b
-                .status
-                .timestamp
-                .map(|t| t.timestamp_millis())
-                .unwrap_or(0);
-            b_time.cmp(&a_time)
-        });
-
-        let total_size = filtered_tasks.len() as i32;
+    async fn import_task<'a>(&self, task: &'a Task) -> Result<(), A2AError> {
+        let mut tasks_guard = self.tasks.lock().await;
+        if tasks_guard.contains_key(&task.id) {
+            return Err(A2AError::ValidationError {
+                field: "id".to_string(),
+                message: format!("Task '{}' already exists", task.id),
+            });
+        }
 
-        // Handle pagination
-        let page_size = params.page_size.unwrap_or(50).clamp(1, 100) as usize;
-        let page_start = if let Some(ref token) = params.page_token {
-            // Parse page token as a number (simple implementation)
-            token.parse::<usize>().unwrap_or(0)
-        } else {
-            0
+        let mut task = Task {
+            history_cursor: None,
+            ..task.clone()
         };
+        self.sanitizer.sanitize_task(&mut task);
+        self.append_events(&task.id, vec![TaskLogEvent::status_update(&task)])
+            .await;
+        tasks_guard.insert(task.id.clone(), task);
+        Ok(())
+    }
 
-        let page_end = (page_start + page_size).min(filtered_tasks.len());
-        let has_more = page_end < filtered_tasks.len();
+    // ===== v0.3.0 New Methods =====
 
-        // Get the page of tasks
-        let mut page_tasks: Vec<_> = filtered_tasks[page_start..page_end].to_vec();
+    async fn list_tasks_v3<'a>(
+        &self,
+        params: &'a crate::domain::ListTasksParams,
+    ) -> Result<crate::domain::ListTasksResult, A2AError> {
+        use crate::domain::ListTasksResult;
 
-        // Apply history length limit
-        let history_length = params.history_length.unwrap_or(0);
-        for task in &mut page_tasks {
-            *task = task.with_limited_history(Some(history_length as u32));
+        let tasks_guard = self.tasks.lock().await;
+        let (task_ids, summary) = self.select_listed_page(&tasks_guard, params);
+        let tasks = task_ids
+            .iter()
+            .filter_map(|task_id| tasks_guard.get(task_id))
+            .map(|task| Self::listed_task(task, params))
+            .collect();
+
+        Ok(ListTasksResult {
+            tasks,
+            total_size: summary.total_size,
+            page_size: summary.page_size,
+            next_page_token: summary.next_page_token,
+        })
+    }
+
+    async fn list_tasks_stream<'a>(
+        &self,
+        params: &'a crate::domain::ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        let (task_ids, summary) = {
+            let tasks_guard = self.tasks.lock().await;
+            self.select_listed_page(&tasks_guard, params)
+        };
 
-            // Remove artifacts if not requested
-            if !params.include_artifacts.unwrap_or(false) {
-                task.artifacts = None;
+        let tasks = self.tasks.clone();
+        let params = params.clone();
+        Ok(stream_listed_tasks(task_ids, summary, move |task_id| {
+            let tasks = tasks.clone();
+            let params = params.clone();
+            async move {
+                let tasks_guard = tasks.lock().await;
+                Ok(tasks_guard
+                    .get(&task_id)
+                    .map(|task| Self::listed_task(task, &params)))
             }
-        }
+        }))
+    }
 
-        // Generate next page token
-        let next_page_token = if has_more {
+    async fn get_task_events<'a>(
+        &self,
+        params: &'a GetTaskEventsParams,
+    ) -> Result<GetTaskEventsResult, A2AError> {
+        let log_guard = self.event_log.lock().await;
+
+        let Some(events) = log_guard.get(&params.id) else {
+            return Err(A2AError::TaskNotFound(params.id.clone()));
+        };
+
+        let total_size = events.len();
+        let page_size = params.page_size.unwrap_or(50).clamp(1, 100) as usize;
+        let page_start = params
+            .page_token
+            .as_ref()
+            .and_then(|token| token.parse::<usize>().ok())
+            .unwrap_or(0)
+            .min(total_size);
+        let page_end = (page_start + page_size).min(total_size);
+
+        let next_page_token = if page_end < total_size {
             page_end.to_string()
         } else {
             String::new()
         };
 
-        Ok(ListTasksResult {
-            tasks: page_tasks,
-            total_size,
+        Ok(GetTaskEventsResult {
+            events: events[page_start..page_end].to_vec(),
+            total_size: total_size as i32,
             page_size: page_size as i32,
             next_page_token,
         })
     }
 
+    async fn list_tasks_updated_before<'a>(
+        &self,
+        state: &'a TaskState,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Task>, A2AError> {
+        let tasks_guard = self.tasks.lock().await;
+
+        let mut expired: Vec<Task> = tasks_guard
+            .values()
+            .filter(|task| &task.status.state == state)
+            .filter(|task| task.status.timestamp.is_some_and(|ts| ts < before))
+            .cloned()
+            .collect();
+        expired.sort_by_key(|task| task.status.timestamp);
+        expired.truncate(limit);
+
+        Ok(expired)
+    }
+
+    async fn delete_tasks<'a>(&self, task_ids: &'a [String]) -> Result<usize, A2AError> {
+        let mut deleted = 0;
+        {
+            let mut tasks_guard = self.tasks.lock().await;
+            let mut log_guard = self.event_log.lock().await;
+            for task_id in task_ids {
+                if tasks_guard.remove(task_id).is_some() {
+                    deleted += 1;
+                }
+                log_guard.remove(task_id);
+            }
+        }
+
+        let mut subscribers_guard = self.subscribers.lock().await;
+        for task_id in task_ids {
+            subscribers_guard.remove(task_id);
+            self.push_notification_registry.unregister(task_id).await?;
+        }
+
+        Ok(deleted)
+    }
+
     async fn get_push_notification_config<'a>(
         &self,
         params: &'a crate::domain::GetTaskPushNotificationConfigParams,
@@ -526,6 +1037,13 @@ impl AsyncNotificationManager for InMemoryTaskStorage {
         self.push_notification_registry
             .register(&config.task_id, config.push_notification_config.clone())
             .await?;
+        self.append_events(
+            &config.task_id,
+            vec![TaskLogEvent::PushConfigSet {
+                config: config.clone(),
+            }],
+        )
+        .await;
 
         #[cfg(feature = "tracing")]
         tracing::info!(
@@ -552,8 +1070,43 @@ impl AsyncNotificationManager for InMemoryTaskStorage {
 
     async fn remove_task_notification<'a>(&self, task_id: &'a str) -> Result<(), A2AError> {
         self.push_notification_registry.unregister(task_id).await?;
+        // Unknown tasks get no log, which a task imported later would inherit
+        if self.tasks.lock().await.contains_key(task_id) {
+            self.append_events(
+                task_id,
+                vec![TaskLogEvent::PushConfigRemoved { config_id: None }],
+            )
+            .await;
+        }
         Ok(())
     }
+
+    async fn list_dead_letters<'a>(
+        &self,
+        params: &'a crate::domain::ListDeadLettersParams,
+    ) -> Result<Vec<crate::domain::DeadLetter>, A2AError> {
+        self.push_notification_registry
+            .list_dead_letters(params)
+            .await
+    }
+
+    async fn requeue_dead_letter<'a>(
+        &self,
+        id: &'a str,
+    ) -> Result<crate::domain::RequeueDeadLetterResult, A2AError> {
+        self.push_notification_registry
+            .requeue_dead_letter(id)
+            .await
+    }
+
+    async fn purge_dead_letters<'a>(
+        &self,
+        params: &'a crate::domain::PurgeDeadLettersParams,
+    ) -> Result<crate::domain::PurgeDeadLettersResult, A2AError> {
+        self.push_notification_registry
+            .purge_dead_letters(params)
+            .await
+    }
 }
 
 // AsyncStreamingHandler implementation
@@ -570,6 +1123,12 @@ impl AsyncStreamingHandler for InMemoryTaskStorage {
             "✅ Adding WebSocket subscriber for status updates"
         );
 
+        // A subscriber keeping its copy of the task up to date with diffs
+        // starts from a snapshot of it
+        let wants_snapshot = subscriber
+            .task_snapshots()
+            .is_some_and(|options| options.diffs);
+
         // Add the subscriber
         {
             let mut subscribers_guard = self.subscribers.lock().await;
@@ -592,11 +1151,18 @@ impl AsyncStreamingHandler for InMemoryTaskStorage {
         // But don't fail if the task doesn't exist yet - the subscriber will get updates when it's created
         if let Ok(task) = self.get_task(task_id, None).await {
             let _ = self
-                .broadcast_status_update(task_id, task.status, false)
+                .broadcast_status_update(
+                    task_id,
+                    task.status.clone(),
+                    false,
+                    task.final_result(),
+                    task.final_artifacts(),
+                    wants_snapshot.then_some(&task),
+                )
                 .await;
         }
 
-        Ok(format!("status-{}-{}", task_id, uuid::Uuid::new_v4()))
+        Ok(format!("status-{}-{}", task_id, self.ids.next_id()))
     }
 
     async fn add_artifact_subscriber<'a>(
@@ -627,7 +1193,7 @@ impl AsyncStreamingHandler for InMemoryTaskStorage {
             }
         }
 
-        Ok(format!("artifact-{}-{}", task_id, uuid::Uuid::new_v4()))
+        Ok(format!("artifact-{}-{}", task_id, self.ids.next_id()))
     }
 
     async fn remove_subscription<'a>(&self, _subscription_id: &'a str) -> Result<(), A2AError> {
@@ -661,8 +1227,15 @@ impl AsyncStreamingHandler for InMemoryTaskStorage {
         task_id: &'a str,
         update: TaskStatusUpdateEvent,
     ) -> Result<(), A2AError> {
-        self.broadcast_status_update(task_id, update.status, update.final_)
-            .await
+        self.broadcast_status_update(
+            task_id,
+            update.status,
+            update.final_,
+            update.result,
+            update.artifacts,
+            update.task.as_deref(),
+        )
+        .await
     }
 
     async fn broadcast_artifact_update<'a>(
@@ -732,6 +1305,11 @@ impl Clone for InMemoryTaskStorage {
             tasks: self.tasks.clone(),
             subscribers: self.subscribers.clone(),
             push_notification_registry: self.push_notification_registry.clone(),
+            event_log: self.event_log.clone(),
+            page_limits: self.page_limits,
+            clock: self.clock.clone(),
+            ids: self.ids.clone(),
+            sanitizer: self.sanitizer.clone(),
         }
     }
 }
@@ -5,14 +5,17 @@
 use async_trait::async_trait;
 use futures::stream::Stream;
 use reqwest::{
-    Client,
-    header::{CONTENT_TYPE, HeaderMap, HeaderValue},
+    Client, StatusCode,
+    header::{CONTENT_TYPE, HeaderMap, HeaderValue, RETRY_AFTER},
 };
-use std::{pin::Pin, time::Duration};
+use std::{collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 
 #[cfg(feature = "tracing")]
 use tracing::{debug, error, instrument};
 
+use super::{PRIORITY_HEADER, REQUEST_ID_HEADER, RequestPriority};
+#[cfg(feature = "signing")]
+use crate::adapter::auth::{RequestSigner, SIGNATURE_HEADER};
 use crate::{
     adapter::error::HttpClientError,
     application::{
@@ -20,12 +23,69 @@ use crate::{
         json_rpc::{self, A2ARequest, SendTaskRequest},
     },
     domain::{
-        A2AError, ListTasksParams, ListTasksResult, Message, Task, TaskIdParams,
-        TaskPushNotificationConfig, TaskQueryParams, TaskSendParams,
+        A2AError, AgentCard, ErrorDetail, IdGenerator, ListTasksParams, ListTasksResult, Message,
+        Task, TaskIdParams, TaskPushNotificationConfig, TaskQueryParams, TaskSendParams,
+        UuidGenerator,
+    },
+    services::{
+        client::{AsyncA2AClient, StreamItem},
+        interceptor::InterceptorChain,
     },
-    services::client::{AsyncA2AClient, StreamItem},
 };
 
+/// How [`HttpClient`] retries requests the server refused as busy
+///
+/// Only `429` and `503` responses are retried, as the server sends those
+/// when it refuses a request before handling it or cannot reach its storage. The server's `Retry-After` hint is used when it
+/// sends one; otherwise the delay doubles from `base_delay` with each attempt.
+/// Either way the delay is capped at `max_delay`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_retries` times with the default delays
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Set the first backoff delay, used when the server sends no hint
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the longest delay between attempts
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Delay before retry number `attempt` (starting at 0)
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let delay =
+            retry_after.unwrap_or_else(|| self.base_delay.saturating_mul(1 << attempt.min(16)));
+        delay.min(self.max_delay)
+    }
+}
+
 /// HTTP client for interacting with the A2A protocol
 pub struct HttpClient {
     /// Base URL of the A2A API
@@ -36,6 +96,17 @@ pub struct HttpClient {
     auth_token: Option<String>,
     /// Timeout in seconds
     timeout: u64,
+    /// Retries for busy responses; none when unset
+    retry_policy: Option<RetryPolicy>,
+    /// Interceptors run around every structured request
+    interceptors: InterceptorChain,
+    /// Ids sent in the `X-Request-ID` header of requests without one
+    request_ids: Arc<dyn IdGenerator>,
+    /// Priority asked for in the `X-A2A-Priority` header, if any
+    priority: Option<RequestPriority>,
+    /// Signer for detached request signatures, if any
+    #[cfg(feature = "signing")]
+    signer: Option<RequestSigner>,
 }
 
 impl HttpClient {
@@ -46,6 +117,12 @@ impl HttpClient {
             client: Client::new(),
             auth_token: None,
             timeout: 30, // Default timeout in seconds
+            retry_policy: None,
+            interceptors: InterceptorChain::new(),
+            request_ids: Arc::new(UuidGenerator),
+            priority: None,
+            #[cfg(feature = "signing")]
+            signer: None,
         }
     }
 
@@ -56,6 +133,12 @@ impl HttpClient {
             client: Client::new(),
             auth_token: Some(auth_token),
             timeout: 30,
+            retry_policy: None,
+            interceptors: InterceptorChain::new(),
+            request_ids: Arc::new(UuidGenerator),
+            priority: None,
+            #[cfg(feature = "signing")]
+            signer: None,
         }
     }
 
@@ -65,6 +148,92 @@ impl HttpClient {
         self
     }
 
+    /// Retry requests the server refuses as busy
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Run every structured request through `interceptors`; raw requests
+    /// bypass the chain
+    pub fn with_interceptors(mut self, interceptors: InterceptorChain) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    /// Mint request ids with `ids` instead of random UUIDs.
+    ///
+    /// Each request gets one id, kept across its retries, unless an
+    /// interceptor already set the `X-Request-ID` header.
+    pub fn with_request_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.request_ids = Arc::new(ids);
+        self
+    }
+
+    /// Ask the server to treat every request with `priority` while it is
+    /// saturated; interceptors may still set the header per request
+    pub fn with_priority(mut self, priority: RequestPriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Send a request with the given `X-Request-ID`, e.g. to correlate it
+    /// with the caller's own logs
+    pub async fn send_request_with_id(
+        &self,
+        request: &A2ARequest,
+        request_id: &str,
+    ) -> Result<JSONRPCResponse, A2AError> {
+        self.interceptors
+            .run(request.clone(), |mut request| async move {
+                request
+                    .headers
+                    .retain(|name, _| !name.eq_ignore_ascii_case(REQUEST_ID_HEADER));
+                request
+                    .headers
+                    .insert(REQUEST_ID_HEADER.to_string(), request_id.to_string());
+                let json = json_rpc::serialize_request(&request.request)?;
+                let response_text = self.send_with_retries(&json, &request.headers).await?;
+                Ok(serde_json::from_str(&response_text)?)
+            })
+            .await
+    }
+
+    /// Sign every request with the given key
+    #[cfg(feature = "signing")]
+    pub fn with_request_signer(mut self, signer: RequestSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Fetch the agent card served next to the JSON-RPC endpoint
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(url = %self.base_url)))]
+    pub async fn get_agent_card(&self) -> Result<AgentCard, A2AError> {
+        let url = format!(
+            "{}/.well-known/agent-card.json",
+            self.base_url.trim_end_matches('/')
+        );
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.get_headers())
+            .timeout(Duration::from_secs(self.timeout))
+            .send()
+            .await
+            .map_err(HttpClientError::Reqwest)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(HttpClientError::Response {
+                status: status.as_u16(),
+                message: body,
+            }
+            .into());
+        }
+        Ok(response.json().await.map_err(HttpClientError::Reqwest)?)
+    }
+
     /// Get the headers for a request
     fn get_headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
@@ -76,22 +245,43 @@ impl HttpClient {
                 HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
             );
         }
+        if let Some(priority) = self.priority {
+            headers.insert(PRIORITY_HEADER, HeaderValue::from_static(priority.as_str()));
+        }
 
         headers
     }
-}
 
-#[async_trait]
-impl AsyncA2AClient for HttpClient {
-    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(url = %self.base_url, request_len = request.len())))]
-    async fn send_raw_request<'a>(&self, request: &'a str) -> Result<String, A2AError> {
+    /// Send one request without retrying
+    async fn send_once(
+        &self,
+        request: &str,
+        extra_headers: &HashMap<String, String>,
+    ) -> Result<String, A2AError> {
         #[cfg(feature = "tracing")]
         debug!("Sending HTTP request");
 
+        let mut headers = self.get_headers();
+        for (name, value) in extra_headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| A2AError::Internal(format!("Invalid header name: {}", e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| A2AError::Internal(format!("Invalid header value: {}", e)))?;
+            headers.insert(name, value);
+        }
+
+        #[cfg(feature = "signing")]
+        if let Some(signer) = &self.signer {
+            let body: serde_json::Value = serde_json::from_str(request)?;
+            let signature = HeaderValue::from_str(&signer.sign(&body))
+                .map_err(|e| A2AError::Internal(format!("Invalid signature header: {}", e)))?;
+            headers.insert(SIGNATURE_HEADER, signature);
+        }
+
         let response = self
             .client
             .post(&self.base_url)
-            .headers(self.get_headers())
+            .headers(headers)
             .body(request.to_string())
             .timeout(Duration::from_secs(self.timeout))
             .send()
@@ -102,14 +292,32 @@ impl AsyncA2AClient for HttpClient {
                 HttpClientError::Reqwest(e)
             })?;
 
-        if response.status().is_success() {
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE {
+            let retry_after = retry_after_header(response.headers());
+            let body = response.text().await.unwrap_or_default();
+            #[cfg(feature = "tracing")]
+            debug!("Server busy with status {}: {}", status, body);
+            return Err(A2AError::ServerBusy {
+                reason: format!("HTTP {}", status.as_u16()),
+                retry_after: retry_after.or_else(|| retry_after_param(&body)),
+            });
+        }
+
+        if status.is_success() {
             let body = response.text().await.map_err(HttpClientError::Reqwest)?;
             #[cfg(feature = "tracing")]
             debug!("HTTP request successful, response length: {}", body.len());
             Ok(body)
         } else {
-            let status = response.status();
             let body = response.text().await.unwrap_or_default();
+            // Storage conflicts and corrupt data come with a JSON-RPC error,
+            // which says more than the status does
+            if is_json_rpc_error(&body) {
+                #[cfg(feature = "tracing")]
+                debug!("Server answered {} with a JSON-RPC error", status);
+                return Ok(body);
+            }
             #[cfg(feature = "tracing")]
             error!("HTTP request failed with status {}: {}", status, body);
             Err(HttpClientError::Response {
@@ -120,12 +328,91 @@ impl AsyncA2AClient for HttpClient {
         }
     }
 
+    /// Send a request, retrying busy responses as the retry policy allows.
+    ///
+    /// Every attempt carries the same request id, so the server's logs tie
+    /// them together.
+    async fn send_with_retries(
+        &self,
+        request: &str,
+        extra_headers: &HashMap<String, String>,
+    ) -> Result<String, A2AError> {
+        let mut headers = extra_headers.clone();
+        if !headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case(REQUEST_ID_HEADER))
+        {
+            headers.insert(REQUEST_ID_HEADER.to_string(), self.request_ids.next_id());
+        }
+
+        let mut attempt = 0;
+        loop {
+            let result = self.send_once(request, &headers).await;
+            let (Some(policy), Err(A2AError::ServerBusy { retry_after, .. })) =
+                (&self.retry_policy, &result)
+            else {
+                return result;
+            };
+            if attempt >= policy.max_retries {
+                return result;
+            }
+
+            let delay = policy.delay(attempt, *retry_after);
+            #[cfg(feature = "tracing")]
+            debug!(
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                "Server busy, retrying"
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Whether `body` is a JSON-RPC response carrying an error
+fn is_json_rpc_error(body: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(body)
+        .is_ok_and(|response| response.get("jsonrpc").is_some() && response.get("error").is_some())
+}
+
+/// Seconds from a `Retry-After` header; HTTP dates are not supported
+fn retry_after_header(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Seconds from the `retryAfter` parameter of a JSON-RPC error body
+fn retry_after_param(body: &str) -> Option<Duration> {
+    let body: serde_json::Value = serde_json::from_str(body).ok()?;
+    let detail = ErrorDetail::from_json_rpc_data(body.get("error")?.get("data")?)?;
+    let seconds = detail.params.get("retryAfter")?.parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+#[async_trait]
+impl AsyncA2AClient for HttpClient {
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(url = %self.base_url, request_len = request.len())))]
+    async fn send_raw_request<'a>(&self, request: &'a str) -> Result<String, A2AError> {
+        self.send_with_retries(request, &HashMap::new()).await
+    }
+
     #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(method = ?request)))]
     async fn send_request<'a>(&self, request: &'a A2ARequest) -> Result<JSONRPCResponse, A2AError> {
-        let json = json_rpc::serialize_request(request)?;
-        let response_text = self.send_raw_request(&json).await?;
-        let response: JSONRPCResponse = serde_json::from_str(&response_text)?;
-        Ok(response)
+        self.interceptors
+            .run(request.clone(), |request| async move {
+                let json = json_rpc::serialize_request(&request.request)?;
+                let response_text = self.send_with_retries(&json, &request.headers).await?;
+                let response: JSONRPCResponse = serde_json::from_str(&response_text)?;
+                Ok(response)
+            })
+            .await
     }
 
     #[cfg_attr(
@@ -146,6 +433,7 @@ impl AsyncA2AClient for HttpClient {
             push_notification: None,
             history_length,
             metadata: None,
+            expected_version: None,
         };
 
         let request = SendTaskRequest::new(params);
@@ -183,6 +471,9 @@ impl AsyncA2AClient for HttpClient {
             id: task_id.to_string(),
             history_length,
             metadata: None,
+            since: None,
+            snapshot: None,
+            exclude: Vec::new(),
         };
 
         let request = json_rpc::GetTaskRequest::new(params);
@@ -212,6 +503,8 @@ impl AsyncA2AClient for HttpClient {
         let params = TaskIdParams {
             id: task_id.to_string(),
             metadata: None,
+            expected_version: None,
+            reason: None,
         };
 
         let request = json_rpc::CancelTaskRequest::new(params);
@@ -271,6 +564,8 @@ impl AsyncA2AClient for HttpClient {
         let params = TaskIdParams {
             id: task_id.to_string(),
             metadata: None,
+            expected_version: None,
+            reason: None,
         };
 
         let request = json_rpc::GetTaskPushNotificationRequest::new(params);
@@ -0,0 +1,455 @@
+//! Rate limiting, a concurrency cap and priority admission for the HTTP server
+//!
+//! Requests over a limit are refused before they reach the processor, with
+//! `429 Too Many Requests` once the rate limit is used up and
+//! `503 Service Unavailable` while too many requests are in flight. Both
+//! responses carry a `Retry-After` header and a `server.busy` JSON-RPC error
+//! whose `retryAfter` parameter holds the same number of seconds.
+//!
+//! With an admission queue, requests arriving at the concurrency cap wait
+//! for a slot instead, and are admitted by [`RequestPriority`]: the most
+//! urgent first, the longest waiting among equals. A full queue sheds its
+//! least urgent request. Waiting requests grow more urgent over time, so low
+//! priority work still runs while high priority work keeps arriving.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{Request, State},
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{CONTENT_LENGTH, RETRY_AFTER},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::{Value, json};
+use tokio::sync::oneshot;
+
+use super::{
+    PRIORITY_HEADER, RequestPriority,
+    request_id::{RequestId, add_to_error},
+};
+use crate::domain::{A2AError, validation::content::SKILL_ID_KEY};
+
+/// Largest request body read to find the method or skill it is for
+const MAX_CLASSIFIED_BODY: u64 = 2 * 1024 * 1024;
+
+/// Limits applied to every request the HTTP server receives
+#[derive(Debug, Clone)]
+pub struct RequestLimits {
+    max_concurrent_requests: Option<usize>,
+    rate_limit: Option<(u32, Duration)>,
+    retry_after: Duration,
+    queue: Option<QueueLimits>,
+    aging: Duration,
+    method_priorities: HashMap<String, RequestPriority>,
+    skill_priorities: HashMap<String, RequestPriority>,
+}
+
+/// How many requests may wait for a slot, and for how long
+#[derive(Debug, Clone, Copy)]
+struct QueueLimits {
+    capacity: usize,
+    max_wait: Duration,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: None,
+            rate_limit: None,
+            retry_after: Duration::from_secs(1),
+            queue: None,
+            aging: Duration::from_secs(1),
+            method_priorities: HashMap::new(),
+            skill_priorities: HashMap::new(),
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Create limits that refuse nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refuse requests with `503` while `max` are already being handled
+    pub fn with_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /// Accept at most `requests` per `window`, refusing the rest with `429`.
+    ///
+    /// The suggested delay is the time left in the current window.
+    pub fn with_rate_limit(mut self, requests: u32, window: Duration) -> Self {
+        self.rate_limit = Some((requests, window));
+        self
+    }
+
+    /// Delay suggested to clients refused by the concurrency cap (default 1 second)
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Let up to `capacity` requests wait at the concurrency cap, each for
+    /// at most `max_wait`, instead of refusing them at once.
+    ///
+    /// Waiting requests are admitted most urgent first. When the queue is
+    /// full, the least urgent of the waiting requests and the new one is
+    /// refused with `503`.
+    pub fn with_admission_queue(mut self, capacity: usize, max_wait: Duration) -> Self {
+        self.queue = Some(QueueLimits { capacity, max_wait });
+        self
+    }
+
+    /// Raise a waiting request's priority by one level for every `interval`
+    /// it has waited (default 1 second), so low priority work is not starved
+    pub fn with_priority_aging(mut self, interval: Duration) -> Self {
+        self.aging = interval;
+        self
+    }
+
+    /// Give requests for the JSON-RPC `method` this priority when they do not
+    /// carry an `X-A2A-Priority` header
+    pub fn with_method_priority(
+        mut self,
+        method: impl Into<String>,
+        priority: RequestPriority,
+    ) -> Self {
+        self.method_priorities.insert(method.into(), priority);
+        self
+    }
+
+    /// Give messages addressed to the skill `skill_id` this priority when
+    /// they do not carry an `X-A2A-Priority` header; wins over the method's
+    pub fn with_skill_priority(
+        mut self,
+        skill_id: impl Into<String>,
+        priority: RequestPriority,
+    ) -> Self {
+        self.skill_priorities.insert(skill_id.into(), priority);
+        self
+    }
+
+    /// The priority a request asks for, or is given by its method or skill
+    fn priority_of(&self, headers: &HeaderMap, body: Option<&Value>) -> RequestPriority {
+        let requested = headers
+            .get(PRIORITY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        if let Some(priority) = requested {
+            return priority;
+        }
+        let Some(body) = body else {
+            return RequestPriority::default();
+        };
+
+        let skill = body
+            .pointer("/params/message/metadata")
+            .and_then(|metadata| metadata.get(SKILL_ID_KEY))
+            .and_then(Value::as_str)
+            .and_then(|skill| self.skill_priorities.get(skill));
+        let method = body
+            .get("method")
+            .and_then(Value::as_str)
+            .and_then(|method| self.method_priorities.get(method));
+        skill.or(method).copied().unwrap_or_default()
+    }
+
+    /// Whether the priority of requests without a header depends on their body
+    fn classifies_bodies(&self) -> bool {
+        !self.method_priorities.is_empty() || !self.skill_priorities.is_empty()
+    }
+}
+
+#[derive(Clone)]
+struct LimitState {
+    limits: Arc<RequestLimits>,
+    admission: Option<Arc<Admission>>,
+    window: Arc<Mutex<Window>>,
+}
+
+/// Requests counted in the current fixed rate-limit window
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+impl LimitState {
+    fn new(limits: RequestLimits) -> Self {
+        Self {
+            admission: limits.max_concurrent_requests.map(|max| {
+                Arc::new(Admission {
+                    max,
+                    queue: limits.queue,
+                    aging: limits.aging,
+                    state: Mutex::new(AdmissionState::default()),
+                })
+            }),
+            limits: Arc::new(limits),
+            window: Arc::new(Mutex::new(Window {
+                started: Instant::now(),
+                count: 0,
+            })),
+        }
+    }
+
+    /// Count a request against the rate limit, returning the time left in the
+    /// window when it is used up
+    fn rate_limited(&self) -> Option<Duration> {
+        let (requests, length) = self.limits.rate_limit?;
+        let mut window = self
+            .window
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let elapsed = window.started.elapsed();
+        if elapsed >= length {
+            window.started = Instant::now();
+            window.count = 0;
+        }
+        if window.count >= requests {
+            return Some(length.saturating_sub(window.started.elapsed()));
+        }
+        window.count += 1;
+        None
+    }
+}
+
+/// Wrap `router` so requests over `limits` are refused
+pub(crate) fn with_request_limits(router: Router, limits: RequestLimits) -> Router {
+    router.layer(axum::middleware::from_fn_with_state(
+        LimitState::new(limits),
+        limit_requests,
+    ))
+}
+
+async fn limit_requests(State(state): State<LimitState>, request: Request, next: Next) -> Response {
+    let request_id = request.extensions().get::<RequestId>().cloned();
+    if let Some(remaining) = state.rate_limited() {
+        return busy(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Rate limit exceeded",
+            remaining,
+            request_id.as_ref(),
+        );
+    }
+    let Some(admission) = &state.admission else {
+        return next.run(request).await;
+    };
+
+    let (request, priority) = classify(&state.limits, request).await;
+    // Held until the response is produced
+    let Some(_permit) = admission.admit(priority).await else {
+        return busy(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many concurrent requests",
+            state.limits.retry_after,
+            request_id.as_ref(),
+        );
+    };
+
+    next.run(request).await
+}
+
+/// Find the priority of `request`, reading its body only when the limits
+/// assign priorities by method or skill and the request sets none itself
+async fn classify(limits: &RequestLimits, request: Request) -> (Request, RequestPriority) {
+    let small = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .is_some_and(|length| length <= MAX_CLASSIFIED_BODY);
+    if request.headers().contains_key(PRIORITY_HEADER) || !limits.classifies_bodies() || !small {
+        let priority = limits.priority_of(request.headers(), None);
+        return (request, priority);
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, MAX_CLASSIFIED_BODY as usize)
+        .await
+        .unwrap_or_default();
+    let value = serde_json::from_slice::<Value>(&bytes).ok();
+    let priority = limits.priority_of(&parts.headers, value.as_ref());
+    (Request::from_parts(parts, Body::from(bytes)), priority)
+}
+
+/// Admits up to `max` requests at a time, queueing the rest by priority
+struct Admission {
+    max: usize,
+    queue: Option<QueueLimits>,
+    aging: Duration,
+    state: Mutex<AdmissionState>,
+}
+
+#[derive(Default)]
+struct AdmissionState {
+    in_flight: usize,
+    waiting: Vec<Waiter>,
+    next_id: u64,
+}
+
+/// A request waiting for a slot, which it is handed over its channel
+struct Waiter {
+    id: u64,
+    priority: RequestPriority,
+    since: Instant,
+    admit: oneshot::Sender<Permit>,
+}
+
+/// A slot held by an admitted request, passed on to the next one when dropped
+struct Permit(Option<Arc<Admission>>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(admission) = self.0.take() {
+            admission.release();
+        }
+    }
+}
+
+impl Admission {
+    fn lock(&self) -> std::sync::MutexGuard<'_, AdmissionState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// How urgent a request of `priority` waiting since `since` is by `now`;
+    /// the longest waiting comes first among equal priorities
+    fn urgency(
+        &self,
+        priority: RequestPriority,
+        since: Instant,
+        now: Instant,
+    ) -> (RequestPriority, std::cmp::Reverse<Instant>) {
+        let waited = now.saturating_duration_since(since);
+        let levels = match self.aging.as_nanos() {
+            0 => 0,
+            aging => (waited.as_nanos() / aging).min(u32::MAX as u128) as u32,
+        };
+        (priority.raised(levels), std::cmp::Reverse(since))
+    }
+
+    /// Wait for a slot, or `None` when the request is shed
+    async fn admit(self: &Arc<Self>, priority: RequestPriority) -> Option<Permit> {
+        let (id, mut admitted, max_wait) = {
+            let mut state = self.lock();
+            if state.in_flight < self.max && state.waiting.is_empty() {
+                state.in_flight += 1;
+                return Some(Permit(Some(self.clone())));
+            }
+            let queue = self.queue?;
+
+            let now = Instant::now();
+            if state.waiting.len() >= queue.capacity {
+                // Shed whichever is least urgent, this request included
+                let least = state
+                    .waiting
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, waiter)| self.urgency(waiter.priority, waiter.since, now))
+                    .map(|(index, waiter)| {
+                        (index, self.urgency(waiter.priority, waiter.since, now))
+                    });
+                match least {
+                    Some((index, urgency)) if urgency < self.urgency(priority, now, now) => {
+                        // Dropping its sender tells the waiter it was shed
+                        state.waiting.swap_remove(index);
+                    }
+                    _ => return None,
+                }
+            }
+
+            let (admit, admitted) = oneshot::channel();
+            let id = state.next_id;
+            state.next_id += 1;
+            state.waiting.push(Waiter {
+                id,
+                priority,
+                since: now,
+                admit,
+            });
+            (id, admitted, queue.max_wait)
+        };
+
+        match tokio::time::timeout(max_wait, &mut admitted).await {
+            Ok(result) => result.ok(),
+            Err(_) => {
+                let mut state = self.lock();
+                if let Some(index) = state.waiting.iter().position(|waiter| waiter.id == id) {
+                    state.waiting.swap_remove(index);
+                    return None;
+                }
+                drop(state);
+                // Admitted or shed just as the wait ran out
+                admitted.try_recv().ok()
+            }
+        }
+    }
+
+    /// Hand the slot of a finished request to the most urgent waiting one
+    fn release(self: &Arc<Self>) {
+        let mut state = self.lock();
+        let mut permit = Permit(Some(self.clone()));
+        let now = Instant::now();
+        while let Some(next) = state
+            .waiting
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, waiter)| self.urgency(waiter.priority, waiter.since, now))
+            .map(|(index, _)| index)
+        {
+            let waiter = state.waiting.swap_remove(next);
+            match waiter.admit.send(permit) {
+                Ok(()) => return,
+                // The request went away while waiting
+                Err(returned) => permit = returned,
+            }
+        }
+        // Nobody is waiting: give the slot up without releasing it again
+        permit.0 = None;
+        state.in_flight -= 1;
+    }
+}
+
+/// A refusal suggesting a retry after `delay`, rounded up to whole seconds,
+/// tagged with the refused request's `request_id`
+fn busy(
+    status: StatusCode,
+    reason: &str,
+    delay: Duration,
+    request_id: Option<&RequestId>,
+) -> Response {
+    let seconds = delay.as_millis().div_ceil(1000).max(1) as u64;
+
+    #[cfg(feature = "tracing")]
+    tracing::warn!(retry_after = seconds, "{}", reason);
+
+    let error = A2AError::ServerBusy {
+        reason: reason.to_string(),
+        retry_after: Some(Duration::from_secs(seconds)),
+    };
+    let mut body = json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": error.to_jsonrpc_error(),
+    });
+    if let Some(id) = request_id {
+        add_to_error(&mut body, id);
+    }
+    let mut response = (status, Json(body)).into_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(seconds));
+    response
+}
@@ -1,14 +1,48 @@
 //! HTTP transport implementations
 
+/// Header carrying the id that correlates a request across client and server logs
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Header in which a client asks for a [`RequestPriority`]
+pub const PRIORITY_HEADER: &str = "x-a2a-priority";
+
 #[cfg(feature = "http-client")]
 pub mod client;
 
+#[cfg(feature = "http-server")]
+pub mod limits;
+
+pub mod priority;
+
+#[cfg(feature = "http-server")]
+pub mod request_id;
+
 #[cfg(feature = "http-server")]
 pub mod server;
 
+#[cfg(all(feature = "http-server", feature = "ws-client"))]
+pub mod sse_bridge;
+
+#[cfg(all(feature = "http-server", feature = "tracing"))]
+pub mod task_logs;
+
 // Re-export HTTP implementations
 #[cfg(feature = "http-client")]
-pub use client::HttpClient;
+pub use client::{HttpClient, RetryPolicy};
+
+#[cfg(feature = "http-server")]
+pub use limits::RequestLimits;
+
+pub use priority::RequestPriority;
+
+#[cfg(feature = "http-server")]
+pub use request_id::RequestId;
 
 #[cfg(feature = "http-server")]
 pub use server::HttpServer;
+
+#[cfg(all(feature = "http-server", feature = "ws-client"))]
+pub use sse_bridge::{SseBridge, sse_bridge_routes};
+
+#[cfg(all(feature = "http-server", feature = "tracing"))]
+pub use task_logs::{TaskLogConfig, task_log_routes};
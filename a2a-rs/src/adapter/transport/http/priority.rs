@@ -0,0 +1,68 @@
+//! Request priorities for admission under load
+//!
+//! A client marks how urgent a request is with the `X-A2A-Priority` header.
+//! When the server is at its concurrency cap, queued requests are admitted
+//! most urgent first, and the least urgent are the first to be refused.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::A2AError;
+
+/// How urgently a request should be handled while the server is saturated
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPriority {
+    /// Work nobody is waiting on, such as bulk imports
+    Low,
+    /// Everything not marked otherwise
+    #[default]
+    Normal,
+    /// Latency-sensitive work, such as interactive chat
+    High,
+}
+
+impl RequestPriority {
+    /// Every priority, least urgent first
+    pub const ALL: [RequestPriority; 3] = [
+        RequestPriority::Low,
+        RequestPriority::Normal,
+        RequestPriority::High,
+    ];
+
+    /// The priority's name in the `X-A2A-Priority` header
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RequestPriority::Low => "low",
+            RequestPriority::Normal => "normal",
+            RequestPriority::High => "high",
+        }
+    }
+
+    /// The priority `levels` steps more urgent, at most [`RequestPriority::High`]
+    pub fn raised(self, levels: u32) -> Self {
+        let index = (self as usize).saturating_add(levels as usize);
+        Self::ALL[index.min(Self::ALL.len() - 1)]
+    }
+}
+
+impl fmt::Display for RequestPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RequestPriority {
+    type Err = A2AError;
+
+    /// Parse a priority from its header name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RequestPriority::ALL
+            .into_iter()
+            .find(|priority| priority.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| A2AError::InvalidParams(format!("Unknown request priority: {}", s)))
+    }
+}
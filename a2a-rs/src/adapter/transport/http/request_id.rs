@@ -0,0 +1,94 @@
+//! Request ids correlating client and server logs
+//!
+//! Every request the HTTP server handles gets an id: the one the client sent
+//! in the `X-Request-ID` header, or a fresh one when it sent none or one that
+//! is unusable. The id is echoed in the response header, recorded on a tracing
+//! span enclosing everything logged while handling the request, and added as
+//! `requestId` to the data of any JSON-RPC error in the response body.
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use serde_json::{Value, json};
+
+use super::REQUEST_ID_HEADER;
+use crate::domain::{IdGenerator, UuidGenerator};
+
+/// Longest client-supplied request id the server accepts
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// The id of the request being handled, available to handlers as an extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// The id as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Wrap `router` so every request carries a [`RequestId`], minting ids with
+/// `ids` for requests that arrive without one
+pub(crate) fn with_request_ids(router: Router, ids: Option<Arc<dyn IdGenerator>>) -> Router {
+    let ids = ids.unwrap_or_else(|| Arc::new(UuidGenerator));
+    router.layer(axum::middleware::from_fn_with_state(ids, tag_request))
+}
+
+async fn tag_request(
+    State(ids): State<Arc<dyn IdGenerator>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_usable(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| ids.next_id());
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    #[cfg(feature = "tracing")]
+    let mut response = {
+        use tracing::Instrument;
+        let span = tracing::info_span!("http_request", request_id = %id);
+        next.run(request).instrument(span).await
+    };
+    #[cfg(not(feature = "tracing"))]
+    let mut response = next.run(request).await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Whether a client-supplied id can be echoed and logged as it is
+fn is_usable(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Add `id` as `requestId` to the data of the JSON-RPC error in `body`, if it
+/// holds one that can take it
+pub(crate) fn add_to_error(body: &mut Value, id: &RequestId) {
+    let Some(error) = body.get_mut("error").and_then(Value::as_object_mut) else {
+        return;
+    };
+    match error.get_mut("data") {
+        Some(Value::Object(data)) => {
+            data.insert("requestId".to_string(), Value::String(id.0.clone()));
+        }
+        None | Some(Value::Null) => {
+            error.insert("data".to_string(), json!({ "requestId": id.as_str() }));
+        }
+        // Data of another shape is left for clients that expect it
+        Some(_) => {}
+    }
+}
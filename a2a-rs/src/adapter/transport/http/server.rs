@@ -2,13 +2,14 @@
 
 // This module is already conditionally compiled with #[cfg(feature = "http-server")] in mod.rs
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
     Json, Router,
-    extract::State,
-    http::StatusCode,
-    response::IntoResponse,
+    body::Bytes,
+    extract::{Extension, State},
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use serde_json::{Value, json};
@@ -16,16 +17,28 @@ use serde_json::{Value, json};
 #[cfg(feature = "tracing")]
 use tracing::{debug, error, info, instrument};
 
+use super::limits::{RequestLimits, with_request_limits};
+use super::request_id::{RequestId, add_to_error, with_request_ids};
+#[cfg(feature = "tracing")]
+use super::task_logs::{TaskLogConfig, task_log_routes};
+#[cfg(feature = "signing")]
+use crate::adapter::auth::{RequestVerifier, with_signature_verification};
+#[cfg(feature = "tracing")]
+use crate::observability::TaskLogHub;
 use crate::{
     adapter::{
-        auth::{NoopAuthenticator, with_auth},
+        auth::{MethodAccessPolicy, NoopAuthenticator, with_auth},
         error::HttpServerError,
     },
-    domain::A2AError,
-    port::Authenticator,
+    domain::{A2AError, IdGenerator, StorageFailure},
+    port::{AuthPrincipal, Authenticator},
     services::server::{AgentInfoProvider, AsyncA2ARequestProcessor},
 };
 
+/// How long clients are asked to wait while storage is unavailable, unless
+/// set with [`HttpServer::with_unavailable_retry_after`]
+const DEFAULT_UNAVAILABLE_RETRY_AFTER: Duration = Duration::from_secs(5);
+
 /// HTTP server for the A2A protocol
 pub struct HttpServer<P, A, Auth = NoopAuthenticator>
 where
@@ -41,6 +54,20 @@ where
     address: String,
     /// Authenticator
     authenticator: Option<Arc<Auth>>,
+    /// Methods each auth role may call
+    method_access: Option<Arc<MethodAccessPolicy>>,
+    /// Rate limit and concurrency cap
+    request_limits: Option<RequestLimits>,
+    /// Ids for requests arriving without one; random UUIDs when unset
+    request_ids: Option<Arc<dyn IdGenerator>>,
+    /// `Retry-After` hint sent while storage is unavailable
+    unavailable_retry_after: Duration,
+    /// Request signature verifier
+    #[cfg(feature = "signing")]
+    request_verifier: Option<RequestVerifier>,
+    /// Per-task debug log stream
+    #[cfg(feature = "tracing")]
+    task_logs: Option<(TaskLogHub, TaskLogConfig)>,
 }
 
 impl<P, A> HttpServer<P, A>
@@ -55,6 +82,14 @@ where
             agent_info: Arc::new(agent_info),
             address,
             authenticator: None,
+            method_access: None,
+            request_limits: None,
+            request_ids: None,
+            unavailable_retry_after: DEFAULT_UNAVAILABLE_RETRY_AFTER,
+            #[cfg(feature = "signing")]
+            request_verifier: None,
+            #[cfg(feature = "tracing")]
+            task_logs: None,
         }
     }
 }
@@ -72,9 +107,66 @@ where
             agent_info: Arc::new(agent_info),
             address,
             authenticator: Some(Arc::new(authenticator)),
+            method_access: None,
+            request_limits: None,
+            request_ids: None,
+            unavailable_retry_after: DEFAULT_UNAVAILABLE_RETRY_AFTER,
+            #[cfg(feature = "signing")]
+            request_verifier: None,
+            #[cfg(feature = "tracing")]
+            task_logs: None,
         }
     }
 
+    /// Restrict the JSON-RPC methods each auth role may call.
+    ///
+    /// Refused requests are answered with a `METHOD_NOT_AUTHORIZED` JSON-RPC
+    /// error. Roles are assigned by the authenticator, so the policy only
+    /// takes effect on a server created with [`HttpServer::with_auth`].
+    pub fn with_method_access(mut self, policy: MethodAccessPolicy) -> Self {
+        self.method_access = Some(Arc::new(policy));
+        self
+    }
+
+    /// Refuse requests over `limits` with `429` or `503` and a `Retry-After`
+    /// hint, before authentication runs
+    pub fn with_request_limits(mut self, limits: RequestLimits) -> Self {
+        self.request_limits = Some(limits);
+        self
+    }
+
+    /// Mint ids for requests arriving without an `X-Request-ID` header with
+    /// `ids` instead of random UUIDs
+    pub fn with_request_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.request_ids = Some(Arc::new(ids));
+        self
+    }
+
+    /// Ask clients to retry after `delay` when a request fails because
+    /// storage is unavailable, instead of five seconds
+    pub fn with_unavailable_retry_after(mut self, delay: Duration) -> Self {
+        self.unavailable_retry_after = delay;
+        self
+    }
+
+    /// Require every JSON-RPC request to carry a valid detached signature
+    #[cfg(feature = "signing")]
+    pub fn with_request_verifier(mut self, verifier: RequestVerifier) -> Self {
+        self.request_verifier = Some(verifier);
+        self
+    }
+
+    /// Serve `GET /tasks/{task_id}/logs`, streaming the lines `hub` captured
+    /// for a task, when `config` is enabled.
+    ///
+    /// The stream is authorized by the config's own authenticator rather than
+    /// the server's, so debug access can be granted separately.
+    #[cfg(feature = "tracing")]
+    pub fn with_task_logs(mut self, hub: TaskLogHub, config: TaskLogConfig) -> Self {
+        self.task_logs = Some((hub, config));
+        self
+    }
+
     /// Start the HTTP server
     #[cfg_attr(feature = "tracing", instrument(skip(self), fields(
         server.address = %self.address,
@@ -87,6 +179,12 @@ where
         let processor = self.processor.clone();
         let agent_info = self.agent_info.clone();
 
+        let state = ServerState {
+            processor: processor.clone(),
+            agent_info: agent_info.clone(),
+            method_access: self.method_access.clone(),
+            unavailable_retry_after: self.unavailable_retry_after,
+        };
         let mut app = Router::new()
             .route("/", post(handle_request))
             // v0.3.0 well-known URI endpoint (RFC 8615)
@@ -95,10 +193,14 @@ where
             .route("/agent-card", get(handle_agent_card))
             .route("/skills", get(handle_skills))
             .route("/skills/{id}", get(handle_skill_by_id))
-            .with_state(ServerState {
-                processor: processor.clone(),
-                agent_info: agent_info.clone(),
-            });
+            .with_state(state.clone());
+
+        // Layered inside authentication, so signatures are only verified,
+        // and their nonces only remembered, for authenticated requests
+        #[cfg(feature = "signing")]
+        if let Some(verifier) = &self.request_verifier {
+            app = with_signature_verification(app, verifier.clone());
+        }
 
         // Apply authentication if provided
         if let Some(auth) = &self.authenticator {
@@ -109,6 +211,31 @@ where
             app = with_auth(app, (*auth_clone).clone());
         }
 
+        // Outermost, so refused requests cost no authentication work
+        if let Some(limits) = &self.request_limits {
+            app = with_request_limits(app, limits.clone());
+        }
+
+        // Merged after the layers above, so only the debug authenticator applies
+        #[cfg(feature = "tracing")]
+        if let Some((hub, config)) = &self.task_logs {
+            if config.is_enabled() {
+                info!("Serving per-task debug logs at /tasks/{{task_id}}/logs");
+            }
+            app = app.merge(task_log_routes(hub.clone(), config.clone()));
+        }
+
+        // Merged after auth and limits, so orchestrators probe without
+        // credentials and are never refused as busy
+        app = app.merge(
+            Router::new()
+                .route("/ready", get(handle_ready))
+                .with_state(state),
+        );
+
+        // Outermost of all, so refusals are tagged too
+        app = with_request_ids(app, self.request_ids.clone());
+
         let listener = tokio::net::TcpListener::bind(&self.address)
             .await
             .map_err(HttpServerError::Io)?;
@@ -135,16 +262,24 @@ where
 {
     processor: Arc<P>,
     agent_info: Arc<A>,
+    method_access: Option<Arc<MethodAccessPolicy>>,
+    unavailable_retry_after: Duration,
 }
 
 /// Handle a request from a client
-#[cfg_attr(feature = "tracing", instrument(skip(state), fields(
-    request.id = %request.get("id").and_then(|v| v.as_str()).unwrap_or("unknown"),
-    request.method = %request.get("method").and_then(|v| v.as_str()).unwrap_or("unknown")
+///
+/// The body is taken as raw bytes rather than through the `Json` extractor,
+/// so that malformed payloads are answered with a JSON-RPC parse error
+/// instead of a plain-text rejection.
+#[cfg_attr(feature = "tracing", instrument(skip(state, principal, request_id, body), fields(
+    request.id = tracing::field::Empty,
+    request.method = tracing::field::Empty
 )))]
 async fn handle_request<P, A>(
     State(state): State<ServerState<P, A>>,
-    Json(request): Json<Value>,
+    principal: Option<Extension<AuthPrincipal>>,
+    request_id: Option<Extension<RequestId>>,
+    body: Bytes,
 ) -> impl IntoResponse
 where
     P: AsyncA2ARequestProcessor + Send + Sync + 'static,
@@ -156,15 +291,14 @@ where
     #[cfg(feature = "tracing")]
     let start_time = std::time::Instant::now();
 
-    // Convert the request to a string
-    let request_str = match serde_json::to_string(&request) {
+    let request_id = request_id.map(|Extension(id)| id);
+    let request_str = match std::str::from_utf8(&body) {
         Ok(str) => str,
         Err(e) => {
             #[cfg(feature = "tracing")]
             error!("Invalid JSON payload: {}", e);
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
+            return json_rpc_response(
+                json!({
                     "jsonrpc": "2.0",
                     "id": null,
                     "error": {
@@ -172,14 +306,52 @@ where
                         "message": "Invalid JSON payload",
                         "data": e.to_string()
                     }
-                })),
-            )
-                .into_response();
+                }),
+                StatusCode::BAD_REQUEST,
+                &state,
+                request_id.as_ref(),
+            );
         }
     };
 
+    #[cfg(feature = "tracing")]
+    if let Ok(request) = serde_json::from_str::<Value>(request_str) {
+        let span = tracing::Span::current();
+        span.record(
+            "request.id",
+            request
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown"),
+        );
+        span.record(
+            "request.method",
+            request
+                .get("method")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown"),
+        );
+    }
+
+    let principal = principal.map(|Extension(principal)| principal);
+
+    // Refuse methods the caller's role may not call
+    if let Some(policy) = &state.method_access {
+        let role = principal.as_ref().and_then(AuthPrincipal::role);
+        if let Some(response) = policy.denial(role, request_str) {
+            #[cfg(feature = "tracing")]
+            info!(role = role.unwrap_or_default(), "Method not authorized");
+            let response = serde_json::from_str(&response).unwrap_or_default();
+            return json_rpc_response(response, StatusCode::OK, &state, request_id.as_ref());
+        }
+    }
+
     // Process the request
-    match state.processor.process_raw_request(&request_str).await {
+    match state
+        .processor
+        .process_raw_request_as(request_str, principal.as_ref())
+        .await
+    {
         Ok(response) => {
             #[cfg(feature = "tracing")]
             debug!("Request processed successfully");
@@ -188,9 +360,8 @@ where
                 Err(e) => {
                     #[cfg(feature = "tracing")]
                     error!("Failed to parse response: {}", e);
-                    return (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(json!({
+                    return json_rpc_response(
+                        json!({
                             "jsonrpc": "2.0",
                             "id": null,
                             "error": {
@@ -198,9 +369,11 @@ where
                                 "message": "Internal error",
                                 "data": "Failed to parse response"
                             }
-                        })),
-                    )
-                        .into_response();
+                        }),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        &state,
+                        request_id.as_ref(),
+                    );
                 }
             };
 
@@ -214,21 +387,92 @@ where
                 );
             }
 
-            (StatusCode::OK, Json(response_value)).into_response()
+            json_rpc_response(response_value, StatusCode::OK, &state, request_id.as_ref())
         }
         Err(e) => {
             #[cfg(feature = "tracing")]
             error!("Request processing failed: {}", e);
             let error = e.to_jsonrpc_error();
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
+            json_rpc_response(
+                json!({
                     "jsonrpc": "2.0",
                     "id": null,
                     "error": error
-                })),
+                }),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &state,
+                request_id.as_ref(),
             )
-                .into_response()
+        }
+    }
+}
+
+/// Answer with a JSON-RPC response, choosing the HTTP status from its error
+/// so that clients and orchestrators can tell an outage from a failed
+/// request without reading the body: `503` with `Retry-After` while storage
+/// is unavailable, `409` for storage conflicts, `500` for corrupt data and
+/// `status` otherwise. An error is tagged with the request's `request_id`.
+fn json_rpc_response<P, A>(
+    mut body: Value,
+    status: StatusCode,
+    state: &ServerState<P, A>,
+    request_id: Option<&RequestId>,
+) -> Response
+where
+    P: AsyncA2ARequestProcessor + Send + Sync + 'static,
+    A: AgentInfoProvider + Send + Sync + 'static,
+{
+    let failure = body
+        .get("error")
+        .and_then(|error| error.get("code"))
+        .and_then(Value::as_i64)
+        .and_then(|code| StorageFailure::from_code(code as i32));
+    let status = match failure {
+        Some(StorageFailure::Unavailable) => StatusCode::SERVICE_UNAVAILABLE,
+        Some(StorageFailure::Conflict) => StatusCode::CONFLICT,
+        Some(StorageFailure::Corrupt) => StatusCode::INTERNAL_SERVER_ERROR,
+        None => status,
+    };
+    if let Some(id) = request_id {
+        add_to_error(&mut body, id);
+    }
+    let mut response = (status, Json(body)).into_response();
+    if failure.is_some_and(|failure| failure.is_retryable()) {
+        with_retry_after(&mut response, state.unavailable_retry_after);
+    }
+    response
+}
+
+/// Add a `Retry-After` header of `delay`, rounded up to whole seconds
+fn with_retry_after(response: &mut Response, delay: Duration) {
+    let seconds = delay.as_millis().div_ceil(1000).max(1) as u64;
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(seconds));
+}
+
+/// Answer a readiness probe: `200` while requests can be served, `503`
+/// otherwise, with a `Retry-After` hint while storage is unavailable
+#[cfg_attr(feature = "tracing", instrument(skip(state)))]
+async fn handle_ready<P, A>(State(state): State<ServerState<P, A>>) -> Response
+where
+    P: AsyncA2ARequestProcessor + Send + Sync + 'static,
+    A: AgentInfoProvider + Send + Sync + 'static,
+{
+    match state.processor.check_ready().await {
+        Ok(()) => (StatusCode::OK, Json(json!({"status": "ready"}))).into_response(),
+        Err(e) => {
+            #[cfg(feature = "tracing")]
+            error!("Readiness check failed: {}", e);
+            let body = json!({
+                "status": "unavailable",
+                "error": e.error_detail(),
+            });
+            let mut response = (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+            if e.is_retryable() {
+                with_retry_after(&mut response, state.unavailable_retry_after);
+            }
+            response
         }
     }
 }
@@ -0,0 +1,400 @@
+//! Server-sent events bridge to a WebSocket-only agent
+//!
+//! `GET /tasks/{task_id}/stream` subscribes to the task over the upstream
+//! agent's WebSocket and forwards every update as an SSE event, so clients
+//! that can only speak HTTP still receive streaming updates. Events use the
+//! same types as the web frontend's stream: `task-update` for a full task,
+//! `task-status` for a status update and `artifact` for an artifact update,
+//! each carrying the JSON payload as data. An upstream failure is sent as a
+//! final `error` event before the stream ends.
+//!
+//! SSE clients watching the same task with the same credentials share one
+//! upstream subscription, opened when the first of them connects and dropped
+//! as soon as the last one goes away. A client joining later starts from the
+//! task as the shared subscription last saw it, then follows its updates. A
+//! bearer token sent to the bridge is forwarded in the upstream auth
+//! handshake, so the agent keeps deciding who may subscribe.
+
+// This module is already conditionally compiled with
+// #[cfg(all(feature = "http-server", feature = "ws-client"))] in mod.rs
+
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::get,
+};
+use futures::{StreamExt, stream};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::AbortHandle,
+};
+
+#[cfg(feature = "tracing")]
+use tracing::{debug, info, warn};
+
+use crate::{
+    adapter::{
+        WebSocketClient, WebSocketCredentials, WebSocketOptions, auth::BearerTokenExtractor,
+        error::HttpServerError,
+    },
+    domain::{A2AError, Task, TaskStatusUpdateEvent},
+    port::AuthContextExtractor,
+    services::client::{AsyncA2AClient, StreamItem},
+};
+
+/// Events buffered for each SSE client of a shared subscription; a client
+/// falling further behind is resynchronized from the task's latest state
+const SUBSCRIBER_BUFFER: usize = 64;
+
+/// A task id and the credential its upstream subscription was opened with
+type SourceKey = (String, Option<String>);
+
+type Sources = Arc<Mutex<HashMap<SourceKey, Arc<SharedSource>>>>;
+
+/// Serves SSE subscriptions backed by an upstream WebSocket agent
+#[derive(Clone)]
+pub struct SseBridge {
+    /// WebSocket URL of the upstream agent
+    upstream_url: String,
+    /// Options for upstream connections
+    options: WebSocketOptions,
+    /// History sent with the initial task of each subscription
+    history_length: Option<u32>,
+    /// Interval of SSE keep-alive comments
+    keep_alive: Duration,
+    /// Open upstream subscriptions
+    sources: Sources,
+}
+
+impl SseBridge {
+    /// Create a bridge to the agent at `upstream_url`
+    pub fn new(upstream_url: String) -> Self {
+        Self {
+            upstream_url,
+            options: WebSocketOptions::default(),
+            history_length: None,
+            keep_alive: Duration::from_secs(15),
+            sources: Arc::default(),
+        }
+    }
+
+    /// Set the message size limit and compression of upstream connections
+    pub fn with_options(mut self, options: WebSocketOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Ask for up to `history_length` messages with each subscription's task
+    pub fn with_history_length(mut self, history_length: u32) -> Self {
+        self.history_length = Some(history_length);
+        self
+    }
+
+    /// Set how often idle streams send a keep-alive comment.
+    ///
+    /// A disconnected client is noticed on the next write, so this also bounds
+    /// how long an idle upstream connection outlives its client.
+    pub fn with_keep_alive(mut self, interval: Duration) -> Self {
+        self.keep_alive = interval;
+        self
+    }
+
+    /// The number of upstream subscriptions currently open
+    pub fn shared_subscriptions(&self) -> usize {
+        self.sources.lock().unwrap().len()
+    }
+
+    /// The upstream client authenticating with `credential`
+    fn upstream(&self, credential: Option<String>) -> WebSocketClient {
+        let client =
+            WebSocketClient::new(self.upstream_url.clone()).with_options(self.options.clone());
+        match credential {
+            Some(credential) => client.with_credentials(WebSocketCredentials::Bearer(credential)),
+            None => client,
+        }
+    }
+
+    /// Join the upstream subscription to `task_id` for `credential`, opening
+    /// it if nobody is watching the task with that credential yet
+    fn join(&self, task_id: String, credential: Option<String>) -> Subscription {
+        let key = (task_id, credential);
+        let mut sources = self.sources.lock().unwrap();
+        let source = match sources.get(&key) {
+            Some(source) => source.clone(),
+            None => {
+                #[cfg(feature = "tracing")]
+                debug!("Opening upstream subscription to task {}", key.0);
+                let source = Arc::new(SharedSource::new(self.history_length));
+                let pump = tokio::spawn(pump(
+                    self.upstream(key.1.clone()),
+                    key.clone(),
+                    source.clone(),
+                    self.sources.clone(),
+                ));
+                source.state.lock().unwrap().pump = Some(pump.abort_handle());
+                sources.insert(key.clone(), source.clone());
+                source
+            }
+        };
+
+        let mut state = source.state.lock().unwrap();
+        state.subscribers += 1;
+        let pending = state.replay(source.history_length);
+        let receiver = state.sender.subscribe();
+        drop(state);
+
+        Subscription {
+            key,
+            source,
+            sources: self.sources.clone(),
+            pending,
+            receiver,
+        }
+    }
+
+    /// Serve the bridge routes on `address`
+    pub async fn start(&self, address: &str) -> Result<(), A2AError> {
+        let listener = tokio::net::TcpListener::bind(address)
+            .await
+            .map_err(HttpServerError::Io)?;
+
+        #[cfg(feature = "tracing")]
+        info!(
+            "SSE bridge listening on {}, forwarding to {}",
+            address, self.upstream_url
+        );
+
+        axum::serve(listener, sse_bridge_routes(self.clone()))
+            .await
+            .map_err(|e| HttpServerError::Server(format!("Server error: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Routes serving task subscriptions through `bridge`
+pub fn sse_bridge_routes(bridge: SseBridge) -> Router {
+    Router::new()
+        .route("/tasks/{task_id}/stream", get(stream_task))
+        .with_state(bridge)
+}
+
+async fn stream_task(
+    State(bridge): State<SseBridge>,
+    Path(task_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let credential = BearerTokenExtractor
+        .extract_from_headers(&headers)
+        .await
+        .map(|context| context.credential);
+    let keep_alive = KeepAlive::new().interval(bridge.keep_alive);
+
+    #[cfg(feature = "tracing")]
+    debug!("Bridging task {} to an SSE client", task_id);
+
+    let subscription = bridge.join(task_id, credential);
+    let events = stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.next().await?;
+        Some((Ok::<_, Infallible>(event), subscription))
+    });
+    Sse::new(events).keep_alive(keep_alive).into_response()
+}
+
+/// An upstream subscription and the SSE clients sharing it
+struct SharedSource {
+    /// History sent with the task to clients joining late
+    history_length: Option<u32>,
+    state: Mutex<SourceState>,
+}
+
+struct SourceState {
+    /// Fans each upstream update out to every client
+    sender: broadcast::Sender<Event>,
+    /// The task as of the latest update, once upstream sent it
+    snapshot: Option<Task>,
+    /// The latest status update, for upstreams that never send the task
+    last_status: Option<TaskStatusUpdateEvent>,
+    /// How many SSE clients share the subscription
+    subscribers: usize,
+    /// The task reading the upstream subscription
+    pump: Option<AbortHandle>,
+}
+
+impl SharedSource {
+    fn new(history_length: Option<u32>) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        Self {
+            history_length,
+            state: Mutex::new(SourceState {
+                sender,
+                snapshot: None,
+                last_status: None,
+                subscribers: 0,
+                pump: None,
+            }),
+        }
+    }
+}
+
+impl SourceState {
+    /// Fold an upstream update into the snapshot
+    fn record(&mut self, item: &StreamItem) {
+        item.apply_to(&mut self.snapshot);
+        if let StreamItem::StatusUpdate(update) = item {
+            self.last_status = Some(update.clone());
+        }
+    }
+
+    /// The events bringing a joining client up to date
+    fn replay(&self, history_length: Option<u32>) -> VecDeque<Event> {
+        let item = match (&self.snapshot, &self.last_status) {
+            (Some(task), _) => StreamItem::Task(task.with_limited_history(history_length)),
+            (None, Some(update)) => StreamItem::StatusUpdate(update.clone()),
+            (None, None) => return VecDeque::new(),
+        };
+        VecDeque::from([transcode(&item)])
+    }
+}
+
+/// Read the upstream subscription to `key`, fanning its updates out to the
+/// clients of `source` until it fails or the last client leaves
+async fn pump(
+    client: WebSocketClient,
+    key: SourceKey,
+    source: Arc<SharedSource>,
+    sources: Sources,
+) {
+    let failure = match client
+        .subscribe_to_task(&key.0, source.history_length)
+        .await
+    {
+        Ok(mut updates) => loop {
+            match updates.next().await {
+                Some(Ok(item)) => {
+                    let mut state = source.state.lock().unwrap();
+                    state.record(&item);
+                    // Nobody listening only means every client just left
+                    let _ = state.sender.send(transcode(&item));
+                }
+                // The upstream stream keeps yielding errors once the socket
+                // closes, so stop after forwarding the first one
+                Some(Err(e)) => break e,
+                None => break A2AError::Internal("Upstream subscription ended".to_string()),
+            }
+        },
+        Err(e) => e,
+    };
+
+    #[cfg(feature = "tracing")]
+    warn!(
+        "Upstream subscription to task {} failed: {}",
+        key.0, failure
+    );
+
+    // Later clients open a fresh subscription; the current ones get the error
+    // and then see the stream end once the sender is dropped with the source
+    let mut sources = sources.lock().unwrap();
+    if sources
+        .get(&key)
+        .is_some_and(|current| Arc::ptr_eq(current, &source))
+    {
+        sources.remove(&key);
+    }
+    let mut state = source.state.lock().unwrap();
+    let _ = state.sender.send(error_event(&failure));
+    let (closed, _) = broadcast::channel(1);
+    state.sender = closed;
+}
+
+/// One SSE client's place in a shared subscription
+struct Subscription {
+    key: SourceKey,
+    source: Arc<SharedSource>,
+    sources: Sources,
+    /// Events to send before reading the shared stream
+    pending: VecDeque<Event>,
+    receiver: broadcast::Receiver<Event>,
+}
+
+impl Subscription {
+    /// The next event for this client, or `None` once the upstream is gone
+    async fn next(&mut self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                // Skip what was missed and start again from the latest state
+                Err(RecvError::Lagged(_)) => {
+                    let state = self.source.state.lock().unwrap();
+                    self.pending = state.replay(self.source.history_length);
+                    self.receiver = state.sender.subscribe();
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut sources = self.sources.lock().unwrap();
+        let mut state = self.source.state.lock().unwrap();
+        state.subscribers -= 1;
+        if state.subscribers > 0 {
+            return;
+        }
+        if let Some(pump) = state.pump.take() {
+            pump.abort();
+        }
+        if sources
+            .get(&self.key)
+            .is_some_and(|current| Arc::ptr_eq(current, &self.source))
+        {
+            #[cfg(feature = "tracing")]
+            debug!("Closing upstream subscription to task {}", self.key.0);
+            sources.remove(&self.key);
+        }
+    }
+}
+
+/// The SSE event forwarding a streamed item
+fn transcode(item: &StreamItem) -> Event {
+    let event = match item {
+        StreamItem::Task(task) => Event::default().event("task-update").json_data(task),
+        StreamItem::StatusUpdate(update) => Event::default().event("task-status").json_data(update),
+        StreamItem::ArtifactUpdate(update) => Event::default().event("artifact").json_data(update),
+    };
+    // Stream items always serialize
+    event.unwrap_or_else(|e| error_event(&A2AError::Internal(e.to_string())))
+}
+
+/// The SSE event reporting an upstream failure as a JSON-RPC error
+fn error_event(error: &A2AError) -> Event {
+    // Errors returned by the agent keep their own code
+    let error = match error {
+        A2AError::JsonRpc {
+            code,
+            message,
+            data,
+        } => serde_json::json!({"code": code, "message": message, "data": data}),
+        other => other.to_jsonrpc_error(),
+    };
+    Event::default()
+        .event("error")
+        .json_data(&error)
+        .unwrap_or_else(|_| Event::default().event("error").data(error.to_string()))
+}
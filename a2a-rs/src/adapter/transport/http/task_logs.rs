@@ -0,0 +1,109 @@
+//! Debug endpoint streaming a task's log lines as server-sent events
+//!
+//! `GET /tasks/{task_id}/logs` replays the lines buffered in a [`TaskLogHub`]
+//! and then follows new ones. Each event has type `log`, the entry id as its SSE
+//! id and the [`TaskLogEntry`](crate::observability::TaskLogEntry) as JSON data,
+//! so a reconnecting client resumes with the standard `Last-Event-ID` header.
+//!
+//! The endpoint exposes internals and is meant for development: it is only
+//! mounted when [`TaskLogConfig`] is enabled, and every request must carry a
+//! bearer token accepted by the config's authenticator.
+
+// This module is already conditionally compiled with #[cfg(feature = "tracing")] in mod.rs
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::get,
+};
+use futures::StreamExt;
+
+use crate::{
+    adapter::auth::BearerTokenExtractor,
+    observability::TaskLogHub,
+    port::{AuthContextExtractor, Authenticator},
+};
+
+/// Whether the task log stream is served, and who may read it
+#[derive(Clone)]
+pub struct TaskLogConfig {
+    enabled: bool,
+    authenticator: Arc<dyn Authenticator>,
+}
+
+impl TaskLogConfig {
+    /// Create a disabled config; once enabled, only bearer tokens accepted by
+    /// `authenticator` may read logs
+    pub fn new(authenticator: impl Authenticator + 'static) -> Self {
+        Self {
+            enabled: false,
+            authenticator: Arc::new(authenticator),
+        }
+    }
+
+    /// Serve the stream; keep this off in production
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Whether the stream is served
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[derive(Clone)]
+struct TaskLogState {
+    hub: TaskLogHub,
+    authenticator: Arc<dyn Authenticator>,
+}
+
+/// Routes serving the task log stream, empty when `config` is disabled
+pub fn task_log_routes(hub: TaskLogHub, config: TaskLogConfig) -> Router {
+    if !config.enabled {
+        return Router::new();
+    }
+    Router::new()
+        .route("/tasks/{task_id}/logs", get(stream_task_logs))
+        .with_state(TaskLogState {
+            hub,
+            authenticator: config.authenticator,
+        })
+}
+
+async fn stream_task_logs(
+    State(state): State<TaskLogState>,
+    Path(task_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(context) = BearerTokenExtractor.extract_from_headers(&headers).await else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    if state.authenticator.authenticate(&context).await.is_err() {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0);
+
+    let events = state.hub.stream(&task_id, last_event_id).map(|entry| {
+        Event::default()
+            .id(entry.id.to_string())
+            .event("log")
+            .json_data(&entry)
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
@@ -14,38 +14,74 @@ use tokio::{
     sync::Mutex, // Changed to tokio::sync::Mutex
 };
 use tokio_tungstenite::{
-    MaybeTlsStream, WebSocketStream, connect_async, tungstenite::protocol::Message as WsMessage,
+    MaybeTlsStream, WebSocketStream, connect_async_with_config,
+    tungstenite::{client::IntoClientRequest, protocol::Message as WsMessage},
 };
 use url::Url;
 
 #[cfg(feature = "tracing")]
 use tracing::{debug, trace};
 
+use super::handshake::{WebSocketCredentials, is_accepted_frame};
+use super::options::{
+    EXTENSIONS_HEADER, WebSocketOptions, decode_message, encode_message, extension_header,
+    offers_compression,
+};
+use super::sequence::SEQUENCE_FIELD;
 use crate::{
     adapter::error::WebSocketClientError,
     application::{
         JSONRPCResponse,
-        json_rpc::{self, A2ARequest, SendTaskRequest, TaskResubscriptionRequest},
+        json_rpc::{
+            self, A2ARequest, ListTasksStreamRequest, ListTasksStreamResponse, SendTaskRequest,
+            TaskResubscriptionRequest,
+        },
     },
     domain::{
-        A2AError, Message, Task, TaskArtifactUpdateEvent, TaskIdParams, TaskPushNotificationConfig,
-        TaskQueryParams, TaskSendParams, TaskStatusUpdateEvent,
+        A2AError, ListTasksParams, ListTasksStreamItem, Message, Task, TaskArtifactUpdateEvent,
+        TaskIdParams, TaskPushNotificationConfig, TaskQueryParams, TaskSendParams,
+        TaskSnapshotOptions, TaskStatusUpdateEvent,
+    },
+    port::ListTasksStream,
+    services::{
+        client::{AsyncA2AClient, StreamItem},
+        interceptor::InterceptorChain,
     },
-    services::client::{AsyncA2AClient, StreamItem},
 };
 
 type WebSocketTx = Arc<Mutex<WebSocketStream<MaybeTlsStream<TcpStream>>>>;
 
+/// Items of a subscription together with their sequence numbers
+pub type SequencedStream = Pin<Box<dyn Stream<Item = Result<SequencedItem, A2AError>> + Send>>;
+
+/// A streamed item and the sequence number the server stamped it with
+#[derive(Debug, Clone)]
+pub struct SequencedItem {
+    /// Position of the event in its subscription; `None` for the reply to the
+    /// subscription request itself and for servers that do not number events
+    pub sequence: Option<u64>,
+    /// The streamed item
+    pub item: StreamItem,
+}
+
 /// WebSocket client for interacting with the A2A protocol with streaming support
 pub struct WebSocketClient {
     /// Base WebSocket URL of the A2A API
     base_url: String,
-    /// Authorization token, if any
-    auth_token: Option<String>,
+    /// Credentials presented in the auth handshake, if any
+    credentials: Option<WebSocketCredentials>,
     /// Connection to the WebSocket server
     connection: Option<WebSocketTx>,
     /// Timeout in seconds
     timeout: u64,
+    /// Message size limit and compression settings
+    options: WebSocketOptions,
+    /// Whether the server accepted compression on the current connection
+    compression: bool,
+    /// Task snapshots requested on subscriptions
+    task_snapshots: Option<TaskSnapshotOptions>,
+    /// Interceptors run around every structured request
+    interceptors: InterceptorChain,
 }
 
 impl WebSocketClient {
@@ -53,20 +89,26 @@ impl WebSocketClient {
     pub fn new(base_url: String) -> Self {
         Self {
             base_url,
-            auth_token: None,
+            credentials: None,
             connection: None,
             timeout: 30, // Default timeout in seconds
+            options: WebSocketOptions::default(),
+            compression: false,
+            task_snapshots: None,
+            interceptors: InterceptorChain::new(),
         }
     }
 
-    /// Create a new WebSocket client with authentication
+    /// Create a new WebSocket client that authenticates with a bearer token
     pub fn with_auth(base_url: String, auth_token: String) -> Self {
-        Self {
-            base_url,
-            auth_token: Some(auth_token),
-            connection: None,
-            timeout: 30,
-        }
+        Self::new(base_url).with_credentials(WebSocketCredentials::Bearer(auth_token))
+    }
+
+    /// Authenticate each connection by sending `credentials` in an auth frame
+    /// before any request
+    pub fn with_credentials(mut self, credentials: WebSocketCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
     }
 
     /// Set the timeout for operations
@@ -75,31 +117,118 @@ impl WebSocketClient {
         self
     }
 
+    /// Set the message size limit and compression settings
+    pub fn with_options(mut self, options: WebSocketOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Ask for the updated task with each status update of a subscription
+    /// whenever its history or artifacts change
+    pub fn with_task_snapshots(mut self, options: TaskSnapshotOptions) -> Self {
+        self.task_snapshots = Some(options);
+        self
+    }
+
+    /// Run every structured request through `interceptors`.
+    ///
+    /// Headers added by interceptors are not sent, as WebSocket frames have
+    /// none, and task subscriptions bypass the chain.
+    pub fn with_interceptors(mut self, interceptors: InterceptorChain) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    /// A copy of this client that opens its own connection on first use,
+    /// for reconnecting after the current connection drops
+    pub fn detached(&self) -> Self {
+        let mut client = self.clone();
+        client.connection = None;
+        client.compression = false;
+        client
+    }
+
+    /// Open a connection and complete the auth handshake, then close it.
+    ///
+    /// Checks that the server is reachable and accepts the credentials
+    /// without leaving a connection behind for later requests to share.
+    pub async fn check_connection(&self) -> Result<(), A2AError> {
+        let mut client = self.detached();
+        client.connect().await?;
+        if let Some(connection) = client.connection.take() {
+            let _ = connection.lock().await.close(None).await;
+        }
+        Ok(())
+    }
+
     /// Connect to the WebSocket server
     async fn connect(&mut self) -> Result<(), A2AError> {
         if self.connection.is_some() {
             return Ok(());
         }
 
-        let mut url = Url::parse(&self.base_url)
+        let url = Url::parse(&self.base_url)
             .map_err(|e| WebSocketClientError::Connection(format!("Invalid URL: {}", e)))?;
 
-        // Add auth token to URL if present
-        if let Some(token) = &self.auth_token {
-            url.query_pairs_mut().append_pair("token", token);
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| WebSocketClientError::Connection(format!("Invalid request: {}", e)))?;
+        if self.options.compression {
+            request
+                .headers_mut()
+                .insert(EXTENSIONS_HEADER, extension_header());
         }
 
-        let (ws_stream, _) = connect_async(url).await.map_err(|e| {
-            WebSocketClientError::Connection(format!("WebSocket connection error: {}", e))
-        })?;
+        let (mut ws_stream, response) =
+            connect_async_with_config(request, Some(self.options.protocol_config()), false)
+                .await
+                .map_err(|e| {
+                    WebSocketClientError::Connection(format!("WebSocket connection error: {}", e))
+                })?;
 
+        if let Some(credentials) = &self.credentials {
+            self.authenticate(&mut ws_stream, credentials).await?;
+        }
+
+        self.compression = self.options.compression && offers_compression(response.headers());
         self.connection = Some(Arc::new(Mutex::new(ws_stream)));
         Ok(())
     }
 
+    /// Perform the auth handshake on a fresh connection
+    async fn authenticate(
+        &self,
+        ws_stream: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+        credentials: &WebSocketCredentials,
+    ) -> Result<(), A2AError> {
+        ws_stream
+            .send(WsMessage::Text(credentials.to_frame()))
+            .await
+            .map_err(|e| WebSocketClientError::Message(format!("Send error: {}", e)))?;
+
+        let reply = tokio::time::timeout(Duration::from_secs(self.timeout), ws_stream.next())
+            .await
+            .map_err(|_| WebSocketClientError::Timeout)?;
+        match reply {
+            Some(Ok(WsMessage::Text(text))) if is_accepted_frame(&text) => Ok(()),
+            Some(Ok(WsMessage::Close(Some(frame)))) => Err(WebSocketClientError::Connection(
+                format!("Authentication rejected: {}", frame.reason),
+            )
+            .into()),
+            Some(Err(e)) => {
+                Err(WebSocketClientError::Message(format!("WebSocket error: {}", e)).into())
+            }
+            _ => {
+                Err(WebSocketClientError::Connection("Authentication rejected".to_string()).into())
+            }
+        }
+    }
+
     /// Send a message to the WebSocket server and get a response
-    async fn send_ws_message(&mut self, message: WsMessage) -> Result<WsMessage, A2AError> {
+    async fn send_ws_message(&mut self, text: String) -> Result<WsMessage, A2AError> {
         self.connect().await?;
+        let message = encode_message(text, self.compression, &self.options);
 
         let conn = self
             .connection
@@ -135,7 +264,294 @@ impl WebSocketClient {
             }
         };
 
-        Ok(response)
+        decode_frame(response, self.compression, &self.options)
+    }
+
+    /// Subscribe to a task's updates, keeping the sequence number of each
+    /// event so callers can detect missed and repeated events
+    pub async fn subscribe_sequenced(
+        &self,
+        task_id: &str,
+        history_length: Option<u32>,
+    ) -> Result<SequencedStream, A2AError> {
+        // First connect to ensure we have a connection
+        let mut client_clone = self.clone();
+        client_clone.connect().await?;
+
+        let params = TaskQueryParams {
+            id: task_id.to_string(),
+            history_length,
+            metadata: None,
+            since: None,
+            snapshot: self.task_snapshots.clone(),
+            exclude: Vec::new(),
+        };
+
+        let request = TaskResubscriptionRequest::new(params);
+        let json = json_rpc::serialize_request(&A2ARequest::TaskResubscription(request))?;
+
+        // Get the connection
+        let connection = client_clone
+            .connection
+            .as_ref()
+            .ok_or_else(|| WebSocketClientError::Connection("No connection".to_string()))?
+            .clone();
+
+        // Send the request
+        {
+            let mut guard = connection.lock().await; // Changed to await
+
+            guard
+                .send(encode_message(
+                    json,
+                    client_clone.compression,
+                    &client_clone.options,
+                ))
+                .await
+                .map_err(|e| WebSocketClientError::Message(format!("Send error: {}", e)))?;
+        }
+
+        // Create a stream that will process incoming messages
+        let compression = client_clone.compression;
+        let options = client_clone.options.clone();
+        let stream = futures::stream::unfold(connection, move |conn| {
+            let options = options.clone();
+            Box::pin(async move {
+                // Loop until we get a non-null message or an error
+                loop {
+                    // Get the next message from the WebSocket
+                    let message_result = {
+                        let mut guard = conn.lock().await;
+                        guard.next().await
+                    }; // Lock is dropped here
+                    // Process result outside the lock scope
+                    let message = match message_result {
+                        Some(Ok(msg)) => match decode_frame(msg, compression, &options) {
+                            Ok(msg) => msg,
+                            Err(e) => return Some((Err(e), conn)),
+                        },
+                        Some(Err(e)) => {
+                            return Some((
+                                Err(WebSocketClientError::Message(format!(
+                                    "WebSocket error: {}",
+                                    e
+                                ))
+                                .into()),
+                                conn,
+                            ));
+                        }
+                        None => {
+                            return Some((Err(WebSocketClientError::Closed.into()), conn));
+                        }
+                    };
+
+                    // Process the message
+                    match message {
+                        WsMessage::Text(text) => {
+                            // Add debug logging for received messages
+                            #[cfg(feature = "tracing")]
+                            trace!("Received WebSocket message: {}", text);
+
+                            // Parse the response
+                            let response: Value = match serde_json::from_str(&text) {
+                                Ok(value) => value,
+                                Err(e) => {
+                                    #[cfg(feature = "tracing")]
+                                    debug!("JSON parse error: {}", e);
+                                    return Some((Err(A2AError::JsonParse(e)), conn));
+                                }
+                            };
+
+                            // Check for errors
+                            if let Some(error) = response.get("error")
+                                && error.is_object()
+                            {
+                                let response_clone = response.clone();
+                                let error: JSONRPCResponse =
+                                    match serde_json::from_value(response_clone) {
+                                        Ok(resp) => resp,
+                                        Err(e) => {
+                                            return Some((Err(A2AError::JsonParse(e)), conn));
+                                        }
+                                    };
+
+                                if let Some(err) = error.error {
+                                    return Some((
+                                        Err(A2AError::JsonRpc {
+                                            code: err.code,
+                                            message: err.message,
+                                            data: err.data,
+                                        }),
+                                        conn,
+                                    ));
+                                }
+                            }
+
+                            // Check if it's a valid JSON-RPC message
+                            if response.get("jsonrpc").is_some() && response.get("result").is_some()
+                            {
+                                let result = response.get("result").cloned().unwrap_or(Value::Null);
+                                let sequence = response.get(SEQUENCE_FIELD).and_then(Value::as_u64);
+                                let sequenced = |item| SequencedItem { sequence, item };
+
+                                // If result is null, the task doesn't exist yet - keep streaming
+                                if result.is_null() {
+                                    #[cfg(feature = "tracing")]
+                                    debug!("Task doesn't exist yet, waiting for next message");
+                                    // Skip this message and wait for the next WebSocket message
+                                    continue; // Continue the loop to get the next message
+                                }
+
+                                // Try to parse as an initial Task response first
+                                if let Ok(task) = serde_json::from_value::<Task>(result.clone()) {
+                                    #[cfg(feature = "tracing")]
+                                    debug!("Parsed streaming response as Task");
+                                    return Some((Ok(sequenced(StreamItem::Task(task))), conn));
+                                }
+
+                                // Try to parse as a status update
+                                if let Ok(status_update) =
+                                    serde_json::from_value::<TaskStatusUpdateEvent>(result.clone())
+                                {
+                                    #[cfg(feature = "tracing")]
+                                    debug!("Parsed streaming response as StatusUpdate");
+                                    return Some((
+                                        Ok(sequenced(StreamItem::StatusUpdate(status_update))),
+                                        conn,
+                                    ));
+                                }
+
+                                // Try to parse as an artifact update
+                                if let Ok(artifact_update) =
+                                    serde_json::from_value::<TaskArtifactUpdateEvent>(result)
+                                {
+                                    #[cfg(feature = "tracing")]
+                                    debug!("Parsed streaming response as ArtifactUpdate");
+                                    return Some((
+                                        Ok(sequenced(StreamItem::ArtifactUpdate(artifact_update))),
+                                        conn,
+                                    ));
+                                }
+                            }
+
+                            // If we got here, we couldn't parse the response
+                            #[cfg(feature = "tracing")]
+                            debug!("Failed to parse streaming response");
+                            return Some((
+                                Err(WebSocketClientError::Protocol(
+                                    "Failed to parse streaming response".to_string(),
+                                )
+                                .into()),
+                                conn,
+                            ));
+                        }
+                        _ => {
+                            return Some((
+                                Err(WebSocketClientError::Protocol(
+                                    "Unexpected WebSocket message type".to_string(),
+                                )
+                                .into()),
+                                conn,
+                            ));
+                        }
+                    }; // End of match
+                } // End of loop
+            })
+        });
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Stream the page of tasks `params` lists, one task at a time as the
+    /// server loads them, ending with the listing's summary.
+    ///
+    /// The stream ends after the summary or the first error.
+    pub async fn list_tasks_stream(
+        &self,
+        params: &ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        let mut client = self.clone();
+        client.connect().await?;
+
+        let request = ListTasksStreamRequest::new(Some(params.clone()));
+        let json = json_rpc::serialize_request(&A2ARequest::ListTasksStream(request))?;
+        let connection = client
+            .connection
+            .as_ref()
+            .ok_or_else(|| WebSocketClientError::Connection("No connection".to_string()))?
+            .clone();
+        connection
+            .lock()
+            .await
+            .send(encode_message(json, client.compression, &client.options))
+            .await
+            .map_err(|e| WebSocketClientError::Message(format!("Send error: {}", e)))?;
+
+        let compression = client.compression;
+        let options = client.options;
+        let stream = futures::stream::unfold(Some(connection), move |conn| {
+            let options = options.clone();
+            Box::pin(async move {
+                let conn = conn?;
+                let item = loop {
+                    let message = conn.lock().await.next().await;
+                    break match message {
+                        Some(Ok(WsMessage::Ping(_) | WsMessage::Pong(_))) => continue,
+                        Some(Ok(msg)) => {
+                            decode_frame(msg, compression, &options).and_then(read_listing_frame)
+                        }
+                        Some(Err(e)) => Err(WebSocketClientError::Message(format!(
+                            "WebSocket error: {}",
+                            e
+                        ))
+                        .into()),
+                        None => Err(WebSocketClientError::Closed.into()),
+                    };
+                };
+                // Only a task is followed by more frames
+                let next = match item {
+                    Ok(ListTasksStreamItem::Task(_)) => Some(conn),
+                    _ => None,
+                };
+                Some((item, next))
+            })
+        });
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Read one frame of a tasks/listStream response
+fn read_listing_frame(message: WsMessage) -> Result<ListTasksStreamItem, A2AError> {
+    let WsMessage::Text(text) = message else {
+        return Err(WebSocketClientError::Protocol(
+            "Unexpected WebSocket message type".to_string(),
+        )
+        .into());
+    };
+    let response: ListTasksStreamResponse = serde_json::from_str(&text)?;
+    match (response.result, response.error) {
+        (_, Some(error)) => Err(A2AError::JsonRpc {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        }),
+        (Some(item), None) => Ok(item),
+        (None, None) => Err(A2AError::Internal("Empty response".to_string())),
+    }
+}
+
+/// Decompress a received binary frame when compression was negotiated
+fn decode_frame(
+    message: WsMessage,
+    compression: bool,
+    options: &WebSocketOptions,
+) -> Result<WsMessage, A2AError> {
+    match message {
+        WsMessage::Binary(data) if compression => {
+            Ok(WsMessage::Text(decode_message(&data, options)?))
+        }
+        other => Ok(other),
     }
 }
 
@@ -143,12 +559,19 @@ impl WebSocketClient {
 impl AsyncA2AClient for WebSocketClient {
     async fn send_raw_request<'a>(&self, request: &'a str) -> Result<String, A2AError> {
         let mut client = self.clone();
-        let response = client
-            .send_ws_message(WsMessage::Text(request.to_string()))
-            .await?;
+        let response = client.send_ws_message(request.to_string()).await?;
 
         match response {
             WsMessage::Text(text) => Ok(text),
+            WsMessage::Close(frame) => Err(WebSocketClientError::Connection(match frame {
+                Some(frame) => format!(
+                    "Closed by server ({}): {}",
+                    u16::from(frame.code),
+                    frame.reason
+                ),
+                None => "Closed by server".to_string(),
+            })
+            .into()),
             _ => Err(A2AError::Internal(
                 "Unexpected WebSocket message type".to_string(),
             )),
@@ -156,10 +579,14 @@ impl AsyncA2AClient for WebSocketClient {
     }
 
     async fn send_request<'a>(&self, request: &'a A2ARequest) -> Result<JSONRPCResponse, A2AError> {
-        let json = json_rpc::serialize_request(request)?;
-        let response_text = self.send_raw_request(&json).await?;
-        let response: JSONRPCResponse = serde_json::from_str(&response_text)?;
-        Ok(response)
+        self.interceptors
+            .run(request.clone(), |request| async move {
+                let json = json_rpc::serialize_request(&request.request)?;
+                let response_text = self.send_raw_request(&json).await?;
+                let response: JSONRPCResponse = serde_json::from_str(&response_text)?;
+                Ok(response)
+            })
+            .await
     }
 
     async fn send_task_message<'a>(
@@ -176,6 +603,7 @@ impl AsyncA2AClient for WebSocketClient {
             push_notification: None,
             history_length,
             metadata: None,
+            expected_version: None,
         };
 
         let request = SendTaskRequest::new(params);
@@ -209,6 +637,9 @@ impl AsyncA2AClient for WebSocketClient {
             id: task_id.to_string(),
             history_length,
             metadata: None,
+            since: None,
+            snapshot: None,
+            exclude: Vec::new(),
         };
 
         let request = json_rpc::GetTaskRequest::new(params);
@@ -233,6 +664,8 @@ impl AsyncA2AClient for WebSocketClient {
         let params = TaskIdParams {
             id: task_id.to_string(),
             metadata: None,
+            expected_version: None,
+            reason: None,
         };
 
         let request = json_rpc::CancelTaskRequest::new(params);
@@ -284,6 +717,8 @@ impl AsyncA2AClient for WebSocketClient {
         let params = TaskIdParams {
             id: task_id.to_string(),
             metadata: None,
+            expected_version: None,
+            reason: None,
         };
 
         let request = json_rpc::GetTaskPushNotificationRequest::new(params);
@@ -445,177 +880,8 @@ impl AsyncA2AClient for WebSocketClient {
         task_id: &'a str,
         history_length: Option<u32>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamItem, A2AError>> + Send>>, A2AError> {
-        // First connect to ensure we have a connection
-        let mut client_clone = self.clone();
-        client_clone.connect().await?;
-
-        let params = TaskQueryParams {
-            id: task_id.to_string(),
-            history_length,
-            metadata: None,
-        };
-
-        let request = TaskResubscriptionRequest::new(params);
-        let json = json_rpc::serialize_request(&A2ARequest::TaskResubscription(request))?;
-
-        // Get the connection
-        let connection = client_clone
-            .connection
-            .as_ref()
-            .ok_or_else(|| WebSocketClientError::Connection("No connection".to_string()))?
-            .clone();
-
-        // Send the request
-        {
-            let mut guard = connection.lock().await; // Changed to await
-
-            guard
-                .send(WsMessage::Text(json))
-                .await
-                .map_err(|e| WebSocketClientError::Message(format!("Send error: {}", e)))?;
-        }
-
-        // Create a stream that will process incoming messages
-        let stream = futures::stream::unfold(connection, move |conn| {
-            Box::pin(async move {
-                // Loop until we get a non-null message or an error
-                loop {
-                    // Get the next message from the WebSocket
-                    let message_result = {
-                        let mut guard = conn.lock().await;
-                        guard.next().await
-                    }; // Lock is dropped here
-                    // Process result outside the lock scope
-                    let message = match message_result {
-                        Some(Ok(msg)) => msg,
-                        Some(Err(e)) => {
-                            return Some((
-                                Err(WebSocketClientError::Message(format!(
-                                    "WebSocket error: {}",
-                                    e
-                                ))
-                                .into()),
-                                conn,
-                            ));
-                        }
-                        None => {
-                            return Some((Err(WebSocketClientError::Closed.into()), conn));
-                        }
-                    };
-
-                    // Process the message
-                    match message {
-                        WsMessage::Text(text) => {
-                            // Add debug logging for received messages
-                            #[cfg(feature = "tracing")]
-                            trace!("Received WebSocket message: {}", text);
-
-                            // Parse the response
-                            let response: Value = match serde_json::from_str(&text) {
-                                Ok(value) => value,
-                                Err(e) => {
-                                    #[cfg(feature = "tracing")]
-                                    debug!("JSON parse error: {}", e);
-                                    return Some((Err(A2AError::JsonParse(e)), conn));
-                                }
-                            };
-
-                            // Check for errors
-                            if let Some(error) = response.get("error")
-                                && error.is_object()
-                            {
-                                let response_clone = response.clone();
-                                let error: JSONRPCResponse =
-                                    match serde_json::from_value(response_clone) {
-                                        Ok(resp) => resp,
-                                        Err(e) => {
-                                            return Some((Err(A2AError::JsonParse(e)), conn));
-                                        }
-                                    };
-
-                                if let Some(err) = error.error {
-                                    return Some((
-                                        Err(A2AError::JsonRpc {
-                                            code: err.code,
-                                            message: err.message,
-                                            data: err.data,
-                                        }),
-                                        conn,
-                                    ));
-                                }
-                            }
-
-                            // Check if it's a valid JSON-RPC message
-                            if response.get("jsonrpc").is_some() && response.get("result").is_some()
-                            {
-                                let result = response.get("result").cloned().unwrap_or(Value::Null);
-
-                                // If result is null, the task doesn't exist yet - keep streaming
-                                if result.is_null() {
-                                    #[cfg(feature = "tracing")]
-                                    debug!("Task doesn't exist yet, waiting for next message");
-                                    // Skip this message and wait for the next WebSocket message
-                                    continue; // Continue the loop to get the next message
-                                }
-
-                                // Try to parse as an initial Task response first
-                                if let Ok(task) = serde_json::from_value::<Task>(result.clone()) {
-                                    #[cfg(feature = "tracing")]
-                                    debug!("Parsed streaming response as Task");
-                                    return Some((Ok(StreamItem::Task(task)), conn));
-                                }
-
-                                // Try to parse as a status update
-                                if let Ok(status_update) =
-                                    serde_json::from_value::<TaskStatusUpdateEvent>(result.clone())
-                                {
-                                    #[cfg(feature = "tracing")]
-                                    debug!("Parsed streaming response as StatusUpdate");
-                                    return Some((
-                                        Ok(StreamItem::StatusUpdate(status_update)),
-                                        conn,
-                                    ));
-                                }
-
-                                // Try to parse as an artifact update
-                                if let Ok(artifact_update) =
-                                    serde_json::from_value::<TaskArtifactUpdateEvent>(result)
-                                {
-                                    #[cfg(feature = "tracing")]
-                                    debug!("Parsed streaming response as ArtifactUpdate");
-                                    return Some((
-                                        Ok(StreamItem::ArtifactUpdate(artifact_update)),
-                                        conn,
-                                    ));
-                                }
-                            }
-
-                            // If we got here, we couldn't parse the response
-                            #[cfg(feature = "tracing")]
-                            debug!("Failed to parse streaming response");
-                            return Some((
-                                Err(WebSocketClientError::Protocol(
-                                    "Failed to parse streaming response".to_string(),
-                                )
-                                .into()),
-                                conn,
-                            ));
-                        }
-                        _ => {
-                            return Some((
-                                Err(WebSocketClientError::Protocol(
-                                    "Unexpected WebSocket message type".to_string(),
-                                )
-                                .into()),
-                                conn,
-                            ));
-                        }
-                    }; // End of match
-                } // End of loop
-            })
-        });
-
-        Ok(Box::pin(stream))
+        let stream = self.subscribe_sequenced(task_id, history_length).await?;
+        Ok(Box::pin(stream.map(|item| item.map(|item| item.item))))
     }
 }
 
@@ -623,9 +889,13 @@ impl Clone for WebSocketClient {
     fn clone(&self) -> Self {
         Self {
             base_url: self.base_url.clone(),
-            auth_token: self.auth_token.clone(),
+            credentials: self.credentials.clone(),
             connection: self.connection.clone(),
             timeout: self.timeout,
+            options: self.options.clone(),
+            compression: self.compression,
+            task_snapshots: self.task_snapshots.clone(),
+            interceptors: self.interceptors.clone(),
         }
     }
 }
@@ -0,0 +1,376 @@
+//! Per-task update sources shared by a server's WebSocket subscriptions
+//!
+//! Every `tasks/sendSubscribe` or `tasks/resubscribe` joins the source for its
+//! task, which the first subscription to the task registers with the
+//! streaming handler and the last one to leave deregisters. Subscriptions
+//! asking for different task snapshots get separate sources, since the
+//! handler shapes each status update for what its subscriber asked. A
+//! subscription joining late starts from the latest status update and the
+//! artifacts the source has seen, then follows its updates.
+
+// This module is already conditionally compiled with #[cfg(feature = "ws-server")] in mod.rs
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, Weak},
+};
+
+use async_trait::async_trait;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+#[cfg(feature = "tracing")]
+use tracing::{debug, warn};
+
+use crate::{
+    domain::{A2AError, TaskArtifactUpdateEvent, TaskSnapshotOptions, TaskStatusUpdateEvent},
+    port::{AsyncStreamingHandler, streaming_handler::Subscriber},
+};
+
+/// Updates buffered for each subscription; one falling further behind is
+/// resynchronized from the source's latest state
+const SUBSCRIBER_BUFFER: usize = 64;
+
+/// A task ID and the snapshots its subscriptions asked for
+type SourceKey = (String, Option<TaskSnapshotOptions>);
+
+type Sources = Arc<Mutex<HashMap<SourceKey, Arc<Source>>>>;
+
+/// An update fanned out by a task source
+#[derive(Debug, Clone)]
+pub(crate) enum SourceEvent {
+    Status(TaskStatusUpdateEvent),
+    Artifact(TaskArtifactUpdateEvent),
+}
+
+/// Updates dropped because a subscription fell behind; the latest state
+/// follows
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Missed(pub u64);
+
+/// The task sources of one server
+pub(crate) struct TaskSources<S>
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    streaming_handler: Arc<S>,
+    sources: Sources,
+}
+
+impl<S> Clone for TaskSources<S>
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            streaming_handler: self.streaming_handler.clone(),
+            sources: self.sources.clone(),
+        }
+    }
+}
+
+impl<S> TaskSources<S>
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    /// Create sources registered with `streaming_handler`
+    pub(crate) fn new(streaming_handler: Arc<S>) -> Self {
+        Self {
+            streaming_handler,
+            sources: Arc::default(),
+        }
+    }
+
+    /// The number of sources currently open
+    pub(crate) fn len(&self) -> usize {
+        self.sources.lock().unwrap().len()
+    }
+
+    /// Join the source of `task_id` for `task_snapshots`, opening it if no
+    /// subscription is following the task with those snapshots yet
+    pub(crate) async fn join(
+        &self,
+        task_id: &str,
+        task_snapshots: Option<TaskSnapshotOptions>,
+    ) -> SourceSubscription<S> {
+        let key = (task_id.to_string(), task_snapshots);
+        let (source, opened, pending, receiver) = {
+            let mut sources = self.sources.lock().unwrap();
+            let (source, opened) = match sources.get(&key) {
+                Some(source) => (source.clone(), false),
+                None => {
+                    let source = Arc::new(Source::new());
+                    sources.insert(key.clone(), source.clone());
+                    (source, true)
+                }
+            };
+            let mut state = source.state.lock().unwrap();
+            state.subscriptions += 1;
+            let pending = state.replay();
+            let receiver = state.sender.subscribe();
+            drop(state);
+            (source, opened, pending, receiver)
+        };
+
+        if opened {
+            self.register(&key, &source).await;
+        }
+
+        SourceSubscription {
+            key,
+            source,
+            sources: self.sources.clone(),
+            streaming_handler: self.streaming_handler.clone(),
+            pending,
+            receiver,
+        }
+    }
+
+    /// Register the subscribers feeding `source` with the streaming handler
+    async fn register(&self, key: &SourceKey, source: &Arc<Source>) {
+        #[cfg(feature = "tracing")]
+        debug!("Opening shared source for task {}", key.0);
+
+        let forwarder = || Forwarder {
+            source: Arc::downgrade(source),
+            task_snapshots: key.1.clone(),
+        };
+        let mut registrations = Vec::new();
+        match self
+            .streaming_handler
+            .add_status_subscriber(&key.0, Box::new(forwarder()))
+            .await
+        {
+            Ok(id) => registrations.push(id),
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                warn!("Error adding status subscriber: {}", e);
+                #[cfg(not(feature = "tracing"))]
+                eprintln!("Error adding status subscriber: {}", e);
+            }
+        }
+        match self
+            .streaming_handler
+            .add_artifact_subscriber(&key.0, Box::new(forwarder()))
+            .await
+        {
+            Ok(id) => registrations.push(id),
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                warn!("Error adding artifact subscriber: {}", e);
+                #[cfg(not(feature = "tracing"))]
+                eprintln!("Error adding artifact subscriber: {}", e);
+            }
+        }
+
+        // Every subscription may have left while registering
+        let mut state = source.state.lock().unwrap();
+        if state.retired {
+            drop(state);
+            deregister(self.streaming_handler.clone(), registrations);
+        } else {
+            state.registrations = registrations;
+        }
+    }
+}
+
+/// Remove a source's subscribers from the streaming handler.
+///
+/// A handler unable to remove them keeps calling them, but they do nothing
+/// once their source is gone.
+fn deregister<S>(streaming_handler: Arc<S>, registrations: Vec<String>)
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    if registrations.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        for id in registrations {
+            if let Err(_e) = streaming_handler.remove_subscription(&id).await {
+                #[cfg(feature = "tracing")]
+                debug!("Leaving subscription {} registered: {}", id, _e);
+            }
+        }
+    });
+}
+
+/// A task's updates and the subscriptions sharing them
+struct Source {
+    state: Mutex<SourceState>,
+}
+
+struct SourceState {
+    /// Fans each update out to every subscription
+    sender: broadcast::Sender<SourceEvent>,
+    /// The latest status update
+    last_status: Option<TaskStatusUpdateEvent>,
+    /// Each artifact as of its latest update
+    artifacts: Vec<TaskArtifactUpdateEvent>,
+    /// How many subscriptions share the source
+    subscriptions: usize,
+    /// IDs of the subscribers feeding the source
+    registrations: Vec<String>,
+    /// Whether the last subscription has left
+    retired: bool,
+}
+
+impl Source {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        Self {
+            state: Mutex::new(SourceState {
+                sender,
+                last_status: None,
+                artifacts: Vec::new(),
+                subscriptions: 0,
+                registrations: Vec::new(),
+                retired: false,
+            }),
+        }
+    }
+}
+
+impl SourceState {
+    /// Fold an update into the state replayed to late subscriptions
+    fn record(&mut self, event: &SourceEvent) {
+        match event {
+            SourceEvent::Status(update) => self.last_status = Some(update.clone()),
+            SourceEvent::Artifact(update) => {
+                let existing = self
+                    .artifacts
+                    .iter_mut()
+                    .find(|seen| seen.artifact.artifact_id == update.artifact.artifact_id);
+                match existing {
+                    Some(existing) if update.append == Some(true) => {
+                        existing
+                            .artifact
+                            .parts
+                            .extend(update.artifact.parts.iter().cloned());
+                        existing.last_chunk = update.last_chunk;
+                    }
+                    Some(existing) => {
+                        *existing = update.clone();
+                        existing.append = None;
+                    }
+                    None => {
+                        let mut update = update.clone();
+                        update.append = None;
+                        self.artifacts.push(update);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The updates bringing a joining subscription up to date
+    fn replay(&self) -> VecDeque<SourceEvent> {
+        self.last_status
+            .iter()
+            .cloned()
+            .map(SourceEvent::Status)
+            .chain(self.artifacts.iter().cloned().map(SourceEvent::Artifact))
+            .collect()
+    }
+}
+
+/// The subscriber registered with the streaming handler for a source
+struct Forwarder {
+    source: Weak<Source>,
+    task_snapshots: Option<TaskSnapshotOptions>,
+}
+
+impl Forwarder {
+    fn forward(&self, event: SourceEvent) {
+        let Some(source) = self.source.upgrade() else {
+            return;
+        };
+        let mut state = source.state.lock().unwrap();
+        state.record(&event);
+        // Nobody listening only means every subscription just left
+        let _ = state.sender.send(event);
+    }
+}
+
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for Forwarder {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        self.forward(SourceEvent::Status(update));
+        Ok(())
+    }
+
+    fn task_snapshots(&self) -> Option<TaskSnapshotOptions> {
+        self.task_snapshots.clone()
+    }
+}
+
+#[async_trait]
+impl Subscriber<TaskArtifactUpdateEvent> for Forwarder {
+    async fn on_update(&self, update: TaskArtifactUpdateEvent) -> Result<(), A2AError> {
+        self.forward(SourceEvent::Artifact(update));
+        Ok(())
+    }
+}
+
+/// One subscription's place in a shared source
+pub(crate) struct SourceSubscription<S>
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    key: SourceKey,
+    source: Arc<Source>,
+    sources: Sources,
+    streaming_handler: Arc<S>,
+    /// Updates to deliver before reading the shared ones
+    pending: VecDeque<SourceEvent>,
+    receiver: broadcast::Receiver<SourceEvent>,
+}
+
+impl<S> SourceSubscription<S>
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    /// The next update, or `None` once the source is gone
+    pub(crate) async fn next(&mut self) -> Option<Result<SourceEvent, Missed>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(Ok(event));
+        }
+        match self.receiver.recv().await {
+            Ok(event) => Some(Ok(event)),
+            // Skip what was missed and start again from the latest state
+            Err(RecvError::Lagged(missed)) => {
+                let state = self.source.state.lock().unwrap();
+                self.pending = state.replay();
+                self.receiver = state.sender.subscribe();
+                Some(Err(Missed(missed)))
+            }
+            Err(RecvError::Closed) => None,
+        }
+    }
+}
+
+impl<S> Drop for SourceSubscription<S>
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        let mut sources = self.sources.lock().unwrap();
+        let mut state = self.source.state.lock().unwrap();
+        state.subscriptions -= 1;
+        if state.subscriptions > 0 {
+            return;
+        }
+        state.retired = true;
+        let registrations = std::mem::take(&mut state.registrations);
+        drop(state);
+        if sources
+            .get(&self.key)
+            .is_some_and(|current| Arc::ptr_eq(current, &self.source))
+        {
+            #[cfg(feature = "tracing")]
+            debug!("Closing shared source for task {}", self.key.0);
+            sources.remove(&self.key);
+        }
+        drop(sources);
+        deregister(self.streaming_handler.clone(), registrations);
+    }
+}
@@ -0,0 +1,119 @@
+//! Authentication handshake shared by the WebSocket client and server
+//!
+//! Browsers cannot set an `Authorization` header on a WebSocket upgrade, so a
+//! server with an authenticator expects the first message on every connection
+//! to be an auth frame:
+//!
+//! ```json
+//! {"type": "auth", "token": "<bearer token>"}
+//! {"type": "auth", "apiKey": "<api key>"}
+//! ```
+//!
+//! The server answers `{"type": "auth", "status": "ok"}` and only then handles
+//! RPCs and subscriptions. A socket that sends anything else, an invalid
+//! credential, or nothing within the handshake timeout is closed with a
+//! policy-violation close code.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ws-server")]
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, frame::coding::CloseCode};
+
+#[cfg(feature = "ws-server")]
+use crate::port::AuthContext;
+
+/// Value of the `type` field of handshake frames
+const AUTH_FRAME_TYPE: &str = "auth";
+
+/// Credential presented in the WebSocket auth handshake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebSocketCredentials {
+    /// A bearer token, checked like an `Authorization: Bearer` header
+    Bearer(String),
+    /// An API key
+    ApiKey(String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthFrame {
+    #[serde(rename = "type")]
+    frame_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+}
+
+impl AuthFrame {
+    fn new() -> Self {
+        Self {
+            frame_type: AUTH_FRAME_TYPE.to_string(),
+            token: None,
+            api_key: None,
+            status: None,
+        }
+    }
+}
+
+impl WebSocketCredentials {
+    /// The auth frame presenting these credentials
+    #[cfg(feature = "ws-client")]
+    pub(crate) fn to_frame(&self) -> String {
+        let mut frame = AuthFrame::new();
+        match self {
+            Self::Bearer(token) => frame.token = Some(token.clone()),
+            Self::ApiKey(key) => frame.api_key = Some(key.clone()),
+        }
+        serde_json::to_string(&frame).expect("auth frame serializes")
+    }
+
+    /// Read the credentials from an auth frame
+    #[cfg(feature = "ws-server")]
+    pub(crate) fn from_frame(text: &str) -> Option<Self> {
+        let frame: AuthFrame = serde_json::from_str(text).ok()?;
+        if frame.frame_type != AUTH_FRAME_TYPE {
+            return None;
+        }
+        match (frame.token, frame.api_key) {
+            (Some(token), None) => Some(Self::Bearer(token)),
+            (None, Some(key)) => Some(Self::ApiKey(key)),
+            _ => None,
+        }
+    }
+
+    /// The context handed to the server's authenticator
+    #[cfg(feature = "ws-server")]
+    pub(crate) fn to_auth_context(&self) -> AuthContext {
+        match self {
+            Self::Bearer(token) => AuthContext::new("bearer".to_string(), token.clone()),
+            Self::ApiKey(key) => AuthContext::new("apikey".to_string(), key.clone()),
+        }
+    }
+}
+
+/// Frame acknowledging a successful handshake
+#[cfg(feature = "ws-server")]
+pub(crate) fn accepted_frame() -> String {
+    let mut frame = AuthFrame::new();
+    frame.status = Some("ok".to_string());
+    serde_json::to_string(&frame).expect("auth frame serializes")
+}
+
+/// Whether a message acknowledges a successful handshake
+#[cfg(feature = "ws-client")]
+pub(crate) fn is_accepted_frame(text: &str) -> bool {
+    serde_json::from_str::<AuthFrame>(text).is_ok_and(|frame| {
+        frame.frame_type == AUTH_FRAME_TYPE && frame.status.as_deref() == Some("ok")
+    })
+}
+
+/// Close frame for a socket that failed or skipped the handshake
+#[cfg(feature = "ws-server")]
+pub(crate) fn rejected(reason: &'static str) -> CloseFrame<'static> {
+    CloseFrame {
+        code: CloseCode::Policy,
+        reason: reason.into(),
+    }
+}
@@ -3,12 +3,22 @@
 #[cfg(feature = "ws-client")]
 pub mod client;
 
+#[cfg(feature = "ws-server")]
+mod fanout;
+pub mod handshake;
+pub mod options;
+pub mod sequence;
+
 #[cfg(feature = "ws-server")]
 pub mod server;
 
 // Re-export WebSocket implementations
 #[cfg(feature = "ws-client")]
-pub use client::WebSocketClient;
+pub use client::{SequencedItem, SequencedStream, WebSocketClient};
+
+pub use handshake::WebSocketCredentials;
+pub use options::{DEFLATE_EXTENSION, WebSocketOptions};
+pub use sequence::{SEQUENCE_FIELD, SequenceCheck, SequenceTracker};
 
 #[cfg(feature = "ws-server")]
 pub use server::WebSocketServer;
@@ -0,0 +1,196 @@
+//! Message size limits and compression shared by the WebSocket client and server
+//!
+//! Compression is negotiated during the handshake: the client offers the
+//! [`DEFLATE_EXTENSION`] token in `Sec-WebSocket-Extensions` and the server echoes
+//! it when it agrees. Once negotiated, either side may send a message as a binary
+//! frame holding the raw DEFLATE encoding of the JSON text, compressed per message
+//! with no shared context (the same stream format as `permessage-deflate` with
+//! `no_context_takeover`). Text frames stay valid, so small messages are sent
+//! uncompressed. The transport's WebSocket library rejects frames with reserved
+//! bits set, which rules out the RFC 7692 framing itself; the dedicated token keeps
+//! peers that speak real `permessage-deflate` from misreading these frames.
+
+#[cfg(feature = "ws-server")]
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, frame::coding::CloseCode};
+use tokio_tungstenite::tungstenite::{
+    self,
+    http::{HeaderMap, HeaderValue},
+    protocol::WebSocketConfig,
+};
+
+use std::time::Duration;
+
+use crate::domain::A2AError;
+
+/// Extension token used to negotiate per-message DEFLATE compression
+pub const DEFLATE_EXTENSION: &str = "a2a-deflate";
+
+/// Header carrying WebSocket extension offers and acceptances
+pub(crate) const EXTENSIONS_HEADER: &str = "sec-websocket-extensions";
+
+/// DEFLATE level used for outgoing messages
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// Size limits and compression settings for a WebSocket connection
+#[derive(Debug, Clone)]
+pub struct WebSocketOptions {
+    /// Largest frame or message accepted, before and after decompression
+    pub max_message_size: usize,
+    /// Whether to negotiate compression
+    pub compression: bool,
+    /// Messages shorter than this are sent uncompressed
+    pub compression_threshold: usize,
+    /// How long a server with an authenticator waits for the auth frame
+    pub auth_timeout: Duration,
+}
+
+impl Default for WebSocketOptions {
+    fn default() -> Self {
+        Self {
+            max_message_size: 16 << 20,
+            compression: true,
+            compression_threshold: 1024,
+            auth_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl WebSocketOptions {
+    /// Create options with a 16 MiB message limit, compression enabled and a
+    /// five second auth handshake timeout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the largest accepted frame or message in bytes
+    pub fn with_max_message_size(mut self, bytes: usize) -> Self {
+        self.max_message_size = bytes;
+        self
+    }
+
+    /// Enable or disable compression negotiation
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Only compress messages of at least `bytes` bytes
+    pub fn with_compression_threshold(mut self, bytes: usize) -> Self {
+        self.compression_threshold = bytes;
+        self
+    }
+
+    /// Close connections that have not authenticated within `timeout`
+    pub fn with_auth_timeout(mut self, timeout: Duration) -> Self {
+        self.auth_timeout = timeout;
+        self
+    }
+
+    /// Protocol configuration enforcing the size limit on incoming data
+    pub(crate) fn protocol_config(&self) -> WebSocketConfig {
+        WebSocketConfig {
+            max_message_size: Some(self.max_message_size),
+            max_frame_size: Some(self.max_message_size),
+            ..WebSocketConfig::default()
+        }
+    }
+}
+
+/// Whether a set of handshake headers lists the compression extension
+pub(crate) fn offers_compression(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(EXTENSIONS_HEADER)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|extension| {
+            extension
+                .split(';')
+                .next()
+                .is_some_and(|name| name.trim().eq_ignore_ascii_case(DEFLATE_EXTENSION))
+        })
+}
+
+/// Header value offering or accepting the compression extension
+pub(crate) fn extension_header() -> HeaderValue {
+    HeaderValue::from_static(DEFLATE_EXTENSION)
+}
+
+/// Encode an outgoing message, compressing it when negotiated and large enough
+pub(crate) fn encode_message(
+    text: String,
+    negotiated: bool,
+    options: &WebSocketOptions,
+) -> tungstenite::Message {
+    if negotiated && text.len() >= options.compression_threshold {
+        tungstenite::Message::Binary(miniz_oxide::deflate::compress_to_vec(
+            text.as_bytes(),
+            COMPRESSION_LEVEL,
+        ))
+    } else {
+        tungstenite::Message::Text(text)
+    }
+}
+
+/// Decode an incoming compressed message, refusing output beyond the size limit
+pub(crate) fn decode_message(
+    data: &[u8],
+    options: &WebSocketOptions,
+) -> Result<String, MessageError> {
+    let bytes = miniz_oxide::inflate::decompress_to_vec_with_limit(data, options.max_message_size)
+        .map_err(|e| match e.status {
+            miniz_oxide::inflate::TINFLStatus::HasMoreOutput => MessageError::TooLarge,
+            _ => MessageError::Invalid(format!("Invalid compressed message: {}", e)),
+        })?;
+    String::from_utf8(bytes)
+        .map_err(|_| MessageError::Invalid("Compressed message is not UTF-8".to_string()))
+}
+
+/// Why an incoming message was refused
+#[derive(Debug)]
+pub(crate) enum MessageError {
+    /// The message exceeds the configured size limit
+    TooLarge,
+    /// The message could not be decoded
+    Invalid(String),
+}
+
+#[cfg(feature = "ws-server")]
+impl MessageError {
+    /// The close frame sent to a peer whose message was refused
+    pub(crate) fn close_frame(&self) -> CloseFrame<'static> {
+        match self {
+            MessageError::TooLarge => policy_violation(),
+            MessageError::Invalid(message) => CloseFrame {
+                code: CloseCode::Invalid,
+                reason: message.clone().into(),
+            },
+        }
+    }
+}
+
+impl From<MessageError> for A2AError {
+    fn from(error: MessageError) -> Self {
+        match error {
+            MessageError::TooLarge => {
+                A2AError::Internal("WebSocket message exceeds the size limit".to_string())
+            }
+            MessageError::Invalid(message) => A2AError::Internal(format!("WebSocket {}", message)),
+        }
+    }
+}
+
+/// Close frame for a message over the size limit
+#[cfg(feature = "ws-server")]
+pub(crate) fn policy_violation() -> CloseFrame<'static> {
+    CloseFrame {
+        code: CloseCode::Policy,
+        reason: "Message exceeds the size limit".into(),
+    }
+}
+
+/// Whether a protocol error means the peer exceeded the size limit
+#[cfg(feature = "ws-server")]
+pub(crate) fn is_capacity_error(error: &tungstenite::Error) -> bool {
+    matches!(error, tungstenite::Error::Capacity(_))
+}
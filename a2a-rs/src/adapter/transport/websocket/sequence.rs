@@ -0,0 +1,63 @@
+//! Ordering of events on a WebSocket subscription
+//!
+//! The server stamps every event frame of a subscription with a sequence
+//! number in [`SEQUENCE_FIELD`], starting at 1 and increasing by one per event.
+//! It is local to the subscription: a new subscription, including one made
+//! after a reconnect, starts again at 1. Resuming from the server's event log
+//! uses the log's own sequence (the SSE `Last-Event-ID`), which is unrelated.
+
+/// Top-level frame field carrying an event's subscription sequence number
+pub const SEQUENCE_FIELD: &str = "sequence";
+
+/// How a received sequence number relates to the ones seen before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// The next event in order
+    InOrder,
+    /// An event already seen, or one that arrived after later events
+    Duplicate,
+    /// Events between the last one seen and this one were missed
+    Gap {
+        /// The sequence number that should have come next
+        expected: u64,
+        /// The sequence number that arrived instead
+        received: u64,
+    },
+}
+
+/// Tracks the sequence numbers of one subscription
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    last: u64,
+}
+
+impl SequenceTracker {
+    /// Create a tracker that expects sequence 1 next
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `sequence` and report how it relates to the ones before.
+    ///
+    /// After a gap, tracking continues from the received number.
+    pub fn observe(&mut self, sequence: u64) -> SequenceCheck {
+        let expected = self.last + 1;
+        if sequence < expected {
+            return SequenceCheck::Duplicate;
+        }
+        self.last = sequence;
+        if sequence == expected {
+            SequenceCheck::InOrder
+        } else {
+            SequenceCheck::Gap {
+                expected,
+                received: sequence,
+            }
+        }
+    }
+
+    /// Forget the numbers seen so far, for a new subscription
+    pub fn reset(&mut self) {
+        self.last = 0;
+    }
+}
@@ -2,24 +2,42 @@
 
 // This module is already conditionally compiled with #[cfg(feature = "ws-server")] in mod.rs
 
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use futures::{SinkExt, StreamExt};
+use futures::{SinkExt, Stream, StreamExt};
 use serde_json::{Value, json};
 use tokio::{
     net::{TcpListener, TcpStream},
     sync::{Mutex, mpsc}, // Changed to tokio::sync::Mutex
 };
-use tokio_tungstenite::{accept_async, tungstenite::Message as WsMessage};
+use tokio_tungstenite::{
+    accept_hdr_async_with_config,
+    tungstenite::{
+        self, Message as WsMessage,
+        handshake::server::{Request, Response},
+        protocol::CloseFrame,
+    },
+};
 
 #[cfg(feature = "tracing")]
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
+use super::fanout::{Missed, SourceEvent, SourceSubscription, TaskSources};
+use super::handshake::{WebSocketCredentials, accepted_frame, rejected};
+use super::options::{
+    EXTENSIONS_HEADER, WebSocketOptions, decode_message, encode_message, extension_header,
+    is_capacity_error, offers_compression, policy_violation,
+};
+use super::sequence::SEQUENCE_FIELD;
 use crate::{
-    adapter::{auth::NoopAuthenticator, error::WebSocketServerError},
-    domain::{A2AError, TaskArtifactUpdateEvent, TaskStatusUpdateEvent},
-    port::{AsyncStreamingHandler, Authenticator, streaming_handler::Subscriber},
+    adapter::{
+        auth::{MethodAccessPolicy, NoopAuthenticator},
+        error::WebSocketServerError,
+    },
+    application::json_rpc::ListTasksStreamRequest,
+    domain::{A2AError, Task, TaskArtifactUpdateEvent, TaskSnapshotOptions, TaskStatusUpdateEvent},
+    port::{AsyncStreamingHandler, AuthPrincipal, Authenticator, streaming_handler::Subscriber},
     services::server::{AgentInfoProvider, AsyncA2ARequestProcessor},
 };
 
@@ -37,14 +55,18 @@ where
     processor: Arc<P>,
     /// Agent info provider
     _agent_info: Arc<A>,
-    /// Streaming handler
-    streaming_handler: Arc<S>,
+    /// Per-task update sources shared by subscriptions
+    sources: TaskSources<S>,
     /// Server address
     address: String,
     /// Connected clients
     clients: ClientMap,
     /// Authenticator
     authenticator: Option<Arc<Auth>>,
+    /// Methods each auth role may call
+    method_access: Option<Arc<MethodAccessPolicy>>,
+    /// Message size limit and compression settings
+    options: WebSocketOptions,
 }
 
 impl<P, A, S> WebSocketServer<P, A, S>
@@ -58,10 +80,12 @@ where
         Self {
             processor: Arc::new(processor),
             _agent_info: Arc::new(agent_info),
-            streaming_handler: Arc::new(streaming_handler),
+            sources: TaskSources::new(Arc::new(streaming_handler)),
             address,
             clients: Arc::new(Mutex::new(HashMap::new())),
             authenticator: None,
+            method_access: None,
+            options: WebSocketOptions::default(),
         }
     }
 }
@@ -84,13 +108,42 @@ where
         Self {
             processor: Arc::new(processor),
             _agent_info: Arc::new(agent_info),
-            streaming_handler: Arc::new(streaming_handler),
+            sources: TaskSources::new(Arc::new(streaming_handler)),
             address,
             clients: Arc::new(Mutex::new(HashMap::new())),
             authenticator: Some(Arc::new(authenticator)),
+            method_access: None,
+            options: WebSocketOptions::default(),
         }
     }
 
+    /// Set the message size limit and compression settings.
+    ///
+    /// Connections sending a frame or message over the limit are closed with a
+    /// policy-violation close code.
+    pub fn with_options(mut self, options: WebSocketOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Restrict the JSON-RPC methods each auth role may call.
+    ///
+    /// The role comes from the principal the connection authenticated as in
+    /// its handshake. Refused requests get a `METHOD_NOT_AUTHORIZED` error
+    /// and the connection stays open.
+    pub fn with_method_access(mut self, policy: MethodAccessPolicy) -> Self {
+        self.method_access = Some(Arc::new(policy));
+        self
+    }
+
+    /// The number of task update sources currently shared by subscriptions.
+    ///
+    /// Subscriptions to one task with the same snapshot options share a
+    /// source, registered with the streaming handler once for all of them.
+    pub fn shared_subscriptions(&self) -> usize {
+        self.sources.len()
+    }
+
     /// Start the WebSocket server
     #[cfg_attr(feature = "tracing", instrument(skip(self), fields(
         server.address = %self.address,
@@ -117,29 +170,24 @@ where
 
         while let Ok((stream, _)) = listener.accept().await {
             let processor = self.processor.clone();
-            let agent_info = self._agent_info.clone();
-            let streaming_handler = self.streaming_handler.clone();
+            let sources = self.sources.clone();
             let clients = self.clients.clone();
+            let options = self.options.clone();
 
             let authenticator = self.authenticator.clone();
+            let method_access = self.method_access.clone();
 
             tokio::spawn(async move {
-                // If an authenticator is present, obtain credentials from query parameters or headers
-                if let Some(_auth) = &authenticator {
-                    // For WebSockets, we'd typically extract auth from the URL query parameters
-                    // or from headers. In this simplified implementation, we'll just assume success.
-                    // In a real implementation, you would extract the token and call authenticate()
-
-                    // For now, we'll just log a message indicating auth is enabled
-                    #[cfg(feature = "tracing")]
-                    debug!("Authentication is enabled for WebSocket connections");
-                    #[cfg(not(feature = "tracing"))]
-                    println!("Authentication is enabled for WebSocket connections");
-                }
-
-                if let Err(e) =
-                    handle_connection(stream, processor, agent_info, streaming_handler, clients)
-                        .await
+                if let Err(e) = handle_connection(
+                    stream,
+                    processor,
+                    sources,
+                    clients,
+                    options,
+                    authenticator,
+                    method_access,
+                )
+                .await
                 {
                     #[cfg(feature = "tracing")]
                     error!("Error handling connection: {}", e);
@@ -155,17 +203,19 @@ where
 
 /// Handle a WebSocket connection
 #[cfg_attr(feature = "tracing", instrument(skip_all, fields(peer_addr)))]
-async fn handle_connection<P, A, S>(
+async fn handle_connection<P, S, Auth>(
     stream: TcpStream,
     processor: Arc<P>,
-    _agent_info: Arc<A>,
-    streaming_handler: Arc<S>,
+    sources: TaskSources<S>,
     clients: ClientMap,
+    options: WebSocketOptions,
+    authenticator: Option<Arc<Auth>>,
+    method_access: Option<Arc<MethodAccessPolicy>>,
 ) -> Result<(), A2AError>
 where
     P: AsyncA2ARequestProcessor + Send + Sync + 'static,
-    A: AgentInfoProvider + Send + Sync + 'static,
     S: AsyncStreamingHandler + Send + Sync + 'static,
+    Auth: Authenticator + Send + Sync + 'static,
 {
     let addr = stream.peer_addr().map_err(|e| {
         WebSocketServerError::Connection(format!("Failed to get peer address: {}", e))
@@ -174,9 +224,25 @@ where
     #[cfg(feature = "tracing")]
     tracing::Span::current().record("peer_addr", addr.to_string());
 
-    let ws_stream = accept_async(stream).await.map_err(|e| {
-        WebSocketServerError::Connection(format!("Error during WebSocket handshake: {}", e))
-    })?;
+    // Accept compression when the client offers it
+    let mut compression = false;
+    // The callback's error type is fixed by the handshake API
+    #[allow(clippy::result_large_err)]
+    let negotiate = |request: &Request, mut response: Response| {
+        if options.compression && offers_compression(request.headers()) {
+            response
+                .headers_mut()
+                .insert(EXTENSIONS_HEADER, extension_header());
+            compression = true;
+        }
+        Ok(response)
+    };
+    let ws_stream =
+        accept_hdr_async_with_config(stream, negotiate, Some(options.protocol_config()))
+            .await
+            .map_err(|e| {
+                WebSocketServerError::Connection(format!("Error during WebSocket handshake: {}", e))
+            })?;
 
     #[cfg(feature = "tracing")]
     info!("WebSocket connection established with: {}", addr);
@@ -185,6 +251,35 @@ where
 
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
+    // Nothing is processed until the connection has authenticated
+    let mut principal = None;
+    if let Some(authenticator) = &authenticator {
+        let result = authenticate_connection(
+            &mut ws_receiver,
+            authenticator.as_ref(),
+            compression,
+            &options,
+        )
+        .await;
+        let reply = match result {
+            Ok(authenticated) => {
+                #[cfg(feature = "tracing")]
+                debug!("WebSocket connection authenticated: {}", authenticated.id);
+                principal = Some(authenticated);
+                WsMessage::Text(accepted_frame())
+            }
+            Err(frame) => {
+                #[cfg(feature = "tracing")]
+                warn!("Closing connection with {}: {}", addr, frame.reason);
+                let _ = ws_sender.send(WsMessage::Close(Some(frame))).await;
+                return Ok(());
+            }
+        };
+        ws_sender.send(reply).await.map_err(|e| {
+            WebSocketServerError::Connection(format!("Error sending auth reply: {}", e))
+        })?;
+    }
+
     // Channel for sending messages to the client
     let (tx, mut rx) = mpsc::channel::<WsMessage>(32);
 
@@ -202,8 +297,13 @@ where
     }
 
     // Task to forward messages from the channel to the WebSocket
-    let forward_task = tokio::spawn(async move {
+    let forward_options = options.clone();
+    let mut forward_task = tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
+            let msg = match msg {
+                WsMessage::Text(text) => encode_message(text, compression, &forward_options),
+                other => other,
+            };
             if let Err(e) = ws_sender.send(msg).await {
                 #[cfg(feature = "tracing")]
                 error!("Error sending WebSocket message: {}", e);
@@ -214,13 +314,57 @@ where
         }
     });
 
+    // Tasks delivering the connection's subscriptions
+    let mut subscriptions = Vec::new();
+
     // Process incoming messages
+    let mut close_frame = None;
     while let Some(result) = ws_receiver.next().await {
         match result {
             Ok(msg) => {
+                // Compressed messages arrive as binary frames
+                let msg = match msg {
+                    WsMessage::Binary(data) if compression => {
+                        match decode_message(&data, &options) {
+                            Ok(text) => WsMessage::Text(text),
+                            Err(e) => {
+                                close_frame = Some(e.close_frame());
+                                break;
+                            }
+                        }
+                    }
+                    other => other,
+                };
+
                 if let WsMessage::Text(text) = msg {
+                    // Refused methods are answered without being processed
+                    let denial = method_access.as_ref().and_then(|policy| {
+                        policy.denial(principal.as_ref().and_then(AuthPrincipal::role), &text)
+                    });
+                    if let Some(response) = denial {
+                        if tx.send(WsMessage::Text(response)).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Listings are answered frame by frame, without holding up
+                    // the connection's other requests
+                    if let Some(request) = list_tasks_stream_request(&text) {
+                        tokio::spawn(stream_task_list(
+                            processor.clone(),
+                            principal.clone(),
+                            request,
+                            tx.clone(),
+                        ));
+                        continue;
+                    }
+
                     // Process the message
-                    let response = match processor.process_raw_request(&text).await {
+                    let response = match processor
+                        .process_raw_request_as(&text, principal.as_ref())
+                        .await
+                    {
                         Ok(response) => response,
                         Err(e) => {
                             let error = e.to_jsonrpc_error();
@@ -249,42 +393,26 @@ where
                                 if let Some(params) = request.get("params") {
                                     if let Some(task_id) = params.get("id").and_then(Value::as_str)
                                     {
-                                        // Create subscribers for status and artifact updates
-                                        let status_subscriber = WebSocketSubscriber {
-                                            client_id: client_id.clone(),
-                                            request_id: request.get("id").cloned(),
-                                            clients: clients.clone(),
-                                        };
-
-                                        let artifact_subscriber = WebSocketSubscriber {
+                                        let task_snapshots: Option<TaskSnapshotOptions> = params
+                                            .get("snapshot")
+                                            .cloned()
+                                            .and_then(|value| serde_json::from_value(value).ok());
+                                        let subscriber = WebSocketSubscriber {
                                             client_id: client_id.clone(),
                                             request_id: request.get("id").cloned(),
                                             clients: clients.clone(),
+                                            task_snapshots: task_snapshots.clone(),
+                                            sequence: Mutex::new(0),
+                                            seen_task: Mutex::new(None),
                                         };
 
-                                        // Register the subscribers
-                                        if let Err(e) = streaming_handler
-                                            .add_status_subscriber(
-                                                task_id,
-                                                Box::new(status_subscriber),
-                                            )
-                                            .await
-                                        {
-                                            eprintln!("Error adding status subscriber: {}", e);
-                                        }
-
-                                        if let Err(e) = streaming_handler
-                                            .add_artifact_subscriber(
-                                                task_id,
-                                                Box::new(artifact_subscriber),
-                                            )
-                                            .await
-                                        {
-                                            #[cfg(feature = "tracing")]
-                                            error!("Error adding artifact subscriber: {}", e);
-                                            #[cfg(not(feature = "tracing"))]
-                                            eprintln!("Error adding artifact subscriber: {}", e);
-                                        }
+                                        // Follow the task through the source
+                                        // shared by its subscriptions
+                                        let updates = sources.join(task_id, task_snapshots).await;
+                                        subscriptions.push(
+                                            tokio::spawn(deliver_updates(updates, subscriber))
+                                                .abort_handle(),
+                                        );
                                     }
                                 }
                             }
@@ -303,6 +431,12 @@ where
                     break;
                 }
             }
+            Err(e) if is_capacity_error(&e) => {
+                #[cfg(feature = "tracing")]
+                warn!("Closing connection with {}: {}", addr, e);
+                close_frame = Some(policy_violation());
+                break;
+            }
             Err(e) => {
                 eprintln!("Error receiving WebSocket message: {}", e);
                 break;
@@ -310,14 +444,28 @@ where
         }
     }
 
+    if let Some(frame) = close_frame {
+        let _ = tx.send(WsMessage::Close(Some(frame))).await;
+    }
+    drop(tx);
+
     // Clean up
+    for subscription in subscriptions {
+        subscription.abort();
+    }
     {
         let mut clients_guard = clients.lock().await; // Changed to await
         clients_guard.remove(&client_id);
     }
 
-    // Cancel the forward task
-    forward_task.abort();
+    // Let the forward task flush what is queued (including any close frame)
+    // before giving up on it
+    if tokio::time::timeout(Duration::from_secs(1), &mut forward_task)
+        .await
+        .is_err()
+    {
+        forward_task.abort();
+    }
 
     #[cfg(feature = "tracing")]
     info!("WebSocket connection closed with: {}", addr);
@@ -326,67 +474,200 @@ where
     Ok(())
 }
 
+/// Wait for the connection's auth frame and check its credentials
+async fn authenticate_connection<R, Auth>(
+    receiver: &mut R,
+    authenticator: &Auth,
+    compression: bool,
+    options: &WebSocketOptions,
+) -> Result<AuthPrincipal, CloseFrame<'static>>
+where
+    R: Stream<Item = Result<WsMessage, tungstenite::Error>> + Unpin,
+    Auth: Authenticator,
+{
+    let first = tokio::time::timeout(options.auth_timeout, async {
+        loop {
+            match receiver.next().await {
+                Some(Ok(WsMessage::Text(text))) => return Some(text),
+                Some(Ok(WsMessage::Binary(data))) if compression => {
+                    return decode_message(&data, options).ok();
+                }
+                Some(Ok(WsMessage::Ping(_) | WsMessage::Pong(_))) => continue,
+                _ => return None,
+            }
+        }
+    })
+    .await
+    .map_err(|_| rejected("Authentication timed out"))?;
+
+    let credentials = first
+        .as_deref()
+        .and_then(WebSocketCredentials::from_frame)
+        .ok_or_else(|| rejected("Expected an auth frame"))?;
+    authenticator
+        .authenticate(&credentials.to_auth_context())
+        .await
+        .map_err(|_| rejected("Authentication failed"))
+}
+
+/// The request in `text`, if it is a tasks/listStream request
+fn list_tasks_stream_request(text: &str) -> Option<ListTasksStreamRequest> {
+    let request: Value = serde_json::from_str(text).ok()?;
+    if request.get("method").and_then(Value::as_str) != Some("tasks/listStream") {
+        return None;
+    }
+    serde_json::from_value(request).ok()
+}
+
+/// Answer a tasks/listStream request with one frame per listed task and a
+/// last one carrying the summary, each stamped with its sequence number.
+/// A failure is sent as an error frame ending the listing.
+async fn stream_task_list<P>(
+    processor: Arc<P>,
+    principal: Option<AuthPrincipal>,
+    request: ListTasksStreamRequest,
+    tx: mpsc::Sender<WsMessage>,
+) where
+    P: AsyncA2ARequestProcessor + Send + Sync + 'static,
+{
+    let error_frame = |e: A2AError| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": request.id,
+            "error": e.to_jsonrpc_error(),
+        })
+    };
+
+    let mut items = match processor
+        .process_list_tasks_stream(&request, principal.as_ref())
+        .await
+    {
+        Ok(items) => items,
+        Err(e) => {
+            let _ = tx.send(WsMessage::Text(error_frame(e).to_string())).await;
+            return;
+        }
+    };
+
+    let mut sequence = 0u64;
+    while let Some(item) = items.next().await {
+        let (frame, last) = match item {
+            Ok(item) => {
+                sequence += 1;
+                let frame = json!({
+                    "jsonrpc": "2.0",
+                    "id": request.id,
+                    "result": item,
+                    SEQUENCE_FIELD: sequence,
+                });
+                (frame, false)
+            }
+            Err(e) => (error_frame(e), true),
+        };
+        if tx.send(WsMessage::Text(frame.to_string())).await.is_err() || last {
+            return;
+        }
+    }
+}
+
 /// WebSocket subscriber for streaming updates
 struct WebSocketSubscriber {
     client_id: String,
     request_id: Option<Value>,
     clients: ClientMap,
+    /// Snapshots requested in the subscription's `snapshot` param
+    task_snapshots: Option<TaskSnapshotOptions>,
+    /// Sequence number of the subscription's last event
+    sequence: Mutex<u64>,
+    /// The task as last sent, for subscriptions that asked for diffs
+    seen_task: Mutex<Option<Task>>,
 }
 
-#[async_trait]
-impl Subscriber<TaskStatusUpdateEvent> for WebSocketSubscriber {
-    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
-        let message = json!({
-            "jsonrpc": "2.0",
-            "id": self.request_id,
-            "result": update
-        });
-
+impl WebSocketSubscriber {
+    /// Send an event frame stamped with the subscription's next sequence number
+    async fn send_event(&self, result: Value) -> Result<(), A2AError> {
         // Get the sender without holding the lock across the await point
         let sender_opt = {
             let clients_guard = self.clients.lock().await; // Changed to await
             clients_guard.get(&self.client_id).cloned()
         };
+        let Some(sender) = sender_opt else {
+            return Ok(());
+        };
 
-        // Send the message if we have a sender
-        if let Some(sender) = sender_opt {
-            sender
-                .send(WsMessage::Text(
-                    serde_json::to_string(&message).map_err(A2AError::JsonParse)?,
-                ))
-                .await
-                .map_err(|e| A2AError::Internal(format!("Send error: {}", e)))?;
-        }
-
-        Ok(())
-    }
-}
-
-#[async_trait]
-impl Subscriber<TaskArtifactUpdateEvent> for WebSocketSubscriber {
-    async fn on_update(&self, update: TaskArtifactUpdateEvent) -> Result<(), A2AError> {
+        // Numbering and sending under one lock keeps frames in sequence order
+        let mut sequence = self.sequence.lock().await;
+        *sequence += 1;
         let message = json!({
             "jsonrpc": "2.0",
             "id": self.request_id,
-            "result": update
+            "result": result,
+            SEQUENCE_FIELD: *sequence,
         });
+        sender
+            .send(WsMessage::Text(
+                serde_json::to_string(&message).map_err(A2AError::JsonParse)?,
+            ))
+            .await
+            .map_err(|e| A2AError::Internal(format!("Send error: {}", e)))
+    }
 
-        // Get the sender without holding the lock across the await point
-        let sender_opt = {
-            let clients_guard = self.clients.lock().await; // Changed to await
-            clients_guard.get(&self.client_id).cloned()
+    /// Skip the sequence numbers of `missed` events, so the client sees the
+    /// gap before the latest state follows
+    async fn skip(&self, missed: u64) {
+        *self.sequence.lock().await += missed;
+    }
+}
+
+/// Send a subscription the updates of its source until the client is gone
+async fn deliver_updates<S>(mut updates: SourceSubscription<S>, subscriber: WebSocketSubscriber)
+where
+    S: AsyncStreamingHandler + Send + Sync + 'static,
+{
+    while let Some(update) = updates.next().await {
+        let sent = match update {
+            Ok(SourceEvent::Status(update)) => {
+                Subscriber::<TaskStatusUpdateEvent>::on_update(&subscriber, update).await
+            }
+            Ok(SourceEvent::Artifact(update)) => {
+                Subscriber::<TaskArtifactUpdateEvent>::on_update(&subscriber, update).await
+            }
+            Err(Missed(missed)) => {
+                subscriber.skip(missed).await;
+                Ok(())
+            }
         };
+        if sent.is_err() {
+            return;
+        }
+    }
+}
 
-        // Send the message if we have a sender
-        if let Some(sender) = sender_opt {
-            sender
-                .send(WsMessage::Text(
-                    serde_json::to_string(&message).map_err(A2AError::JsonParse)?,
-                ))
-                .await
-                .map_err(|e| A2AError::Internal(format!("Send error: {}", e)))?;
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for WebSocketSubscriber {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        if !self
+            .task_snapshots
+            .as_ref()
+            .is_some_and(|options| options.diffs)
+        {
+            return self.send_event(serde_json::to_value(update)?).await;
         }
+        // Diffing and sending under one lock keeps each diff against the
+        // snapshot sent just before it
+        let mut seen = self.seen_task.lock().await;
+        let update = update.diffed(&mut seen);
+        self.send_event(serde_json::to_value(update)?).await
+    }
 
-        Ok(())
+    fn task_snapshots(&self) -> Option<TaskSnapshotOptions> {
+        self.task_snapshots.clone()
+    }
+}
+
+#[async_trait]
+impl Subscriber<TaskArtifactUpdateEvent> for WebSocketSubscriber {
+    async fn on_update(&self, update: TaskArtifactUpdateEvent) -> Result<(), A2AError> {
+        self.send_event(serde_json::to_value(update)?).await
     }
 }
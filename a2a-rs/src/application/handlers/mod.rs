@@ -15,13 +15,20 @@ pub use message::{
     SendTaskStreamingResponse,
 };
 pub use notification::{
-    GetTaskPushNotificationRequest, GetTaskPushNotificationResponse,
-    SetTaskPushNotificationRequest, SetTaskPushNotificationResponse,
+    GetTaskPushNotificationRequest, GetTaskPushNotificationResponse, ListDeadLettersRequest,
+    ListDeadLettersResponse, PurgeDeadLettersRequest, PurgeDeadLettersResponse,
+    RequeueDeadLetterRequest, RequeueDeadLetterResponse, SetTaskPushNotificationRequest,
+    SetTaskPushNotificationResponse,
 };
 pub use task::{
-    CancelTaskRequest, CancelTaskResponse, DeleteTaskPushNotificationConfigRequest,
-    DeleteTaskPushNotificationConfigResponse, GetTaskPushNotificationConfigRequest,
-    GetTaskPushNotificationConfigResponse, GetTaskRequest, GetTaskResponse,
-    ListTaskPushNotificationConfigRequest, ListTaskPushNotificationConfigResponse,
-    ListTasksRequest, ListTasksResponse, TaskResubscriptionRequest,
+    AddTaskTagsRequest, CancelTaskRequest, CancelTaskResponse,
+    DeleteTaskPushNotificationConfigRequest, DeleteTaskPushNotificationConfigResponse,
+    GetOrCreateTaskRequest, GetOrCreateTaskResponse, GetTaskArtifactRequest,
+    GetTaskArtifactResponse, GetTaskEventsRequest, GetTaskEventsResponse,
+    GetTaskPushNotificationConfigRequest, GetTaskPushNotificationConfigResponse, GetTaskRequest,
+    GetTaskResponse, ImportTasksRequest, ImportTasksResponse, ListTaskArtifactsRequest,
+    ListTaskArtifactsResponse, ListTaskPushNotificationConfigRequest,
+    ListTaskPushNotificationConfigResponse, ListTasksRequest, ListTasksResponse,
+    ListTasksStreamRequest, ListTasksStreamResponse, RemoveTaskTagsRequest,
+    TaskResubscriptionRequest, TaskTagsResponse,
 };
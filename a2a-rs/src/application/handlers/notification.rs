@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::domain::{TaskIdParams, TaskPushNotificationConfig};
+use crate::domain::{
+    DeadLetter, ListDeadLettersParams, PurgeDeadLettersParams, PurgeDeadLettersResult,
+    RequeueDeadLetterParams, RequeueDeadLetterResult, TaskIdParams, TaskPushNotificationConfig,
+};
 
 /// Request to set task push notification
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,3 +71,102 @@ pub struct GetTaskPushNotificationResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<crate::domain::protocols::JSONRPCError>,
 }
+
+/// Request to list push notifications that could not be delivered (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDeadLettersRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: ListDeadLettersParams,
+}
+
+impl ListDeadLettersRequest {
+    pub fn new(params: ListDeadLettersParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "admin/pushNotifications/deadLetters/list".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the admin/pushNotifications/deadLetters/list method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDeadLettersResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Vec<DeadLetter>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to deliver a dead-lettered push notification again (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequeueDeadLetterRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: RequeueDeadLetterParams,
+}
+
+impl RequeueDeadLetterRequest {
+    pub fn new(params: RequeueDeadLetterParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "admin/pushNotifications/deadLetters/requeue".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the admin/pushNotifications/deadLetters/requeue method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequeueDeadLetterResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<RequeueDeadLetterResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to drop dead-lettered push notifications (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeDeadLettersRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: PurgeDeadLettersParams,
+}
+
+impl PurgeDeadLettersRequest {
+    pub fn new(params: PurgeDeadLettersParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "admin/pushNotifications/deadLetters/purge".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the admin/pushNotifications/deadLetters/purge method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeDeadLettersResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<PurgeDeadLettersResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
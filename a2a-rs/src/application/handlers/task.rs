@@ -2,9 +2,12 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::domain::{
-    DeleteTaskPushNotificationConfigParams, GetTaskPushNotificationConfigParams,
-    ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult, Task, TaskIdParams,
-    TaskPushNotificationConfig, TaskQueryParams,
+    DeleteTaskPushNotificationConfigParams, GetOrCreateTaskParams, GetOrCreateTaskResult,
+    GetTaskArtifactParams, GetTaskArtifactResult, GetTaskEventsParams, GetTaskEventsResult,
+    GetTaskPushNotificationConfigParams, ImportTasksParams, ImportTasksResult,
+    ListTaskArtifactsResult, ListTaskPushNotificationConfigParams, ListTasksParams,
+    ListTasksResult, ListTasksStreamItem, Task, TaskIdParams, TaskPushNotificationConfig,
+    TaskQueryParams, TaskTagsParams,
 };
 
 /// Request to get a task
@@ -130,6 +133,261 @@ pub struct ListTasksResponse {
     pub error: Option<crate::domain::protocols::JSONRPCError>,
 }
 
+/// Request to stream the page of tasks `tasks/list` would return
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTasksStreamRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<ListTasksParams>,
+}
+
+impl ListTasksStreamRequest {
+    pub fn new(params: Option<ListTasksParams>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/listStream".to_string(),
+            params,
+        }
+    }
+}
+
+/// One frame of a tasks/listStream response: a task, the closing summary,
+/// or an error ending the stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTasksStreamResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ListTasksStreamItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to read a task's event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTaskEventsRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: GetTaskEventsParams,
+}
+
+impl GetTaskEventsRequest {
+    pub fn new(params: GetTaskEventsParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/events".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for tasks/events method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTaskEventsResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetTaskEventsResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to list the artifacts of a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTaskArtifactsRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: TaskIdParams,
+}
+
+impl ListTaskArtifactsRequest {
+    pub fn new(params: TaskIdParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/artifacts/list".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the tasks/artifacts/list method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTaskArtifactsResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ListTaskArtifactsResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to read a page of a task artifact's parts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTaskArtifactRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: GetTaskArtifactParams,
+}
+
+impl GetTaskArtifactRequest {
+    pub fn new(params: GetTaskArtifactParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/artifacts/get".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the tasks/artifacts/get method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTaskArtifactResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetTaskArtifactResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to add tags to a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddTaskTagsRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: TaskTagsParams,
+}
+
+impl AddTaskTagsRequest {
+    pub fn new(params: TaskTagsParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/tags/add".to_string(),
+            params,
+        }
+    }
+}
+
+/// Request to remove tags from a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveTaskTagsRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: TaskTagsParams,
+}
+
+impl RemoveTaskTagsRequest {
+    pub fn new(params: TaskTagsParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/tags/remove".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the tasks/tags/add and tasks/tags/remove methods, carrying
+/// the updated task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTagsResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Task>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to import tasks as they are, for admins migrating or seeding data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportTasksRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: ImportTasksParams,
+}
+
+impl ImportTasksRequest {
+    pub fn new(params: ImportTasksParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "admin/tasks/import".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the admin/tasks/import method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportTasksResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ImportTasksResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Request to open a task, creating it if it does not exist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetOrCreateTaskRequest {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: GetOrCreateTaskParams,
+}
+
+impl GetOrCreateTaskRequest {
+    pub fn new(params: GetOrCreateTaskParams) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::String(uuid::Uuid::new_v4().to_string())),
+            method: "tasks/getOrCreate".to_string(),
+            params,
+        }
+    }
+}
+
+/// Response for the tasks/getOrCreate method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetOrCreateTaskResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GetOrCreateTaskResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
 /// Request to get push notification config(s) for a task (v0.3.0)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetTaskPushNotificationConfigRequest {
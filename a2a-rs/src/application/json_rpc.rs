@@ -5,19 +5,26 @@ use crate::domain::{A2AError, JSONRPCRequest};
 
 // Re-export handler types
 pub use crate::application::handlers::{
-    CancelTaskRequest, CancelTaskResponse, DeleteTaskPushNotificationConfigRequest,
-    DeleteTaskPushNotificationConfigResponse, GetAuthenticatedExtendedCardRequest,
-    GetAuthenticatedExtendedCardResponse, GetExtendedCardRequest, GetExtendedCardResponse,
-    GetTaskPushNotificationConfigRequest, GetTaskPushNotificationConfigResponse,
-    GetTaskPushNotificationRequest, GetTaskPushNotificationResponse, GetTaskRequest,
-    GetTaskResponse, ListTaskPushNotificationConfigRequest, ListTaskPushNotificationConfigResponse,
-    ListTasksRequest, ListTasksResponse, SendMessageRequest, SendMessageResponse,
+    AddTaskTagsRequest, CancelTaskRequest, CancelTaskResponse,
+    DeleteTaskPushNotificationConfigRequest, DeleteTaskPushNotificationConfigResponse,
+    GetAuthenticatedExtendedCardRequest, GetAuthenticatedExtendedCardResponse,
+    GetExtendedCardRequest, GetExtendedCardResponse, GetOrCreateTaskRequest,
+    GetOrCreateTaskResponse, GetTaskArtifactRequest, GetTaskArtifactResponse, GetTaskEventsRequest,
+    GetTaskEventsResponse, GetTaskPushNotificationConfigRequest,
+    GetTaskPushNotificationConfigResponse, GetTaskPushNotificationRequest,
+    GetTaskPushNotificationResponse, GetTaskRequest, GetTaskResponse, ImportTasksRequest,
+    ImportTasksResponse, ListDeadLettersRequest, ListDeadLettersResponse, ListTaskArtifactsRequest,
+    ListTaskArtifactsResponse, ListTaskPushNotificationConfigRequest,
+    ListTaskPushNotificationConfigResponse, ListTasksRequest, ListTasksResponse,
+    ListTasksStreamRequest, ListTasksStreamResponse, PurgeDeadLettersRequest,
+    PurgeDeadLettersResponse, RemoveTaskTagsRequest, RequeueDeadLetterRequest,
+    RequeueDeadLetterResponse, SendMessageRequest, SendMessageResponse,
     SendMessageStreamingRequest, SendMessageStreamingResponse, SendTaskRequest, SendTaskResponse,
     SendTaskStreamingRequest, SendTaskStreamingResponse, SetTaskPushNotificationRequest,
-    SetTaskPushNotificationResponse, TaskResubscriptionRequest,
+    SetTaskPushNotificationResponse, TaskResubscriptionRequest, TaskTagsResponse,
 };
 
-/// Union type representing any A2A protocol request.\n///\n/// This enum provides a unified interface for all possible A2A protocol requests,\n/// automatically handling method-based routing during deserialization. The enum\n/// covers all standard A2A operations including message sending, task management,\n/// and notification configuration.\n///\n/// # Supported Request Types\n/// - `SendMessage`: Send a message to an agent\n/// - `SendMessageStreaming`: Send a message with streaming response\n/// - `SendTask`: Legacy task sending (replaced by SendMessage)\n/// - `SendTaskStreaming`: Legacy streaming task (replaced by SendMessageStreaming)\n/// - `GetTask`: Retrieve task status and information\n/// - `CancelTask`: Cancel a running task\n/// - `SetTaskPushNotification`: Configure push notifications for a task\n/// - `GetTaskPushNotification`: Retrieve push notification configuration\n/// - `TaskResubscription`: Re-subscribe to task updates\n/// - `GetExtendedCard`: Get extended agent card (v0.3.0)\n/// - `ListTasks`: List tasks with filtering and pagination (v0.3.0)\n/// - `GetTaskPushNotificationConfig`: Get specific push notification config (v0.3.0)\n/// - `ListTaskPushNotificationConfigs`: List all push notification configs (v0.3.0)\n/// - `DeleteTaskPushNotificationConfig`: Delete a push notification config (v0.3.0)\n/// - `GetAuthenticatedExtendedCard`: Get authenticated extended card (v0.3.0)\n/// - `Generic`: Fallback for custom or unknown requests
+/// Union type representing any A2A protocol request.\n///\n/// This enum provides a unified interface for all possible A2A protocol requests,\n/// automatically handling method-based routing during deserialization. The enum\n/// covers all standard A2A operations including message sending, task management,\n/// and notification configuration.\n///\n/// # Supported Request Types\n/// - `SendMessage`: Send a message to an agent\n/// - `SendMessageStreaming`: Send a message with streaming response\n/// - `SendTask`: Legacy task sending (replaced by SendMessage)\n/// - `SendTaskStreaming`: Legacy streaming task (replaced by SendMessageStreaming)\n/// - `GetTask`: Retrieve task status and information\n/// - `CancelTask`: Cancel a running task\n/// - `SetTaskPushNotification`: Configure push notifications for a task\n/// - `GetTaskPushNotification`: Retrieve push notification configuration\n/// - `TaskResubscription`: Re-subscribe to task updates\n/// - `GetExtendedCard`: Get extended agent card (v0.3.0)\n/// - `ListTasks`: List tasks with filtering and pagination (v0.3.0)\n/// - `ListTasksStream`: Stream a page of tasks one task at a time\n/// - `GetTaskPushNotificationConfig`: Get specific push notification config (v0.3.0)\n/// - `ListTaskPushNotificationConfigs`: List all push notification configs (v0.3.0)\n/// - `DeleteTaskPushNotificationConfig`: Delete a push notification config (v0.3.0)\n/// - `GetAuthenticatedExtendedCard`: Get authenticated extended card (v0.3.0)\n/// - `GetTaskEvents`: Read the append-only event log of a task\n/// - `AddTaskTags`: Add tags to a task\n/// - `RemoveTaskTags`: Remove tags from a task\n/// - `GetOrCreateTask`: Open a task, creating it atomically if it does not exist\n/// - `ListTaskArtifacts`: List the artifacts a task produced\n/// - `GetTaskArtifact`: Read a page of a task artifact's parts\n/// - `ImportTasks`: Import tasks as they are (admin only)\n/// - `ListDeadLetters`: List push notifications that could not be delivered (admin only)\n/// - `RequeueDeadLetter`: Deliver a dead-lettered push notification again (admin only)\n/// - `PurgeDeadLetters`: Drop dead-lettered push notifications (admin only)\n/// - `Generic`: Fallback for custom or unknown requests
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum A2ARequest {
@@ -33,10 +40,21 @@ pub enum A2ARequest {
     GetExtendedCard(GetExtendedCardRequest),
     // v0.3.0 new methods
     ListTasks(ListTasksRequest),
+    ListTasksStream(ListTasksStreamRequest),
     GetTaskPushNotificationConfig(GetTaskPushNotificationConfigRequest),
     ListTaskPushNotificationConfigs(ListTaskPushNotificationConfigRequest),
     DeleteTaskPushNotificationConfig(DeleteTaskPushNotificationConfigRequest),
     GetAuthenticatedExtendedCard(GetAuthenticatedExtendedCardRequest),
+    GetTaskEvents(GetTaskEventsRequest),
+    AddTaskTags(AddTaskTagsRequest),
+    RemoveTaskTags(RemoveTaskTagsRequest),
+    GetOrCreateTask(GetOrCreateTaskRequest),
+    ListTaskArtifacts(ListTaskArtifactsRequest),
+    GetTaskArtifact(GetTaskArtifactRequest),
+    ImportTasks(ImportTasksRequest),
+    ListDeadLetters(ListDeadLettersRequest),
+    RequeueDeadLetter(RequeueDeadLetterRequest),
+    PurgeDeadLetters(PurgeDeadLettersRequest),
     Generic(JSONRPCRequest),
 }
 
@@ -132,6 +150,13 @@ impl<'de> Deserialize<'de> for A2ARequest {
                 let req = ListTasksRequest::deserialize(value).map_err(serde::de::Error::custom)?;
                 A2ARequest::ListTasks(req)
             }
+            "tasks/listStream" => {
+                // Re-parse as ListTasksStreamRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    ListTasksStreamRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::ListTasksStream(req)
+            }
             "tasks/pushNotificationConfig/list" => {
                 // Re-parse as ListTaskPushNotificationConfigRequest (v0.3.0)
                 let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
@@ -146,6 +171,76 @@ impl<'de> Deserialize<'de> for A2ARequest {
                     .map_err(serde::de::Error::custom)?;
                 A2ARequest::DeleteTaskPushNotificationConfig(req)
             }
+            "tasks/events" => {
+                // Re-parse as GetTaskEventsRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    GetTaskEventsRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::GetTaskEvents(req)
+            }
+            "tasks/tags/add" => {
+                // Re-parse as AddTaskTagsRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    AddTaskTagsRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::AddTaskTags(req)
+            }
+            "tasks/tags/remove" => {
+                // Re-parse as RemoveTaskTagsRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    RemoveTaskTagsRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::RemoveTaskTags(req)
+            }
+            "tasks/getOrCreate" => {
+                // Re-parse as GetOrCreateTaskRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    GetOrCreateTaskRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::GetOrCreateTask(req)
+            }
+            "tasks/artifacts/list" => {
+                // Re-parse as ListTaskArtifactsRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req = ListTaskArtifactsRequest::deserialize(value)
+                    .map_err(serde::de::Error::custom)?;
+                A2ARequest::ListTaskArtifacts(req)
+            }
+            "tasks/artifacts/get" => {
+                // Re-parse as GetTaskArtifactRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    GetTaskArtifactRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::GetTaskArtifact(req)
+            }
+            "admin/tasks/import" => {
+                // Re-parse as ImportTasksRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    ImportTasksRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::ImportTasks(req)
+            }
+            "admin/pushNotifications/deadLetters/list" => {
+                // Re-parse as ListDeadLettersRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req =
+                    ListDeadLettersRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+                A2ARequest::ListDeadLetters(req)
+            }
+            "admin/pushNotifications/deadLetters/requeue" => {
+                // Re-parse as RequeueDeadLetterRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req = RequeueDeadLetterRequest::deserialize(value)
+                    .map_err(serde::de::Error::custom)?;
+                A2ARequest::RequeueDeadLetter(req)
+            }
+            "admin/pushNotifications/deadLetters/purge" => {
+                // Re-parse as PurgeDeadLettersRequest
+                let value = serde_json::to_value(&json_req).map_err(serde::de::Error::custom)?;
+                let req = PurgeDeadLettersRequest::deserialize(value)
+                    .map_err(serde::de::Error::custom)?;
+                A2ARequest::PurgeDeadLetters(req)
+            }
             _ => {
                 // For other methods, use Generic variant
                 A2ARequest::Generic(json_req)
@@ -171,10 +266,21 @@ impl A2ARequest {
             A2ARequest::TaskResubscription(req) => &req.method,
             A2ARequest::GetExtendedCard(req) => &req.method,
             A2ARequest::ListTasks(req) => &req.method,
+            A2ARequest::ListTasksStream(req) => &req.method,
             A2ARequest::GetTaskPushNotificationConfig(req) => &req.method,
             A2ARequest::ListTaskPushNotificationConfigs(req) => &req.method,
             A2ARequest::DeleteTaskPushNotificationConfig(req) => &req.method,
             A2ARequest::GetAuthenticatedExtendedCard(req) => &req.method,
+            A2ARequest::GetTaskEvents(req) => &req.method,
+            A2ARequest::AddTaskTags(req) => &req.method,
+            A2ARequest::RemoveTaskTags(req) => &req.method,
+            A2ARequest::GetOrCreateTask(req) => &req.method,
+            A2ARequest::ListTaskArtifacts(req) => &req.method,
+            A2ARequest::GetTaskArtifact(req) => &req.method,
+            A2ARequest::ImportTasks(req) => &req.method,
+            A2ARequest::ListDeadLetters(req) => &req.method,
+            A2ARequest::RequeueDeadLetter(req) => &req.method,
+            A2ARequest::PurgeDeadLetters(req) => &req.method,
             A2ARequest::Generic(req) => &req.method,
         }
     }
@@ -193,10 +299,21 @@ impl A2ARequest {
             A2ARequest::TaskResubscription(req) => req.id.as_ref(),
             A2ARequest::GetExtendedCard(req) => req.id.as_ref(),
             A2ARequest::ListTasks(req) => req.id.as_ref(),
+            A2ARequest::ListTasksStream(req) => req.id.as_ref(),
             A2ARequest::GetTaskPushNotificationConfig(req) => req.id.as_ref(),
             A2ARequest::ListTaskPushNotificationConfigs(req) => req.id.as_ref(),
             A2ARequest::DeleteTaskPushNotificationConfig(req) => req.id.as_ref(),
             A2ARequest::GetAuthenticatedExtendedCard(req) => req.id.as_ref(),
+            A2ARequest::GetTaskEvents(req) => req.id.as_ref(),
+            A2ARequest::AddTaskTags(req) => req.id.as_ref(),
+            A2ARequest::RemoveTaskTags(req) => req.id.as_ref(),
+            A2ARequest::GetOrCreateTask(req) => req.id.as_ref(),
+            A2ARequest::ListTaskArtifacts(req) => req.id.as_ref(),
+            A2ARequest::GetTaskArtifact(req) => req.id.as_ref(),
+            A2ARequest::ImportTasks(req) => req.id.as_ref(),
+            A2ARequest::ListDeadLetters(req) => req.id.as_ref(),
+            A2ARequest::RequeueDeadLetter(req) => req.id.as_ref(),
+            A2ARequest::PurgeDeadLetters(req) => req.id.as_ref(),
             A2ARequest::Generic(req) => req.id.as_ref(),
         }
     }
@@ -208,12 +325,17 @@ impl A2ARequest {
 /// based on the JSON-RPC method field. It automatically handles method-based routing
 /// and validates the request structure according to the A2A specification.
 ///
+/// Parsing happens in stages so that malformed input maps onto the matching
+/// JSON-RPC error code instead of a generic failure.
+///
 /// # Arguments
 /// * `json` - JSON string containing the request
 ///
 /// # Returns
 /// * `Ok(A2ARequest)` - Successfully parsed request
-/// * `Err(A2AError::JsonParse)` - JSON parsing or validation failed
+/// * `Err(A2AError::JsonParse)` - The input is not valid JSON
+/// * `Err(A2AError::InvalidRequest)` - The JSON is not a JSON-RPC request object
+/// * `Err(A2AError::InvalidParams)` - The params do not match the method
 ///
 /// # Example
 /// ```rust
@@ -232,9 +354,21 @@ impl A2ARequest {
 /// }
 /// ```
 pub fn parse_request(json: &str) -> Result<A2ARequest, A2AError> {
-    match serde_json::from_str::<A2ARequest>(json) {
-        Ok(request) => Ok(request),
-        Err(err) => Err(A2AError::JsonParse(err)),
+    let value: Value = serde_json::from_str(json)?;
+    let envelope = JSONRPCRequest::deserialize(&value)
+        .map_err(|err| A2AError::InvalidRequest(err.to_string()))?;
+    A2ARequest::deserialize(value).map_err(|err| {
+        A2AError::InvalidParams(format!("Invalid params for {}: {}", envelope.method, err))
+    })
+}
+
+/// Read the id of a request that failed to parse, so the error response can
+/// still be matched to it. Only ids a JSON-RPC request may carry are returned.
+pub(crate) fn request_id(json: &str) -> Option<Value> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    match value.get("id")? {
+        id @ (Value::String(_) | Value::Number(_)) => Some(id.clone()),
+        _ => None,
     }
 }
 
@@ -1,7 +1,11 @@
 use bon::Builder;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 
+use super::{message::Part, task::TaskResult};
+use crate::domain::{error::A2AError, validation::ValidationResult};
+
 /// Supported A2A transport protocols (v0.3.0).
 ///
 /// Defines the transport protocols that agents can use for communication.
@@ -230,6 +234,7 @@ pub struct PasswordOAuthFlow {
 /// - `push_notifications`: Whether the agent can send push notifications
 /// - `state_transition_history`: Whether the agent maintains task state history
 /// - `extensions`: List of protocol extensions supported by the agent (v0.3.0)
+/// - `supported_part_kinds`: Message part kinds the agent accepts; all kinds when absent
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AgentCapabilities {
     #[serde(default)]
@@ -241,6 +246,49 @@ pub struct AgentCapabilities {
     /// List of protocol extensions supported by the agent (v0.3.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<Vec<AgentExtension>>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "supportedPartKinds")]
+    pub supported_part_kinds: Option<Vec<String>>,
+}
+
+impl AgentCapabilities {
+    /// Whether the agent accepts message parts of `kind` (`text`, `file` or `data`)
+    pub fn supports_part_kind(&self, kind: &str) -> bool {
+        self.supported_part_kinds
+            .as_ref()
+            .is_none_or(|kinds| kinds.iter().any(|k| k.eq_ignore_ascii_case(kind)))
+    }
+
+    /// Fail unless the agent supports streaming
+    pub fn ensure_streaming(&self) -> Result<(), A2AError> {
+        if self.streaming {
+            Ok(())
+        } else {
+            Err(A2AError::UnsupportedOperation(
+                "Agent does not support streaming".to_string(),
+            ))
+        }
+    }
+
+    /// Fail unless the agent supports push notifications
+    pub fn ensure_push_notifications(&self) -> Result<(), A2AError> {
+        if self.push_notifications {
+            Ok(())
+        } else {
+            Err(A2AError::PushNotificationNotSupported)
+        }
+    }
+
+    /// Fail if any part is of a kind the agent does not accept
+    pub fn ensure_parts(&self, parts: &[Part]) -> Result<(), A2AError> {
+        let unsupported = parts.iter().find(|p| !self.supports_part_kind(p.kind()));
+        match unsupported {
+            Some(part) => Err(A2AError::UnsupportedOperation(format!(
+                "Agent does not accept {} parts",
+                part.kind()
+            ))),
+            None => Ok(()),
+        }
+    }
 }
 
 /// A skill provided by an agent with metadata and examples.\n///\n/// Skills define specific capabilities that an agent can perform,\n/// including natural language descriptions, categorization tags,\n/// usage examples, and supported input/output modes.\n///\n/// # Example\n/// ```rust\n/// use a2a_rs::AgentSkill;\n/// \n/// let skill = AgentSkill::new(\n///     \"text-generation\".to_string(),\n///     \"Text Generation\".to_string(), \n///     \"Generate natural language text based on prompts\".to_string(),\n///     vec![\"nlp\".to_string(), \"generation\".to_string()]\n/// );\n/// ```
@@ -256,6 +304,12 @@ pub struct AgentSkill {
     pub input_modes: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "outputModes")]
     pub output_modes: Option<Vec<String>>,
+    /// JSON schema of the structured data messages to the skill must carry
+    #[serde(skip_serializing_if = "Option::is_none", rename = "inputSchema")]
+    pub input_schema: Option<Value>,
+    /// JSON schema of the structured result the skill produces
+    #[serde(skip_serializing_if = "Option::is_none", rename = "outputSchema")]
+    pub output_schema: Option<Value>,
     /// Per-skill security requirements (v0.3.0) - maps security scheme names to required scopes
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security: Option<Vec<HashMap<String, Vec<String>>>>,
@@ -272,6 +326,8 @@ impl AgentSkill {
             examples: None,
             input_modes: None,
             output_modes: None,
+            input_schema: None,
+            output_schema: None,
             security: None,
         }
     }
@@ -294,6 +350,27 @@ impl AgentSkill {
         self
     }
 
+    /// Declare the JSON schema of the structured input the skill's messages
+    /// must carry
+    pub fn with_input_schema(mut self, schema: Value) -> Self {
+        self.input_schema = Some(schema);
+        self
+    }
+
+    /// Declare the JSON schema of the skill's structured result
+    pub fn with_output_schema(mut self, schema: Value) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
+
+    /// Validate a result produced by this skill against its output schema
+    pub fn validate_result(&self, result: &TaskResult) -> ValidationResult<()> {
+        match &self.output_schema {
+            Some(schema) => result.validate_against(schema),
+            None => Ok(()),
+        }
+    }
+
     /// Add security requirements to the skill (v0.3.0)
     pub fn with_security(mut self, security: Vec<HashMap<String, Vec<String>>>) -> Self {
         self.security = Some(security);
@@ -320,6 +397,8 @@ impl AgentSkill {
             examples,
             input_modes,
             output_modes,
+            input_schema: None,
+            output_schema: None,
             security,
         }
     }
@@ -559,6 +638,8 @@ mod tests {
             examples: None,
             input_modes: None,
             output_modes: None,
+            input_schema: None,
+            output_schema: None,
             security: Some(vec![security_req]),
         };
 
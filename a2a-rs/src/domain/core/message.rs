@@ -1,4 +1,5 @@
 use bon::Builder;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 
@@ -130,7 +131,7 @@ impl FileContent {
 }
 
 /// Parts that can make up a message (text, file, or structured data).\n///\n/// Messages in the A2A protocol consist of one or more parts, each of which\n/// can contain different types of content:\n/// - `Text`: Plain text content with optional metadata\n/// - `File`: File content (embedded or URI-based) with optional metadata  \n/// - `Data`: Structured JSON data with optional metadata\n///\n/// Each part type supports optional metadata for additional context.\n///\n/// # Example\n/// ```rust\n/// use a2a_rs::{Part, FileContent};\n/// use serde_json::{Map, Value};\n/// \n/// // Text part\n/// let text_part = Part::Text {\n///     text: \"Hello, world!\".to_string(),\n///     metadata: None,\n/// };\n/// \n/// // File part with metadata\n/// let mut metadata = Map::new();\n/// metadata.insert(\"source\".to_string(), Value::String(\"user_upload\".to_string()));\n/// \n/// let file_part = Part::File {\n///     file: FileContent {\n///         name: Some(\"example.txt\".to_string()),\n///         mime_type: Some(\"text/plain\".to_string()),\n///         bytes: Some(\"SGVsbG8=\".to_string()),\n///         uri: None,\n///     },\n///     metadata: Some(metadata),\n/// };\n/// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(tag = "kind")]
 pub enum Part {
     #[serde(rename = "text")]
@@ -151,9 +152,72 @@ pub enum Part {
         #[serde(skip_serializing_if = "Option::is_none")]
         metadata: Option<Map<String, Value>>,
     },
+    /// A part of a kind this version does not know, kept exactly as received
+    /// (including its `kind`) so it can be stored and passed on unchanged
+    #[serde(untagged)]
+    Unknown(Map<String, Value>),
+}
+
+// Parts of a known kind must be well formed, but an unrecognized kind is kept
+// as `Part::Unknown` instead of failing the whole message
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(tag = "kind")]
+        enum KnownPart {
+            #[serde(rename = "text")]
+            Text {
+                text: String,
+                metadata: Option<Map<String, Value>>,
+            },
+            #[serde(rename = "file")]
+            File {
+                file: FileContent,
+                metadata: Option<Map<String, Value>>,
+            },
+            #[serde(rename = "data")]
+            Data {
+                data: Map<String, Value>,
+                metadata: Option<Map<String, Value>>,
+            },
+        }
+
+        let fields = Map::<String, Value>::deserialize(deserializer)?;
+        match fields.get("kind") {
+            Some(Value::String(kind)) if matches!(kind.as_str(), "text" | "file" | "data") => {
+                let part = KnownPart::deserialize(Value::Object(fields))
+                    .map_err(serde::de::Error::custom)?;
+                Ok(match part {
+                    KnownPart::Text { text, metadata } => Part::Text { text, metadata },
+                    KnownPart::File { file, metadata } => Part::File { file, metadata },
+                    KnownPart::Data { data, metadata } => Part::Data { data, metadata },
+                })
+            }
+            Some(Value::String(_)) => Ok(Part::Unknown(fields)),
+            Some(_) => Err(serde::de::Error::custom("part `kind` must be a string")),
+            None => Err(serde::de::Error::missing_field("kind")),
+        }
+    }
 }
 
 impl Part {
+    /// The part's `kind` discriminator: `text`, `file`, `data`, or the kind
+    /// of an unknown part as received
+    pub fn kind(&self) -> &str {
+        match self {
+            Part::Text { .. } => "text",
+            Part::File { .. } => "file",
+            Part::Data { .. } => "data",
+            Part::Unknown(fields) => fields
+                .get("kind")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        }
+    }
+
     /// Helper method to get the text content if this is a Text part
     #[cfg(test)]
     pub fn get_text(&self) -> Option<&str> {
@@ -225,6 +289,7 @@ pub struct Message {
 ///     }],
 ///     metadata: None,
 ///     extensions: None,
+///     created_at: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -241,6 +306,61 @@ pub struct Artifact {
     /// URIs of extensions relevant to this artifact (v0.3.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<Vec<String>>,
+    /// When the agent produced the artifact, stamped by the storage recording it
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// An artifact as listed for its task, without its content
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactSummary {
+    pub artifact_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Number of parts the content is made of
+    pub part_count: usize,
+}
+
+impl Artifact {
+    /// Check that the artifact has an ID and content, and that its file parts
+    /// are well formed
+    pub fn validate(&self) -> Result<(), A2AError> {
+        if self.artifact_id.trim().is_empty() {
+            return Err(A2AError::ValidationError {
+                field: "artifactId".to_string(),
+                message: "Artifact ID must not be empty".to_string(),
+            });
+        }
+        if self.parts.is_empty() {
+            return Err(A2AError::ValidationError {
+                field: "parts".to_string(),
+                message: format!("Artifact '{}' has no parts", self.artifact_id),
+            });
+        }
+        for part in &self.parts {
+            if let Part::File { file, .. } = part {
+                file.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&Artifact> for ArtifactSummary {
+    fn from(artifact: &Artifact) -> Self {
+        Self {
+            artifact_id: artifact.artifact_id.clone(),
+            name: artifact.name.clone(),
+            description: artifact.description.clone(),
+            created_at: artifact.created_at,
+            part_count: artifact.parts.len(),
+        }
+    }
 }
 
 /// Helper methods for creating parts
@@ -357,6 +477,9 @@ impl PartBuilder {
             Part::Text { metadata: meta, .. } => *meta = Some(metadata),
             Part::Data { metadata: meta, .. } => *meta = Some(metadata),
             Part::File { metadata: meta, .. } => *meta = Some(metadata),
+            Part::Unknown(fields) => {
+                fields.insert("metadata".to_string(), Value::Object(metadata));
+            }
         }
         self
     }
@@ -495,14 +618,10 @@ impl Message {
         }
 
         #[cfg(feature = "tracing")]
-        {
-            let part_type = match &part {
-                Part::Text { .. } => "text",
-                Part::File { .. } => "file",
-                Part::Data { .. } => "data",
-            };
-            tracing::debug!(part_type = part_type, "Part added successfully to message");
-        }
+        tracing::debug!(
+            part_type = part.kind(),
+            "Part added successfully to message"
+        );
 
         self.parts.push(part);
         Ok(())
@@ -10,10 +10,14 @@ pub use agent::{
     ImplicitOAuthFlow, OAuthFlows, PasswordOAuthFlow, PushNotificationAuthenticationInfo,
     PushNotificationConfig, SecurityScheme, TransportProtocol,
 };
-pub use message::{Artifact, FileContent, Message, Part, Role};
+pub use message::{Artifact, ArtifactSummary, FileContent, Message, Part, Role};
 pub use task::{
-    DeleteTaskPushNotificationConfigParams, GetTaskPushNotificationConfigParams,
-    ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult,
-    MessageSendConfiguration, MessageSendParams, Task, TaskIdParams, TaskPushNotificationConfig,
-    TaskQueryParams, TaskSendParams, TaskState, TaskStatus,
+    DeleteTaskPushNotificationConfigParams, GetOrCreateTaskParams, GetOrCreateTaskResult,
+    GetTaskArtifactParams, GetTaskArtifactResult, GetTaskEventsParams, GetTaskEventsResult,
+    GetTaskPushNotificationConfigParams, ImportTasksParams, ImportTasksResult, InputRequest,
+    ListTaskArtifactsResult, ListTaskPushNotificationConfigParams, ListTasksParams,
+    ListTasksResult, ListTasksStreamItem, ListTasksSummary, MessageSendConfiguration,
+    MessageSendParams, PageSizeLimits, TagMatch, Task, TaskCancellation, TaskField, TaskIdParams,
+    TaskImportOutcome, TaskPushNotificationConfig, TaskQueryParams, TaskResult, TaskSendParams,
+    TaskState, TaskStatus, TaskTagsParams,
 };
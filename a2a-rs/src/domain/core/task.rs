@@ -1,6 +1,8 @@
+use std::{fmt, str::FromStr};
+
 use bon::Builder;
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
 
 #[cfg(feature = "tracing")]
@@ -8,7 +10,12 @@ use tracing::instrument;
 
 use super::{
     agent::PushNotificationConfig,
-    message::{Artifact, Message},
+    message::{Artifact, ArtifactSummary, FileContent, Message, Part},
+};
+use crate::domain::{
+    error::A2AError,
+    events::TaskSnapshotOptions,
+    validation::{ValidationResult, validate_json_schema},
 };
 
 #[cfg(feature = "tracing")]
@@ -40,6 +47,62 @@ pub enum TaskState {
     Unknown,
 }
 
+impl TaskState {
+    /// Every state, in declaration order
+    pub const ALL: [TaskState; 9] = [
+        TaskState::Submitted,
+        TaskState::Working,
+        TaskState::InputRequired,
+        TaskState::Completed,
+        TaskState::Canceled,
+        TaskState::Failed,
+        TaskState::Rejected,
+        TaskState::AuthRequired,
+        TaskState::Unknown,
+    ];
+
+    /// The state's wire name, as serialized (e.g. `"input-required"`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskState::Submitted => "submitted",
+            TaskState::Working => "working",
+            TaskState::InputRequired => "input-required",
+            TaskState::Completed => "completed",
+            TaskState::Canceled => "canceled",
+            TaskState::Failed => "failed",
+            TaskState::Rejected => "rejected",
+            TaskState::AuthRequired => "auth-required",
+            TaskState::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the task can make no further progress from this state
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskState::Completed | TaskState::Canceled | TaskState::Failed | TaskState::Rejected
+        )
+    }
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TaskState {
+    type Err = A2AError;
+
+    /// Parse a state from its wire name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TaskState::ALL
+            .into_iter()
+            .find(|state| state.as_str() == s)
+            .ok_or_else(|| A2AError::InvalidParams(format!("Unknown task state: {}", s)))
+    }
+}
+
 /// Status of a task including state, optional message, and timestamp.
 ///
 /// Represents a point-in-time status of a task, including its current state,
@@ -66,6 +129,22 @@ pub struct TaskStatus {
     pub timestamp: Option<DateTime<Utc>>,
 }
 
+impl TaskStatus {
+    /// What the agent is waiting for, if this is an input-required status
+    pub fn input_request(&self) -> Option<InputRequest> {
+        if self.state != TaskState::InputRequired {
+            return None;
+        }
+        let request = self
+            .message
+            .as_ref()?
+            .metadata
+            .as_ref()?
+            .get(INPUT_REQUEST_METADATA_KEY)?;
+        serde_json::from_value(request.clone()).ok()
+    }
+}
+
 impl Default for TaskStatus {
     fn default() -> Self {
         Self {
@@ -84,6 +163,9 @@ impl Default for TaskStatus {
 /// - Optional artifacts produced during processing
 /// - Optional message history for the conversation
 /// - Optional metadata for additional context
+/// - A version number that increases with every status or history change,
+///   used for optimistic concurrency control
+/// - Tags for organizing tasks, which do not change the version
 ///
 /// # Example
 /// ```rust
@@ -112,8 +194,91 @@ pub struct Task {
     pub history: Option<Vec<Message>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Map<String, Value>>,
+    /// Structured outcome of the skill that completed the task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<TaskResult>,
     #[builder(default = "task".to_string())]
     pub kind: String, // Always "task"
+    /// Incremented on every status or history change; clients send it back as
+    /// `expectedVersion` to detect concurrent modifications
+    #[serde(default)]
+    #[builder(default = 1)]
+    pub version: u64,
+    /// Labels for organizing tasks, sorted and without duplicates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    pub tags: Vec<String>,
+    /// Tasks referenced by messages in this task, sorted and without duplicates
+    #[serde(
+        rename = "referenceTaskIds",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[builder(default)]
+    pub reference_task_ids: Vec<String>,
+    /// Position after the last history entry, set on `tasks/get` responses;
+    /// send it back as `since` to fetch only the entries added after it
+    #[serde(
+        rename = "historyCursor",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub history_cursor: Option<String>,
+}
+
+/// Structured result of a skill invocation.
+///
+/// Agents attach a result when a task completes so clients can read the outcome
+/// programmatically instead of parsing it out of message text. The data is
+/// expected to match the output schema of the skill that produced it.
+///
+/// # Example
+/// ```rust
+/// use a2a_rs::TaskResult;
+/// use serde_json::json;
+///
+/// let result = TaskResult::new(json!({"status": "approved", "amount": 42.5}))
+///     .with_skill_id("process_reimbursement".to_string());
+/// assert_eq!(result.data["status"], "approved");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskResult {
+    /// ID of the skill that produced the result
+    #[serde(skip_serializing_if = "Option::is_none", rename = "skillId")]
+    pub skill_id: Option<String>,
+    /// Result payload
+    pub data: Value,
+}
+
+impl TaskResult {
+    /// Create a result from its payload
+    pub fn new(data: Value) -> Self {
+        Self {
+            skill_id: None,
+            data,
+        }
+    }
+
+    /// Record the skill that produced the result
+    pub fn with_skill_id(mut self, skill_id: String) -> Self {
+        self.skill_id = Some(skill_id);
+        self
+    }
+
+    /// Create a result by serializing a typed value
+    pub fn from_value<T: Serialize>(data: &T) -> Result<Self, A2AError> {
+        Ok(Self::new(serde_json::to_value(data)?))
+    }
+
+    /// Deserialize the payload into a typed value
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, A2AError> {
+        Ok(serde_json::from_value(self.data.clone())?)
+    }
+
+    /// Validate the payload against a skill's output schema
+    pub fn validate_against(&self, schema: &Value) -> ValidationResult<()> {
+        validate_json_schema(&self.data, schema, "result")
+    }
 }
 
 /// Parameters for identifying a task by ID.
@@ -125,6 +290,12 @@ pub struct TaskIdParams {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Map<String, Value>>,
+    /// Reject the request unless the task is still at this version
+    #[serde(skip_serializing_if = "Option::is_none", rename = "expectedVersion")]
+    pub expected_version: Option<u64>,
+    /// Why the task is being canceled; only read by `tasks/cancel`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// Parameters for querying a task with optional history constraints.
@@ -138,6 +309,18 @@ pub struct TaskQueryParams {
     pub history_length: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Map<String, Value>>,
+    /// On `tasks/get`, only return history entries after this cursor, taken
+    /// from the `historyCursor` of an earlier response. With `historyLength`,
+    /// only the first that many are returned, and the response's cursor
+    /// points after the last of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// On `tasks/resubscribe`, ask for task snapshots with status updates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<TaskSnapshotOptions>,
+    /// On `tasks/get`, parts of the task to leave out of the response
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<TaskField>,
 }
 
 /// Configuration options for sending messages including output modes and notifications.
@@ -192,6 +375,9 @@ pub struct TaskSendParams {
     pub history_length: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Map<String, Value>>,
+    /// Reject the request unless the task is still at this version
+    #[serde(skip_serializing_if = "Option::is_none", rename = "expectedVersion")]
+    pub expected_version: Option<u64>,
 }
 
 /// Configuration for task push notifications
@@ -220,6 +406,8 @@ pub struct TaskPushNotificationConfig {
 ///     history_length: Some(5),
 ///     include_artifacts: Some(true),
 ///     last_updated_after: None,
+///     tags: Some(vec!["urgent".to_string()]),
+///     tag_match: None,
 ///     metadata: None,
 /// };
 /// ```
@@ -231,7 +419,8 @@ pub struct ListTasksParams {
     /// Filter tasks by their current status state
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<TaskState>,
-    /// Maximum number of tasks to return (1-100, default 50)
+    /// Maximum number of tasks to return; the server applies its
+    /// [`PageSizeLimits`] when this is missing, zero or too large
     #[serde(skip_serializing_if = "Option::is_none", rename = "pageSize")]
     pub page_size: Option<i32>,
     /// Token for pagination from previous response
@@ -246,10 +435,199 @@ pub struct ListTasksParams {
     /// Filter tasks updated after this timestamp (milliseconds since epoch)
     #[serde(skip_serializing_if = "Option::is_none", rename = "lastUpdatedAfter")]
     pub last_updated_after: Option<i64>,
+    /// Filter tasks by tag; see `tag_match` for how several tags combine
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Whether a task needs any or all of `tags` (default all)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tagMatch")]
+    pub tag_match: Option<TagMatch>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Map<String, Value>>,
 }
 
+/// A part of a task that `tasks/get` can leave out to lighten its response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskField {
+    /// The message history; the store does not load it at all
+    History,
+    /// The artifacts the agent produced
+    Artifacts,
+    /// Metadata of the task and of its messages, artifacts and parts
+    Metadata,
+    /// Inline bytes of file parts, replaced by a reference to the part
+    FileBytes,
+}
+
+/// The fields a read of one task leaves out, for stores to apply to each
+/// message and artifact as they read it
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TaskExclusions<'a> {
+    task_id: &'a str,
+    pub(crate) history: bool,
+    pub(crate) artifacts: bool,
+    pub(crate) metadata: bool,
+    pub(crate) bytes: bool,
+}
+
+impl<'a> TaskExclusions<'a> {
+    pub(crate) fn new(task_id: &'a str, excluded: &[TaskField]) -> Self {
+        Self {
+            task_id,
+            history: excluded.contains(&TaskField::History),
+            artifacts: excluded.contains(&TaskField::Artifacts),
+            metadata: excluded.contains(&TaskField::Metadata),
+            bytes: excluded.contains(&TaskField::FileBytes),
+        }
+    }
+
+    /// The history length to read when `requested` was asked for
+    pub(crate) fn history_length(&self, requested: Option<u32>) -> Option<u32> {
+        if self.history { Some(0) } else { requested }
+    }
+
+    /// Leave the excluded fields out of `message`
+    pub(crate) fn strip_message(&self, message: &mut Message) {
+        if self.metadata {
+            message.metadata = None;
+        }
+        let owner = format!("message:{}", message.message_id);
+        self.strip_parts(&owner, &mut message.parts);
+    }
+
+    /// Leave the excluded fields out of `artifact`
+    pub(crate) fn strip_artifact(&self, artifact: &mut Artifact) {
+        if self.metadata {
+            artifact.metadata = None;
+        }
+        let owner = format!("artifact:{}", artifact.artifact_id);
+        self.strip_parts(&owner, &mut artifact.parts);
+    }
+
+    /// Copy `message` without the excluded fields
+    pub(crate) fn copy_message(&self, message: &Message) -> Message {
+        let owner = format!("message:{}", message.message_id);
+        Message {
+            role: message.role.clone(),
+            parts: self.copy_parts(&owner, &message.parts),
+            metadata: self.copy_metadata(&message.metadata),
+            reference_task_ids: message.reference_task_ids.clone(),
+            message_id: message.message_id.clone(),
+            task_id: message.task_id.clone(),
+            context_id: message.context_id.clone(),
+            extensions: message.extensions.clone(),
+            kind: message.kind.clone(),
+        }
+    }
+
+    /// Copy `artifact` without the excluded fields
+    pub(crate) fn copy_artifact(&self, artifact: &Artifact) -> Artifact {
+        let owner = format!("artifact:{}", artifact.artifact_id);
+        Artifact {
+            artifact_id: artifact.artifact_id.clone(),
+            name: artifact.name.clone(),
+            description: artifact.description.clone(),
+            parts: self.copy_parts(&owner, &artifact.parts),
+            metadata: self.copy_metadata(&artifact.metadata),
+            extensions: artifact.extensions.clone(),
+            created_at: artifact.created_at,
+        }
+    }
+
+    fn strip_parts(&self, owner: &str, parts: &mut [Part]) {
+        if !self.metadata && !self.bytes {
+            return;
+        }
+        for (index, part) in parts.iter_mut().enumerate() {
+            let (file, part_metadata) = match part {
+                Part::Text { metadata, .. } | Part::Data { metadata, .. } => (None, metadata),
+                Part::File { file, metadata } => (Some(file), metadata),
+                Part::Unknown(_) => continue,
+            };
+            if self.metadata {
+                *part_metadata = None;
+            }
+            if let Some(file) = file.filter(|file| self.bytes && file.bytes.is_some()) {
+                file.bytes = None;
+                file.uri = Some(self.part_uri(owner, index));
+            }
+        }
+    }
+
+    fn copy_parts(&self, owner: &str, parts: &[Part]) -> Vec<Part> {
+        let metadata = |metadata: &Option<Map<String, Value>>| self.copy_metadata(metadata);
+        parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| match part {
+                Part::Text { text, metadata: m } => Part::Text {
+                    text: text.clone(),
+                    metadata: metadata(m),
+                },
+                Part::Data { data, metadata: m } => Part::Data {
+                    data: data.clone(),
+                    metadata: metadata(m),
+                },
+                Part::File { file, metadata: m } => {
+                    let (bytes, uri) = if self.bytes && file.bytes.is_some() {
+                        (None, Some(self.part_uri(owner, index)))
+                    } else {
+                        (file.bytes.clone(), file.uri.clone())
+                    };
+                    Part::File {
+                        file: FileContent {
+                            name: file.name.clone(),
+                            mime_type: file.mime_type.clone(),
+                            bytes,
+                            uri,
+                        },
+                        metadata: metadata(m),
+                    }
+                }
+                Part::Unknown(fields) => Part::Unknown(fields.clone()),
+            })
+            .collect()
+    }
+
+    /// Copy `metadata` unless metadata is excluded
+    pub(crate) fn copy_metadata(
+        &self,
+        metadata: &Option<Map<String, Value>>,
+    ) -> Option<Map<String, Value>> {
+        if self.metadata {
+            None
+        } else {
+            metadata.clone()
+        }
+    }
+
+    /// The URI naming the part at `index` of `owner` in place of its bytes
+    fn part_uri(&self, owner: &str, index: usize) -> String {
+        format!("urn:a2a:task:{}:{}:part:{}", self.task_id, owner, index)
+    }
+}
+
+/// How the `tags` filter of [`ListTasksParams`] combines several tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    /// Tasks carrying at least one of the tags
+    Any,
+    /// Tasks carrying every one of the tags
+    #[default]
+    All,
+}
+
+impl TagMatch {
+    /// Whether `task_tags` satisfies the filter `wanted`
+    pub fn matches(self, wanted: &[String], task_tags: &[String]) -> bool {
+        match self {
+            TagMatch::Any => wanted.iter().any(|tag| task_tags.contains(tag)),
+            TagMatch::All => wanted.iter().all(|tag| task_tags.contains(tag)),
+        }
+    }
+}
+
 /// Result object for tasks/list method (v0.3.0).
 ///
 /// Contains the list of tasks matching the query criteria along with
@@ -269,6 +647,454 @@ pub struct ListTasksResult {
     pub next_page_token: String,
 }
 
+impl ListTasksResult {
+    /// The page as the items of a streamed listing: its tasks, then a summary
+    /// counting them
+    pub fn into_stream_items(self) -> Vec<ListTasksStreamItem> {
+        let summary = ListTasksSummary {
+            count: self.tasks.len() as i32,
+            total_size: self.total_size,
+            page_size: self.page_size,
+            next_page_token: self.next_page_token,
+        };
+        self.tasks
+            .into_iter()
+            .map(|task| ListTasksStreamItem::Task(Box::new(task)))
+            .chain(std::iter::once(ListTasksStreamItem::Summary(summary)))
+            .collect()
+    }
+}
+
+/// Item of a streamed task listing.
+///
+/// A stream yields the same page of tasks as `tasks/list`, one task at a time,
+/// and ends with a single [`ListTasksStreamItem::Summary`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ListTasksStreamItem {
+    /// The next matching task
+    Task(Box<Task>),
+    /// Counts for the listing, sent after the last task
+    Summary(ListTasksSummary),
+}
+
+/// Trailer of a streamed task listing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListTasksSummary {
+    /// Number of tasks the stream yielded
+    pub count: i32,
+    /// Total number of tasks available (before pagination)
+    #[serde(rename = "totalSize")]
+    pub total_size: i32,
+    /// Maximum number of tasks in this page
+    #[serde(rename = "pageSize")]
+    pub page_size: i32,
+    /// Token for next page (empty string if no more results)
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: String,
+}
+
+/// Default and maximum page size a server applies to `tasks/list`.
+///
+/// A request without a page size, or with zero or a negative one, gets
+/// `default_page_size` tasks. Larger requests are clamped to `max_page_size`,
+/// and [`ListTasksResult::page_size`] reports the size actually used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageSizeLimits {
+    pub default_page_size: u32,
+    pub max_page_size: u32,
+}
+
+impl Default for PageSizeLimits {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl PageSizeLimits {
+    /// Create limits, raising `max_page_size` to `default_page_size` if needed
+    pub fn new(default_page_size: u32, max_page_size: u32) -> Self {
+        let default_page_size = default_page_size.max(1);
+        Self {
+            default_page_size,
+            max_page_size: max_page_size.max(default_page_size),
+        }
+    }
+
+    /// The page size to use for a requested one
+    pub fn resolve(&self, requested: Option<i32>) -> u32 {
+        match requested {
+            Some(size) if size > 0 => (size as u32).min(self.max_page_size),
+            _ => self.default_page_size.min(self.max_page_size),
+        }
+    }
+}
+
+/// Parameters for reading a task's event log.
+///
+/// Events are returned in the order they were recorded, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GetTaskEventsParams {
+    /// Task ID
+    pub id: String,
+    /// Maximum number of events to return (1-100, default 50)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pageSize")]
+    pub page_size: Option<i32>,
+    /// Token for pagination from previous response
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pageToken")]
+    pub page_token: Option<String>,
+}
+
+/// Parameters for the tasks/tags/add and tasks/tags/remove methods.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TaskTagsParams {
+    /// Task ID
+    pub id: String,
+    /// Tags to add or remove
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+}
+
+/// Longest tag accepted, in characters
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Most tags a single task may carry
+pub const MAX_TAGS_PER_TASK: usize = 20;
+
+/// Check that a tag is 1 to [`MAX_TAG_LENGTH`] characters of ASCII letters,
+/// digits, `-`, `_` or `.`
+pub fn validate_tag(tag: &str) -> Result<(), A2AError> {
+    if tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH {
+        return Err(A2AError::ValidationError {
+            field: "tags".to_string(),
+            message: format!(
+                "Tag '{}' must be between 1 and {} characters",
+                tag, MAX_TAG_LENGTH
+            ),
+        });
+    }
+    if !tag
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(A2AError::ValidationError {
+            field: "tags".to_string(),
+            message: format!(
+                "Tag '{}' may only contain letters, digits, '-', '_' and '.'",
+                tag
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Longest cancel reason accepted, in characters
+pub const MAX_CANCEL_REASON_LENGTH: usize = 500;
+
+/// Metadata key of the cancellation details on a canceled task's status message
+pub const CANCELLATION_METADATA_KEY: &str = "cancellation";
+
+/// Who canceled a task and why.
+///
+/// The details are recorded in the metadata of the status message written
+/// when the task is canceled, so they are returned with the task, carried by
+/// the final status event and kept in the task's event log.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskCancellation {
+    /// ID of the authenticated principal that canceled the task
+    #[serde(skip_serializing_if = "Option::is_none", rename = "canceledBy")]
+    pub canceled_by: Option<String>,
+    /// Why the task was canceled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl TaskCancellation {
+    /// Create a cancellation without details
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record who canceled the task
+    pub fn with_canceled_by(mut self, canceled_by: String) -> Self {
+        self.canceled_by = Some(canceled_by);
+        self
+    }
+
+    /// Record why the task was canceled
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Check that the reason is at most [`MAX_CANCEL_REASON_LENGTH`] characters
+    pub fn validate(&self) -> Result<(), A2AError> {
+        match &self.reason {
+            Some(reason) if reason.chars().count() > MAX_CANCEL_REASON_LENGTH => {
+                Err(A2AError::ValidationError {
+                    field: "reason".to_string(),
+                    message: format!(
+                        "Cancel reason cannot exceed {} characters",
+                        MAX_CANCEL_REASON_LENGTH
+                    ),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The agent message `message_id` recording the cancellation of a task,
+    /// such as "Task 42 canceled by alice: duplicate submission"
+    pub fn status_message(&self, task_id: &str, context_id: &str, message_id: String) -> Message {
+        let mut text = format!("Task {} canceled", task_id);
+        if let Some(canceled_by) = &self.canceled_by {
+            text.push_str(&format!(" by {}", canceled_by));
+        }
+        match &self.reason {
+            Some(reason) => text.push_str(&format!(": {}", reason)),
+            None => text.push('.'),
+        }
+
+        let mut message = Message::agent_text(text, message_id);
+        message.task_id = Some(task_id.to_string());
+        message.context_id = Some(context_id.to_string());
+        if *self != Self::default() {
+            let mut metadata = Map::new();
+            // Serializing two optional strings cannot fail
+            if let Ok(details) = serde_json::to_value(self) {
+                metadata.insert(CANCELLATION_METADATA_KEY.to_string(), details);
+            }
+            message.metadata = Some(metadata);
+        }
+        message
+    }
+}
+
+/// Metadata key of the pending request on an input-required task's status message
+pub const INPUT_REQUEST_METADATA_KEY: &str = "inputRequest";
+
+/// What an agent needs from the client before a paused task can continue.
+///
+/// The request travels in the metadata of the input-required status message,
+/// so clients receive it with the task and the status event. The client
+/// answers by sending a follow-up message to the same task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputRequest {
+    /// The question shown to the user, such as "Which project code?"
+    pub prompt: String,
+    /// JSON Schema of the answer, when the agent expects structured data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+}
+
+impl InputRequest {
+    /// Ask the client a free-form question
+    pub fn new(prompt: String) -> Self {
+        Self {
+            prompt,
+            schema: None,
+        }
+    }
+
+    /// Describe the expected answer with a JSON Schema
+    pub fn with_schema(mut self, schema: Value) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// The agent message `message_id` asking for the input, carrying the
+    /// request in its metadata
+    pub fn status_message(&self, task_id: &str, context_id: &str, message_id: String) -> Message {
+        let mut message = Message::agent_text(self.prompt.clone(), message_id);
+        message.task_id = Some(task_id.to_string());
+        message.context_id = Some(context_id.to_string());
+        let mut metadata = Map::new();
+        // Serializing a string and an optional JSON value cannot fail
+        if let Ok(request) = serde_json::to_value(self) {
+            metadata.insert(INPUT_REQUEST_METADATA_KEY.to_string(), request);
+        }
+        message.metadata = Some(metadata);
+        message
+    }
+}
+
+/// Most tasks a single admin/tasks/import request may carry
+pub const MAX_IMPORT_TASKS: usize = 1000;
+
+/// Parameters for the admin/tasks/import method.
+///
+/// Tasks are stored exactly as given, in whatever state they are in, without
+/// passing through the message handler.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImportTasksParams {
+    /// Tasks to import, each with its full history and artifacts. Each is
+    /// decoded on its own, so a malformed record fails only that task.
+    pub tasks: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+}
+
+/// Outcome of importing one task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskImportOutcome {
+    /// ID of the task in the request
+    pub id: String,
+    /// Whether the task was stored
+    pub imported: bool,
+    /// Why the task was not stored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::domain::protocols::JSONRPCError>,
+}
+
+/// Result object for the admin/tasks/import method.
+///
+/// A task that fails validation or storage is reported here without
+/// stopping the tasks after it.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImportTasksResult {
+    /// One outcome per requested task, in request order
+    pub results: Vec<TaskImportOutcome>,
+    /// Number of tasks stored
+    pub imported: usize,
+    /// Number of tasks rejected
+    pub failed: usize,
+}
+
+impl ImportTasksResult {
+    /// Record whether the task `id` was stored
+    pub fn record(&mut self, id: &str, outcome: Result<(), A2AError>) {
+        let error = match outcome {
+            Ok(()) => {
+                self.imported += 1;
+                None
+            }
+            Err(e) => {
+                self.failed += 1;
+                Some(e.into())
+            }
+        };
+        self.results.push(TaskImportOutcome {
+            id: id.to_string(),
+            imported: error.is_none(),
+            error,
+        });
+    }
+}
+
+/// Parameters for the tasks/getOrCreate method.
+///
+/// Opens the task `id` if it exists and creates it otherwise, atomically, so
+/// concurrent callers with the same ID all end up with the same task.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GetOrCreateTaskParams {
+    /// Task ID
+    pub id: String,
+    /// Context of a created task, `default` when not given; ignored if the
+    /// task exists
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contextId")]
+    pub context_id: Option<String>,
+    /// First message of a created task, recorded in its history without
+    /// being handled; ignored if the task exists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+    /// Parts of the task to leave out of the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<TaskField>,
+}
+
+/// Result object for the tasks/getOrCreate method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetOrCreateTaskResult {
+    /// The existing or created task
+    pub task: Task,
+    /// Whether this call created the task
+    pub created: bool,
+}
+
+/// Result object for the tasks/artifacts/list method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTaskArtifactsResult {
+    /// The task's artifacts in the order they were produced
+    pub artifacts: Vec<ArtifactSummary>,
+}
+
+/// Number of parts a tasks/artifacts/get call returns when no limit is given
+pub const DEFAULT_ARTIFACT_PART_LIMIT: usize = 1;
+
+/// Parameters for the tasks/artifacts/get method.
+///
+/// An artifact's content is read a page of parts at a time, so large
+/// artifacts such as generated documents can be streamed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskArtifactParams {
+    /// Task ID
+    pub id: String,
+    /// Artifact ID
+    pub artifact_id: String,
+    /// Index of the first part to return (default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_offset: Option<usize>,
+    /// Maximum number of parts to return (default
+    /// [`DEFAULT_ARTIFACT_PART_LIMIT`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+}
+
+/// Result object for the tasks/artifacts/get method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskArtifactResult {
+    /// The artifact, with only the requested page of its parts
+    pub artifact: Artifact,
+    /// Offset of the next page, absent once the last part was returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_part_offset: Option<usize>,
+}
+
+impl GetTaskArtifactResult {
+    /// The page of `artifact` that `params` asks for
+    pub fn page(artifact: &Artifact, params: &GetTaskArtifactParams) -> Self {
+        let offset = params.part_offset.unwrap_or(0).min(artifact.parts.len());
+        let limit = params
+            .part_limit
+            .unwrap_or(DEFAULT_ARTIFACT_PART_LIMIT)
+            .max(1);
+        let end = offset.saturating_add(limit).min(artifact.parts.len());
+        Self {
+            artifact: Artifact {
+                parts: artifact.parts[offset..end].to_vec(),
+                ..artifact.clone()
+            },
+            next_part_offset: (end < artifact.parts.len()).then_some(end),
+        }
+    }
+}
+
+/// Result object for the tasks/events method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTaskEventsResult {
+    /// Events in this page, ordered by sequence number
+    pub events: Vec<crate::domain::events::TaskEventRecord>,
+    /// Total number of events recorded for the task
+    #[serde(rename = "totalSize")]
+    pub total_size: i32,
+    /// Maximum number of events in this response
+    #[serde(rename = "pageSize")]
+    pub page_size: i32,
+    /// Token for next page (empty string if no more results)
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: String,
+}
+
 /// Parameters for getting a specific push notification config (v0.3.0).
 ///
 /// Enhanced version that allows retrieving a specific config by ID,
@@ -311,19 +1137,167 @@ pub struct DeleteTaskPushNotificationConfigParams {
 impl Task {
     /// Create a new task with the given ID in the submitted state
     pub fn new(id: String, context_id: String) -> Self {
+        Self::new_at(id, context_id, Utc::now())
+    }
+
+    /// Create a new task in the submitted state, stamped with `created_at`
+    pub fn new_at(id: String, context_id: String, created_at: DateTime<Utc>) -> Self {
         Self {
             id,
             context_id,
             status: TaskStatus {
                 state: TaskState::Submitted,
                 message: None,
-                timestamp: Some(Utc::now()),
+                timestamp: Some(created_at),
             },
             artifacts: None,
             history: None,
             metadata: None,
+            result: None,
             kind: "task".to_string(),
+            version: 1,
+            tags: Vec::new(),
+            reference_task_ids: Vec::new(),
+            history_cursor: None,
+        }
+    }
+
+    /// Start a new task with `message` as its status message and first
+    /// history entry. The message is part of the task as created rather than
+    /// a change to it, so the version stays the same.
+    pub fn with_initial_message(mut self, message: Message) -> Self {
+        if let Some(references) = &message.reference_task_ids {
+            self.add_references(references);
         }
+        self.status.message = Some(message.clone());
+        self.history = Some(vec![message]);
+        self
+    }
+
+    /// Fail with [`A2AError::VersionConflict`] unless the task is at `expected_version`
+    pub fn check_version(&self, expected_version: u64) -> Result<(), A2AError> {
+        if self.version == expected_version {
+            Ok(())
+        } else {
+            Err(A2AError::VersionConflict {
+                task_id: self.id.clone(),
+                expected: expected_version,
+                actual: self.version,
+            })
+        }
+    }
+
+    /// Fail with [`A2AError::InvalidParams`] once the task is in a terminal
+    /// state, since it can no longer pause for input
+    pub fn check_can_request_input(&self) -> Result<(), A2AError> {
+        if self.status.state.is_terminal() {
+            return Err(A2AError::InvalidParams(format!(
+                "Task {} is {} and cannot request input",
+                self.id, self.status.state
+            )));
+        }
+        Ok(())
+    }
+
+    /// Add tags, ignoring ones the task already carries.
+    ///
+    /// Fails without changing the task if any tag is invalid or the task would
+    /// end up with more than [`MAX_TAGS_PER_TASK`] tags.
+    pub fn add_tags(&mut self, tags: &[String]) -> Result<(), A2AError> {
+        for tag in tags {
+            validate_tag(tag)?;
+        }
+        let mut merged = self.tags.clone();
+        merged.extend(tags.iter().cloned());
+        merged.sort();
+        merged.dedup();
+        if merged.len() > MAX_TAGS_PER_TASK {
+            return Err(A2AError::ValidationError {
+                field: "tags".to_string(),
+                message: format!("A task may carry at most {} tags", MAX_TAGS_PER_TASK),
+            });
+        }
+        self.tags = merged;
+        Ok(())
+    }
+
+    /// Record references to other tasks, ignoring ones already recorded and
+    /// references to the task itself
+    pub fn add_references(&mut self, task_ids: &[String]) {
+        let mut merged = self.reference_task_ids.clone();
+        merged.extend(task_ids.iter().filter(|id| **id != self.id).cloned());
+        merged.sort();
+        merged.dedup();
+        self.reference_task_ids = merged;
+    }
+
+    /// Remove tags; tags the task does not carry are ignored
+    pub fn remove_tags(&mut self, tags: &[String]) {
+        self.tags.retain(|tag| !tags.contains(tag));
+    }
+
+    /// The structured result, once the task has reached a terminal state
+    pub fn final_result(&self) -> Option<TaskResult> {
+        self.result
+            .clone()
+            .filter(|_| self.status.state.is_terminal())
+    }
+
+    /// Summaries of the artifacts the agent produced, once the task has
+    /// reached a terminal state
+    pub fn final_artifacts(&self) -> Option<Vec<ArtifactSummary>> {
+        let artifacts = self.artifacts.as_deref().filter(|a| !a.is_empty())?;
+        self.status
+            .state
+            .is_terminal()
+            .then(|| artifacts.iter().map(ArtifactSummary::from).collect())
+    }
+
+    /// The artifact `artifact_id`, if the task has it
+    pub fn artifact(&self, artifact_id: &str) -> Option<&Artifact> {
+        self.artifacts
+            .as_deref()?
+            .iter()
+            .find(|artifact| artifact.artifact_id == artifact_id)
+    }
+
+    /// Record an artifact, replacing the one with the same ID if there is one
+    pub fn put_artifact(&mut self, artifact: Artifact) {
+        let existing = self
+            .artifacts
+            .iter_mut()
+            .flatten()
+            .find(|existing| existing.artifact_id == artifact.artifact_id);
+        match existing {
+            Some(existing) => {
+                *existing = artifact;
+                self.version += 1;
+            }
+            None => self.add_artifact(artifact),
+        }
+    }
+
+    /// What the agent is waiting for, while the task is input-required.
+    ///
+    /// A handler resuming the task reads this before it records the client's
+    /// answer, since moving the task out of input-required clears it.
+    pub fn input_request(&self) -> Option<InputRequest> {
+        self.status.input_request()
+    }
+
+    /// Who canceled the task and why, once it is canceled
+    pub fn cancellation(&self) -> Option<TaskCancellation> {
+        if self.status.state != TaskState::Canceled {
+            return None;
+        }
+        let details = self
+            .status
+            .message
+            .as_ref()?
+            .metadata
+            .as_ref()?
+            .get(CANCELLATION_METADATA_KEY)?;
+        serde_json::from_value(details.clone()).ok()
     }
 
     /// Create a new task with the given ID and context ID in the submitted state
@@ -332,25 +1306,39 @@ impl Task {
     }
 
     /// Update the task status
+    pub fn update_status(&mut self, state: TaskState, message: Option<Message>) {
+        self.update_status_at(state, message, Utc::now());
+    }
+
+    /// Update the task status, stamping the new status with `updated_at`
     #[cfg_attr(feature = "tracing", instrument(skip(self, message), fields(
         task.id = %self.id,
         task.old_state = ?self.status.state,
         task.new_state = ?state,
         task.has_message = message.is_some()
     )))]
-    pub fn update_status(&mut self, state: TaskState, message: Option<Message>) {
+    pub fn update_status_at(
+        &mut self,
+        state: TaskState,
+        message: Option<Message>,
+        updated_at: DateTime<Utc>,
+    ) {
         #[cfg(feature = "tracing")]
         tracing::info!("Updating task status");
 
         // Set the new status
+        self.version += 1;
         self.status = TaskStatus {
             state: state.clone(),
             message: message.clone(),
-            timestamp: Some(Utc::now()),
+            timestamp: Some(updated_at),
         };
 
         // Add message to history if provided and state_transition_history is enabled
         if let Some(msg) = message {
+            if let Some(references) = &msg.reference_task_ids {
+                self.add_references(references);
+            }
             if let Some(history) = &mut self.history {
                 #[cfg(feature = "tracing")]
                 tracing::info!(
@@ -376,6 +1364,61 @@ impl Task {
         tracing::info!("Task status updated successfully");
     }
 
+    /// Get a copy of this task with only the history entries after `since`,
+    /// and `history_cursor` pointing after its last entry.
+    ///
+    /// History is only ever appended to, so a cursor is the number of entries
+    /// it covers and stays valid while other messages arrive. A cursor that is
+    /// not a number, or lies beyond the history, is a validation error.
+    pub fn with_history_since(&self, since: Option<&str>) -> Result<Self, A2AError> {
+        self.with_history_page(since, None)
+    }
+
+    /// Like [`Task::with_history_since`], but keep at most `limit` entries:
+    /// the first ones after `since`, with `history_cursor` pointing after the
+    /// last one kept so the next page starts where this one ends.
+    pub fn with_history_page(
+        &self,
+        since: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Self, A2AError> {
+        let total = self.history.as_ref().map_or(0, Vec::len);
+        let position = match since {
+            Some(since) => {
+                let position: usize = since.parse().map_err(|_| A2AError::ValidationError {
+                    field: "since".to_string(),
+                    message: format!("'{}' is not a history cursor", since),
+                })?;
+                if position > total {
+                    return Err(A2AError::ValidationError {
+                        field: "since".to_string(),
+                        message: format!(
+                            "cursor {} is beyond the task's {} history entries",
+                            position, total
+                        ),
+                    });
+                }
+                position
+            }
+            None => 0,
+        };
+        let end = limit.map_or(total, |limit| total.min(position + limit as usize));
+
+        let mut task = self.clone();
+        task.history_cursor = Some(end.to_string());
+        if since.is_some() || limit.is_some() {
+            task.history = Some(
+                self.history
+                    .as_deref()
+                    .unwrap_or_default()
+                    .get(position..end)
+                    .unwrap_or_default()
+                    .to_vec(),
+            );
+        }
+        Ok(task)
+    }
+
     /// Get a copy of this task with history limited to the specified length
     ///
     /// This method follows the A2A spec for history truncation:
@@ -441,6 +1484,90 @@ impl Task {
         task_copy
     }
 
+    /// Leave the `excluded` fields out of this task.
+    ///
+    /// A file part whose bytes are excluded keeps its name and MIME type, and
+    /// its bytes are replaced by a `urn:a2a:task:` URI naming where the part
+    /// is in the task, so it is still well formed; fetch the task without
+    /// excluding [`TaskField::FileBytes`] to get them.
+    pub fn without_fields(mut self, excluded: &[TaskField]) -> Self {
+        let exclusions = TaskExclusions::new(&self.id, excluded);
+        if exclusions.history {
+            self.history = None;
+            self.history_cursor = None;
+        }
+        if exclusions.artifacts {
+            self.artifacts = None;
+        }
+        if !exclusions.metadata && !exclusions.bytes {
+            return self;
+        }
+        if exclusions.metadata {
+            self.metadata = None;
+        }
+        for message in self.history.iter_mut().flatten() {
+            exclusions.strip_message(message);
+        }
+        if let Some(message) = &mut self.status.message {
+            exclusions.strip_message(message);
+        }
+        for artifact in self.artifacts.iter_mut().flatten() {
+            exclusions.strip_artifact(artifact);
+        }
+        self
+    }
+
+    /// Copy this task without the `excluded` fields, keeping at most the
+    /// latest `history_length` history entries.
+    ///
+    /// Like [`Task::without_fields`] on a clone, except that what is left
+    /// out is never copied.
+    pub fn copy_without_fields(&self, history_length: Option<u32>, excluded: &[TaskField]) -> Self {
+        let exclusions = TaskExclusions::new(&self.id, excluded);
+        let history = match exclusions.history_length(history_length) {
+            Some(0) => None,
+            limit => self.history.as_ref().map(|history| {
+                let kept = limit.map_or(history.len(), |limit| history.len().min(limit as usize));
+                history[history.len() - kept..]
+                    .iter()
+                    .map(|message| exclusions.copy_message(message))
+                    .collect()
+            }),
+        };
+        let artifacts = self
+            .artifacts
+            .as_ref()
+            .filter(|_| !exclusions.artifacts)
+            .map(|artifacts| {
+                artifacts
+                    .iter()
+                    .map(|artifact| exclusions.copy_artifact(artifact))
+                    .collect()
+            });
+        Task {
+            id: self.id.clone(),
+            context_id: self.context_id.clone(),
+            status: TaskStatus {
+                state: self.status.state.clone(),
+                message: self
+                    .status
+                    .message
+                    .as_ref()
+                    .map(|message| exclusions.copy_message(message)),
+                timestamp: self.status.timestamp,
+            },
+            artifacts,
+            history,
+            metadata: exclusions.copy_metadata(&self.metadata),
+            result: self.result.clone(),
+            kind: self.kind.clone(),
+            version: self.version,
+            tags: self.tags.clone(),
+            reference_task_ids: self.reference_task_ids.clone(),
+            history_cursor: self.history_cursor.clone().filter(|_| !exclusions.history),
+        }
+    }
+
     /// Add an artifact to the task
     #[cfg_attr(feature = "tracing", instrument(skip(self, artifact), fields(
         task.id = %self.id,
@@ -448,6 +1575,7 @@ impl Task {
         artifacts.count = self.artifacts.as_ref().map(|a| a.len()).unwrap_or(0)
     )))]
     pub fn add_artifact(&mut self, artifact: Artifact) {
+        self.version += 1;
         if let Some(artifacts) = &mut self.artifacts {
             #[cfg(feature = "tracing")]
             tracing::debug!("Adding artifact to existing list");
@@ -520,4 +1648,93 @@ impl Task {
         tracing::debug!("Task validation successful");
         Ok(())
     }
+
+    /// Validate a task arriving through an import, beyond [`Task::validate`].
+    ///
+    /// Imported tasks skip the handler, so this checks what the handler would
+    /// otherwise guarantee: the state is a known one, a result is only present
+    /// once the task has finished, messages belong to this task, the status
+    /// message if recorded in history is its last entry, and every part of the
+    /// artifacts is well formed.
+    pub fn validate_for_import(&self) -> Result<(), A2AError> {
+        self.validate()?;
+
+        let invalid = |field: &str, message: String| A2AError::ValidationError {
+            field: field.to_string(),
+            message,
+        };
+        if self.id.is_empty() {
+            return Err(invalid("id", "Task ID cannot be empty".to_string()));
+        }
+        if self.version == 0 {
+            return Err(invalid(
+                "version",
+                "Task version must be at least 1".to_string(),
+            ));
+        }
+        if self.status.state == TaskState::Unknown {
+            return Err(invalid(
+                "status.state",
+                "Cannot import a task in the unknown state".to_string(),
+            ));
+        }
+        if self.result.is_some() && !self.status.state.is_terminal() {
+            return Err(invalid(
+                "result",
+                format!(
+                    "Only a finished task can carry a result, not one in state {:?}",
+                    self.status.state
+                ),
+            ));
+        }
+
+        let history = self.history.as_deref().unwrap_or_default();
+        for message in history.iter().chain(self.status.message.as_ref()) {
+            if message.task_id.as_ref().is_some_and(|id| *id != self.id) {
+                return Err(invalid(
+                    "history",
+                    format!("Message '{}' belongs to another task", message.message_id),
+                ));
+            }
+        }
+        if let Some(status_message) = &self.status.message {
+            let position = history
+                .iter()
+                .position(|message| message.message_id == status_message.message_id);
+            if position.is_some_and(|position| position + 1 != history.len()) {
+                return Err(invalid(
+                    "history",
+                    format!(
+                        "Status message '{}' must be the last history entry",
+                        status_message.message_id
+                    ),
+                ));
+            }
+        }
+
+        for artifact in self.artifacts.as_deref().unwrap_or_default() {
+            if artifact.parts.is_empty() {
+                return Err(invalid(
+                    "artifacts",
+                    format!("Artifact '{}' has no parts", artifact.artifact_id),
+                ));
+            }
+            for part in &artifact.parts {
+                if let Part::File { file, .. } = part {
+                    file.validate()?;
+                }
+            }
+        }
+
+        if self.tags.len() > MAX_TAGS_PER_TASK {
+            return Err(invalid(
+                "tags",
+                format!("A task may carry at most {} tags", MAX_TAGS_PER_TASK),
+            ));
+        }
+        for tag in &self.tags {
+            validate_tag(tag)?;
+        }
+        Ok(())
+    }
 }
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use super::error_catalog::{ErrorDetail, codes};
+
 /// Standard JSON-RPC error codes
 pub const PARSE_ERROR: i32 = -32700;
 pub const INVALID_REQUEST: i32 = -32600;
@@ -18,6 +20,41 @@ pub const AUTHENTICATED_EXTENDED_CARD_NOT_CONFIGURED: i32 = -32007;
 
 /// Custom application-specific error codes (outside spec range)
 pub const DATABASE_ERROR: i32 = -32100;
+pub const VERSION_CONFLICT: i32 = -32101;
+pub const METHOD_NOT_AUTHORIZED: i32 = -32102;
+pub const SERVER_BUSY: i32 = -32103;
+pub const UNAVAILABLE: i32 = -32104;
+pub const STORAGE_CORRUPT: i32 = -32105;
+pub const STORAGE_CONFLICT: i32 = -32106;
+
+/// The kind of a storage failure, telling callers whether retrying can help
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageFailure {
+    /// The backend is out of reach, for example down or out of connections;
+    /// the same request may succeed later
+    Unavailable,
+    /// Stored data could not be read back; retrying will not help
+    Corrupt,
+    /// The write clashed with data already stored, such as a duplicate key
+    Conflict,
+}
+
+impl StorageFailure {
+    /// The kind of storage failure a JSON-RPC error code reports, if any
+    pub fn from_code(code: i32) -> Option<Self> {
+        match code {
+            UNAVAILABLE => Some(StorageFailure::Unavailable),
+            STORAGE_CORRUPT => Some(StorageFailure::Corrupt),
+            STORAGE_CONFLICT => Some(StorageFailure::Conflict),
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed when retried later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, StorageFailure::Unavailable)
+    }
+}
 
 /// Error type for the A2A protocol operations
 #[derive(Error, Debug)]
@@ -41,12 +78,36 @@ pub enum A2AError {
     #[error("Method not found: {0}")]
     MethodNotFound(String),
 
+    /// The caller's role may not call this method
+    #[error("Method not authorized: {0}")]
+    MethodNotAuthorized(String),
+
     #[error("Task not found: {0}")]
     TaskNotFound(String),
 
     #[error("Task not cancelable: {0}")]
     TaskNotCancelable(String),
 
+    /// The task changed since the client read it; refetch and retry
+    #[error("Task {task_id} is at version {actual}, expected {expected}")]
+    VersionConflict {
+        task_id: String,
+        expected: u64,
+        actual: u64,
+    },
+
+    /// The server is rate limiting or at capacity; retry after `retry_after`
+    #[error("Server busy: {reason}")]
+    ServerBusy {
+        reason: String,
+        retry_after: Option<std::time::Duration>,
+    },
+
+    /// Something the server depends on, such as its database, is out of
+    /// reach for now; the same request may succeed when retried
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
+
     #[error("Push notification not supported")]
     PushNotificationNotSupported,
 
@@ -68,23 +129,62 @@ pub enum A2AError {
     #[error("Validation error in {field}: {message}")]
     ValidationError { field: String, message: String },
 
+    /// A failure the user can act on, described by a stable code and parameters
+    #[error("{0}")]
+    UserError(ErrorDetail),
+
     #[error("Database error: {0}")]
     DatabaseError(String),
 
+    /// Stored data could not be read back, see [`StorageFailure::Corrupt`]
+    #[error("Storage corrupt: {0}")]
+    StorageCorrupt(String),
+
+    /// A write clashed with stored data, see [`StorageFailure::Conflict`]
+    #[error("Storage conflict: {0}")]
+    StorageConflict(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
 
 impl A2AError {
+    /// Whether the failure is temporary, so the same request may succeed
+    /// when retried later
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            A2AError::ServerBusy { .. } | A2AError::Unavailable(_) => true,
+            A2AError::JsonRpc { code, .. } => matches!(*code, SERVER_BUSY | UNAVAILABLE),
+            _ => false,
+        }
+    }
+
+    /// The kind of storage failure this is, if it is one.
+    ///
+    /// Errors that crossed the wire are classified by their JSON-RPC code.
+    pub fn storage_failure(&self) -> Option<StorageFailure> {
+        match self {
+            A2AError::Unavailable(_) => Some(StorageFailure::Unavailable),
+            A2AError::StorageCorrupt(_) => Some(StorageFailure::Corrupt),
+            A2AError::StorageConflict(_) => Some(StorageFailure::Conflict),
+            A2AError::JsonRpc { code, .. } => StorageFailure::from_code(*code),
+            _ => None,
+        }
+    }
+
     /// Convert an A2AError to a JSON-RPC error value
     pub fn to_jsonrpc_error(&self) -> serde_json::Value {
         let (code, message) = match self {
             A2AError::JsonParse(_) => (PARSE_ERROR, "Invalid JSON payload"),
             A2AError::InvalidRequest(_) => (INVALID_REQUEST, "Request payload validation error"),
             A2AError::MethodNotFound(_) => (METHOD_NOT_FOUND, "Method not found"),
+            A2AError::MethodNotAuthorized(_) => (METHOD_NOT_AUTHORIZED, "Method not authorized"),
             A2AError::InvalidParams(_) => (INVALID_PARAMS, "Invalid parameters"),
             A2AError::TaskNotFound(_) => (TASK_NOT_FOUND, "Task not found"),
             A2AError::TaskNotCancelable(_) => (TASK_NOT_CANCELABLE, "Task cannot be canceled"),
+            A2AError::VersionConflict { .. } => (VERSION_CONFLICT, "Task version conflict"),
+            A2AError::ServerBusy { .. } => (SERVER_BUSY, "Server busy"),
+            A2AError::Unavailable(_) => (UNAVAILABLE, "Service unavailable"),
             A2AError::PushNotificationNotSupported => (
                 PUSH_NOTIFICATION_NOT_SUPPORTED,
                 "Push Notification is not supported",
@@ -101,7 +201,10 @@ impl A2AError {
                 "Authenticated Extended Card is not configured",
             ),
             A2AError::ValidationError { .. } => (INVALID_PARAMS, "Validation error"),
+            A2AError::UserError(_) => (INVALID_PARAMS, "Validation error"),
             A2AError::DatabaseError(_) => (DATABASE_ERROR, "Database error"),
+            A2AError::StorageCorrupt(_) => (STORAGE_CORRUPT, "Stored data is corrupt"),
+            A2AError::StorageConflict(_) => (STORAGE_CONFLICT, "Storage conflict"),
             A2AError::Internal(_) => (INTERNAL_ERROR, "Internal error"),
             _ => (INTERNAL_ERROR, "Internal error"),
         };
@@ -109,7 +212,95 @@ impl A2AError {
         serde_json::json!({
             "code": code,
             "message": message,
-            "data": null,
+            "data": self.error_detail(),
         })
     }
+
+    /// The stable error code and parameters describing this error to a user.
+    ///
+    /// Internal failures map to [`codes::INTERNAL`] without parameters so their
+    /// details never reach clients.
+    pub fn error_detail(&self) -> ErrorDetail {
+        match self {
+            A2AError::UserError(detail) => detail.clone(),
+            A2AError::JsonRpc { code, data, .. } => data
+                .as_ref()
+                .and_then(ErrorDetail::from_json_rpc_data)
+                .unwrap_or_else(|| ErrorDetail::new(code_for_jsonrpc(*code))),
+            A2AError::ValidationError { field, message } => {
+                ErrorDetail::field(codes::VALIDATION_INVALID, field.clone())
+                    .with_param("reason", message.clone())
+            }
+            A2AError::JsonParse(_) => ErrorDetail::new(codes::REQUEST_INVALID_JSON),
+            A2AError::InvalidRequest(_) => ErrorDetail::new(codes::REQUEST_INVALID),
+            A2AError::InvalidParams(_) => ErrorDetail::new(codes::REQUEST_INVALID_PARAMS),
+            A2AError::MethodNotFound(method) => ErrorDetail::new(codes::REQUEST_METHOD_NOT_FOUND)
+                .with_param("method", method.clone()),
+            A2AError::MethodNotAuthorized(method) => {
+                ErrorDetail::new(codes::REQUEST_METHOD_NOT_AUTHORIZED)
+                    .with_param("method", method.clone())
+            }
+            A2AError::TaskNotFound(task_id) => {
+                ErrorDetail::new(codes::TASK_NOT_FOUND).with_param("taskId", task_id.clone())
+            }
+            A2AError::TaskNotCancelable(task_id) => {
+                ErrorDetail::new(codes::TASK_NOT_CANCELABLE).with_param("taskId", task_id.clone())
+            }
+            A2AError::VersionConflict {
+                task_id,
+                expected,
+                actual,
+            } => ErrorDetail::new(codes::TASK_VERSION_CONFLICT)
+                .with_param("taskId", task_id.clone())
+                .with_param("expected", expected.to_string())
+                .with_param("actual", actual.to_string()),
+            A2AError::ServerBusy { retry_after, .. } => {
+                let detail = ErrorDetail::new(codes::SERVER_BUSY);
+                match retry_after {
+                    Some(delay) => detail.with_param("retryAfter", delay.as_secs().to_string()),
+                    None => detail,
+                }
+            }
+            A2AError::Unavailable(_) => ErrorDetail::new(codes::SERVER_UNAVAILABLE),
+            A2AError::StorageCorrupt(_) => ErrorDetail::new(codes::STORAGE_CORRUPT),
+            A2AError::StorageConflict(_) => ErrorDetail::new(codes::STORAGE_CONFLICT),
+            A2AError::PushNotificationNotSupported => ErrorDetail::new(codes::PUSH_NOT_SUPPORTED),
+            A2AError::UnsupportedOperation(_) => ErrorDetail::new(codes::OPERATION_UNSUPPORTED),
+            A2AError::ContentTypeNotSupported(content_type) => {
+                ErrorDetail::new(codes::CONTENT_UNSUPPORTED_TYPE)
+                    .with_param("contentType", content_type.clone())
+            }
+            A2AError::InvalidAgentResponse(_) => ErrorDetail::new(codes::AGENT_INVALID_RESPONSE),
+            A2AError::AuthenticatedExtendedCardNotConfigured => {
+                ErrorDetail::new(codes::CARD_NOT_CONFIGURED)
+            }
+            A2AError::Internal(_) | A2AError::DatabaseError(_) | A2AError::Io(_) => {
+                ErrorDetail::new(codes::INTERNAL)
+            }
+        }
+    }
+}
+
+/// Error code for a JSON-RPC error that carried no detail
+fn code_for_jsonrpc(code: i32) -> &'static str {
+    match code {
+        PARSE_ERROR => codes::REQUEST_INVALID_JSON,
+        INVALID_REQUEST => codes::REQUEST_INVALID,
+        METHOD_NOT_FOUND => codes::REQUEST_METHOD_NOT_FOUND,
+        METHOD_NOT_AUTHORIZED => codes::REQUEST_METHOD_NOT_AUTHORIZED,
+        INVALID_PARAMS => codes::REQUEST_INVALID_PARAMS,
+        TASK_NOT_FOUND => codes::TASK_NOT_FOUND,
+        TASK_NOT_CANCELABLE => codes::TASK_NOT_CANCELABLE,
+        VERSION_CONFLICT => codes::TASK_VERSION_CONFLICT,
+        SERVER_BUSY => codes::SERVER_BUSY,
+        UNAVAILABLE => codes::SERVER_UNAVAILABLE,
+        STORAGE_CORRUPT => codes::STORAGE_CORRUPT,
+        STORAGE_CONFLICT => codes::STORAGE_CONFLICT,
+        PUSH_NOTIFICATION_NOT_SUPPORTED => codes::PUSH_NOT_SUPPORTED,
+        UNSUPPORTED_OPERATION => codes::OPERATION_UNSUPPORTED,
+        CONTENT_TYPE_NOT_SUPPORTED => codes::CONTENT_UNSUPPORTED_TYPE,
+        INVALID_AGENT_RESPONSE => codes::AGENT_INVALID_RESPONSE,
+        AUTHENTICATED_EXTENDED_CARD_NOT_CONFIGURED => codes::CARD_NOT_CONFIGURED,
+        _ => codes::INTERNAL,
+    }
 }
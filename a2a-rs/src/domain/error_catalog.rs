@@ -0,0 +1,338 @@
+//! Stable error codes and localizable message templates
+//!
+//! Errors that a user may need to act on are described by an [`ErrorDetail`]: a
+//! stable, dotted error code plus named parameters. Servers send the detail in the
+//! `data` member of a JSON-RPC error and leave wording to the client, which renders
+//! it for the user's locale with an [`ErrorCatalog`].
+//!
+//! Templates refer to parameters as `{name}`. The value of a `field` parameter is
+//! itself looked up as `field.<value>` so field names can be translated too.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::LazyLock,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Stable error codes understood by [`ErrorCatalog`]
+pub mod codes {
+    /// A required field is missing or empty (`field`)
+    pub const VALIDATION_REQUIRED: &str = "validation.required";
+    /// A field that should hold a number doesn't (`field`)
+    pub const VALIDATION_INVALID_NUMBER: &str = "validation.invalid_number";
+    /// A numeric field is zero or negative (`field`)
+    pub const VALIDATION_NOT_POSITIVE: &str = "validation.not_positive";
+    /// A field holds a value outside the accepted set (`field`, `value`)
+    pub const VALIDATION_NOT_ALLOWED: &str = "validation.not_allowed";
+    /// A field doesn't match its expected format (`field`)
+    pub const VALIDATION_INVALID_FORMAT: &str = "validation.invalid_format";
+    /// Any other validation failure (`field`, `reason`)
+    pub const VALIDATION_INVALID: &str = "validation.invalid";
+    /// A message does not carry the input its skill requires (`skillId`,
+    /// `fields`, `reason`, and `missing` and `invalid` when there are such)
+    pub const VALIDATION_SCHEMA: &str = "validation.schema";
+    /// The request body is not valid JSON
+    pub const REQUEST_INVALID_JSON: &str = "request.invalid_json";
+    /// The request is malformed
+    pub const REQUEST_INVALID: &str = "request.invalid";
+    /// The requested method does not exist (`method`)
+    pub const REQUEST_METHOD_NOT_FOUND: &str = "request.method_not_found";
+    /// The caller may not call the requested method (`method`)
+    pub const REQUEST_METHOD_NOT_AUTHORIZED: &str = "request.method_not_authorized";
+    /// The request parameters are invalid
+    pub const REQUEST_INVALID_PARAMS: &str = "request.invalid_params";
+    /// The task does not exist (`taskId`)
+    pub const TASK_NOT_FOUND: &str = "task.not_found";
+    /// The task can no longer be canceled (`taskId`)
+    pub const TASK_NOT_CANCELABLE: &str = "task.not_cancelable";
+    /// The task changed since the client read it (`taskId`, `expected`, `actual`)
+    pub const TASK_VERSION_CONFLICT: &str = "task.version_conflict";
+    /// The task did not finish in time (`taskId`, `timeoutSeconds`)
+    pub const TASK_TIMEOUT: &str = "task.timeout";
+    /// A task has no artifact with the requested ID (`taskId`, `artifactId`)
+    pub const ARTIFACT_NOT_FOUND: &str = "artifact.not_found";
+    /// The message names a skill the agent does not have (`skillId`)
+    pub const SKILL_NOT_FOUND: &str = "skill.not_found";
+    /// No skill of the agent accepts the message
+    pub const SKILL_NO_MATCH: &str = "skill.no_match";
+    /// The agent does not support push notifications
+    pub const PUSH_NOT_SUPPORTED: &str = "push.not_supported";
+    /// No push notification was dead-lettered with the requested ID (`id`)
+    pub const PUSH_DEAD_LETTER_NOT_FOUND: &str = "push.dead_letter_not_found";
+    /// The agent does not support the operation
+    pub const OPERATION_UNSUPPORTED: &str = "operation.unsupported";
+    /// The content type is not accepted (`contentType`)
+    pub const CONTENT_UNSUPPORTED_TYPE: &str = "content.unsupported_type";
+    /// The agent produced a response that could not be used
+    pub const AGENT_INVALID_RESPONSE: &str = "agent.invalid_response";
+    /// No authenticated extended card is configured
+    pub const CARD_NOT_CONFIGURED: &str = "card.extended_not_configured";
+    /// The server is rate limiting or at capacity (`retryAfter` seconds, when known)
+    pub const SERVER_BUSY: &str = "server.busy";
+    /// A resource the server depends on is temporarily unavailable
+    pub const SERVER_UNAVAILABLE: &str = "server.unavailable";
+    /// Stored data could not be read back
+    pub const STORAGE_CORRUPT: &str = "storage.corrupt";
+    /// A write clashed with data already stored
+    pub const STORAGE_CONFLICT: &str = "storage.conflict";
+    /// An unexpected failure; details stay in the server logs
+    pub const INTERNAL: &str = "internal";
+}
+
+/// Locale used when no template exists for the requested one
+pub const DEFAULT_LOCALE: &str = "en";
+
+const ENGLISH_TEMPLATES: &[(&str, &str)] = &[
+    (codes::VALIDATION_REQUIRED, "{field} is required"),
+    (codes::VALIDATION_INVALID_NUMBER, "{field} must be a number"),
+    (
+        codes::VALIDATION_NOT_POSITIVE,
+        "{field} must be greater than zero",
+    ),
+    (
+        codes::VALIDATION_NOT_ALLOWED,
+        "{field} '{value}' is not allowed",
+    ),
+    (
+        codes::VALIDATION_INVALID_FORMAT,
+        "{field} has an invalid format",
+    ),
+    (codes::VALIDATION_INVALID, "{field} is invalid: {reason}"),
+    (
+        codes::VALIDATION_SCHEMA,
+        "The message does not provide what '{skillId}' needs: {reason}",
+    ),
+    (codes::REQUEST_INVALID_JSON, "The request is not valid JSON"),
+    (codes::REQUEST_INVALID, "The request is invalid"),
+    (
+        codes::REQUEST_METHOD_NOT_FOUND,
+        "Method '{method}' was not found",
+    ),
+    (
+        codes::REQUEST_METHOD_NOT_AUTHORIZED,
+        "You are not allowed to call '{method}'",
+    ),
+    (
+        codes::REQUEST_INVALID_PARAMS,
+        "The request parameters are invalid",
+    ),
+    (codes::TASK_NOT_FOUND, "Task '{taskId}' was not found"),
+    (
+        codes::TASK_NOT_CANCELABLE,
+        "Task '{taskId}' can no longer be canceled",
+    ),
+    (
+        codes::TASK_VERSION_CONFLICT,
+        "Task '{taskId}' was changed by someone else, reload it and try again",
+    ),
+    (
+        codes::TASK_TIMEOUT,
+        "Task '{taskId}' did not finish within {timeoutSeconds} seconds",
+    ),
+    (
+        codes::ARTIFACT_NOT_FOUND,
+        "Task '{taskId}' has no artifact '{artifactId}'",
+    ),
+    (codes::SKILL_NOT_FOUND, "The agent has no skill '{skillId}'"),
+    (
+        codes::SKILL_NO_MATCH,
+        "None of the agent's skills can handle this message",
+    ),
+    (
+        codes::PUSH_NOT_SUPPORTED,
+        "Push notifications are not supported",
+    ),
+    (
+        codes::PUSH_DEAD_LETTER_NOT_FOUND,
+        "No undelivered push notification '{id}' was found",
+    ),
+    (
+        codes::OPERATION_UNSUPPORTED,
+        "This operation is not supported",
+    ),
+    (
+        codes::CONTENT_UNSUPPORTED_TYPE,
+        "Content type '{contentType}' is not supported",
+    ),
+    (
+        codes::AGENT_INVALID_RESPONSE,
+        "The agent returned an invalid response",
+    ),
+    (
+        codes::CARD_NOT_CONFIGURED,
+        "No extended agent card is configured",
+    ),
+    (
+        codes::SERVER_BUSY,
+        "The server is busy, please try again shortly",
+    ),
+    (
+        codes::SERVER_UNAVAILABLE,
+        "The service is temporarily unavailable, please try again shortly",
+    ),
+    (
+        codes::STORAGE_CORRUPT,
+        "Stored data could not be read, please contact support",
+    ),
+    (
+        codes::STORAGE_CONFLICT,
+        "The change conflicts with data already stored",
+    ),
+    (
+        codes::INTERNAL,
+        "Something went wrong, please try again later",
+    ),
+];
+
+static DEFAULT_CATALOG: LazyLock<ErrorCatalog> = LazyLock::new(ErrorCatalog::new);
+
+/// A user-facing error: a stable code and the parameters its message needs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorDetail {
+    /// Stable, dotted error code, e.g. `validation.invalid_number`
+    pub error_code: String,
+    /// Named values substituted into the message template
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+}
+
+impl ErrorDetail {
+    /// Create a detail with no parameters
+    pub fn new(error_code: impl Into<String>) -> Self {
+        Self {
+            error_code: error_code.into(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Shorthand for a validation error on `field`
+    pub fn field(error_code: impl Into<String>, field: impl Into<String>) -> Self {
+        Self::new(error_code).with_param("field", field)
+    }
+
+    /// Add a template parameter
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Read a detail from the `data` member of a JSON-RPC error, if it holds one
+    pub fn from_json_rpc_data(data: &serde_json::Value) -> Option<Self> {
+        data.get("errorCode")?;
+        serde_json::from_value(data.clone()).ok()
+    }
+
+    /// Render the message in the default locale
+    pub fn message(&self) -> String {
+        DEFAULT_CATALOG.render(self, DEFAULT_LOCALE)
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+/// Message templates for error codes, per locale
+///
+/// A new catalog holds English templates for every code in [`codes`]; other
+/// locales are added with [`with_locale`](Self::with_locale).
+#[derive(Debug, Clone)]
+pub struct ErrorCatalog {
+    locales: HashMap<String, HashMap<String, String>>,
+}
+
+impl Default for ErrorCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorCatalog {
+    /// Create a catalog with the built-in English templates
+    pub fn new() -> Self {
+        Self {
+            locales: HashMap::new(),
+        }
+        .with_locale(DEFAULT_LOCALE, ENGLISH_TEMPLATES.iter().copied())
+    }
+
+    /// Add or replace the template for `code` in `locale`
+    pub fn with_template(
+        mut self,
+        locale: impl Into<String>,
+        code: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.locales
+            .entry(locale.into().to_ascii_lowercase())
+            .or_default()
+            .insert(code.into(), template.into());
+        self
+    }
+
+    /// Add or replace several templates for `locale`
+    pub fn with_locale<C, T>(
+        mut self,
+        locale: impl Into<String>,
+        templates: impl IntoIterator<Item = (C, T)>,
+    ) -> Self
+    where
+        C: Into<String>,
+        T: Into<String>,
+    {
+        let entry = self
+            .locales
+            .entry(locale.into().to_ascii_lowercase())
+            .or_default();
+        for (code, template) in templates {
+            entry.insert(code.into(), template.into());
+        }
+        self
+    }
+
+    /// Look up a template, falling back from `fr-CA` to `fr` to the default locale
+    pub fn template(&self, code: &str, locale: &str) -> Option<&str> {
+        let locale = locale.to_ascii_lowercase();
+        let language = locale.split(['-', '_']).next().unwrap_or_default();
+
+        [locale.as_str(), language, DEFAULT_LOCALE]
+            .into_iter()
+            .find_map(|candidate| self.locales.get(candidate)?.get(code))
+            .map(String::as_str)
+    }
+
+    /// Render a detail's message for `locale`.
+    ///
+    /// Unknown codes render as the code itself and unknown parameters are left
+    /// as `{name}`, so a newer server never produces an empty message.
+    pub fn render(&self, detail: &ErrorDetail, locale: &str) -> String {
+        let Some(template) = self.template(&detail.error_code, locale) else {
+            return detail.error_code.clone();
+        };
+
+        let mut message = template.to_string();
+        for (name, value) in &detail.params {
+            let value = match name.as_str() {
+                "field" => self
+                    .template(&format!("field.{}", value), locale)
+                    .unwrap_or(value),
+                _ => value,
+            };
+            message = message.replace(&format!("{{{}}}", name), value);
+        }
+        capitalize(message)
+    }
+}
+
+fn capitalize(message: String) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) if first.is_lowercase() => first.to_uppercase().chain(chars).collect(),
+        _ => message,
+    }
+}
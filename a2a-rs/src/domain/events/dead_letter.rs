@@ -0,0 +1,102 @@
+//! Push notifications that could not be delivered
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::webhook::WebhookEvent;
+
+/// One try at delivering a push notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    /// When the request was sent
+    #[serde(rename = "attemptedAt")]
+    pub attempted_at: DateTime<Utc>,
+    /// HTTP status the webhook answered with, absent if it never answered
+    #[serde(skip_serializing_if = "Option::is_none", rename = "statusCode")]
+    pub status_code: Option<u16>,
+    /// Why the attempt failed, absent if it succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A push notification set aside after every delivery attempt failed.
+///
+/// Dead letters keep the event as it would have been delivered, so an admin
+/// can inspect the failures and requeue it once the webhook is fixed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// ID of the dead letter, used to requeue or purge it
+    pub id: String,
+    /// ID of the task the notification is about
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    /// Webhook the notification was sent to
+    pub url: String,
+    /// The undelivered event
+    pub event: WebhookEvent,
+    /// Every delivery attempt, oldest first, including those of requeues
+    pub attempts: Vec<DeliveryAttempt>,
+    /// Why the last attempt failed
+    #[serde(rename = "lastError")]
+    pub last_error: String,
+    /// When the notification was last set aside
+    #[serde(rename = "deadLetteredAt")]
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// Parameters for the admin/pushNotifications/deadLetters/list method
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ListDeadLettersParams {
+    /// Only dead letters about this task
+    #[serde(skip_serializing_if = "Option::is_none", rename = "taskId")]
+    pub task_id: Option<String>,
+}
+
+/// Parameters for the admin/pushNotifications/deadLetters/requeue method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequeueDeadLetterParams {
+    /// ID of the dead letter to deliver again
+    pub id: String,
+}
+
+/// Result object for the admin/pushNotifications/deadLetters/requeue method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequeueDeadLetterResult {
+    /// Whether the webhook accepted the notification this time
+    pub delivered: bool,
+    /// The dead letter with the attempts of this requeue appended; it stays
+    /// dead-lettered unless it was delivered
+    #[serde(rename = "deadLetter")]
+    pub dead_letter: DeadLetter,
+}
+
+/// Parameters for the admin/pushNotifications/deadLetters/purge method.
+///
+/// With no filter every dead letter is purged.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PurgeDeadLettersParams {
+    /// Only purge dead letters with these IDs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<String>,
+    /// Only purge dead letters about this task
+    #[serde(skip_serializing_if = "Option::is_none", rename = "taskId")]
+    pub task_id: Option<String>,
+}
+
+impl PurgeDeadLettersParams {
+    /// Whether `letter` is one of those to purge
+    pub fn matches(&self, letter: &DeadLetter) -> bool {
+        (self.ids.is_empty() || self.ids.contains(&letter.id))
+            && self
+                .task_id
+                .as_ref()
+                .is_none_or(|task_id| *task_id == letter.task_id)
+    }
+}
+
+/// Result object for the admin/pushNotifications/deadLetters/purge method
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PurgeDeadLettersResult {
+    /// Number of dead letters removed
+    pub purged: usize,
+}
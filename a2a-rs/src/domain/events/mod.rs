@@ -1,5 +1,16 @@
 //! Event types for streaming and notifications
 
+pub mod dead_letter;
+pub mod task_diff;
 pub mod task_events;
+pub mod task_log;
+pub mod webhook;
 
-pub use task_events::{TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
+pub use dead_letter::{
+    DeadLetter, DeliveryAttempt, ListDeadLettersParams, PurgeDeadLettersParams,
+    PurgeDeadLettersResult, RequeueDeadLetterParams, RequeueDeadLetterResult,
+};
+pub use task_diff::TaskDiff;
+pub use task_events::{TaskArtifactUpdateEvent, TaskSnapshotOptions, TaskStatusUpdateEvent};
+pub use task_log::{TaskEventRecord, TaskLogEvent};
+pub use webhook::WebhookEvent;
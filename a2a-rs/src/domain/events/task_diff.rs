@@ -0,0 +1,201 @@
+//! Incremental task updates for subscribers that keep a local copy of a task
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::domain::core::{
+    message::{Artifact, Message},
+    task::{Task, TaskResult, TaskStatus},
+};
+
+/// What changed in a task between two of its snapshots.
+///
+/// Shaped like a JSON merge patch: only what changed is present. History is
+/// append-only, so new entries are listed rather than the whole history, and
+/// artifacts are added or replaced by their `artifactId`. Applying the diff
+/// to the earlier snapshot gives the later one.
+///
+/// # Example
+/// ```rust
+/// use a2a_rs::domain::{Message, Task, TaskDiff, TaskState};
+///
+/// let before = Task::new("expense-1".to_string(), "expenses".to_string());
+/// let mut after = before.clone();
+/// after.update_status(
+///     TaskState::Working,
+///     Some(Message::user_text("Reimburse my taxi".to_string(), "m-1".to_string())),
+/// );
+///
+/// let diff = TaskDiff::between(&before, &after);
+/// assert_eq!(diff.history_appended.len(), 1);
+///
+/// let mut replica = before.clone();
+/// diff.apply(&mut replica);
+/// assert_eq!(replica.status.state, TaskState::Working);
+/// assert_eq!(replica.history.unwrap().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDiff {
+    /// The task's version after the change
+    pub version: u64,
+    /// The new status, when it changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    /// Entries appended to the history, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history_appended: Vec<Message>,
+    /// Artifacts that were added or changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+    /// Metadata keys that were set, with `null` for keys that were removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+    /// The task's result, when it changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<TaskResult>,
+    /// The task's tags, when they changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// The task's referenced tasks, when they changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_task_ids: Option<Vec<String>>,
+}
+
+impl TaskDiff {
+    /// The changes that turn `old` into `new`
+    pub fn between(old: &Task, new: &Task) -> Self {
+        let old_history = old.history.as_deref().unwrap_or_default();
+        let new_history = new.history.as_deref().unwrap_or_default();
+        // Entries after the last one `old` has, or all of them when it has
+        // none of those still in `new`
+        let appended_from = old_history
+            .last()
+            .and_then(|last| {
+                new_history
+                    .iter()
+                    .rposition(|message| message.message_id == last.message_id)
+            })
+            .map_or(0, |position| position + 1);
+
+        let old_artifacts = old.artifacts.as_deref().unwrap_or_default();
+        let artifacts = new
+            .artifacts
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|artifact| {
+                !old_artifacts.iter().any(|existing| {
+                    existing.artifact_id == artifact.artifact_id && same(existing, *artifact)
+                })
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            version: new.version,
+            status: (!same(&old.status, &new.status)).then(|| new.status.clone()),
+            history_appended: new_history[appended_from..].to_vec(),
+            artifacts,
+            metadata: metadata_patch(old.metadata.as_ref(), new.metadata.as_ref()),
+            result: (!same(&old.result, &new.result))
+                .then(|| new.result.clone())
+                .flatten(),
+            tags: (old.tags != new.tags).then(|| new.tags.clone()),
+            reference_task_ids: (old.reference_task_ids != new.reference_task_ids)
+                .then(|| new.reference_task_ids.clone()),
+        }
+    }
+
+    /// Whether nothing but the version changed
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.history_appended.is_empty()
+            && self.artifacts.is_empty()
+            && self.metadata.is_none()
+            && self.result.is_none()
+            && self.tags.is_none()
+            && self.reference_task_ids.is_none()
+    }
+
+    /// Bring `task` up to date with these changes
+    pub fn apply(&self, task: &mut Task) {
+        task.version = self.version;
+        if let Some(status) = &self.status {
+            task.status = status.clone();
+        }
+
+        if !self.history_appended.is_empty() {
+            let history = task.history.get_or_insert_with(Vec::new);
+            for message in &self.history_appended {
+                // Applying the same diff twice does not duplicate entries
+                if !history
+                    .iter()
+                    .any(|existing| existing.message_id == message.message_id)
+                {
+                    history.push(message.clone());
+                }
+            }
+        }
+
+        if !self.artifacts.is_empty() {
+            let artifacts = task.artifacts.get_or_insert_with(Vec::new);
+            for artifact in &self.artifacts {
+                match artifacts
+                    .iter_mut()
+                    .find(|existing| existing.artifact_id == artifact.artifact_id)
+                {
+                    Some(existing) => *existing = artifact.clone(),
+                    None => artifacts.push(artifact.clone()),
+                }
+            }
+        }
+
+        if let Some(patch) = &self.metadata {
+            let metadata = task.metadata.get_or_insert_with(Map::new);
+            for (key, value) in patch {
+                if value.is_null() {
+                    metadata.remove(key);
+                } else {
+                    metadata.insert(key.clone(), value.clone());
+                }
+            }
+            if metadata.is_empty() {
+                task.metadata = None;
+            }
+        }
+
+        if let Some(result) = &self.result {
+            task.result = Some(result.clone());
+        }
+        if let Some(tags) = &self.tags {
+            task.tags = tags.clone();
+        }
+        if let Some(reference_task_ids) = &self.reference_task_ids {
+            task.reference_task_ids = reference_task_ids.clone();
+        }
+    }
+}
+
+/// Whether two values serialize alike, for types without `PartialEq`
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// The merge patch turning `old` metadata into `new`, if they differ
+fn metadata_patch(
+    old: Option<&Map<String, Value>>,
+    new: Option<&Map<String, Value>>,
+) -> Option<Map<String, Value>> {
+    let empty = Map::new();
+    let (old, new) = (old.unwrap_or(&empty), new.unwrap_or(&empty));
+    let mut patch: Map<String, Value> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    (!patch.is_empty()).then_some(patch)
+}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use crate::domain::core::{message::Artifact, task::TaskStatus};
+use super::task_diff::TaskDiff;
+use crate::domain::core::{
+    message::{Artifact, ArtifactSummary},
+    task::{Task, TaskResult, TaskStatus},
+};
 
 /// Event for task status updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,8 +18,160 @@ pub struct TaskStatusUpdateEvent {
     pub status: TaskStatus,
     #[serde(rename = "final")]
     pub final_: bool,
+    /// Structured task result, present on the final event of a task that has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<TaskResult>,
+    /// The artifacts the agent produced, listed on the final event of a task
+    /// that has any; their content is read with `tasks/artifacts/get`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<ArtifactSummary>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Map<String, Value>>,
+    /// The task as it is after the update, sent only to subscribers that
+    /// asked for snapshots and only when the task's history or artifacts changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<Box<Task>>,
+    /// What changed in the task since the subscriber's previous snapshot,
+    /// sent instead of `task` to subscribers that asked for diffs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<Box<TaskDiff>>,
+}
+
+impl TaskStatusUpdateEvent {
+    /// This event as sent to one subscriber: carrying its requested subset
+    /// of `snapshot`, or no snapshot when it did not opt in
+    pub fn for_subscriber(
+        &self,
+        snapshot: Option<&Task>,
+        options: Option<&TaskSnapshotOptions>,
+    ) -> Self {
+        let mut event = self.clone();
+        event.task = match (snapshot, options) {
+            (Some(task), Some(options)) => Some(Box::new(options.apply(task))),
+            _ => None,
+        };
+        event.diff = None;
+        event
+    }
+
+    /// This event as sent to a subscriber that asked for diffs, given the
+    /// task as that subscriber last saw it.
+    ///
+    /// The first snapshot is sent whole and later ones as a [`TaskDiff`]
+    /// against it; `seen` is kept up to date either way.
+    pub fn diffed(mut self, seen: &mut Option<Task>) -> Self {
+        match (self.task.take(), seen.as_mut()) {
+            (Some(task), Some(previous)) => {
+                let diff = TaskDiff::between(previous, &task);
+                *previous = *task;
+                self.diff = (!diff.is_empty()).then(|| Box::new(diff));
+            }
+            (Some(task), None) => {
+                *seen = Some((*task).clone());
+                self.task = Some(task);
+            }
+            (None, Some(previous)) => self.apply_to_status(previous),
+            (None, None) => {}
+        }
+        self
+    }
+
+    /// Bring a locally kept copy of the task up to date with this event.
+    ///
+    /// A snapshot replaces the copy, a diff is applied to it, and a plain
+    /// status update changes only its status. Nothing can be rebuilt from a
+    /// diff or status update before a first snapshot, so those leave `task`
+    /// unset.
+    pub fn apply_to(&self, task: &mut Option<Task>) {
+        if let Some(snapshot) = &self.task {
+            *task = Some((**snapshot).clone());
+            return;
+        }
+        let Some(task) = task.as_mut() else {
+            return;
+        };
+        match &self.diff {
+            Some(diff) => diff.apply(task),
+            None => self.apply_to_status(task),
+        }
+    }
+
+    fn apply_to_status(&self, task: &mut Task) {
+        task.status = self.status.clone();
+        if self.result.is_some() {
+            task.result = self.result.clone();
+        }
+    }
+}
+
+/// Opt-in for full task snapshots on a status subscription.
+///
+/// Subscribers receive status deltas by default. One that sets these options
+/// also receives the updated task inline whenever a message is appended to
+/// its history or its artifacts change, and need not call `tasks/get` to
+/// catch up. With [`diffs`](Self::diffs) set, only the first snapshot is sent
+/// whole and each later one as what changed since, which a client applies
+/// with [`TaskStatusUpdateEvent::apply_to`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSnapshotOptions {
+    /// Keep only this many of the most recent history entries, all when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_length: Option<u32>,
+    /// Whether the snapshot includes the task's artifacts
+    #[serde(default = "include_by_default")]
+    pub include_artifacts: bool,
+    /// Send snapshots after the first as a [`TaskDiff`] against the last one
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub diffs: bool,
+}
+
+fn include_by_default() -> bool {
+    true
+}
+
+impl Default for TaskSnapshotOptions {
+    fn default() -> Self {
+        Self {
+            history_length: None,
+            include_artifacts: true,
+            diffs: false,
+        }
+    }
+}
+
+impl TaskSnapshotOptions {
+    /// Snapshots of the full task
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit snapshots to the most recent `history_length` history entries
+    pub fn with_history_length(mut self, history_length: u32) -> Self {
+        self.history_length = Some(history_length);
+        self
+    }
+
+    /// Leave artifacts out of snapshots
+    pub fn without_artifacts(mut self) -> Self {
+        self.include_artifacts = false;
+        self
+    }
+
+    /// Send what changed instead of each full snapshot after the first
+    pub fn with_diffs(mut self) -> Self {
+        self.diffs = true;
+        self
+    }
+
+    /// The subset of `task` these options ask for
+    pub fn apply(&self, task: &Task) -> Task {
+        let mut snapshot = task.with_limited_history(self.history_length);
+        if !self.include_artifacts {
+            snapshot.artifacts = None;
+        }
+        snapshot
+    }
 }
 
 /// Event for task artifact updates
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    core::{
+        message::{ArtifactSummary, Message},
+        task::{Task, TaskPushNotificationConfig, TaskResult},
+    },
+    events::task_events::TaskStatusUpdateEvent,
+};
+
+/// An entry in a task's append-only event log.
+///
+/// Every status transition, appended message, produced artifact, recorded
+/// result and push notification config change is recorded with a per-task sequence number (starting at 1) and the time it was
+/// written. Records are never modified or removed once appended, so the log gives
+/// a complete audit trail of the task lifecycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEventRecord {
+    /// Position of this event in the task's log (1-based, strictly increasing)
+    pub sequence: u64,
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    /// When the event was recorded
+    pub timestamp: DateTime<Utc>,
+    pub event: TaskLogEvent,
+}
+
+/// The kinds of events recorded in a task's event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TaskLogEvent {
+    /// The task transitioned to a new status
+    StatusUpdate(Box<TaskStatusUpdateEvent>),
+    /// A message was appended to the task history
+    MessageAppended { message: Message },
+    /// The agent produced an artifact, or replaced one with the same ID
+    ArtifactAdded { artifact: ArtifactSummary },
+    /// A structured result was attached to the task, replacing any earlier one
+    ResultSet { result: TaskResult },
+    /// A push notification config was registered or replaced
+    PushConfigSet { config: TaskPushNotificationConfig },
+    /// A push notification config was removed (all configs when `configId` is absent)
+    PushConfigRemoved {
+        #[serde(skip_serializing_if = "Option::is_none", rename = "configId")]
+        config_id: Option<String>,
+    },
+}
+
+impl TaskLogEvent {
+    /// Status update event for the task's current status
+    pub fn status_update(task: &Task) -> Self {
+        TaskLogEvent::StatusUpdate(Box::new(TaskStatusUpdateEvent {
+            task_id: task.id.clone(),
+            context_id: task.context_id.clone(),
+            kind: "status-update".to_string(),
+            status: task.status.clone(),
+            final_: task.status.state.is_terminal(),
+            result: task.final_result(),
+            artifacts: task.final_artifacts(),
+            metadata: None,
+            task: None,
+            diff: None,
+        }))
+    }
+
+    /// Stable name of the event kind, matching its serialized `type` tag
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            TaskLogEvent::StatusUpdate(_) => "statusUpdate",
+            TaskLogEvent::MessageAppended { .. } => "messageAppended",
+            TaskLogEvent::ArtifactAdded { .. } => "artifactAdded",
+            TaskLogEvent::ResultSet { .. } => "resultSet",
+            TaskLogEvent::PushConfigSet { .. } => "pushConfigSet",
+            TaskLogEvent::PushConfigRemoved { .. } => "pushConfigRemoved",
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Events delivered to push notification webhooks
+
+use serde::{Deserialize, Deserializer, Serialize, de::Error as _};
+use serde_json::Value;
+
+use super::task_events::{TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
+use crate::domain::core::task::TaskState;
+
+/// What a webhook receives: the kind of event and its payload.
+///
+/// On the wire this is `{"eventKind": "...", "payload": {...}}`, so receivers
+/// can tell an artifact from a status change, and a prompt for input or a
+/// finished task from routine progress, without inspecting the payload.
+/// Bare status and artifact update events, as delivered before events were
+/// wrapped, are still accepted and classified the same way.
+///
+/// # Example
+/// ```rust
+/// use a2a_rs::domain::WebhookEvent;
+///
+/// let body = r#"{
+///     "eventKind": "input-required",
+///     "payload": {
+///         "taskId": "expense-1",
+///         "contextId": "expenses",
+///         "kind": "status-update",
+///         "status": {"state": "input-required"},
+///         "final": false
+///     }
+/// }"#;
+/// let event: WebhookEvent = serde_json::from_str(body).unwrap();
+/// assert!(matches!(event, WebhookEvent::InputRequired(_)));
+/// assert_eq!(event.task_id(), "expense-1");
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "eventKind", content = "payload", rename_all = "kebab-case")]
+pub enum WebhookEvent {
+    /// The task moved to a state that needs no action from the receiver
+    StatusUpdate(TaskStatusUpdateEvent),
+    /// The agent is waiting for the user to answer its prompt
+    InputRequired(TaskStatusUpdateEvent),
+    /// The task ended: completed with its result, or failed, canceled or
+    /// rejected
+    Finished(TaskStatusUpdateEvent),
+    /// The agent produced or extended an artifact
+    ArtifactUpdate(TaskArtifactUpdateEvent),
+}
+
+impl WebhookEvent {
+    /// Wrap a status update in the kind its new state calls for
+    pub fn from_status_update(event: TaskStatusUpdateEvent) -> Self {
+        match &event.status.state {
+            TaskState::InputRequired => WebhookEvent::InputRequired(event),
+            state if state.is_terminal() => WebhookEvent::Finished(event),
+            _ => WebhookEvent::StatusUpdate(event),
+        }
+    }
+
+    /// The `eventKind` this event is delivered with
+    pub fn event_kind(&self) -> &'static str {
+        match self {
+            WebhookEvent::StatusUpdate(_) => "status-update",
+            WebhookEvent::InputRequired(_) => "input-required",
+            WebhookEvent::Finished(_) => "finished",
+            WebhookEvent::ArtifactUpdate(_) => "artifact-update",
+        }
+    }
+
+    /// ID of the task the event is about
+    pub fn task_id(&self) -> &str {
+        match self {
+            WebhookEvent::StatusUpdate(event)
+            | WebhookEvent::InputRequired(event)
+            | WebhookEvent::Finished(event) => &event.task_id,
+            WebhookEvent::ArtifactUpdate(event) => &event.task_id,
+        }
+    }
+}
+
+impl From<TaskStatusUpdateEvent> for WebhookEvent {
+    fn from(event: TaskStatusUpdateEvent) -> Self {
+        WebhookEvent::from_status_update(event)
+    }
+}
+
+impl From<TaskArtifactUpdateEvent> for WebhookEvent {
+    fn from(event: TaskArtifactUpdateEvent) -> Self {
+        WebhookEvent::ArtifactUpdate(event)
+    }
+}
+
+/// The enveloped shape of [`WebhookEvent`], deserialized as derived
+#[derive(Deserialize)]
+#[serde(tag = "eventKind", content = "payload", rename_all = "kebab-case")]
+enum Envelope {
+    StatusUpdate(TaskStatusUpdateEvent),
+    InputRequired(TaskStatusUpdateEvent),
+    Finished(TaskStatusUpdateEvent),
+    ArtifactUpdate(TaskArtifactUpdateEvent),
+}
+
+impl From<Envelope> for WebhookEvent {
+    fn from(envelope: Envelope) -> Self {
+        match envelope {
+            Envelope::StatusUpdate(event) => WebhookEvent::StatusUpdate(event),
+            Envelope::InputRequired(event) => WebhookEvent::InputRequired(event),
+            Envelope::Finished(event) => WebhookEvent::Finished(event),
+            Envelope::ArtifactUpdate(event) => WebhookEvent::ArtifactUpdate(event),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WebhookEvent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        if value.get("eventKind").is_some() {
+            return Envelope::deserialize(value)
+                .map(Into::into)
+                .map_err(D::Error::custom);
+        }
+
+        // A bare event, told apart by its own `kind`
+        match value.get("kind").and_then(Value::as_str) {
+            Some("artifact-update") => TaskArtifactUpdateEvent::deserialize(value)
+                .map(WebhookEvent::ArtifactUpdate)
+                .map_err(D::Error::custom),
+            _ => TaskStatusUpdateEvent::deserialize(value)
+                .map(WebhookEvent::from_status_update)
+                .map_err(D::Error::custom),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Sources of generated identifiers
+//!
+//! Components that mint message and subscription ids take an [`IdGenerator`]
+//! so tests can swap random UUIDs for a [`SequentialIdGenerator`].
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+/// A source of unique identifiers
+pub trait IdGenerator: Send + Sync {
+    /// A new identifier, distinct from every one returned before
+    fn next_id(&self) -> String;
+}
+
+impl<T: IdGenerator + ?Sized> IdGenerator for Arc<T> {
+    fn next_id(&self) -> String {
+        (**self).next_id()
+    }
+}
+
+/// Random v4 UUIDs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidGenerator;
+
+impl IdGenerator for UuidGenerator {
+    fn next_id(&self) -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+}
+
+/// Numbered identifiers such as `msg-1`, `msg-2`, ...
+///
+/// Clones share the same counter.
+#[derive(Debug, Clone)]
+pub struct SequentialIdGenerator {
+    prefix: String,
+    next: Arc<AtomicU64>,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator whose first id is `{prefix}-1`
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            next: Arc::new(AtomicU64::new(1)),
+        }
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self) -> String {
+        let n = self.next.fetch_add(1, Ordering::SeqCst);
+        format!("{}-{}", self.prefix, n)
+    }
+}
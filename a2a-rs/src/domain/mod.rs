@@ -1,27 +1,45 @@
 //! Domain models for the A2A protocol
 
+pub mod clock;
 pub mod core;
 pub mod error;
+pub mod error_catalog;
 pub mod events;
+pub mod id_generator;
 pub mod protocols;
 #[cfg(test)]
 mod tests;
 pub mod validation;
 
 // Re-export key types for convenience
+pub use clock::{Clock, FixedClock, SystemClock};
 pub use core::{
     AgentCapabilities, AgentCard, AgentCardSignature, AgentExtension, AgentInterface,
-    AgentProvider, AgentSkill, Artifact, AuthorizationCodeOAuthFlow, ClientCredentialsOAuthFlow,
-    DeleteTaskPushNotificationConfigParams, FileContent, GetTaskPushNotificationConfigParams,
-    ImplicitOAuthFlow, ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult,
-    Message, MessageSendConfiguration, MessageSendParams, OAuthFlows, Part, PasswordOAuthFlow,
-    PushNotificationAuthenticationInfo, PushNotificationConfig, Role, SecurityScheme, Task,
-    TaskIdParams, TaskPushNotificationConfig, TaskQueryParams, TaskSendParams, TaskState,
-    TaskStatus, TransportProtocol,
+    AgentProvider, AgentSkill, Artifact, ArtifactSummary, AuthorizationCodeOAuthFlow,
+    ClientCredentialsOAuthFlow, DeleteTaskPushNotificationConfigParams, FileContent,
+    GetOrCreateTaskParams, GetOrCreateTaskResult, GetTaskArtifactParams, GetTaskArtifactResult,
+    GetTaskEventsParams, GetTaskEventsResult, GetTaskPushNotificationConfigParams,
+    ImplicitOAuthFlow, ImportTasksParams, ImportTasksResult, InputRequest, ListTaskArtifactsResult,
+    ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult, ListTasksStreamItem,
+    ListTasksSummary, Message, MessageSendConfiguration, MessageSendParams, OAuthFlows,
+    PageSizeLimits, Part, PasswordOAuthFlow, PushNotificationAuthenticationInfo,
+    PushNotificationConfig, Role, SecurityScheme, TagMatch, Task, TaskCancellation, TaskField,
+    TaskIdParams, TaskImportOutcome, TaskPushNotificationConfig, TaskQueryParams, TaskResult,
+    TaskSendParams, TaskState, TaskStatus, TaskTagsParams, TransportProtocol,
 };
-pub use error::A2AError;
-pub use events::{TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
+pub use error::{A2AError, StorageFailure};
+pub use error_catalog::{ErrorCatalog, ErrorDetail};
+pub use events::{
+    DeadLetter, DeliveryAttempt, ListDeadLettersParams, PurgeDeadLettersParams,
+    PurgeDeadLettersResult, RequeueDeadLetterParams, RequeueDeadLetterResult,
+    TaskArtifactUpdateEvent, TaskDiff, TaskEventRecord, TaskLogEvent, TaskSnapshotOptions,
+    TaskStatusUpdateEvent, WebhookEvent,
+};
+pub use id_generator::{IdGenerator, SequentialIdGenerator, UuidGenerator};
 pub use protocols::{
     JSONRPCError, JSONRPCMessage, JSONRPCNotification, JSONRPCRequest, JSONRPCResponse,
 };
-pub use validation::{Validate, ValidationResult};
+pub use validation::{
+    ContentPolicy, ControlCharacters, MessageSanitizer, MessageSchemas, SanitizePolicy, Validate,
+    ValidationResult,
+};
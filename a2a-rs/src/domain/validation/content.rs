@@ -0,0 +1,271 @@
+//! Content-type sniffing and validation for file parts
+//!
+//! Clients label uploaded files with whatever mime type the browser or caller
+//! supplied. [`sniff_mime_type`] inspects the leading magic bytes to detect the
+//! real type, and [`ContentPolicy`] uses it to reject mislabeled files, enforce
+//! allowlists and record the detected type on the part.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use serde_json::Value;
+
+use crate::domain::{
+    core::message::{FileContent, Message, Part},
+    error::A2AError,
+    validation::ValidationResult,
+};
+
+/// Part metadata key holding the type detected from the file contents
+pub const DETECTED_MIME_TYPE_KEY: &str = "detectedMimeType";
+/// Part metadata key holding the type the client originally declared
+pub const DECLARED_MIME_TYPE_KEY: &str = "declaredMimeType";
+/// Message metadata key naming the skill a message is for
+pub const SKILL_ID_KEY: &str = "skillId";
+
+/// Magic byte signatures, checked in order
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\xFF\xD8\xFF", "image/jpeg"),
+    (b"\x89PNG\r\n\x1A\n", "image/png"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1F\x8B", "application/gzip"),
+    (b"II*\x00", "image/tiff"),
+    (b"MM\x00*", "image/tiff"),
+];
+
+/// Detect the mime type of file contents from their magic bytes.
+///
+/// Falls back to `text/plain` for valid UTF-8 without control characters and
+/// returns `None` when the type can't be determined.
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if let Some((_, mime)) = SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+    {
+        return Some(mime);
+    }
+
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+
+    if !bytes.is_empty()
+        && std::str::from_utf8(bytes).is_ok_and(|text| {
+            text.chars()
+                .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        })
+    {
+        return Some("text/plain");
+    }
+
+    None
+}
+
+/// Normalize a mime type for comparison: lowercase, parameters stripped and
+/// common aliases folded
+pub(crate) fn normalize(mime: &str) -> String {
+    let base = mime
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match base.as_str() {
+        "image/jpg" | "image/pjpeg" => "image/jpeg".to_string(),
+        "application/x-pdf" => "application/pdf".to_string(),
+        "application/x-zip-compressed" => "application/zip".to_string(),
+        "application/x-gzip" => "application/gzip".to_string(),
+        _ => base,
+    }
+}
+
+/// Whether a declared type is consistent with the detected one.
+///
+/// Text detection is a fallback, so any `text/*` type or a text-based structured
+/// format is accepted for content sniffed as `text/plain`.
+fn is_compatible(declared: &str, detected: &str) -> bool {
+    if declared == detected {
+        return true;
+    }
+    detected == "text/plain"
+        && (declared.starts_with("text/")
+            || matches!(
+                declared,
+                "application/json" | "application/xml" | "image/svg+xml"
+            ))
+}
+
+/// Whether a type matches an allowlist entry (`*/*`, `image/*` or an exact type)
+pub(crate) fn matches_pattern(pattern: &str, mime: &str) -> bool {
+    let pattern = normalize(pattern);
+    match pattern.strip_suffix("/*") {
+        Some("*") => true,
+        Some(prefix) => mime.split('/').next() == Some(prefix),
+        None => pattern == mime,
+    }
+}
+
+/// Outcome of inspecting a file part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileInspection {
+    /// Type detected from the contents, if the file has embedded bytes
+    pub detected_type: Option<String>,
+    /// Type the file should be served as: the declared type when compatible with
+    /// the contents, otherwise the detected one
+    pub effective_type: Option<String>,
+}
+
+/// Server-side policy for file parts in incoming messages.
+///
+/// Files with embedded bytes are sniffed; URI-only files can't be inspected and
+/// are checked against the allowlist by their declared type.
+#[derive(Debug, Clone, Default)]
+pub struct ContentPolicy {
+    /// Types accepted by the agent, `None` accepts everything
+    allowed_types: Option<Vec<String>>,
+    /// Per-skill allowlists, taking precedence over the agent-wide list
+    skill_allowed_types: HashMap<String, Vec<String>>,
+    /// Reject files whose declared type disagrees with the detected one
+    reject_mismatches: bool,
+}
+
+impl ContentPolicy {
+    /// Create a policy that sniffs and records types without rejecting anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict accepted file types for the whole agent
+    pub fn with_allowed_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_types = Some(types);
+        self
+    }
+
+    /// Restrict accepted file types for a specific skill
+    pub fn with_skill_allowed_types(mut self, skill_id: String, types: Vec<String>) -> Self {
+        self.skill_allowed_types.insert(skill_id, types);
+        self
+    }
+
+    /// Reject files whose declared mime type doesn't match their contents
+    pub fn reject_mismatches(mut self, reject: bool) -> Self {
+        self.reject_mismatches = reject;
+        self
+    }
+
+    fn allowlist_for(&self, skill_id: Option<&str>) -> Option<&Vec<String>> {
+        skill_id
+            .and_then(|id| self.skill_allowed_types.get(id))
+            .or(self.allowed_types.as_ref())
+    }
+
+    /// Inspect a single file against the policy
+    pub fn inspect_file(
+        &self,
+        file: &FileContent,
+        skill_id: Option<&str>,
+    ) -> ValidationResult<FileInspection> {
+        // A generic binary label carries no claim about the contents
+        let declared = file
+            .mime_type
+            .as_deref()
+            .map(normalize)
+            .filter(|mime| mime != "application/octet-stream");
+
+        let detected = match &file.bytes {
+            Some(encoded) => {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| A2AError::ValidationError {
+                        field: "file.bytes".to_string(),
+                        message: format!("File content is not valid base64: {}", e),
+                    })?;
+                sniff_mime_type(&bytes).map(str::to_string)
+            }
+            None => None,
+        };
+
+        if let (Some(declared), Some(detected)) = (&declared, &detected) {
+            if self.reject_mismatches && !is_compatible(declared, detected) {
+                return Err(A2AError::ContentTypeNotSupported(format!(
+                    "File {} is declared as {} but its contents are {}",
+                    file.name.as_deref().unwrap_or("<unnamed>"),
+                    declared,
+                    detected
+                )));
+            }
+        }
+
+        // Keep a compatible declared type (e.g. text/csv) over the text fallback
+        let effective = match (&declared, &detected) {
+            (Some(declared), Some(detected)) if is_compatible(declared, detected) => {
+                Some(declared.clone())
+            }
+            (_, Some(detected)) => Some(detected.clone()),
+            (declared, None) => declared.clone(),
+        };
+
+        if let Some(allowed) = self.allowlist_for(skill_id) {
+            let accepted = effective
+                .as_deref()
+                .is_some_and(|mime| allowed.iter().any(|pattern| matches_pattern(pattern, mime)));
+            if !accepted {
+                return Err(A2AError::ContentTypeNotSupported(format!(
+                    "File type {} is not accepted{}",
+                    effective.as_deref().unwrap_or("unknown"),
+                    skill_id
+                        .filter(|id| self.skill_allowed_types.contains_key(*id))
+                        .map(|id| format!(" by skill {}", id))
+                        .unwrap_or_default()
+                )));
+            }
+        }
+
+        Ok(FileInspection {
+            detected_type: detected,
+            effective_type: effective,
+        })
+    }
+
+    /// Validate every file part of a message and record the detected types.
+    ///
+    /// On success each file's `mime_type` is replaced with the effective type, and
+    /// the part metadata records both the detected and declared types so downloads
+    /// can serve the correct `Content-Type`. The skill is selected by the
+    /// message's `skillId` metadata, if present.
+    pub fn apply(&self, message: &mut Message) -> ValidationResult<()> {
+        let skill_id = message
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(SKILL_ID_KEY))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        for part in &mut message.parts {
+            let Part::File { file, metadata } = part else {
+                continue;
+            };
+
+            let inspection = self.inspect_file(file, skill_id.as_deref())?;
+
+            if let Some(detected) = inspection.detected_type {
+                let metadata = metadata.get_or_insert_with(Default::default);
+                metadata.insert(DETECTED_MIME_TYPE_KEY.to_string(), Value::String(detected));
+                if let Some(declared) = &file.mime_type {
+                    metadata.insert(
+                        DECLARED_MIME_TYPE_KEY.to_string(),
+                        Value::String(declared.clone()),
+                    );
+                }
+            }
+            if inspection.effective_type.is_some() {
+                file.mime_type = inspection.effective_type;
+            }
+        }
+
+        Ok(())
+    }
+}
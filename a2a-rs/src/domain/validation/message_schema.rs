@@ -0,0 +1,164 @@
+//! Required input of messages sent to a skill
+//!
+//! A skill may declare an input schema: the shape of the structured data its
+//! messages must carry. [`MessageSchemas`] checks each incoming message
+//! against the schema of the skill its task belongs to, so a follow-up on an
+//! expense task must still provide what the expense skill needs.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::domain::{
+    core::{
+        agent::AgentSkill,
+        message::{Message, Part},
+        task::Task,
+    },
+    error::A2AError,
+    error_catalog::{ErrorDetail, codes},
+    validation::{ValidationResult, content::SKILL_ID_KEY, schema::schema_violations},
+};
+
+/// Input schemas of an agent's skills, enforced on incoming messages.
+///
+/// A message's input is the data of its data parts, merged into one object;
+/// a message without data parts has an empty input. Messages for skills
+/// without a schema are not checked.
+///
+/// # Example
+/// ```rust
+/// use a2a_rs::domain::{Message, MessageSchemas, Part};
+/// use serde_json::json;
+///
+/// let schemas = MessageSchemas::new().with_skill_schema(
+///     "process_reimbursement".to_string(),
+///     json!({"type": "object", "required": ["amount", "purpose"]}),
+/// );
+///
+/// let mut message = Message::user_text("Taxi".to_string(), "m-1".to_string());
+/// message.add_part(Part::data(json!({"amount": 20}).as_object().unwrap().clone()));
+///
+/// let error = schemas
+///     .validate(&message, Some("process_reimbursement"))
+///     .unwrap_err();
+/// assert_eq!(error.error_detail().params["missing"], "purpose");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageSchemas {
+    skill_schemas: HashMap<String, Value>,
+}
+
+impl MessageSchemas {
+    /// Create a set without schemas, accepting every message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The input schemas declared by `skills`
+    pub fn from_skills(skills: &[AgentSkill]) -> Self {
+        skills
+            .iter()
+            .filter_map(|skill| Some((skill.id.clone(), skill.input_schema.clone()?)))
+            .fold(Self::new(), |schemas, (skill_id, schema)| {
+                schemas.with_skill_schema(skill_id, schema)
+            })
+    }
+
+    /// Require messages for `skill_id` to match `schema`
+    pub fn with_skill_schema(mut self, skill_id: String, schema: Value) -> Self {
+        self.skill_schemas.insert(skill_id, schema);
+        self
+    }
+
+    /// Whether no skill has a schema
+    pub fn is_empty(&self) -> bool {
+        self.skill_schemas.is_empty()
+    }
+
+    /// The input schema of `skill_id`, if it has one
+    pub fn schema_for(&self, skill_id: &str) -> Option<&Value> {
+        self.skill_schemas.get(skill_id)
+    }
+
+    /// Check a message for the skill `skill_id` against that skill's schema.
+    ///
+    /// Fails with [`codes::VALIDATION_SCHEMA`], naming every field that is
+    /// missing or invalid: all of them in `fields`, split into `missing` and
+    /// `invalid`, with what is wrong with each in `reason`.
+    pub fn validate(&self, message: &Message, skill_id: Option<&str>) -> ValidationResult<()> {
+        let Some((skill_id, schema)) =
+            skill_id.and_then(|id| Some((id, self.skill_schemas.get(id)?)))
+        else {
+            return Ok(());
+        };
+
+        let violations = schema_violations(&message_input(message), schema, "");
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        let list = |missing: bool| {
+            violations
+                .iter()
+                .filter(|violation| violation.missing == missing)
+                .map(|violation| violation.field.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut detail = ErrorDetail::new(codes::VALIDATION_SCHEMA)
+            .with_param("skillId", skill_id)
+            .with_param(
+                "fields",
+                violations
+                    .iter()
+                    .map(|violation| violation.field.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+            .with_param(
+                "reason",
+                violations
+                    .iter()
+                    .map(|violation| format!("{}: {}", violation.field, violation.message))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+        for (name, fields) in [("missing", list(true)), ("invalid", list(false))] {
+            if !fields.is_empty() {
+                detail = detail.with_param(name, fields);
+            }
+        }
+        Err(A2AError::UserError(detail))
+    }
+}
+
+/// The skill a message names in its `skillId` metadata
+pub fn message_skill_id(message: &Message) -> Option<&str> {
+    message
+        .metadata
+        .as_ref()?
+        .get(SKILL_ID_KEY)
+        .and_then(Value::as_str)
+}
+
+/// The skill a task belongs to: the one named in its metadata, or else by the
+/// first message in its history that names one
+pub fn task_skill_id(task: &Task) -> Option<&str> {
+    task.metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(SKILL_ID_KEY))
+        .and_then(Value::as_str)
+        .or_else(|| task.history.as_deref()?.iter().find_map(message_skill_id))
+}
+
+/// The data of a message's data parts, merged into one object
+fn message_input(message: &Message) -> Value {
+    let mut input = Map::new();
+    for part in &message.parts {
+        if let Part::Data { data, .. } = part {
+            input.extend(data.clone());
+        }
+    }
+    Value::Object(input)
+}
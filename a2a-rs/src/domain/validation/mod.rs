@@ -2,6 +2,16 @@
 
 use crate::domain::error::A2AError;
 
+pub mod content;
+pub mod message_schema;
+pub mod sanitize;
+pub mod schema;
+
+pub use content::{ContentPolicy, FileInspection, sniff_mime_type};
+pub use message_schema::MessageSchemas;
+pub use sanitize::{ControlCharacters, MessageSanitizer, SanitizePolicy};
+pub use schema::{SchemaViolation, schema_violations, validate_json_schema};
+
 /// Validation result type
 pub type ValidationResult<T> = Result<T, A2AError>;
 
@@ -0,0 +1,308 @@
+//! Sanitization of message text before it is stored
+//!
+//! Text from agents and users may carry control characters, stray carriage
+//! returns or long runs of blank lines that break chat rendering, and parts
+//! of any length. A [`SanitizePolicy`] cleans up the text parts of a message,
+//! and a [`MessageSanitizer`] applies one policy to agent output and another,
+//! usually stricter, to user input. Both are off unless configured, so by
+//! default messages are stored byte for byte.
+//!
+//! Artifacts and results are agent output too, and cancellation reasons are
+//! user input, so storages sanitize them with the same policies.
+
+use serde_json::Value;
+
+use crate::domain::core::{
+    message::{Artifact, Message, Part, Role},
+    task::{Task, TaskCancellation, TaskResult},
+};
+
+/// Appended to text parts cut short by a length limit
+pub const DEFAULT_TRUNCATION_INDICATOR: &str = "… [truncated]";
+
+/// What to do with control characters other than newlines and tabs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ControlCharacters {
+    /// Leave them in place
+    #[default]
+    Keep,
+    /// Remove them
+    Strip,
+    /// Replace each with its visible `\u{..}` escape
+    Escape,
+}
+
+/// How the text parts of a message are cleaned up.
+///
+/// Steps run in order: control characters, line endings, blank lines, then
+/// the length limit. File and data parts are left alone.
+///
+/// # Example
+/// ```rust
+/// use a2a_rs::domain::{ControlCharacters, SanitizePolicy};
+///
+/// let policy = SanitizePolicy::new()
+///     .with_control_characters(ControlCharacters::Strip)
+///     .with_normalized_line_endings()
+///     .with_max_text_length(25);
+///
+/// assert_eq!(policy.sanitize_text("Total:\u{7} $20\r\n"), "Total: $20\n");
+/// assert_eq!(
+///     policy.sanitize_text("Receipt for a long taxi ride"),
+///     "Receipt for … [truncated]"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    control_characters: ControlCharacters,
+    normalize_line_endings: bool,
+    max_blank_lines: Option<usize>,
+    max_text_length: Option<usize>,
+    truncation_indicator: String,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self {
+            control_characters: ControlCharacters::Keep,
+            normalize_line_endings: false,
+            max_blank_lines: None,
+            max_text_length: None,
+            truncation_indicator: DEFAULT_TRUNCATION_INDICATOR.to_string(),
+        }
+    }
+}
+
+impl SanitizePolicy {
+    /// Create a policy that leaves text unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy for agent output: control characters stripped, line endings
+    /// normalized, at most two blank lines in a row and 32,768 characters per
+    /// part
+    pub fn agent_output() -> Self {
+        Self::new()
+            .with_control_characters(ControlCharacters::Strip)
+            .with_normalized_line_endings()
+            .with_max_blank_lines(2)
+            .with_max_text_length(32_768)
+    }
+
+    /// A stricter policy for user input: control characters stripped, line
+    /// endings normalized, at most one blank line in a row and 8,192
+    /// characters per part
+    pub fn user_input() -> Self {
+        Self::new()
+            .with_control_characters(ControlCharacters::Strip)
+            .with_normalized_line_endings()
+            .with_max_blank_lines(1)
+            .with_max_text_length(8_192)
+    }
+
+    /// Strip or escape control characters other than newlines and tabs
+    pub fn with_control_characters(mut self, handling: ControlCharacters) -> Self {
+        self.control_characters = handling;
+        self
+    }
+
+    /// Turn `\r\n` and lone `\r` into `\n`
+    pub fn with_normalized_line_endings(mut self) -> Self {
+        self.normalize_line_endings = true;
+        self
+    }
+
+    /// Collapse runs of more than `max` blank lines
+    pub fn with_max_blank_lines(mut self, max: usize) -> Self {
+        self.max_blank_lines = Some(max);
+        self
+    }
+
+    /// Cut text parts longer than `max` characters, indicator included
+    pub fn with_max_text_length(mut self, max: usize) -> Self {
+        self.max_text_length = Some(max);
+        self
+    }
+
+    /// Mark truncated parts with `indicator` instead of
+    /// [`DEFAULT_TRUNCATION_INDICATOR`]
+    pub fn with_truncation_indicator(mut self, indicator: impl Into<String>) -> Self {
+        self.truncation_indicator = indicator.into();
+        self
+    }
+
+    /// The text as this policy stores it
+    pub fn sanitize_text(&self, text: &str) -> String {
+        let mut text = self.handle_control_characters(text);
+        if self.normalize_line_endings {
+            text = text.replace("\r\n", "\n").replace('\r', "\n");
+        }
+        if let Some(max) = self.max_blank_lines {
+            text = collapse_blank_lines(&text, max);
+        }
+        match self.max_text_length {
+            Some(max) if text.chars().count() > max => {
+                let kept = max.saturating_sub(self.truncation_indicator.chars().count());
+                let mut truncated: String = text.chars().take(kept).collect();
+                truncated.push_str(&self.truncation_indicator);
+                truncated
+            }
+            _ => text,
+        }
+    }
+
+    /// Sanitize the text parts of `message` in place
+    pub fn sanitize_message(&self, message: &mut Message) {
+        self.sanitize_parts(&mut message.parts);
+    }
+
+    /// Sanitize the text parts among `parts` in place
+    pub fn sanitize_parts(&self, parts: &mut [Part]) {
+        for part in parts {
+            if let Part::Text { text, .. } = part {
+                *text = self.sanitize_text(text);
+            }
+        }
+    }
+
+    /// Sanitize every string in a JSON value in place, leaving object keys
+    pub fn sanitize_value(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = self.sanitize_text(text),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.sanitize_value(item)),
+            Value::Object(fields) => fields
+                .values_mut()
+                .for_each(|field| self.sanitize_value(field)),
+            _ => {}
+        }
+    }
+
+    fn handle_control_characters(&self, text: &str) -> String {
+        // Carriage returns are left to line ending normalization
+        let is_unwanted = |c: char| c.is_control() && !matches!(c, '\n' | '\t' | '\r');
+        match self.control_characters {
+            ControlCharacters::Keep => text.to_string(),
+            ControlCharacters::Strip => text.chars().filter(|c| !is_unwanted(*c)).collect(),
+            ControlCharacters::Escape => text
+                .chars()
+                .map(|c| {
+                    if is_unwanted(c) {
+                        c.escape_unicode().to_string()
+                    } else {
+                        c.to_string()
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Drop blank lines beyond the first `max` of each run
+fn collapse_blank_lines(text: &str, max: usize) -> String {
+    let mut blank_run = 0;
+    text.split('\n')
+        .filter(|line| {
+            if line.trim().is_empty() {
+                blank_run += 1;
+                blank_run <= max
+            } else {
+                blank_run = 0;
+                true
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Sanitization applied to messages before they are stored: one policy for
+/// agent output and one for user input, each off unless set.
+///
+/// # Example
+/// ```rust
+/// use a2a_rs::domain::{Message, MessageSanitizer, Part, SanitizePolicy};
+///
+/// let sanitizer = MessageSanitizer::new().with_user_policy(SanitizePolicy::user_input());
+///
+/// let mut message = Message::user_text("Lunch\u{1b}[31m\r\n".to_string(), "m-1".to_string());
+/// sanitizer.sanitize(&mut message);
+/// assert!(matches!(&message.parts[0], Part::Text { text, .. } if text == "Lunch[31m\n"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageSanitizer {
+    agent: Option<SanitizePolicy>,
+    user: Option<SanitizePolicy>,
+}
+
+impl MessageSanitizer {
+    /// Create a sanitizer that stores every message unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sanitize agent messages with `policy`
+    pub fn with_agent_policy(mut self, policy: SanitizePolicy) -> Self {
+        self.agent = Some(policy);
+        self
+    }
+
+    /// Sanitize user messages with `policy`
+    pub fn with_user_policy(mut self, policy: SanitizePolicy) -> Self {
+        self.user = Some(policy);
+        self
+    }
+
+    /// Whether any message is changed at all
+    pub fn is_enabled(&self) -> bool {
+        self.agent.is_some() || self.user.is_some()
+    }
+
+    /// Sanitize `message` with the policy for its role, if there is one
+    pub fn sanitize(&self, message: &mut Message) {
+        let policy = match message.role {
+            Role::Agent => &self.agent,
+            Role::User => &self.user,
+        };
+        if let Some(policy) = policy {
+            policy.sanitize_message(message);
+        }
+    }
+
+    /// Sanitize the text parts of `artifact` with the agent policy
+    pub fn sanitize_artifact(&self, artifact: &mut Artifact) {
+        if let Some(policy) = &self.agent {
+            policy.sanitize_parts(&mut artifact.parts);
+        }
+    }
+
+    /// Sanitize the strings of a result payload with the agent policy
+    pub fn sanitize_result(&self, result: &mut TaskResult) {
+        if let Some(policy) = &self.agent {
+            policy.sanitize_value(&mut result.data);
+        }
+    }
+
+    /// Sanitize the reason given for a cancellation with the user policy
+    pub fn sanitize_cancellation(&self, cancellation: &mut TaskCancellation) {
+        if let (Some(policy), Some(reason)) = (&self.user, cancellation.reason.as_mut()) {
+            *reason = policy.sanitize_text(reason);
+        }
+    }
+
+    /// Sanitize everything stored with `task`: its messages by role, and its
+    /// artifacts and result as agent output
+    pub fn sanitize_task(&self, task: &mut Task) {
+        for message in task.history.iter_mut().flatten() {
+            self.sanitize(message);
+        }
+        if let Some(message) = task.status.message.as_mut() {
+            self.sanitize(message);
+        }
+        for artifact in task.artifacts.iter_mut().flatten() {
+            self.sanitize_artifact(artifact);
+        }
+        if let Some(result) = task.result.as_mut() {
+            self.sanitize_result(result);
+        }
+    }
+}
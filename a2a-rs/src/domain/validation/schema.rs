@@ -0,0 +1,168 @@
+//! Minimal JSON Schema validation for structured skill data
+//!
+//! Supports the subset of JSON Schema used to describe skill inputs and outputs:
+//! `type`, `enum`, `properties`, `required`, `additionalProperties: false` and
+//! `items`. Unknown keywords are ignored, so richer schemas still validate their
+//! structural parts.
+
+use serde_json::Value;
+
+use crate::domain::{error::A2AError, validation::ValidationResult};
+
+/// A place where a value does not match its schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Path of the offending value, e.g. `result.amount` or `items[2]`
+    pub field: String,
+    /// What is wrong with it
+    pub message: String,
+    /// Whether the field is required but absent, rather than invalid
+    pub missing: bool,
+}
+
+impl From<SchemaViolation> for A2AError {
+    fn from(violation: SchemaViolation) -> Self {
+        A2AError::ValidationError {
+            field: violation.field,
+            message: violation.message,
+        }
+    }
+}
+
+/// Validate a value against a JSON schema.
+///
+/// `path` names the value in error messages; nested fields are reported as
+/// `path.field` and `path[index]`. Fails with the first violation found.
+pub fn validate_json_schema(value: &Value, schema: &Value, path: &str) -> ValidationResult<()> {
+    match schema_violations(value, schema, path).into_iter().next() {
+        Some(violation) => Err(violation.into()),
+        None => Ok(()),
+    }
+}
+
+/// Every place where a value does not match a JSON schema, in the order
+/// [`validate_json_schema`] would find them
+pub fn schema_violations(value: &Value, schema: &Value, path: &str) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    collect_violations(value, schema, path, &mut violations);
+    violations
+}
+
+fn collect_violations(
+    value: &Value,
+    schema: &Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(schema) = schema.as_object() else {
+        // `true` and `{}` accept everything
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let accepted = match expected {
+            Value::String(ty) => matches_type(value, ty),
+            Value::Array(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|ty| matches_type(value, ty)),
+            _ => true,
+        };
+        if !accepted {
+            violations.push(invalid(
+                path,
+                format!("expected {}, got {}", expected, type_name(value)),
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            violations.push(invalid(path, format!("{} is not an allowed value", value)));
+            return;
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for field in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(field) {
+                        violations.push(SchemaViolation {
+                            field: join(path, field),
+                            message: "required field is missing".to_string(),
+                            missing: true,
+                        });
+                    }
+                }
+            }
+
+            for (field, field_value) in object {
+                match properties.and_then(|p| p.get(field)) {
+                    Some(field_schema) => collect_violations(
+                        field_value,
+                        field_schema,
+                        &join(path, field),
+                        violations,
+                    ),
+                    None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        violations
+                            .push(invalid(&join(path, field), "unexpected field".to_string()));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    collect_violations(item, item_schema, &format!("{}[{}]", path, i), violations);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+fn invalid(path: &str, message: String) -> SchemaViolation {
+    SchemaViolation {
+        field: path.to_string(),
+        message,
+        missing: false,
+    }
+}
@@ -83,13 +83,21 @@ pub mod observability;
 // Public API exports
 pub use domain::{
     A2AError, AgentCapabilities, AgentCard, AgentCardSignature, AgentExtension, AgentInterface,
-    AgentProvider, AgentSkill, Artifact, AuthorizationCodeOAuthFlow, ClientCredentialsOAuthFlow,
-    DeleteTaskPushNotificationConfigParams, FileContent, GetTaskPushNotificationConfigParams,
-    ImplicitOAuthFlow, ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult,
-    Message, MessageSendConfiguration, MessageSendParams, OAuthFlows, Part, PasswordOAuthFlow,
-    PushNotificationAuthenticationInfo, PushNotificationConfig, Role, SecurityScheme, Task,
-    TaskArtifactUpdateEvent, TaskIdParams, TaskPushNotificationConfig, TaskQueryParams,
-    TaskSendParams, TaskState, TaskStatus, TaskStatusUpdateEvent, TransportProtocol,
+    AgentProvider, AgentSkill, Artifact, ArtifactSummary, AuthorizationCodeOAuthFlow, ClientCredentialsOAuthFlow,
+    DeadLetter, DeleteTaskPushNotificationConfigParams, DeliveryAttempt, ErrorCatalog, ErrorDetail, FileContent,
+    GetOrCreateTaskParams, GetOrCreateTaskResult, GetTaskArtifactParams, GetTaskArtifactResult,
+    GetTaskEventsParams, GetTaskEventsResult,
+    GetTaskPushNotificationConfigParams, ImplicitOAuthFlow, ImportTasksParams, ImportTasksResult,
+    InputRequest, ListTaskArtifactsResult,
+    ListTaskPushNotificationConfigParams,
+    ListTasksParams, ListTasksResult, ListTasksStreamItem, ListTasksSummary, Message,
+    MessageSendConfiguration, MessageSendParams,
+    OAuthFlows, Part, PasswordOAuthFlow, PushNotificationAuthenticationInfo,
+    PushNotificationConfig, Role, SecurityScheme, TagMatch, Task, TaskArtifactUpdateEvent, TaskCancellation, TaskDiff, TaskField,
+    TaskEventRecord,
+    TaskIdParams, TaskImportOutcome, TaskLogEvent, TaskPushNotificationConfig, TaskQueryParams, TaskResult, TaskSendParams,
+    TaskSnapshotOptions,
+    TaskState, TaskStatus, TaskStatusUpdateEvent, TaskTagsParams, TransportProtocol, WebhookEvent,
 };
 
 // Port traits for better separation of concerns
@@ -100,20 +108,23 @@ pub use port::{
 };
 
 #[cfg(feature = "http-client")]
-pub use adapter::HttpClient;
+pub use adapter::{HttpClient, RetryPolicy};
 
 #[cfg(feature = "ws-client")]
 pub use adapter::WebSocketClient;
 
+#[cfg(any(feature = "ws-client", feature = "ws-server"))]
+pub use adapter::{WebSocketCredentials, WebSocketOptions};
+
 #[cfg(feature = "http-server")]
-pub use adapter::HttpServer;
+pub use adapter::{HttpServer, RequestLimits};
 
 #[cfg(feature = "ws-server")]
 pub use adapter::WebSocketServer;
 
 #[cfg(feature = "server")]
 pub use adapter::{
-    DefaultRequestProcessor, InMemoryTaskStorage, NoopPushNotificationSender,
+    DefaultRequestProcessor, InMemoryTaskStorage, NoopPushNotificationSender, ProcessingTimeout,
     PushNotificationRegistry, PushNotificationSender, SimpleAgentInfo,
 };
 
@@ -121,8 +132,12 @@ pub use adapter::{
 pub use adapter::HttpPushNotificationSender;
 
 #[cfg(any(feature = "http-server", feature = "ws-server"))]
-pub use adapter::{ApiKeyAuthenticator, BearerTokenAuthenticator, NoopAuthenticator};
+pub use adapter::{
+    ApiKeyAuthenticator, BearerTokenAuthenticator, MethodAccessPolicy, NoopAuthenticator, RoleAccess,
+};
 #[cfg(feature = "auth")]
 pub use adapter::{JwtAuthenticator, OAuth2Authenticator, OpenIdConnectAuthenticator};
+#[cfg(feature = "signing")]
+pub use adapter::{RequestSigner, RequestVerifier};
 #[cfg(any(feature = "http-server", feature = "ws-server"))]
 pub use port::Authenticator;
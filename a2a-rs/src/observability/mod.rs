@@ -3,6 +3,14 @@
 //! This module provides utilities for structured logging, tracing, and metrics collection
 //! to help with debugging, monitoring, and understanding system behavior.
 
+pub mod redaction;
+#[cfg(feature = "server")]
+pub mod task_logs;
+
+pub use redaction::RedactionConfig;
+#[cfg(feature = "server")]
+pub use task_logs::{TaskLogEntry, TaskLogHub, TaskLogLayer};
+
 #[cfg(feature = "tracing")]
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
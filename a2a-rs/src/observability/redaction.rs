@@ -0,0 +1,94 @@
+//! Redaction of sensitive values before they are logged or streamed
+//!
+//! A single [`RedactionConfig`] describes which field names are sensitive, so
+//! request bodies, log fields and debug streams all hide the same values.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Field names redacted by default; matching is case-insensitive and by substring
+pub const DEFAULT_SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "authorization",
+    "api_key",
+    "apikey",
+    "cookie",
+    "credential",
+];
+
+/// Which fields to hide and what to replace them with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// Name fragments; a field whose name contains one is redacted
+    pub fields: Vec<String>,
+    /// Value written in place of a redacted one
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+}
+
+fn default_replacement() -> String {
+    "[REDACTED]".to_string()
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            fields: DEFAULT_SENSITIVE_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+            replacement: default_replacement(),
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Create a config redacting [`DEFAULT_SENSITIVE_FIELDS`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redact fields whose name contains `field`
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.fields.push(field.into());
+        self
+    }
+
+    /// Set the value written in place of redacted ones
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+
+    /// Whether a field with this name holds a sensitive value
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        self.fields
+            .iter()
+            .any(|field| name.contains(&field.to_ascii_lowercase()))
+    }
+
+    /// Redact the value of field `name`, descending into JSON objects and arrays
+    pub fn redact_field(&self, name: &str, value: &mut Value) {
+        if self.is_sensitive(name) {
+            *value = Value::String(self.replacement.clone());
+        } else {
+            self.redact_json(value);
+        }
+    }
+
+    /// Redact every sensitive field of a JSON document in place
+    pub fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (name, value) in map.iter_mut() {
+                    self.redact_field(name, value);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Per-task capture of tracing events for debugging
+//!
+//! [`TaskLogHub`] keeps the recent log lines of each task. Its
+//! [`layer`](TaskLogHub::layer) is added to the tracing subscriber and records
+//! every event that carries a `task_id` (or `task.id`) field, either on the event
+//! itself or on one of its enclosing spans. Sensitive fields are redacted with
+//! the hub's [`RedactionConfig`] before anything is stored.
+//!
+//! ```rust,no_run
+//! use a2a_rs::observability::TaskLogHub;
+//! use tracing_subscriber::prelude::*;
+//!
+//! let hub = TaskLogHub::new();
+//! tracing_subscriber::registry()
+//!     .with(tracing_subscriber::fmt::layer())
+//!     .with(hub.layer())
+//!     .init();
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+use super::redaction::RedactionConfig;
+
+/// Field names that associate an event or span with a task
+const TASK_ID_FIELDS: &[&str] = &["task_id", "task.id", "taskId"];
+
+/// A log line captured for a task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskLogEntry {
+    /// Increases across all tasks; used as the SSE event id for resuming
+    pub id: u64,
+    pub task_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// `TRACE`, `DEBUG`, `INFO`, `WARN` or `ERROR`
+    pub level: String,
+    /// Module that emitted the event
+    pub target: String,
+    pub message: String,
+    /// Structured fields of the event, already redacted
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+}
+
+#[derive(Default)]
+struct Buffers {
+    next_id: u64,
+    logs: HashMap<String, VecDeque<TaskLogEntry>>,
+    /// Tasks in the order they first logged, for evicting the oldest
+    order: VecDeque<String>,
+}
+
+/// Recent log lines per task, fed by [`TaskLogLayer`] and read by debug endpoints
+#[derive(Clone)]
+pub struct TaskLogHub {
+    capacity: usize,
+    max_tasks: usize,
+    redaction: Arc<RedactionConfig>,
+    buffers: Arc<Mutex<Buffers>>,
+    sender: broadcast::Sender<TaskLogEntry>,
+}
+
+impl Default for TaskLogHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskLogHub {
+    /// Create a hub keeping the last 500 lines of up to 256 tasks
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(1024);
+        Self {
+            capacity: 500,
+            max_tasks: 256,
+            redaction: Arc::new(RedactionConfig::default()),
+            buffers: Arc::default(),
+            sender,
+        }
+    }
+
+    /// Set how many lines are kept per task
+    pub fn with_capacity(mut self, lines: usize) -> Self {
+        self.capacity = lines.max(1);
+        self
+    }
+
+    /// Set how many tasks are tracked before the oldest is dropped
+    pub fn with_max_tasks(mut self, tasks: usize) -> Self {
+        self.max_tasks = tasks.max(1);
+        self
+    }
+
+    /// Set the fields hidden from captured lines
+    pub fn with_redaction(mut self, redaction: RedactionConfig) -> Self {
+        self.redaction = Arc::new(redaction);
+        self
+    }
+
+    /// A tracing layer that records task events into this hub
+    pub fn layer(&self) -> TaskLogLayer {
+        TaskLogLayer { hub: self.clone() }
+    }
+
+    /// Buffered lines of a task with an id greater than `after`, oldest first
+    pub fn entries(&self, task_id: &str, after: u64) -> Vec<TaskLogEntry> {
+        let buffers = self.buffers.lock().unwrap();
+        buffers
+            .logs
+            .get(task_id)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.id > after)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Buffered lines of a task after `after`, followed by new lines as they
+    /// are logged
+    pub fn stream(
+        &self,
+        task_id: &str,
+        after: u64,
+    ) -> impl Stream<Item = TaskLogEntry> + Send + 'static {
+        // Subscribe before reading the buffer so no line falls in between
+        let receiver = self.sender.subscribe();
+        let backlog = self.entries(task_id, after);
+        let last = backlog.last().map_or(after, |entry| entry.id);
+        let task_id = task_id.to_string();
+
+        let live = futures::stream::unfold((receiver, last), move |(mut receiver, last)| {
+            let task_id = task_id.clone();
+            async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(entry) if entry.task_id == task_id && entry.id > last => {
+                            let id = entry.id;
+                            return Some((entry, (receiver, id)));
+                        }
+                        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+        futures::stream::iter(backlog).chain(live)
+    }
+
+    fn record(&self, task_id: String, event: &Event<'_>, mut visitor: FieldVisitor) {
+        for (name, value) in visitor.fields.iter_mut() {
+            self.redaction.redact_field(name, value);
+        }
+        let metadata = event.metadata();
+
+        // Ids are handed out under the lock so buffers and stream stay in order
+        let mut buffers = self.buffers.lock().unwrap();
+        buffers.next_id += 1;
+        let entry = TaskLogEntry {
+            id: buffers.next_id,
+            task_id,
+            timestamp: Utc::now(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message.unwrap_or_default(),
+            fields: visitor.fields,
+        };
+
+        if !buffers.logs.contains_key(&entry.task_id) {
+            if buffers.order.len() >= self.max_tasks {
+                if let Some(oldest) = buffers.order.pop_front() {
+                    buffers.logs.remove(&oldest);
+                }
+            }
+            buffers.order.push_back(entry.task_id.clone());
+        }
+        let entries = buffers.logs.entry(entry.task_id.clone()).or_default();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+
+        // No receivers just means nobody is watching
+        let _ = self.sender.send(entry);
+    }
+}
+
+/// Tracing layer feeding a [`TaskLogHub`]
+pub struct TaskLogLayer {
+    hub: TaskLogHub,
+}
+
+/// Task id recorded on a span, inherited by the events inside it
+struct SpanTaskId(String);
+
+impl<S> Layer<S> for TaskLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(task_id), Some(span)) = (visitor.task_id, ctx.span(id)) {
+            span.extensions_mut().insert(SpanTaskId(task_id));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let (Some(task_id), Some(span)) = (visitor.task_id, ctx.span(id)) {
+            span.extensions_mut().replace(SpanTaskId(task_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let task_id = visitor.task_id.take().or_else(|| {
+            ctx.event_scope(event)?.find_map(|span| {
+                span.extensions()
+                    .get::<SpanTaskId>()
+                    .map(|task_id| task_id.0.clone())
+            })
+        });
+        if let Some(task_id) = task_id {
+            self.hub.record(task_id, event, visitor);
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    task_id: Option<String>,
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        match field.name() {
+            "message" => {
+                self.message = Some(match value {
+                    Value::String(message) => message,
+                    other => other.to_string(),
+                })
+            }
+            name if TASK_ID_FIELDS.contains(&name) => {
+                self.task_id = Some(match value {
+                    Value::String(task_id) => task_id,
+                    other => other.to_string(),
+                })
+            }
+            name => {
+                self.fields.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::String(format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+}
@@ -51,6 +51,12 @@ pub trait Authenticator: Send + Sync {
     fn validate_context(&self, context: &AuthContext) -> Result<(), A2AError>;
 }
 
+/// Principal attribute holding the caller's role
+pub const ROLE_ATTRIBUTE: &str = "role";
+
+/// Role required to call `admin/*` methods
+pub const ADMIN_ROLE: &str = "admin";
+
 /// Represents an authenticated principal
 #[derive(Debug, Clone)]
 pub struct AuthPrincipal {
@@ -77,6 +83,16 @@ impl AuthPrincipal {
         self.attributes.insert(key, value);
         self
     }
+
+    /// Assign the principal a role, which decides the methods it may call
+    pub fn with_role(self, role: String) -> Self {
+        self.with_attribute(ROLE_ATTRIBUTE.to_string(), role)
+    }
+
+    /// The principal's role, if it was assigned one
+    pub fn role(&self) -> Option<&str> {
+        self.attributes.get(ROLE_ATTRIBUTE).map(String::as_str)
+    }
 }
 
 /// Port interface for authentication context extraction
@@ -46,6 +46,27 @@ pub trait AsyncMessageHandler: Send + Sync {
         session_id: Option<&'a str>,
     ) -> Result<Task, A2AError>;
 
+    /// Process a message for a task the sender expects to be at
+    /// `expected_version`.
+    ///
+    /// Handlers compare the version as part of their first write to the task,
+    /// such as with
+    /// [`update_task_status_at_version`](crate::port::AsyncTaskManager::update_task_status_at_version),
+    /// so that of several sends expecting one version only one goes ahead,
+    /// and a send failing before that write leaves the version as it was.
+    /// The default refuses the message, since it cannot make that write.
+    async fn process_message_at_version<'a>(
+        &self,
+        _task_id: &'a str,
+        _message: &'a Message,
+        _session_id: Option<&'a str>,
+        _expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "This agent cannot process messages at an expected task version".to_string(),
+        ))
+    }
+
     /// Validate a message before processing
     async fn validate_message<'a>(&self, message: &'a Message) -> Result<(), A2AError> {
         // Default implementation - can be overridden
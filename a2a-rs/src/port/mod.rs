@@ -28,4 +28,4 @@ pub use notification_manager::{AsyncNotificationManager, NotificationManager};
 pub use streaming_handler::{
     AsyncStreamingHandler, StreamingHandler, Subscriber as StreamingSubscriber, UpdateEvent,
 };
-pub use task_manager::{AsyncTaskManager, TaskManager};
+pub use task_manager::{AsyncTaskManager, ListTasksStream, TaskManager};
@@ -4,6 +4,11 @@
 use async_trait::async_trait;
 
 use crate::domain::{A2AError, PushNotificationConfig, TaskIdParams, TaskPushNotificationConfig};
+#[cfg(feature = "server")]
+use crate::domain::{
+    DeadLetter, ListDeadLettersParams, PurgeDeadLettersParams, PurgeDeadLettersResult,
+    RequeueDeadLetterResult,
+};
 
 /// A trait for managing push notification configurations and delivery
 pub trait NotificationManager {
@@ -195,4 +200,34 @@ pub trait AsyncNotificationManager: Send + Sync {
 
         Ok(())
     }
+
+    /// Push notifications that could not be delivered, oldest first
+    async fn list_dead_letters<'a>(
+        &self,
+        _params: &'a ListDeadLettersParams,
+    ) -> Result<Vec<DeadLetter>, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Undelivered push notifications are not kept".to_string(),
+        ))
+    }
+
+    /// Deliver the dead letter `id` again
+    async fn requeue_dead_letter<'a>(
+        &self,
+        _id: &'a str,
+    ) -> Result<RequeueDeadLetterResult, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Undelivered push notifications are not kept".to_string(),
+        ))
+    }
+
+    /// Drop the dead letters matching `params`
+    async fn purge_dead_letters<'a>(
+        &self,
+        _params: &'a PurgeDeadLettersParams,
+    ) -> Result<PurgeDeadLettersResult, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Undelivered push notifications are not kept".to_string(),
+        ))
+    }
 }
@@ -5,6 +5,8 @@ use async_trait::async_trait;
 use futures::Stream;
 use std::pin::Pin;
 
+#[cfg(feature = "server")]
+use crate::domain::TaskSnapshotOptions;
 use crate::domain::{A2AError, TaskArtifactUpdateEvent, TaskStatusUpdateEvent};
 
 /// A trait for subscribing to real-time updates
@@ -26,6 +28,12 @@ pub trait Subscriber<T>: Send + Sync {
         // Default implementation - no-op
         Ok(())
     }
+
+    /// The task snapshots this subscriber wants with status updates, if any.
+    /// Only status subscribers are sent snapshots.
+    fn task_snapshots(&self) -> Option<TaskSnapshotOptions> {
+        None
+    }
 }
 
 /// A trait for managing streaming connections and real-time updates
@@ -6,9 +6,13 @@ use async_trait::async_trait;
 use crate::{
     Message,
     domain::{
-        A2AError, DeleteTaskPushNotificationConfigParams, GetTaskPushNotificationConfigParams,
-        ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult, Task, TaskIdParams,
-        TaskPushNotificationConfig, TaskQueryParams, TaskState,
+        A2AError, Artifact, ArtifactSummary, DeleteTaskPushNotificationConfigParams, ErrorDetail,
+        GetOrCreateTaskResult, GetTaskEventsParams, GetTaskEventsResult,
+        GetTaskPushNotificationConfigParams, ImportTasksResult, InputRequest,
+        ListTaskPushNotificationConfigParams, ListTasksParams, ListTasksResult,
+        ListTasksStreamItem, Task, TaskCancellation, TaskField, TaskIdParams,
+        TaskPushNotificationConfig, TaskQueryParams, TaskResult, TaskState, TaskTagsParams,
+        core::task::MAX_IMPORT_TASKS, error_catalog::codes,
     },
 };
 
@@ -78,6 +82,11 @@ pub trait TaskManager {
     }
 }
 
+/// Stream of a task listing, see [`AsyncTaskManager::list_tasks_stream`]
+#[cfg(any(feature = "client", feature = "server"))]
+pub type ListTasksStream =
+    std::pin::Pin<Box<dyn futures::Stream<Item = Result<ListTasksStreamItem, A2AError>> + Send>>;
+
 #[cfg(feature = "server")]
 #[async_trait]
 /// An async trait for managing task lifecycle and operations
@@ -96,6 +105,66 @@ pub trait AsyncTaskManager: Send + Sync {
         history_length: Option<u32>,
     ) -> Result<Task, A2AError>;
 
+    /// Get a task by ID with optional history, leaving the `excluded` fields
+    /// out as described on [`Task::without_fields`].
+    ///
+    /// The default strips them from the task [`get_task`](Self::get_task)
+    /// returns; storages override it to never load what is left out.
+    async fn get_task_excluding<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+        excluded: &'a [TaskField],
+    ) -> Result<Task, A2AError> {
+        let history_length = if excluded.contains(&TaskField::History) {
+            Some(0)
+        } else {
+            history_length
+        };
+        Ok(self
+            .get_task(task_id, history_length)
+            .await?
+            .without_fields(excluded))
+    }
+
+    /// Return the task `task_id` if it exists, or create it with
+    /// `initial_message` in its history, reporting which happened.
+    ///
+    /// Concurrent callers with the same ID must end up with one task, created
+    /// by exactly one of them. The default relies on `create_task` refusing an
+    /// existing ID and records the message in a second step; storages should
+    /// override it to create the task and its message at once.
+    async fn get_or_create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+        initial_message: Option<&'a Message>,
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        let task = match self.create_task(task_id, context_id).await {
+            Ok(task) => task,
+            Err(e) => {
+                if !self.task_exists(task_id).await? {
+                    return Err(e);
+                }
+                return Ok(GetOrCreateTaskResult {
+                    task: self.get_task(task_id, None).await?,
+                    created: false,
+                });
+            }
+        };
+        let task = match initial_message {
+            Some(message) => {
+                self.update_task_status(task_id, TaskState::Submitted, Some(message.clone()))
+                    .await?
+            }
+            None => task,
+        };
+        Ok(GetOrCreateTaskResult {
+            task,
+            created: true,
+        })
+    }
+
     /// Update task status with an optional message to add to history
     async fn update_task_status<'a>(
         &self,
@@ -184,6 +253,285 @@ pub trait AsyncTaskManager: Send + Sync {
         ))
     }
 
+    /// Stream the page of tasks `list_tasks_v3` would return, one task at a
+    /// time, followed by a [`ListTasksSummary`].
+    ///
+    /// `page_size` still bounds the number of tasks. The default buffers the
+    /// whole page; storages should override it to load each task only when
+    /// the consumer asks for it.
+    async fn list_tasks_stream<'a>(
+        &self,
+        params: &'a ListTasksParams,
+    ) -> Result<ListTasksStream, A2AError> {
+        let result = self.list_tasks_v3(params).await?;
+        let items = result.into_stream_items().into_iter().map(Ok);
+        Ok(Box::pin(futures::stream::iter(items)))
+    }
+
+    /// Get a page of the task's append-only event log, oldest event first
+    async fn get_task_events<'a>(
+        &self,
+        _params: &'a GetTaskEventsParams,
+    ) -> Result<GetTaskEventsResult, A2AError> {
+        // Default implementation returns unsupported error
+        Err(A2AError::UnsupportedOperation(
+            "Task event log not implemented".to_string(),
+        ))
+    }
+
+    // ===== Results =====
+
+    /// Attach a structured result to a task, replacing any previous result
+    async fn set_task_result<'a>(
+        &self,
+        _task_id: &'a str,
+        _result: TaskResult,
+    ) -> Result<Task, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task results not implemented".to_string(),
+        ))
+    }
+
+    /// Attach a structured result and mark the task completed, so the terminal
+    /// status event carries the result.
+    ///
+    /// The default sets the result and the status one after the other;
+    /// storages override it to make both changes in one write.
+    async fn complete_task_with_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: TaskResult,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.set_task_result(task_id, result).await?;
+        self.update_task_status(task_id, TaskState::Completed, message)
+            .await
+    }
+
+    // ===== Artifacts =====
+
+    /// Record an artifact the agent produced for a task, replacing the one
+    /// with the same ID. The artifact is stamped with its creation time, sent
+    /// to artifact subscribers and listed on the task's terminal status event.
+    async fn add_task_artifact<'a>(
+        &self,
+        _task_id: &'a str,
+        _artifact: Artifact,
+    ) -> Result<Task, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task artifacts not implemented".to_string(),
+        ))
+    }
+
+    /// Summaries of a task's artifacts, in the order they were produced
+    async fn list_task_artifacts<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<Vec<ArtifactSummary>, A2AError> {
+        let task = self.get_task(task_id, Some(0)).await?;
+        Ok(task
+            .artifacts
+            .iter()
+            .flatten()
+            .map(ArtifactSummary::from)
+            .collect())
+    }
+
+    /// One artifact of a task, failing with [`codes::ARTIFACT_NOT_FOUND`] if
+    /// the task has none with that ID
+    async fn get_task_artifact<'a>(
+        &self,
+        task_id: &'a str,
+        artifact_id: &'a str,
+    ) -> Result<Artifact, A2AError> {
+        let task = self.get_task(task_id, Some(0)).await?;
+        task.artifact(artifact_id).cloned().ok_or_else(|| {
+            A2AError::UserError(
+                ErrorDetail::new(codes::ARTIFACT_NOT_FOUND)
+                    .with_param("taskId", task_id)
+                    .with_param("artifactId", artifact_id),
+            )
+        })
+    }
+
+    // ===== Input =====
+
+    /// Pause a task until the client answers `request`.
+    ///
+    /// The task moves to input-required and the request is delivered with its
+    /// status event; the client's next message to the task resumes it. Tasks
+    /// in a terminal state are refused with [`A2AError::InvalidParams`].
+    ///
+    /// The default checks the state before updating it, so a task finishing
+    /// in between can still be reopened; storages override it to check and
+    /// update in one step.
+    async fn request_input<'a>(
+        &self,
+        task_id: &'a str,
+        request: &'a InputRequest,
+    ) -> Result<Task, A2AError> {
+        let task = self.get_task(task_id, Some(0)).await?;
+        task.check_can_request_input()?;
+        let message =
+            request.status_message(task_id, &task.context_id, uuid::Uuid::new_v4().to_string());
+        self.update_task_status(task_id, TaskState::InputRequired, Some(message))
+            .await
+    }
+
+    // ===== Concurrency =====
+
+    /// Fail with [`A2AError::VersionConflict`] unless the task is at
+    /// `expected_version`, returning the task when it is
+    async fn check_task_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        let task = self.get_task(task_id, Some(0)).await?;
+        task.check_version(expected_version)?;
+        Ok(task)
+    }
+
+    /// Update task status only if the task is still at `expected_version`.
+    ///
+    /// The default checks the version before updating; storages should
+    /// override it to check and update atomically.
+    async fn update_task_status_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.check_task_version(task_id, expected_version).await?;
+        self.update_task_status(task_id, state, message).await
+    }
+
+    /// Cancel a task only if it is still at `expected_version`.
+    ///
+    /// The default checks the version before canceling; storages should
+    /// override it to check and cancel atomically.
+    async fn cancel_task_at_version<'a>(
+        &self,
+        task_id: &'a str,
+        expected_version: u64,
+    ) -> Result<Task, A2AError> {
+        self.check_task_version(task_id, expected_version).await?;
+        self.cancel_task(task_id).await
+    }
+
+    /// Cancel a task, recording who canceled it and why.
+    ///
+    /// With `expected_version` this behaves like `cancel_task_at_version`.
+    /// The default drops the cancellation details; storages should override
+    /// it to record them on the task.
+    async fn cancel_task_with<'a>(
+        &self,
+        task_id: &'a str,
+        cancellation: &'a TaskCancellation,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        let _ = cancellation;
+        match expected_version {
+            Some(expected_version) => self.cancel_task_at_version(task_id, expected_version).await,
+            None => self.cancel_task(task_id).await,
+        }
+    }
+
+    // ===== Tags =====
+
+    /// Add tags to a task, returning the updated task. Tags the task already
+    /// carries are ignored; an invalid tag fails the whole request.
+    async fn add_task_tags<'a>(&self, _params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task tags not implemented".to_string(),
+        ))
+    }
+
+    /// Remove tags from a task, returning the updated task. Tags the task
+    /// does not carry are ignored.
+    async fn remove_task_tags<'a>(&self, _params: &'a TaskTagsParams) -> Result<Task, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task tags not implemented".to_string(),
+        ))
+    }
+
+    // ===== References =====
+
+    /// List the tasks whose messages reference `task_id`, ordered by id and
+    /// without history
+    async fn list_referencing_tasks<'a>(&self, _task_id: &'a str) -> Result<Vec<Task>, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task references not implemented".to_string(),
+        ))
+    }
+
+    // ===== Import =====
+
+    /// Store a task as given, bypassing message handling; only the storage's
+    /// sanitizer may change its text. Fails if a task with the same ID
+    /// already exists.
+    ///
+    /// Callers validate the task first; [`AsyncTaskManager::import_tasks`]
+    /// does so for each task it imports.
+    async fn import_task<'a>(&self, _task: &'a Task) -> Result<(), A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task import not implemented".to_string(),
+        ))
+    }
+
+    /// Validate and store each task, reporting every task's outcome so that
+    /// one bad record does not stop the rest
+    async fn import_tasks<'a>(&self, tasks: &'a [Task]) -> Result<ImportTasksResult, A2AError> {
+        if tasks.len() > MAX_IMPORT_TASKS {
+            return Err(A2AError::InvalidParams(format!(
+                "An import may carry at most {} tasks",
+                MAX_IMPORT_TASKS
+            )));
+        }
+        let mut result = ImportTasksResult::default();
+        for task in tasks {
+            let outcome = match task.validate_for_import() {
+                Ok(()) => self.import_task(task).await,
+                Err(e) => Err(e),
+            };
+            result.record(&task.id, outcome);
+        }
+        Ok(result)
+    }
+
+    // ===== Retention =====
+
+    /// List tasks in `state` whose status was last updated before `before`,
+    /// oldest first, returning at most `limit` tasks
+    async fn list_tasks_updated_before<'a>(
+        &self,
+        _state: &'a TaskState,
+        _before: chrono::DateTime<chrono::Utc>,
+        _limit: usize,
+    ) -> Result<Vec<Task>, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Listing tasks by age not implemented".to_string(),
+        ))
+    }
+
+    /// Permanently delete tasks and their associated data, returning how many
+    /// were removed. Unknown IDs are ignored.
+    async fn delete_tasks<'a>(&self, _task_ids: &'a [String]) -> Result<usize, A2AError> {
+        Err(A2AError::UnsupportedOperation(
+            "Task deletion not implemented".to_string(),
+        ))
+    }
+
+    // ===== Health =====
+
+    /// Check that the storage can serve requests, failing with
+    /// [`A2AError::Unavailable`] while its backend is out of reach. Readiness
+    /// probes call this; storages without a backend are always ready.
+    async fn check_health(&self) -> Result<(), A2AError> {
+        Ok(())
+    }
+
     /// Get push notification config by ID (v0.3.0)
     async fn get_push_notification_config<'a>(
         &self,
@@ -1,14 +1,25 @@
 //! Client interface traits
 
 use async_trait::async_trait;
-use futures::Stream;
+use futures::{Stream, TryStreamExt, stream};
 use std::pin::Pin;
 
 use crate::{
+    application::json_rpc::{
+        AddTaskTagsRequest, CancelTaskRequest, GetOrCreateTaskRequest, GetTaskArtifactRequest,
+        GetTaskEventsRequest, GetTaskRequest, ImportTasksRequest, ListDeadLettersRequest,
+        ListTaskArtifactsRequest, PurgeDeadLettersRequest, RemoveTaskTagsRequest,
+        RequeueDeadLetterRequest,
+    },
     application::{JSONRPCResponse, json_rpc::A2ARequest},
     domain::{
-        A2AError, ListTasksParams, ListTasksResult, Message, Task, TaskArtifactUpdateEvent,
-        TaskPushNotificationConfig, TaskStatusUpdateEvent,
+        A2AError, ArtifactSummary, DeadLetter, GetOrCreateTaskParams, GetOrCreateTaskResult,
+        GetTaskArtifactParams, GetTaskArtifactResult, GetTaskEventsParams, GetTaskEventsResult,
+        ImportTasksParams, ImportTasksResult, ListDeadLettersParams, ListTaskArtifactsResult,
+        ListTasksParams, ListTasksResult, Message, Part, PurgeDeadLettersParams,
+        PurgeDeadLettersResult, RequeueDeadLetterParams, RequeueDeadLetterResult, Task,
+        TaskArtifactUpdateEvent, TaskField, TaskIdParams, TaskPushNotificationConfig,
+        TaskQueryParams, TaskStatusUpdateEvent, TaskTagsParams,
     },
 };
 
@@ -37,9 +48,64 @@ pub trait AsyncA2AClient: Send + Sync {
         history_length: Option<u32>,
     ) -> Result<Task, A2AError>;
 
+    /// Fetch a task with only the history entries added after `since`, a
+    /// `historyCursor` from an earlier response; `None` returns the full history
+    async fn get_task_since<'a>(
+        &self,
+        task_id: &'a str,
+        since: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        let request = GetTaskRequest::new(TaskQueryParams {
+            id: task_id.to_string(),
+            history_length: None,
+            metadata: None,
+            since: since.map(str::to_string),
+            snapshot: None,
+            exclude: Vec::new(),
+        });
+        let response = self.send_request(&A2ARequest::GetTask(request)).await?;
+        decode_result(response)
+    }
+
+    /// Fetch a task without the `exclude`d fields, for callers that only show
+    /// part of it and need not download the rest
+    async fn get_task_excluding<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+        exclude: &'a [TaskField],
+    ) -> Result<Task, A2AError> {
+        let request = GetTaskRequest::new(TaskQueryParams {
+            id: task_id.to_string(),
+            history_length,
+            metadata: None,
+            since: None,
+            snapshot: None,
+            exclude: exclude.to_vec(),
+        });
+        let response = self.send_request(&A2ARequest::GetTask(request)).await?;
+        decode_result(response)
+    }
+
     /// Cancel a task
     async fn cancel_task<'a>(&self, task_id: &'a str) -> Result<Task, A2AError>;
 
+    /// Cancel a task, recording why on the task
+    async fn cancel_task_with_reason<'a>(
+        &self,
+        task_id: &'a str,
+        reason: &'a str,
+    ) -> Result<Task, A2AError> {
+        let request = CancelTaskRequest::new(TaskIdParams {
+            id: task_id.to_string(),
+            metadata: None,
+            expected_version: None,
+            reason: Some(reason.to_string()),
+        });
+        let response = self.send_request(&A2ARequest::CancelTask(request)).await?;
+        decode_result(response)
+    }
+
     /// Set up push notifications for a task
     async fn set_task_push_notification<'a>(
         &self,
@@ -78,6 +144,189 @@ pub trait AsyncA2AClient: Send + Sync {
         config_id: &'a str,
     ) -> Result<(), A2AError>;
 
+    /// Get a page of a task's event log, oldest event first
+    async fn get_task_events<'a>(
+        &self,
+        params: &'a GetTaskEventsParams,
+    ) -> Result<GetTaskEventsResult, A2AError> {
+        let request = GetTaskEventsRequest::new(params.clone());
+        let response = self
+            .send_request(&A2ARequest::GetTaskEvents(request))
+            .await?;
+        decode_result(response)
+    }
+
+    /// List the artifacts a task produced, without their content
+    async fn list_task_artifacts<'a>(
+        &self,
+        task_id: &'a str,
+    ) -> Result<Vec<ArtifactSummary>, A2AError> {
+        let request = ListTaskArtifactsRequest::new(TaskIdParams {
+            id: task_id.to_string(),
+            metadata: None,
+            expected_version: None,
+            reason: None,
+        });
+        let response = self
+            .send_request(&A2ARequest::ListTaskArtifacts(request))
+            .await?;
+        let result: ListTaskArtifactsResult = decode_result(response)?;
+        Ok(result.artifacts)
+    }
+
+    /// Stream the parts of a task's artifact, fetching one page of parts per
+    /// request so a large artifact is never held in a single response
+    fn get_task_artifact<'a>(
+        &'a self,
+        task_id: &'a str,
+        artifact_id: &'a str,
+    ) -> Pin<Box<dyn Stream<Item = Result<Part, A2AError>> + Send + 'a>> {
+        let pages = stream::try_unfold(Some(0), move |offset| async move {
+            let Some(offset) = offset else {
+                return Ok::<_, A2AError>(None);
+            };
+            let request = GetTaskArtifactRequest::new(GetTaskArtifactParams {
+                id: task_id.to_string(),
+                artifact_id: artifact_id.to_string(),
+                part_offset: Some(offset),
+                ..Default::default()
+            });
+            let response = self
+                .send_request(&A2ARequest::GetTaskArtifact(request))
+                .await?;
+            let page: GetTaskArtifactResult = decode_result(response)?;
+            Ok(Some((page.artifact.parts, page.next_part_offset)))
+        });
+        Box::pin(
+            pages
+                .map_ok(|parts| stream::iter(parts.into_iter().map(Ok)))
+                .try_flatten(),
+        )
+    }
+
+    /// Add tags to a task, returning the updated task
+    async fn add_task_tags<'a>(
+        &self,
+        task_id: &'a str,
+        tags: &'a [String],
+    ) -> Result<Task, A2AError> {
+        let request = AddTaskTagsRequest::new(TaskTagsParams {
+            id: task_id.to_string(),
+            tags: tags.to_vec(),
+            metadata: None,
+        });
+        let response = self.send_request(&A2ARequest::AddTaskTags(request)).await?;
+        decode_result(response)
+    }
+
+    /// Remove tags from a task, returning the updated task
+    async fn remove_task_tags<'a>(
+        &self,
+        task_id: &'a str,
+        tags: &'a [String],
+    ) -> Result<Task, A2AError> {
+        let request = RemoveTaskTagsRequest::new(TaskTagsParams {
+            id: task_id.to_string(),
+            tags: tags.to_vec(),
+            metadata: None,
+        });
+        let response = self
+            .send_request(&A2ARequest::RemoveTaskTags(request))
+            .await?;
+        decode_result(response)
+    }
+
+    /// Open the task `task_id`, creating it with `initial_message` if it does
+    /// not exist yet. Calling this again with the same ID returns the same
+    /// task, even when several clients race to create it.
+    async fn get_or_create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: Option<&'a str>,
+        initial_message: Option<&'a Message>,
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        self.get_or_create_task_excluding(task_id, context_id, initial_message, &[])
+            .await
+    }
+
+    /// Like [`get_or_create_task`](Self::get_or_create_task), leaving the
+    /// `exclude`d fields out of the returned task
+    async fn get_or_create_task_excluding<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: Option<&'a str>,
+        initial_message: Option<&'a Message>,
+        exclude: &'a [TaskField],
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        let request = GetOrCreateTaskRequest::new(GetOrCreateTaskParams {
+            id: task_id.to_string(),
+            context_id: context_id.map(str::to_string),
+            message: initial_message.cloned(),
+            metadata: None,
+            exclude: exclude.to_vec(),
+        });
+        let response = self
+            .send_request(&A2ARequest::GetOrCreateTask(request))
+            .await?;
+        decode_result(response)
+    }
+
+    /// Import tasks as they are, reporting which were stored. The agent only
+    /// accepts this from admins.
+    async fn import_tasks<'a>(&self, tasks: &'a [Task]) -> Result<ImportTasksResult, A2AError> {
+        let tasks = tasks
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()?;
+        let request = ImportTasksRequest::new(ImportTasksParams {
+            tasks,
+            metadata: None,
+        });
+        let response = self.send_request(&A2ARequest::ImportTasks(request)).await?;
+        decode_result(response)
+    }
+
+    /// List the push notifications the agent could not deliver, optionally
+    /// only those about `task_id`. The agent only accepts this from admins.
+    async fn list_dead_letters<'a>(
+        &self,
+        task_id: Option<&'a str>,
+    ) -> Result<Vec<DeadLetter>, A2AError> {
+        let request = ListDeadLettersRequest::new(ListDeadLettersParams {
+            task_id: task_id.map(str::to_string),
+        });
+        let response = self
+            .send_request(&A2ARequest::ListDeadLetters(request))
+            .await?;
+        decode_result(response)
+    }
+
+    /// Have the agent deliver the dead letter `id` again. The agent only
+    /// accepts this from admins.
+    async fn requeue_dead_letter<'a>(
+        &self,
+        id: &'a str,
+    ) -> Result<RequeueDeadLetterResult, A2AError> {
+        let request = RequeueDeadLetterRequest::new(RequeueDeadLetterParams { id: id.to_string() });
+        let response = self
+            .send_request(&A2ARequest::RequeueDeadLetter(request))
+            .await?;
+        decode_result(response)
+    }
+
+    /// Drop the agent's dead letters matching `params`. The agent only
+    /// accepts this from admins.
+    async fn purge_dead_letters<'a>(
+        &self,
+        params: &'a PurgeDeadLettersParams,
+    ) -> Result<PurgeDeadLettersResult, A2AError> {
+        let request = PurgeDeadLettersRequest::new(params.clone());
+        let response = self
+            .send_request(&A2ARequest::PurgeDeadLetters(request))
+            .await?;
+        decode_result(response)
+    }
+
     /// Subscribe to task updates (for streaming)
     async fn subscribe_to_task<'a>(
         &self,
@@ -86,6 +335,21 @@ pub trait AsyncA2AClient: Send + Sync {
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamItem, A2AError>> + Send>>, A2AError>;
 }
 
+/// Deserialize the result of a JSON-RPC response, or surface its error
+fn decode_result<T: serde::de::DeserializeOwned>(response: JSONRPCResponse) -> Result<T, A2AError> {
+    match response.result {
+        Some(value) => Ok(serde_json::from_value(value)?),
+        None => match response.error {
+            Some(error) => Err(A2AError::JsonRpc {
+                code: error.code,
+                message: error.message,
+                data: error.data,
+            }),
+            None => Err(A2AError::Internal("Empty response".to_string())),
+        },
+    }
+}
+
 /// Items that can be streamed from the server during task subscriptions.\n///\n/// When subscribing to streaming updates for a task, the server can send\n/// different types of items:\n/// - `Task`: The complete initial task state when subscription starts\n/// - `StatusUpdate`: Updates to the task's status (state changes, progress)\n/// - `ArtifactUpdate`: Notifications about new or updated artifacts\n///\n/// This allows clients to receive real-time updates about task progress\n/// and results as they become available.
 #[derive(Debug, Clone)]
 pub enum StreamItem {
@@ -96,3 +360,45 @@ pub enum StreamItem {
     /// A task artifact update
     ArtifactUpdate(TaskArtifactUpdateEvent),
 }
+
+impl StreamItem {
+    /// Bring a locally kept copy of the task up to date with this item.
+    ///
+    /// A task replaces the copy, a status update sets its status and appends
+    /// the status message to its history, and an artifact update adds,
+    /// replaces or appends to the artifact. Updates arriving before any task
+    /// leave `task` unset.
+    pub fn apply_to(&self, task: &mut Option<Task>) {
+        match self {
+            StreamItem::Task(snapshot) => *task = Some(snapshot.clone()),
+            StreamItem::StatusUpdate(update) => {
+                if let Some(snapshot) = &update.task {
+                    *task = Some(snapshot.as_ref().clone());
+                } else if let Some(task) = task {
+                    task.status = update.status.clone();
+                    if let Some(message) = &update.status.message {
+                        let history = task.history.get_or_insert_with(Vec::new);
+                        if !history.iter().any(|m| m.message_id == message.message_id) {
+                            history.push(message.clone());
+                        }
+                    }
+                }
+            }
+            StreamItem::ArtifactUpdate(update) => {
+                if let Some(task) = task {
+                    let artifacts = task.artifacts.get_or_insert_with(Vec::new);
+                    let existing = artifacts
+                        .iter_mut()
+                        .find(|artifact| artifact.artifact_id == update.artifact.artifact_id);
+                    match existing {
+                        Some(existing) if update.append == Some(true) => {
+                            existing.parts.extend(update.artifact.parts.iter().cloned())
+                        }
+                        Some(existing) => *existing = update.artifact.clone(),
+                        None => artifacts.push(update.artifact.clone()),
+                    }
+                }
+            }
+        }
+    }
+}
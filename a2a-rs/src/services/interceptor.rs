@@ -0,0 +1,134 @@
+//! Interceptors for outgoing client requests
+//!
+//! Interceptors see every structured request a client sends. They run in
+//! registration order before the request goes out, and in reverse order once
+//! the response is back, so the first interceptor registered wraps all others.
+
+use async_trait::async_trait;
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use crate::{
+    application::{JSONRPCResponse, json_rpc::A2ARequest},
+    domain::A2AError,
+};
+
+/// An outgoing request as seen by interceptors
+#[derive(Debug, Clone)]
+pub struct ClientRequest {
+    /// The JSON-RPC request, which interceptors may rewrite
+    pub request: A2ARequest,
+    /// Extra HTTP headers sent with the request.
+    ///
+    /// WebSocket frames have no headers, so these are dropped over WebSocket.
+    pub headers: HashMap<String, String>,
+}
+
+impl ClientRequest {
+    /// Wrap a request with no extra headers
+    pub fn new(request: A2ARequest) -> Self {
+        Self {
+            request,
+            headers: HashMap::new(),
+        }
+    }
+
+    /// The JSON-RPC method of the request
+    pub fn method(&self) -> &str {
+        self.request.method()
+    }
+}
+
+/// Hooks run around every structured request a client sends
+#[async_trait]
+pub trait RequestInterceptor: Send + Sync {
+    /// Inspect or rewrite a request before it is sent.
+    ///
+    /// Returning a response skips the transport and the interceptors after this
+    /// one; returning an error fails the request without sending it.
+    async fn before_request(
+        &self,
+        _request: &mut ClientRequest,
+    ) -> Result<Option<JSONRPCResponse>, A2AError> {
+        Ok(None)
+    }
+
+    /// Inspect or replace the outcome of a request
+    async fn after_response(
+        &self,
+        _request: &ClientRequest,
+        _response: &mut Result<JSONRPCResponse, A2AError>,
+    ) {
+    }
+}
+
+/// An ordered list of interceptors shared by a client and its clones
+#[derive(Clone, Default)]
+pub struct InterceptorChain {
+    interceptors: Vec<Arc<dyn RequestInterceptor>>,
+}
+
+impl std::fmt::Debug for InterceptorChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InterceptorChain")
+            .field("interceptors", &self.interceptors.len())
+            .finish()
+    }
+}
+
+impl InterceptorChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an interceptor to the chain
+    pub fn with(mut self, interceptor: impl RequestInterceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Whether the chain has no interceptors
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// Run `request` through the chain, calling `send` unless an interceptor
+    /// answers it first
+    pub async fn run<F, Fut>(
+        &self,
+        request: A2ARequest,
+        send: F,
+    ) -> Result<JSONRPCResponse, A2AError>
+    where
+        F: FnOnce(ClientRequest) -> Fut,
+        Fut: Future<Output = Result<JSONRPCResponse, A2AError>>,
+    {
+        let mut request = ClientRequest::new(request);
+        let mut ran = 0;
+        let mut answered = None;
+        for interceptor in &self.interceptors {
+            ran += 1;
+            match interceptor.before_request(&mut request).await {
+                Ok(None) => {}
+                Ok(Some(response)) => {
+                    answered = Some(Ok(response));
+                    break;
+                }
+                Err(e) => {
+                    answered = Some(Err(e));
+                    break;
+                }
+            }
+        }
+
+        let mut response = match answered {
+            Some(response) => response,
+            None => send(request.clone()).await,
+        };
+        // Only interceptors that saw the request see its response
+        for interceptor in self.interceptors[..ran].iter().rev() {
+            interceptor.after_response(&request, &mut response).await;
+        }
+        response
+    }
+}
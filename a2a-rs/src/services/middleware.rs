@@ -0,0 +1,122 @@
+//! Middleware around server-side request dispatch
+//!
+//! Middleware sees every parsed request a processor dispatches, together with
+//! the principal that sent it. It runs in registration order before dispatch
+//! and in reverse order afterwards, so the first middleware registered wraps
+//! all others.
+
+use async_trait::async_trait;
+use std::{future::Future, sync::Arc};
+
+use crate::{
+    application::{JSONRPCResponse, json_rpc::A2ARequest},
+    domain::A2AError,
+    port::AuthPrincipal,
+};
+
+/// An incoming request as seen by middleware
+#[derive(Debug, Clone)]
+pub struct ServerRequest {
+    /// The JSON-RPC request, which middleware may rewrite
+    pub request: A2ARequest,
+    /// The authenticated caller, if the transport authenticated one
+    pub principal: Option<AuthPrincipal>,
+}
+
+impl ServerRequest {
+    /// Wrap a request sent by `principal`
+    pub fn new(request: A2ARequest, principal: Option<AuthPrincipal>) -> Self {
+        Self { request, principal }
+    }
+
+    /// The JSON-RPC method of the request
+    pub fn method(&self) -> &str {
+        self.request.method()
+    }
+}
+
+/// Hooks run around the dispatch of every request
+#[async_trait]
+pub trait RequestMiddleware: Send + Sync {
+    /// Inspect or rewrite a request before it reaches the handlers.
+    ///
+    /// Returning an error rejects the request; the handlers and the middleware
+    /// after this one never see it.
+    async fn before_dispatch(&self, _request: &mut ServerRequest) -> Result<(), A2AError> {
+        Ok(())
+    }
+
+    /// Inspect or replace the outcome of a request
+    async fn after_dispatch(
+        &self,
+        _request: &ServerRequest,
+        _response: &mut Result<JSONRPCResponse, A2AError>,
+    ) {
+    }
+}
+
+/// An ordered list of middleware shared by a processor and its clones
+#[derive(Clone, Default)]
+pub struct MiddlewareChain {
+    middleware: Vec<Arc<dyn RequestMiddleware>>,
+}
+
+impl std::fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("middleware", &self.middleware.len())
+            .finish()
+    }
+}
+
+impl MiddlewareChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append middleware to the chain
+    pub fn with(mut self, middleware: impl RequestMiddleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Whether the chain has no middleware
+    pub fn is_empty(&self) -> bool {
+        self.middleware.is_empty()
+    }
+
+    /// Run `request` through the chain, calling `dispatch` unless middleware
+    /// rejects it first
+    pub async fn run<F, Fut>(
+        &self,
+        request: A2ARequest,
+        principal: Option<AuthPrincipal>,
+        dispatch: F,
+    ) -> Result<JSONRPCResponse, A2AError>
+    where
+        F: FnOnce(ServerRequest) -> Fut,
+        Fut: Future<Output = Result<JSONRPCResponse, A2AError>>,
+    {
+        let mut request = ServerRequest::new(request, principal);
+        let mut ran = 0;
+        let mut rejected = None;
+        for middleware in &self.middleware {
+            ran += 1;
+            if let Err(e) = middleware.before_dispatch(&mut request).await {
+                rejected = Some(e);
+                break;
+            }
+        }
+
+        let mut response = match rejected {
+            Some(e) => Err(e),
+            None => dispatch(request.clone()).await,
+        };
+        // Only middleware that saw the request sees its outcome
+        for middleware in self.middleware[..ran].iter().rev() {
+            middleware.after_dispatch(&request, &mut response).await;
+        }
+        response
+    }
+}
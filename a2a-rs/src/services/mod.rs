@@ -5,12 +5,20 @@
 
 #[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "client")]
+pub mod interceptor;
 
+#[cfg(feature = "server")]
+pub mod middleware;
 #[cfg(feature = "server")]
 pub mod server;
 
 #[cfg(feature = "client")]
 pub use client::{AsyncA2AClient, StreamItem};
+#[cfg(feature = "client")]
+pub use interceptor::{ClientRequest, InterceptorChain, RequestInterceptor};
 
+#[cfg(feature = "server")]
+pub use middleware::{MiddlewareChain, RequestMiddleware, ServerRequest};
 #[cfg(feature = "server")]
 pub use server::{AgentInfoProvider, AsyncA2ARequestProcessor};
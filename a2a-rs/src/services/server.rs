@@ -3,8 +3,12 @@
 use async_trait::async_trait;
 
 use crate::{
-    application::{JSONRPCResponse, json_rpc::A2ARequest},
+    application::{
+        JSONRPCResponse,
+        json_rpc::{A2ARequest, ListTasksStreamRequest},
+    },
     domain::{A2AError, AgentCard, AgentSkill},
+    port::{AuthPrincipal, ListTasksStream},
 };
 
 /// A trait for providing agent information
@@ -47,9 +51,42 @@ pub trait AsyncA2ARequestProcessor: Send + Sync {
     /// Process a raw JSON-RPC request string
     async fn process_raw_request<'a>(&self, request: &'a str) -> Result<String, A2AError>;
 
+    /// Process a raw JSON-RPC request sent by an authenticated principal.
+    ///
+    /// Processors override this to record who made a request, such as the
+    /// canceler of a task; the default ignores the principal.
+    async fn process_raw_request_as<'a>(
+        &self,
+        request: &'a str,
+        principal: Option<&'a AuthPrincipal>,
+    ) -> Result<String, A2AError> {
+        let _ = principal;
+        self.process_raw_request(request).await
+    }
+
     /// Process a parsed A2A request
     async fn process_request<'a>(
         &self,
         request: &'a A2ARequest,
     ) -> Result<JSONRPCResponse, A2AError>;
+
+    /// Stream the page of tasks a `tasks/listStream` request lists, one task
+    /// at a time followed by the listing's summary, for transports that can
+    /// answer a request in several frames. The default refuses the request.
+    async fn process_list_tasks_stream<'a>(
+        &self,
+        request: &'a ListTasksStreamRequest,
+        principal: Option<&'a AuthPrincipal>,
+    ) -> Result<ListTasksStream, A2AError> {
+        let _ = (request, principal);
+        Err(A2AError::UnsupportedOperation(
+            "Streamed task listings are not supported".to_string(),
+        ))
+    }
+
+    /// Check that requests can be served, for readiness probes. The default
+    /// is always ready.
+    async fn check_ready(&self) -> Result<(), A2AError> {
+        Ok(())
+    }
 }
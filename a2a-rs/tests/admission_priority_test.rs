@@ -0,0 +1,240 @@
+//! Tests for admitting requests by priority while the server is saturated
+
+#![cfg(all(feature = "http-server", feature = "http-client"))]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, PRIORITY_HEADER, RequestLimits,
+        RequestPriority, SimpleAgentInfo,
+    },
+    domain::{A2AError, Message, Task, TaskState},
+    port::AsyncMessageHandler,
+};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+
+/// Takes a while over every message, keeping the server's only slot busy
+#[derive(Clone)]
+struct SlowHandler;
+
+#[async_trait]
+impl AsyncMessageHandler for SlowHandler {
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        _message: &'a Message,
+        session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let mut task = Task::new(
+            task_id.to_string(),
+            session_id.unwrap_or("default").to_string(),
+        );
+        task.update_status(TaskState::Completed, None);
+        Ok(task)
+    }
+}
+
+async fn start_server(port: u16, limits: RequestLimits) -> String {
+    let address = format!("127.0.0.1:{}", port);
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new("Busy Agent".to_string(), format!("http://{}", address));
+    let processor =
+        DefaultRequestProcessor::new(SlowHandler, storage.clone(), storage, agent_info.clone());
+    let server =
+        HttpServer::new(processor, agent_info, address.clone()).with_request_limits(limits);
+    tokio::spawn(async move { server.start().await });
+    for _ in 0..50 {
+        if TcpStream::connect(&address).await.is_ok() {
+            return format!("http://{}", address);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Nothing listening on {}", address);
+}
+
+/// Occupy the server's only slot for a while
+fn occupy(url: &str) -> tokio::task::JoinHandle<reqwest::Response> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/send",
+        "params": {
+            "id": "bulk-1",
+            "message": {
+                "role": "user",
+                "parts": [{"kind": "text", "text": "Import last year's expenses"}],
+                "messageId": "m-1",
+                "kind": "message"
+            }
+        }
+    });
+    let url = url.to_string();
+    tokio::spawn(async move {
+        reqwest::Client::new()
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+    })
+}
+
+/// Send `method` with `priority`, if any, recording `label` once answered
+fn send(
+    url: &str,
+    method: &str,
+    priority: Option<RequestPriority>,
+    label: &'static str,
+    answered: &Arc<Mutex<Vec<&'static str>>>,
+) -> tokio::task::JoinHandle<reqwest::Response> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": method,
+        "params": {"id": "expense-1"}
+    });
+    let mut builder = reqwest::Client::new().post(url).json(&request);
+    if let Some(priority) = priority {
+        builder = builder.header(PRIORITY_HEADER, priority.as_str());
+    }
+    let answered = answered.clone();
+    tokio::spawn(async move {
+        let response = builder.send().await.unwrap();
+        answered.lock().unwrap().push(label);
+        response
+    })
+}
+
+async fn pause() {
+    tokio::time::sleep(Duration::from_millis(100)).await;
+}
+
+#[tokio::test]
+async fn test_high_priority_request_is_admitted_ahead_of_queued_low_priority_one() {
+    let url = start_server(
+        8362,
+        RequestLimits::new()
+            .with_max_concurrent_requests(1)
+            .with_admission_queue(4, Duration::from_secs(5))
+            .with_priority_aging(Duration::from_secs(60)),
+    )
+    .await;
+    let answered = Arc::new(Mutex::new(Vec::new()));
+
+    let busy = occupy(&url);
+    pause().await;
+    let low = send(
+        &url,
+        "tasks/get",
+        Some(RequestPriority::Low),
+        "low",
+        &answered,
+    );
+    pause().await;
+    let high = send(
+        &url,
+        "tasks/get",
+        Some(RequestPriority::High),
+        "high",
+        &answered,
+    );
+
+    assert_eq!(busy.await.unwrap().status(), reqwest::StatusCode::OK);
+    assert_eq!(high.await.unwrap().status(), reqwest::StatusCode::OK);
+    assert_eq!(low.await.unwrap().status(), reqwest::StatusCode::OK);
+    // Both waited, but the high priority one went first
+    assert_eq!(*answered.lock().unwrap(), vec!["high", "low"]);
+}
+
+#[tokio::test]
+async fn test_full_queue_sheds_low_priority_work_first() {
+    let url = start_server(
+        8363,
+        RequestLimits::new()
+            .with_max_concurrent_requests(1)
+            .with_admission_queue(1, Duration::from_secs(5))
+            .with_retry_after(Duration::from_secs(2))
+            .with_method_priority("tasks/list", RequestPriority::Low),
+    )
+    .await;
+    let answered = Arc::new(Mutex::new(Vec::new()));
+
+    let busy = occupy(&url);
+    pause().await;
+    // Low by its method, since it asks for no priority itself
+    let low = send(&url, "tasks/list", None, "low", &answered);
+    pause().await;
+    let high = send(
+        &url,
+        "tasks/get",
+        Some(RequestPriority::High),
+        "high",
+        &answered,
+    );
+
+    let shed = low.await.unwrap();
+    assert_eq!(shed.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(shed.headers()["retry-after"], "2");
+    let body: Value = shed.json().await.unwrap();
+    assert_eq!(body["error"]["data"]["errorCode"], "server.busy");
+
+    assert_eq!(high.await.unwrap().status(), reqwest::StatusCode::OK);
+    assert_eq!(busy.await.unwrap().status(), reqwest::StatusCode::OK);
+
+    // A request no more urgent than those waiting is the one shed
+    let busy = occupy(&url);
+    pause().await;
+    let first = send(&url, "tasks/get", None, "first", &answered);
+    pause().await;
+    let second = send(&url, "tasks/get", None, "second", &answered);
+    assert_eq!(
+        second.await.unwrap().status(),
+        reqwest::StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(first.await.unwrap().status(), reqwest::StatusCode::OK);
+    busy.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_waiting_low_priority_work_is_not_starved() {
+    let url = start_server(
+        8364,
+        RequestLimits::new()
+            .with_max_concurrent_requests(1)
+            .with_admission_queue(4, Duration::from_secs(5))
+            .with_priority_aging(Duration::from_millis(100)),
+    )
+    .await;
+    let answered = Arc::new(Mutex::new(Vec::new()));
+
+    let busy = occupy(&url);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let low = send(
+        &url,
+        "tasks/get",
+        Some(RequestPriority::Low),
+        "low",
+        &answered,
+    );
+    // By now the low priority request has waited long enough to count as high
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let high = send(
+        &url,
+        "tasks/get",
+        Some(RequestPriority::High),
+        "high",
+        &answered,
+    );
+
+    busy.await.unwrap();
+    low.await.unwrap();
+    high.await.unwrap();
+    assert_eq!(*answered.lock().unwrap(), vec!["low", "high"]);
+}
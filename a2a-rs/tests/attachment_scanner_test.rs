@@ -0,0 +1,173 @@
+//! Tests for scanning file attachments before messages are handled
+
+mod common;
+
+use a2a_rs::{
+    adapter::{
+        InMemoryTaskStorage,
+        business::{
+            AttachmentScanner, ScanVerdict, SizeTypeScanner, attachment_scanner::SCAN_METADATA_KEY,
+        },
+    },
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{A2AError, FileContent, Message, Part, Role, TaskSendParams},
+    port::AsyncTaskManager,
+    services::server::AsyncA2ARequestProcessor,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use common::processor;
+use serde_json::json;
+
+const PDF_BYTES: &[u8] = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n";
+
+fn receipt(bytes: &[u8], name: &str, mime_type: &str) -> Message {
+    Message::builder()
+        .role(Role::User)
+        .parts(vec![
+            Part::text("Here is my receipt".to_string()),
+            Part::file_from_bytes(
+                base64::engine::general_purpose::STANDARD.encode(bytes),
+                Some(name.to_string()),
+                Some(mime_type.to_string()),
+            ),
+        ])
+        .message_id(uuid::Uuid::new_v4().to_string())
+        .build()
+}
+
+fn send(task_id: &str, message: Message) -> A2ARequest {
+    A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+        id: task_id.to_string(),
+        session_id: None,
+        message,
+        push_notification: None,
+        history_length: None,
+        metadata: None,
+        expected_version: None,
+    }))
+}
+
+#[tokio::test]
+async fn test_oversized_file_is_rejected_before_it_is_stored() {
+    let storage = InMemoryTaskStorage::new();
+    let processor =
+        processor(&storage).with_attachment_scanner(SizeTypeScanner::new().with_max_size(1024));
+
+    let request = send(
+        "oversized",
+        receipt(&[b'x'; 2048], "receipt.txt", "text/plain"),
+    );
+    let error = processor.process_request(&request).await.unwrap_err();
+    match &error {
+        A2AError::ValidationError { field, message } => {
+            assert_eq!(field, "parts[1].file");
+            assert!(message.contains("receipt.txt"), "{}", message);
+            assert!(message.contains("2048 bytes"), "{}", message);
+        }
+        other => panic!("Expected a validation error, got {:?}", other),
+    }
+    assert!(!storage.task_exists("oversized").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_clean_file_is_stored_with_scan_metadata() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage).with_attachment_scanner(
+        SizeTypeScanner::new()
+            .with_max_size(1024)
+            .with_blocked_types(vec!["application/x-msdownload".to_string()]),
+    );
+
+    let request = send(
+        "clean",
+        receipt(PDF_BYTES, "receipt.pdf", "application/pdf"),
+    );
+    processor.process_request(&request).await.unwrap();
+
+    let task = storage.get_task("clean", None).await.unwrap();
+    let stored = task
+        .history
+        .unwrap()
+        .into_iter()
+        .find_map(|message| {
+            message.parts.into_iter().find_map(|part| match part {
+                Part::File { file, metadata } => Some((file, metadata)),
+                _ => None,
+            })
+        })
+        .unwrap();
+    assert_eq!(stored.0.name.as_deref(), Some("receipt.pdf"));
+    assert_eq!(
+        stored.1.unwrap()[SCAN_METADATA_KEY],
+        json!({
+            "scanner": "size-type",
+            "inspected": true,
+            "sizeBytes": PDF_BYTES.len(),
+        })
+    );
+
+    // An executable disguised by its name is caught by its declared type
+    let request = send(
+        "blocked",
+        receipt(b"MZ\x90\x00", "receipt.pdf", "application/x-msdownload"),
+    );
+    assert!(processor.process_request(&request).await.is_err());
+    assert!(!storage.task_exists("blocked").await.unwrap());
+}
+
+/// Stands in for an external malware scanner
+struct SignatureScanner;
+
+#[async_trait]
+impl AttachmentScanner for SignatureScanner {
+    async fn scan(&self, file: &FileContent) -> Result<ScanVerdict, A2AError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(file.bytes.as_deref().unwrap_or_default())
+            .unwrap();
+        if bytes.windows(5).any(|window| window == b"EICAR") {
+            return Ok(ScanVerdict::Rejected {
+                reason: "matched signature Eicar-Test-Signature".to_string(),
+            });
+        }
+        if bytes.is_empty() {
+            return Err(A2AError::Unavailable("scanner offline".to_string()));
+        }
+        Ok(ScanVerdict::Clean {
+            details: Some(json!({ "engine": "signatures" })),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_custom_scanner_blocks_infected_files_and_fails_closed() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage).with_attachment_scanner(SignatureScanner);
+
+    let request = send(
+        "infected",
+        receipt(b"X5O!P%@AP EICAR-STANDARD", "receipt.txt", "text/plain"),
+    );
+    let error = processor.process_request(&request).await.unwrap_err();
+    assert!(
+        error.to_string().contains("Eicar-Test-Signature"),
+        "{}",
+        error
+    );
+
+    // A scanner error blocks the message too
+    let request = send("unscanned", receipt(b"", "receipt.txt", "text/plain"));
+    assert!(matches!(
+        processor.process_request(&request).await,
+        Err(A2AError::Unavailable(_))
+    ));
+
+    // Messages without files are not scanned
+    let request = send(
+        "text-only",
+        Message::user_text("No receipt yet".to_string(), "m-1".to_string()),
+    );
+    processor.process_request(&request).await.unwrap();
+    assert!(!storage.task_exists("infected").await.unwrap());
+    assert!(!storage.task_exists("unscanned").await.unwrap());
+}
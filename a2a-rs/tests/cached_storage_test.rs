@@ -0,0 +1,284 @@
+//! Tests for the read-through cache in front of task storage
+
+use std::time::Duration;
+
+use a2a_rs::{
+    adapter::{
+        CachedTaskStorage, DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo,
+        TaskCacheConfig, business::DefaultMessageHandler,
+    },
+    domain::{ListTasksParams, Message, TaskState},
+    port::AsyncTaskManager,
+    services::AsyncA2ARequestProcessor,
+};
+use serde_json::{Value, json};
+
+async fn cached_task(task_id: &str) -> CachedTaskStorage<InMemoryTaskStorage> {
+    let storage = CachedTaskStorage::new(InMemoryTaskStorage::new());
+    storage.create_task(task_id, "ctx-dashboard").await.unwrap();
+    storage
+}
+
+fn listing(context_id: &str) -> ListTasksParams {
+    ListTasksParams {
+        context_id: Some(context_id.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_second_identical_get_task_is_served_from_cache() {
+    let storage = cached_task("expense").await;
+    let first = storage.get_task("expense", None).await.unwrap();
+
+    // A write that bypasses the cache goes unseen until the entry expires
+    storage
+        .inner()
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    let second = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(second.status.state, TaskState::Submitted);
+    assert_eq!(
+        serde_json::to_value(&first).unwrap(),
+        serde_json::to_value(&second).unwrap()
+    );
+
+    let stats = storage.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.hit_ratio(), Some(0.5));
+
+    // Reads with a different history length are cached apart
+    let task = storage.get_task("expense", Some(0)).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+    assert_eq!(storage.stats().misses, 2);
+}
+
+#[tokio::test]
+async fn test_write_through_the_cache_invalidates_the_task() {
+    let storage = cached_task("expense").await;
+    storage.get_task("expense", None).await.unwrap();
+    storage.get_task("expense", Some(0)).await.unwrap();
+
+    let message = Message::agent_text("Approved".to_string(), "m-1".to_string());
+    storage
+        .update_task_status("expense", TaskState::Completed, Some(message))
+        .await
+        .unwrap();
+    assert_eq!(storage.stats().entries, 0);
+
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Completed);
+    assert_eq!(task.history.unwrap().len(), 1);
+    let stats = storage.stats();
+    assert_eq!((stats.hits, stats.misses), (0, 3));
+    assert_eq!(stats.invalidations, 2);
+
+    // Failed writes drop the entry too, since the storage may have changed
+    storage.get_task("expense", None).await.unwrap();
+    assert!(
+        storage
+            .create_task("expense", "ctx-dashboard")
+            .await
+            .is_err()
+    );
+    assert_eq!(storage.stats().entries, 0);
+}
+
+#[tokio::test]
+async fn test_entries_expire_after_their_ttl() {
+    let storage = CachedTaskStorage::new(InMemoryTaskStorage::new())
+        .with_config(TaskCacheConfig::new().with_task_ttl(Duration::from_millis(50)));
+    storage
+        .create_task("expense", "ctx-dashboard")
+        .await
+        .unwrap();
+    storage.get_task("expense", None).await.unwrap();
+    storage
+        .inner()
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+    assert_eq!(storage.stats().hits, 0);
+}
+
+#[tokio::test]
+async fn test_listings_are_cached_by_their_params() {
+    let storage = CachedTaskStorage::new(InMemoryTaskStorage::new())
+        .with_config(TaskCacheConfig::new().with_list_ttl(Duration::from_secs(5)));
+    storage.create_task("expense-1", "ctx-a").await.unwrap();
+
+    let page = storage.list_tasks_v3(&listing("ctx-a")).await.unwrap();
+    assert_eq!(page.tasks.len(), 1);
+    storage
+        .inner()
+        .create_task("expense-2", "ctx-a")
+        .await
+        .unwrap();
+    let page = storage.list_tasks_v3(&listing("ctx-a")).await.unwrap();
+    assert_eq!(page.tasks.len(), 1);
+    assert_eq!(storage.stats().hits, 1);
+
+    // Other params are another entry
+    let page = storage.list_tasks_v3(&listing("ctx-b")).await.unwrap();
+    assert!(page.tasks.is_empty());
+    assert_eq!(storage.stats().misses, 2);
+
+    // Any write through the cache drops every listing
+    storage.create_task("expense-3", "ctx-a").await.unwrap();
+    let page = storage.list_tasks_v3(&listing("ctx-a")).await.unwrap();
+    assert_eq!(page.tasks.len(), 3);
+}
+
+#[tokio::test]
+async fn test_disabled_cache_reads_the_storage() {
+    let storage = cached_task("expense").await;
+    storage.get_task("expense", None).await.unwrap();
+
+    storage.set_enabled(false);
+    assert!(!storage.is_enabled());
+    assert_eq!(storage.stats().entries, 0);
+    storage
+        .inner()
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+    storage.get_task("expense", None).await.unwrap();
+    let stats = storage.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 1, 0));
+
+    // Clones share the toggle
+    storage.clone().set_enabled(true);
+    storage.get_task("expense", None).await.unwrap();
+    storage.get_task("expense", None).await.unwrap();
+    assert_eq!(storage.stats().hits, 1);
+}
+
+#[tokio::test]
+async fn test_oldest_entries_are_evicted_at_capacity() {
+    let storage = CachedTaskStorage::new(InMemoryTaskStorage::new())
+        .with_config(TaskCacheConfig::new().with_capacity(2));
+    for task_id in ["a", "b", "c"] {
+        storage.create_task(task_id, "ctx").await.unwrap();
+    }
+    for task_id in ["a", "b", "c"] {
+        storage.get_task(task_id, None).await.unwrap();
+    }
+    let stats = storage.stats();
+    assert_eq!((stats.entries, stats.evictions), (2, 1));
+
+    storage.get_task("c", None).await.unwrap();
+    storage.get_task("a", None).await.unwrap();
+    assert_eq!(storage.stats().hits, 1);
+}
+
+#[tokio::test]
+async fn test_request_processor_reads_through_the_cache() {
+    let inner = InMemoryTaskStorage::new();
+    let storage = CachedTaskStorage::new(inner.clone());
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        inner,
+        SimpleAgentInfo::new("Dashboard".to_string(), "http://localhost".to_string()),
+    );
+    storage
+        .create_task("expense", "ctx-dashboard")
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        let request =
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": {"id": "expense"}});
+        let response = processor
+            .process_raw_request(&request.to_string())
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["result"]["id"], "expense", "{}", response);
+    }
+    assert_eq!(storage.stats().hits, 2);
+}
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+#[tokio::test]
+async fn test_cached_storage_serves_every_slot_of_a_websocket_server() {
+    use a2a_rs::{
+        adapter::{WebSocketClient, WebSocketServer},
+        port::AsyncStreamingHandler,
+        services::{AsyncA2AClient, StreamItem},
+    };
+    use futures::StreamExt;
+
+    let storage = CachedTaskStorage::new(InMemoryTaskStorage::new());
+    let agent_info =
+        SimpleAgentInfo::new("Dashboard".to_string(), "ws://127.0.0.1:8380".to_string())
+            .with_streaming();
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = WebSocketServer::new(
+        processor,
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8380".to_string(),
+    );
+    let server = tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    storage
+        .create_task("expense", "ctx-dashboard")
+        .await
+        .unwrap();
+    let client = WebSocketClient::new("ws://127.0.0.1:8380".to_string());
+    let mut stream = client.subscribe_to_task("expense", None).await.unwrap();
+    let first = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .unwrap();
+    assert!(matches!(first, Some(Ok(StreamItem::Task(_)))));
+
+    // Subscriptions made through the server reach the wrapped storage
+    let subscribers = storage
+        .inner()
+        .get_subscriber_count("expense")
+        .await
+        .unwrap();
+    assert!(subscribers > 0);
+    assert_eq!(
+        storage.get_subscriber_count("expense").await.unwrap(),
+        subscribers
+    );
+
+    // A write through the wrapper is broadcast and invalidates the task
+    storage.get_task("expense", None).await.unwrap();
+    storage
+        .update_task_status("expense", TaskState::Completed, None)
+        .await
+        .unwrap();
+    let completed = tokio::time::timeout(Duration::from_secs(2), async {
+        while let Some(item) = stream.next().await {
+            if let Ok(StreamItem::StatusUpdate(update)) = item
+                && update.status.state == TaskState::Completed
+            {
+                return true;
+            }
+        }
+        false
+    })
+    .await
+    .unwrap();
+    assert!(completed);
+    let task = client.get_task("expense", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Completed);
+
+    server.abort();
+}
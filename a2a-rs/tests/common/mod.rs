@@ -1,7 +1,47 @@
 //! Common test utilities
-
 pub mod test_handler;
-
 #[allow(unused_imports)]
 pub use test_handler::TestBusinessHandler;
 
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo,
+        business::DefaultMessageHandler,
+    },
+    port::AsyncMessageHandler,
+};
+
+/// A request processor keeping tasks and push notification configs in an
+/// in-memory storage, handling messages with `M`
+#[allow(dead_code)]
+pub type Processor<M = DefaultMessageHandler<InMemoryTaskStorage>> =
+    DefaultRequestProcessor<M, InMemoryTaskStorage, InMemoryTaskStorage>;
+
+/// Agent info for the agent under test
+#[allow(dead_code)]
+pub fn agent_info() -> SimpleAgentInfo {
+    SimpleAgentInfo::new("Test Agent".to_string(), "http://localhost".to_string())
+}
+
+/// A request processor over `storage` handling messages with `handler`
+#[allow(dead_code)]
+pub fn processor_with<M>(
+    handler: M,
+    storage: &InMemoryTaskStorage,
+    agent_info: SimpleAgentInfo,
+) -> Processor<M>
+where
+    M: AsyncMessageHandler + Send + Sync + 'static,
+{
+    DefaultRequestProcessor::new(handler, storage.clone(), storage.clone(), agent_info)
+}
+
+/// A request processor over `storage` with the default message handler
+#[allow(dead_code)]
+pub fn processor(storage: &InMemoryTaskStorage) -> Processor {
+    processor_with(
+        DefaultMessageHandler::new(storage.clone()),
+        storage,
+        agent_info(),
+    )
+}
@@ -10,8 +10,8 @@ use async_trait::async_trait;
 use a2a_rs::{
     adapter::{business::DefaultMessageHandler, storage::InMemoryTaskStorage},
     domain::{
-        A2AError, Message, Task, TaskArtifactUpdateEvent, TaskPushNotificationConfig, TaskState,
-        TaskStatusUpdateEvent,
+        A2AError, GetOrCreateTaskResult, Message, Task, TaskArtifactUpdateEvent,
+        TaskPushNotificationConfig, TaskState, TaskStatusUpdateEvent,
     },
     port::{
         AsyncMessageHandler, AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager,
@@ -37,6 +37,7 @@ impl TestBusinessHandler {
     }
 
     /// Create with a custom storage implementation
+    #[allow(dead_code)]
     pub fn with_storage(storage: InMemoryTaskStorage) -> Self {
         Self {
             storage: Arc::new(storage),
@@ -209,6 +210,17 @@ impl AsyncTaskManager for TestBusinessHandler {
         self.storage.get_task(task_id, history_length).await
     }
 
+    async fn get_or_create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+        initial_message: Option<&'a Message>,
+    ) -> Result<GetOrCreateTaskResult, A2AError> {
+        self.storage
+            .get_or_create_task(task_id, context_id, initial_message)
+            .await
+    }
+
     async fn update_task_status<'a>(
         &self,
         task_id: &'a str,
@@ -224,10 +236,40 @@ impl AsyncTaskManager for TestBusinessHandler {
         self.storage.cancel_task(task_id).await
     }
 
+    async fn cancel_task_with<'a>(
+        &self,
+        task_id: &'a str,
+        cancellation: &'a a2a_rs::domain::TaskCancellation,
+        expected_version: Option<u64>,
+    ) -> Result<Task, A2AError> {
+        self.storage
+            .cancel_task_with(task_id, cancellation, expected_version)
+            .await
+    }
+
     async fn task_exists<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
         self.storage.task_exists(task_id).await
     }
 
+    async fn set_task_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: a2a_rs::domain::TaskResult,
+    ) -> Result<Task, A2AError> {
+        self.storage.set_task_result(task_id, result).await
+    }
+
+    async fn complete_task_with_result<'a>(
+        &self,
+        task_id: &'a str,
+        result: a2a_rs::domain::TaskResult,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.storage
+            .complete_task_with_result(task_id, result, message)
+            .await
+    }
+
     async fn list_tasks_v3<'a>(
         &self,
         params: &'a a2a_rs::domain::ListTasksParams,
@@ -235,6 +277,34 @@ impl AsyncTaskManager for TestBusinessHandler {
         self.storage.list_tasks_v3(params).await
     }
 
+    async fn list_tasks_stream<'a>(
+        &self,
+        params: &'a a2a_rs::domain::ListTasksParams,
+    ) -> Result<a2a_rs::port::ListTasksStream, A2AError> {
+        self.storage.list_tasks_stream(params).await
+    }
+
+    async fn get_task_events<'a>(
+        &self,
+        params: &'a a2a_rs::domain::GetTaskEventsParams,
+    ) -> Result<a2a_rs::domain::GetTaskEventsResult, A2AError> {
+        self.storage.get_task_events(params).await
+    }
+
+    async fn add_task_tags<'a>(
+        &self,
+        params: &'a a2a_rs::domain::TaskTagsParams,
+    ) -> Result<Task, A2AError> {
+        self.storage.add_task_tags(params).await
+    }
+
+    async fn remove_task_tags<'a>(
+        &self,
+        params: &'a a2a_rs::domain::TaskTagsParams,
+    ) -> Result<Task, A2AError> {
+        self.storage.remove_task_tags(params).await
+    }
+
     async fn get_push_notification_config<'a>(
         &self,
         params: &'a a2a_rs::domain::GetTaskPushNotificationConfigParams,
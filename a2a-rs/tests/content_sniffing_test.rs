@@ -0,0 +1,201 @@
+//! Tests for content-type sniffing and validation of file parts
+
+mod common;
+
+use a2a_rs::{
+    adapter::{DefaultRequestProcessor, SimpleAgentInfo},
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{
+        A2AError, ContentPolicy, Message, Part, Role, TaskSendParams,
+        validation::{content::DETECTED_MIME_TYPE_KEY, sniff_mime_type},
+    },
+    port::AsyncTaskManager,
+    services::server::AsyncA2ARequestProcessor,
+};
+use base64::Engine;
+use common::TestBusinessHandler;
+use serde_json::{Map, Value, json};
+
+const JPEG_BYTES: &[u8] = b"\xFF\xD8\xFF\xE0\x00\x10JFIF\x00\x01\x01\x00\x00\x01";
+const PNG_BYTES: &[u8] = b"\x89PNG\r\n\x1A\n\x00\x00\x00\x0DIHDR";
+const PDF_BYTES: &[u8] = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n";
+const GIF_BYTES: &[u8] = b"GIF89a\x01\x00\x01\x00\x80\x00\x00";
+
+fn file_message(bytes: &[u8], name: &str, mime_type: &str) -> Message {
+    Message::builder()
+        .role(Role::User)
+        .parts(vec![Part::file_from_bytes(
+            base64::engine::general_purpose::STANDARD.encode(bytes),
+            Some(name.to_string()),
+            Some(mime_type.to_string()),
+        )])
+        .message_id(uuid::Uuid::new_v4().to_string())
+        .build()
+}
+
+fn file_mime_type(message: &Message) -> (Option<String>, Option<Map<String, Value>>) {
+    match &message.parts[0] {
+        Part::File { file, metadata } => (file.mime_type.clone(), metadata.clone()),
+        other => panic!("Expected file part, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_sniff_known_types() {
+    assert_eq!(sniff_mime_type(JPEG_BYTES), Some("image/jpeg"));
+    assert_eq!(sniff_mime_type(PNG_BYTES), Some("image/png"));
+    assert_eq!(sniff_mime_type(PDF_BYTES), Some("application/pdf"));
+    assert_eq!(sniff_mime_type(GIF_BYTES), Some("image/gif"));
+    assert_eq!(
+        sniff_mime_type(b"RIFF\x24\x00\x00\x00WEBPVP8 "),
+        Some("image/webp")
+    );
+    assert_eq!(
+        sniff_mime_type(b"amount,purpose\n20,lunch\n"),
+        Some("text/plain")
+    );
+    assert_eq!(sniff_mime_type(b"\x00\x01\x02\x03"), None);
+    assert_eq!(sniff_mime_type(b""), None);
+}
+
+#[test]
+fn test_jpeg_mislabeled_as_pdf_is_detected() {
+    let mut message = file_message(JPEG_BYTES, "receipt.pdf", "application/pdf");
+
+    ContentPolicy::new().apply(&mut message).unwrap();
+
+    let (mime_type, metadata) = file_mime_type(&message);
+    assert_eq!(mime_type.as_deref(), Some("image/jpeg"));
+    let metadata = metadata.unwrap();
+    assert_eq!(metadata[DETECTED_MIME_TYPE_KEY], json!("image/jpeg"));
+    assert_eq!(metadata["declaredMimeType"], json!("application/pdf"));
+}
+
+#[test]
+fn test_mismatch_is_rejected_when_configured() {
+    let policy = ContentPolicy::new().reject_mismatches(true);
+
+    let mut message = file_message(JPEG_BYTES, "receipt.pdf", "application/pdf");
+    let err = policy.apply(&mut message).unwrap_err();
+    assert!(matches!(err, A2AError::ContentTypeNotSupported(_)));
+    assert!(err.to_string().contains("image/jpeg"));
+
+    // Matching labels, aliases and generic binary labels are accepted
+    let mut message = file_message(JPEG_BYTES, "receipt.jpg", "image/jpg");
+    policy.apply(&mut message).unwrap();
+    assert_eq!(file_mime_type(&message).0.as_deref(), Some("image/jpeg"));
+
+    let mut message = file_message(PDF_BYTES, "receipt", "application/octet-stream");
+    policy.apply(&mut message).unwrap();
+    assert_eq!(
+        file_mime_type(&message).0.as_deref(),
+        Some("application/pdf")
+    );
+
+    // Specific text types are kept over the plain-text fallback
+    let mut message = file_message(b"a,b\n1,2\n", "expenses.csv", "text/csv");
+    policy.apply(&mut message).unwrap();
+    assert_eq!(file_mime_type(&message).0.as_deref(), Some("text/csv"));
+}
+
+#[test]
+fn test_disallowed_type_is_rejected() {
+    let policy = ContentPolicy::new()
+        .with_allowed_types(vec!["image/png".to_string(), "application/pdf".to_string()]);
+
+    let mut message = file_message(GIF_BYTES, "receipt.gif", "image/gif");
+    let err = policy.apply(&mut message).unwrap_err();
+    assert!(matches!(err, A2AError::ContentTypeNotSupported(_)));
+
+    // The allowlist applies to the detected type, not the declared one
+    let mut message = file_message(GIF_BYTES, "receipt.png", "image/png");
+    assert!(policy.apply(&mut message).is_err());
+
+    let mut message = file_message(PNG_BYTES, "receipt.png", "image/png");
+    policy.apply(&mut message).unwrap();
+
+    // Text parts are unaffected
+    let mut message = Message::user_text("no files".to_string(), "msg-text".to_string());
+    policy.apply(&mut message).unwrap();
+}
+
+#[test]
+fn test_skill_allowlist_overrides_agent_allowlist() {
+    let policy = ContentPolicy::new()
+        .with_allowed_types(vec!["application/pdf".to_string()])
+        .with_skill_allowed_types("scan_receipt".to_string(), vec!["image/*".to_string()]);
+
+    let mut message = file_message(JPEG_BYTES, "receipt.jpg", "image/jpeg");
+    assert!(policy.apply(&mut message).is_err());
+
+    let mut metadata = Map::new();
+    metadata.insert("skillId".to_string(), json!("scan_receipt"));
+    let mut message = file_message(JPEG_BYTES, "receipt.jpg", "image/jpeg");
+    message.metadata = Some(metadata.clone());
+    policy.apply(&mut message).unwrap();
+
+    let mut message = file_message(PDF_BYTES, "receipt.pdf", "application/pdf");
+    message.metadata = Some(metadata);
+    let err = policy.apply(&mut message).unwrap_err();
+    assert!(err.to_string().contains("scan_receipt"));
+}
+
+#[test]
+fn test_invalid_base64_is_rejected() {
+    let mut message = file_message(b"", "broken.pdf", "application/pdf");
+    if let Part::File { file, .. } = &mut message.parts[0] {
+        file.bytes = Some("not base64!".to_string());
+    }
+
+    let err = ContentPolicy::new().apply(&mut message).unwrap_err();
+    assert!(matches!(err, A2AError::ValidationError { .. }));
+}
+
+#[tokio::test]
+async fn test_request_processor_applies_content_policy() {
+    let handler = TestBusinessHandler::new();
+    let processor = DefaultRequestProcessor::with_handler(
+        handler.clone(),
+        SimpleAgentInfo::new("Agent".to_string(), "http://localhost".to_string()),
+    )
+    .with_content_policy(
+        ContentPolicy::new()
+            .with_allowed_types(vec!["image/*".to_string(), "application/pdf".to_string()]),
+    );
+
+    let send = |task_id: &str, message: Message| {
+        A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+            id: task_id.to_string(),
+            session_id: None,
+            message,
+            push_notification: None,
+            history_length: None,
+            metadata: None,
+            expected_version: None,
+        }))
+    };
+
+    // A JPEG labeled as PDF is stored with its real type
+    let request = send(
+        "sniff-task",
+        file_message(JPEG_BYTES, "receipt.pdf", "application/pdf"),
+    );
+    processor.process_request(&request).await.unwrap();
+
+    let task = handler.get_task("sniff-task", None).await.unwrap();
+    let stored = task
+        .history
+        .unwrap()
+        .into_iter()
+        .find(|m| m.parts.iter().any(|p| matches!(p, Part::File { .. })))
+        .unwrap();
+    assert_eq!(file_mime_type(&stored).0.as_deref(), Some("image/jpeg"));
+
+    // Disallowed types never reach the handler
+    let request = send(
+        "rejected-task",
+        file_message(b"#!/bin/sh\necho hi\n", "receipt.pdf", "application/pdf"),
+    );
+    assert!(processor.process_request(&request).await.is_err());
+    assert!(!handler.task_exists("rejected-task").await.unwrap());
+}
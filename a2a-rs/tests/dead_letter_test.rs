@@ -0,0 +1,296 @@
+//! Tests for inspecting, requeuing and purging dead-lettered push notifications
+
+#![cfg(all(feature = "http-client", feature = "http-server"))]
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use a2a_rs::{
+    adapter::{
+        DeadLetterQueue, DefaultRequestProcessor, HttpPushNotificationSender, InMemoryTaskStorage,
+        SimpleAgentInfo, business::DefaultMessageHandler,
+    },
+    domain::{
+        DeadLetter, PurgeDeadLettersParams, PushNotificationConfig, RequeueDeadLetterResult,
+        TaskPushNotificationConfig, TaskState,
+        error::{METHOD_NOT_AUTHORIZED, UNSUPPORTED_OPERATION},
+    },
+    port::{AsyncNotificationManager, AsyncTaskManager, AuthPrincipal},
+    services::AsyncA2ARequestProcessor,
+};
+use axum::{Router, extract::State, http::StatusCode, routing::post};
+use serde_json::{Value, json};
+
+/// A webhook that fails with 503 until it is made healthy
+#[derive(Clone, Default)]
+struct Webhook {
+    healthy: Arc<AtomicBool>,
+    hits: Arc<AtomicUsize>,
+    delivered: Arc<AtomicUsize>,
+}
+
+impl Webhook {
+    async fn start(address: &str) -> Self {
+        let webhook = Webhook::default();
+        let app = Router::new()
+            .route(
+                "/webhook",
+                post(|State(webhook): State<Webhook>| async move {
+                    webhook.hits.fetch_add(1, Ordering::SeqCst);
+                    if webhook.healthy.load(Ordering::SeqCst) {
+                        webhook.delivered.fetch_add(1, Ordering::SeqCst);
+                        StatusCode::OK
+                    } else {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    }
+                }),
+            )
+            .with_state(webhook.clone());
+        let listener = tokio::net::TcpListener::bind(address).await.unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        webhook
+    }
+}
+
+/// Storage whose notifications to `address` are retried once, then
+/// dead-lettered in `queue`
+async fn storage_notifying(address: &str, queue: &DeadLetterQueue) -> InMemoryTaskStorage {
+    let sender = HttpPushNotificationSender::new()
+        .with_max_retries(1)
+        .with_backoff_ms(10)
+        .with_dead_letters(queue.clone());
+    let storage = InMemoryTaskStorage::with_push_sender(sender);
+    storage.create_task("expense", "ctx").await.unwrap();
+    storage
+        .set_task_notification(&TaskPushNotificationConfig {
+            task_id: "expense".to_string(),
+            push_notification_config: PushNotificationConfig {
+                id: None,
+                url: format!("http://{}/webhook", address),
+                token: Some("secret-token".to_string()),
+                authentication: None,
+            },
+        })
+        .await
+        .unwrap();
+    storage
+}
+
+fn admin() -> AuthPrincipal {
+    AuthPrincipal::new("ops".to_string(), "bearer".to_string()).with_role("admin".to_string())
+}
+
+/// Call `method` on a processor for `storage` as `principal`
+async fn call(
+    storage: &InMemoryTaskStorage,
+    method: &str,
+    params: Value,
+    principal: Option<&AuthPrincipal>,
+) -> Value {
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        SimpleAgentInfo::new("Expense Agent".to_string(), "http://localhost".to_string()),
+    );
+    let request = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+    let response = processor
+        .process_raw_request_as(&request.to_string(), principal)
+        .await
+        .unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+async fn list(storage: &InMemoryTaskStorage, params: Value) -> Vec<DeadLetter> {
+    let response = call(
+        storage,
+        "admin/pushNotifications/deadLetters/list",
+        params,
+        Some(&admin()),
+    )
+    .await;
+    serde_json::from_value(response["result"].clone()).unwrap_or_else(|_| panic!("{}", response))
+}
+
+async fn requeue(storage: &InMemoryTaskStorage, id: &str) -> Value {
+    call(
+        storage,
+        "admin/pushNotifications/deadLetters/requeue",
+        json!({"id": id}),
+        Some(&admin()),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_dead_letter_is_listed_and_requeued_to_a_healthy_webhook() {
+    let address = "127.0.0.1:8378";
+    let webhook = Webhook::start(address).await;
+    let queue = DeadLetterQueue::new();
+    let storage = storage_notifying(address, &queue).await;
+
+    storage
+        .update_task_status("expense", TaskState::Completed, None)
+        .await
+        .unwrap();
+    assert_eq!(webhook.hits.load(Ordering::SeqCst), 2);
+    assert_eq!(queue.len(), 1);
+
+    let letters = list(&storage, json!({})).await;
+    assert_eq!(letters.len(), 1);
+    let letter = &letters[0];
+    assert_eq!(letter.task_id, "expense");
+    assert_eq!(letter.url, format!("http://{}/webhook", address));
+    assert_eq!(letter.event.event_kind(), "finished");
+    assert_eq!(letter.attempts.len(), 2);
+    assert!(
+        letter
+            .attempts
+            .iter()
+            .all(|attempt| attempt.status_code == Some(503) && attempt.error.is_some())
+    );
+    assert!(letter.last_error.contains("503"), "{}", letter.last_error);
+    assert!(list(&storage, json!({"taskId": "other"})).await.is_empty());
+
+    // The webhook's credentials are not listed
+    let response = call(
+        &storage,
+        "admin/pushNotifications/deadLetters/list",
+        json!({}),
+        Some(&admin()),
+    )
+    .await;
+    assert!(
+        !response.to_string().contains("secret-token"),
+        "{}",
+        response
+    );
+
+    webhook.healthy.store(true, Ordering::SeqCst);
+    let response = requeue(&storage, &letter.id).await;
+    let result: RequeueDeadLetterResult = serde_json::from_value(response["result"].clone())
+        .unwrap_or_else(|_| panic!("{}", response));
+    assert!(result.delivered);
+    assert_eq!(result.dead_letter.id, letter.id);
+    assert_eq!(result.dead_letter.attempts.len(), 3);
+    let last = result.dead_letter.attempts.last().unwrap();
+    assert_eq!(last.status_code, Some(200));
+    assert!(last.error.is_none());
+    assert_eq!(webhook.delivered.load(Ordering::SeqCst), 1);
+    assert!(queue.is_empty());
+
+    // A delivered dead letter is gone
+    let response = requeue(&storage, &letter.id).await;
+    assert_eq!(
+        response["error"]["data"]["errorCode"],
+        "push.dead_letter_not_found"
+    );
+}
+
+#[tokio::test]
+async fn test_failed_requeue_keeps_the_dead_letter_and_purge_removes_it() {
+    let address = "127.0.0.1:8379";
+    let webhook = Webhook::start(address).await;
+    let queue = DeadLetterQueue::new();
+    let storage = storage_notifying(address, &queue).await;
+    storage
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    storage
+        .update_task_status("expense", TaskState::Completed, None)
+        .await
+        .unwrap();
+    let letters = queue.list(&Default::default());
+    assert_eq!(letters.len(), 2);
+
+    // Requeuing retries with the same backoff and records every attempt
+    let response = requeue(&storage, &letters[0].id).await;
+    assert_eq!(response["result"]["delivered"], false);
+    assert_eq!(
+        response["result"]["deadLetter"]["attempts"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+    assert_eq!(webhook.hits.load(Ordering::SeqCst), 6);
+    let letters = list(&storage, json!({"taskId": "expense"})).await;
+    assert_eq!(letters.len(), 2);
+    assert_eq!(letters[1].attempts.len(), 4);
+
+    let response = call(
+        &storage,
+        "admin/pushNotifications/deadLetters/purge",
+        json!({"ids": [letters[0].id]}),
+        Some(&admin()),
+    )
+    .await;
+    assert_eq!(response["result"]["purged"], 1);
+    assert_eq!(
+        queue.purge(&PurgeDeadLettersParams {
+            task_id: Some("expense".to_string()),
+            ..Default::default()
+        }),
+        1
+    );
+    assert!(queue.is_empty());
+}
+
+#[tokio::test]
+async fn test_dead_letters_are_for_admins_only() {
+    let queue = DeadLetterQueue::new();
+    let storage = storage_notifying("127.0.0.1:9", &queue).await;
+    let user = AuthPrincipal::new("alice".to_string(), "bearer".to_string());
+    for method in ["list", "requeue", "purge"] {
+        let method = format!("admin/pushNotifications/deadLetters/{}", method);
+        for principal in [None, Some(&user)] {
+            let response = call(&storage, &method, json!({"id": "any"}), principal).await;
+            assert_eq!(
+                response["error"]["code"], METHOD_NOT_AUTHORIZED,
+                "{}",
+                response
+            );
+        }
+    }
+
+    // Senders without a queue keep no dead letters to manage
+    let storage = InMemoryTaskStorage::new();
+    let response = call(
+        &storage,
+        "admin/pushNotifications/deadLetters/list",
+        json!({}),
+        Some(&admin()),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], UNSUPPORTED_OPERATION);
+}
+
+#[tokio::test]
+async fn test_full_queue_drops_the_oldest_dead_letter() {
+    let queue = DeadLetterQueue::new().with_capacity(2);
+    let storage = storage_notifying("127.0.0.1:9", &queue).await;
+    for state in [
+        TaskState::Working,
+        TaskState::InputRequired,
+        TaskState::Completed,
+    ] {
+        storage
+            .update_task_status("expense", state, None)
+            .await
+            .unwrap();
+    }
+    let letters = queue.list(&Default::default());
+    let kinds: Vec<&str> = letters
+        .iter()
+        .map(|letter| letter.event.event_kind())
+        .collect();
+    assert_eq!(kinds, vec!["input-required", "finished"]);
+
+    // Attempts that got no answer record only the error
+    let attempt = &letters[0].attempts[0];
+    assert!(attempt.status_code.is_none());
+    assert!(attempt.error.is_some());
+}
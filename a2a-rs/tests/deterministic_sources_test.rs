@@ -0,0 +1,185 @@
+//! Golden tests of task creation with a fixed clock and sequential ids
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo,
+        business::DefaultMessageHandler,
+    },
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{
+        Clock, FixedClock, GetTaskEventsParams, IdGenerator, ListTasksParams, Message,
+        SequentialIdGenerator, Task, TaskSendParams,
+    },
+    port::AsyncTaskManager,
+    services::AsyncA2ARequestProcessor,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+
+fn start() -> DateTime<Utc> {
+    "2025-01-01T09:00:00Z".parse().unwrap()
+}
+
+fn send(task_id: &str) -> A2ARequest {
+    A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+        id: task_id.to_string(),
+        session_id: Some("ctx-1".to_string()),
+        message: Message::user_text("Reimburse $20 for lunch".to_string(), "user-1".into()),
+        push_notification: None,
+        history_length: None,
+        metadata: None,
+        expected_version: None,
+    }))
+}
+
+#[test]
+fn test_sources_are_deterministic() {
+    let clock = FixedClock::new(start());
+    let copy = clock.clone();
+    copy.advance(Duration::seconds(90));
+    assert_eq!(
+        clock.now(),
+        "2025-01-01T09:01:30Z".parse::<DateTime<Utc>>().unwrap()
+    );
+
+    let ids = SequentialIdGenerator::new("msg");
+    let copy = ids.clone();
+    assert_eq!(ids.next_id(), "msg-1");
+    assert_eq!(copy.next_id(), "msg-2");
+}
+
+#[tokio::test]
+async fn test_task_creation_is_reproducible() {
+    let clock = FixedClock::new(start());
+    let storage = InMemoryTaskStorage::new()
+        .with_clock(clock.clone())
+        .with_id_generator(SequentialIdGenerator::new("sub"));
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone())
+            .with_id_generator(SequentialIdGenerator::new("agent")),
+        storage.clone(),
+        storage.clone(),
+        SimpleAgentInfo::new("Golden Agent".to_string(), "http://localhost".to_string()),
+    );
+
+    let response = processor.process_request(&send("first")).await.unwrap();
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(
+        serde_json::to_value(&task).unwrap(),
+        json!({
+            "id": "first",
+            "contextId": "ctx-1",
+            "status": {
+                "state": "working",
+                "message": {
+                    "role": "agent",
+                    "parts": [{"kind": "text", "text": "Echo: Reimburse $20 for lunch"}],
+                    "messageId": "agent-1",
+                    "taskId": "first",
+                    "contextId": "",
+                    "kind": "message"
+                },
+                "timestamp": "2025-01-01T09:00:00Z"
+            },
+            "history": [
+                {
+                    "role": "user",
+                    "parts": [{"kind": "text", "text": "Reimburse $20 for lunch"}],
+                    "messageId": "user-1",
+                    "kind": "message"
+                },
+                {
+                    "role": "agent",
+                    "parts": [{"kind": "text", "text": "Echo: Reimburse $20 for lunch"}],
+                    "messageId": "agent-1",
+                    "taskId": "first",
+                    "contextId": "",
+                    "kind": "message"
+                }
+            ],
+            "kind": "task",
+            "version": 3
+        })
+    );
+
+    // Every logged event carries the clock's time
+    let log = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "first".to_string(),
+            page_size: Some(100),
+            page_token: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(log.events.len(), 5);
+    assert!(log.events.iter().all(|record| record.timestamp == start()));
+
+    // A later task sorts first, and the cancellation uses the next id
+    clock.advance(Duration::minutes(5));
+    processor.process_request(&send("second")).await.unwrap();
+    let canceled = storage.cancel_task("second").await.unwrap();
+    assert_eq!(canceled.status.message.unwrap().message_id, "sub-1");
+    assert_eq!(
+        canceled.status.timestamp,
+        Some(start() + Duration::minutes(5))
+    );
+
+    let listed = storage
+        .list_tasks_v3(&ListTasksParams::default())
+        .await
+        .unwrap();
+    let ids: Vec<&str> = listed.tasks.iter().map(|task| task.id.as_str()).collect();
+    assert_eq!(ids, vec!["second", "first"]);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_storage_uses_the_injected_sources() {
+    use a2a_rs::{
+        adapter::storage::SqlxTaskStorage,
+        domain::{PushNotificationConfig, TaskPushNotificationConfig, TaskState},
+        port::AsyncNotificationManager,
+    };
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:")
+        .await
+        .unwrap()
+        .with_clock(FixedClock::new(start()))
+        .with_id_generator(SequentialIdGenerator::new("sqlx"));
+
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    storage
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    let canceled = storage.cancel_task("expense").await.unwrap();
+    assert_eq!(canceled.status.message.unwrap().message_id, "sqlx-1");
+
+    let config = storage
+        .set_task_notification(&TaskPushNotificationConfig {
+            task_id: "expense".to_string(),
+            push_notification_config: PushNotificationConfig {
+                id: None,
+                url: "https://example.com/hook".to_string(),
+                token: None,
+                authentication: None,
+            },
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        config.push_notification_config.id.as_deref(),
+        Some("sqlx-2")
+    );
+
+    let log = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "expense".to_string(),
+            page_size: Some(100),
+            page_token: None,
+        })
+        .await
+        .unwrap();
+    assert!(!log.events.is_empty());
+    assert!(log.events.iter().all(|record| record.timestamp == start()));
+}
@@ -0,0 +1,132 @@
+//! Tests for stable error codes and localized error messages
+
+#![cfg(all(feature = "http-client", feature = "http-server"))]
+
+mod common;
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, HttpClient, HttpServer, InMemoryTaskStorage, SimpleAgentInfo,
+    },
+    domain::{
+        A2AError, ErrorCatalog, ErrorDetail, JSONRPCError,
+        error_catalog::{DEFAULT_LOCALE, codes},
+    },
+    services::AsyncA2AClient,
+};
+use common::TestBusinessHandler;
+use serde_json::json;
+use std::time::Duration;
+
+fn french_catalog() -> ErrorCatalog {
+    ErrorCatalog::new().with_locale(
+        "fr",
+        [
+            (
+                codes::VALIDATION_INVALID_NUMBER,
+                "{field} doit être un nombre",
+            ),
+            (codes::TASK_NOT_FOUND, "La tâche '{taskId}' est introuvable"),
+            ("field.amount", "le montant"),
+        ],
+    )
+}
+
+#[test]
+fn test_validation_failure_maps_to_stable_code() {
+    let error = A2AError::UserError(ErrorDetail::field(
+        codes::VALIDATION_INVALID_NUMBER,
+        "amount",
+    ));
+
+    // The wire format carries the code and params next to the JSON-RPC error
+    let value = error.to_jsonrpc_error();
+    assert_eq!(value["code"], -32602);
+    assert_eq!(
+        value["data"],
+        json!({"errorCode": "validation.invalid_number", "params": {"field": "amount"}})
+    );
+
+    // A client decoding the response recovers the same detail
+    let wire: JSONRPCError = serde_json::from_value(value).unwrap();
+    let received = A2AError::JsonRpc {
+        code: wire.code,
+        message: wire.message,
+        data: wire.data,
+    };
+    assert_eq!(received.error_detail(), error.error_detail());
+
+    // Free-form validation errors keep their field and reason
+    let detail = A2AError::ValidationError {
+        field: "date".to_string(),
+        message: "must be in the past".to_string(),
+    }
+    .error_detail();
+    assert_eq!(detail.error_code, codes::VALIDATION_INVALID);
+    assert_eq!(detail.params["field"], "date");
+    assert_eq!(detail.message(), "Date is invalid: must be in the past");
+
+    // Internal failures never leak their details
+    let detail =
+        A2AError::DatabaseError("connection refused on 10.0.0.3".to_string()).error_detail();
+    assert_eq!(detail, ErrorDetail::new(codes::INTERNAL));
+}
+
+#[test]
+fn test_catalog_renders_localized_messages() {
+    let detail = ErrorDetail::field(codes::VALIDATION_INVALID_NUMBER, "amount");
+    let catalog = french_catalog();
+
+    assert_eq!(
+        catalog.render(&detail, DEFAULT_LOCALE),
+        "Amount must be a number"
+    );
+    assert_eq!(detail.to_string(), "Amount must be a number");
+    assert_eq!(
+        catalog.render(&detail, "fr"),
+        "Le montant doit être un nombre"
+    );
+
+    // Regional locales fall back to their language, then to English
+    assert_eq!(
+        catalog.render(&detail, "fr-CA"),
+        "Le montant doit être un nombre"
+    );
+    let required = ErrorDetail::field(codes::VALIDATION_REQUIRED, "date");
+    assert_eq!(catalog.render(&required, "fr_FR"), "Date is required");
+
+    // Clients can override individual templates
+    let catalog = catalog.with_template("en", codes::VALIDATION_REQUIRED, "Please enter a {field}");
+    assert_eq!(catalog.render(&required, "en-GB"), "Please enter a date");
+
+    // Codes unknown to the client render as the code itself
+    let unknown = ErrorDetail::new("billing.over_budget").with_param("limit", "500");
+    assert_eq!(catalog.render(&unknown, "fr"), "billing.over_budget");
+}
+
+#[tokio::test]
+async fn test_server_errors_carry_detail_to_client() {
+    let storage = InMemoryTaskStorage::new();
+    let handler = TestBusinessHandler::with_storage(storage);
+    let agent_info = SimpleAgentInfo::new(
+        "Catalog Agent".to_string(),
+        "http://127.0.0.1:8313".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler, agent_info.clone());
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8313".to_string());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = HttpClient::new("http://127.0.0.1:8313".to_string());
+    let err = client.get_task("missing-task", None).await.unwrap_err();
+
+    let detail = err.error_detail();
+    assert_eq!(
+        detail,
+        ErrorDetail::new(codes::TASK_NOT_FOUND).with_param("taskId", "missing-task")
+    );
+    assert_eq!(
+        french_catalog().render(&detail, "fr"),
+        "La tâche 'missing-task' est introuvable"
+    );
+}
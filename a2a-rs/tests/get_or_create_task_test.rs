@@ -0,0 +1,213 @@
+//! Tests for opening a task, creating it if it does not exist
+
+mod common;
+
+use std::sync::Arc;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    application::json_rpc::{A2ARequest, GetOrCreateTaskRequest},
+    domain::{
+        A2AError, GetOrCreateTaskParams, GetOrCreateTaskResult, GetTaskEventsParams,
+        ListTasksParams, Message, TaskState,
+    },
+    port::AsyncTaskManager,
+    services::AsyncA2ARequestProcessor,
+};
+use common::{Processor, processor};
+use futures::future::join_all;
+
+async fn open(
+    processor: &Processor,
+    task_id: &str,
+    message: Option<Message>,
+) -> Result<GetOrCreateTaskResult, A2AError> {
+    let request = A2ARequest::GetOrCreateTask(GetOrCreateTaskRequest::new(GetOrCreateTaskParams {
+        id: task_id.to_string(),
+        context_id: Some("expenses".to_string()),
+        message,
+        metadata: None,
+        exclude: Vec::new(),
+    }));
+    let response = processor.process_request(&request).await?;
+    Ok(serde_json::from_value(response.result.unwrap()).unwrap())
+}
+
+fn message_ids(history: Option<&Vec<Message>>) -> Vec<String> {
+    history
+        .into_iter()
+        .flatten()
+        .map(|message| message.message_id.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_concurrent_calls_with_the_same_id_create_one_task() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = Arc::new(processor(&storage));
+
+    let calls = (0..8).map(|i| {
+        let processor = processor.clone();
+        tokio::spawn(async move {
+            let message =
+                Message::user_text(format!("Hello from caller {}", i), format!("m-{}", i));
+            open(&processor, "chat-1", Some(message)).await.unwrap()
+        })
+    });
+    let results: Vec<_> = join_all(calls)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(results.iter().filter(|result| result.created).count(), 1);
+    let creator = results.iter().find(|result| result.created).unwrap();
+    let history = message_ids(creator.task.history.as_ref());
+    assert_eq!(history.len(), 1);
+    for result in &results {
+        assert_eq!(result.task.id, "chat-1");
+        assert_eq!(result.task.context_id, "expenses");
+    }
+
+    // Only the creator's message was recorded
+    let stored = storage.get_task("chat-1", None).await.unwrap();
+    assert_eq!(message_ids(stored.history.as_ref()), history);
+    assert_eq!(stored.status.state, TaskState::Submitted);
+    let listed = storage
+        .list_tasks_v3(&ListTasksParams::default())
+        .await
+        .unwrap();
+    assert_eq!(listed.total_size, 1);
+}
+
+#[tokio::test]
+async fn test_existing_task_is_returned_as_it_is() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+    storage.create_task("chat-1", "support").await.unwrap();
+    storage
+        .update_task_status("chat-1", TaskState::Working, None)
+        .await
+        .unwrap();
+
+    let message = Message::user_text("Hello".to_string(), "m-1".to_string());
+    let result = open(&processor, "chat-1", Some(message)).await.unwrap();
+    assert!(!result.created);
+    assert_eq!(result.task.status.state, TaskState::Working);
+    assert_eq!(result.task.context_id, "support");
+    assert!(result.task.history.unwrap_or_default().is_empty());
+
+    // Without a message the task starts with an empty history
+    let result = open(&processor, "chat-2", None).await.unwrap();
+    assert!(result.created);
+    assert_eq!(result.task.status.state, TaskState::Submitted);
+    assert!(storage.task_exists("chat-2").await.unwrap());
+}
+
+/// Check that `storage` creates a task and its message in one step
+async fn assert_creates_the_task_with_its_message(storage: &impl AsyncTaskManager) {
+    let message = Message::user_text("Hello".to_string(), "m-1".to_string());
+    let result = storage
+        .get_or_create_task("chat-1", "expenses", Some(&message))
+        .await
+        .unwrap();
+    assert!(result.created);
+    assert_eq!(result.task.version, 1);
+    assert_eq!(message_ids(result.task.history.as_ref()), vec!["m-1"]);
+    assert_eq!(
+        result.task.status.message.map(|message| message.message_id),
+        Some("m-1".to_string())
+    );
+
+    let stored = storage.get_task("chat-1", None).await.unwrap();
+    assert_eq!(stored.version, 1);
+    assert_eq!(message_ids(stored.history.as_ref()), vec!["m-1"]);
+    let events = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "chat-1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let kinds: Vec<&str> = events
+        .events
+        .iter()
+        .map(|record| record.event.event_type())
+        .collect();
+    assert_eq!(kinds, ["messageAppended", "statusUpdate"]);
+}
+
+#[tokio::test]
+async fn test_task_starts_with_its_message_at_the_first_version() {
+    assert_creates_the_task_with_its_message(&InMemoryTaskStorage::new()).await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlite_task_starts_with_its_message_at_the_first_version() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    assert_creates_the_task_with_its_message(&storage).await;
+}
+
+#[tokio::test]
+async fn test_raw_request_is_routed_and_blank_ids_are_refused() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+
+    let raw = r#"{"jsonrpc":"2.0","id":1,"method":"tasks/getOrCreate","params":{"id":"chat-1"}}"#;
+    let response: serde_json::Value =
+        serde_json::from_str(&processor.process_raw_request(raw).await.unwrap()).unwrap();
+    assert_eq!(response["result"]["created"], true);
+    assert_eq!(response["result"]["task"]["contextId"], "default");
+
+    let response: serde_json::Value =
+        serde_json::from_str(&processor.process_raw_request(raw).await.unwrap()).unwrap();
+    assert_eq!(response["result"]["created"], false);
+
+    assert!(matches!(
+        open(&processor, " ", None).await,
+        Err(A2AError::ValidationError { .. })
+    ));
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_concurrent_calls_create_one_task_in_sqlite() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let path = std::env::temp_dir().join(format!("a2a-get-or-create-{}.db", uuid::Uuid::new_v4()));
+    let url = format!("sqlite://{}?mode=rwc", path.display());
+    let storage = Arc::new(SqlxTaskStorage::new(&url).await.unwrap());
+
+    let calls = (0..8).map(|i| {
+        let storage = storage.clone();
+        tokio::spawn(async move {
+            let message =
+                Message::user_text(format!("Hello from caller {}", i), format!("m-{}", i));
+            storage
+                .get_or_create_task("chat-1", "expenses", Some(&message))
+                .await
+                .unwrap()
+        })
+    });
+    let results: Vec<_> = join_all(calls)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(results.iter().filter(|result| result.created).count(), 1);
+    let stored = storage.get_task("chat-1", None).await.unwrap();
+    let history = message_ids(stored.history.as_ref());
+    assert_eq!(history.len(), 1);
+    let creator = results.iter().find(|result| result.created).unwrap();
+    assert_eq!(message_ids(creator.task.history.as_ref()), history);
+
+    // create_task still refuses an existing ID
+    assert!(storage.create_task("chat-1", "expenses").await.is_err());
+
+    drop(storage);
+    std::fs::remove_file(&path).ok();
+}
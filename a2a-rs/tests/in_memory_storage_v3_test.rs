@@ -4,8 +4,8 @@ use a2a_rs::{
     adapter::InMemoryTaskStorage,
     domain::{
         DeleteTaskPushNotificationConfigParams, GetTaskPushNotificationConfigParams,
-        ListTaskPushNotificationConfigParams, ListTasksParams, PushNotificationConfig,
-        TaskPushNotificationConfig, TaskState,
+        ListTaskPushNotificationConfigParams, ListTasksParams, PageSizeLimits,
+        PushNotificationConfig, TaskPushNotificationConfig, TaskState,
     },
     port::{AsyncNotificationManager, AsyncTaskManager},
 };
@@ -422,7 +422,50 @@ async fn test_list_tasks_v3_page_size_clamping() {
 
     assert_eq!(result.page_size, 100, "Page size should be clamped to 100");
 
-    // Test page_size too small (should be clamped to 1)
+    // A zero or negative page_size means "use the default", not an empty page
+    for page_size in [0, -3] {
+        let params = ListTasksParams {
+            page_size: Some(page_size),
+            ..Default::default()
+        };
+        let result = storage
+            .list_tasks_v3(&params)
+            .await
+            .expect("Failed to list tasks");
+
+        assert_eq!(result.page_size, 50, "Page size should fall back to 50");
+        assert_eq!(result.tasks.len(), 5, "Should return all 5 tasks");
+    }
+}
+
+#[tokio::test]
+async fn test_list_tasks_v3_configured_page_limits() {
+    let storage = InMemoryTaskStorage::new().with_page_limits(PageSizeLimits::new(2, 4));
+    let _task_ids = create_test_tasks(&storage, 6, "limits-context").await;
+
+    // The configured default applies when no page size is given
+    let result = storage
+        .list_tasks_v3(&ListTasksParams::default())
+        .await
+        .expect("Failed to list tasks");
+    assert_eq!(result.page_size, 2);
+    assert_eq!(result.tasks.len(), 2);
+    assert_eq!(result.total_size, 6);
+
+    // Oversized requests are clamped and the used size is reported back
+    let params = ListTasksParams {
+        page_size: Some(1000),
+        ..Default::default()
+    };
+    let result = storage
+        .list_tasks_v3(&params)
+        .await
+        .expect("Failed to list tasks");
+    assert_eq!(result.page_size, 4);
+    assert_eq!(result.tasks.len(), 4);
+    assert_eq!(result.next_page_token, "4");
+
+    // Zero is treated as the default
     let params = ListTasksParams {
         page_size: Some(0),
         ..Default::default()
@@ -431,9 +474,31 @@ async fn test_list_tasks_v3_page_size_clamping() {
         .list_tasks_v3(&params)
         .await
         .expect("Failed to list tasks");
+    assert_eq!(result.page_size, 2);
+    assert_eq!(result.tasks.len(), 2);
+
+    // Sizes within the cap are honoured
+    let params = ListTasksParams {
+        page_size: Some(3),
+        ..Default::default()
+    };
+    let result = storage
+        .list_tasks_v3(&params)
+        .await
+        .expect("Failed to list tasks");
+    assert_eq!(result.page_size, 3);
+}
+
+#[test]
+fn test_page_size_limits_keep_default_within_max() {
+    let limits = PageSizeLimits::new(0, 0);
+    assert_eq!(limits.default_page_size, 1);
+    assert_eq!(limits.max_page_size, 1);
 
-    assert_eq!(result.page_size, 1, "Page size should be clamped to 1");
-    assert_eq!(result.tasks.len(), 1, "Should return 1 task");
+    let limits = PageSizeLimits::new(80, 20);
+    assert_eq!(limits.max_page_size, 80);
+    assert_eq!(limits.resolve(None), 80);
+    assert_eq!(limits.resolve(Some(500)), 80);
 }
 
 #[tokio::test]
@@ -577,6 +642,7 @@ async fn test_list_tasks_v3_include_artifacts() {
         }],
         metadata: None,
         extensions: None,
+        created_at: None,
     });
 
     // Update task in storage (through status update to trigger save)
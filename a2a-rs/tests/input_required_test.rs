@@ -0,0 +1,227 @@
+//! Tests for agents pausing a task to ask the client for more input
+
+use std::sync::Arc;
+
+use a2a_rs::{
+    adapter::{DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo},
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{
+        A2AError, InputRequest, Message, Part, SequentialIdGenerator, Task, TaskSendParams,
+        TaskState, TaskStatusUpdateEvent,
+    },
+    port::{
+        AsyncMessageHandler, AsyncStreamingHandler, AsyncTaskManager, streaming_handler::Subscriber,
+    },
+    services::AsyncA2ARequestProcessor,
+};
+use async_trait::async_trait;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+/// Asks which project an expense belongs to, then files it there
+#[derive(Clone)]
+struct ProjectCodeHandler {
+    storage: InMemoryTaskStorage,
+}
+
+fn project_code_request() -> InputRequest {
+    InputRequest::new("Which project code?".to_string())
+        .with_schema(json!({"type": "string", "pattern": "^PRJ-[0-9]+$"}))
+}
+
+fn text(message: &Message) -> &str {
+    match &message.parts[0] {
+        Part::Text { text, .. } => text,
+        other => panic!("Expected a text part, got {:?}", other),
+    }
+}
+
+#[async_trait]
+impl AsyncMessageHandler for ProjectCodeHandler {
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        _session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        // Resume a paused task with the project code the client sent
+        if self.storage.task_exists(task_id).await? {
+            let task = self.storage.get_task(task_id, None).await?;
+            if task.input_request() == Some(project_code_request()) {
+                self.storage
+                    .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+                    .await?;
+                let reply = Message::agent_text(
+                    format!("Filed under {}", text(message)),
+                    "msg-filed".to_string(),
+                );
+                return self
+                    .storage
+                    .update_task_status(task_id, TaskState::Completed, Some(reply))
+                    .await;
+            }
+        }
+
+        self.storage.create_task(task_id, "ctx-expense").await?;
+        self.storage
+            .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+            .await?;
+        self.storage
+            .request_input(task_id, &project_code_request())
+            .await
+    }
+}
+
+/// Records the status updates it receives
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    updates: Arc<Mutex<Vec<TaskStatusUpdateEvent>>>,
+}
+
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for RecordingSubscriber {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        self.updates.lock().await.push(update);
+        Ok(())
+    }
+}
+
+fn send(task_id: &str, text: &str, message_id: &str) -> A2ARequest {
+    A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+        id: task_id.to_string(),
+        session_id: None,
+        message: Message::user_text(text.to_string(), message_id.to_string()),
+        push_notification: None,
+        history_length: None,
+        metadata: None,
+        expected_version: None,
+    }))
+}
+
+#[tokio::test]
+async fn test_handler_pauses_for_input_and_resumes_on_reply() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = DefaultRequestProcessor::new(
+        ProjectCodeHandler {
+            storage: storage.clone(),
+        },
+        storage.clone(),
+        storage.clone(),
+        SimpleAgentInfo::new("Expense Agent".to_string(), "http://localhost".to_string()),
+    );
+    let subscriber = RecordingSubscriber::default();
+    storage
+        .add_status_subscriber("expense", Box::new(subscriber.clone()))
+        .await
+        .unwrap();
+
+    let response = processor
+        .process_request(&send("expense", "Reimburse $20 for lunch", "msg-1"))
+        .await
+        .unwrap();
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(task.status.state, TaskState::InputRequired);
+    assert_eq!(task.input_request(), Some(project_code_request()));
+    assert_eq!(
+        text(task.status.message.as_ref().unwrap()),
+        "Which project code?"
+    );
+
+    // The status event carries the request to streaming clients
+    let updates = subscriber.updates.lock().await.clone();
+    let update = updates.last().unwrap();
+    assert!(!update.final_);
+    assert_eq!(update.status.input_request(), Some(project_code_request()));
+
+    // The follow-up message resumes the same task
+    let response = processor
+        .process_request(&send("expense", "PRJ-42", "msg-2"))
+        .await
+        .unwrap();
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(task.status.state, TaskState::Completed);
+    assert_eq!(task.input_request(), None);
+    assert_eq!(
+        text(task.status.message.as_ref().unwrap()),
+        "Filed under PRJ-42"
+    );
+
+    let history: Vec<&str> = task.history.as_ref().unwrap().iter().map(text).collect();
+    assert_eq!(
+        history,
+        vec![
+            "Reimburse $20 for lunch",
+            "Which project code?",
+            "PRJ-42",
+            "Filed under PRJ-42"
+        ]
+    );
+}
+
+#[test]
+fn test_input_request_round_trips_through_status_message() {
+    let request = project_code_request();
+    let message = request.status_message("expense", "ctx-expense", "msg-input".to_string());
+    assert_eq!(message.task_id.as_deref(), Some("expense"));
+    assert_eq!(
+        message.metadata.as_ref().unwrap()["inputRequest"],
+        json!({
+            "prompt": "Which project code?",
+            "schema": {"type": "string", "pattern": "^PRJ-[0-9]+$"}
+        })
+    );
+
+    // Only an input-required status exposes the request
+    let mut task = Task::new("expense".to_string(), "ctx-expense".to_string());
+    task.update_status(TaskState::InputRequired, Some(message.clone()));
+    assert_eq!(task.input_request(), Some(request));
+    task.update_status(TaskState::Working, Some(message));
+    assert_eq!(task.input_request(), None);
+}
+
+/// Check that `storage` pauses working tasks with an id from `ids_prefix`
+/// and refuses to reopen finished ones
+async fn assert_requests_input_only_while_unfinished(
+    storage: &impl AsyncTaskManager,
+    ids_prefix: &str,
+) {
+    storage.create_task("expense", "ctx-expense").await.unwrap();
+    let task = storage
+        .request_input("expense", &project_code_request())
+        .await
+        .unwrap();
+    assert_eq!(task.input_request(), Some(project_code_request()));
+    assert_eq!(
+        task.status.message.unwrap().message_id,
+        format!("{}-1", ids_prefix)
+    );
+    let stored = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(stored.input_request(), Some(project_code_request()));
+
+    storage
+        .update_task_status("expense", TaskState::Completed, None)
+        .await
+        .unwrap();
+    let refused = storage
+        .request_input("expense", &project_code_request())
+        .await;
+    assert!(matches!(refused, Err(A2AError::InvalidParams(_))));
+    let stored = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(stored.status.state, TaskState::Completed);
+}
+
+#[tokio::test]
+async fn test_finished_tasks_cannot_request_input() {
+    let storage = InMemoryTaskStorage::new().with_id_generator(SequentialIdGenerator::new("input"));
+    assert_requests_input_only_while_unfinished(&storage, "input").await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_finished_sqlx_tasks_cannot_request_input() {
+    let storage = a2a_rs::adapter::storage::SqlxTaskStorage::new("sqlite::memory:")
+        .await
+        .unwrap()
+        .with_id_generator(SequentialIdGenerator::new("input"));
+    assert_requests_input_only_while_unfinished(&storage, "input").await;
+}
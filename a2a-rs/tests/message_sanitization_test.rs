@@ -0,0 +1,304 @@
+//! Tests for sanitizing message text before it is stored
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{
+        Artifact, ControlCharacters, GetTaskEventsParams, Message, MessageSanitizer, Part,
+        SanitizePolicy, Task, TaskCancellation, TaskLogEvent, TaskResult, TaskState,
+    },
+    port::AsyncTaskManager,
+};
+use serde_json::json;
+
+fn text(message: &Message) -> &str {
+    match &message.parts[0] {
+        Part::Text { text, .. } => text,
+        other => panic!("Expected a text part, got {:?}", other),
+    }
+}
+
+fn sanitizer() -> MessageSanitizer {
+    MessageSanitizer::new()
+        .with_agent_policy(SanitizePolicy::agent_output().with_max_text_length(40))
+        .with_user_policy(SanitizePolicy::user_input().with_max_text_length(20))
+}
+
+#[test]
+fn test_control_characters_are_stripped_or_escaped() {
+    let raw = "Approved\u{0}\u{1b}[0m for\u{7f} $20\tnet";
+    let strip = SanitizePolicy::new().with_control_characters(ControlCharacters::Strip);
+    assert_eq!(strip.sanitize_text(raw), "Approved[0m for $20\tnet");
+
+    let escape = SanitizePolicy::new().with_control_characters(ControlCharacters::Escape);
+    assert_eq!(escape.sanitize_text("Bell\u{7}"), "Bell\\u{7}");
+
+    // Nothing changes unless a policy asks for it
+    assert_eq!(SanitizePolicy::new().sanitize_text(raw), raw);
+}
+
+#[test]
+fn test_line_endings_and_blank_lines_are_normalized() {
+    let policy = SanitizePolicy::new()
+        .with_normalized_line_endings()
+        .with_max_blank_lines(1);
+    assert_eq!(
+        policy.sanitize_text("Line one\r\nLine two\r\r\n\n\n   \nLine three\n"),
+        "Line one\nLine two\n\nLine three\n"
+    );
+}
+
+#[test]
+fn test_long_parts_are_truncated_with_the_indicator() {
+    let policy = SanitizePolicy::new().with_max_text_length(20);
+    let truncated = policy.sanitize_text(&"é".repeat(50));
+    assert_eq!(truncated.chars().count(), 20);
+    assert!(truncated.ends_with("… [truncated]"));
+    assert!(truncated.starts_with("ééééééé…"));
+
+    let policy = policy.with_truncation_indicator("...");
+    assert_eq!(
+        policy.sanitize_text(&"a".repeat(30)),
+        format!("{}...", "a".repeat(17))
+    );
+    assert_eq!(policy.sanitize_text("short"), "short");
+}
+
+#[tokio::test]
+async fn test_stored_messages_are_sanitized_by_role() {
+    let storage = InMemoryTaskStorage::new().with_sanitizer(sanitizer());
+    storage.create_task("expense", "ctx").await.unwrap();
+
+    let mut request = Message::user_text(
+        "Please reimburse\u{1b}[2J my taxi ride from the airport".to_string(),
+        "m-1".to_string(),
+    );
+    request.add_part(Part::data(
+        json!({"note": "kept\u{7} as is"})
+            .as_object()
+            .unwrap()
+            .clone(),
+    ));
+    storage
+        .update_task_status("expense", TaskState::Working, Some(request))
+        .await
+        .unwrap();
+    let reply = Message::agent_text(
+        "Approved\u{0}\r\n\n\n\nReference R-1".to_string(),
+        "m-2".to_string(),
+    );
+    let task = storage
+        .update_task_status("expense", TaskState::Completed, Some(reply))
+        .await
+        .unwrap();
+
+    let history = task.history.as_ref().unwrap();
+    assert_eq!(text(&history[0]), "Please … [truncated]");
+    assert_eq!(text(&history[0]).chars().count(), 20);
+    match &history[0].parts[1] {
+        Part::Data { data, .. } => assert_eq!(data["note"], "kept\u{7} as is"),
+        other => panic!("Expected a data part, got {:?}", other),
+    }
+    assert_eq!(text(&history[1]), "Approved\n\n\nReference R-1");
+
+    // The event log records the sanitized messages too
+    let log = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "expense".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let appended: Vec<String> = log
+        .events
+        .iter()
+        .filter_map(|record| match &record.event {
+            TaskLogEvent::MessageAppended { message } => Some(text(message).to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(appended, vec![text(&history[0]), text(&history[1])]);
+
+    let opened = storage
+        .get_or_create_task(
+            "chat",
+            "ctx",
+            Some(&Message::user_text(
+                "Hi\u{7} there".to_string(),
+                "m-3".to_string(),
+            )),
+        )
+        .await
+        .unwrap();
+    assert_eq!(text(&opened.task.history.unwrap()[0]), "Hi there");
+}
+
+fn report(text: &str) -> Artifact {
+    Artifact {
+        artifact_id: "report".to_string(),
+        name: None,
+        description: None,
+        parts: vec![Part::text(text.to_string())],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    }
+}
+
+fn duplicate_cancellation() -> TaskCancellation {
+    TaskCancellation::new()
+        .with_canceled_by("alice".to_string())
+        .with_reason("Duplicate\u{7} of #41\r\n".to_string())
+}
+
+#[tokio::test]
+async fn test_artifacts_and_results_are_sanitized_as_agent_output() {
+    let storage = InMemoryTaskStorage::new().with_sanitizer(sanitizer());
+    storage.create_task("expense", "ctx").await.unwrap();
+
+    let task = storage
+        .add_task_artifact("expense", report("Total\u{0}: $20\r\n\n\n\nPaid"))
+        .await
+        .unwrap();
+    let artifacts = task.artifacts.unwrap();
+    assert!(
+        matches!(&artifacts[0].parts[0], Part::Text { text, .. } if text == "Total: $20\n\n\nPaid")
+    );
+
+    let result = TaskResult::new(json!({
+        "summary": format!("Approved\u{7} {}", "!".repeat(100)),
+        "lines": ["Taxi\r\n$20"],
+        "amount": 20,
+    }));
+    let task = storage
+        .complete_task_with_result("expense", result, None)
+        .await
+        .unwrap();
+    let data = &task.result.unwrap().data;
+    let summary = data["summary"].as_str().unwrap();
+    assert_eq!(summary.chars().count(), 40);
+    assert!(summary.starts_with("Approved !!!"));
+    assert_eq!(data["lines"][0], "Taxi\n$20");
+    assert_eq!(data["amount"], 20);
+}
+
+#[tokio::test]
+async fn test_cancel_reasons_are_sanitized_as_user_input() {
+    let storage = InMemoryTaskStorage::new().with_sanitizer(sanitizer());
+    storage.create_task("expense", "ctx").await.unwrap();
+    storage
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+
+    let task = storage
+        .cancel_task_with("expense", &duplicate_cancellation(), None)
+        .await
+        .unwrap();
+    let cancellation = task.cancellation().unwrap();
+    assert_eq!(cancellation.reason.as_deref(), Some("Duplicate of #41\n"));
+    assert_eq!(cancellation.canceled_by.as_deref(), Some("alice"));
+    assert_eq!(
+        text(task.status.message.as_ref().unwrap()),
+        "Task expense canceled by alice: Duplicate of #41\n"
+    );
+}
+
+#[tokio::test]
+async fn test_imported_tasks_are_sanitized() {
+    let storage = InMemoryTaskStorage::new().with_sanitizer(sanitizer());
+    let mut task = Task::new("imported".to_string(), "ctx".to_string());
+    task.history = Some(vec![Message::user_text(
+        "Hi\u{7} there".to_string(),
+        "m-1".to_string(),
+    )]);
+    task.artifacts = Some(vec![report("Paid\u{0}")]);
+    task.result = Some(TaskResult::new(json!({"summary": "Done\u{7}"})));
+    storage.import_task(&task).await.unwrap();
+
+    let task = storage.get_task("imported", None).await.unwrap();
+    assert_eq!(text(&task.history.unwrap()[0]), "Hi there");
+    assert!(
+        matches!(&task.artifacts.unwrap()[0].parts[0], Part::Text { text, .. } if text == "Paid")
+    );
+    assert_eq!(task.result.unwrap().data["summary"], "Done");
+}
+
+#[tokio::test]
+async fn test_messages_are_stored_unchanged_by_default() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx").await.unwrap();
+    let raw = "Bytes\u{0}\r\n\n\n\nas sent".repeat(1000);
+    let task = storage
+        .update_task_status(
+            "expense",
+            TaskState::Working,
+            Some(Message::agent_text(raw.clone(), "m-1".to_string())),
+        )
+        .await
+        .unwrap();
+    assert_eq!(text(&task.history.unwrap()[0]), raw);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlite_storage_sanitizes_messages() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:")
+        .await
+        .unwrap()
+        .with_sanitizer(sanitizer());
+    storage.create_task("expense", "ctx").await.unwrap();
+    let reply = Message::agent_text(
+        format!("Approved\u{1b}{}", "!".repeat(100)),
+        "m-1".to_string(),
+    );
+    storage
+        .update_task_status("expense", TaskState::Completed, Some(reply))
+        .await
+        .unwrap();
+
+    let task = storage.get_task("expense", None).await.unwrap();
+    let stored = text(&task.history.as_ref().unwrap()[0]);
+    assert_eq!(stored.chars().count(), 40);
+    assert!(stored.starts_with("Approved!!!"));
+    assert!(stored.ends_with("… [truncated]"));
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlite_storage_sanitizes_artifacts_results_and_cancel_reasons() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:")
+        .await
+        .unwrap()
+        .with_sanitizer(sanitizer());
+    storage.create_task("expense", "ctx").await.unwrap();
+    storage
+        .add_task_artifact("expense", report("Total\u{0}: $20"))
+        .await
+        .unwrap();
+    storage
+        .set_task_result("expense", TaskResult::new(json!({"summary": "Done\u{7}"})))
+        .await
+        .unwrap();
+    storage
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    storage
+        .cancel_task_with("expense", &duplicate_cancellation(), None)
+        .await
+        .unwrap();
+
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert!(
+        matches!(&task.artifacts.as_ref().unwrap()[0].parts[0], Part::Text { text, .. } if text == "Total: $20")
+    );
+    assert_eq!(task.result.as_ref().unwrap().data["summary"], "Done");
+    assert_eq!(
+        task.cancellation().unwrap().reason.as_deref(),
+        Some("Duplicate of #41\n")
+    );
+}
@@ -0,0 +1,113 @@
+//! Tests for checking messages against the input schema of their task's skill
+
+mod common;
+
+use a2a_rs::{
+    adapter::{InMemoryTaskStorage, business::DefaultMessageHandler},
+    domain::{AgentSkill, Message, MessageSchemas, Part},
+    services::AsyncA2ARequestProcessor,
+};
+use serde_json::{Value, json};
+
+fn expense_skill() -> AgentSkill {
+    AgentSkill::new(
+        "process_reimbursement".to_string(),
+        "Process reimbursement".to_string(),
+        "Reimburse an expense".to_string(),
+        vec!["expenses".to_string()],
+    )
+    .with_input_schema(json!({
+        "type": "object",
+        "properties": {
+            "amount": {"type": "number"},
+            "date": {"type": "string"},
+            "purpose": {"type": "string"}
+        },
+        "required": ["amount", "date", "purpose"]
+    }))
+}
+
+fn processor() -> impl AsyncA2ARequestProcessor {
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = common::agent_info().add_skill_object(expense_skill());
+    common::processor_with(
+        DefaultMessageHandler::new(storage.clone()),
+        &storage,
+        agent_info,
+    )
+    .with_message_schemas(MessageSchemas::from_skills(&[expense_skill()]))
+}
+
+fn message(id: &str, skill_id: Option<&str>, data: Value) -> Message {
+    let mut message = Message::user_text("About my taxi ride".to_string(), id.to_string());
+    message.add_part(Part::data(data.as_object().unwrap().clone()));
+    message.metadata =
+        skill_id.map(|skill_id| json!({"skillId": skill_id}).as_object().unwrap().clone());
+    message
+}
+
+async fn send(processor: &impl AsyncA2ARequestProcessor, task_id: &str, message: Message) -> Value {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/send",
+        "params": {"id": task_id, "message": message},
+    });
+    let response = processor
+        .process_raw_request(&request.to_string())
+        .await
+        .unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+#[tokio::test]
+async fn test_follow_up_missing_an_expense_field_is_rejected() {
+    let processor = processor();
+    let expense = json!({"amount": 20, "date": "2026-10-01", "purpose": "Taxi to the airport"});
+    let response = send(
+        &processor,
+        "expense-1",
+        message("m-1", Some("process_reimbursement"), expense),
+    )
+    .await;
+    assert!(response["result"].is_object(), "{}", response);
+
+    // The follow-up names no skill, but its task is an expense
+    let response = send(
+        &processor,
+        "expense-1",
+        message(
+            "m-2",
+            None,
+            json!({"amount": "twenty", "date": "2026-10-01"}),
+        ),
+    )
+    .await;
+    let error = &response["error"];
+    assert_eq!(error["code"], -32602, "{}", response);
+    let detail = &error["data"];
+    assert_eq!(detail["errorCode"], "validation.schema");
+    assert_eq!(detail["params"]["skillId"], "process_reimbursement");
+    assert_eq!(detail["params"]["missing"], "purpose");
+    assert_eq!(detail["params"]["invalid"], "amount");
+    assert_eq!(detail["params"]["fields"], "purpose, amount");
+
+    let corrected = json!({"amount": 25, "date": "2026-10-01", "purpose": "Taxi back"});
+    let response = send(&processor, "expense-1", message("m-3", None, corrected)).await;
+    assert!(response["result"].is_object(), "{}", response);
+    let history = response["result"]["history"].as_array().unwrap();
+    assert!(history.iter().any(|entry| entry["messageId"] == "m-3"));
+    assert!(history.iter().all(|entry| entry["messageId"] != "m-2"));
+}
+
+#[tokio::test]
+async fn test_messages_for_skills_without_a_schema_are_not_checked() {
+    let processor = processor();
+    let chat = Message::user_text("Hello there".to_string(), "m-1".to_string());
+    let response = send(&processor, "chat-1", chat).await;
+    assert!(response["result"].is_object(), "{}", response);
+
+    let follow_up = Message::user_text("How long do refunds take?".to_string(), "m-2".to_string());
+    let response = send(&processor, "chat-1", follow_up).await;
+    assert!(response["result"].is_object(), "{}", response);
+}
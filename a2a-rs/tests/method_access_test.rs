@@ -0,0 +1,228 @@
+//! Tests for restricting JSON-RPC methods per auth role
+
+#![cfg(any(feature = "http-server", feature = "ws-server"))]
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+mod common;
+
+use a2a_rs::{
+    adapter::{BearerTokenAuthenticator, MethodAccessPolicy, RoleAccess},
+    domain::A2AError,
+    port::{AuthContext, Authenticator},
+};
+
+fn policy() -> MethodAccessPolicy {
+    MethodAccessPolicy::new()
+        .with_role("viewer", RoleAccess::read_only())
+        .with_role(
+            "operator",
+            RoleAccess::new().denying(vec!["tasks/pushNotificationConfig/*".to_string()]),
+        )
+}
+
+#[test]
+fn test_policy_checks_methods_per_role() {
+    let policy = policy();
+
+    assert!(policy.check(Some("viewer"), "tasks/get").is_ok());
+    assert!(policy.check(Some("viewer"), "tasks/list").is_ok());
+    for method in ["message/send", "tasks/cancel", "tasks/tags/add"] {
+        match policy.check(Some("viewer"), method).unwrap_err() {
+            A2AError::MethodNotAuthorized(denied) => assert_eq!(denied, method),
+            other => panic!("Expected method not authorized, got {:?}", other),
+        }
+    }
+
+    // Deny patterns match by prefix and win over a missing allow list
+    assert!(policy.check(Some("operator"), "tasks/cancel").is_ok());
+    assert!(
+        policy
+            .check(Some("operator"), "tasks/pushNotificationConfig/set")
+            .is_err()
+    );
+
+    // No role and an undefined role both have no access
+    assert!(policy.check(None, "tasks/get").is_err());
+    assert!(policy.check(Some("auditor"), "tasks/get").is_err());
+
+    // Unless the policy names a default role for principals without one
+    let defaulted = policy.clone().with_default_role("operator");
+    assert!(defaulted.check(None, "tasks/cancel").is_ok());
+    assert!(
+        defaulted
+            .check(None, "tasks/pushNotificationConfig/set")
+            .is_err()
+    );
+    assert!(defaulted.check(Some("viewer"), "tasks/cancel").is_err());
+
+    // Roles deserialize from config
+    let policy: MethodAccessPolicy = serde_json::from_value(serde_json::json!({
+        "roles": {
+            "viewer": {"allow": ["tasks/get"]},
+            "writer": {"deny": ["tasks/cancel"]}
+        },
+        "default_role": "viewer"
+    }))
+    .unwrap();
+    assert!(policy.check(None, "tasks/get").is_ok());
+    assert!(policy.check(Some("viewer"), "tasks/get").is_ok());
+    assert!(policy.check(Some("viewer"), "tasks/list").is_err());
+    assert!(policy.check(Some("writer"), "message/send").is_ok());
+    assert!(policy.check(Some("writer"), "tasks/cancel").is_err());
+}
+
+#[tokio::test]
+async fn test_authenticators_assign_roles() {
+    let authenticator = BearerTokenAuthenticator::new(vec!["admin-token".to_string()])
+        .with_role("viewer-token".to_string(), "viewer".to_string());
+
+    let principal = authenticator
+        .authenticate(&AuthContext::new(
+            "bearer".to_string(),
+            "viewer-token".to_string(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(principal.role(), Some("viewer"));
+
+    let principal = authenticator
+        .authenticate(&AuthContext::new(
+            "bearer".to_string(),
+            "admin-token".to_string(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(principal.role(), None);
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_viewer_token_is_limited_to_reads_over_http() {
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, HttpClient, HttpServer, InMemoryTaskStorage, SimpleAgentInfo,
+            business::DefaultMessageHandler,
+        },
+        domain::{ListTasksParams, Message, error::METHOD_NOT_AUTHORIZED},
+        port::AsyncTaskManager,
+        services::AsyncA2AClient,
+    };
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-roles").await.unwrap();
+
+    let agent_info = SimpleAgentInfo::new(
+        "Role Agent".to_string(),
+        "http://127.0.0.1:8327".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let authenticator = BearerTokenAuthenticator::new(vec!["admin-token".to_string()])
+        .with_role("viewer-token".to_string(), "viewer".to_string());
+    let server = HttpServer::with_auth(
+        processor,
+        agent_info,
+        "127.0.0.1:8327".to_string(),
+        authenticator,
+    )
+    .with_method_access(policy().with_default_role("operator"));
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let viewer = HttpClient::with_auth(
+        "http://127.0.0.1:8327".to_string(),
+        "viewer-token".to_string(),
+    );
+    let task = viewer.get_task("expense", None).await.unwrap();
+    assert_eq!(task.id, "expense");
+    let listed = viewer
+        .list_tasks(&ListTasksParams::default())
+        .await
+        .unwrap();
+    assert_eq!(listed.total_size, 1);
+
+    let message = Message::user_text("Lunch".to_string(), "msg-viewer".to_string());
+    match viewer
+        .send_task_message("expense", &message, None, None)
+        .await
+        .unwrap_err()
+    {
+        A2AError::JsonRpc { code, .. } => assert_eq!(code, METHOD_NOT_AUTHORIZED),
+        other => panic!("Expected a JSON-RPC error, got {:?}", other),
+    }
+    match viewer.cancel_task("expense").await.unwrap_err() {
+        A2AError::JsonRpc { code, .. } => assert_eq!(code, METHOD_NOT_AUTHORIZED),
+        other => panic!("Expected a JSON-RPC error, got {:?}", other),
+    }
+    // The refused request never reached the task
+    let task = storage.get_task("expense", Some(10)).await.unwrap();
+    assert!(task.history.unwrap_or_default().is_empty());
+
+    // A token without a role gets the default role's access
+    let admin = HttpClient::with_auth(
+        "http://127.0.0.1:8327".to_string(),
+        "admin-token".to_string(),
+    );
+    admin
+        .send_task_message("expense", &message, None, None)
+        .await
+        .unwrap();
+}
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+#[tokio::test]
+async fn test_viewer_token_is_limited_to_reads_over_websocket() {
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo, WebSocketClient,
+            WebSocketServer,
+        },
+        domain::{Message, error::METHOD_NOT_AUTHORIZED},
+        port::AsyncTaskManager,
+        services::AsyncA2AClient,
+    };
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    storage
+        .create_task("ws-expense", "ctx-roles")
+        .await
+        .unwrap();
+
+    let handler = common::TestBusinessHandler::with_storage(storage.clone());
+    let agent_info =
+        SimpleAgentInfo::new("Role Agent".to_string(), "ws://127.0.0.1:8328".to_string());
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let authenticator = BearerTokenAuthenticator::new(Vec::new())
+        .with_role("viewer-token".to_string(), "viewer".to_string());
+    let server = WebSocketServer::with_auth(
+        processor,
+        agent_info,
+        handler,
+        "127.0.0.1:8328".to_string(),
+        authenticator,
+    )
+    .with_method_access(policy());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let viewer =
+        WebSocketClient::with_auth("ws://127.0.0.1:8328".to_string(), "viewer-token".into());
+    let task = viewer.get_task("ws-expense", None).await.unwrap();
+    assert_eq!(task.id, "ws-expense");
+
+    let message = Message::user_text("Taxi".to_string(), "msg-ws-viewer".to_string());
+    match viewer
+        .send_task_message("ws-expense", &message, None, None)
+        .await
+        .unwrap_err()
+    {
+        A2AError::JsonRpc { code, .. } => assert_eq!(code, METHOD_NOT_AUTHORIZED),
+        other => panic!("Expected a JSON-RPC error, got {:?}", other),
+    }
+}
@@ -17,7 +17,7 @@ use a2a_rs::{
         DefaultRequestProcessor, HttpClient, HttpServer, InMemoryTaskStorage, SimpleAgentInfo,
         WebSocketClient, WebSocketServer,
     },
-    domain::{Message, Part, TaskState},
+    domain::{ListTasksParams, Message, Part, TaskState},
     services::{AsyncA2AClient, StreamItem},
 };
 use base64::Engine;
@@ -617,3 +617,124 @@ async fn test_error_handling_across_transports() {
     let _ = ws_shutdown_tx.send(());
     let _ = tokio::join!(http_handle, ws_handle);
 }
+
+/// Test that tasks/list over WebSocket returns exactly what the HTTP path returns,
+/// including filters, total size and page tokens
+#[tokio::test]
+async fn test_list_tasks_over_websocket_matches_http() {
+    let storage = InMemoryTaskStorage::new();
+    let handler = TestBusinessHandler::with_storage(storage.clone());
+    let test_agent_info = SimpleAgentInfo::new(
+        "test-agent".to_string(),
+        "http://localhost:8300".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), test_agent_info);
+
+    let agent_info = SimpleAgentInfo::new(
+        "List Tasks Agent".to_string(),
+        "http://localhost:8300".to_string(),
+    )
+    .with_streaming();
+
+    let http_server = HttpServer::new(
+        processor.clone(),
+        agent_info.clone(),
+        "127.0.0.1:8300".to_string(),
+    );
+    let ws_server =
+        WebSocketServer::new(processor, agent_info, handler, "127.0.0.1:8301".to_string());
+
+    let (http_shutdown_tx, http_shutdown_rx) = oneshot::channel::<()>();
+    let (ws_shutdown_tx, ws_shutdown_rx) = oneshot::channel::<()>();
+
+    let http_handle = tokio::spawn(async move {
+        tokio::select! {
+            _ = http_server.start() => {},
+            _ = http_shutdown_rx => {}
+        }
+    });
+
+    let ws_handle = tokio::spawn(async move {
+        tokio::select! {
+            _ = ws_server.start() => {},
+            _ = ws_shutdown_rx => {}
+        }
+    });
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let http_client = HttpClient::new("http://localhost:8300".to_string());
+    let ws_client = WebSocketClient::new("ws://localhost:8301".to_string());
+
+    // Create a handful of tasks, half of them via each transport
+    for i in 0..5 {
+        let task_id = format!("list-task-{}-{}", i, uuid::Uuid::new_v4());
+        let message = Message::user_text(
+            format!("List me {}", i),
+            format!("msg-{}", uuid::Uuid::new_v4()),
+        );
+        if i % 2 == 0 {
+            http_client
+                .send_task_message(&task_id, &message, None, None)
+                .await
+                .expect("Failed to create task via HTTP");
+        } else {
+            ws_client
+                .send_task_message(&task_id, &message, None, None)
+                .await
+                .expect("Failed to create task via WebSocket");
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    // Walk every page on both transports and compare the raw result shape
+    let mut params = ListTasksParams {
+        page_size: Some(2),
+        history_length: Some(1),
+        ..Default::default()
+    };
+    let mut pages = 0;
+    loop {
+        let http_result = http_client
+            .list_tasks(&params)
+            .await
+            .expect("Failed to list tasks via HTTP");
+        let ws_result = ws_client
+            .list_tasks(&params)
+            .await
+            .expect("Failed to list tasks via WebSocket");
+
+        assert_eq!(
+            serde_json::to_value(&http_result).unwrap(),
+            serde_json::to_value(&ws_result).unwrap(),
+            "WebSocket page {} should match HTTP",
+            pages
+        );
+        assert_eq!(ws_result.total_size, 5);
+        assert_eq!(ws_result.page_size, 2);
+
+        pages += 1;
+        if ws_result.next_page_token.is_empty() {
+            break;
+        }
+        params.page_token = Some(ws_result.next_page_token);
+    }
+    assert_eq!(pages, 3);
+
+    // Filters are honored the same way
+    let filtered = ListTasksParams {
+        status: Some(TaskState::Canceled),
+        ..Default::default()
+    };
+    let http_result = http_client.list_tasks(&filtered).await.unwrap();
+    let ws_result = ws_client.list_tasks(&filtered).await.unwrap();
+    assert_eq!(ws_result.total_size, 0);
+    assert_eq!(
+        serde_json::to_value(&http_result).unwrap(),
+        serde_json::to_value(&ws_result).unwrap()
+    );
+
+    let _ = http_shutdown_tx.send(());
+    let _ = ws_shutdown_tx.send(());
+    let _ = tokio::join!(http_handle, ws_handle);
+}
@@ -0,0 +1,181 @@
+//! Tests for failing tasks whose message handler never finishes
+
+mod common;
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use a2a_rs::{
+    adapter::{
+        InMemoryTaskStorage, ProcessingTimeout, business::processing_timeout::ERROR_METADATA_KEY,
+    },
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{
+        A2AError, GetTaskEventsParams, Message, SequentialIdGenerator, Task, TaskLogEvent,
+        TaskSendParams, TaskState, error_catalog::codes,
+    },
+    port::{AsyncMessageHandler, AsyncTaskManager},
+    services::server::AsyncA2ARequestProcessor,
+};
+use async_trait::async_trait;
+use serde_json::{Map, json};
+
+/// Marks the task working, then waits forever
+#[derive(Clone)]
+struct HangingHandler {
+    storage: InMemoryTaskStorage,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Records that the handler future was dropped
+struct CancelGuard(Arc<AtomicBool>);
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl AsyncMessageHandler for HangingHandler {
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        _session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        let _guard = CancelGuard(self.cancelled.clone());
+        self.storage.create_task(task_id, "ctx-hang").await?;
+        self.storage
+            .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+            .await?;
+        futures::future::pending().await
+    }
+}
+
+fn processor(
+    timeout: ProcessingTimeout,
+) -> (
+    impl AsyncA2ARequestProcessor,
+    InMemoryTaskStorage,
+    Arc<AtomicBool>,
+) {
+    let storage = InMemoryTaskStorage::new();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let handler = HangingHandler {
+        storage: storage.clone(),
+        cancelled: cancelled.clone(),
+    };
+    let processor = common::processor_with(handler, &storage, common::agent_info())
+        .with_processing_timeout(timeout)
+        .with_id_generator(SequentialIdGenerator::new("timeout"));
+    (processor, storage, cancelled)
+}
+
+fn send(task_id: &str, skill_id: Option<&str>) -> A2ARequest {
+    let mut message = Message::user_text("Reimburse $20 for lunch".to_string(), "msg-1".into());
+    if let Some(skill_id) = skill_id {
+        let mut metadata = Map::new();
+        metadata.insert("skillId".to_string(), json!(skill_id));
+        message.metadata = Some(metadata);
+    }
+    A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+        id: task_id.to_string(),
+        session_id: None,
+        message,
+        push_notification: None,
+        history_length: None,
+        metadata: None,
+        expected_version: None,
+    }))
+}
+
+#[test]
+fn test_skill_timeout_overrides_default() {
+    let timeout = ProcessingTimeout::new()
+        .with_default_timeout(Duration::from_secs(30))
+        .with_skill_timeout("process_reimbursement", Duration::from_secs(120));
+
+    let message = |skill_id: &str| {
+        let mut message = Message::user_text("hi".to_string(), "msg-1".to_string());
+        let mut metadata = Map::new();
+        metadata.insert("skillId".to_string(), json!(skill_id));
+        message.metadata = Some(metadata);
+        message
+    };
+    assert_eq!(
+        timeout.for_message(&message("process_reimbursement")),
+        Some(Duration::from_secs(120))
+    );
+    assert_eq!(
+        timeout.for_message(&message("other")),
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(
+        ProcessingTimeout::new().for_message(&message("other")),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_hanging_handler_is_failed_after_timeout() {
+    let (processor, storage, cancelled) =
+        processor(ProcessingTimeout::new().with_default_timeout(Duration::from_millis(100)));
+
+    let response = processor
+        .process_request(&send("hang-task", None))
+        .await
+        .unwrap();
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(task.status.state, TaskState::Failed);
+    assert!(cancelled.load(Ordering::SeqCst), "handler was not dropped");
+
+    // The status message carries a structured timeout error
+    let status = task.status.message.unwrap();
+    assert_eq!(status.message_id, "timeout-1");
+    let error = &status.metadata.unwrap()[ERROR_METADATA_KEY];
+    assert_eq!(error["errorCode"], codes::TASK_TIMEOUT);
+    assert_eq!(error["params"]["taskId"], "hang-task");
+    assert_eq!(error["params"]["timeoutSeconds"], "0.1");
+
+    // The failure is the last event in the task's log
+    let log = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "hang-task".to_string(),
+            page_size: Some(100),
+            page_token: None,
+        })
+        .await
+        .unwrap();
+    match &log.events.last().unwrap().event {
+        TaskLogEvent::StatusUpdate(event) => {
+            assert_eq!(event.status.state, TaskState::Failed);
+            assert!(event.final_);
+        }
+        other => panic!("Expected a status update, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_skill_override_applies_to_hanging_handler() {
+    let (processor, storage, _) = processor(
+        ProcessingTimeout::new()
+            .with_default_timeout(Duration::from_secs(30))
+            .with_skill_timeout("quick", Duration::from_millis(100)),
+    );
+
+    let started = Instant::now();
+    processor
+        .process_request(&send("quick-task", Some("quick")))
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    let task = storage.get_task("quick-task", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Failed);
+}
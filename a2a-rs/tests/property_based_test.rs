@@ -129,6 +129,8 @@ prop_compose! {
             tags,
             input_modes,
             output_modes,
+            input_schema: None,
+            output_schema: None,
             security: None,
         }
     }
@@ -169,6 +169,7 @@ async fn test_push_notifications() {
         parts: vec![artifact_part],
         metadata: None,
         extensions: None,
+        created_at: None,
     };
 
     let artifact_message_id = format!("msg-{}", uuid::Uuid::new_v4());
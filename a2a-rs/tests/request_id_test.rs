@@ -0,0 +1,182 @@
+//! Tests for correlating requests by their `X-Request-ID`
+
+#![cfg(all(feature = "http-server", feature = "http-client"))]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use a2a_rs::{
+    HttpClient, RetryPolicy,
+    adapter::{
+        DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, REQUEST_ID_HEADER, RequestLimits,
+        SimpleAgentInfo, business::DefaultMessageHandler,
+    },
+    application::json_rpc::{A2ARequest, GetTaskRequest},
+    domain::{SequentialIdGenerator, TaskQueryParams},
+    services::AsyncA2AClient,
+};
+use axum::{
+    Router,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+};
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+
+async fn wait_until_listening(address: &str) {
+    for _ in 0..50 {
+        if TcpStream::connect(address).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Nothing listening on {}", address);
+}
+
+async fn start_agent(address: &str, limits: RequestLimits) {
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new("Agent".to_string(), format!("http://{}", address));
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage,
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, address.to_string())
+        .with_request_id_generator(SequentialIdGenerator::new("server"))
+        .with_request_limits(limits);
+    tokio::spawn(async move { server.start().await });
+    wait_until_listening(address).await;
+}
+
+fn get_task(task_id: &str) -> A2ARequest {
+    A2ARequest::GetTask(GetTaskRequest::new(TaskQueryParams {
+        id: task_id.to_string(),
+        history_length: None,
+        metadata: None,
+        since: None,
+        snapshot: None,
+        exclude: Vec::new(),
+    }))
+}
+
+#[tokio::test]
+async fn test_client_request_id_is_echoed_in_responses_and_errors() {
+    start_agent("127.0.0.1:8359", RequestLimits::new()).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post("http://127.0.0.1:8359/")
+        .header(REQUEST_ID_HEADER, "frontend-42")
+        .json(&get_task("missing"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], "frontend-42");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["data"]["requestId"], "frontend-42");
+    // The error detail is still intact
+    assert!(body["error"]["data"]["errorCode"].is_string(), "{}", body);
+
+    // Without an id, or with an unusable one, the server mints one
+    let response = client
+        .post("http://127.0.0.1:8359/")
+        .body("not json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], "server-1");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["data"]["requestId"], "server-1");
+
+    let response = client
+        .post("http://127.0.0.1:8359/")
+        .header(REQUEST_ID_HEADER, "x".repeat(500))
+        .json(&get_task("missing"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], "server-2");
+
+    // Successful responses carry the header but keep their body as it is
+    let response = client
+        .get("http://127.0.0.1:8359/.well-known/agent-card.json")
+        .header(REQUEST_ID_HEADER, "card-1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], "card-1");
+    let card: Value = response.json().await.unwrap();
+    assert_eq!(card["name"], "Agent");
+}
+
+#[tokio::test]
+async fn test_refused_requests_carry_the_request_id() {
+    start_agent(
+        "127.0.0.1:8390",
+        RequestLimits::new().with_rate_limit(1, Duration::from_secs(60)),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let send = |id: &str| {
+        client
+            .post("http://127.0.0.1:8390/")
+            .header(REQUEST_ID_HEADER, id)
+            .json(&get_task("missing"))
+            .send()
+    };
+
+    send("first").await.unwrap();
+    let response = send("second").await.unwrap();
+    assert_eq!(response.status(), 429);
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], "second");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["data"]["requestId"], "second");
+    assert!(body["error"]["data"]["errorCode"].is_string(), "{}", body);
+}
+
+#[tokio::test]
+async fn test_retries_keep_the_request_id() {
+    // Busy twice, then answers; records the id of every attempt
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = seen.clone();
+    let app = Router::new().route(
+        "/",
+        post(move |headers: HeaderMap| {
+            let seen = recorded.clone();
+            async move {
+                let id = headers[REQUEST_ID_HEADER].to_str().unwrap().to_string();
+                let mut seen = seen.lock().unwrap();
+                seen.push(id);
+                if seen.len() % 3 != 0 {
+                    return StatusCode::SERVICE_UNAVAILABLE.into_response();
+                }
+                axum::Json(json!({"jsonrpc": "2.0", "id": 1, "result": null})).into_response()
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8360")
+        .await
+        .unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let client = HttpClient::new("http://127.0.0.1:8360".to_string())
+        .with_retry_policy(RetryPolicy::new(3).with_base_delay(Duration::from_millis(10)))
+        .with_request_id_generator(SequentialIdGenerator::new("web"));
+
+    client.send_request(&get_task("expense")).await.unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec!["web-1", "web-1", "web-1"]);
+
+    // A caller-provided id is used as it is, across retries too
+    client
+        .send_request_with_id(&get_task("expense"), "chat-page-7")
+        .await
+        .unwrap();
+    client.send_request(&get_task("expense")).await.unwrap();
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen[3..6], ["chat-page-7", "chat-page-7", "chat-page-7"]);
+    assert_eq!(seen[6..], ["web-2", "web-2", "web-2"]);
+}
@@ -0,0 +1,158 @@
+//! Tests for Retry-After hints on rate-limited and overloaded responses
+
+#![cfg(all(feature = "http-server", feature = "http-client"))]
+
+use std::time::{Duration, Instant};
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, HttpClient, HttpServer, InMemoryTaskStorage, RequestLimits,
+        RetryPolicy, SimpleAgentInfo, business::DefaultMessageHandler,
+    },
+    domain::{A2AError, error::SERVER_BUSY},
+    port::AsyncTaskManager,
+    services::AsyncA2AClient,
+};
+use serde_json::{Value, json};
+
+async fn start_server(port: u16, limits: RequestLimits) -> InMemoryTaskStorage {
+    let storage = InMemoryTaskStorage::new();
+    let url = format!("http://127.0.0.1:{}", port);
+    let agent_info = SimpleAgentInfo::new("Busy Agent".to_string(), url);
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, format!("127.0.0.1:{}", port))
+        .with_request_limits(limits);
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    storage
+}
+
+fn get_task_request(task_id: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/get",
+        "params": {"id": task_id}
+    })
+}
+
+#[test]
+fn test_retry_policy_prefers_the_server_hint() {
+    let policy = RetryPolicy::new(5)
+        .with_base_delay(Duration::from_millis(100))
+        .with_max_delay(Duration::from_secs(10));
+
+    assert_eq!(policy.delay(0, None), Duration::from_millis(100));
+    assert_eq!(policy.delay(2, None), Duration::from_millis(400));
+    assert_eq!(policy.delay(10, None), Duration::from_secs(10));
+    assert_eq!(
+        policy.delay(0, Some(Duration::from_secs(3))),
+        Duration::from_secs(3)
+    );
+    assert_eq!(
+        policy.delay(0, Some(Duration::from_secs(60))),
+        Duration::from_secs(10)
+    );
+}
+
+#[tokio::test]
+async fn test_rate_limited_response_carries_retry_after() {
+    start_server(
+        8338,
+        RequestLimits::new().with_rate_limit(1, Duration::from_secs(2)),
+    )
+    .await;
+
+    let http = reqwest::Client::new();
+    let url = "http://127.0.0.1:8338";
+    let first = http
+        .post(url)
+        .json(&get_task_request("missing"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), reqwest::StatusCode::OK);
+    assert!(first.headers().get("retry-after").is_none());
+
+    let refused = http
+        .post(url)
+        .json(&get_task_request("missing"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(refused.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = refused.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=2).contains(&retry_after));
+
+    let body: Value = refused.json().await.unwrap();
+    assert_eq!(body["error"]["code"], SERVER_BUSY);
+    assert_eq!(body["error"]["data"]["errorCode"], "server.busy");
+    assert_eq!(
+        body["error"]["data"]["params"]["retryAfter"],
+        retry_after.to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_overloaded_response_surfaces_the_hint() {
+    start_server(
+        8339,
+        RequestLimits::new()
+            .with_max_concurrent_requests(0)
+            .with_retry_after(Duration::from_secs(3)),
+    )
+    .await;
+
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:8339")
+        .json(&get_task_request("missing"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()["retry-after"], "3");
+
+    // Without a retry policy the client reports the hint straight away
+    let client = HttpClient::new("http://127.0.0.1:8339".to_string());
+    match client.get_task("missing", None).await.unwrap_err() {
+        A2AError::ServerBusy { retry_after, .. } => {
+            assert_eq!(retry_after, Some(Duration::from_secs(3)))
+        }
+        other => panic!("Expected a busy error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_client_waits_the_suggested_delay_before_retrying() {
+    let storage = start_server(
+        8340,
+        RequestLimits::new().with_rate_limit(1, Duration::from_secs(1)),
+    )
+    .await;
+    storage.create_task("expense", "ctx-busy").await.unwrap();
+
+    // The backoff alone would retry far too early
+    let client = HttpClient::new("http://127.0.0.1:8340".to_string())
+        .with_retry_policy(RetryPolicy::new(3).with_base_delay(Duration::from_millis(10)));
+    client.get_task("expense", None).await.unwrap();
+
+    let started = Instant::now();
+    let task = client.get_task("expense", None).await.unwrap();
+    assert_eq!(task.id, "expense");
+    let waited = started.elapsed();
+    assert!(
+        waited >= Duration::from_secs(1),
+        "retried after {:?}",
+        waited
+    );
+    assert!(waited < Duration::from_secs(3), "waited {:?}", waited);
+}
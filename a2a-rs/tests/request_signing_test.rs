@@ -0,0 +1,236 @@
+//! Tests for detached Ed25519 request signatures
+
+#![cfg(feature = "signing")]
+
+mod common;
+
+use a2a_rs::{
+    RequestSigner, RequestVerifier,
+    adapter::auth::{SIGNATURE_HEADER, canonicalize_json},
+};
+use serde_json::json;
+
+const CLIENT_SECRET: [u8; 32] = [7u8; 32];
+const NOW: i64 = 1_700_000_000;
+
+fn signer() -> RequestSigner {
+    RequestSigner::from_bytes("client-1", &CLIENT_SECRET)
+}
+
+fn verifier() -> RequestVerifier {
+    RequestVerifier::new().with_key("client-1", signer().verifying_key())
+}
+
+fn request_body() -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "method": "tasks/get",
+        "params": {"id": "task-1", "historyLength": 5}
+    })
+}
+
+#[test]
+fn test_canonicalization_is_order_and_whitespace_independent() {
+    let a: serde_json::Value =
+        serde_json::from_str(r#"{"b": 1, "a": {"y": [1, {"q": true, "p": null}], "x": "s"}}"#)
+            .unwrap();
+    let b: serde_json::Value =
+        serde_json::from_str(r#"{"a":{"x":"s","y":[1,{"p":null,"q":true}]},"b":1}"#).unwrap();
+
+    assert_eq!(canonicalize_json(&a), canonicalize_json(&b));
+    assert_eq!(
+        canonicalize_json(&a),
+        r#"{"a":{"x":"s","y":[1,{"p":null,"q":true}]},"b":1}"#
+    );
+}
+
+#[test]
+fn test_valid_signature_is_accepted() {
+    let header = signer().sign_with(&request_body(), NOW, "nonce-1");
+
+    let key_id = verifier()
+        .verify_at(&header, &request_body(), NOW + 10)
+        .unwrap();
+    assert_eq!(key_id, "client-1");
+}
+
+#[test]
+fn test_tampered_body_is_rejected() {
+    let header = signer().sign_with(&request_body(), NOW, "nonce-1");
+
+    let mut tampered = request_body();
+    tampered["params"]["id"] = json!("task-2");
+
+    let err = verifier().verify_at(&header, &tampered, NOW).unwrap_err();
+    assert!(err.to_string().contains("does not match"));
+}
+
+#[test]
+fn test_replayed_request_is_rejected() {
+    let verifier = verifier();
+    let header = signer().sign_with(&request_body(), NOW, "nonce-1");
+
+    assert!(verifier.verify_at(&header, &request_body(), NOW).is_ok());
+    let err = verifier
+        .verify_at(&header, &request_body(), NOW + 1)
+        .unwrap_err();
+    assert!(err.to_string().contains("replayed"));
+
+    // Clones share the replay cache
+    let err = verifier
+        .clone()
+        .verify_at(&header, &request_body(), NOW + 2)
+        .unwrap_err();
+    assert!(err.to_string().contains("replayed"));
+}
+
+#[test]
+fn test_stale_timestamp_and_unknown_key_are_rejected() {
+    let header = signer().sign_with(&request_body(), NOW, "nonce-1");
+    let err = verifier()
+        .with_max_skew(60)
+        .verify_at(&header, &request_body(), NOW + 61)
+        .unwrap_err();
+    assert!(err.to_string().contains("timestamp"));
+
+    let other = RequestSigner::from_bytes("client-2", &[9u8; 32]);
+    let header = other.sign_with(&request_body(), NOW, "nonce-2");
+    let err = verifier()
+        .verify_at(&header, &request_body(), NOW)
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown key id"));
+
+    // A known key id with a signature from a different key
+    let forged = RequestSigner::from_bytes("client-1", &[9u8; 32]);
+    let header = forged.sign_with(&request_body(), NOW, "nonce-3");
+    assert!(verifier().verify_at(&header, &request_body(), NOW).is_err());
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_signed_requests_over_http() {
+    use a2a_rs::{
+        HttpClient,
+        adapter::{DefaultRequestProcessor, HttpServer, SimpleAgentInfo},
+        domain::Message,
+        services::AsyncA2AClient,
+    };
+    use common::TestBusinessHandler;
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    let handler = TestBusinessHandler::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Signed Agent".to_string(),
+        "http://localhost:8303".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler, agent_info.clone());
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8303".to_string())
+        .with_request_verifier(verifier());
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server_handle = tokio::spawn(async move {
+        tokio::select! {
+            _ = server.start() => {},
+            _ = shutdown_rx => {}
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let message = Message::user_text("Hello".to_string(), "msg-signed".to_string());
+
+    // Signed client succeeds
+    let client = HttpClient::new("http://localhost:8303".to_string()).with_request_signer(signer());
+    let task = client
+        .send_task_message("signed-task", &message, None, None)
+        .await
+        .unwrap();
+    assert_eq!(task.id, "signed-task");
+
+    // Unsigned client is rejected
+    let unsigned = HttpClient::new("http://localhost:8303".to_string());
+    assert!(unsigned.get_task("signed-task", None).await.is_err());
+
+    // Agent card discovery stays public
+    let http = reqwest::Client::new();
+    let card = http
+        .get("http://localhost:8303/agent-card")
+        .send()
+        .await
+        .unwrap();
+    assert!(card.status().is_success());
+
+    // Replaying a captured request with its signature is rejected
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": "raw-1",
+        "method": "tasks/get",
+        "params": {"id": "signed-task"}
+    });
+    let header = signer().sign(&body);
+    let send = || {
+        http.post("http://localhost:8303")
+            .header("Content-Type", "application/json")
+            .header(SIGNATURE_HEADER, header.clone())
+            // Whitespace differs from the canonical form the client signed
+            .body(serde_json::to_string_pretty(&body).unwrap())
+            .send()
+    };
+    assert_eq!(send().await.unwrap().status(), 200);
+    assert_eq!(send().await.unwrap().status(), 401);
+
+    shutdown_tx.send(()).ok();
+    server_handle.await.ok();
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_signatures_are_verified_after_authentication() {
+    use a2a_rs::adapter::{
+        BearerTokenAuthenticator, DefaultRequestProcessor, HttpServer, SimpleAgentInfo,
+    };
+    use common::TestBusinessHandler;
+    use std::time::Duration;
+
+    let handler = TestBusinessHandler::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Signed Agent".to_string(),
+        "http://localhost:8304".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler, agent_info.clone());
+    let server = HttpServer::with_auth(
+        processor,
+        agent_info,
+        "127.0.0.1:8304".to_string(),
+        BearerTokenAuthenticator::new(vec!["alice".to_string()]),
+    )
+    .with_request_verifier(verifier());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let body = request_body();
+    let header = signer().sign(&body);
+    let http = reqwest::Client::new();
+    let send = |token: Option<&str>| {
+        let mut request = http
+            .post("http://localhost:8304")
+            .header(SIGNATURE_HEADER, header.clone())
+            .json(&body);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send()
+    };
+
+    // Refused before its signature is looked at, so its nonce stays unused
+    let response = send(None).await.unwrap();
+    assert_eq!(response.status(), 401);
+    assert_eq!(send(Some("mallory")).await.unwrap().status(), 401);
+
+    let response = send(Some("alice")).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let response: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(response["id"], "req-1");
+    assert_eq!(send(Some("alice")).await.unwrap().status(), 401);
+}
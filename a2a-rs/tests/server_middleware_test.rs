@@ -0,0 +1,145 @@
+//! Tests for middleware around server-side dispatch
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    application::{
+        JSONRPCResponse,
+        json_rpc::{A2ARequest, GetTaskRequest},
+    },
+    domain::{A2AError, Task, TaskQueryParams, error::INVALID_PARAMS},
+    port::{AsyncTaskManager, AuthPrincipal},
+    services::{AsyncA2ARequestProcessor, MiddlewareChain, RequestMiddleware, ServerRequest},
+};
+use async_trait::async_trait;
+use common::processor;
+
+type Calls = Arc<Mutex<Vec<String>>>;
+
+/// Records when it runs, under a name
+struct Recorder {
+    name: &'static str,
+    calls: Calls,
+}
+
+#[async_trait]
+impl RequestMiddleware for Recorder {
+    async fn before_dispatch(&self, request: &mut ServerRequest) -> Result<(), A2AError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} before {}", self.name, request.method()));
+        Ok(())
+    }
+
+    async fn after_dispatch(
+        &self,
+        _request: &ServerRequest,
+        response: &mut Result<JSONRPCResponse, A2AError>,
+    ) {
+        let outcome = if response.is_ok() { "ok" } else { "err" };
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} after {}", self.name, outcome));
+    }
+}
+
+/// Scopes task ids to the caller's tenant
+struct TenantScope;
+
+#[async_trait]
+impl RequestMiddleware for TenantScope {
+    async fn before_dispatch(&self, request: &mut ServerRequest) -> Result<(), A2AError> {
+        let tenant = request
+            .principal
+            .as_ref()
+            .map(|principal| principal.id.clone())
+            .ok_or_else(|| A2AError::InvalidParams("A tenant is required".to_string()))?;
+        if let A2ARequest::GetTask(get) = &mut request.request {
+            get.params.id = format!("{}/{}", tenant, get.params.id);
+        }
+        Ok(())
+    }
+}
+
+fn get_task(task_id: &str) -> String {
+    let request = A2ARequest::GetTask(GetTaskRequest::new(TaskQueryParams {
+        id: task_id.to_string(),
+        history_length: None,
+        metadata: None,
+        since: None,
+        snapshot: None,
+        exclude: Vec::new(),
+    }));
+    serde_json::to_string(&request).unwrap()
+}
+
+#[tokio::test]
+async fn test_middleware_rewrites_requests_for_the_principal() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("acme/expense", "ctx-1").await.unwrap();
+    let processor = processor(&storage).with_middleware(MiddlewareChain::new().with(TenantScope));
+
+    let acme = AuthPrincipal::new("acme".to_string(), "bearer".to_string());
+    let response = processor
+        .process_raw_request_as(&get_task("expense"), Some(&acme))
+        .await
+        .unwrap();
+    let response: JSONRPCResponse = serde_json::from_str(&response).unwrap();
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(task.id, "acme/expense");
+
+    // Rejected before dispatch when no principal is known
+    let response = processor
+        .process_raw_request(&get_task("expense"))
+        .await
+        .unwrap();
+    let response: JSONRPCResponse = serde_json::from_str(&response).unwrap();
+    assert_eq!(response.error.unwrap().code, INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_middleware_runs_in_order_and_stops_at_rejection() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    let calls = Calls::default();
+    let processor = processor(&storage).with_middleware(
+        MiddlewareChain::new()
+            .with(Recorder {
+                name: "outer",
+                calls: calls.clone(),
+            })
+            .with(TenantScope)
+            .with(Recorder {
+                name: "inner",
+                calls: calls.clone(),
+            }),
+    );
+
+    let acme = AuthPrincipal::new("acme".to_string(), "bearer".to_string());
+    processor
+        .process_raw_request_as(&get_task("missing"), Some(&acme))
+        .await
+        .unwrap();
+    processor
+        .process_raw_request(&get_task("expense"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "outer before tasks/get",
+            "inner before tasks/get",
+            "inner after err",
+            "outer after err",
+            // The tenant check rejects the anonymous request before `inner`
+            "outer before tasks/get",
+            "outer after err",
+        ]
+    );
+}
@@ -0,0 +1,201 @@
+//! Tests for routing messages to the skills an agent advertises
+
+mod common;
+
+use a2a_rs::{
+    adapter::{InMemoryTaskStorage, SimpleAgentInfo, SkillRouter},
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{
+        A2AError, AgentSkill, Message, Part, Task, TaskSendParams, TaskState, error_catalog::codes,
+        validation::content::SKILL_ID_KEY,
+    },
+    port::AsyncMessageHandler,
+    services::{AgentInfoProvider, AsyncA2ARequestProcessor},
+};
+use async_trait::async_trait;
+use common::Processor;
+use serde_json::json;
+
+/// Completes every task with a reply naming its skill
+struct SkillHandler(&'static str);
+
+#[async_trait]
+impl AsyncMessageHandler for SkillHandler {
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        let mut task = Task::new(
+            task_id.to_string(),
+            session_id.unwrap_or("default").to_string(),
+        );
+        let reply = Message::agent_text(
+            format!("{} handled {}", self.0, message.message_id),
+            "reply".to_string(),
+        );
+        task.update_status(TaskState::Completed, Some(reply));
+        Ok(task)
+    }
+}
+
+fn text_of(message: &Message) -> String {
+    message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Text { text, .. } => Some(text.to_lowercase()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn router() -> SkillRouter {
+    SkillRouter::new()
+        .with_matched_skill(
+            AgentSkill::new(
+                "submit_expense".to_string(),
+                "Submit expense".to_string(),
+                "Files an expense for reimbursement".to_string(),
+                vec!["expenses".to_string()],
+            )
+            .with_examples(vec!["Reimburse my $20 taxi ride".to_string()]),
+            |message| text_of(message).contains("reimburse"),
+            SkillHandler("submit_expense"),
+        )
+        .with_matched_skill(
+            AgentSkill::new(
+                "book_travel".to_string(),
+                "Book travel".to_string(),
+                "Books flights and hotels".to_string(),
+                vec!["travel".to_string()],
+            ),
+            |message| {
+                let text = text_of(message);
+                text.contains("flight") || text.contains("hotel")
+            },
+            SkillHandler("book_travel"),
+        )
+}
+
+fn processor(router: SkillRouter) -> Processor<SkillRouter> {
+    let agent_info = common::agent_info().with_skills(router.skills());
+    common::processor_with(router, &InMemoryTaskStorage::new(), agent_info)
+}
+
+fn message(id: &str, text: &str, skill_id: Option<&str>) -> Message {
+    let mut message = Message::user_text(text.to_string(), id.to_string());
+    if let Some(skill_id) = skill_id {
+        message.metadata = json!({ SKILL_ID_KEY: skill_id }).as_object().cloned();
+    }
+    message
+}
+
+async fn send(processor: &Processor<SkillRouter>, message: Message) -> Result<String, A2AError> {
+    let request = A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+        id: format!("task-{}", message.message_id),
+        session_id: None,
+        message,
+        push_notification: None,
+        history_length: None,
+        metadata: None,
+        expected_version: None,
+    }));
+    let response = processor.process_request(&request).await?;
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    Ok(text_of(&task.status.message.unwrap()))
+}
+
+#[tokio::test]
+async fn test_messages_are_routed_to_the_matching_skill() {
+    let processor = processor(router());
+
+    let reply = send(
+        &processor,
+        message("m-1", "Please reimburse my taxi ride", None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(reply, "submit_expense handled m-1");
+
+    let reply = send(
+        &processor,
+        message("m-2", "I need a flight to Jakarta", None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(reply, "book_travel handled m-2");
+}
+
+#[tokio::test]
+async fn test_explicit_skill_id_wins_over_matchers() {
+    let processor = processor(router());
+
+    // The text matches the expense skill, but the client picked travel
+    let reply = send(
+        &processor,
+        message("m-1", "Reimburse the hotel deposit", Some("book_travel")),
+    )
+    .await
+    .unwrap();
+    assert_eq!(reply, "book_travel handled m-1");
+
+    let router = router();
+    let picked = message("m-2", "anything at all", Some("submit_expense"));
+    assert_eq!(router.route(&picked).unwrap().id, "submit_expense");
+}
+
+#[tokio::test]
+async fn test_unmatched_messages_get_a_clear_error() {
+    let processor = processor(router());
+
+    let error = send(&processor, message("m-1", "What's the weather?", None))
+        .await
+        .unwrap_err();
+    assert_eq!(error.error_detail().error_code, codes::SKILL_NO_MATCH);
+    assert_eq!(
+        error.to_string(),
+        "None of the agent's skills can handle this message"
+    );
+
+    let error = send(
+        &processor,
+        message("m-2", "Reimburse my taxi ride", Some("order_lunch")),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.error_detail().error_code, codes::SKILL_NOT_FOUND);
+    assert_eq!(error.error_detail().params["skillId"], "order_lunch");
+}
+
+#[tokio::test]
+async fn test_registered_skills_are_advertised_in_the_agent_card() {
+    let router = router().with_skill(
+        AgentSkill::new(
+            "book_travel".to_string(),
+            "Book travel".to_string(),
+            "Books flights, hotels and trains".to_string(),
+            vec!["travel".to_string()],
+        ),
+        SkillHandler("book_travel_v2"),
+    );
+    let agent_info =
+        SimpleAgentInfo::new("Office Agent".to_string(), "http://localhost".to_string())
+            .with_skills(router.skills());
+
+    let card = agent_info.get_agent_card().await.unwrap();
+    let ids: Vec<_> = card.skills.iter().map(|skill| skill.id.as_str()).collect();
+    assert_eq!(ids, vec!["submit_expense", "book_travel"]);
+    assert_eq!(
+        card.skills[1].description,
+        "Books flights, hotels and trains"
+    );
+
+    // The replacement is reached by id only
+    let travel = message("m-1", "Book a flight", None);
+    assert!(router.route(&travel).is_err());
+    let travel = message("m-1", "Book a flight", Some("book_travel"));
+    assert_eq!(router.route(&travel).unwrap().id, "book_travel");
+}
@@ -598,6 +598,7 @@ fn test_artifact_extensions_field() {
         extensions: Some(vec![
             "https://example.com/extensions/artifact-encryption".to_string(),
         ]),
+        created_at: None,
     };
 
     // Serialize and verify
@@ -740,6 +741,8 @@ fn test_list_tasks_params() {
         history_length: Some(10),
         include_artifacts: Some(true),
         last_updated_after: Some(1704067200000), // 2024-01-01 00:00:00 UTC
+        tags: None,
+        tag_match: None,
         metadata: None,
     };
 
@@ -975,7 +978,7 @@ async fn test_task_list_page_size_validation() {
         "page_size > 100 should be clamped, not error"
     );
 
-    // Test page_size < 1 (should clamp to 1, not error)
+    // Test page_size < 1 (should use the default page size, not error)
     let request = json_rpc::ListTasksRequest::new(Some(a2a_rs::domain::ListTasksParams {
         page_size: Some(0),
         ..Default::default()
@@ -989,7 +992,7 @@ async fn test_task_list_page_size_validation() {
     // According to spec, page_size should be clamped, not return error
     assert!(
         response.error.is_none(),
-        "page_size < 1 should be clamped, not error"
+        "page_size < 1 should use the default, not error"
     );
 
     shutdown_tx.send(()).ok();
@@ -1099,7 +1102,7 @@ fn test_error_code_ranges() {
 
     for code in jsonrpc_codes {
         assert!(
-            code >= -32700 && code <= -32600,
+            (-32700..=-32600).contains(&code),
             "JSON-RPC error code {} should be in range -32700 to -32600",
             code
         );
@@ -1110,7 +1113,7 @@ fn test_error_code_ranges() {
 
     for code in a2a_codes {
         assert!(
-            code >= -32007 && code <= -32001,
+            (-32007..=-32001).contains(&code),
             "A2A error code {} should be in range -32007 to -32001",
             code
         );
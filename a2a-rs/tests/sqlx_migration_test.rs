@@ -0,0 +1,165 @@
+//! Tests for the versioned SQLx schema migrations
+
+#![cfg(feature = "sqlx-storage")]
+
+use a2a_rs::{
+    adapter::storage::{
+        SqlxTaskStorage,
+        migrations::{APPLICATION_SCOPE, BASE_SCOPE, SCHEMA_VERSION, migrate, schema_version},
+    },
+    domain::A2AError,
+    port::AsyncTaskManager,
+};
+use sqlx::{Row, SqlitePool};
+
+async fn columns(pool: &SqlitePool, table: &str) -> Vec<String> {
+    sqlx::query("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get("name"))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_fresh_database_gets_the_full_schema() {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    assert_eq!(schema_version(&pool).await.unwrap(), 0);
+
+    let applied = migrate(&pool, &[]).await.unwrap();
+    let versions: Vec<i64> = applied.iter().map(|m| m.version).collect();
+    assert_eq!(versions, (1..=SCHEMA_VERSION).collect::<Vec<_>>());
+    assert!(applied.iter().all(|m| m.scope == BASE_SCOPE));
+    assert_eq!(schema_version(&pool).await.unwrap(), SCHEMA_VERSION);
+
+    for (table, expected) in [
+        (
+            "tasks",
+            &[
+                "id",
+                "context_id",
+                "status_state",
+                "status_message",
+                "metadata",
+            ][..],
+        ),
+        (
+            "task_history",
+            &["task_id", "timestamp", "status_state", "message"],
+        ),
+        (
+            "push_notification_configs",
+            &["id", "task_id", "url", "token", "authentication"],
+        ),
+        ("task_events", &["task_id", "sequence"]),
+        ("task_results", &["task_id"]),
+        ("task_versions", &["task_id"]),
+        ("task_tags", &["task_id", "tag"]),
+        ("task_references", &["task_id", "referenced_task_id"]),
+        (
+            "schema_migrations",
+            &["scope", "version", "name", "applied_at"],
+        ),
+    ] {
+        let found = columns(&pool, table).await;
+        for column in expected {
+            assert!(
+                found.iter().any(|name| name == column),
+                "{}.{} missing, found {:?}",
+                table,
+                column,
+                found
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_migrating_again_keeps_existing_data() {
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    sqlx::query("INSERT INTO push_notification_configs (id, task_id, url) VALUES (?, ?, ?)")
+        .bind("config-1")
+        .bind("expense")
+        .bind("https://example.com/hook")
+        .execute(storage.pool())
+        .await
+        .unwrap();
+
+    // Restarting used to rerun migration 002, which drops push configs
+    assert!(migrate(storage.pool(), &[]).await.unwrap().is_empty());
+    let configs: i64 = sqlx::query("SELECT COUNT(*) AS count FROM push_notification_configs")
+        .fetch_one(storage.pool())
+        .await
+        .unwrap()
+        .get("count");
+    assert_eq!(configs, 1);
+    assert_eq!(
+        storage.get_task("expense", None).await.unwrap().id,
+        "expense"
+    );
+}
+
+#[tokio::test]
+async fn test_additional_migrations_are_applied_once_in_order() {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    let first = "CREATE TABLE receipts (id TEXT PRIMARY KEY)";
+    let second = "ALTER TABLE receipts ADD COLUMN amount REAL";
+
+    let applied = migrate(&pool, &[first]).await.unwrap();
+    let application: Vec<_> = applied
+        .iter()
+        .filter(|m| m.scope == APPLICATION_SCOPE)
+        .map(|m| m.version)
+        .collect();
+    assert_eq!(application, vec![1]);
+
+    // A non-idempotent statement would fail if it ran a second time
+    let applied = migrate(&pool, &[first, second]).await.unwrap();
+    assert_eq!(applied.len(), 1);
+    assert_eq!(applied[0].scope, APPLICATION_SCOPE);
+    assert_eq!(applied[0].version, 2);
+    assert_eq!(columns(&pool, "receipts").await, vec!["id", "amount"]);
+    assert!(migrate(&pool, &[first, second]).await.unwrap().is_empty());
+
+    // Dropping migrations the database already has is a downgrade
+    assert!(matches!(
+        migrate(&pool, &[first]).await,
+        Err(A2AError::DatabaseError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_failed_migration_is_rolled_back_and_not_recorded() {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    let broken = "CREATE TABLE receipts (id TEXT PRIMARY KEY); INSERT INTO missing VALUES (1)";
+
+    assert!(matches!(
+        migrate(&pool, &[broken]).await,
+        Err(A2AError::DatabaseError(_))
+    ));
+    assert!(columns(&pool, "receipts").await.is_empty());
+
+    let fixed = "CREATE TABLE receipts (id TEXT PRIMARY KEY)";
+    let applied = migrate(&pool, &[fixed]).await.unwrap();
+    assert_eq!(applied.len(), 1);
+}
+
+#[tokio::test]
+async fn test_newer_schema_is_refused() {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    migrate(&pool, &[]).await.unwrap();
+    sqlx::query("INSERT INTO schema_migrations (scope, version, name) VALUES (?, ?, ?)")
+        .bind(BASE_SCOPE)
+        .bind(SCHEMA_VERSION + 1)
+        .bind("from_the_future")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let error = migrate(&pool, &[]).await.unwrap_err();
+    assert!(matches!(error, A2AError::DatabaseError(_)), "{}", error);
+    assert!(error.to_string().contains("newer"), "{}", error);
+}
@@ -0,0 +1,53 @@
+//! Tests for SQLx connection pool settings
+
+#![cfg(feature = "sqlx-storage")]
+
+use std::time::{Duration, Instant};
+
+use a2a_rs::{
+    adapter::storage::{DatabaseConfig, SqlxTaskStorage},
+    domain::{A2AError, error::UNAVAILABLE},
+    port::AsyncTaskManager,
+};
+
+#[tokio::test]
+async fn test_exhausted_pool_fails_with_a_retryable_error() {
+    let config = DatabaseConfig::builder()
+        .url("sqlite::memory:".to_string())
+        .max_connections(1)
+        .timeout_seconds(1)
+        .build();
+    let storage = SqlxTaskStorage::with_config(&config, &[]).await.unwrap();
+    storage.create_task("expense", "ctx-1").await.unwrap();
+
+    // Hold the only connection
+    let held = storage.pool().acquire().await.unwrap();
+
+    let started = Instant::now();
+    let error = tokio::time::timeout(Duration::from_secs(5), storage.get_task("expense", None))
+        .await
+        .expect("request hung on the exhausted pool")
+        .unwrap_err();
+    assert!(matches!(error, A2AError::Unavailable(_)), "{}", error);
+    assert!(error.is_retryable());
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert_eq!(error.to_jsonrpc_error()["code"], UNAVAILABLE);
+
+    // Once the connection is back, requests succeed again
+    drop(held);
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(task.id, "expense");
+}
+
+#[tokio::test]
+async fn test_invalid_pool_settings_are_rejected() {
+    let config = DatabaseConfig::builder()
+        .url("sqlite::memory:".to_string())
+        .max_connections(2)
+        .min_connections(3)
+        .build();
+    assert!(matches!(
+        SqlxTaskStorage::with_config(&config, &[]).await,
+        Err(A2AError::DatabaseError(_))
+    ));
+}
@@ -3,7 +3,7 @@
 #[cfg(feature = "sqlx-storage")]
 mod sqlx_tests {
     use a2a_rs::adapter::storage::{DatabaseConfig, SqlxTaskStorage};
-    use a2a_rs::domain::TaskState;
+    use a2a_rs::domain::{TaskCancellation, TaskState};
     use a2a_rs::port::{AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager};
     use a2a_rs::{A2AError, PushNotificationConfig, TaskPushNotificationConfig};
     use std::sync::Arc;
@@ -81,6 +81,31 @@ mod sqlx_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_cancellation_details_are_persisted() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = create_test_storage().await?;
+        let task_id = Uuid::new_v4().to_string();
+
+        storage.create_task(&task_id, "test-context").await?;
+        storage
+            .update_task_status(&task_id, TaskState::Working, None)
+            .await?;
+
+        let cancellation = TaskCancellation::new()
+            .with_canceled_by("alice".to_string())
+            .with_reason("duplicate submission".to_string());
+        let canceled_task = storage
+            .cancel_task_with(&task_id, &cancellation, None)
+            .await?;
+        assert_eq!(canceled_task.cancellation(), Some(cancellation.clone()));
+
+        let stored_task = storage.get_task(&task_id, None).await?;
+        assert_eq!(stored_task.status.state, TaskState::Canceled);
+        assert_eq!(stored_task.cancellation(), Some(cancellation));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cannot_cancel_completed_task() -> Result<(), Box<dyn std::error::Error>> {
         let storage = create_test_storage().await?;
@@ -331,6 +356,35 @@ mod sqlx_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_list_tasks_v3_page_limits() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = create_test_storage()
+            .await?
+            .with_page_limits(a2a_rs::domain::PageSizeLimits::new(2, 3));
+        for i in 0..5 {
+            storage
+                .create_task(&format!("task-{}", i), "limits-context")
+                .await?;
+        }
+
+        let page = |page_size| a2a_rs::domain::ListTasksParams {
+            page_size,
+            ..Default::default()
+        };
+
+        let result = storage.list_tasks_v3(&page(None)).await?;
+        assert_eq!((result.page_size, result.tasks.len()), (2, 2));
+
+        let result = storage.list_tasks_v3(&page(Some(0))).await?;
+        assert_eq!((result.page_size, result.tasks.len()), (2, 2));
+
+        let result = storage.list_tasks_v3(&page(Some(10_000))).await?;
+        assert_eq!((result.page_size, result.tasks.len()), (3, 3));
+        assert_eq!(result.next_page_token, "3");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_list_tasks_v3_filtering() -> Result<(), Box<dyn std::error::Error>> {
         let storage = create_test_storage().await?;
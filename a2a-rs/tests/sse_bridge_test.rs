@@ -0,0 +1,317 @@
+//! Tests for the SSE bridge to WebSocket-only agents
+
+#![cfg(all(feature = "http-server", feature = "ws-client", feature = "ws-server"))]
+
+mod common;
+
+use std::time::Duration;
+
+use a2a_rs::{
+    adapter::{
+        BearerTokenAuthenticator, DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo,
+        SseBridge, WebSocketServer,
+    },
+    domain::{Message, TaskState, TaskStatus, TaskStatusUpdateEvent},
+    port::{AsyncStreamingHandler, AsyncTaskManager},
+};
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{accept_async, tungstenite::Message as WsMessage};
+
+async fn wait_until_listening(address: &str) {
+    for _ in 0..50 {
+        if TcpStream::connect(address).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Nothing listening on {}", address);
+}
+
+/// Reads server-sent events from a streaming response
+struct SseReader {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl SseReader {
+    fn new(response: reqwest::Response) -> Self {
+        Self {
+            response,
+            buffer: String::new(),
+        }
+    }
+
+    /// The next event's type and data, skipping keep-alive comments
+    async fn next_event(&mut self) -> Option<(String, Value)> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let block: String = self.buffer.drain(..end + 2).collect();
+                let mut event = None;
+                let mut data = String::new();
+                for line in block.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        event = Some(value.trim().to_string());
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data.push_str(value.trim());
+                    }
+                }
+                if let Some(event) = event {
+                    return Some((event, serde_json::from_str(&data).unwrap()));
+                }
+                continue;
+            }
+            let chunk = self.response.chunk().await.ok()??;
+            self.buffer.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_sse_consumer_receives_websocket_updates() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("bridged", "ctx-bridge").await.unwrap();
+
+    // The agent itself only speaks WebSocket
+    let handler = common::TestBusinessHandler::with_storage(storage.clone());
+    let agent_info = SimpleAgentInfo::new(
+        "WebSocket Agent".to_string(),
+        "ws://127.0.0.1:8329".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let server = WebSocketServer::with_auth(
+        processor,
+        agent_info,
+        handler,
+        "127.0.0.1:8329".to_string(),
+        BearerTokenAuthenticator::new(vec!["bridge-token".to_string()]),
+    );
+    tokio::spawn(async move { server.start().await });
+    wait_until_listening("127.0.0.1:8329").await;
+
+    let bridge = SseBridge::new("ws://127.0.0.1:8329".to_string()).with_history_length(10);
+    tokio::spawn(async move { bridge.start("127.0.0.1:8330").await });
+    wait_until_listening("127.0.0.1:8330").await;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get("http://127.0.0.1:8330/tasks/bridged/stream")
+        .bearer_auth("bridge-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/event-stream",
+        "{:?}",
+        response.headers()
+    );
+    let mut events = SseReader::new(response);
+
+    let (event, task) = events.next_event().await.unwrap();
+    assert_eq!(event, "task-update");
+    assert_eq!(task["id"], "bridged");
+
+    let message = Message::agent_text("Receipt approved".to_string(), "msg-bridge".to_string());
+    storage
+        .update_task_status("bridged", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+
+    let update = tokio::time::timeout(Duration::from_secs(3), async {
+        loop {
+            let (event, data) = events.next_event().await.expect("stream ended");
+            if event == "task-status" && data["status"]["state"] == "working" {
+                return data;
+            }
+        }
+    })
+    .await
+    .expect("status update was not bridged");
+    assert_eq!(update["taskId"], "bridged");
+
+    // The agent still decides who may subscribe
+    let response = client
+        .get("http://127.0.0.1:8330/tasks/bridged/stream")
+        .send()
+        .await
+        .unwrap();
+    let (event, error) = SseReader::new(response).next_event().await.unwrap();
+    assert_eq!(event, "error");
+    assert!(error["code"].is_i64(), "{}", error);
+}
+
+#[tokio::test]
+async fn test_upstream_socket_closes_with_sse_client() {
+    // A WebSocket source sending a status update every 50ms until the bridge
+    // hangs up
+    let listener = TcpListener::bind("127.0.0.1:8331").await.unwrap();
+    let (closed_tx, closed_rx) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let socket = accept_async(stream).await.unwrap();
+        let (mut sender, mut receiver) = socket.split();
+        let Some(Ok(WsMessage::Text(request))) = receiver.next().await else {
+            panic!("Expected a subscription request");
+        };
+        let request: Value = serde_json::from_str(&request).unwrap();
+        assert_eq!(request["method"], "tasks/resubscribe");
+
+        let update = TaskStatusUpdateEvent {
+            task_id: "remote".to_string(),
+            context_id: "ctx-remote".to_string(),
+            kind: "status-update".to_string(),
+            status: TaskStatus {
+                state: TaskState::Working,
+                message: None,
+                timestamp: None,
+            },
+            final_: false,
+            result: None,
+            artifacts: None,
+            metadata: None,
+            task: None,
+            diff: None,
+        };
+        let frame = json!({"jsonrpc": "2.0", "id": request["id"], "result": update}).to_string();
+        let mut ticks = tokio::time::interval(Duration::from_millis(50));
+        loop {
+            tokio::select! {
+                _ = ticks.tick() => {
+                    if sender.send(WsMessage::Text(frame.clone())).await.is_err() {
+                        break;
+                    }
+                }
+                message = receiver.next() => {
+                    if !matches!(message, Some(Ok(WsMessage::Text(_)))) {
+                        break;
+                    }
+                }
+            }
+        }
+        let _ = closed_tx.send(());
+    });
+
+    let bridge = SseBridge::new("ws://127.0.0.1:8331".to_string())
+        .with_keep_alive(Duration::from_millis(100));
+    tokio::spawn(async move { bridge.start("127.0.0.1:8332").await });
+    wait_until_listening("127.0.0.1:8332").await;
+
+    let response = reqwest::get("http://127.0.0.1:8332/tasks/remote/stream")
+        .await
+        .unwrap();
+    let mut events = SseReader::new(response);
+    let (event, update) = events.next_event().await.unwrap();
+    assert_eq!(event, "task-status");
+    assert_eq!(update["taskId"], "remote");
+
+    drop(events);
+    tokio::time::timeout(Duration::from_secs(3), closed_rx)
+        .await
+        .expect("upstream WebSocket outlived the SSE client")
+        .unwrap();
+}
+
+/// Open an SSE subscription to `task_id` through the bridge at `address`
+async fn subscribe(address: &str, task_id: &str, token: &str) -> SseReader {
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/tasks/{}/stream", address, task_id))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    SseReader::new(response)
+}
+
+/// The next `task-status` event for `state`
+async fn status_event(events: &mut SseReader, state: &str) -> Value {
+    tokio::time::timeout(Duration::from_secs(3), async {
+        loop {
+            let (event, data) = events.next_event().await.expect("stream ended");
+            if event == "task-status" && data["status"]["state"] == state {
+                return data;
+            }
+        }
+    })
+    .await
+    .expect("status update was not bridged")
+}
+
+#[tokio::test]
+async fn test_subscribers_to_one_task_share_one_upstream_subscription() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("shared", "ctx-shared").await.unwrap();
+
+    let handler = common::TestBusinessHandler::with_storage(storage.clone());
+    let agent_info = SimpleAgentInfo::new(
+        "WebSocket Agent".to_string(),
+        "ws://127.0.0.1:8357".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let server = WebSocketServer::with_auth(
+        processor,
+        agent_info,
+        handler,
+        "127.0.0.1:8357".to_string(),
+        BearerTokenAuthenticator::new(vec!["tab-token".to_string(), "other-token".to_string()]),
+    );
+    tokio::spawn(async move { server.start().await });
+    wait_until_listening("127.0.0.1:8357").await;
+
+    let bridge = SseBridge::new("ws://127.0.0.1:8357".to_string())
+        .with_keep_alive(Duration::from_millis(100));
+    let served = bridge.clone();
+    tokio::spawn(async move { served.start("127.0.0.1:8358").await });
+    wait_until_listening("127.0.0.1:8358").await;
+
+    // Two browser tabs watching the same task
+    let mut first = subscribe("127.0.0.1:8358", "shared", "tab-token").await;
+    let (event, _) = first.next_event().await.unwrap();
+    assert_eq!(event, "task-update");
+    let mut second = subscribe("127.0.0.1:8358", "shared", "tab-token").await;
+    let (event, task) = second.next_event().await.unwrap();
+    assert_eq!(event, "task-update");
+    assert_eq!(task["id"], "shared");
+
+    // One upstream subscription: a status and an artifact subscriber
+    assert_eq!(bridge.shared_subscriptions(), 1);
+    assert_eq!(storage.get_subscriber_count("shared").await.unwrap(), 2);
+
+    let message = Message::agent_text("Receipt approved".to_string(), "msg-shared".to_string());
+    storage
+        .update_task_status("shared", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+    let seen_by_first = status_event(&mut first, "working").await;
+    let seen_by_second = status_event(&mut second, "working").await;
+    assert_eq!(seen_by_first, seen_by_second);
+
+    // A late subscriber starts from the task as it is now
+    let mut late = subscribe("127.0.0.1:8358", "shared", "tab-token").await;
+    let (event, task) = late.next_event().await.unwrap();
+    assert_eq!(event, "task-update");
+    assert_eq!(task["status"]["state"], "working");
+    let history = task["history"].as_array().unwrap();
+    assert_eq!(history.last().unwrap()["messageId"], "msg-shared");
+    assert_eq!(bridge.shared_subscriptions(), 1);
+
+    // Other credentials get their own upstream subscription
+    let mut other = subscribe("127.0.0.1:8358", "shared", "other-token").await;
+    let (event, _) = other.next_event().await.unwrap();
+    assert_eq!(event, "task-update");
+    assert_eq!(bridge.shared_subscriptions(), 2);
+
+    // The upstream subscription closes with its last subscriber
+    drop(first);
+    drop(second);
+    drop(late);
+    tokio::time::timeout(Duration::from_secs(3), async {
+        while bridge.shared_subscriptions() > 1 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("shared subscription outlived its subscribers");
+    drop(other);
+}
@@ -0,0 +1,323 @@
+//! Tests for classifying storage failures and answering them over HTTP
+
+#![cfg(all(feature = "http-server", feature = "http-client"))]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use a2a_rs::{
+    HttpClient,
+    adapter::{
+        DefaultRequestProcessor, HttpServer, InMemoryTaskStorage, SimpleAgentInfo,
+        business::DefaultMessageHandler,
+    },
+    domain::{
+        A2AError, Message, StorageFailure, Task, TaskState,
+        error::{STORAGE_CONFLICT, STORAGE_CORRUPT, UNAVAILABLE},
+    },
+    port::AsyncTaskManager,
+    services::AsyncA2AClient,
+};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+
+/// Task storage that fails every call with the configured failure
+#[derive(Clone, Default)]
+struct FlakyStorage {
+    inner: InMemoryTaskStorage,
+    failure: Arc<Mutex<Option<StorageFailure>>>,
+}
+
+impl FlakyStorage {
+    fn fail_with(&self, failure: Option<StorageFailure>) {
+        *self.failure.lock().unwrap() = failure;
+    }
+
+    fn check(&self) -> Result<(), A2AError> {
+        match *self.failure.lock().unwrap() {
+            Some(StorageFailure::Unavailable) => Err(A2AError::Unavailable(
+                "connection refused by 10.0.0.3".to_string(),
+            )),
+            Some(StorageFailure::Corrupt) => Err(A2AError::StorageCorrupt(
+                "Failed to parse metadata: expected value".to_string(),
+            )),
+            Some(StorageFailure::Conflict) => Err(A2AError::StorageConflict(
+                "UNIQUE constraint failed: tasks.id".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncTaskManager for FlakyStorage {
+    async fn create_task<'a>(
+        &self,
+        task_id: &'a str,
+        context_id: &'a str,
+    ) -> Result<Task, A2AError> {
+        self.check()?;
+        self.inner.create_task(task_id, context_id).await
+    }
+
+    async fn get_task<'a>(
+        &self,
+        task_id: &'a str,
+        history_length: Option<u32>,
+    ) -> Result<Task, A2AError> {
+        self.check()?;
+        self.inner.get_task(task_id, history_length).await
+    }
+
+    async fn update_task_status<'a>(
+        &self,
+        task_id: &'a str,
+        state: TaskState,
+        message: Option<Message>,
+    ) -> Result<Task, A2AError> {
+        self.check()?;
+        self.inner.update_task_status(task_id, state, message).await
+    }
+
+    async fn cancel_task<'a>(&self, task_id: &'a str) -> Result<Task, A2AError> {
+        self.check()?;
+        self.inner.cancel_task(task_id).await
+    }
+
+    async fn task_exists<'a>(&self, task_id: &'a str) -> Result<bool, A2AError> {
+        self.check()?;
+        self.inner.task_exists(task_id).await
+    }
+
+    async fn check_health(&self) -> Result<(), A2AError> {
+        self.check()
+    }
+}
+
+/// Serve `storage` on `address`, asking clients to wait seven seconds
+/// during outages
+async fn serve(storage: &FlakyStorage, address: &str) {
+    let agent_info =
+        SimpleAgentInfo::new("Expense Agent".to_string(), format!("http://{}", address));
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.inner.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, address.to_string())
+        .with_unavailable_retry_after(Duration::from_secs(7));
+    tokio::spawn(async move { server.start().await });
+    for _ in 0..50 {
+        if TcpStream::connect(address).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("{} never became reachable", address);
+}
+
+/// Post `tasks/get` for `expense`, returning the status, `Retry-After` and body
+async fn get_expense(address: &str) -> (u16, Option<String>, Value) {
+    let request =
+        json!({"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": {"id": "expense"}});
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/", address))
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+    read(response).await
+}
+
+async fn probe(address: &str) -> (u16, Option<String>, Value) {
+    let response = reqwest::get(format!("http://{}/ready", address))
+        .await
+        .unwrap();
+    read(response).await
+}
+
+async fn read(response: reqwest::Response) -> (u16, Option<String>, Value) {
+    let status = response.status().as_u16();
+    let retry_after = response
+        .headers()
+        .get("retry-after")
+        .map(|value| value.to_str().unwrap().to_string());
+    (status, retry_after, response.json().await.unwrap())
+}
+
+#[test]
+fn test_storage_errors_are_classified() {
+    let cases = [
+        (
+            A2AError::Unavailable("pool closed".to_string()),
+            StorageFailure::Unavailable,
+            UNAVAILABLE,
+            "server.unavailable",
+        ),
+        (
+            A2AError::StorageCorrupt("bad row".to_string()),
+            StorageFailure::Corrupt,
+            STORAGE_CORRUPT,
+            "storage.corrupt",
+        ),
+        (
+            A2AError::StorageConflict("duplicate key".to_string()),
+            StorageFailure::Conflict,
+            STORAGE_CONFLICT,
+            "storage.conflict",
+        ),
+    ];
+    for (error, failure, code, error_code) in cases {
+        assert_eq!(error.storage_failure(), Some(failure));
+        assert_eq!(error.is_retryable(), failure.is_retryable());
+        let value = error.to_jsonrpc_error();
+        assert_eq!(value["code"], code);
+        assert_eq!(value["data"]["errorCode"], error_code);
+        // Internal details stay out of the error sent to clients
+        assert!(value["data"].get("params").is_none(), "{}", value);
+
+        // Errors that crossed the wire keep their class
+        let received = A2AError::JsonRpc {
+            code,
+            message: value["message"].as_str().unwrap().to_string(),
+            data: Some(value["data"].clone()),
+        };
+        assert_eq!(received.storage_failure(), Some(failure));
+    }
+    assert!(StorageFailure::Unavailable.is_retryable());
+    assert!(!StorageFailure::Corrupt.is_retryable());
+    assert!(!StorageFailure::Conflict.is_retryable());
+
+    assert_eq!(
+        A2AError::DatabaseError("syntax error".to_string()).storage_failure(),
+        None
+    );
+    assert_eq!(
+        A2AError::TaskNotFound("expense".to_string()).storage_failure(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_unavailable_storage_answers_503_with_retry_after() {
+    let address = "127.0.0.1:8376";
+    let storage = FlakyStorage::default();
+    storage.create_task("expense", "ctx").await.unwrap();
+    serve(&storage, address).await;
+
+    let (status, _, body) = probe(address).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "ready");
+
+    storage.fail_with(Some(StorageFailure::Unavailable));
+    let (status, retry_after, body) = get_expense(address).await;
+    assert_eq!(status, 503);
+    assert_eq!(retry_after.as_deref(), Some("7"));
+    assert_eq!(body["id"], 1);
+    assert_eq!(body["error"]["code"], UNAVAILABLE);
+    assert_eq!(body["error"]["data"]["errorCode"], "server.unavailable");
+
+    let (status, retry_after, body) = probe(address).await;
+    assert_eq!(status, 503);
+    assert_eq!(retry_after.as_deref(), Some("7"));
+    assert_eq!(body["status"], "unavailable");
+    assert_eq!(body["error"]["errorCode"], "server.unavailable");
+
+    // The client sees a retryable refusal carrying the hint
+    let client = HttpClient::new(format!("http://{}", address));
+    let error = client.get_task("expense", None).await.unwrap_err();
+    assert!(error.is_retryable(), "{}", error);
+    assert!(matches!(
+        error,
+        A2AError::ServerBusy {
+            retry_after: Some(delay),
+            ..
+        } if delay == Duration::from_secs(7)
+    ));
+
+    // Once the storage is back, all is well again
+    storage.fail_with(None);
+    let (status, retry_after, body) = get_expense(address).await;
+    assert_eq!(status, 200);
+    assert!(retry_after.is_none());
+    assert_eq!(body["result"]["id"], "expense");
+    assert_eq!(probe(address).await.0, 200);
+}
+
+#[tokio::test]
+async fn test_conflicting_and_corrupt_storage_map_to_their_statuses() {
+    let address = "127.0.0.1:8377";
+    let storage = FlakyStorage::default();
+    storage.create_task("expense", "ctx").await.unwrap();
+    serve(&storage, address).await;
+    let client = HttpClient::new(format!("http://{}", address));
+
+    storage.fail_with(Some(StorageFailure::Conflict));
+    let (status, retry_after, body) = get_expense(address).await;
+    assert_eq!(status, 409);
+    assert!(retry_after.is_none());
+    assert_eq!(body["error"]["code"], STORAGE_CONFLICT);
+    assert_eq!(body["error"]["data"]["errorCode"], "storage.conflict");
+
+    let error = client.get_task("expense", None).await.unwrap_err();
+    assert_eq!(error.storage_failure(), Some(StorageFailure::Conflict));
+    assert!(!error.is_retryable());
+
+    storage.fail_with(Some(StorageFailure::Corrupt));
+    let (status, retry_after, body) = get_expense(address).await;
+    assert_eq!(status, 500);
+    assert!(retry_after.is_none());
+    assert_eq!(body["error"]["code"], STORAGE_CORRUPT);
+    assert!(!body.to_string().contains("Failed to parse"), "{}", body);
+
+    let error = client.get_task("expense", None).await.unwrap_err();
+    assert_eq!(error.storage_failure(), Some(StorageFailure::Corrupt));
+    assert!(!error.is_retryable());
+
+    // Corrupt storage is not ready either, but waiting will not fix it
+    let (status, retry_after, body) = probe(address).await;
+    assert_eq!(status, 503);
+    assert!(retry_after.is_none());
+    assert_eq!(body["error"]["errorCode"], "storage.corrupt");
+
+    // Other failures keep the status they had
+    storage.fail_with(None);
+    let request =
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tasks/get", "params": {"id": "missing"}});
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/", address))
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlite_failures_are_classified() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    storage.create_task("expense", "ctx").await.unwrap();
+    storage.check_health().await.unwrap();
+
+    sqlx::query("UPDATE tasks SET metadata = '{not json' WHERE id = 'expense'")
+        .execute(storage.pool())
+        .await
+        .unwrap();
+    let error = storage.get_task("expense", None).await.unwrap_err();
+    assert!(matches!(error, A2AError::StorageCorrupt(_)), "{}", error);
+    assert!(!error.is_retryable());
+
+    storage.pool().close().await;
+    let error = storage.get_task("expense", None).await.unwrap_err();
+    assert!(matches!(error, A2AError::Unavailable(_)), "{}", error);
+    assert!(error.is_retryable());
+    let error = storage.check_health().await.unwrap_err();
+    assert_eq!(error.storage_failure(), Some(StorageFailure::Unavailable));
+}
@@ -0,0 +1,289 @@
+//! Tests for artifacts produced while a task is processed
+
+#![cfg(all(feature = "http-server", feature = "http-client"))]
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use a2a_rs::{
+    adapter::{HttpClient, HttpServer, InMemoryTaskStorage, SimpleAgentInfo},
+    domain::{
+        A2AError, Artifact, GetTaskEventsParams, Message, Part, Task, TaskLogEvent, TaskState,
+        TaskStatusUpdateEvent,
+    },
+    port::{
+        AsyncMessageHandler, AsyncStreamingHandler, AsyncTaskManager, streaming_handler::Subscriber,
+    },
+    services::{AsyncA2AClient, AsyncA2ARequestProcessor},
+};
+use async_trait::async_trait;
+use common::Processor;
+use futures::TryStreamExt;
+use serde_json::{Value, json};
+use tokio::{net::TcpStream, sync::Mutex};
+
+/// Approves every expense and attaches an approval document of three pages
+#[derive(Clone)]
+struct ApprovingHandler {
+    storage: InMemoryTaskStorage,
+}
+
+fn approval() -> Artifact {
+    Artifact {
+        artifact_id: "approval".to_string(),
+        name: Some("approval.pdf".to_string()),
+        description: Some("Signed approval".to_string()),
+        parts: (1..=3)
+            .map(|page| {
+                Part::file_from_bytes(
+                    format!("cGFnZS0{}", page),
+                    Some(format!("page-{}.pdf", page)),
+                    Some("application/pdf".to_string()),
+                )
+            })
+            .collect(),
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    }
+}
+
+#[async_trait]
+impl AsyncMessageHandler for ApprovingHandler {
+    async fn process_message<'a>(
+        &self,
+        task_id: &'a str,
+        message: &'a Message,
+        _session_id: Option<&'a str>,
+    ) -> Result<Task, A2AError> {
+        self.storage.create_task(task_id, "ctx-expense").await?;
+        self.storage
+            .update_task_status(task_id, TaskState::Working, Some(message.clone()))
+            .await?;
+        self.storage.add_task_artifact(task_id, approval()).await?;
+        let reply = Message::agent_text("Approved".to_string(), "msg-approved".to_string());
+        self.storage
+            .update_task_status(task_id, TaskState::Completed, Some(reply))
+            .await
+    }
+}
+
+/// Records the status updates it receives
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    updates: Arc<Mutex<Vec<TaskStatusUpdateEvent>>>,
+}
+
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for RecordingSubscriber {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        self.updates.lock().await.push(update);
+        Ok(())
+    }
+}
+
+fn processor(storage: &InMemoryTaskStorage) -> Processor<ApprovingHandler> {
+    let handler = ApprovingHandler {
+        storage: storage.clone(),
+    };
+    common::processor_with(handler, storage, common::agent_info())
+}
+
+async fn call(processor: &impl AsyncA2ARequestProcessor, method: &str, params: Value) -> Value {
+    let request = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+    let response = processor
+        .process_raw_request(&request.to_string())
+        .await
+        .unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+async fn send_expense(processor: &impl AsyncA2ARequestProcessor, task_id: &str) -> Value {
+    let message = Message::user_text("Taxi, $20".to_string(), "msg-1".to_string());
+    call(
+        processor,
+        "tasks/send",
+        json!({"id": task_id, "message": message}),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_artifact_created_during_processing_is_listed_and_paged() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+    let subscriber = RecordingSubscriber::default();
+    storage
+        .add_status_subscriber("expense", Box::new(subscriber.clone()))
+        .await
+        .unwrap();
+
+    let response = send_expense(&processor, "expense").await;
+    assert_eq!(
+        response["result"]["status"]["state"], "completed",
+        "{}",
+        response
+    );
+    assert!(response["result"]["artifacts"][0]["createdAt"].is_string());
+
+    // Only the terminal status event lists the artifacts
+    let updates = subscriber.updates.lock().await.clone();
+    let (last, earlier) = updates.split_last().unwrap();
+    assert_eq!(last.status.state, TaskState::Completed);
+    let listed = last.artifacts.as_ref().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].artifact_id, "approval");
+    assert_eq!(listed[0].part_count, 3);
+    assert!(earlier.iter().all(|update| update.artifacts.is_none()));
+
+    let response = call(&processor, "tasks/artifacts/list", json!({"id": "expense"})).await;
+    let artifacts = response["result"]["artifacts"].as_array().unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0]["artifactId"], "approval");
+    assert_eq!(artifacts[0]["name"], "approval.pdf");
+    assert_eq!(artifacts[0]["partCount"], 3);
+    assert!(artifacts[0].get("parts").is_none());
+
+    // The content comes a page of parts at a time
+    let params = json!({"id": "expense", "artifactId": "approval", "partLimit": 2});
+    let response = call(&processor, "tasks/artifacts/get", params).await;
+    let page = &response["result"];
+    assert_eq!(page["artifact"]["parts"].as_array().unwrap().len(), 2);
+    assert_eq!(page["artifact"]["parts"][0]["file"]["name"], "page-1.pdf");
+    assert_eq!(page["nextPartOffset"], 2);
+
+    let params =
+        json!({"id": "expense", "artifactId": "approval", "partOffset": 2, "partLimit": 2});
+    let response = call(&processor, "tasks/artifacts/get", params).await;
+    let page = &response["result"];
+    assert_eq!(page["artifact"]["parts"].as_array().unwrap().len(), 1);
+    assert_eq!(page["artifact"]["parts"][0]["file"]["name"], "page-3.pdf");
+    assert!(page.get("nextPartOffset").is_none());
+
+    // The artifact is recorded in the task's event log
+    let log = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "expense".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(log.events.iter().any(|record| matches!(
+        &record.event,
+        TaskLogEvent::ArtifactAdded { artifact } if artifact.artifact_id == "approval"
+    )));
+}
+
+#[tokio::test]
+async fn test_replacing_an_artifact_keeps_one_entry() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-expense").await.unwrap();
+    storage
+        .add_task_artifact("expense", approval())
+        .await
+        .unwrap();
+
+    let mut revised = approval();
+    revised.parts.truncate(1);
+    let task = storage.add_task_artifact("expense", revised).await.unwrap();
+    assert_eq!(task.artifacts.as_ref().unwrap().len(), 1);
+
+    let listed = storage.list_task_artifacts("expense").await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].part_count, 1);
+
+    // Artifacts without content are rejected
+    let mut empty = approval();
+    empty.parts.clear();
+    assert!(storage.add_task_artifact("expense", empty).await.is_err());
+}
+
+#[tokio::test]
+async fn test_unknown_artifact_is_reported_by_code() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+    send_expense(&processor, "expense").await;
+
+    let params = json!({"id": "expense", "artifactId": "receipt"});
+    let response = call(&processor, "tasks/artifacts/get", params).await;
+    let detail = &response["error"]["data"];
+    assert_eq!(detail["errorCode"], "artifact.not_found", "{}", response);
+    assert_eq!(detail["params"]["taskId"], "expense");
+    assert_eq!(detail["params"]["artifactId"], "receipt");
+}
+
+#[tokio::test]
+async fn test_client_streams_artifact_content() {
+    let address = "127.0.0.1:8369";
+    let storage = InMemoryTaskStorage::new();
+    let agent_info =
+        SimpleAgentInfo::new("Expense Agent".to_string(), format!("http://{}", address));
+    let server = HttpServer::new(processor(&storage), agent_info, address.to_string());
+    tokio::spawn(async move { server.start().await });
+    for _ in 0..50 {
+        if TcpStream::connect(address).await.is_ok() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let client = HttpClient::new(format!("http://{}", address));
+    let message = Message::user_text("Taxi, $20".to_string(), "msg-1".to_string());
+    client
+        .send_task_message("expense", &message, None, None)
+        .await
+        .unwrap();
+
+    let summaries = client.list_task_artifacts("expense").await.unwrap();
+    assert_eq!(summaries[0].artifact_id, "approval");
+
+    let parts: Vec<Part> = client
+        .get_task_artifact("expense", "approval")
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&parts).unwrap(),
+        serde_json::to_value(&approval().parts).unwrap()
+    );
+
+    let missing: Result<Vec<Part>, A2AError> = client
+        .get_task_artifact("expense", "receipt")
+        .try_collect()
+        .await;
+    assert!(missing.is_err());
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_artifacts_persist_in_sqlite() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    storage.create_task("expense", "ctx-expense").await.unwrap();
+    storage
+        .add_task_artifact("expense", approval())
+        .await
+        .unwrap();
+    let mut revised = approval();
+    revised.parts.truncate(2);
+    storage.add_task_artifact("expense", revised).await.unwrap();
+
+    let listed = storage.list_task_artifacts("expense").await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].part_count, 2);
+    assert!(listed[0].created_at.is_some());
+
+    let stored = storage
+        .get_task_artifact("expense", "approval")
+        .await
+        .unwrap();
+    assert_eq!(stored.name.as_deref(), Some("approval.pdf"));
+    assert!(
+        storage
+            .add_task_artifact("missing", approval())
+            .await
+            .is_err()
+    );
+}
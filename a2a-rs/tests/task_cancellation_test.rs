@@ -0,0 +1,205 @@
+//! Tests for recording who canceled a task and why
+
+use std::sync::Arc;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{
+        A2AError, GetTaskEventsParams, Part, TaskCancellation, TaskLogEvent, TaskState,
+        TaskStatusUpdateEvent,
+        core::task::{CANCELLATION_METADATA_KEY, MAX_CANCEL_REASON_LENGTH},
+    },
+    port::{AsyncStreamingHandler, AsyncTaskManager, streaming_handler::Subscriber},
+};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+/// Records the status updates it receives
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    updates: Arc<Mutex<Vec<TaskStatusUpdateEvent>>>,
+}
+
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for RecordingSubscriber {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        self.updates.lock().await.push(update);
+        Ok(())
+    }
+}
+
+async fn working_task(storage: &InMemoryTaskStorage, task_id: &str) {
+    storage.create_task(task_id, "ctx-cancel").await.unwrap();
+    storage
+        .update_task_status(task_id, TaskState::Working, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_cancellation_is_recorded_on_task_event_and_log() {
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "expense").await;
+    let subscriber = RecordingSubscriber::default();
+    storage
+        .add_status_subscriber("expense", Box::new(subscriber.clone()))
+        .await
+        .unwrap();
+
+    let cancellation = TaskCancellation::new()
+        .with_canceled_by("alice".to_string())
+        .with_reason("duplicate submission".to_string());
+    let task = storage
+        .cancel_task_with("expense", &cancellation, None)
+        .await
+        .unwrap();
+    assert_eq!(task.status.state, TaskState::Canceled);
+    assert_eq!(task.cancellation(), Some(cancellation.clone()));
+
+    // Reads return the details
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(task.cancellation(), Some(cancellation.clone()));
+    let message = task.status.message.unwrap();
+    match &message.parts[0] {
+        Part::Text { text, .. } => {
+            assert_eq!(text, "Task expense canceled by alice: duplicate submission")
+        }
+        other => panic!("Expected a text part, got {:?}", other),
+    }
+    assert_eq!(
+        message.metadata.unwrap()[CANCELLATION_METADATA_KEY],
+        serde_json::json!({"canceledBy": "alice", "reason": "duplicate submission"})
+    );
+
+    // The final status event carries them
+    let updates = subscriber.updates.lock().await.clone();
+    let update = updates.last().unwrap();
+    assert!(update.final_);
+    assert_eq!(update.status.state, TaskState::Canceled);
+    let details = &update
+        .status
+        .message
+        .as_ref()
+        .unwrap()
+        .metadata
+        .as_ref()
+        .unwrap()[CANCELLATION_METADATA_KEY];
+    assert_eq!(details["reason"], "duplicate submission");
+
+    // And so does the event log
+    let events = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "expense".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    match &events.events.last().unwrap().event {
+        TaskLogEvent::StatusUpdate(update) => {
+            let message = update.status.message.as_ref().unwrap();
+            assert_eq!(
+                message.metadata.as_ref().unwrap()[CANCELLATION_METADATA_KEY]["canceledBy"],
+                "alice"
+            );
+        }
+        other => panic!("Expected a status event, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_cancel_without_reason_still_works() {
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "plain").await;
+
+    let task = storage.cancel_task("plain").await.unwrap();
+    assert_eq!(task.status.state, TaskState::Canceled);
+    assert_eq!(task.cancellation(), None);
+    let message = task.status.message.unwrap();
+    assert!(message.metadata.is_none());
+    match &message.parts[0] {
+        Part::Text { text, .. } => assert_eq!(text, "Task plain canceled."),
+        other => panic!("Expected a text part, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cancel_reason_length_is_limited() {
+    let at_limit = TaskCancellation::new().with_reason("x".repeat(MAX_CANCEL_REASON_LENGTH));
+    assert!(at_limit.validate().is_ok());
+
+    let too_long = TaskCancellation::new().with_reason("x".repeat(MAX_CANCEL_REASON_LENGTH + 1));
+    match too_long.validate().unwrap_err() {
+        A2AError::ValidationError { field, .. } => assert_eq!(field, "reason"),
+        other => panic!("Expected a validation error, got {:?}", other),
+    }
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_canceler_is_the_authenticated_principal() {
+    use a2a_rs::{
+        adapter::{
+            BearerTokenAuthenticator, DefaultRequestProcessor, HttpClient, HttpServer,
+            SimpleAgentInfo, business::DefaultMessageHandler,
+        },
+        domain::error::INVALID_PARAMS,
+        services::AsyncA2AClient,
+    };
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    working_task(&storage, "audited").await;
+    working_task(&storage, "unexplained").await;
+    working_task(&storage, "rambling").await;
+
+    let agent_info = SimpleAgentInfo::new(
+        "Cancel Agent".to_string(),
+        "http://127.0.0.1:8335".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::with_auth(
+        processor,
+        agent_info,
+        "127.0.0.1:8335".to_string(),
+        BearerTokenAuthenticator::new(vec!["alice".to_string()]),
+    );
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = HttpClient::with_auth("http://127.0.0.1:8335".to_string(), "alice".to_string());
+    let task = client
+        .cancel_task_with_reason("audited", "duplicate submission")
+        .await
+        .unwrap();
+    let expected = TaskCancellation::new()
+        .with_canceled_by("alice".to_string())
+        .with_reason("duplicate submission".to_string());
+    assert_eq!(task.cancellation(), Some(expected.clone()));
+    let read = client.get_task("audited", None).await.unwrap();
+    assert_eq!(read.cancellation(), Some(expected));
+
+    // Without a reason only the canceler is recorded
+    let task = client.cancel_task("unexplained").await.unwrap();
+    assert_eq!(
+        task.cancellation(),
+        Some(TaskCancellation::new().with_canceled_by("alice".to_string()))
+    );
+
+    // An overlong reason is refused and the task keeps running
+    let reason = "x".repeat(MAX_CANCEL_REASON_LENGTH + 1);
+    match client
+        .cancel_task_with_reason("rambling", &reason)
+        .await
+        .unwrap_err()
+    {
+        A2AError::JsonRpc { code, .. } => assert_eq!(code, INVALID_PARAMS),
+        other => panic!("Expected a JSON-RPC error, got {:?}", other),
+    }
+    let task = storage.get_task("rambling", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+}
@@ -0,0 +1,169 @@
+//! Tests for task TTL policies and the background cleanup worker
+
+use std::time::Duration;
+
+use a2a_rs::{
+    adapter::{InMemoryTaskStorage, TaskCleanupWorker, TaskRetentionPolicy},
+    domain::{Task, TaskState},
+    port::AsyncTaskManager,
+};
+use chrono::Utc;
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+async fn finish_task<S: AsyncTaskManager>(storage: &S, task_id: &str, state: TaskState) {
+    storage.create_task(task_id, "ctx-cleanup").await.unwrap();
+    storage
+        .update_task_status(task_id, state, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_expired_task_is_removed_and_recent_one_survives() {
+    let storage = InMemoryTaskStorage::new();
+    finish_task(&storage, "old-task", TaskState::Completed).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    finish_task(&storage, "recent-task", TaskState::Completed).await;
+
+    let policy =
+        TaskRetentionPolicy::new().with_ttl(TaskState::Completed, Duration::from_millis(200));
+    let report = TaskCleanupWorker::new(storage.clone(), policy)
+        .run_once()
+        .await
+        .unwrap();
+
+    assert_eq!(report.deleted, 1);
+    assert!(!storage.task_exists("old-task").await.unwrap());
+    assert!(storage.task_exists("recent-task").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_ttls_are_applied_per_state() {
+    let storage = InMemoryTaskStorage::new();
+    finish_task(&storage, "completed-task", TaskState::Completed).await;
+    finish_task(&storage, "failed-task", TaskState::Failed).await;
+    finish_task(&storage, "working-task", TaskState::Working).await;
+    finish_task(&storage, "canceled-task", TaskState::Canceled).await;
+
+    let policy = TaskRetentionPolicy::new()
+        .with_ttl(TaskState::Completed, HOUR)
+        .with_ttl(TaskState::Failed, HOUR * 24);
+    let worker = TaskCleanupWorker::new(storage.clone(), policy);
+
+    let report = worker
+        .run_once_at(Utc::now() + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(report.deleted, 1);
+
+    // Completed exceeded its TTL, failed has a longer one, and states without a
+    // TTL are never touched
+    assert!(!storage.task_exists("completed-task").await.unwrap());
+    assert!(storage.task_exists("failed-task").await.unwrap());
+    assert!(storage.task_exists("working-task").await.unwrap());
+    assert!(storage.task_exists("canceled-task").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_cleanup_deletes_in_batches() {
+    let storage = InMemoryTaskStorage::new();
+    for i in 0..7 {
+        finish_task(&storage, &format!("task-{}", i), TaskState::Completed).await;
+    }
+
+    let policy = TaskRetentionPolicy::new()
+        .with_ttl(TaskState::Completed, HOUR)
+        .with_batch_size(3);
+    let later = Utc::now() + chrono::Duration::hours(2);
+
+    // The storage never hands out more than one batch at a time
+    let batch = storage
+        .list_tasks_updated_before(&TaskState::Completed, later, 3)
+        .await
+        .unwrap();
+    assert_eq!(batch.len(), 3);
+
+    let report = TaskCleanupWorker::new(storage.clone(), policy)
+        .run_once_at(later)
+        .await
+        .unwrap();
+    assert_eq!(report.deleted, 7);
+    for i in 0..7 {
+        assert!(!storage.task_exists(&format!("task-{}", i)).await.unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_expired_tasks_are_archived_before_deletion() {
+    let storage = InMemoryTaskStorage::new();
+    finish_task(&storage, "archived-1", TaskState::Completed).await;
+    finish_task(&storage, "archived-2", TaskState::Canceled).await;
+
+    let archive = std::env::temp_dir().join(format!("a2a-archive-{}.ndjson", uuid::Uuid::new_v4()));
+    let policy = TaskRetentionPolicy::new()
+        .with_ttl(TaskState::Completed, HOUR)
+        .with_ttl(TaskState::Canceled, HOUR)
+        .with_archive_path(&archive);
+
+    let report = TaskCleanupWorker::new(storage.clone(), policy)
+        .run_once_at(Utc::now() + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(report.archived, 2);
+    assert_eq!(report.deleted, 2);
+
+    let contents = std::fs::read_to_string(&archive).unwrap();
+    std::fs::remove_file(&archive).ok();
+
+    let mut ids: Vec<String> = contents
+        .lines()
+        .map(|line| serde_json::from_str::<Task>(line).unwrap().id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["archived-1", "archived-2"]);
+}
+
+#[tokio::test]
+async fn test_background_worker_sweeps_and_shuts_down() {
+    let storage = InMemoryTaskStorage::new();
+    finish_task(&storage, "expired-task", TaskState::Completed).await;
+
+    let policy = TaskRetentionPolicy::new()
+        .with_ttl(TaskState::Completed, Duration::from_millis(20))
+        .with_interval(Duration::from_millis(20));
+    let handle = TaskCleanupWorker::new(storage.clone(), policy).spawn();
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!storage.task_exists("expired-task").await.unwrap());
+
+    tokio::time::timeout(Duration::from_secs(1), handle.shutdown())
+        .await
+        .expect("cleanup worker did not shut down");
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_cleanup_removes_expired_tasks() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    finish_task(&storage, "sqlx-old", TaskState::Completed).await;
+    finish_task(&storage, "sqlx-working", TaskState::Working).await;
+
+    let policy = TaskRetentionPolicy::new().with_ttl(TaskState::Completed, HOUR);
+    let worker = TaskCleanupWorker::new(storage.clone(), policy);
+
+    let report = worker.run_once().await.unwrap();
+    assert_eq!(report.deleted, 0);
+    assert!(storage.task_exists("sqlx-old").await.unwrap());
+
+    let report = worker
+        .run_once_at(Utc::now() + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(report.deleted, 1);
+    assert!(!storage.task_exists("sqlx-old").await.unwrap());
+    assert!(storage.task_exists("sqlx-working").await.unwrap());
+    assert!(storage.get_task("sqlx-old", None).await.is_err());
+}
@@ -0,0 +1,153 @@
+//! Tests for keeping a copy of a task up to date from diffs
+
+use std::sync::Arc;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{
+        A2AError, Artifact, Message, Part, Task, TaskDiff, TaskResult, TaskSnapshotOptions,
+        TaskState, TaskStatusUpdateEvent,
+    },
+    port::{AsyncStreamingHandler, AsyncTaskManager, streaming_handler::Subscriber},
+};
+use async_trait::async_trait;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+/// Records what a subscriber asking for diffs is sent, diffing each snapshot
+/// against the last as the WebSocket transport does
+#[derive(Clone, Default)]
+struct DiffingSubscriber {
+    seen: Arc<Mutex<Option<Task>>>,
+    updates: Arc<Mutex<Vec<TaskStatusUpdateEvent>>>,
+}
+
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for DiffingSubscriber {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        let update = update.diffed(&mut *self.seen.lock().await);
+        self.updates.lock().await.push(update);
+        Ok(())
+    }
+
+    fn task_snapshots(&self) -> Option<TaskSnapshotOptions> {
+        Some(TaskSnapshotOptions::new().with_diffs())
+    }
+}
+
+fn receipt(text: &str) -> Artifact {
+    Artifact {
+        artifact_id: "receipt".to_string(),
+        name: Some("Receipt".to_string()),
+        description: None,
+        parts: vec![Part::text(text.to_string())],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_diffs_applied_to_the_first_snapshot_match_the_task() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-diff").await.unwrap();
+    let message = Message::user_text("Reimburse my taxi".to_string(), "m-0".to_string());
+    storage
+        .update_task_status("expense", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+
+    let subscriber = DiffingSubscriber::default();
+    storage
+        .add_status_subscriber("expense", Box::new(subscriber.clone()))
+        .await
+        .unwrap();
+
+    for i in 1..4 {
+        let message = Message::agent_text(format!("Checking receipt {}", i), format!("m-{}", i));
+        storage
+            .update_task_status("expense", TaskState::Working, Some(message))
+            .await
+            .unwrap();
+    }
+    // A plain status change between snapshots
+    storage
+        .update_task_status("expense", TaskState::InputRequired, None)
+        .await
+        .unwrap();
+    storage
+        .complete_task_with_result(
+            "expense",
+            TaskResult::new(json!({"status": "approved"})),
+            Some(Message::agent_text(
+                "Approved".to_string(),
+                "m-4".to_string(),
+            )),
+        )
+        .await
+        .unwrap();
+
+    let updates = subscriber.updates.lock().await.clone();
+    // The subscription starts from the whole task
+    let first = updates[0].task.as_ref().expect("first event is a snapshot");
+    assert_eq!(first.history.as_ref().unwrap().len(), 1);
+    assert!(updates[0].diff.is_none());
+    // After that only what changed is sent
+    for update in &updates[1..] {
+        assert!(update.task.is_none());
+        if let Some(diff) = &update.diff {
+            assert!(diff.history_appended.len() <= 1);
+        }
+    }
+    let wire = serde_json::to_value(&updates[1]).unwrap();
+    assert!(wire.get("task").is_none());
+    assert_eq!(wire["diff"]["historyAppended"][0]["messageId"], "m-1");
+
+    let mut replica = None;
+    for update in &updates {
+        update.apply_to(&mut replica);
+    }
+    let replica = replica.unwrap();
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(
+        serde_json::to_value(&replica).unwrap(),
+        serde_json::to_value(&task).unwrap()
+    );
+    assert_eq!(replica.status.state, TaskState::Completed);
+    assert_eq!(replica.history.unwrap().len(), 5);
+}
+
+#[test]
+fn test_diff_is_a_merge_patch() {
+    let mut before = Task::new("expense".to_string(), "ctx-diff".to_string());
+    before.add_artifact(receipt("Taxi, $20"));
+    before.metadata = json!({"department": "sales", "region": "emea"})
+        .as_object()
+        .cloned();
+
+    let mut after = before.clone();
+    after.artifacts = Some(vec![receipt("Taxi, $25")]);
+    after.metadata = json!({"department": "finance"}).as_object().cloned();
+    after.add_tags(&["travel".to_string()]).unwrap();
+
+    let diff = TaskDiff::between(&before, &after);
+    let wire = serde_json::to_value(&diff).unwrap();
+    assert!(wire.get("status").is_none(), "{}", wire);
+    assert!(wire.get("historyAppended").is_none(), "{}", wire);
+    assert_eq!(
+        wire["metadata"],
+        json!({"department": "finance", "region": null})
+    );
+    assert_eq!(wire["artifacts"][0]["parts"][0]["text"], "Taxi, $25");
+    assert_eq!(wire["tags"], json!(["travel"]));
+
+    let mut replica = before.clone();
+    diff.apply(&mut replica);
+    assert_eq!(
+        serde_json::to_value(&replica).unwrap(),
+        serde_json::to_value(&after).unwrap()
+    );
+
+    // Between equal tasks there is nothing to send
+    assert!(TaskDiff::between(&after, &after).is_empty());
+}
@@ -0,0 +1,301 @@
+//! Tests for the per-task append-only event log (tasks/events)
+
+mod common;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{
+        A2AError, GetTaskEventsParams, Message, PushNotificationConfig, Task, TaskLogEvent,
+        TaskPushNotificationConfig, TaskState,
+    },
+    port::{AsyncNotificationManager, AsyncTaskManager},
+};
+
+fn events_params(task_id: &str) -> GetTaskEventsParams {
+    GetTaskEventsParams {
+        id: task_id.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Extract the status state of a status update event
+fn status_state(event: &TaskLogEvent) -> Option<TaskState> {
+    match event {
+        TaskLogEvent::StatusUpdate(update) => Some(update.status.state.clone()),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_event_log_records_lifecycle_in_order() {
+    let storage = InMemoryTaskStorage::new();
+    let task_id = "event-log-lifecycle";
+
+    storage.create_task(task_id, "ctx-events").await.unwrap();
+    storage
+        .update_task_status(task_id, TaskState::Working, None)
+        .await
+        .unwrap();
+    let reply = Message::agent_text("Expense approved".to_string(), "msg-reply".to_string());
+    storage
+        .update_task_status(task_id, TaskState::Completed, Some(reply))
+        .await
+        .unwrap();
+
+    let result = storage
+        .get_task_events(&events_params(task_id))
+        .await
+        .unwrap();
+
+    assert_eq!(result.total_size, 4);
+    assert!(result.next_page_token.is_empty());
+
+    let sequences: Vec<u64> = result.events.iter().map(|e| e.sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3, 4]);
+
+    assert_eq!(
+        status_state(&result.events[0].event),
+        Some(TaskState::Submitted)
+    );
+    assert_eq!(
+        status_state(&result.events[1].event),
+        Some(TaskState::Working)
+    );
+    match &result.events[2].event {
+        TaskLogEvent::MessageAppended { message } => assert_eq!(message.message_id, "msg-reply"),
+        other => panic!("Expected message event, got {:?}", other),
+    }
+    match &result.events[3].event {
+        TaskLogEvent::StatusUpdate(update) => {
+            assert_eq!(update.status.state, TaskState::Completed);
+            assert_eq!(update.context_id, "ctx-events");
+            assert!(update.final_);
+        }
+        other => panic!("Expected status event, got {:?}", other),
+    }
+
+    // Timestamps never go backwards
+    for pair in result.events.windows(2) {
+        assert!(pair[0].timestamp <= pair[1].timestamp);
+    }
+}
+
+#[tokio::test]
+async fn test_event_log_pagination() {
+    let storage = InMemoryTaskStorage::new();
+    let task_id = "event-log-pages";
+
+    storage.create_task(task_id, "ctx").await.unwrap();
+    for _ in 0..4 {
+        storage
+            .update_task_status(task_id, TaskState::Working, None)
+            .await
+            .unwrap();
+    }
+
+    let mut params = GetTaskEventsParams {
+        id: task_id.to_string(),
+        page_size: Some(2),
+        page_token: None,
+    };
+    let mut sequences = Vec::new();
+    loop {
+        let page = storage.get_task_events(&params).await.unwrap();
+        assert_eq!(page.total_size, 5);
+        assert!(page.events.len() <= 2);
+        sequences.extend(page.events.iter().map(|e| e.sequence));
+        if page.next_page_token.is_empty() {
+            break;
+        }
+        params.page_token = Some(page.next_page_token);
+    }
+
+    assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn test_event_log_records_push_config_changes() {
+    let storage = InMemoryTaskStorage::new();
+    let task_id = "event-log-push";
+
+    storage.create_task(task_id, "ctx").await.unwrap();
+    storage
+        .set_task_notification(&TaskPushNotificationConfig {
+            task_id: task_id.to_string(),
+            push_notification_config: PushNotificationConfig {
+                id: Some("config-1".to_string()),
+                url: "https://example.com/webhook".to_string(),
+                token: None,
+                authentication: None,
+            },
+        })
+        .await
+        .unwrap();
+    storage.remove_task_notification(task_id).await.unwrap();
+
+    let result = storage
+        .get_task_events(&events_params(task_id))
+        .await
+        .unwrap();
+    let kinds: Vec<&str> = result.events.iter().map(|e| e.event.event_type()).collect();
+    assert_eq!(
+        kinds,
+        vec!["statusUpdate", "pushConfigSet", "pushConfigRemoved"]
+    );
+}
+
+#[tokio::test]
+async fn test_event_log_unknown_task() {
+    let storage = InMemoryTaskStorage::new();
+
+    let result = storage.get_task_events(&events_params("missing")).await;
+    assert!(matches!(result, Err(A2AError::TaskNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_removing_push_config_of_unknown_task_logs_nothing() {
+    let storage = InMemoryTaskStorage::new();
+    storage.remove_task_notification("missing").await.unwrap();
+
+    let result = storage.get_task_events(&events_params("missing")).await;
+    assert!(matches!(result, Err(A2AError::TaskNotFound(_))));
+
+    // A task imported under that ID starts with a log of its own
+    storage
+        .import_task(&Task::new("missing".to_string(), "ctx".to_string()))
+        .await
+        .unwrap();
+    let result = storage
+        .get_task_events(&events_params("missing"))
+        .await
+        .unwrap();
+    let kinds: Vec<&str> = result.events.iter().map(|e| e.event.event_type()).collect();
+    assert!(!kinds.contains(&"pushConfigRemoved"), "{:?}", kinds);
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_get_task_events_over_http() {
+    use a2a_rs::{
+        adapter::{DefaultRequestProcessor, HttpClient, HttpServer, SimpleAgentInfo},
+        services::AsyncA2AClient,
+    };
+    use common::TestBusinessHandler;
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    let handler = TestBusinessHandler::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Event Log Agent".to_string(),
+        "http://localhost:8302".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8302".to_string());
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server_handle = tokio::spawn(async move {
+        tokio::select! {
+            _ = server.start() => {},
+            _ = shutdown_rx => {}
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = HttpClient::new("http://localhost:8302".to_string());
+    let task_id = format!("event-log-http-{}", uuid::Uuid::new_v4());
+    let message = Message::user_text("Hello".to_string(), "msg-http".to_string());
+    client
+        .send_task_message(&task_id, &message, None, None)
+        .await
+        .unwrap();
+
+    let result = client
+        .get_task_events(&events_params(&task_id))
+        .await
+        .unwrap();
+    assert!(result.total_size >= 2);
+    assert_eq!(
+        status_state(&result.events[0].event),
+        Some(TaskState::Submitted)
+    );
+    assert!(
+        result
+            .events
+            .iter()
+            .any(|e| matches!(&e.event, TaskLogEvent::MessageAppended { message } if message.message_id == "msg-http"))
+    );
+
+    let missing = client.get_task_events(&events_params("missing")).await;
+    assert!(missing.is_err());
+
+    shutdown_tx.send(()).ok();
+    server_handle.await.ok();
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_event_log_records_lifecycle_in_order() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    let task_id = "sqlx-event-log";
+
+    storage.create_task(task_id, "ctx-sqlx").await.unwrap();
+    storage
+        .update_task_status(task_id, TaskState::Working, None)
+        .await
+        .unwrap();
+    let reply = Message::agent_text("Done".to_string(), "msg-done".to_string());
+    storage
+        .update_task_status(task_id, TaskState::Completed, Some(reply))
+        .await
+        .unwrap();
+
+    let result = storage
+        .get_task_events(&events_params(task_id))
+        .await
+        .unwrap();
+
+    assert_eq!(result.total_size, 4);
+    let sequences: Vec<u64> = result.events.iter().map(|e| e.sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3, 4]);
+    let states: Vec<Option<TaskState>> = result
+        .events
+        .iter()
+        .map(|e| status_state(&e.event))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            Some(TaskState::Submitted),
+            Some(TaskState::Working),
+            None,
+            Some(TaskState::Completed)
+        ]
+    );
+    for pair in result.events.windows(2) {
+        assert!(pair[0].timestamp <= pair[1].timestamp);
+    }
+
+    // Second page via token
+    let page = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: task_id.to_string(),
+            page_size: Some(3),
+            page_token: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.next_page_token, "3");
+    let rest = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: task_id.to_string(),
+            page_size: Some(3),
+            page_token: Some(page.next_page_token),
+        })
+        .await
+        .unwrap();
+    assert_eq!(rest.events.len(), 1);
+    assert_eq!(rest.events[0].sequence, 4);
+    assert!(rest.next_page_token.is_empty());
+}
@@ -0,0 +1,207 @@
+//! Tests for incremental history sync with `since` cursors
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{A2AError, Message, Part, TaskState},
+    port::AsyncTaskManager,
+};
+
+async fn append(storage: &InMemoryTaskStorage, task_id: &str, text: &str) {
+    let message = Message::user_text(text.to_string(), format!("msg-{}", text));
+    storage
+        .update_task_status(task_id, TaskState::Working, Some(message))
+        .await
+        .unwrap();
+}
+
+fn texts(task: &a2a_rs::domain::Task) -> Vec<String> {
+    task.history
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|message| match &message.parts[0] {
+            Part::Text { text, .. } => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_since_returns_only_newer_entries() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-sync").await.unwrap();
+    append(&storage, "expense", "one").await;
+    append(&storage, "expense", "two").await;
+
+    let task = storage.get_task("expense", None).await.unwrap();
+    let first = task.with_history_since(None).unwrap();
+    assert_eq!(texts(&first), vec!["one", "two"]);
+    let cursor = first.history_cursor.unwrap();
+
+    // Nothing new yet
+    let task = storage.get_task("expense", None).await.unwrap();
+    let unchanged = task.with_history_since(Some(&cursor)).unwrap();
+    assert!(texts(&unchanged).is_empty());
+    assert_eq!(unchanged.history_cursor.as_deref(), Some(cursor.as_str()));
+
+    append(&storage, "expense", "three").await;
+    append(&storage, "expense", "four").await;
+    let task = storage.get_task("expense", None).await.unwrap();
+    let newer = task.with_history_since(Some(&cursor)).unwrap();
+    assert_eq!(texts(&newer), vec!["three", "four"]);
+    let advanced = newer.history_cursor.unwrap();
+    assert_ne!(advanced, cursor);
+
+    append(&storage, "expense", "five").await;
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(
+        texts(&task.with_history_since(Some(&advanced)).unwrap()),
+        vec!["five"]
+    );
+}
+
+#[test]
+fn test_invalid_cursors_are_rejected() {
+    let task = a2a_rs::domain::Task::new("expense".to_string(), "ctx-sync".to_string());
+    for cursor in ["not-a-cursor", "5"] {
+        match task.with_history_since(Some(cursor)).unwrap_err() {
+            A2AError::ValidationError { field, .. } => assert_eq!(field, "since"),
+            other => panic!("Expected a validation error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_history_pages_start_after_the_cursor() {
+    let mut task = a2a_rs::domain::Task::new("expense".to_string(), "ctx-sync".to_string());
+    task.history = Some(
+        ["one", "two", "three", "four", "five"]
+            .iter()
+            .map(|text| Message::user_text(text.to_string(), format!("msg-{}", text)))
+            .collect(),
+    );
+
+    let page = task.with_history_page(Some("1"), Some(2)).unwrap();
+    assert_eq!(texts(&page), vec!["two", "three"]);
+    assert_eq!(page.history_cursor.as_deref(), Some("3"));
+
+    // The last page is short and ends at the end of the history
+    let page = task.with_history_page(Some("3"), Some(5)).unwrap();
+    assert_eq!(texts(&page), vec!["four", "five"]);
+    assert_eq!(page.history_cursor.as_deref(), Some("5"));
+
+    let page = task.with_history_page(Some("5"), Some(2)).unwrap();
+    assert!(texts(&page).is_empty());
+    assert_eq!(page.history_cursor.as_deref(), Some("5"));
+}
+
+#[tokio::test]
+async fn test_get_task_pages_history_after_the_cursor() {
+    use a2a_rs::{
+        adapter::{DefaultRequestProcessor, SimpleAgentInfo, business::DefaultMessageHandler},
+        services::AsyncA2ARequestProcessor,
+    };
+    use serde_json::{Value, json};
+
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-sync").await.unwrap();
+    for text in ["one", "two", "three", "four", "five"] {
+        append(&storage, "expense", text).await;
+    }
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage,
+        SimpleAgentInfo::new("Sync Agent".to_string(), "http://localhost".to_string()),
+    );
+
+    let mut cursor = "0".to_string();
+    let mut pages = Vec::new();
+    loop {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tasks/get",
+            "params": {"id": "expense", "since": cursor, "historyLength": 2},
+        });
+        let response = processor
+            .process_raw_request(&request.to_string())
+            .await
+            .unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        let task: a2a_rs::domain::Task =
+            serde_json::from_value(response["result"].clone()).unwrap();
+        let next = task.history_cursor.clone().unwrap();
+        if next == cursor {
+            break;
+        }
+        pages.push(texts(&task));
+        cursor = next;
+    }
+    assert_eq!(
+        pages,
+        vec![vec!["one", "two"], vec!["three", "four"], vec!["five"]]
+    );
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_get_task_since_over_http() {
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, HttpClient, HttpServer, SimpleAgentInfo,
+            business::DefaultMessageHandler,
+        },
+        services::AsyncA2AClient,
+    };
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-sync").await.unwrap();
+    append(&storage, "expense", "one").await;
+    append(&storage, "expense", "two").await;
+
+    let agent_info = SimpleAgentInfo::new(
+        "Sync Agent".to_string(),
+        "http://127.0.0.1:8341".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8341".to_string());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = HttpClient::new("http://127.0.0.1:8341".to_string());
+
+    // A limited window still points after the whole history
+    let task = client.get_task("expense", Some(1)).await.unwrap();
+    assert_eq!(texts(&task), vec!["two"]);
+    assert_eq!(task.history_cursor.as_deref(), Some("2"));
+
+    let task = client.get_task("expense", None).await.unwrap();
+    assert_eq!(texts(&task), vec!["one", "two"]);
+    let cursor = task.history_cursor.unwrap();
+
+    append(&storage, "expense", "three").await;
+    let task = client
+        .get_task_since("expense", Some(&cursor))
+        .await
+        .unwrap();
+    assert_eq!(texts(&task), vec!["three"]);
+
+    let next = task.history_cursor.unwrap();
+    let task = client.get_task_since("expense", Some(&next)).await.unwrap();
+    assert!(texts(&task).is_empty());
+    assert_eq!(task.history_cursor, Some(next));
+
+    assert!(
+        client
+            .get_task_since("expense", Some("bogus"))
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,300 @@
+//! Tests for importing tasks in bulk through the admin import method
+
+mod common;
+
+use a2a_rs::{
+    adapter::{
+        CachedTaskStorage, DefaultRequestProcessor, InMemoryTaskStorage,
+        business::DefaultMessageHandler,
+    },
+    application::json_rpc::{A2ARequest, ImportTasksRequest},
+    domain::{
+        FileContent, GetTaskEventsParams, ImportTasksParams, ImportTasksResult, Message, Part,
+        Task, TaskResult, TaskState,
+        error::{INVALID_PARAMS, METHOD_NOT_AUTHORIZED},
+    },
+    port::{AsyncTaskManager, AuthPrincipal},
+    services::AsyncA2ARequestProcessor,
+};
+use common::processor;
+use serde_json::json;
+
+/// A finished expense claim, as exported from another system
+fn completed_claim(id: &str) -> Task {
+    let mut task = Task::new(id.to_string(), "ctx-import".to_string());
+    task.update_status(
+        TaskState::Working,
+        Some(Message::user_text(
+            "Reimburse $20 for lunch".to_string(),
+            format!("{}-request", id),
+        )),
+    );
+    task.update_status(
+        TaskState::Completed,
+        Some(Message::agent_text(
+            "Approved".to_string(),
+            format!("{}-reply", id),
+        )),
+    );
+    task.result = Some(TaskResult::new(json!({"status": "approved"})));
+    task.tags = vec!["migrated".to_string()];
+    task
+}
+
+fn import_request(tasks: Vec<Task>) -> A2ARequest {
+    let tasks = tasks
+        .iter()
+        .map(|task| serde_json::to_value(task).unwrap())
+        .collect();
+    A2ARequest::ImportTasks(ImportTasksRequest::new(ImportTasksParams {
+        tasks,
+        metadata: None,
+    }))
+}
+
+fn admin() -> AuthPrincipal {
+    AuthPrincipal::new("ops".to_string(), "bearer".to_string()).with_role("admin".to_string())
+}
+
+#[tokio::test]
+async fn test_import_reports_each_task_and_keeps_the_valid_ones() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("existing", "ctx-import").await.unwrap();
+
+    let mut working = Task::new("working".to_string(), "ctx-import".to_string());
+    working.update_status(TaskState::Working, None);
+
+    let mut unknown_state = completed_claim("unknown-state");
+    unknown_state.status.state = TaskState::Unknown;
+
+    let mut early_result = completed_claim("early-result");
+    early_result.status.state = TaskState::Working;
+
+    let mut reordered = completed_claim("reordered");
+    reordered.history.as_mut().unwrap().reverse();
+
+    let mut bad_part = completed_claim("bad-part");
+    bad_part.artifacts = Some(vec![a2a_rs::domain::Artifact {
+        artifact_id: "receipt".to_string(),
+        name: None,
+        description: None,
+        parts: vec![Part::File {
+            file: FileContent {
+                name: Some("receipt.pdf".to_string()),
+                mime_type: Some("application/pdf".to_string()),
+                bytes: Some("JVBERi0=".to_string()),
+                uri: Some("https://example.com/receipt.pdf".to_string()),
+            },
+            metadata: None,
+        }],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    }]);
+
+    let request = import_request(vec![
+        completed_claim("claim-1"),
+        unknown_state,
+        working,
+        early_result,
+        reordered,
+        bad_part,
+        completed_claim("existing"),
+    ]);
+    let raw = serde_json::to_string(&request).unwrap();
+    let response: serde_json::Value = serde_json::from_str(
+        &processor(&storage)
+            .process_raw_request_as(&raw, Some(&admin()))
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let result: ImportTasksResult = serde_json::from_value(response["result"].clone()).unwrap();
+
+    assert_eq!((result.imported, result.failed), (2, 5));
+    let outcomes: Vec<(&str, bool)> = result
+        .results
+        .iter()
+        .map(|outcome| (outcome.id.as_str(), outcome.imported))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            ("claim-1", true),
+            ("unknown-state", false),
+            ("working", true),
+            ("early-result", false),
+            ("reordered", false),
+            ("bad-part", false),
+            ("existing", false),
+        ]
+    );
+    for outcome in result.results.iter().filter(|outcome| !outcome.imported) {
+        assert_eq!(outcome.error.as_ref().unwrap().code, INVALID_PARAMS);
+    }
+
+    // Imported tasks keep their state, history, result and tags as given
+    let claim = storage.get_task("claim-1", None).await.unwrap();
+    assert_eq!(claim.status.state, TaskState::Completed);
+    assert_eq!(claim.version, 3);
+    assert_eq!(claim.history.as_ref().unwrap().len(), 2);
+    assert_eq!(claim.final_result().unwrap().data["status"], "approved");
+    assert_eq!(claim.tags, vec!["migrated"]);
+    let events = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: "claim-1".to_string(),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(events.total_size, 1);
+
+    assert_eq!(
+        storage
+            .get_task("working", None)
+            .await
+            .unwrap()
+            .status
+            .state,
+        TaskState::Working
+    );
+    for rejected in ["unknown-state", "early-result", "reordered", "bad-part"] {
+        assert!(!storage.task_exists(rejected).await.unwrap());
+    }
+    // The task that was already there is left alone
+    assert_eq!(
+        storage
+            .get_task("existing", None)
+            .await
+            .unwrap()
+            .status
+            .state,
+        TaskState::Submitted
+    );
+}
+
+#[tokio::test]
+async fn test_import_requires_the_admin_role() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+    let request = import_request(vec![completed_claim("claim-1")]);
+
+    let viewer = AuthPrincipal::new("alice".to_string(), "bearer".to_string())
+        .with_role("viewer".to_string());
+    for principal in [None, Some(&viewer)] {
+        let raw = serde_json::to_string(&request).unwrap();
+        let response: serde_json::Value = serde_json::from_str(
+            &processor
+                .process_raw_request_as(&raw, principal)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_AUTHORIZED);
+    }
+    assert!(!storage.task_exists("claim-1").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_import_goes_through_the_task_managers_batch_import() {
+    let inner = InMemoryTaskStorage::new();
+    let storage = CachedTaskStorage::new(inner.clone());
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        inner,
+        common::agent_info(),
+    );
+
+    let mut request = import_request(vec![completed_claim("claim-1"), completed_claim("claim-2")]);
+    if let A2ARequest::ImportTasks(import) = &mut request {
+        import
+            .params
+            .tasks
+            .insert(1, json!({"id": "garbled", "status": "done"}));
+    }
+    let raw = serde_json::to_string(&request).unwrap();
+    let response: serde_json::Value = serde_json::from_str(
+        &processor
+            .process_raw_request_as(&raw, Some(&admin()))
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let result: ImportTasksResult = serde_json::from_value(response["result"].clone()).unwrap();
+
+    // Records that fail to decode keep their place among the outcomes
+    assert_eq!((result.imported, result.failed), (2, 1));
+    let outcomes: Vec<(&str, bool)> = result
+        .results
+        .iter()
+        .map(|outcome| (outcome.id.as_str(), outcome.imported))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![("claim-1", true), ("garbled", false), ("claim-2", true)]
+    );
+    // The decoded tasks were imported in one batch through the cache
+    assert_eq!(storage.stats().invalidations, 1);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_import_round_trips_tasks() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    let mut claim = completed_claim("claim-1");
+    claim.status.timestamp = Some("2024-03-01T12:00:00Z".parse().unwrap());
+
+    let result = storage
+        .import_tasks(&[claim.clone(), claim.clone()])
+        .await
+        .unwrap();
+    assert_eq!((result.imported, result.failed), (1, 1));
+
+    let stored = storage.get_task("claim-1", None).await.unwrap();
+    assert_eq!(stored.status.state, TaskState::Completed);
+    assert_eq!(stored.status.timestamp, claim.status.timestamp);
+    assert_eq!(stored.version, claim.version);
+    assert_eq!(stored.tags, claim.tags);
+    assert_eq!(stored.result, claim.result);
+    let history: Vec<String> = stored
+        .history
+        .unwrap()
+        .into_iter()
+        .map(|message| message.message_id)
+        .collect();
+    assert_eq!(history, vec!["claim-1-request", "claim-1-reply"]);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_concurrent_sqlx_imports_of_one_task_store_it_once() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+    use std::sync::Arc;
+
+    let path = std::env::temp_dir().join(format!("a2a-import-{}.db", uuid::Uuid::new_v4()));
+    let url = format!("sqlite://{}?mode=rwc", path.display());
+    let storage = Arc::new(SqlxTaskStorage::new(&url).await.unwrap());
+    let claim = completed_claim("claim-1");
+
+    let imports = (0..8).map(|_| {
+        let storage = storage.clone();
+        let claim = claim.clone();
+        tokio::spawn(async move { storage.import_task(&claim).await })
+    });
+    let outcomes: Vec<_> = futures::future::join_all(imports)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(outcomes.iter().filter(|outcome| outcome.is_ok()).count(), 1);
+    let stored = storage.get_task("claim-1", None).await.unwrap();
+    assert_eq!(stored.history.unwrap().len(), 2);
+
+    drop(storage);
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,317 @@
+//! Tests for streaming task listings
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{ListTasksParams, ListTasksStreamItem, ListTasksSummary, Message, Task, TaskState},
+    port::{AsyncTaskManager, ListTasksStream},
+};
+use futures::StreamExt;
+
+async fn seed_tasks<S: AsyncTaskManager>(storage: &S, count: usize) {
+    for i in 0..count {
+        let task_id = format!("stream-{}", i);
+        storage.create_task(&task_id, "ctx-stream").await.unwrap();
+        let message = Message::user_text(format!("Expense {}", i), format!("msg-{}", i));
+        storage
+            .update_task_status(&task_id, TaskState::Working, Some(message))
+            .await
+            .unwrap();
+    }
+}
+
+fn history_params() -> ListTasksParams {
+    ListTasksParams {
+        history_length: Some(10),
+        ..Default::default()
+    }
+}
+
+/// Split the remaining items of a stream into its tasks and summary
+async fn drain(stream: &mut ListTasksStream) -> (Vec<Task>, ListTasksSummary) {
+    let mut tasks = Vec::new();
+    while let Some(item) = stream.next().await {
+        match item.unwrap() {
+            ListTasksStreamItem::Task(task) => tasks.push(*task),
+            ListTasksStreamItem::Summary(summary) => {
+                assert!(stream.next().await.is_none(), "the summary comes last");
+                return (tasks, summary);
+            }
+        }
+    }
+    panic!("stream ended without a summary");
+}
+
+/// Tasks are loaded as the consumer polls: a message added to a task after
+/// the first item was received still shows up when that task is yielded
+async fn assert_yields_incrementally<S: AsyncTaskManager>(storage: &S) {
+    seed_tasks(storage, 3).await;
+
+    let mut stream = storage.list_tasks_stream(&history_params()).await.unwrap();
+    let first = match stream.next().await.unwrap().unwrap() {
+        ListTasksStreamItem::Task(task) => task,
+        other => panic!("Expected a task first, got {:?}", other),
+    };
+
+    let later_id = ["stream-0", "stream-1", "stream-2"]
+        .into_iter()
+        .find(|id| *id != first.id)
+        .unwrap();
+    let late = Message::agent_text("Added mid-stream".to_string(), "msg-late".to_string());
+    storage
+        .update_task_status(later_id, TaskState::Working, Some(late))
+        .await
+        .unwrap();
+
+    let (rest, summary) = drain(&mut stream).await;
+    assert_eq!(rest.len(), 2);
+    let later = rest.iter().find(|task| task.id == later_id).unwrap();
+    let history = later.history.as_ref().unwrap();
+    assert!(
+        history
+            .iter()
+            .any(|message| message.message_id == "msg-late")
+    );
+
+    assert_eq!(summary.count, 3);
+    assert_eq!(summary.total_size, 3);
+    assert_eq!(summary.next_page_token, "");
+}
+
+#[tokio::test]
+async fn test_stream_yields_tasks_incrementally() {
+    let storage = InMemoryTaskStorage::new();
+    assert_yields_incrementally(&storage).await;
+}
+
+#[tokio::test]
+async fn test_stream_matches_buffered_listing() {
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 5).await;
+
+    let params = ListTasksParams {
+        page_size: Some(2),
+        ..Default::default()
+    };
+    let listed = storage.list_tasks_v3(&params).await.unwrap();
+    let mut stream = storage.list_tasks_stream(&params).await.unwrap();
+    let (tasks, summary) = drain(&mut stream).await;
+
+    // page_size bounds the stream just like the buffered listing
+    let ids: Vec<_> = tasks.iter().map(|task| task.id.clone()).collect();
+    let listed_ids: Vec<_> = listed.tasks.iter().map(|task| task.id.clone()).collect();
+    assert_eq!(ids, listed_ids);
+    assert_eq!(
+        summary,
+        ListTasksSummary {
+            count: 2,
+            total_size: 5,
+            page_size: 2,
+            next_page_token: listed.next_page_token.clone(),
+        }
+    );
+    assert!(tasks.iter().all(|task| task.history.is_none()));
+
+    // Following the token streams the next page
+    let params = ListTasksParams {
+        page_size: Some(2),
+        page_token: Some(summary.next_page_token),
+        ..Default::default()
+    };
+    let mut stream = storage.list_tasks_stream(&params).await.unwrap();
+    let (tasks, summary) = drain(&mut stream).await;
+    assert_eq!(tasks.len(), 2);
+    assert!(tasks.iter().all(|task| !ids.contains(&task.id)));
+    assert_eq!(summary.count, 2);
+}
+
+#[tokio::test]
+async fn test_stream_skips_tasks_deleted_mid_stream() {
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 3).await;
+
+    let mut stream = storage
+        .list_tasks_stream(&ListTasksParams::default())
+        .await
+        .unwrap();
+    let Some(Ok(ListTasksStreamItem::Task(first))) = stream.next().await else {
+        panic!("Expected a task first");
+    };
+    let others: Vec<String> = ["stream-0", "stream-1", "stream-2"]
+        .into_iter()
+        .filter(|id| *id != first.id)
+        .map(String::from)
+        .collect();
+    storage.delete_tasks(&others[..1]).await.unwrap();
+
+    let (rest, summary) = drain(&mut stream).await;
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest[0].id, others[1]);
+    // The running count covers the tasks actually yielded
+    assert_eq!(summary.count, 2);
+    assert_eq!(summary.total_size, 3);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_stream_yields_tasks_incrementally() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    assert_yields_incrementally(&storage).await;
+}
+
+#[tokio::test]
+async fn test_list_stream_request_is_answered_whole_without_a_streaming_transport() {
+    use a2a_rs::{
+        adapter::{DefaultRequestProcessor, SimpleAgentInfo, business::DefaultMessageHandler},
+        services::AsyncA2ARequestProcessor,
+    };
+    use serde_json::{Value, json};
+
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 3).await;
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        SimpleAgentInfo::new("Lister".to_string(), "http://localhost".to_string()),
+    );
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/listStream",
+        "params": {"pageSize": 2},
+    });
+    let response = processor
+        .process_raw_request(&request.to_string())
+        .await
+        .unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["result"]["tasks"].as_array().unwrap().len(), 2);
+    assert_eq!(response["result"]["totalSize"], 3);
+}
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+#[tokio::test]
+async fn test_websocket_streams_a_listing_frame_by_frame() {
+    use std::time::Duration;
+
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, SimpleAgentInfo, WebSocketClient, WebSocketServer,
+            business::DefaultMessageHandler,
+        },
+        services::AsyncA2AClient,
+    };
+
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 5).await;
+    let agent_info = SimpleAgentInfo::new("Lister".to_string(), "ws://127.0.0.1:8381".to_string());
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = WebSocketServer::new(
+        processor,
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8381".to_string(),
+    );
+    let server = tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let params = ListTasksParams {
+        page_size: Some(2),
+        history_length: Some(10),
+        ..Default::default()
+    };
+    let listed = storage.list_tasks_v3(&params).await.unwrap();
+    let client = WebSocketClient::new("ws://127.0.0.1:8381".to_string());
+    let mut stream = client.list_tasks_stream(&params).await.unwrap();
+    let (tasks, summary) = tokio::time::timeout(Duration::from_secs(2), drain(&mut stream))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = tasks.iter().map(|task| task.id.clone()).collect();
+    let listed_ids: Vec<_> = listed.tasks.iter().map(|task| task.id.clone()).collect();
+    assert_eq!(ids, listed_ids);
+    assert!(tasks.iter().all(|task| task.history.is_some()));
+    assert_eq!(
+        summary,
+        ListTasksSummary {
+            count: 2,
+            total_size: 5,
+            page_size: 2,
+            next_page_token: listed.next_page_token,
+        }
+    );
+
+    // The listing's frames are all consumed, so the connection carries on
+    let task = client.get_task("stream-0", None).await.unwrap();
+    assert_eq!(task.id, "stream-0");
+
+    server.abort();
+}
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+#[tokio::test]
+async fn test_websocket_listing_refused_by_middleware_ends_with_its_error() {
+    use std::time::Duration;
+
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, SimpleAgentInfo, WebSocketClient, WebSocketServer,
+            business::DefaultMessageHandler,
+        },
+        domain::A2AError,
+        services::{MiddlewareChain, RequestMiddleware, ServerRequest},
+    };
+    use async_trait::async_trait;
+
+    struct RefuseListings;
+
+    #[async_trait]
+    impl RequestMiddleware for RefuseListings {
+        async fn before_dispatch(&self, request: &mut ServerRequest) -> Result<(), A2AError> {
+            if request.method() == "tasks/listStream" {
+                return Err(A2AError::MethodNotAuthorized(request.method().to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage, 2).await;
+    let agent_info = SimpleAgentInfo::new("Lister".to_string(), "ws://127.0.0.1:8382".to_string());
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    )
+    .with_middleware(MiddlewareChain::new().with(RefuseListings));
+    let server = WebSocketServer::new(
+        processor,
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8382".to_string(),
+    );
+    let server = tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = WebSocketClient::new("ws://127.0.0.1:8382".to_string());
+    let mut stream = client
+        .list_tasks_stream(&ListTasksParams::default())
+        .await
+        .unwrap();
+    let first = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .unwrap();
+    assert!(matches!(first, Some(Err(A2AError::JsonRpc { .. }))));
+    assert!(stream.next().await.is_none());
+
+    server.abort();
+}
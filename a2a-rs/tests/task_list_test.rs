@@ -463,6 +463,7 @@ async fn test_task_list_include_artifacts() {
         }],
         metadata: None,
         extensions: None,
+        created_at: None,
     });
 
     // Update task in storage
@@ -483,14 +484,14 @@ async fn test_task_list_include_artifacts() {
         .await
         .expect("Failed to list tasks");
 
-    let _task = result
+    let listed = result
         .tasks
         .iter()
         .find(|t| t.id == task_id)
         .expect("Task not found");
 
     assert!(
-        task.artifacts.is_none(),
+        listed.artifacts.is_none(),
         "Artifacts should be excluded when include_artifacts is false"
     );
 
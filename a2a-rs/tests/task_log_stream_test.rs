@@ -0,0 +1,189 @@
+//! Tests for the per-task debug log stream
+
+#![cfg(all(feature = "http-client", feature = "http-server", feature = "tracing"))]
+
+use std::time::Duration;
+
+use a2a_rs::{
+    adapter::{
+        BearerTokenAuthenticator, DefaultRequestProcessor, HttpClient, HttpServer,
+        InMemoryTaskStorage, SimpleAgentInfo, TaskLogConfig, business::DefaultMessageHandler,
+    },
+    domain::Message,
+    observability::{TaskLogEntry, TaskLogHub},
+    services::AsyncA2AClient,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+use tracing_subscriber::prelude::*;
+
+const ADDRESS: &str = "127.0.0.1:8318";
+
+/// Open the log stream and return the status line and a reader over the body
+async fn open_stream(
+    task_id: &str,
+    token: Option<&str>,
+    last_event_id: Option<u64>,
+) -> (String, BufReader<TcpStream>) {
+    let mut socket = TcpStream::connect(ADDRESS).await.unwrap();
+    let mut head = format!(
+        "GET /tasks/{}/logs HTTP/1.1\r\nHost: {}\r\nAccept: text/event-stream\r\n",
+        task_id, ADDRESS
+    );
+    if let Some(token) = token {
+        head.push_str(&format!("Authorization: Bearer {}\r\n", token));
+    }
+    if let Some(id) = last_event_id {
+        head.push_str(&format!("Last-Event-ID: {}\r\n", id));
+    }
+    head.push_str("\r\n");
+    socket.write_all(head.as_bytes()).await.unwrap();
+
+    let mut reader = BufReader::new(socket);
+    let mut status = String::new();
+    reader.read_line(&mut status).await.unwrap();
+    // Skip the response headers
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).await.unwrap();
+        if line == "\r\n" {
+            break;
+        }
+    }
+    (status, reader)
+}
+
+/// Read `log` events until one matches, returning the SSE id and the entry
+async fn next_entry_where(
+    reader: &mut BufReader<TcpStream>,
+    matches: impl Fn(&TaskLogEntry) -> bool,
+) -> (String, TaskLogEntry) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        let mut id = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            // Chunked framing lines carry no SSE fields and are skipped
+            if let Some(value) = line.trim_end().strip_prefix("id: ") {
+                id = value.to_string();
+            } else if let Some(data) = line.trim_end().strip_prefix("data: ") {
+                let entry: TaskLogEntry = serde_json::from_str(data).unwrap();
+                if matches(&entry) {
+                    return (id.clone(), entry);
+                }
+            }
+        }
+    })
+    .await
+    .expect("log line never arrived on the stream")
+}
+
+#[tokio::test]
+async fn test_processing_logs_appear_on_the_task_stream() {
+    let hub = TaskLogHub::new();
+    tracing_subscriber::registry().with(hub.layer()).init();
+
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new("Log Agent".to_string(), format!("http://{}", ADDRESS));
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage,
+        agent_info.clone(),
+    );
+    let config = TaskLogConfig::new(BearerTokenAuthenticator::new(vec![
+        "debug-token".to_string(),
+    ]))
+    .with_enabled(true);
+    let server =
+        HttpServer::new(processor, agent_info, ADDRESS.to_string()).with_task_logs(hub, config);
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Only debug tokens may read logs
+    let (status, _) = open_stream("expense-1", None, None).await;
+    assert!(status.contains("401"), "{}", status);
+    let (status, _) = open_stream("expense-1", Some("wrong"), None).await;
+    assert!(status.contains("401"), "{}", status);
+
+    let (status, mut reader) = open_stream("expense-1", Some("debug-token"), None).await;
+    assert!(status.contains("200"), "{}", status);
+
+    // A line logged while the server processes the task shows up live
+    let client = HttpClient::new(format!("http://{}", ADDRESS));
+    let message = Message::user_text("Lunch, $20".to_string(), "msg-log".to_string());
+    client
+        .send_task_message("expense-1", &message, None, None)
+        .await
+        .unwrap();
+    let (id, entry) = next_entry_where(&mut reader, |entry| {
+        entry.message.contains("About to call message_handler")
+    })
+    .await;
+    assert_eq!(id, entry.id.to_string());
+    assert_eq!(entry.task_id, "expense-1");
+    assert_eq!(entry.level, "INFO");
+    assert_eq!(entry.fields["message_id"], "msg-log");
+    assert!(entry.timestamp <= chrono::Utc::now());
+
+    // Events inherit the task from their span, and sensitive fields are hidden
+    tracing::info_span!("call_expense_api", task.id = "expense-1").in_scope(|| {
+        tracing::warn!(
+            api_token = "sk-live-123",
+            amount = 20,
+            "Calling expense API"
+        );
+    });
+    let (_, redacted) =
+        next_entry_where(&mut reader, |entry| entry.message == "Calling expense API").await;
+    assert_eq!(redacted.level, "WARN");
+    assert_eq!(redacted.fields["api_token"], "[REDACTED]");
+    assert_eq!(redacted.fields["amount"], 20);
+
+    // Reconnecting with Last-Event-ID resumes after the last line seen
+    let (_, mut resumed) = open_stream("expense-1", Some("debug-token"), Some(entry.id)).await;
+    let (_, next) = next_entry_where(&mut resumed, |_| true).await;
+    assert!(next.id > entry.id);
+    assert_eq!(next.task_id, "expense-1");
+
+    // Lines of other tasks never reach the stream
+    tracing::info!(task_id = "expense-2", "Other task");
+    tracing::info!(task_id = "expense-1", "Still watching");
+    let (_, next) =
+        next_entry_where(&mut reader, |entry| entry.message != "Calling expense API").await;
+    assert_eq!(next.message, "Still watching");
+}
+
+#[tokio::test]
+async fn test_task_log_stream_is_off_by_default() {
+    let hub = TaskLogHub::new();
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Quiet Agent".to_string(),
+        "http://127.0.0.1:8319".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage,
+        agent_info.clone(),
+    );
+    let config = TaskLogConfig::new(BearerTokenAuthenticator::new(vec![
+        "debug-token".to_string(),
+    ]));
+    assert!(!config.is_enabled());
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8319".to_string())
+        .with_task_logs(hub, config);
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let response = reqwest::Client::new()
+        .get("http://127.0.0.1:8319/tasks/expense-1/logs")
+        .bearer_auth("debug-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,224 @@
+//! Tests for leaving parts of a task out of `tasks/get` responses
+
+mod common;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{Artifact, Message, Part, Task, TaskState},
+    port::AsyncTaskManager,
+    services::AsyncA2ARequestProcessor,
+};
+use common::{Processor, processor};
+use serde_json::{Value, json};
+
+const RECEIPT: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk";
+
+/// An expense task with a receipt attached to its message and artifact
+fn receipt_task() -> Task {
+    let mut message = Message::user_text("Here is my taxi receipt".to_string(), "m-1".to_string());
+    message.add_part(Part::file_from_bytes(
+        RECEIPT.to_string(),
+        Some("receipt.png".to_string()),
+        Some("image/png".to_string()),
+    ));
+    message.metadata = json!({"client": "chat"}).as_object().cloned();
+    let mut task = Task::new("expense-1".to_string(), "expenses".to_string());
+    task.update_status(TaskState::Working, Some(message));
+    task.add_artifact(Artifact {
+        artifact_id: "scan".to_string(),
+        name: Some("Scanned receipt".to_string()),
+        description: None,
+        parts: vec![
+            Part::text("Scanned receipt".to_string()),
+            Part::file_from_bytes(RECEIPT.to_string(), Some("scan.png".to_string()), None),
+        ],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    });
+    task.metadata = json!({"department": "sales"}).as_object().cloned();
+    task
+}
+
+async fn processor_with_receipt() -> Processor {
+    let storage = InMemoryTaskStorage::new();
+    storage.import_task(&receipt_task()).await.unwrap();
+    processor(&storage)
+}
+
+async fn get_task(
+    processor: &impl AsyncA2ARequestProcessor,
+    method: &str,
+    exclude: Value,
+) -> Value {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": {"id": "expense-1", "exclude": exclude},
+    });
+    let response = processor
+        .process_raw_request(&request.to_string())
+        .await
+        .unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    match method {
+        "tasks/getOrCreate" => response["result"]["task"].clone(),
+        _ => response["result"].clone(),
+    }
+}
+
+#[tokio::test]
+async fn test_excluding_file_bytes_keeps_text_and_references_the_files() {
+    let processor = processor_with_receipt().await;
+
+    let task = get_task(&processor, "tasks/get", json!(["fileBytes"])).await;
+    let parts = &task["history"][0]["parts"];
+    assert_eq!(parts[0]["text"], "Here is my taxi receipt");
+    let file = &parts[1]["file"];
+    assert!(file.get("bytes").is_none(), "{}", file);
+    assert_eq!(file["name"], "receipt.png");
+    assert_eq!(file["mimeType"], "image/png");
+    assert_eq!(file["uri"], "urn:a2a:task:expense-1:message:m-1:part:1");
+    let file = &task["artifacts"][0]["parts"][1]["file"];
+    assert!(file.get("bytes").is_none(), "{}", file);
+    assert_eq!(file["uri"], "urn:a2a:task:expense-1:artifact:scan:part:1");
+    // Only the bytes were left out
+    assert_eq!(task["metadata"]["department"], "sales");
+    assert_eq!(task["history"][0]["metadata"]["client"], "chat");
+
+    // The response is still a valid task
+    let task: Task = serde_json::from_value(task).unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+
+    // Without an exclusion the bytes are still there
+    let task = get_task(&processor, "tasks/get", json!([])).await;
+    assert_eq!(task["history"][0]["parts"][1]["file"]["bytes"], RECEIPT);
+}
+
+#[tokio::test]
+async fn test_excluded_fields_are_left_out() {
+    let processor = processor_with_receipt().await;
+
+    let task = get_task(
+        &processor,
+        "tasks/get",
+        json!(["history", "artifacts", "metadata"]),
+    )
+    .await;
+    assert!(task.get("history").is_none(), "{}", task);
+    assert!(task.get("historyCursor").is_none(), "{}", task);
+    assert!(task.get("artifacts").is_none(), "{}", task);
+    assert!(task.get("metadata").is_none(), "{}", task);
+    assert!(task["status"]["message"].get("metadata").is_none());
+    assert_eq!(task["status"]["state"], "working");
+
+    let task = get_task(&processor, "tasks/getOrCreate", json!(["fileBytes"])).await;
+    let file = &task["history"][0]["parts"][1]["file"];
+    assert!(file.get("bytes").is_none(), "{}", file);
+
+    // Unknown fields are refused rather than ignored
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/get",
+        "params": {"id": "expense-1", "exclude": ["everything"]},
+    });
+    let response = processor
+        .process_raw_request(&request.to_string())
+        .await
+        .unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert!(response["error"].is_object(), "{}", response);
+}
+
+/// Check that `storage` leaves each combination of fields out as the
+/// stripped full task does, while keeping the stored task whole
+async fn assert_reads_exclude_fields(storage: &impl AsyncTaskManager) {
+    use a2a_rs::domain::TaskField;
+
+    let fields = [
+        TaskField::History,
+        TaskField::Artifacts,
+        TaskField::Metadata,
+        TaskField::FileBytes,
+    ];
+    let full = storage.get_task("expense-1", None).await.unwrap();
+    for mask in 0..1 << fields.len() {
+        let excluded: Vec<TaskField> = (0..fields.len())
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| fields[bit])
+            .collect();
+        for history_length in [None, Some(1)] {
+            let read = storage
+                .get_task_excluding("expense-1", history_length, &excluded)
+                .await
+                .unwrap();
+            let expected = full
+                .with_limited_history(history_length)
+                .without_fields(&excluded);
+            assert_eq!(
+                serde_json::to_value(&read).unwrap(),
+                serde_json::to_value(&expected).unwrap(),
+                "excluding {:?} with history length {:?}",
+                excluded,
+                history_length
+            );
+        }
+    }
+
+    let stored = storage.get_task("expense-1", None).await.unwrap();
+    assert_eq!(
+        serde_json::to_value(&stored).unwrap(),
+        serde_json::to_value(&full).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_in_memory_reads_leave_excluded_fields_out() {
+    let storage = InMemoryTaskStorage::new();
+    storage.import_task(&receipt_task()).await.unwrap();
+    assert_reads_exclude_fields(&storage).await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlite_reads_leave_excluded_fields_out() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    storage.import_task(&receipt_task()).await.unwrap();
+    assert_reads_exclude_fields(&storage).await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_excluded_history_is_not_loaded_from_sqlite() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let path = std::env::temp_dir().join(format!("a2a-projection-{}.db", uuid::Uuid::new_v4()));
+    let url = format!("sqlite://{}?mode=rwc", path.display());
+    let storage = SqlxTaskStorage::new(&url).await.unwrap();
+    storage.create_task("expense-1", "expenses").await.unwrap();
+    let message = Message::user_text("Reimburse my taxi".to_string(), "m-1".to_string());
+    storage
+        .update_task_status("expense-1", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+
+    let task = storage.get_task("expense-1", Some(0)).await.unwrap();
+    assert!(task.history.is_none());
+    assert_eq!(task.status.state, TaskState::Working);
+    assert_eq!(
+        storage
+            .get_task("expense-1", None)
+            .await
+            .unwrap()
+            .history
+            .map(|history| history.len()),
+        Some(1)
+    );
+
+    drop(storage);
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,134 @@
+//! Tests for messages that reference earlier tasks
+
+mod common;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    application::json_rpc::{A2ARequest, SendTaskRequest},
+    domain::{A2AError, Message, TaskSendParams, TaskState},
+    port::AsyncTaskManager,
+    services::AsyncA2ARequestProcessor,
+};
+use common::processor;
+
+fn send(task_id: &str, references: &[&str]) -> A2ARequest {
+    let mut message = Message::user_text(
+        "Correct the amount to $25".to_string(),
+        format!("msg-{}", task_id),
+    );
+    message.reference_task_ids = Some(references.iter().map(|id| id.to_string()).collect());
+    A2ARequest::SendTask(SendTaskRequest::new(TaskSendParams {
+        id: task_id.to_string(),
+        session_id: None,
+        message,
+        push_notification: None,
+        history_length: None,
+        metadata: None,
+        expected_version: None,
+    }))
+}
+
+/// Seed an original task and a correction of it, then check both directions
+/// of the link
+async fn assert_references<S: AsyncTaskManager>(storage: &S) {
+    storage.create_task("original", "ctx-1").await.unwrap();
+    storage.create_task("correction", "ctx-1").await.unwrap();
+    let mut message = Message::user_text("Use $25".to_string(), "msg-2".to_string());
+    message.reference_task_ids = Some(vec![
+        "original".to_string(),
+        "original".to_string(),
+        "correction".to_string(),
+    ]);
+    storage
+        .update_task_status("correction", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+
+    // Duplicates and self references are dropped
+    let task = storage.get_task("correction", None).await.unwrap();
+    assert_eq!(task.reference_task_ids, vec!["original".to_string()]);
+
+    let referencing = storage.list_referencing_tasks("original").await.unwrap();
+    let ids: Vec<&str> = referencing.iter().map(|task| task.id.as_str()).collect();
+    assert_eq!(ids, vec!["correction"]);
+    assert!(referencing[0].history.is_none());
+
+    assert!(
+        storage
+            .list_referencing_tasks("correction")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(matches!(
+        storage.list_referencing_tasks("missing").await,
+        Err(A2AError::TaskNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_send_rejects_unknown_references() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+
+    let error = processor
+        .process_request(&send("correction", &["missing"]))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        A2AError::ValidationError { ref field, .. } if field == "referenceTaskIds"
+    ));
+    assert!(!storage.task_exists("correction").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_send_records_references() {
+    let storage = InMemoryTaskStorage::new();
+    let processor = processor(&storage);
+
+    processor
+        .process_request(&send("original", &[]))
+        .await
+        .unwrap();
+    processor
+        .process_request(&send("correction", &["original"]))
+        .await
+        .unwrap();
+
+    let task = storage.get_task("correction", None).await.unwrap();
+    assert_eq!(task.reference_task_ids, vec!["original".to_string()]);
+    let json = serde_json::to_value(&task).unwrap();
+    assert_eq!(json["referenceTaskIds"], serde_json::json!(["original"]));
+
+    let referencing = storage.list_referencing_tasks("original").await.unwrap();
+    assert_eq!(referencing.len(), 1);
+    assert_eq!(referencing[0].id, "correction");
+}
+
+#[tokio::test]
+async fn test_in_memory_reverse_lookup() {
+    assert_references(&InMemoryTaskStorage::new()).await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_references_are_persisted() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    assert_references(&storage).await;
+
+    // Deleting a task drops the links it made
+    storage
+        .delete_tasks(&["correction".to_string()])
+        .await
+        .unwrap();
+    assert!(
+        storage
+            .list_referencing_tasks("original")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,270 @@
+//! Tests for structured skill results attached to tasks
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{
+        A2AError, AgentSkill, GetTaskEventsParams, Message, Task, TaskLogEvent, TaskResult,
+        TaskState,
+    },
+    port::AsyncTaskManager,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct ReceiptSummary {
+    merchant: String,
+    total: f64,
+    category: String,
+    line_items: Vec<String>,
+}
+
+fn summarize_skill() -> AgentSkill {
+    AgentSkill::new(
+        "summarize_receipt".to_string(),
+        "Summarize Receipt".to_string(),
+        "Extract the totals from a receipt".to_string(),
+        vec!["receipts".to_string()],
+    )
+    .with_output_schema(json!({
+        "type": "object",
+        "required": ["merchant", "total", "category"],
+        "additionalProperties": false,
+        "properties": {
+            "merchant": {"type": "string"},
+            "total": {"type": "number"},
+            "category": {"type": "string", "enum": ["meals", "travel", "supplies"]},
+            "line_items": {"type": "array", "items": {"type": "string"}}
+        }
+    }))
+}
+
+fn summary() -> ReceiptSummary {
+    ReceiptSummary {
+        merchant: "Corner Cafe".to_string(),
+        total: 23.5,
+        category: "meals".to_string(),
+        line_items: vec!["Sandwich".to_string(), "Coffee".to_string()],
+    }
+}
+
+fn summary_result() -> TaskResult {
+    TaskResult::from_value(&summary())
+        .unwrap()
+        .with_skill_id("summarize_receipt".to_string())
+}
+
+/// Assert a result conforms to the skill's declared schema using a full validator
+fn assert_matches_schema(skill: &AgentSkill, data: &Value) {
+    let validator = jsonschema::validator_for(skill.output_schema.as_ref().unwrap()).unwrap();
+    assert!(validator.is_valid(data), "{} does not match schema", data);
+}
+
+#[test]
+fn test_skill_output_schema_validation() {
+    let skill = summarize_skill();
+    skill.validate_result(&summary_result()).unwrap();
+
+    let missing = TaskResult::new(json!({"merchant": "Corner Cafe", "category": "meals"}));
+    let err = skill.validate_result(&missing).unwrap_err();
+    match err {
+        A2AError::ValidationError { field, .. } => assert_eq!(field, "result.total"),
+        other => panic!("Expected validation error, got {:?}", other),
+    }
+
+    let wrong_type = TaskResult::new(json!({
+        "merchant": "Corner Cafe",
+        "total": 23.5,
+        "category": "meals",
+        "line_items": ["Sandwich", 2]
+    }));
+    let err = skill.validate_result(&wrong_type).unwrap_err();
+    assert!(err.to_string().contains("result.line_items[1]"));
+
+    let bad_enum = TaskResult::new(json!({
+        "merchant": "Corner Cafe",
+        "total": 23.5,
+        "category": "gifts"
+    }));
+    assert!(skill.validate_result(&bad_enum).is_err());
+
+    let extra = TaskResult::new(json!({
+        "merchant": "Corner Cafe",
+        "total": 23.5,
+        "category": "meals",
+        "tip": 2
+    }));
+    assert!(skill.validate_result(&extra).is_err());
+
+    // Skills without a schema accept any result
+    let untyped = AgentSkill::new(
+        "echo".to_string(),
+        "Echo".to_string(),
+        "Echo input".to_string(),
+        vec![],
+    );
+    untyped.validate_result(&missing).unwrap();
+}
+
+#[tokio::test]
+async fn test_completed_task_exposes_structured_result() {
+    let storage = InMemoryTaskStorage::new();
+    let skill = summarize_skill();
+    let task_id = "result-task";
+
+    storage.create_task(task_id, "ctx-results").await.unwrap();
+    let working = storage
+        .update_task_status(task_id, TaskState::Working, None)
+        .await
+        .unwrap();
+
+    let result = summary_result();
+    skill.validate_result(&result).unwrap();
+    let reply = Message::agent_text("Receipt summarized".to_string(), "msg-done".to_string());
+    let completed = storage
+        .complete_task_with_result(task_id, result.clone(), Some(reply))
+        .await
+        .unwrap();
+    assert_eq!(completed.status.state, TaskState::Completed);
+    // The result and the status land in one write
+    assert_eq!(completed.version, working.version + 1);
+
+    // get_task exposes the result, typed via the skill's schema
+    let task = storage.get_task(task_id, None).await.unwrap();
+    let stored = task.result.clone().unwrap();
+    assert_eq!(stored.skill_id.as_deref(), Some("summarize_receipt"));
+    assert_matches_schema(&skill, &stored.data);
+    assert_eq!(stored.parse::<ReceiptSummary>().unwrap(), summary());
+
+    // The result survives serialization as a first-class task field
+    let wire = serde_json::to_value(&task).unwrap();
+    assert_eq!(wire["result"]["skillId"], "summarize_receipt");
+    assert_eq!(wire["result"]["data"]["total"], 23.5);
+    let decoded: Task = serde_json::from_value(wire).unwrap();
+    assert_eq!(decoded.result, Some(result.clone()));
+
+    // Only the terminal status event carries the result
+    let events = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: task_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let updates: Vec<_> = events
+        .events
+        .iter()
+        .filter_map(|record| match &record.event {
+            TaskLogEvent::StatusUpdate(update) => Some(update),
+            _ => None,
+        })
+        .collect();
+    let (last, earlier) = updates.split_last().unwrap();
+    assert!(last.final_);
+    assert_eq!(last.result, Some(result.clone()));
+    assert!(earlier.iter().all(|update| update.result.is_none()));
+
+    // The log records the result ahead of the completion it came with
+    let kinds: Vec<&str> = events
+        .events
+        .iter()
+        .map(|record| record.event.event_type())
+        .collect();
+    assert_eq!(
+        kinds[kinds.len() - 3..],
+        ["resultSet", "messageAppended", "statusUpdate"]
+    );
+    match &events.events[kinds.len() - 3].event {
+        TaskLogEvent::ResultSet { result: logged } => assert_eq!(logged, &result),
+        other => panic!("Expected result event, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_tasks_without_results_omit_the_field() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("plain-task", "ctx").await.unwrap();
+    storage
+        .update_task_status("plain-task", TaskState::Completed, None)
+        .await
+        .unwrap();
+
+    let task = storage.get_task("plain-task", None).await.unwrap();
+    assert!(task.result.is_none());
+    assert!(serde_json::to_value(&task).unwrap().get("result").is_none());
+
+    let err = storage
+        .set_task_result("missing-task", summary_result())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, A2AError::TaskNotFound(_)));
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_task_result_round_trip() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    let task_id = "sqlx-result-task";
+    storage.create_task(task_id, "ctx-results").await.unwrap();
+
+    // A result set while working is stored but not yet final
+    storage
+        .set_task_result(task_id, summary_result())
+        .await
+        .unwrap();
+    let working = storage
+        .update_task_status(task_id, TaskState::Working, None)
+        .await
+        .unwrap();
+    assert!(working.final_result().is_none());
+
+    let task = storage
+        .complete_task_with_result(task_id, summary_result(), None)
+        .await
+        .unwrap();
+    assert_eq!(task.version, working.version + 1);
+    assert_eq!(task.result, Some(summary_result()));
+    assert_eq!(
+        task.result.unwrap().parse::<ReceiptSummary>().unwrap(),
+        summary()
+    );
+
+    let events = storage
+        .get_task_events(&GetTaskEventsParams {
+            id: task_id.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    match &events.events.last().unwrap().event {
+        TaskLogEvent::StatusUpdate(update) => {
+            assert_eq!(update.status.state, TaskState::Completed);
+            assert_eq!(update.result, Some(summary_result()));
+        }
+        other => panic!("Expected status update, got {:?}", other),
+    }
+    // Both results were logged when set
+    let kinds: Vec<&str> = events
+        .events
+        .iter()
+        .map(|record| record.event.event_type())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "statusUpdate",
+            "resultSet",
+            "statusUpdate",
+            "resultSet",
+            "statusUpdate"
+        ]
+    );
+
+    let err = storage
+        .set_task_result("missing-task", summary_result())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, A2AError::TaskNotFound(_)));
+}
@@ -0,0 +1,237 @@
+//! Tests for task snapshots carried on status update events
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+mod common;
+
+use std::sync::Arc;
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{
+        A2AError, Artifact, Message, Part, TaskSnapshotOptions, TaskState, TaskStatusUpdateEvent,
+    },
+    port::{AsyncStreamingHandler, AsyncTaskManager, streaming_handler::Subscriber},
+};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+/// Records the status updates it receives
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    snapshots: Option<TaskSnapshotOptions>,
+    updates: Arc<Mutex<Vec<TaskStatusUpdateEvent>>>,
+}
+
+impl RecordingSubscriber {
+    fn with_snapshots(options: TaskSnapshotOptions) -> Self {
+        Self {
+            snapshots: Some(options),
+            ..Default::default()
+        }
+    }
+
+    async fn take(&self) -> Vec<TaskStatusUpdateEvent> {
+        std::mem::take(&mut *self.updates.lock().await)
+    }
+}
+
+#[async_trait]
+impl Subscriber<TaskStatusUpdateEvent> for RecordingSubscriber {
+    async fn on_update(&self, update: TaskStatusUpdateEvent) -> Result<(), A2AError> {
+        self.updates.lock().await.push(update);
+        Ok(())
+    }
+
+    fn task_snapshots(&self) -> Option<TaskSnapshotOptions> {
+        self.snapshots.clone()
+    }
+}
+
+async fn subscribe<S: AsyncStreamingHandler>(storage: &S, subscribers: &[&RecordingSubscriber]) {
+    for subscriber in subscribers {
+        storage
+            .add_status_subscriber("expense", Box::new((*subscriber).clone()))
+            .await
+            .unwrap();
+    }
+    // Drop the current status sent to every subscriber on each subscription
+    for subscriber in subscribers {
+        subscriber.take().await;
+    }
+}
+
+/// A message append reaches opted-in subscribers inline, and only them
+async fn assert_message_append_is_inline<S>(storage: &S)
+where
+    S: AsyncTaskManager + AsyncStreamingHandler,
+{
+    storage.create_task("expense", "ctx-snap").await.unwrap();
+    let full = RecordingSubscriber::with_snapshots(TaskSnapshotOptions::new());
+    let plain = RecordingSubscriber::default();
+    subscribe(storage, &[&full, &plain]).await;
+
+    let message = Message::user_text("Taxi to the airport".to_string(), "msg-taxi".to_string());
+    storage
+        .update_task_status("expense", TaskState::Working, Some(message))
+        .await
+        .unwrap();
+
+    let updates = full.take().await;
+    assert_eq!(updates.len(), 1);
+    let task = updates[0].task.as_ref().expect("snapshot sent inline");
+    assert_eq!(task.id, "expense");
+    assert_eq!(task.status.state, TaskState::Working);
+    let history = task.history.as_ref().unwrap();
+    assert!(history.iter().any(|entry| entry.message_id == "msg-taxi"));
+
+    let updates = plain.take().await;
+    assert_eq!(updates.len(), 1);
+    assert!(updates[0].task.is_none());
+    assert!(
+        serde_json::to_value(&updates[0])
+            .unwrap()
+            .get("task")
+            .is_none()
+    );
+
+    // A status change without a new message is sent as a plain delta
+    storage
+        .update_task_status("expense", TaskState::InputRequired, None)
+        .await
+        .unwrap();
+    let updates = full.take().await;
+    assert_eq!(updates.len(), 1);
+    assert!(updates[0].task.is_none());
+}
+
+#[tokio::test]
+async fn test_message_append_delivers_history_inline() {
+    let storage = InMemoryTaskStorage::new();
+    assert_message_append_is_inline(&storage).await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_message_append_delivers_history_inline() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    assert_message_append_is_inline(&storage).await;
+}
+
+#[tokio::test]
+async fn test_snapshot_subset() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-snap").await.unwrap();
+    let recent = RecordingSubscriber::with_snapshots(
+        TaskSnapshotOptions::new()
+            .with_history_length(1)
+            .without_artifacts(),
+    );
+    subscribe(&storage, &[&recent]).await;
+
+    for i in 0..3 {
+        let message = Message::user_text(format!("Receipt {}", i), format!("msg-{}", i));
+        storage
+            .update_task_status("expense", TaskState::Working, Some(message))
+            .await
+            .unwrap();
+    }
+    let updates = recent.take().await;
+    assert_eq!(updates.len(), 3);
+    let task = updates[2].task.as_ref().unwrap();
+    let history = task.history.as_ref().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].message_id, "msg-2");
+
+    // A snapshot passed to the trait broadcast is trimmed the same way
+    let mut task = storage.get_task("expense", None).await.unwrap();
+    task.add_artifact(Artifact {
+        artifact_id: "report".to_string(),
+        name: None,
+        description: None,
+        parts: vec![Part::text("Summary".to_string())],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    });
+    let event = TaskStatusUpdateEvent {
+        task_id: task.id.clone(),
+        context_id: task.context_id.clone(),
+        kind: "status-update".to_string(),
+        status: task.status.clone(),
+        final_: false,
+        result: None,
+        artifacts: None,
+        metadata: None,
+        task: Some(Box::new(task)),
+        diff: None,
+    };
+    AsyncStreamingHandler::broadcast_status_update(&storage, "expense", event)
+        .await
+        .unwrap();
+    let updates = recent.take().await;
+    let task = updates[0].task.as_ref().unwrap();
+    assert!(task.artifacts.is_none());
+    assert_eq!(task.history.as_ref().unwrap().len(), 1);
+
+    // Options default to the full task when sent over the wire
+    let options: TaskSnapshotOptions = serde_json::from_str("{}").unwrap();
+    assert_eq!(options, TaskSnapshotOptions::new());
+}
+
+#[cfg(all(feature = "ws-client", feature = "ws-server"))]
+#[tokio::test]
+async fn test_websocket_subscription_receives_snapshots() {
+    use a2a_rs::{
+        adapter::{DefaultRequestProcessor, SimpleAgentInfo, WebSocketClient, WebSocketServer},
+        services::{AsyncA2AClient, StreamItem},
+    };
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    let handler = common::TestBusinessHandler::with_storage(storage.clone());
+    let agent_info = SimpleAgentInfo::new(
+        "Snapshot Agent".to_string(),
+        "ws://127.0.0.1:8326".to_string(),
+    );
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let server = WebSocketServer::new(processor, agent_info, handler, "127.0.0.1:8326".to_string());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    storage.create_task("ws-expense", "ctx-snap").await.unwrap();
+    let client = WebSocketClient::new("ws://127.0.0.1:8326".to_string())
+        .with_task_snapshots(TaskSnapshotOptions::new());
+    let mut stream = client.subscribe_to_task("ws-expense", None).await.unwrap();
+
+    let message = Message::agent_text("Approved".to_string(), "msg-approved".to_string());
+    let update_storage = storage.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        update_storage
+            .update_task_status("ws-expense", TaskState::Working, Some(message))
+            .await
+            .unwrap();
+    });
+
+    let task = tokio::time::timeout(Duration::from_secs(3), async {
+        while let Some(item) = stream.next().await {
+            if let Ok(StreamItem::StatusUpdate(update)) = item {
+                if let Some(task) = update.task {
+                    return task;
+                }
+            }
+        }
+        panic!("stream ended without a snapshot");
+    })
+    .await
+    .expect("no snapshot received");
+    let history = task.history.as_ref().unwrap();
+    assert!(
+        history
+            .iter()
+            .any(|entry| entry.message_id == "msg-approved")
+    );
+}
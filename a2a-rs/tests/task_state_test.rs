@@ -0,0 +1,44 @@
+//! Tests for the string mapping of task states
+
+use std::collections::HashSet;
+
+use a2a_rs::domain::{A2AError, TaskState};
+
+#[test]
+fn test_every_state_round_trips_through_its_wire_name() {
+    for state in TaskState::ALL {
+        assert_eq!(state.as_str().parse::<TaskState>().unwrap(), state);
+        assert_eq!(state.to_string(), state.as_str());
+    }
+    let names: HashSet<_> = TaskState::ALL.iter().map(TaskState::as_str).collect();
+    assert_eq!(names.len(), TaskState::ALL.len());
+}
+
+#[test]
+fn test_wire_names_match_serde() {
+    for state in TaskState::ALL {
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::Value::String(state.as_str().to_string())
+        );
+    }
+    assert_eq!(TaskState::InputRequired.as_str(), "input-required");
+    assert_eq!(TaskState::AuthRequired.to_string(), "auth-required");
+}
+
+#[test]
+fn test_debug_names_and_unknown_strings_are_rejected() {
+    for name in [
+        "InputRequired",
+        "Completed",
+        "input_required",
+        " working",
+        "",
+    ] {
+        assert!(
+            matches!(name.parse::<TaskState>(), Err(A2AError::InvalidParams(_))),
+            "{:?} parsed",
+            name
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Tests for task tags and tag-filtered task listing
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{A2AError, ListTasksParams, TagMatch, TaskTagsParams},
+    port::AsyncTaskManager,
+};
+
+fn tags(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn tag_params(task_id: &str, names: &[&str]) -> TaskTagsParams {
+    TaskTagsParams {
+        id: task_id.to_string(),
+        tags: tags(names),
+        metadata: None,
+    }
+}
+
+fn tag_filter(names: &[&str], tag_match: Option<TagMatch>) -> ListTasksParams {
+    ListTasksParams {
+        tags: Some(tags(names)),
+        tag_match,
+        ..Default::default()
+    }
+}
+
+fn listed_ids(result: &a2a_rs::domain::ListTasksResult) -> Vec<String> {
+    let mut ids: Vec<String> = result.tasks.iter().map(|task| task.id.clone()).collect();
+    ids.sort();
+    ids
+}
+
+/// Create three expense tasks: one urgent travel expense from Q3, one urgent
+/// meal and one untagged
+async fn seed_tasks<S: AsyncTaskManager>(storage: &S) {
+    for task_id in ["trip", "lunch", "plain"] {
+        storage.create_task(task_id, "ctx-tags").await.unwrap();
+    }
+    storage
+        .add_task_tags(&tag_params("trip", &["urgent", "travel", "Q3"]))
+        .await
+        .unwrap();
+    storage
+        .add_task_tags(&tag_params("lunch", &["urgent", "meals"]))
+        .await
+        .unwrap();
+}
+
+/// Filtering by one tag, by several with all-match and by several with any-match
+async fn assert_tag_filters<S: AsyncTaskManager>(storage: &S) {
+    let result = storage
+        .list_tasks_v3(&tag_filter(&["urgent"], None))
+        .await
+        .unwrap();
+    assert_eq!(listed_ids(&result), vec!["lunch", "trip"]);
+    assert_eq!(result.total_size, 2);
+
+    // All-match is the default
+    let result = storage
+        .list_tasks_v3(&tag_filter(&["urgent", "travel"], None))
+        .await
+        .unwrap();
+    assert_eq!(listed_ids(&result), vec!["trip"]);
+    assert_eq!(result.total_size, 1);
+    assert_eq!(result.tasks[0].tags, tags(&["Q3", "travel", "urgent"]));
+
+    let result = storage
+        .list_tasks_v3(&tag_filter(
+            &["urgent", "travel", "meals"],
+            Some(TagMatch::All),
+        ))
+        .await
+        .unwrap();
+    assert!(result.tasks.is_empty());
+
+    let result = storage
+        .list_tasks_v3(&tag_filter(&["travel", "meals"], Some(TagMatch::Any)))
+        .await
+        .unwrap();
+    assert_eq!(listed_ids(&result), vec!["lunch", "trip"]);
+
+    // A repeated tag does not make all-match stricter
+    let result = storage
+        .list_tasks_v3(&tag_filter(&["travel", "travel"], None))
+        .await
+        .unwrap();
+    assert_eq!(listed_ids(&result), vec!["trip"]);
+
+    // Without a tag filter every task is listed
+    let result = storage
+        .list_tasks_v3(&ListTasksParams::default())
+        .await
+        .unwrap();
+    assert_eq!(result.total_size, 3);
+}
+
+#[tokio::test]
+async fn test_add_and_remove_tags() {
+    let storage = InMemoryTaskStorage::new();
+    let task = storage.create_task("expense", "ctx-tags").await.unwrap();
+    assert!(task.tags.is_empty());
+
+    // Tags are kept sorted and duplicates are dropped
+    let task = storage
+        .add_task_tags(&tag_params("expense", &["urgent", "travel", "urgent"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["travel", "urgent"]));
+    let version = task.version;
+
+    let task = storage
+        .add_task_tags(&tag_params("expense", &["Q3", "travel"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["Q3", "travel", "urgent"]));
+    assert_eq!(task.version, version, "tags do not change the task version");
+
+    // Removing a tag the task does not carry is not an error
+    let task = storage
+        .remove_task_tags(&tag_params("expense", &["urgent", "missing"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["Q3", "travel"]));
+
+    let stored = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(stored.tags, tags(&["Q3", "travel"]));
+
+    // Tags are serialized only when present
+    let json = serde_json::to_value(&stored).unwrap();
+    assert_eq!(json["tags"], serde_json::json!(["Q3", "travel"]));
+    let untagged = storage.create_task("untagged", "ctx-tags").await.unwrap();
+    assert!(
+        serde_json::to_value(&untagged)
+            .unwrap()
+            .get("tags")
+            .is_none()
+    );
+
+    let err = storage
+        .add_task_tags(&tag_params("nope", &["urgent"]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, A2AError::TaskNotFound(_)));
+}
+
+#[tokio::test]
+async fn test_invalid_tags_are_rejected() {
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("expense", "ctx-tags").await.unwrap();
+
+    let too_long = "x".repeat(33);
+    for bad in ["", "has space", "semi;colon", "émoji", too_long.as_str()] {
+        let err = storage
+            .add_task_tags(&tag_params("expense", &["ok", bad]))
+            .await
+            .unwrap_err();
+        match err {
+            A2AError::ValidationError { field, .. } => assert_eq!(field, "tags"),
+            other => panic!("Expected validation error for {:?}, got {:?}", bad, other),
+        }
+    }
+    // A rejected request adds none of its tags
+    let task = storage.get_task("expense", None).await.unwrap();
+    assert!(task.tags.is_empty());
+
+    storage
+        .add_task_tags(&tag_params(
+            "expense",
+            &["travel-2024", "Q3_review", "v1.2"],
+        ))
+        .await
+        .unwrap();
+
+    let many: Vec<String> = (0..21).map(|i| format!("tag{}", i)).collect();
+    let err = storage
+        .add_task_tags(&TaskTagsParams {
+            id: "expense".to_string(),
+            tags: many,
+            metadata: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("at most 20 tags"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_list_tasks_by_tags() {
+    let storage = InMemoryTaskStorage::new();
+    seed_tasks(&storage).await;
+    assert_tag_filters(&storage).await;
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_tags_are_persisted_and_filtered() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    seed_tasks(&storage).await;
+    assert_tag_filters(&storage).await;
+
+    let task = storage
+        .add_task_tags(&tag_params("trip", &["travel", "approved"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["Q3", "approved", "travel", "urgent"]));
+    let task = storage
+        .remove_task_tags(&tag_params("trip", &["urgent"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["Q3", "approved", "travel"]));
+
+    // Going over the limit rolls the whole request back
+    let many: Vec<String> = (0..20).map(|i| format!("tag{}", i)).collect();
+    assert!(
+        storage
+            .add_task_tags(&TaskTagsParams {
+                id: "trip".to_string(),
+                tags: many,
+                metadata: None,
+            })
+            .await
+            .is_err()
+    );
+    let task = storage.get_task("trip", None).await.unwrap();
+    assert_eq!(task.tags, tags(&["Q3", "approved", "travel"]));
+
+    assert!(
+        storage
+            .add_task_tags(&tag_params("trip", &["bad tag"]))
+            .await
+            .is_err()
+    );
+
+    // Deleting a task drops its tags with it
+    storage.delete_tasks(&tags(&["trip"])).await.unwrap();
+    storage.create_task("trip", "ctx-tags").await.unwrap();
+    assert!(
+        storage
+            .get_task("trip", None)
+            .await
+            .unwrap()
+            .tags
+            .is_empty()
+    );
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_tag_rpcs_over_http() {
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, HttpClient, HttpServer, SimpleAgentInfo,
+            business::DefaultMessageHandler,
+        },
+        domain::error::INVALID_PARAMS,
+        services::AsyncA2AClient,
+    };
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    storage.create_task("rpc-trip", "ctx-tags").await.unwrap();
+    storage.create_task("rpc-lunch", "ctx-tags").await.unwrap();
+
+    let agent_info =
+        SimpleAgentInfo::new("Tag Agent".to_string(), "http://127.0.0.1:8324".to_string());
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8324".to_string());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = HttpClient::new("http://127.0.0.1:8324".to_string());
+    let task = client
+        .add_task_tags("rpc-trip", &tags(&["urgent", "travel"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["travel", "urgent"]));
+    client
+        .add_task_tags("rpc-lunch", &tags(&["urgent"]))
+        .await
+        .unwrap();
+
+    let result = client
+        .list_tasks(&tag_filter(&["urgent", "travel"], None))
+        .await
+        .unwrap();
+    assert_eq!(listed_ids(&result), vec!["rpc-trip"]);
+
+    let task = client
+        .remove_task_tags("rpc-trip", &tags(&["travel"]))
+        .await
+        .unwrap();
+    assert_eq!(task.tags, tags(&["urgent"]));
+    let result = client
+        .list_tasks(&tag_filter(&["urgent"], None))
+        .await
+        .unwrap();
+    assert_eq!(listed_ids(&result), vec!["rpc-lunch", "rpc-trip"]);
+
+    match client
+        .add_task_tags("rpc-trip", &tags(&["not valid!"]))
+        .await
+        .unwrap_err()
+    {
+        A2AError::JsonRpc { code, .. } => assert_eq!(code, INVALID_PARAMS),
+        other => panic!("Expected a JSON-RPC error, got {:?}", other),
+    }
+}
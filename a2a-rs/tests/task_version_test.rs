@@ -0,0 +1,370 @@
+//! Tests for per-task version numbers and optimistic concurrency
+
+use a2a_rs::{
+    adapter::InMemoryTaskStorage,
+    domain::{A2AError, Message, TaskState},
+    port::AsyncTaskManager,
+};
+
+fn history_len(task: &a2a_rs::domain::Task) -> usize {
+    task.history.as_ref().map_or(0, Vec::len)
+}
+
+#[tokio::test]
+async fn test_stale_version_update_is_rejected() {
+    let storage = InMemoryTaskStorage::new();
+    let task = storage
+        .create_task("versioned", "ctx-version")
+        .await
+        .unwrap();
+    assert_eq!(task.version, 1);
+
+    // Every status or history change bumps the version
+    let reply = Message::agent_text("Working on it".to_string(), "msg-1".to_string());
+    let task = storage
+        .update_task_status_at_version("versioned", 1, TaskState::Working, Some(reply))
+        .await
+        .unwrap();
+    assert_eq!(task.version, 2);
+    assert_eq!(
+        storage.get_task("versioned", None).await.unwrap().version,
+        2
+    );
+
+    // A writer still holding version 1 is told to refetch
+    let stale = Message::agent_text("Stale".to_string(), "msg-stale".to_string());
+    let err = storage
+        .update_task_status_at_version("versioned", 1, TaskState::Completed, Some(stale))
+        .await
+        .unwrap_err();
+    match err {
+        A2AError::VersionConflict {
+            task_id,
+            expected,
+            actual,
+        } => {
+            assert_eq!(task_id, "versioned");
+            assert_eq!(expected, 1);
+            assert_eq!(actual, 2);
+        }
+        other => panic!("Expected version conflict, got {:?}", other),
+    }
+
+    // The rejected update left no trace
+    let task = storage.get_task("versioned", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+    assert_eq!(history_len(&task), 1);
+
+    // Of two writers racing from the same version, exactly one wins
+    let first =
+        storage.update_task_status_at_version("versioned", 2, TaskState::InputRequired, None);
+    let second = storage.cancel_task_at_version("versioned", 2);
+    let (first, second) = tokio::join!(first, second);
+    assert!(first.is_ok() != second.is_ok());
+    assert_eq!(
+        storage.get_task("versioned", None).await.unwrap().version,
+        3
+    );
+
+    // Unconditional updates still go through
+    let task = storage
+        .update_task_status("versioned", TaskState::Working, None)
+        .await
+        .unwrap();
+    assert_eq!(task.version, 4);
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_cancel_rpc_checks_expected_version() {
+    use a2a_rs::{
+        adapter::{
+            DefaultRequestProcessor, HttpClient, HttpServer, SimpleAgentInfo,
+            business::DefaultMessageHandler,
+        },
+        application::json_rpc::{A2ARequest, CancelTaskRequest},
+        domain::{Task, TaskIdParams, error::VERSION_CONFLICT, error_catalog::codes},
+        services::AsyncA2AClient,
+    };
+    use std::time::Duration;
+
+    let storage = InMemoryTaskStorage::new();
+    storage
+        .create_task("cancel-me", "ctx-cancel")
+        .await
+        .unwrap();
+    storage
+        .update_task_status("cancel-me", TaskState::Working, None)
+        .await
+        .unwrap();
+
+    let agent_info = SimpleAgentInfo::new(
+        "Version Agent".to_string(),
+        "http://127.0.0.1:8317".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = HttpServer::new(processor, agent_info, "127.0.0.1:8317".to_string());
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = HttpClient::new("http://127.0.0.1:8317".to_string());
+    let read = client.get_task("cancel-me", None).await.unwrap();
+    assert_eq!(read.version, 2);
+
+    let cancel = |expected_version| {
+        A2ARequest::CancelTask(CancelTaskRequest::new(TaskIdParams {
+            id: "cancel-me".to_string(),
+            metadata: None,
+            expected_version: Some(expected_version),
+            reason: None,
+        }))
+    };
+
+    // A cancel based on an outdated read is refused with a conflict
+    let response = client.send_request(&cancel(1)).await.unwrap();
+    let error = response.error.unwrap();
+    assert_eq!(error.code, VERSION_CONFLICT);
+    let data = error.data.unwrap();
+    assert_eq!(data["errorCode"], codes::TASK_VERSION_CONFLICT);
+    assert_eq!(data["params"]["actual"], "2");
+    assert_eq!(
+        client
+            .get_task("cancel-me", None)
+            .await
+            .unwrap()
+            .status
+            .state,
+        TaskState::Working
+    );
+
+    // The version from the latest read succeeds
+    let response = client.send_request(&cancel(read.version)).await.unwrap();
+    let task: Task = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(task.status.state, TaskState::Canceled);
+    assert_eq!(task.version, 3);
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test]
+async fn test_sqlx_versions_are_checked_in_the_transaction() {
+    use a2a_rs::adapter::storage::SqlxTaskStorage;
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    let task = storage
+        .create_task("sqlx-versioned", "ctx-sqlx")
+        .await
+        .unwrap();
+    assert_eq!(task.version, 1);
+
+    let task = storage
+        .update_task_status_at_version("sqlx-versioned", 1, TaskState::Working, None)
+        .await
+        .unwrap();
+    assert_eq!(task.version, 2);
+
+    let err = storage
+        .update_task_status_at_version("sqlx-versioned", 1, TaskState::Completed, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        A2AError::VersionConflict {
+            expected: 1,
+            actual: 2,
+            ..
+        }
+    ));
+    let task = storage.get_task("sqlx-versioned", None).await.unwrap();
+    assert_eq!(task.status.state, TaskState::Working);
+    assert_eq!(task.version, 2);
+
+    let err = storage
+        .cancel_task_at_version("sqlx-versioned", 1)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, A2AError::VersionConflict { .. }));
+    let task = storage
+        .cancel_task_at_version("sqlx-versioned", 2)
+        .await
+        .unwrap();
+    assert_eq!(task.status.state, TaskState::Canceled);
+    assert_eq!(task.version, 3);
+}
+
+/// Send the same follow-up twice at once, both expecting `expected_version`,
+/// returning the JSON-RPC error code of each response, if any
+async fn send_concurrently<S>(storage: S, expected_version: u64) -> Vec<Option<i64>>
+where
+    S: AsyncTaskManager + a2a_rs::port::AsyncNotificationManager + Clone + 'static,
+{
+    use a2a_rs::{
+        adapter::{DefaultRequestProcessor, SimpleAgentInfo, business::DefaultMessageHandler},
+        services::AsyncA2ARequestProcessor,
+    };
+    use serde_json::{Value, json};
+
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage,
+        SimpleAgentInfo::new("Version Agent".to_string(), "http://localhost".to_string()),
+    );
+    let send = |message_id: &str| {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tasks/send",
+            "params": {
+                "id": "expense",
+                "expectedVersion": expected_version,
+                "message": {
+                    "role": "user",
+                    "parts": [{"kind": "text", "text": "Add the hotel receipt"}],
+                    "messageId": message_id,
+                    "kind": "message"
+                }
+            }
+        });
+        let processor = processor.clone();
+        tokio::spawn(async move {
+            let response = processor
+                .process_raw_request(&request.to_string())
+                .await
+                .unwrap();
+            let response: Value = serde_json::from_str(&response).unwrap();
+            response["error"]["code"].as_i64()
+        })
+    };
+    let (first, second) = tokio::join!(send("m-1"), send("m-2"));
+    vec![first.unwrap(), second.unwrap()]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_sends_expecting_one_version_let_one_through() {
+    use a2a_rs::domain::error::VERSION_CONFLICT;
+
+    for _ in 0..20 {
+        let storage = InMemoryTaskStorage::new();
+        let task = storage.create_task("expense", "ctx").await.unwrap();
+
+        let mut codes = send_concurrently(storage.clone(), task.version).await;
+        codes.sort();
+        assert_eq!(codes, vec![None, Some(VERSION_CONFLICT as i64)]);
+        // Only the send that went through reached the history
+        let history = storage
+            .get_task("expense", None)
+            .await
+            .unwrap()
+            .history
+            .unwrap();
+        let sent: Vec<_> = history
+            .iter()
+            .filter(|message| message.role == a2a_rs::domain::Role::User)
+            .collect();
+        assert_eq!(sent.len(), 1);
+    }
+}
+
+#[cfg(feature = "sqlx-storage")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_sqlx_sends_expecting_one_version_let_one_through() {
+    use a2a_rs::{adapter::storage::SqlxTaskStorage, domain::error::VERSION_CONFLICT};
+
+    let storage = SqlxTaskStorage::new("sqlite::memory:").await.unwrap();
+    let task = storage.create_task("expense", "ctx").await.unwrap();
+
+    let mut codes = send_concurrently(storage.clone(), task.version).await;
+    codes.sort();
+    assert_eq!(codes, vec![None, Some(VERSION_CONFLICT as i64)]);
+}
+
+/// A handler failing every message before touching its task
+#[derive(Clone)]
+struct FailingHandler;
+
+#[async_trait::async_trait]
+impl a2a_rs::port::AsyncMessageHandler for FailingHandler {
+    async fn process_message<'a>(
+        &self,
+        _task_id: &'a str,
+        _message: &'a Message,
+        _session_id: Option<&'a str>,
+    ) -> Result<a2a_rs::domain::Task, A2AError> {
+        Err(A2AError::Internal("Agent unavailable".to_string()))
+    }
+
+    async fn process_message_at_version<'a>(
+        &self,
+        _task_id: &'a str,
+        _message: &'a Message,
+        _session_id: Option<&'a str>,
+        _expected_version: u64,
+    ) -> Result<a2a_rs::domain::Task, A2AError> {
+        Err(A2AError::Internal("Agent unavailable".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_send_at_version_moves_the_version_only_with_its_writes() {
+    use a2a_rs::{
+        adapter::{DefaultRequestProcessor, SimpleAgentInfo, business::DefaultMessageHandler},
+        port::AsyncMessageHandler,
+        services::AsyncA2ARequestProcessor,
+    };
+    use serde_json::{Value, json};
+
+    async fn send<H>(storage: &InMemoryTaskStorage, handler: H, expected_version: u64) -> Value
+    where
+        H: AsyncMessageHandler + Clone + Send + Sync + 'static,
+    {
+        let processor = DefaultRequestProcessor::new(
+            handler,
+            storage.clone(),
+            storage.clone(),
+            SimpleAgentInfo::new("Version Agent".to_string(), "http://localhost".to_string()),
+        );
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tasks/send",
+            "params": {
+                "id": "expense",
+                "expectedVersion": expected_version,
+                "message": {
+                    "role": "user",
+                    "parts": [{"kind": "text", "text": "Add the hotel receipt"}],
+                    "messageId": format!("m-{}", expected_version),
+                    "kind": "message"
+                }
+            }
+        });
+        let response = processor
+            .process_raw_request(&request.to_string())
+            .await
+            .unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    let storage = InMemoryTaskStorage::new();
+    let task = storage.create_task("expense", "ctx").await.unwrap();
+
+    // A handler failing before its first write leaves the version alone
+    let response = send(&storage, FailingHandler, task.version).await;
+    assert!(response["error"].is_object());
+    let stored = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(stored.version, task.version);
+
+    // A send that goes through bumps the version once per change it makes:
+    // the sent message and the echoed reply
+    let handler = DefaultMessageHandler::new(storage.clone());
+    let response = send(&storage, handler, task.version).await;
+    assert!(response["error"].is_null(), "{}", response);
+    let stored = storage.get_task("expense", None).await.unwrap();
+    assert_eq!(history_len(&stored), 2);
+    assert_eq!(stored.version, task.version + 2);
+}
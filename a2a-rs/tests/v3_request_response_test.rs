@@ -27,6 +27,8 @@ fn test_list_tasks_request_serialization() {
         history_length: Some(10),
         include_artifacts: Some(true),
         last_updated_after: Some(1704067200000), // 2024-01-01 00:00:00 UTC
+        tags: None,
+        tag_match: None,
         metadata: None,
     };
 
@@ -394,6 +396,8 @@ fn test_list_tasks_with_complex_filters() {
         history_length: Some(20),
         include_artifacts: Some(false),
         last_updated_after: Some(1704153600000), // 2024-01-02 00:00:00 UTC
+        tags: None,
+        tag_match: None,
         metadata: Some(
             json!({
                 "filter": "custom",
@@ -0,0 +1,182 @@
+//! Tests for the event envelope delivered to push notification webhooks
+
+#![cfg(all(feature = "http-client", feature = "http-server"))]
+
+use std::time::Duration;
+
+use a2a_rs::{
+    adapter::{HttpPushNotificationSender, InMemoryTaskStorage},
+    domain::{
+        Artifact, Message, Part, PushNotificationConfig, TaskArtifactUpdateEvent,
+        TaskPushNotificationConfig, TaskState, WebhookEvent,
+    },
+    port::{AsyncNotificationManager, AsyncStreamingHandler, AsyncTaskManager},
+};
+use axum::{Json, Router, extract::State, routing::post};
+use serde_json::json;
+use tokio::sync::mpsc;
+
+/// Start a webhook on `address` that forwards every event it receives
+async fn start_webhook(address: &str) -> mpsc::UnboundedReceiver<WebhookEvent> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let app = Router::new()
+        .route(
+            "/webhook",
+            post(
+                |State(sender): State<mpsc::UnboundedSender<WebhookEvent>>,
+                 Json(event): Json<WebhookEvent>| async move {
+                    sender.send(event).ok();
+                },
+            ),
+        )
+        .with_state(sender);
+    let listener = tokio::net::TcpListener::bind(address).await.unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    receiver
+}
+
+async fn next_event(events: &mut mpsc::UnboundedReceiver<WebhookEvent>) -> WebhookEvent {
+    tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("No webhook event delivered")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_artifact_and_input_required_events_reach_the_webhook() {
+    let mut events = start_webhook("127.0.0.1:8361").await;
+    let storage = InMemoryTaskStorage::with_push_sender(
+        HttpPushNotificationSender::new().with_max_retries(0),
+    );
+    storage.create_task("expense-1", "expenses").await.unwrap();
+    storage
+        .set_task_notification(&TaskPushNotificationConfig {
+            task_id: "expense-1".to_string(),
+            push_notification_config: PushNotificationConfig {
+                id: None,
+                url: "http://127.0.0.1:8361/webhook".to_string(),
+                token: None,
+                authentication: None,
+            },
+        })
+        .await
+        .unwrap();
+
+    let prompt = Message::agent_text(
+        "Which project should the taxi ride be billed to?".to_string(),
+        "prompt-1".to_string(),
+    );
+    storage
+        .update_task_status("expense-1", TaskState::InputRequired, Some(prompt))
+        .await
+        .unwrap();
+    match next_event(&mut events).await {
+        WebhookEvent::InputRequired(event) => {
+            assert_eq!(event.task_id, "expense-1");
+            let prompt = event.status.message.unwrap();
+            assert_eq!(prompt.message_id, "prompt-1");
+        }
+        other => panic!("Expected an input-required event, got {:?}", other),
+    }
+
+    let artifact = Artifact {
+        artifact_id: "receipt".to_string(),
+        name: Some("Receipt".to_string()),
+        description: None,
+        parts: vec![Part::text("Taxi, $20".to_string())],
+        metadata: None,
+        extensions: None,
+        created_at: None,
+    };
+    storage
+        .broadcast_artifact_update(
+            "expense-1",
+            TaskArtifactUpdateEvent {
+                task_id: "expense-1".to_string(),
+                context_id: "expenses".to_string(),
+                kind: "artifact-update".to_string(),
+                artifact,
+                append: None,
+                last_chunk: Some(true),
+                metadata: None,
+            },
+        )
+        .await
+        .unwrap();
+    let event = next_event(&mut events).await;
+    assert_eq!(event.event_kind(), "artifact-update");
+    match event {
+        WebhookEvent::ArtifactUpdate(event) => {
+            assert_eq!(event.task_id, "expense-1");
+            assert_eq!(event.artifact.artifact_id, "receipt");
+        }
+        other => panic!("Expected an artifact event, got {:?}", other),
+    }
+
+    storage
+        .update_task_status("expense-1", TaskState::Completed, None)
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_event(&mut events).await,
+        WebhookEvent::Finished(event) if event.status.state == TaskState::Completed
+    ));
+}
+
+#[test]
+fn test_events_are_wrapped_with_their_kind() {
+    let event: WebhookEvent = serde_json::from_value(json!({
+        "taskId": "expense-1",
+        "contextId": "expenses",
+        "kind": "status-update",
+        "status": {"state": "working"},
+        "final": false
+    }))
+    .unwrap();
+    assert!(matches!(event, WebhookEvent::StatusUpdate(_)));
+
+    let wire = serde_json::to_value(&event).unwrap();
+    assert_eq!(wire["eventKind"], "status-update");
+    assert_eq!(wire["payload"]["taskId"], "expense-1");
+    let again: WebhookEvent = serde_json::from_value(wire).unwrap();
+    assert_eq!(again.task_id(), "expense-1");
+}
+
+#[test]
+fn test_bare_events_are_still_accepted() {
+    // Status events sent before the envelope are classified by their state
+    let event: WebhookEvent = serde_json::from_value(json!({
+        "taskId": "expense-1",
+        "contextId": "expenses",
+        "kind": "status-update",
+        "status": {"state": "canceled"},
+        "final": true
+    }))
+    .unwrap();
+    assert!(matches!(event, WebhookEvent::Finished(_)));
+
+    let event: WebhookEvent = serde_json::from_value(json!({
+        "taskId": "expense-1",
+        "contextId": "expenses",
+        "kind": "status-update",
+        "status": {"state": "input-required"},
+        "final": false
+    }))
+    .unwrap();
+    assert!(matches!(event, WebhookEvent::InputRequired(_)));
+
+    let event: WebhookEvent = serde_json::from_value(json!({
+        "taskId": "expense-1",
+        "contextId": "expenses",
+        "kind": "artifact-update",
+        "artifact": {"artifactId": "receipt", "parts": []}
+    }))
+    .unwrap();
+    assert!(matches!(event, WebhookEvent::ArtifactUpdate(_)));
+
+    let error = serde_json::from_value::<WebhookEvent>(json!({
+        "eventKind": "surprise",
+        "payload": {}
+    }));
+    assert!(error.is_err());
+}
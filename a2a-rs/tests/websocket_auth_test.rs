@@ -0,0 +1,194 @@
+//! Tests for the WebSocket first-message auth handshake
+
+#![cfg(all(feature = "ws-client", feature = "ws-server"))]
+
+mod common;
+
+use a2a_rs::{
+    adapter::{
+        ApiKeyAuthenticator, BearerTokenAuthenticator, DefaultRequestProcessor,
+        InMemoryTaskStorage, SimpleAgentInfo, WebSocketClient, WebSocketCredentials,
+        WebSocketOptions, WebSocketServer,
+    },
+    domain::Message,
+    port::Authenticator,
+    services::AsyncA2AClient,
+};
+use common::TestBusinessHandler;
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{Message as WsMessage, protocol::frame::coding::CloseCode},
+};
+
+/// Start a WebSocket server requiring `authenticator` on `port`
+async fn start_server<Auth>(port: u16, authenticator: Auth)
+where
+    Auth: Authenticator + Clone + Send + Sync + 'static,
+{
+    let address = format!("127.0.0.1:{}", port);
+    let storage = InMemoryTaskStorage::new();
+    let handler = TestBusinessHandler::with_storage(storage.clone());
+    let agent_info = SimpleAgentInfo::new("ws-agent".to_string(), format!("ws://{}", address));
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let server = WebSocketServer::with_auth(
+        processor,
+        agent_info,
+        handler,
+        address.clone(),
+        authenticator,
+    )
+    .with_options(WebSocketOptions::new().with_auth_timeout(Duration::from_millis(300)));
+    tokio::spawn(async move { server.start().await });
+
+    for _ in 0..50 {
+        if TcpStream::connect(&address).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("WebSocket server on {} never started", address);
+}
+
+fn get_task_request(task_id: &str) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/get",
+        "params": {"id": task_id}
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_valid_handshake_allows_requests() {
+    start_server(
+        8320,
+        BearerTokenAuthenticator::new(vec!["ws-token".to_string()]),
+    )
+    .await;
+
+    let client = WebSocketClient::with_auth("ws://127.0.0.1:8320".to_string(), "ws-token".into());
+    let message = Message::user_text("Hello".to_string(), "msg-auth".to_string());
+    let task = client
+        .send_task_message("auth-task", &message, None, None)
+        .await
+        .unwrap();
+    assert_eq!(task.id, "auth-task");
+
+    // The handshake is a plain frame, so any WebSocket client can perform it
+    let (mut socket, _) = connect_async("ws://127.0.0.1:8320").await.unwrap();
+    socket
+        .send(WsMessage::Text(
+            json!({"type": "auth", "token": "ws-token"}).to_string(),
+        ))
+        .await
+        .unwrap();
+    let reply: Value =
+        serde_json::from_str(&socket.next().await.unwrap().unwrap().into_text().unwrap()).unwrap();
+    assert_eq!(reply, json!({"type": "auth", "status": "ok"}));
+
+    socket
+        .send(WsMessage::Text(get_task_request("auth-task")))
+        .await
+        .unwrap();
+    let response: Value =
+        serde_json::from_str(&socket.next().await.unwrap().unwrap().into_text().unwrap()).unwrap();
+    assert_eq!(response["result"]["id"], "auth-task");
+}
+
+#[tokio::test]
+async fn test_api_key_handshake() {
+    start_server(
+        8321,
+        ApiKeyAuthenticator::header(vec!["ws-key".to_string()], "X-API-Key".to_string()),
+    )
+    .await;
+
+    let client = WebSocketClient::new("ws://127.0.0.1:8321".to_string())
+        .with_credentials(WebSocketCredentials::ApiKey("ws-key".to_string()));
+    let message = Message::user_text("Hello".to_string(), "msg-key".to_string());
+    client
+        .send_task_message("key-task", &message, None, None)
+        .await
+        .unwrap();
+
+    // A bearer token is not an API key
+    let client = WebSocketClient::with_auth("ws://127.0.0.1:8321".to_string(), "ws-key".into());
+    assert!(client.get_task("key-task", None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_missing_handshake_times_out() {
+    start_server(
+        8322,
+        BearerTokenAuthenticator::new(vec!["ws-token".to_string()]),
+    )
+    .await;
+
+    // A socket that stays silent is closed once the auth timeout passes
+    let (mut socket, _) = connect_async("ws://127.0.0.1:8322").await.unwrap();
+    let closed = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .expect("server kept the unauthenticated socket open");
+    match closed {
+        Some(Ok(WsMessage::Close(Some(frame)))) => {
+            assert_eq!(frame.code, CloseCode::Policy);
+            assert!(frame.reason.contains("timed out"), "{}", frame.reason);
+        }
+        other => panic!("Expected a close frame, got {:?}", other),
+    }
+
+    // An RPC sent in place of the auth frame is never processed
+    let (mut socket, _) = connect_async("ws://127.0.0.1:8322").await.unwrap();
+    socket
+        .send(WsMessage::Text(get_task_request("anything")))
+        .await
+        .unwrap();
+    match socket.next().await {
+        Some(Ok(WsMessage::Close(Some(frame)))) => {
+            assert_eq!(frame.code, CloseCode::Policy);
+            assert!(frame.reason.contains("auth frame"), "{}", frame.reason);
+        }
+        other => panic!("Expected a close frame, got {:?}", other),
+    }
+
+    // Clients without credentials are turned away the same way
+    let client = WebSocketClient::new("ws://127.0.0.1:8322".to_string());
+    assert!(client.get_task("anything", None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invalid_token_is_rejected() {
+    start_server(
+        8323,
+        BearerTokenAuthenticator::new(vec!["ws-token".to_string()]),
+    )
+    .await;
+
+    let client = WebSocketClient::with_auth("ws://127.0.0.1:8323".to_string(), "wrong".into());
+    let err = client.get_task("anything", None).await.unwrap_err();
+    assert!(
+        err.to_string().contains("Authentication rejected"),
+        "{}",
+        err
+    );
+
+    let (mut socket, _) = connect_async("ws://127.0.0.1:8323").await.unwrap();
+    socket
+        .send(WsMessage::Text(
+            json!({"type": "auth", "token": "wrong"}).to_string(),
+        ))
+        .await
+        .unwrap();
+    match socket.next().await {
+        Some(Ok(WsMessage::Close(Some(frame)))) => {
+            assert_eq!(frame.code, CloseCode::Policy);
+            assert_eq!(frame.reason, "Authentication failed");
+        }
+        other => panic!("Expected a close frame, got {:?}", other),
+    }
+}
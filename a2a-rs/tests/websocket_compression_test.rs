@@ -0,0 +1,170 @@
+//! Tests for WebSocket compression negotiation and message size limits
+
+#![cfg(all(feature = "ws-client", feature = "ws-server"))]
+
+mod common;
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo, WebSocketClient,
+        WebSocketOptions, WebSocketServer, transport::websocket::DEFLATE_EXTENSION,
+    },
+    domain::Message,
+    services::AsyncA2AClient,
+};
+use common::TestBusinessHandler;
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        Message as WsMessage, client::IntoClientRequest, protocol::frame::coding::CloseCode,
+    },
+};
+
+/// Start a WebSocket server with the given options on `port`
+async fn start_server(port: u16, options: WebSocketOptions) {
+    let address = format!("127.0.0.1:{}", port);
+    let storage = InMemoryTaskStorage::new();
+    let handler = TestBusinessHandler::with_storage(storage.clone());
+    let agent_info = SimpleAgentInfo::new("ws-agent".to_string(), format!("ws://{}", address));
+    let processor = DefaultRequestProcessor::with_handler(handler.clone(), agent_info.clone());
+    let server =
+        WebSocketServer::new(processor, agent_info, handler, address.clone()).with_options(options);
+    tokio::spawn(async move { server.start().await });
+
+    for _ in 0..50 {
+        if TcpStream::connect(&address).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("WebSocket server on {} never started", address);
+}
+
+fn send_task_request(task_id: &str, text: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tasks/send",
+        "params": {
+            "id": task_id,
+            "message": {
+                "role": "user",
+                "parts": [{"kind": "text", "text": text}],
+                "messageId": "msg-compressed",
+                "kind": "message"
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_compressed_round_trip() {
+    start_server(8311, WebSocketOptions::default()).await;
+
+    // Highly repetitive payload, far above the compression threshold
+    let text = "Large task history entry. ".repeat(4000);
+
+    // On the wire: the server accepts the offer and answers in compressed frames
+    let mut request = "ws://127.0.0.1:8311".into_client_request().unwrap();
+    request.headers_mut().insert(
+        "Sec-WebSocket-Extensions",
+        DEFLATE_EXTENSION.parse().unwrap(),
+    );
+    let (mut socket, response) = connect_async(request).await.unwrap();
+    assert_eq!(
+        response.headers()["sec-websocket-extensions"],
+        DEFLATE_EXTENSION
+    );
+
+    let body = send_task_request("compressed-task", text.clone()).to_string();
+    let compressed = miniz_oxide::deflate::compress_to_vec(body.as_bytes(), 6);
+    assert!(compressed.len() < body.len() / 10);
+    socket.send(WsMessage::Binary(compressed)).await.unwrap();
+
+    let reply = match socket.next().await.unwrap().unwrap() {
+        WsMessage::Binary(data) => miniz_oxide::inflate::decompress_to_vec(&data).unwrap(),
+        other => panic!("Expected a compressed reply, got {:?}", other),
+    };
+    let reply: Value = serde_json::from_slice(&reply).unwrap();
+    assert_eq!(reply["result"]["id"], "compressed-task");
+
+    // The client library negotiates compression and decodes replies transparently
+    let client = WebSocketClient::new("ws://127.0.0.1:8311".to_string());
+    let message = Message::user_text(text.clone(), "msg-client".to_string());
+    let task = client
+        .send_task_message("client-task", &message, None, None)
+        .await
+        .unwrap();
+    assert_eq!(task.id, "client-task");
+
+    let task = client.get_task("client-task", None).await.unwrap();
+    let history = task.history.unwrap();
+    assert!(history.iter().any(|m| m.message_id == "msg-client"));
+
+    // Peers that don't offer compression keep getting plain text frames
+    let (mut plain, response) = connect_async("ws://127.0.0.1:8311").await.unwrap();
+    assert!(response.headers().get("sec-websocket-extensions").is_none());
+    plain
+        .send(WsMessage::Text(
+            send_task_request("plain-task", text).to_string(),
+        ))
+        .await
+        .unwrap();
+    assert!(matches!(
+        plain.next().await.unwrap().unwrap(),
+        WsMessage::Text(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_over_limit_message_is_rejected() {
+    start_server(
+        8312,
+        WebSocketOptions::new().with_max_message_size(64 * 1024),
+    )
+    .await;
+
+    let oversized = send_task_request("huge-task", "x".repeat(128 * 1024)).to_string();
+
+    // A plain frame over the limit closes the connection with a policy violation
+    let (mut socket, _) = connect_async("ws://127.0.0.1:8312").await.unwrap();
+    socket
+        .send(WsMessage::Text(oversized.clone()))
+        .await
+        .unwrap();
+    match socket.next().await.unwrap().unwrap() {
+        WsMessage::Close(Some(frame)) => assert_eq!(frame.code, CloseCode::Policy),
+        other => panic!("Expected a close frame, got {:?}", other),
+    }
+
+    // So does a small compressed frame that inflates past the limit
+    let mut request = "ws://127.0.0.1:8312".into_client_request().unwrap();
+    request.headers_mut().insert(
+        "Sec-WebSocket-Extensions",
+        DEFLATE_EXTENSION.parse().unwrap(),
+    );
+    let (mut socket, _) = connect_async(request).await.unwrap();
+    let bomb = miniz_oxide::deflate::compress_to_vec(oversized.as_bytes(), 6);
+    assert!(bomb.len() < 64 * 1024);
+    socket.send(WsMessage::Binary(bomb)).await.unwrap();
+    match socket.next().await.unwrap().unwrap() {
+        WsMessage::Close(Some(frame)) => assert_eq!(frame.code, CloseCode::Policy),
+        other => panic!("Expected a close frame, got {:?}", other),
+    }
+
+    // The client surfaces the closure as an error, and requests under the limit still work
+    let client = WebSocketClient::new("ws://127.0.0.1:8312".to_string());
+    let err = client.send_raw_request(&oversized).await.unwrap_err();
+    assert!(err.to_string().contains("1008"), "{}", err);
+
+    let message = Message::user_text("small".to_string(), "msg-small".to_string());
+    let task = client
+        .send_task_message("small-task", &message, None, None)
+        .await
+        .unwrap();
+    assert_eq!(task.id, "small-task");
+}
@@ -0,0 +1,125 @@
+//! Tests for WebSocket subscriptions sharing one source per task
+
+#![cfg(all(feature = "ws-client", feature = "ws-server"))]
+
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo, WebSocketClient,
+        WebSocketServer, business::DefaultMessageHandler,
+    },
+    domain::{A2AError, TaskState, TaskStatusUpdateEvent},
+    port::{AsyncStreamingHandler, AsyncTaskManager},
+    services::{AsyncA2AClient, StreamItem},
+};
+use futures::{Stream, StreamExt};
+
+type Updates = Pin<Box<dyn Stream<Item = Result<StreamItem, A2AError>> + Send>>;
+
+/// The next item of `updates`, failing the test if none arrives in time
+async fn next_item(updates: &mut Updates) -> StreamItem {
+    tokio::time::timeout(Duration::from_secs(3), updates.next())
+        .await
+        .expect("no update received")
+        .expect("stream ended")
+        .expect("stream error")
+}
+
+/// The status update `item`
+fn status_update(item: &StreamItem) -> &TaskStatusUpdateEvent {
+    match item {
+        StreamItem::StatusUpdate(update) => update,
+        other => panic!("expected a status update, got {:?}", other),
+    }
+}
+
+/// The state of the status update `item`
+fn status_state(item: &StreamItem) -> TaskState {
+    status_update(item).status.state.clone()
+}
+
+#[tokio::test]
+async fn test_subscriptions_to_one_task_share_one_source() {
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Fanout Agent".to_string(),
+        "ws://127.0.0.1:8387".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = Arc::new(WebSocketServer::new(
+        processor,
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8387".to_string(),
+    ));
+    let running = server.clone();
+    tokio::spawn(async move { running.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    let client = WebSocketClient::new("ws://127.0.0.1:8387".to_string());
+    let mut first = client.subscribe_to_task("expense", None).await.unwrap();
+    let mut second = client.subscribe_to_task("expense", None).await.unwrap();
+
+    // Each gets the task, then its current status
+    for updates in [&mut first, &mut second] {
+        assert!(matches!(next_item(updates).await, StreamItem::Task(_)));
+        assert_eq!(
+            status_state(&next_item(updates).await),
+            TaskState::Submitted
+        );
+    }
+
+    // One status and one artifact subscriber feed both subscriptions
+    assert_eq!(server.shared_subscriptions(), 1);
+    assert_eq!(storage.get_subscriber_count("expense").await.unwrap(), 2);
+
+    storage
+        .update_task_status("expense", TaskState::Working, None)
+        .await
+        .unwrap();
+    let first_update = next_item(&mut first).await;
+    let second_update = next_item(&mut second).await;
+    assert_eq!(status_state(&first_update), TaskState::Working);
+    assert_eq!(
+        serde_json::to_value(status_update(&first_update)).unwrap(),
+        serde_json::to_value(status_update(&second_update)).unwrap()
+    );
+
+    // A late subscription starts from the latest status, then follows along
+    let mut late = client.subscribe_to_task("expense", None).await.unwrap();
+    assert!(matches!(next_item(&mut late).await, StreamItem::Task(_)));
+    assert_eq!(
+        status_state(&next_item(&mut late).await),
+        TaskState::Working
+    );
+    assert_eq!(server.shared_subscriptions(), 1);
+    assert_eq!(storage.get_subscriber_count("expense").await.unwrap(), 2);
+
+    storage
+        .update_task_status("expense", TaskState::Completed, None)
+        .await
+        .unwrap();
+    for updates in [&mut first, &mut second, &mut late] {
+        assert_eq!(
+            status_state(&next_item(updates).await),
+            TaskState::Completed
+        );
+    }
+
+    // The source closes once the last subscription leaves
+    drop((first, second, late));
+    tokio::time::timeout(Duration::from_secs(3), async {
+        while server.shared_subscriptions() > 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("the shared source was not closed");
+}
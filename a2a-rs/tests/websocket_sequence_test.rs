@@ -0,0 +1,79 @@
+//! Tests for sequence numbers on WebSocket subscription events
+
+#![cfg(all(feature = "ws-client", feature = "ws-server"))]
+
+use std::time::Duration;
+
+use a2a_rs::{
+    adapter::{
+        DefaultRequestProcessor, InMemoryTaskStorage, SimpleAgentInfo, WebSocketClient,
+        WebSocketServer, business::DefaultMessageHandler,
+    },
+    domain::TaskState,
+    port::AsyncTaskManager,
+    services::StreamItem,
+};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn test_subscription_events_are_numbered_in_order() {
+    let storage = InMemoryTaskStorage::new();
+    let agent_info = SimpleAgentInfo::new(
+        "Sequenced Agent".to_string(),
+        "ws://127.0.0.1:8349".to_string(),
+    );
+    let processor = DefaultRequestProcessor::new(
+        DefaultMessageHandler::new(storage.clone()),
+        storage.clone(),
+        storage.clone(),
+        agent_info.clone(),
+    );
+    let server = WebSocketServer::new(
+        processor,
+        agent_info,
+        storage.clone(),
+        "127.0.0.1:8349".to_string(),
+    );
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    storage.create_task("expense", "ctx-1").await.unwrap();
+    let client = WebSocketClient::new("ws://127.0.0.1:8349".to_string());
+    let mut stream = client.subscribe_sequenced("expense", None).await.unwrap();
+
+    let update_storage = storage.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        for state in [TaskState::Working, TaskState::Completed] {
+            update_storage
+                .update_task_status("expense", state, None)
+                .await
+                .unwrap();
+        }
+    });
+
+    let received = tokio::time::timeout(Duration::from_secs(3), async {
+        let mut received = Vec::new();
+        while let Some(item) = stream.next().await {
+            let item = item.unwrap();
+            let finished = matches!(
+                &item.item,
+                StreamItem::StatusUpdate(update) if update.status.state.is_terminal()
+            );
+            received.push(item);
+            if finished {
+                return received;
+            }
+        }
+        panic!("stream ended before the final status");
+    })
+    .await
+    .expect("no final status received");
+
+    // The reply to the subscription request is not an event
+    assert!(matches!(received[0].item, StreamItem::Task(_)));
+    assert_eq!(received[0].sequence, None);
+    let sequences: Vec<Option<u64>> = received[1..].iter().map(|item| item.sequence).collect();
+    // The current status on subscribing, then the two updates
+    assert_eq!(sequences, vec![Some(1), Some(2), Some(3)]);
+}
@@ -0,0 +1,333 @@
+//! Tests for parsing untrusted wire-format input
+//!
+//! Malformed requests, messages and parts must come back as JSON-RPC errors,
+//! never as panics. The proptest cases mirror the `parse_message` fuzz target
+//! in `a2a-rs/fuzz` so the same properties are checked on every test run.
+
+mod common;
+
+use a2a_rs::{
+    adapter::{InMemoryTaskStorage, SimpleAgentInfo},
+    application::{A2ARequest, parse_request},
+    domain::{
+        A2AError, ContentPolicy, FileContent, Message, Part, Task,
+        error::{INVALID_PARAMS, INVALID_REQUEST, PARSE_ERROR},
+    },
+    services::AsyncA2ARequestProcessor,
+};
+use common::processor;
+use proptest::prelude::*;
+use serde_json::{Value, json};
+
+fn send_message_request(parts: Value) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": "req-1",
+        "method": "message/send",
+        "params": {
+            "message": {
+                "kind": "message",
+                "messageId": "msg-1",
+                "role": "user",
+                "parts": parts
+            }
+        }
+    })
+    .to_string()
+}
+
+/// The JSON-RPC error code a parse failure is reported with
+fn error_code(err: A2AError) -> i32 {
+    match err {
+        A2AError::JsonParse(_) => PARSE_ERROR,
+        A2AError::InvalidRequest(_) => INVALID_REQUEST,
+        A2AError::InvalidParams(_) => INVALID_PARAMS,
+        other => panic!("Unexpected parse error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_part_kind_round_trips() {
+    let raw = json!({"kind": "video", "url": "https://example.com/v.mp4", "seconds": 12});
+    let part: Part = serde_json::from_value(raw.clone()).unwrap();
+    assert!(matches!(part, Part::Unknown(_)));
+    assert_eq!(part.kind(), "video");
+    assert_eq!(serde_json::to_value(&part).unwrap(), raw);
+
+    // A message carrying an unknown part still parses as a request
+    let request = parse_request(&send_message_request(json!([
+        {"kind": "text", "text": "See attached"},
+        raw
+    ])))
+    .unwrap();
+    let A2ARequest::SendMessage(request) = request else {
+        panic!("Expected a message/send request");
+    };
+    assert_eq!(request.params.message.parts.len(), 2);
+    assert_eq!(request.params.message.parts[1].kind(), "video");
+}
+
+#[test]
+fn test_malformed_parts_are_rejected() {
+    let cases = [
+        json!({"text": "no kind"}),
+        json!({"kind": 7, "text": "numeric kind"}),
+        json!({"kind": null}),
+        json!({"kind": "text"}),
+        json!({"kind": "text", "text": 42}),
+        json!({"kind": "file"}),
+        json!({"kind": "file", "file": "not an object"}),
+        json!({"kind": "data", "data": [1, 2, 3]}),
+        json!("just a string"),
+        json!([{"kind": "text", "text": "nested"}]),
+    ];
+    for case in cases {
+        assert!(
+            serde_json::from_value::<Part>(case.clone()).is_err(),
+            "{} should not parse as a part",
+            case
+        );
+        let err = parse_request(&send_message_request(json!([case]))).unwrap_err();
+        assert_eq!(error_code(err), INVALID_PARAMS, "{}", case);
+    }
+
+    let err = parse_request(&send_message_request(json!({"kind": "text"}))).unwrap_err();
+    assert_eq!(error_code(err), INVALID_PARAMS);
+}
+
+#[test]
+fn test_file_part_content_is_checked_without_panicking() {
+    // A file needs exactly one of bytes and uri
+    for file in [
+        json!({"bytes": "SGVsbG8=", "uri": "https://example.com/a.txt"}),
+        json!({"name": "empty.txt"}),
+    ] {
+        let part = json!({"kind": "file", "file": file});
+        assert!(
+            serde_json::from_value::<Part>(part.clone()).is_err(),
+            "{}",
+            file
+        );
+        let err = parse_request(&send_message_request(json!([part]))).unwrap_err();
+        assert_eq!(error_code(err), INVALID_PARAMS, "{}", file);
+    }
+
+    let file = FileContent {
+        name: Some("receipt.png".to_string()),
+        mime_type: Some("image/png".to_string()),
+        bytes: Some("not base64!!".to_string()),
+        uri: None,
+    };
+    match ContentPolicy::new().inspect_file(&file, None).unwrap_err() {
+        A2AError::ValidationError { field, .. } => assert_eq!(field, "file.bytes"),
+        other => panic!("Expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_request_errors_use_matching_codes() {
+    let cases = [
+        ("", PARSE_ERROR),
+        ("{", PARSE_ERROR),
+        (r#"{"jsonrpc": "2.0", "method": "tasks/get",}"#, PARSE_ERROR),
+        ("[]", INVALID_REQUEST),
+        ("null", INVALID_REQUEST),
+        (r#""message/send""#, INVALID_REQUEST),
+        (r#"{"jsonrpc": "2.0", "id": 1}"#, INVALID_REQUEST),
+        (
+            r#"{"jsonrpc": "2.0", "id": 1, "method": 5}"#,
+            INVALID_REQUEST,
+        ),
+        (
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": 3}"#,
+            INVALID_PARAMS,
+        ),
+        (
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": {}}"#,
+            INVALID_PARAMS,
+        ),
+    ];
+    for (input, code) in cases {
+        let err = parse_request(input).unwrap_err();
+        assert_eq!(error_code(err), code, "{:?}", input);
+    }
+
+    // Deep nesting hits the parser's recursion limit instead of the stack
+    let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    assert_eq!(error_code(parse_request(&deep).unwrap_err()), PARSE_ERROR);
+    let deep_params = format!(
+        r#"{{"jsonrpc": "2.0", "id": 1, "method": "message/send", "params": {}}}"#,
+        "[".repeat(10_000)
+    );
+    assert_eq!(
+        error_code(parse_request(&deep_params).unwrap_err()),
+        PARSE_ERROR
+    );
+}
+
+#[tokio::test]
+async fn test_processor_answers_malformed_requests() {
+    let processor = processor(&InMemoryTaskStorage::new());
+
+    let response: Value =
+        serde_json::from_str(&processor.process_raw_request("not json").await.unwrap()).unwrap();
+    assert_eq!(response["error"]["code"], PARSE_ERROR);
+    assert_eq!(response["id"], Value::Null);
+
+    // The id of a request with bad params is echoed back
+    let response: Value = serde_json::from_str(
+        &processor
+            .process_raw_request(&send_message_request(json!([{"kind": "text"}])))
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    assert_eq!(response["id"], "req-1");
+
+    // An id that is not a valid JSON-RPC id is not echoed
+    let response: Value = serde_json::from_str(
+        &processor
+            .process_raw_request(r#"{"jsonrpc": "2.0", "id": {"x": 1}}"#)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response["error"]["code"], INVALID_REQUEST);
+    assert_eq!(response["id"], Value::Null);
+}
+
+#[cfg(all(feature = "http-client", feature = "http-server"))]
+#[tokio::test]
+async fn test_http_server_answers_malformed_bodies() {
+    use a2a_rs::adapter::HttpServer;
+    use std::time::Duration;
+
+    let agent_info = SimpleAgentInfo::new(
+        "Wire Agent".to_string(),
+        "http://127.0.0.1:8325".to_string(),
+    );
+    let server = HttpServer::new(
+        processor(&InMemoryTaskStorage::new()),
+        agent_info,
+        "127.0.0.1:8325".to_string(),
+    );
+    tokio::spawn(async move { server.start().await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let client = reqwest::Client::new();
+    let cases = [
+        ("{\"jsonrpc\": \"2.0\", \"id\": 1,".to_string(), PARSE_ERROR),
+        ("[1, 2, 3]".to_string(), INVALID_REQUEST),
+        (
+            send_message_request(json!([{"kind": "file", "file": 1}])),
+            INVALID_PARAMS,
+        ),
+    ];
+    for (body, code) in cases {
+        // No content type, as sent by some clients, is handled the same way
+        let response = client
+            .post("http://127.0.0.1:8325/")
+            .body(body.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(!response.status().is_server_error(), "{}", body);
+        let response: Value = response.json().await.unwrap();
+        assert_eq!(response["error"]["code"], code, "{}", body);
+    }
+
+    let response = client
+        .post("http://127.0.0.1:8325/")
+        .header("content-type", "application/json")
+        .body(vec![0xff, 0xfe, b'{'])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let response: Value = response.json().await.unwrap();
+    assert_eq!(response["error"]["code"], PARSE_ERROR);
+}
+
+/// Arbitrary JSON values, biased towards the shapes parts are built from
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::from),
+        prop::sample::select(vec!["text", "file", "data", "kind", "bytes", "uri", ""])
+            .prop_map(Value::from),
+        ".{0,12}".prop_map(Value::String),
+    ];
+    leaf.prop_recursive(4, 32, 6, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::vec(
+                (
+                    prop::sample::select(vec![
+                        "kind", "text", "file", "data", "metadata", "bytes", "uri", "name",
+                        "mimeType", "other",
+                    ]),
+                    inner
+                ),
+                0..5
+            )
+            .prop_map(|fields| {
+                Value::Object(
+                    fields
+                        .into_iter()
+                        .map(|(key, value)| (key.to_string(), value))
+                        .collect(),
+                )
+            }),
+        ]
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    /// Arbitrary text never panics the request parser
+    #[test]
+    fn arbitrary_text_never_panics(input in ".{0,256}") {
+        if let Err(err) = parse_request(&input) {
+            error_code(err);
+        }
+        let _ = serde_json::from_str::<Message>(&input);
+        let _ = serde_json::from_str::<Task>(&input);
+    }
+
+    /// Any value that parses as a part serializes back to an equal part
+    #[test]
+    fn parsed_parts_round_trip(value in arb_json()) {
+        if let Ok(part) = serde_json::from_value::<Part>(value.clone()) {
+            let json = serde_json::to_value(&part).unwrap();
+            let reparsed: Part = serde_json::from_value(json.clone()).unwrap();
+            prop_assert_eq!(serde_json::to_value(&reparsed).unwrap(), json);
+        }
+        if let Err(err) = parse_request(&send_message_request(Value::Array(vec![value]))) {
+            prop_assert_eq!(error_code(err), INVALID_PARAMS);
+        }
+    }
+
+    /// Truncating or corrupting a valid request yields a clean error
+    #[test]
+    fn corrupted_requests_never_panic(
+        cut in 0usize..200,
+        position in 0usize..200,
+        byte in prop::sample::select(vec![b'{', b'}', b'[', b'"', b',', b':', b'0', b'x', b'\\']),
+    ) {
+        let valid = send_message_request(json!([
+            {"kind": "text", "text": "Lunch receipt"},
+            {"kind": "file", "file": {"name": "r.png", "bytes": "SGVsbG8="}}
+        ]));
+        let mut bytes = valid.into_bytes();
+        bytes.truncate(bytes.len().saturating_sub(cut));
+        let position = position.min(bytes.len());
+        bytes.insert(position, byte);
+        let input = String::from_utf8(bytes).unwrap();
+        if let Err(err) = parse_request(&input) {
+            error_code(err);
+        }
+    }
+}